@@ -0,0 +1,129 @@
+//! Benchmarks for the parsing hot paths on a large repository: status and
+//! diff parsing, and loading Timeline pages, through libgit2 and through git.
+//!
+//! zit is a binary crate, so the git modules under test are compiled straight
+//! into the benchmark from `src/git/`.
+//!
+//!     cargo bench                               # 100k commits, 10k files
+//!     cargo bench -- --save-baseline main       # record a baseline
+//!     cargo bench -- --baseline main            # compare against it
+//!
+//! `ZIT_BENCH_COMMITS` / `ZIT_BENCH_FILES` change the repository size.
+
+#![allow(dead_code)]
+// The included modules' unit tests run with the main crate, not here
+#![cfg_attr(test, allow(unused_imports))]
+
+#[path = "../src/git/backend.rs"]
+mod backend;
+#[path = "../src/git/branch.rs"]
+mod branch;
+#[path = "../src/git/diff.rs"]
+mod diff;
+#[path = "../src/git/log.rs"]
+mod log;
+#[path = "../src/git/runner.rs"]
+mod runner;
+#[path = "../src/git/scope.rs"]
+mod scope;
+#[path = "../src/git/status.rs"]
+mod status;
+mod support;
+
+use backend::GitBackend;
+use criterion::{Criterion, criterion_group, criterion_main};
+use std::hint::black_box;
+use std::process::Command;
+use std::sync::OnceLock;
+
+use support::RepoSpec;
+
+/// Generate (or reuse) the large repository and run git commands inside it.
+fn enter_repo() -> RepoSpec {
+    static SPEC: OnceLock<RepoSpec> = OnceLock::new();
+    *SPEC.get_or_init(|| {
+        let spec = RepoSpec::from_env();
+        std::env::set_current_dir(support::large_repo(spec)).unwrap();
+        spec
+    })
+}
+
+fn status(c: &mut Criterion) {
+    let spec = enter_repo();
+    let output = support::status_output(spec.files);
+    let mut group = c.benchmark_group("status");
+    group.bench_function("parse", |b| {
+        b.iter(|| status::parse_status(black_box(&output)))
+    });
+    group.sample_size(10);
+    group.bench_function("get_status", |b| b.iter(|| status::get_status().unwrap()));
+    group.bench_function("get_status_cli", |b| {
+        b.iter(|| backend::Cli.status().unwrap())
+    });
+    group.finish();
+}
+
+fn diff(c: &mut Criterion) {
+    enter_repo();
+    let output = support::diff_output(1_000, 5);
+    let mut group = c.benchmark_group("diff");
+    group.bench_function("parse", |b| {
+        b.iter(|| diff::parse_diff_output(black_box(&output)))
+    });
+    // What the Staging view's background line counts do
+    group.bench_function("parse_summary", |b| {
+        b.iter(|| {
+            let mut parser = diff::DiffParser::summary();
+            let counted = black_box(&output)
+                .lines()
+                .filter_map(|l| parser.feed(l))
+                .count();
+            counted + parser.finish().iter().len()
+        })
+    });
+    group.sample_size(10);
+    group.bench_function("get_unstaged_diff", |b| {
+        b.iter(|| diff::get_unstaged_diff().unwrap())
+    });
+    group.finish();
+}
+
+/// What the Timeline view does on open and when paging: `git log --graph` for
+/// one page of 100 commits, then parsing.
+fn timeline(c: &mut Criterion) {
+    let spec = enter_repo();
+    let graph = Command::new("git")
+        .args([
+            "log",
+            "-5000",
+            &format!("--format={}", log::LOG_FORMAT),
+            "--graph",
+            "--color=never",
+            "--decorate=full",
+            "--branches",
+            "--tags",
+        ])
+        .output()
+        .unwrap()
+        .stdout;
+    let graph = String::from_utf8(graph).unwrap();
+
+    let mut group = c.benchmark_group("timeline");
+    group.bench_function("parse_5000", |b| {
+        b.iter(|| log::parse_log_output(black_box(&graph)))
+    });
+    group.sample_size(10);
+    group.bench_function("first_page", |b| {
+        b.iter(|| log::get_log(100, 0, &[]).unwrap())
+    });
+    group.bench_function("middle_page", |b| {
+        b.iter(|| log::get_log(100, spec.commits / 2, &[]).unwrap())
+    });
+    group.bench_function("all_refs_first_page", |b| {
+        b.iter(|| log::get_log(100, 0, &["--branches", "--tags", "HEAD"]).unwrap())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, status, diff, timeline);
+criterion_main!(benches);
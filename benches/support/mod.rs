@@ -0,0 +1,239 @@
+//! Synthetic inputs for the benchmarks: a large git repository built with
+//! `git fast-import`, and generated `git status` / `git diff` output.
+//!
+//! The repository is cached under Cargo's target tmp dir, keyed by its size,
+//! so only the first run pays for generating it.
+
+use std::fmt::Write as _;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// Lines in every generated file.
+const FILE_LINES: usize = 20;
+
+/// Size of the generated repository.
+#[derive(Debug, Clone, Copy)]
+pub struct RepoSpec {
+    pub commits: usize,
+    pub files: usize,
+}
+
+impl RepoSpec {
+    /// 100k commits over 10k files under `cargo bench`; a small repository when
+    /// the benchmarks only run once as a smoke test (`cargo test --all-targets`).
+    /// `ZIT_BENCH_COMMITS` and `ZIT_BENCH_FILES` override either size.
+    pub fn from_env() -> Self {
+        let benchmarking = std::env::args().any(|a| a == "--bench");
+        let (commits, files) = if benchmarking {
+            (100_000, 10_000)
+        } else {
+            (300, 200)
+        };
+        let size = |var: &str, default: usize| {
+            std::env::var(var)
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .filter(|&n: &usize| n > 0)
+                .unwrap_or(default)
+        };
+        Self {
+            commits: size("ZIT_BENCH_COMMITS", commits),
+            files: size("ZIT_BENCH_FILES", files),
+        }
+    }
+}
+
+fn file_path(index: usize) -> String {
+    format!("src/mod{:02}/file{:05}.rs", index % 100, index)
+}
+
+fn file_content(index: usize, commit: usize) -> String {
+    let mut content = String::new();
+    for line in 0..FILE_LINES {
+        if commit > 0 && line == commit % FILE_LINES {
+            let _ = writeln!(content, "// file {} changed in commit {}", index, commit);
+        } else {
+            let _ = writeln!(content, "fn item_{}_{}() {{}}", index, line);
+        }
+    }
+    content
+}
+
+fn git(dir: &Path, args: &[&str]) {
+    let status = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .status()
+        .expect("git is installed");
+    assert!(status.success(), "git {} failed", args.join(" "));
+}
+
+/// The generated repository for `spec`, created on first use. The history is
+/// linear on `main`, with a branch every 1,000 commits and a tag every 10,000.
+/// The working tree has 5% of the files modified (a fifth of those staged) and
+/// 200 untracked files.
+pub fn large_repo(spec: RepoSpec) -> PathBuf {
+    let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR"))
+        .join(format!("large-repo-{}x{}", spec.commits, spec.files));
+    let marker = dir.join(".git").join("zit-bench-complete");
+    if marker.exists() {
+        return dir;
+    }
+
+    eprintln!(
+        "Generating a repository with {} commits and {} files in {}",
+        spec.commits,
+        spec.files,
+        dir.display()
+    );
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    git(&dir, &["init", "-q"]);
+    git(&dir, &["symbolic-ref", "HEAD", "refs/heads/main"]);
+    git(&dir, &["config", "user.name", "Bench"]);
+    git(&dir, &["config", "user.email", "bench@example.com"]);
+    git(&dir, &["config", "commit.gpgsign", "false"]);
+
+    let mut import = Command::new("git")
+        .args(["fast-import", "--quiet"])
+        .current_dir(&dir)
+        .stdin(Stdio::piped())
+        .spawn()
+        .expect("git is installed");
+    {
+        let mut out = BufWriter::new(import.stdin.take().unwrap());
+        for commit in 1..=spec.commits {
+            let message = if commit == 1 {
+                "Initial import".to_string()
+            } else {
+                format!("Update file {} (#{})", (commit * 7919) % spec.files, commit)
+            };
+            let author = ["Ada", "Grace", "Linus", "Margaret"][commit % 4];
+            writeln!(out, "commit refs/heads/main").unwrap();
+            writeln!(out, "mark :{}", commit).unwrap();
+            writeln!(
+                out,
+                "committer {} <{}@example.com> {} +0000",
+                author,
+                author.to_lowercase(),
+                1_600_000_000 + commit * 600
+            )
+            .unwrap();
+            writeln!(out, "data {}\n{}", message.len(), message).unwrap();
+            let changed: Vec<usize> = if commit == 1 {
+                (0..spec.files).collect()
+            } else {
+                vec![(commit * 7919) % spec.files]
+            };
+            for index in changed {
+                let content = file_content(index, commit - 1);
+                writeln!(out, "M 100644 inline {}", file_path(index)).unwrap();
+                writeln!(out, "data {}\n{}", content.len(), content).unwrap();
+            }
+            writeln!(out).unwrap();
+            if commit % 1_000 == 0 {
+                writeln!(
+                    out,
+                    "reset refs/heads/feature/{}\nfrom :{}\n",
+                    commit, commit
+                )
+                .unwrap();
+            }
+            if commit % 10_000 == 0 {
+                writeln!(
+                    out,
+                    "reset refs/tags/v{}.0\nfrom :{}\n",
+                    commit / 10_000,
+                    commit
+                )
+                .unwrap();
+            }
+        }
+        out.flush().unwrap();
+    }
+    assert!(import.wait().unwrap().success(), "git fast-import failed");
+    git(&dir, &["reset", "-q", "--hard", "main"]);
+
+    // Dirty the working tree
+    let mut staged = Vec::new();
+    for index in (0..spec.files).step_by(20) {
+        let path = file_path(index);
+        let mut content = std::fs::read_to_string(dir.join(&path)).unwrap();
+        content.push_str("// local edit\n");
+        std::fs::write(dir.join(&path), content).unwrap();
+        if index % 100 == 0 {
+            staged.push(path);
+        }
+    }
+    for chunk in staged.chunks(500) {
+        let mut args = vec!["add", "--"];
+        args.extend(chunk.iter().map(String::as_str));
+        git(&dir, &args);
+    }
+    std::fs::create_dir_all(dir.join("untracked")).unwrap();
+    for index in 0..200 {
+        std::fs::write(dir.join(format!("untracked/new{:03}.txt", index)), "new\n").unwrap();
+    }
+
+    std::fs::write(&marker, "").unwrap();
+    dir
+}
+
+/// `git status --porcelain=v2 --branch` output with `files` entries: a mix of
+/// modified, staged, renamed, conflicted and untracked paths.
+pub fn status_output(files: usize) -> String {
+    let mut out = String::from(
+        "# branch.oid 0123456789abcdef0123456789abcdef01234567\n\
+         # branch.head main\n\
+         # branch.upstream origin/main\n\
+         # branch.ab +3 -1\n",
+    );
+    let hash = "0123456789abcdef0123456789abcdef01234567";
+    for index in 0..files {
+        let path = file_path(index);
+        let _ = match index % 10 {
+            0..=4 => writeln!(out, "1 .M N... 100644 100644 100644 {hash} {hash} {path}"),
+            5 | 6 => writeln!(out, "1 M. N... 100644 100644 100644 {hash} {hash} {path}"),
+            7 => writeln!(
+                out,
+                "2 R. N... 100644 100644 100644 {hash} {hash} R100 {path}\told/{path}"
+            ),
+            8 => writeln!(
+                out,
+                "u UU N... 100644 100644 100644 100644 {hash} {hash} {hash} {path}"
+            ),
+            _ => writeln!(out, "? {path}"),
+        };
+    }
+    out
+}
+
+/// `git diff` output for `files` files with `hunks` hunks each.
+pub fn diff_output(files: usize, hunks: usize) -> String {
+    let mut out = String::new();
+    for index in 0..files {
+        let path = file_path(index);
+        let _ = writeln!(
+            out,
+            "diff --git a/{path} b/{path}\nindex 1234567..89abcde 100644\n--- a/{path}\n+++ b/{path}"
+        );
+        for hunk in 0..hunks {
+            let start = hunk * 30 + 1;
+            let _ = writeln!(
+                out,
+                "@@ -{start},7 +{start},8 @@ fn item_{index}_{hunk}() {{}}"
+            );
+            for line in 0..3 {
+                let _ = writeln!(out, " fn context_{}() {{}}", line);
+            }
+            let _ = writeln!(out, "-fn old_{}_{}() {{}}", index, hunk);
+            let _ = writeln!(out, "+fn new_{}_{}() {{}}", index, hunk);
+            let _ = writeln!(out, "+fn added_{}_{}() {{}}", index, hunk);
+            for line in 3..6 {
+                let _ = writeln!(out, " fn context_{}() {{}}", line);
+            }
+        }
+    }
+    out
+}
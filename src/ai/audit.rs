@@ -0,0 +1,199 @@
+//! Local audit log of outbound AI requests.
+//!
+//! Every request that actually leaves the machine is appended as one JSON line to
+//! `~/.config/zit/ai_audit.jsonl`: what kind of request it was, which pieces of
+//! context it carried, how large it was, where it went and how long it took.
+//! Only metadata is recorded — never the payload itself.
+
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::ui::datetime;
+
+/// Number of entries shown in the in-app audit view.
+pub const AUDIT_VIEW_LIMIT: usize = 100;
+
+/// Keep the file bounded: once it grows past this many entries it is trimmed back.
+const MAX_ENTRIES: usize = 1000;
+
+/// One outbound AI request.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub timestamp: String,
+    pub request_type: String,
+    pub provider: String,
+    /// Context fields included in the payload (e.g. "diff", "staged_files(3)").
+    pub context: Vec<String>,
+    /// Size of the serialized payload in bytes.
+    pub bytes: usize,
+    pub duration_ms: u64,
+    #[serde(default)]
+    pub redacted_lines: usize,
+    /// `None` on success, otherwise the error message.
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+impl AuditEntry {
+    pub fn new(request_type: &str, provider: &str, context: Vec<String>, bytes: usize) -> Self {
+        Self {
+            timestamp: datetime::now_iso(),
+            request_type: request_type.to_string(),
+            provider: provider.to_string(),
+            context,
+            bytes,
+            duration_ms: 0,
+            redacted_lines: 0,
+            error: None,
+        }
+    }
+}
+
+/// Get the audit log path (ai_audit.jsonl in the state directory).
+pub fn audit_path() -> PathBuf {
+    crate::paths::state_file("ai_audit.jsonl")
+}
+
+/// Append an entry to the audit log (best-effort, errors are logged and ignored).
+pub fn record(entry: &AuditEntry) {
+    if let Err(e) = append_to(&audit_path(), entry) {
+        log::warn!("Failed to write AI audit log: {}", e);
+    }
+}
+
+/// Most recent `limit` entries, oldest first. Empty on any error.
+pub fn load_recent(limit: usize) -> Vec<AuditEntry> {
+    load_from(&audit_path(), limit)
+}
+
+fn append_to(path: &Path, entry: &AuditEntry) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let line = serde_json::to_string(entry)?;
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+    writeln!(file, "{}", line)?;
+    drop(file);
+
+    // Trim the oldest entries once the file exceeds the cap
+    let content = std::fs::read_to_string(path)?;
+    let count = content.lines().count();
+    if count > MAX_ENTRIES {
+        let kept: Vec<&str> = content.lines().skip(count - MAX_ENTRIES).collect();
+        std::fs::write(path, kept.join("\n") + "\n")?;
+    }
+    Ok(())
+}
+
+fn load_from(path: &Path, limit: usize) -> Vec<AuditEntry> {
+    let Ok(content) = std::fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    let entries: Vec<AuditEntry> = content
+        .lines()
+        .filter_map(|l| serde_json::from_str(l).ok())
+        .collect();
+    let skip = entries.len().saturating_sub(limit);
+    entries.into_iter().skip(skip).collect()
+}
+
+/// Render entries for the in-app audit view, newest first.
+pub fn format_entries(entries: &[AuditEntry]) -> String {
+    if entries.is_empty() {
+        return "No AI requests recorded yet.\n\nEvery request sent to an AI provider is logged here \
+                (metadata only, never the content)."
+            .to_string();
+    }
+
+    let mut out = format!(
+        "{} request(s) — log file: {}\n",
+        entries.len(),
+        audit_path().display()
+    );
+    for e in entries.iter().rev() {
+        let outcome = match e.error {
+            None => "ok".to_string(),
+            Some(ref err) => format!("failed: {}", err),
+        };
+        out.push_str(&format!(
+            "\n[{}] {} → {}\n  {} bytes · {} ms · {}\n",
+            datetime::format_iso(&e.timestamp, &e.timestamp),
+            e.request_type,
+            e.provider,
+            e.bytes,
+            e.duration_ms,
+            outcome
+        ));
+        let context = if e.context.is_empty() {
+            "none".to_string()
+        } else {
+            e.context.join(", ")
+        };
+        out.push_str(&format!("  context: {}\n", context));
+        if e.redacted_lines > 0 {
+            out.push_str(&format!("  redacted: {} line(s)\n", e.redacted_lines));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(request_type: &str) -> AuditEntry {
+        AuditEntry::new(
+            request_type,
+            "openai",
+            vec!["branch".to_string(), "diff".to_string()],
+            1234,
+        )
+    }
+
+    #[test]
+    fn test_append_and_load_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("audit.jsonl");
+        append_to(&path, &entry("review")).unwrap();
+        append_to(&path, &entry("explain")).unwrap();
+
+        let loaded = load_from(&path, 10);
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(loaded[0].request_type, "review");
+        assert_eq!(loaded[1].bytes, 1234);
+
+        let last = load_from(&path, 1);
+        assert_eq!(last.len(), 1);
+        assert_eq!(last[0].request_type, "explain");
+    }
+
+    #[test]
+    fn test_load_skips_corrupt_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("audit.jsonl");
+        std::fs::write(&path, "not json\n").unwrap();
+        append_to(&path, &entry("learn")).unwrap();
+        assert_eq!(load_from(&path, 10).len(), 1);
+        assert!(load_from(&dir.path().join("missing"), 10).is_empty());
+    }
+
+    #[test]
+    fn test_format_entries() {
+        let mut failed = entry("commit_suggestion");
+        failed.error = Some("timeout".to_string());
+        failed.redacted_lines = 2;
+        let text = format_entries(&[entry("review"), failed]);
+        assert!(text.contains("commit_suggestion → openai"));
+        assert!(text.contains("failed: timeout"));
+        assert!(text.contains("context: branch, diff"));
+        assert!(text.contains("redacted: 2 line(s)"));
+        // Newest first
+        assert!(text.find("commit_suggestion").unwrap() < text.find("review").unwrap());
+
+        assert!(format_entries(&[]).contains("No AI requests"));
+    }
+}
@@ -0,0 +1,200 @@
+//! Fit diffs into the AI character budget (`[ai] diff_budget`).
+//!
+//! Instead of cutting the combined diff at a fixed offset, files are ordered by
+//! change size so the main change comes first, whole hunks are kept or dropped,
+//! and anything left out is reported so the prompt can say what is missing.
+
+use crate::git::diff::{DiffLineType, FileDiff, Hunk};
+
+const TRUNCATED: &str = "...(truncated)";
+
+/// A diff trimmed to fit the budget.
+#[derive(Debug, Default, PartialEq)]
+pub struct BudgetedDiff {
+    pub text: String,
+    /// Files left out entirely, or only partly shown ("path (2 of 5 hunks)").
+    pub omitted_files: Vec<String>,
+}
+
+/// Number of added/removed lines in a file diff.
+fn changed_lines(file: &FileDiff) -> usize {
+    file.hunks
+        .iter()
+        .flat_map(|h| &h.lines)
+        .filter(|l| matches!(l.line_type, DiffLineType::Added | DiffLineType::Removed))
+        .count()
+}
+
+fn hunk_text(hunk: &Hunk) -> String {
+    let mut text = String::new();
+    for line in &hunk.lines {
+        text.push_str(&line.content);
+        text.push('\n');
+    }
+    text
+}
+
+/// Combine file diffs into at most `budget` characters, largest change first,
+/// keeping hunks whole. The first hunk is cut at a line break if even it does not fit,
+/// so the main change is never dropped entirely.
+pub fn fit_file_diffs(diffs: &[&FileDiff], budget: usize) -> BudgetedDiff {
+    let mut files: Vec<&FileDiff> = diffs.to_vec();
+    // Stable sort keeps git's path order among equally sized changes
+    files.sort_by_key(|f| std::cmp::Reverse(changed_lines(f)));
+
+    let mut out = BudgetedDiff::default();
+    let mut used = 0;
+
+    for file in files {
+        let header = format!("--- {}\n", file.path);
+        let mut shown = 0;
+
+        for hunk in &file.hunks {
+            let text = hunk_text(hunk);
+            let extra = text.len() + if shown == 0 { header.len() } else { 0 };
+            if used + extra <= budget {
+                if shown == 0 {
+                    out.text.push_str(&header);
+                }
+                out.text.push_str(&text);
+                used += extra;
+                shown += 1;
+            } else if used == 0 {
+                // Nothing fits yet: show the start of the biggest hunk rather than nothing
+                out.text.push_str(&header);
+                let room = budget.saturating_sub(header.len());
+                out.text.push_str(&fit_text(&text, room));
+                out.text.push('\n');
+                used = budget;
+                shown += 1;
+                break;
+            } else {
+                break;
+            }
+        }
+
+        if shown == 0 {
+            out.omitted_files.push(file.path.clone());
+        } else if shown < file.hunks.len() {
+            out.omitted_files.push(format!(
+                "{} ({} of {} hunks)",
+                file.path,
+                shown,
+                file.hunks.len()
+            ));
+        }
+    }
+
+    out
+}
+
+/// Trim free-form diff text to `budget` characters, preferring to stop at a hunk
+/// boundary (`@@`), then at a line break. Appends a truncation marker when cut.
+pub fn fit_text(text: &str, budget: usize) -> String {
+    if text.len() <= budget {
+        return text.to_string();
+    }
+    let mut end = budget;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    let head = &text[..end];
+    let cut = head
+        .rfind("\n@@")
+        .filter(|&i| i > 0)
+        .or_else(|| head.rfind('\n'))
+        .unwrap_or(end);
+    format!("{}\n{}", text[..cut].trim_end_matches('\n'), TRUNCATED)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::diff::DiffLine;
+
+    fn hunk(start: u32, added: usize) -> Hunk {
+        let header = format!("@@ -{},0 +{},{} @@", start, start, added);
+        let mut lines = vec![DiffLine {
+            line_type: DiffLineType::Header,
+            content: header.clone(),
+        }];
+        for i in 0..added {
+            lines.push(DiffLine {
+                line_type: DiffLineType::Added,
+                content: format!("+line {}", i),
+            });
+        }
+        Hunk {
+            header,
+            old_start: start,
+            old_count: 0,
+            new_start: start,
+            new_count: added as u32,
+            lines,
+        }
+    }
+
+    fn file(path: &str, hunks: Vec<Hunk>) -> FileDiff {
+        FileDiff {
+            path: path.to_string(),
+            old_path: None,
+            hunks,
+            additions: 0,
+            deletions: 0,
+        }
+    }
+
+    #[test]
+    fn test_everything_fits() {
+        let a = file("a.rs", vec![hunk(1, 2)]);
+        let out = fit_file_diffs(&[&a], 10_000);
+        assert!(out.text.starts_with("--- a.rs\n@@"));
+        assert!(out.text.contains("+line 1\n"));
+        assert!(out.omitted_files.is_empty());
+    }
+
+    #[test]
+    fn test_largest_file_first_and_small_omitted() {
+        let small = file("small.rs", vec![hunk(1, 1)]);
+        let big = file("big.rs", vec![hunk(1, 20)]);
+        let big_len = fit_file_diffs(&[&big], 10_000).text.len();
+
+        let out = fit_file_diffs(&[&small, &big], big_len + 5);
+        assert!(out.text.starts_with("--- big.rs"));
+        assert!(!out.text.contains("small.rs"));
+        assert_eq!(out.omitted_files, vec!["small.rs"]);
+    }
+
+    #[test]
+    fn test_keeps_whole_hunks_and_reports_partial_file() {
+        let f = file("a.rs", vec![hunk(1, 3), hunk(50, 3)]);
+        let one = fit_file_diffs(&[&file("a.rs", vec![hunk(1, 3)])], 10_000);
+
+        let out = fit_file_diffs(&[&f], one.text.len() + 10);
+        assert_eq!(out.text, one.text);
+        assert_eq!(out.omitted_files, vec!["a.rs (1 of 2 hunks)"]);
+    }
+
+    #[test]
+    fn test_oversized_first_hunk_is_cut_not_dropped() {
+        let f = file("a.rs", vec![hunk(1, 100)]);
+        let out = fit_file_diffs(&[&f], 80);
+        assert!(out.text.starts_with("--- a.rs\n@@"));
+        assert!(out.text.contains(TRUNCATED));
+        assert!(out.omitted_files.is_empty());
+    }
+
+    #[test]
+    fn test_fit_text_prefers_hunk_boundary() {
+        let text = "@@ -1 +1 @@\n+a\n+b\n@@ -9 +9 @@\n+c\n+d\n";
+        assert_eq!(fit_text(text, 1000), text);
+        assert_eq!(fit_text(text, 30), "@@ -1 +1 @@\n+a\n+b\n...(truncated)");
+        assert_eq!(fit_text("+aaaa\n+bbbb", 8), "+aaaa\n...(truncated)");
+    }
+
+    #[test]
+    fn test_fit_text_respects_char_boundaries() {
+        let out = fit_text("ééééé", 3);
+        assert!(out.ends_with(TRUNCATED));
+    }
+}
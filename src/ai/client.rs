@@ -6,27 +6,41 @@
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::io::Read;
-use std::sync::{mpsc, Arc, Mutex};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, mpsc};
 use std::time::{Duration, Instant};
 
-use crate::config::AiConfig;
+use super::audit::{self, AuditEntry};
+use super::budget;
+use super::redact::Redactor;
+use crate::config::{AiConfig, AiContextConfig};
 use crate::git;
+use crate::git::runner::CommandRecord;
+use crate::http::{self, Method, Request, Response};
 
 // ─── Constants ─────────────────────────────────────────────────
 
 /// Maximum number of retry attempts for transient failures.
 const MAX_RETRIES: u32 = 2;
 
-/// Re-export from parent module for local use.
-use super::DIFF_TRUNCATE_AT;
-
 /// Cache TTL — responses are cached for 5 minutes.
 const CACHE_TTL: Duration = Duration::from_secs(300);
 
 /// Maximum cached entries before eviction.
 const CACHE_MAX_ENTRIES: usize = 50;
 
+/// Recent commits listed per side when comparing branches for the strategy advisor.
+const STRATEGY_COMMIT_LIMIT: usize = 10;
+
+/// Unchanged lines sent above and below a conflict region for per-region resolution.
+const REGION_CONTEXT_LINES: usize = 5;
+
+/// Maximum characters taken from README / CONTRIBUTING for project context.
+const PROJECT_DOC_EXCERPT: usize = 1500;
+
+/// Maximum characters of CONTRIBUTING sent for the readiness summary.
+const CONTRIBUTING_EXCERPT: usize = 6000;
+
 // ─── Request / Response Types ──────────────────────────────────
 
 #[derive(Debug, Serialize)]
@@ -41,7 +55,7 @@ pub struct MentorRequest {
     pub error: Option<String>,
 }
 
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Default, Serialize, Clone)]
 pub struct RepoContext {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub repo_path: Option<String>,
@@ -55,6 +69,9 @@ pub struct RepoContext {
     pub diff_stats: Option<DiffStats>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub diff: Option<String>,
+    /// Files left out of `diff` (or only partly shown) to stay within the diff budget.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub omitted_files: Vec<String>,
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub conflict_files: Vec<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -65,6 +82,110 @@ pub struct RepoContext {
     pub merge_type: Option<String>,
     #[serde(default)]
     pub detached_head: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project: Option<ProjectContext>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub strategy: Option<StrategyContext>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub failure: Option<FailureContext>,
+}
+
+/// Optional project-level context, enabled per item via `[ai.context]`.
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct ProjectContext {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_branch: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub recent_commits: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub readme: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contributing: Option<String>,
+}
+
+/// How to rework a previous AI commit message suggestion.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CommitRefinement {
+    /// Same request, different wording.
+    Retry,
+    Shorter,
+    Longer,
+    /// Free-form instruction, e.g. "mention the config change".
+    Custom(String),
+}
+
+impl CommitRefinement {
+    /// Short label for status messages and menus.
+    pub fn label(&self) -> &str {
+        match self {
+            CommitRefinement::Retry => "Try again",
+            CommitRefinement::Shorter => "Shorter",
+            CommitRefinement::Longer => "Longer",
+            CommitRefinement::Custom(instruction) => instruction,
+        }
+    }
+
+    fn instruction(&self) -> String {
+        match self {
+            CommitRefinement::Retry => {
+                "Suggest different commit messages than the previous ones.".to_string()
+            }
+            CommitRefinement::Shorter => {
+                "Make the previous suggestion shorter and tighter, keeping its meaning.".to_string()
+            }
+            CommitRefinement::Longer => {
+                "Make the previous suggestion more descriptive; the subject may exceed 50 characters."
+                    .to_string()
+            }
+            CommitRefinement::Custom(instruction) => {
+                format!("Revise the previous suggestion: {}", instruction.trim())
+            }
+        }
+    }
+}
+
+/// Query for a refinement request: the previous suggestion plus what to change.
+fn refinement_query(previous: &str, refinement: &CommitRefinement) -> String {
+    format!(
+        "Previous suggestion:\n{}\n\nInstruction: {}\nRespond in the same [SUGGESTION] format.",
+        previous.trim(),
+        refinement.instruction()
+    )
+}
+
+/// Branch divergence and conflict summary for the merge-strategy advisor.
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct StrategyContext {
+    pub ours_branch: String,
+    pub theirs_branch: String,
+    pub ahead: usize,
+    pub behind: usize,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub ours_commits: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub theirs_commits: Vec<String>,
+    /// Conflicted files with their conflict region counts, e.g. "src/app.rs (2 regions)".
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub conflicts: Vec<String>,
+}
+
+/// What happened when a git command failed, for error explanations.
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct FailureContext {
+    /// The command as run, e.g. "git push origin main". `None` if the error
+    /// couldn't be tied to a command in the journal.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exit_code: Option<i32>,
+    /// How the command ended when there's no exit code, e.g. "timed out".
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub outcome: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stderr: Option<String>,
+    /// Repository state at the time, e.g. "rebase in progress", "2 behind origin/main".
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub repo_state: Vec<String>,
 }
 
 #[derive(Debug, Serialize, Clone)]
@@ -114,6 +235,7 @@ fn cache_key(request: &MentorRequest) -> String {
         ctx.branch.hash(&mut hasher);
         ctx.staged_files.hash(&mut hasher);
         ctx.diff.hash(&mut hasher);
+        ctx.conflict_diff.hash(&mut hasher);
     }
     format!("{:x}", hasher.finish())
 }
@@ -130,8 +252,17 @@ pub struct AiClient {
     endpoint: String,
     api_key: String,
     provider_kind: String,
-    client: reqwest::blocking::Client,
+    /// Per-request timeout for the Bedrock path.
+    timeout: Duration,
     cache: ResponseCache,
+    /// Which optional project context to attach to requests.
+    context_options: AiContextConfig,
+    /// Secret redaction applied to every outgoing request.
+    redactor: Redactor,
+    /// Number of lines redacted from the most recent request.
+    redacted_lines: AtomicUsize,
+    /// Maximum diff characters per request.
+    diff_budget: usize,
 }
 
 impl AiClient {
@@ -146,21 +277,17 @@ impl AiClient {
         let endpoint = config.effective_endpoint().unwrap_or_default();
         let api_key = config.resolved_api_key().unwrap_or_default();
 
-        let client = reqwest::blocking::Client::builder()
-            .timeout(std::time::Duration::from_secs(
-                config.timeout_secs.unwrap_or(30),
-            ))
-            .connect_timeout(std::time::Duration::from_secs(5))
-            .build()
-            .ok()?;
-
         Some(Self {
             provider: prov,
             endpoint,
             api_key,
             provider_kind,
-            client,
+            timeout: Duration::from_secs(config.timeout_secs.unwrap_or(30)),
             cache: Arc::new(Mutex::new(HashMap::new())),
+            context_options: config.context.clone(),
+            redactor: Redactor::from_config(&config.redaction),
+            redacted_lines: AtomicUsize::new(0),
+            diff_budget: config.effective_diff_budget(),
         })
     }
 
@@ -175,6 +302,70 @@ impl AiClient {
         self.provider.model_name()
     }
 
+    /// Number of lines redacted from the most recent request (for the "N lines redacted" note).
+    pub fn redacted_lines(&self) -> usize {
+        self.redacted_lines.load(Ordering::Relaxed)
+    }
+
+    /// Mask secrets in every free-text field of a request and record how many lines were touched.
+    fn redact_request(&self, request: &MentorRequest) -> MentorRequest {
+        let mut lines = 0;
+        let redacted = {
+            let mut redact = |text: &Option<String>| {
+                text.as_ref().map(|t| {
+                    let (out, n) = self.redactor.redact(t);
+                    lines += n;
+                    out
+                })
+            };
+            MentorRequest {
+                request_type: request.request_type.clone(),
+                context: request.context.clone().map(|mut ctx| {
+                    ctx.diff = redact(&ctx.diff);
+                    ctx.conflict_diff = redact(&ctx.conflict_diff);
+                    if let Some(ref mut failure) = ctx.failure {
+                        failure.command = redact(&failure.command);
+                        failure.stderr = redact(&failure.stderr);
+                    }
+                    if let Some(ref mut project) = ctx.project {
+                        project.readme = redact(&project.readme);
+                        project.contributing = redact(&project.contributing);
+                        project.recent_commits = project
+                            .recent_commits
+                            .iter()
+                            .map(|subject| redact(&Some(subject.clone())).unwrap_or_default())
+                            .collect();
+                    }
+                    ctx
+                }),
+                query: redact(&request.query),
+                error: redact(&request.error),
+            }
+        };
+        if lines > 0 {
+            log::info!(
+                "AI request: redacted {} line(s) for type={}",
+                lines,
+                request.request_type
+            );
+        }
+        self.redacted_lines.store(lines, Ordering::Relaxed);
+        redacted
+    }
+
+    /// Start an audit log entry describing what an outgoing request carries.
+    fn audit_entry(&self, request: &MentorRequest) -> AuditEntry {
+        let bytes = serde_json::to_vec(request).map(|b| b.len()).unwrap_or(0);
+        let mut entry = AuditEntry::new(
+            &request.request_type,
+            &self.provider_kind,
+            describe_context(request),
+            bytes,
+        );
+        entry.redacted_lines = self.redacted_lines();
+        entry
+    }
+
     /// Generate a unique request ID for tracing.
     fn request_id() -> String {
         use std::time::SystemTime;
@@ -188,10 +379,10 @@ impl AiClient {
     /// Look up a cached response. Returns None if not found or expired.
     fn get_cached(&self, key: &str) -> Option<String> {
         let cache = self.cache.lock().ok()?;
-        if let Some(entry) = cache.get(key) {
-            if entry.created.elapsed() < CACHE_TTL {
-                return Some(entry.response.clone());
-            }
+        if let Some(entry) = cache.get(key)
+            && entry.created.elapsed() < CACHE_TTL
+        {
+            return Some(entry.response.clone());
         }
         None
     }
@@ -202,14 +393,13 @@ impl AiClient {
             // Evict expired entries
             cache.retain(|_, v| v.created.elapsed() < CACHE_TTL);
             // Evict oldest if at capacity
-            if cache.len() >= CACHE_MAX_ENTRIES {
-                if let Some(oldest_key) = cache
+            if cache.len() >= CACHE_MAX_ENTRIES
+                && let Some(oldest_key) = cache
                     .iter()
                     .min_by_key(|(_, v)| v.created)
                     .map(|(k, _)| k.clone())
-                {
-                    cache.remove(&oldest_key);
-                }
+            {
+                cache.remove(&oldest_key);
             }
             cache.insert(
                 key,
@@ -225,6 +415,8 @@ impl AiClient {
     /// For Bedrock, sends the full MentorRequest JSON to Lambda.
     /// For other providers, builds prompts client-side and calls provider.chat().
     fn call(&self, request: &MentorRequest) -> Result<String> {
+        let request = &self.redact_request(request);
+
         // Check cache first
         let ckey = cache_key(request);
         if let Some(cached) = self.get_cached(&ckey) {
@@ -240,6 +432,9 @@ impl AiClient {
             request_id
         );
 
+        let mut audit_entry = self.audit_entry(request);
+        let started = Instant::now();
+
         let result = if self.provider_kind == "bedrock" {
             // Bedrock: send full JSON to Lambda (Lambda constructs prompts)
             self.call_bedrock(request)
@@ -248,6 +443,10 @@ impl AiClient {
             self.call_direct(request)
         };
 
+        audit_entry.duration_ms = started.elapsed().as_millis() as u64;
+        audit_entry.error = result.as_ref().err().map(|e| e.to_string());
+        audit::record(&audit_entry);
+
         // Cache on success
         if let Ok(ref response) = result {
             self.set_cached(ckey, response.clone());
@@ -271,10 +470,8 @@ impl AiClient {
                 ));
             }
 
-            let send_result = self
-                .client
-                .post(&self.endpoint)
-                .header("Content-Type", "application/json")
+            let send_result = Request::new(Method::Post, &self.endpoint)
+                .timeout(self.timeout)
                 .header("x-api-key", &self.api_key)
                 .header("x-request-id", Self::request_id())
                 .json(&body)
@@ -282,21 +479,19 @@ impl AiClient {
 
             match send_result {
                 Ok(resp) => {
-                    let status = resp.status();
-
-                    if status.is_client_error() {
-                        return self.parse_error_response(resp, status.as_u16());
+                    if resp.is_client_error() {
+                        return self.parse_error_response(&resp);
                     }
 
-                    if status.is_server_error() {
-                        last_error = Some(anyhow::anyhow!(classify_http_error(status.as_u16())));
+                    if resp.is_server_error() {
+                        last_error = Some(anyhow::anyhow!(classify_http_error(resp.status)));
                         continue;
                     }
 
-                    return self.parse_success_response(resp);
+                    return self.parse_success_response(&resp);
                 }
                 Err(e) => {
-                    if attempt == 0 && e.is_connect() {
+                    if attempt == 0 && matches!(e, http::Error::Connect(_)) {
                         return Err(anyhow::anyhow!(
                             "You appear to be offline — cannot reach AI service."
                         ));
@@ -311,7 +506,7 @@ impl AiClient {
     }
 
     /// Parse a successful Bedrock/Lambda response.
-    fn parse_success_response(&self, resp: reqwest::blocking::Response) -> Result<String> {
+    fn parse_success_response(&self, resp: &Response) -> Result<String> {
         let body: MentorApiResponse = resp.json().context("Failed to parse AI mentor response")?;
 
         if !body.success {
@@ -327,17 +522,13 @@ impl AiClient {
     }
 
     /// Parse an error response from Bedrock/Lambda.
-    fn parse_error_response(
-        &self,
-        resp: reqwest::blocking::Response,
-        status: u16,
-    ) -> Result<String> {
-        if let Ok(body) = resp.json::<MentorApiResponse>() {
-            if let Some(err) = body.error {
-                anyhow::bail!("{}", err);
-            }
+    fn parse_error_response(&self, resp: &Response) -> Result<String> {
+        if let Ok(body) = resp.json::<MentorApiResponse>()
+            && let Some(err) = body.error
+        {
+            anyhow::bail!("{}", err);
         }
-        anyhow::bail!("{}", classify_http_error(status));
+        anyhow::bail!("{}", classify_http_error(resp.status));
     }
 
     /// Call Bedrock with streaming response for agent mode.
@@ -355,10 +546,8 @@ impl AiClient {
                 ));
             }
 
-            let send_result = self
-                .client
-                .post(&self.endpoint)
-                .header("Content-Type", "application/json")
+            let send_result = Request::new(Method::Post, &self.endpoint)
+                .timeout(self.timeout)
                 .header("x-api-key", &self.api_key)
                 .header("x-request-id", Self::request_id())
                 .json(&body)
@@ -366,19 +555,16 @@ impl AiClient {
 
             match send_result {
                 Ok(resp) => {
-                    let status = resp.status();
-
-                    if status.is_client_error() {
-                        return self.parse_error_response(resp, status.as_u16());
+                    if resp.is_client_error() {
+                        return self.parse_error_response(&resp);
                     }
 
-                    if status.is_server_error() {
-                        last_error = Some(anyhow::anyhow!(classify_http_error(status.as_u16())));
+                    if resp.is_server_error() {
+                        last_error = Some(anyhow::anyhow!(classify_http_error(resp.status)));
                         continue;
                     }
 
-                    // Read the full response body as text
-                    let text = resp.text().context("Failed to read response body")?;
+                    let text = resp.text();
 
                     // Try to parse as JSON API response
                     if let Ok(api_resp) = serde_json::from_str::<MentorApiResponse>(&text) {
@@ -401,7 +587,7 @@ impl AiClient {
                     anyhow::bail!("Empty response from AI service");
                 }
                 Err(e) => {
-                    if attempt == 0 && e.is_connect() {
+                    if attempt == 0 && matches!(e, http::Error::Connect(_)) {
                         return Err(anyhow::anyhow!(
                             "You appear to be offline — cannot reach AI service."
                         ));
@@ -428,11 +614,15 @@ impl AiClient {
                 unstaged_files: vec![],
                 diff_stats: None,
                 diff: None,
+                omitted_files: vec![],
                 conflict_files: vec![],
                 conflict_diff: None,
                 has_conflicts: false,
                 merge_type: None,
                 detached_head: false,
+                project: None,
+                strategy: None,
+                failure: None,
             });
 
         let system_prompt = prompts::system_prompt_for(&request.request_type);
@@ -474,7 +664,12 @@ impl AiClient {
 
     /// Suggest a commit message based on staged changes.
     pub fn suggest_commit_message(&self) -> Result<String> {
-        let ctx = build_repo_context(true)?;
+        let ctx = build_repo_context(
+            true,
+            &self.context_options,
+            &self.redactor,
+            self.diff_budget,
+        )?;
         let request = MentorRequest {
             request_type: "commit_suggestion".to_string(),
             context: Some(ctx),
@@ -484,9 +679,36 @@ impl AiClient {
         self.call(&request)
     }
 
+    /// Regenerate commit message suggestions, sending the previous suggestion and
+    /// the refinement instruction so the AI iterates instead of starting over.
+    pub fn refine_commit_message(
+        &self,
+        previous: &str,
+        refinement: &CommitRefinement,
+    ) -> Result<String> {
+        let ctx = build_repo_context(
+            true,
+            &self.context_options,
+            &self.redactor,
+            self.diff_budget,
+        )?;
+        let request = MentorRequest {
+            request_type: "commit_suggestion".to_string(),
+            context: Some(ctx),
+            query: Some(refinement_query(previous, refinement)),
+            error: None,
+        };
+        self.call(&request)
+    }
+
     /// Explain the current repository state.
     pub fn explain_repo(&self, query: Option<&str>) -> Result<String> {
-        let ctx = build_repo_context(false)?;
+        let ctx = build_repo_context(
+            false,
+            &self.context_options,
+            &self.redactor,
+            self.diff_budget,
+        )?;
         let request = MentorRequest {
             request_type: "explain".to_string(),
             context: Some(ctx),
@@ -496,9 +718,20 @@ impl AiClient {
         self.call(&request)
     }
 
-    /// Explain a git error and suggest fixes.
-    pub fn explain_error(&self, error_message: &str) -> Result<String> {
-        let ctx = build_repo_context(false)?;
+    /// Explain a git error and suggest fixes. `failed` is the journal entry for
+    /// the command behind it, captured when the error happened.
+    pub fn explain_error(
+        &self,
+        error_message: &str,
+        failed: Option<&CommandRecord>,
+    ) -> Result<String> {
+        let mut ctx = build_repo_context(
+            false,
+            &self.context_options,
+            &self.redactor,
+            self.diff_budget,
+        )?;
+        ctx.failure = Some(build_failure_context(failed, &ctx));
         let request = MentorRequest {
             request_type: "error".to_string(),
             context: Some(ctx),
@@ -510,7 +743,12 @@ impl AiClient {
 
     /// Get a recommendation for a git operation.
     pub fn recommend(&self, query: &str) -> Result<String> {
-        let ctx = build_repo_context(false)?;
+        let ctx = build_repo_context(
+            false,
+            &self.context_options,
+            &self.redactor,
+            self.diff_budget,
+        )?;
         let request = MentorRequest {
             request_type: "recommend".to_string(),
             context: Some(ctx),
@@ -527,14 +765,16 @@ impl AiClient {
 
     /// Review a specific file's diff using AI. Returns review comments/suggestions.
     pub fn review_diff(&self, file_path: &str, diff_content: &str) -> Result<String> {
+        if self.redactor.is_excluded(file_path) {
+            anyhow::bail!(
+                "'{}' is excluded from AI requests ([ai.redaction] exclude_files)",
+                file_path
+            );
+        }
         let branch = git::branch::BranchOps::current().ok();
 
-        // Truncate diff if too long
-        let diff_text = if diff_content.len() > DIFF_TRUNCATE_AT {
-            format!("{}...(truncated)", &diff_content[..DIFF_TRUNCATE_AT])
-        } else {
-            diff_content.to_string()
-        };
+        // Trim to the diff budget, stopping at a hunk boundary
+        let diff_text = budget::fit_text(diff_content, self.diff_budget);
 
         let context = RepoContext {
             repo_path: None,
@@ -543,11 +783,15 @@ impl AiClient {
             unstaged_files: vec![],
             diff_stats: None,
             diff: Some(diff_text),
+            omitted_files: vec![],
             conflict_files: vec![],
             conflict_diff: None,
             has_conflicts: false,
             merge_type: None,
             detached_head: false,
+            project: None,
+            strategy: None,
+            failure: None,
         };
 
         let request = MentorRequest {
@@ -567,7 +811,12 @@ impl AiClient {
 
     /// Learn about a git topic with beginner-friendly explanations.
     pub fn learn(&self, topic: &str) -> Result<String> {
-        let ctx = build_repo_context(false)?;
+        let ctx = build_repo_context(
+            false,
+            &self.context_options,
+            &self.redactor,
+            self.diff_budget,
+        )?;
         let request = MentorRequest {
             request_type: "learn".to_string(),
             context: Some(ctx),
@@ -579,7 +828,12 @@ impl AiClient {
 
     /// Ask a free-form question with full repo context.
     pub fn ask(&self, question: &str) -> Result<String> {
-        let ctx = build_repo_context(false)?;
+        let ctx = build_repo_context(
+            false,
+            &self.context_options,
+            &self.redactor,
+            self.diff_budget,
+        )?;
         let request = MentorRequest {
             request_type: "explain".to_string(),
             context: Some(ctx),
@@ -595,17 +849,19 @@ impl AiClient {
         file_path: &str,
         conflict_content: &str,
     ) -> Result<String> {
+        if self.redactor.is_excluded(file_path) {
+            anyhow::bail!(
+                "'{}' is excluded from AI requests ([ai.redaction] exclude_files)",
+                file_path
+            );
+        }
         let branch = git::branch::BranchOps::current().ok();
         let status = git::status::get_status().unwrap_or_default();
         let conflict_files: Vec<String> = status.conflicts.iter().map(|f| f.path.clone()).collect();
         let merge_state = git::merge::get_merge_state();
 
-        // Truncate if too long
-        let conflict_text = if conflict_content.len() > DIFF_TRUNCATE_AT {
-            format!("{}...(truncated)", &conflict_content[..DIFF_TRUNCATE_AT])
-        } else {
-            conflict_content.to_string()
-        };
+        // Trim to the diff budget
+        let conflict_text = budget::fit_text(conflict_content, self.diff_budget);
 
         let context = RepoContext {
             repo_path: None,
@@ -614,11 +870,15 @@ impl AiClient {
             unstaged_files: vec![],
             diff_stats: None,
             diff: None,
+            omitted_files: vec![],
             conflict_files,
             conflict_diff: Some(conflict_text),
             has_conflicts: true,
             merge_type: merge_state.map(|s| s.merge_type.to_string()),
             detached_head: false,
+            project: None,
+            strategy: None,
+            failure: None,
         };
 
         let request = MentorRequest {
@@ -634,9 +894,80 @@ impl AiClient {
         self.call(&request)
     }
 
+    /// Suggest a resolution for a single conflict region (`index` is 0-based).
+    /// Only the region and a few surrounding lines are sent, so this is much
+    /// smaller and faster than resolving the whole file.
+    pub fn suggest_region_resolution(
+        &self,
+        file_path: &str,
+        conflict_content: &str,
+        regions: &[git::merge::ConflictRegion],
+        index: usize,
+    ) -> Result<String> {
+        if self.redactor.is_excluded(file_path) {
+            anyhow::bail!(
+                "'{}' is excluded from AI requests ([ai.redaction] exclude_files)",
+                file_path
+            );
+        }
+        let Some(region) = regions.get(index) else {
+            anyhow::bail!("Conflict region {} not found in '{}'", index + 1, file_path);
+        };
+        let region_text =
+            git::merge::region_with_context(conflict_content, region, REGION_CONTEXT_LINES);
+        let region_text = budget::fit_text(&region_text, self.diff_budget);
+
+        let context = RepoContext {
+            repo_path: None,
+            branch: git::branch::BranchOps::current().ok(),
+            staged_files: vec![],
+            unstaged_files: vec![],
+            diff_stats: None,
+            diff: None,
+            omitted_files: vec![],
+            conflict_files: vec![file_path.to_string()],
+            conflict_diff: Some(region_text),
+            has_conflicts: true,
+            merge_type: git::merge::get_merge_state().map(|s| s.merge_type.to_string()),
+            detached_head: false,
+            project: None,
+            strategy: None,
+            failure: None,
+        };
+
+        let request = MentorRequest {
+            request_type: "merge_resolve_region".to_string(),
+            context: Some(context),
+            query: Some(format!(
+                "Resolve conflict region {} of {} in file '{}' (lines {}-{}, {} vs {}).",
+                index + 1,
+                regions.len(),
+                file_path,
+                region.start_line,
+                region.end_line,
+                region.current_label,
+                region.incoming_label
+            )),
+            error: None,
+        };
+
+        self.call(&request)
+    }
+
     /// Get AI recommendation for the best merge strategy.
-    pub fn suggest_merge_strategy(&self, query: Option<&str>) -> Result<String> {
-        let ctx = build_repo_context(false)?;
+    /// `target` is the branch to integrate; defaults to the in-progress merge's other side.
+    pub fn suggest_merge_strategy(
+        &self,
+        target: Option<&str>,
+        query: Option<&str>,
+    ) -> Result<String> {
+        let mut ctx = build_repo_context(
+            false,
+            &self.context_options,
+            &self.redactor,
+            self.diff_budget,
+        )?;
+        ctx.strategy = build_strategy_context(target, &ctx);
         let request = MentorRequest {
             request_type: "merge_strategy".to_string(),
             context: Some(ctx),
@@ -683,6 +1014,56 @@ impl AiClient {
         self.call(&request)
     }
 
+    /// Summarize a contributing readiness report, with an excerpt of the project's
+    /// contributing guide, into a checklist for a first PR.
+    pub fn summarize_contributing(&self, report: &str, guide: &str) -> Result<String> {
+        let guide = guide.trim();
+        // Redacted with the rest of the request in `call`
+        let guide = (!guide.is_empty()).then(|| excerpt(guide, CONTRIBUTING_EXCERPT));
+        let request = MentorRequest {
+            request_type: "contributing".to_string(),
+            context: None,
+            query: Some(report.to_string()),
+            error: guide, // reuse error field for the guide, like the .gitignore request
+        };
+        self.call(&request)
+    }
+
+    /// Polish a markdown release notes draft without changing what it lists.
+    pub fn polish_release_notes(&self, draft: &str) -> Result<String> {
+        let request = MentorRequest {
+            request_type: "release_notes".to_string(),
+            context: None,
+            query: Some(draft.to_string()),
+            error: None,
+        };
+        self.call(&request)
+    }
+
+    /// Draft a release changelog from the commits a tag adds over the one
+    /// before it.
+    pub fn draft_changelog(&self, commits: &str) -> Result<String> {
+        let request = MentorRequest {
+            request_type: "changelog".to_string(),
+            context: None,
+            query: Some(commits.to_string()),
+            error: None,
+        };
+        self.call(&request)
+    }
+
+    /// Draft a pull request title and description from the branch's commits
+    /// and diffstat. The title is the first line of the answer.
+    pub fn describe_pull_request(&self, branch: &str) -> Result<String> {
+        let request = MentorRequest {
+            request_type: "pull_request".to_string(),
+            context: None,
+            query: Some(branch.to_string()),
+            error: None,
+        };
+        self.call(&request)
+    }
+
     /// Get AI recommendation for resetting to a specific commit.
     pub fn suggest_reset(
         &self,
@@ -691,7 +1072,12 @@ impl AiClient {
         target_msg: &str,
         commits_back: usize,
     ) -> Result<String> {
-        let ctx = build_repo_context(false)?;
+        let ctx = build_repo_context(
+            false,
+            &self.context_options,
+            &self.redactor,
+            self.diff_budget,
+        )?;
         let query = format!(
             "I want to reset from current HEAD ({}) to commit {} (\"{}\"), \
              which is {} commit(s) back. \
@@ -717,7 +1103,12 @@ impl AiClient {
     /// Agent mode: send a user message with full conversation context.
     /// Returns a receiver that yields the final Result<String, String>.
     pub fn agent_chat(&self, user_message: &str) -> mpsc::Receiver<Result<String, String>> {
-        let ctx = match build_repo_context(false) {
+        let ctx = match build_repo_context(
+            false,
+            &self.context_options,
+            &self.redactor,
+            self.diff_budget,
+        ) {
             Ok(c) => c,
             Err(e) => {
                 let (tx, rx) = mpsc::channel();
@@ -725,12 +1116,12 @@ impl AiClient {
                 return rx;
             }
         };
-        let request = MentorRequest {
+        let request = self.redact_request(&MentorRequest {
             request_type: "agent".to_string(),
             context: Some(ctx),
             query: Some(user_message.to_string()),
             error: None,
-        };
+        });
 
         let endpoint = self.endpoint.clone();
         let api_key = self.api_key.clone();
@@ -743,99 +1134,86 @@ impl AiClient {
             }
         };
 
+        let mut audit_entry = self.audit_entry(&request);
         let (tx, rx) = mpsc::channel();
+        let transport = http::transport();
 
         std::thread::spawn(move || {
-            let client = match reqwest::blocking::Client::builder()
-                .timeout(Duration::from_secs(300))
-                .build()
-            {
-                Ok(c) => c,
-                Err(e) => {
-                    let _ = tx.send(Err(format!("Failed to create HTTP client: {}", e)));
-                    return;
-                }
-            };
+            let started = Instant::now();
+            let result = Self::send_agent_request(&*transport, &endpoint, &api_key, &body);
+            audit_entry.duration_ms = started.elapsed().as_millis() as u64;
+            audit_entry.error = result.as_ref().err().cloned();
+            audit::record(&audit_entry);
+            let _ = tx.send(result);
+        });
 
-            let resp = match client
-                .post(&endpoint)
-                .header("Content-Type", "application/json")
-                .header("x-api-key", &api_key)
-                .header("x-request-id", Self::request_id())
-                .json(&body)
-                .send()
-            {
-                Ok(r) => r,
-                Err(e) => {
-                    let _ = tx.send(Err(format!("Failed to send request: {}", e)));
-                    return;
-                }
-            };
+        rx
+    }
 
-            let status = resp.status();
-            if status.is_client_error() || status.is_server_error() {
-                let text = resp.text().unwrap_or_default();
-                let _ = tx.send(Err(format!(
-                    "AI service error ({}): {}",
-                    status.as_u16(),
-                    text
-                )));
-                return;
+    /// Send an agent request to the Lambda endpoint and read the full response.
+    fn send_agent_request(
+        transport: &dyn http::Transport,
+        endpoint: &str,
+        api_key: &str,
+        body: &serde_json::Value,
+    ) -> Result<String, String> {
+        let request = Request::new(Method::Post, endpoint)
+            .timeout(Duration::from_secs(300))
+            .header("x-api-key", api_key)
+            .header("x-request-id", Self::request_id())
+            .json(body);
+        let resp = match transport.send(request) {
+            Ok(r) => r,
+            Err(e) => {
+                return Err(format!("Failed to send request: {}", e));
             }
+        };
 
-            // Read response body incrementally
-            let mut full_text = String::new();
-            let mut reader = resp;
-            let mut buf = [0u8; 1024];
-
-            loop {
-                match reader.read(&mut buf) {
-                    Ok(0) => break,
-                    Ok(n) => {
-                        let chunk = String::from_utf8_lossy(&buf[..n]);
-                        full_text.push_str(&chunk);
-                    }
-                    Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
-                    Err(e) => {
-                        let _ = tx.send(Err(format!("Stream error: {}", e)));
-                        return;
-                    }
-                }
-            }
+        if resp.is_client_error() || resp.is_server_error() {
+            return Err(format!(
+                "AI service error ({}): {}",
+                resp.status,
+                resp.text()
+            ));
+        }
 
-            // Parse as JSON API response and extract content
-            if let Ok(api_resp) = serde_json::from_str::<MentorApiResponse>(&full_text) {
-                if !api_resp.success {
-                    let err_msg = api_resp
-                        .error
-                        .unwrap_or_else(|| "Unknown API error".to_string());
-                    let _ = tx.send(Err(err_msg));
-                    return;
-                }
-                if let Some(content) = api_resp.response.and_then(|r| r.content) {
-                    let _ = tx.send(Ok(content));
-                    return;
-                }
-            }
+        let full_text = resp.text();
 
-            // Fallback: return raw text
-            if !full_text.is_empty() {
-                let _ = tx.send(Ok(full_text));
-                return;
+        // Parse as JSON API response and extract content
+        if let Ok(api_resp) = serde_json::from_str::<MentorApiResponse>(&full_text) {
+            if !api_resp.success {
+                let err_msg = api_resp
+                    .error
+                    .unwrap_or_else(|| "Unknown API error".to_string());
+                return Err(err_msg);
+            }
+            if let Some(content) = api_resp.response.and_then(|r| r.content) {
+                return Ok(content);
             }
+        }
 
-            let _ = tx.send(Err("Empty response from AI service".to_string()));
-        });
+        // Fallback: return raw text
+        if !full_text.is_empty() {
+            return Ok(full_text);
+        }
 
-        rx
+        Err("Empty response from AI service".to_string())
     }
 }
 
 // ─── Helpers ───────────────────────────────────────────────────
 
 /// Build repository context from the current git state.
-fn build_repo_context(include_diff: bool) -> Result<RepoContext> {
-    let repo_path = git::run_git(&["rev-parse", "--show-toplevel"]).ok();
+/// Files excluded by `[ai.redaction]` are left out of the diff entirely.
+fn build_repo_context(
+    include_diff: bool,
+    options: &AiContextConfig,
+    redactor: &Redactor,
+    diff_budget: usize,
+) -> Result<RepoContext> {
+    let repo_path = git::run_git(&["rev-parse", "--show-toplevel"])
+        .ok()
+        .map(|p| p.trim().to_string());
     let branch = git::branch::BranchOps::current().ok();
     let detached_head = branch.as_deref() == Some("HEAD");
 
@@ -854,6 +1232,7 @@ fn build_repo_context(include_diff: bool) -> Result<RepoContext> {
 
     let mut diff_text = None;
     let mut diff_stats = None;
+    let mut omitted_files = Vec::new();
 
     if include_diff && !staged_files.is_empty() {
         // Get staged diff for commit suggestions
@@ -867,31 +1246,26 @@ fn build_repo_context(include_diff: bool) -> Result<RepoContext> {
         }
 
         if let Ok(diffs) = git::diff::get_staged_diff() {
-            let mut combined = String::new();
-            for file_diff in &diffs {
-                for hunk in &file_diff.hunks {
-                    for line in &hunk.lines {
-                        combined.push_str(&line.content);
-                        combined.push('\n');
-                        if combined.len() > DIFF_TRUNCATE_AT {
-                            combined.push_str("...(truncated)");
-                            break;
-                        }
-                    }
-                    if combined.len() > DIFF_TRUNCATE_AT {
-                        break;
+            let included: Vec<&git::diff::FileDiff> = diffs
+                .iter()
+                .filter(|f| {
+                    let excluded = redactor.is_excluded(&f.path);
+                    if excluded {
+                        log::debug!("[AI] Excluding {} from diff context", f.path);
                     }
-                }
-                if combined.len() > DIFF_TRUNCATE_AT {
-                    break;
-                }
-            }
-            if !combined.is_empty() {
-                diff_text = Some(combined);
+                    !excluded
+                })
+                .collect();
+            let fitted = budget::fit_file_diffs(&included, diff_budget);
+            if !fitted.text.is_empty() {
+                diff_text = Some(fitted.text);
             }
+            omitted_files = fitted.omitted_files;
         }
     }
 
+    let project = build_project_context(repo_path.as_deref(), options);
+
     Ok(RepoContext {
         repo_path,
         branch,
@@ -899,30 +1273,247 @@ fn build_repo_context(include_diff: bool) -> Result<RepoContext> {
         unstaged_files,
         diff_stats,
         diff: diff_text,
+        omitted_files,
         conflict_files,
         conflict_diff: None,
         has_conflicts,
         merge_type,
         detached_head,
+        project,
+        strategy: None,
+        failure: None,
     })
 }
 
+/// Compare the current branch with the branch being integrated and summarize conflicts.
+/// The other side is `target`, else the in-progress merge/cherry-pick head, else the
+/// default branch. Returns `None` if there is nothing to compare against.
+fn build_strategy_context(target: Option<&str>, ctx: &RepoContext) -> Option<StrategyContext> {
+    let ours = ctx.branch.clone().unwrap_or_else(|| "HEAD".to_string());
+    let theirs = match target {
+        Some(t) => t.to_string(),
+        None => match git::merge::get_merge_state().and_then(|s| s.merge_head) {
+            Some(head) => git::merge::ref_label(&head),
+            None => git::BranchOps::default_branch().filter(|b| *b != ours)?,
+        },
+    };
+
+    let comparison = match git::merge::compare_branches("HEAD", &theirs, STRATEGY_COMMIT_LIMIT) {
+        Ok(c) => c,
+        Err(e) => {
+            log::debug!("[AI] Cannot compare HEAD with {}: {}", theirs, e);
+            return None;
+        }
+    };
+
+    let conflicts = ctx
+        .conflict_files
+        .iter()
+        .map(|path| match git::merge::get_conflict_file(path) {
+            Ok(f) => format!("{} ({} regions)", path, f.regions.len()),
+            Err(_) => path.clone(),
+        })
+        .collect();
+
+    Some(StrategyContext {
+        ours_branch: ours,
+        theirs_branch: theirs,
+        ahead: comparison.ahead,
+        behind: comparison.behind,
+        ours_commits: comparison.ours_commits,
+        theirs_commits: comparison.theirs_commits,
+        conflicts,
+    })
+}
+
+/// The failed command from the journal plus flags describing the repository
+/// state, so an explanation can refer to what actually happened.
+fn build_failure_context(failed: Option<&CommandRecord>, ctx: &RepoContext) -> FailureContext {
+    let mut repo_state = Vec::new();
+    if ctx.detached_head {
+        repo_state.push("detached HEAD".to_string());
+    } else if let Some(ref branch) = ctx.branch {
+        repo_state.push(format!("on branch {}", branch));
+    }
+    if let Some(ref merge_type) = ctx.merge_type {
+        repo_state.push(format!("{} in progress", merge_type));
+    }
+    if !ctx.conflict_files.is_empty() {
+        repo_state.push(format!("{} conflicted file(s)", ctx.conflict_files.len()));
+    }
+    if let Ok(status) = git::status::get_status() {
+        repo_state.push(match status.upstream {
+            Some(ref upstream) => format!(
+                "{} ahead, {} behind {}",
+                status.ahead, status.behind, upstream
+            ),
+            None => "no upstream branch".to_string(),
+        });
+        repo_state.push(format!(
+            "{} staged, {} unstaged, {} untracked",
+            status.staged.len(),
+            status.unstaged.len(),
+            status.untracked.len()
+        ));
+    }
+    if git::lock::index_locked() {
+        repo_state.push("another git process holds .git/index.lock".to_string());
+    }
+    if !git::lock::other_instances().is_empty() {
+        repo_state.push("another zit instance is open on the repository".to_string());
+    }
+
+    FailureContext {
+        command: failed.map(|c| format!("git {}", c.args)),
+        exit_code: failed.and_then(|c| c.exit_code),
+        outcome: failed
+            .filter(|c| c.exit_code.is_none())
+            .map(|c| c.status.clone()),
+        stderr: failed.map(|c| c.stderr.clone()).filter(|s| !s.is_empty()),
+        repo_state,
+    }
+}
+
+/// Names of the context fields a request carries, for the audit log.
+fn describe_context(request: &MentorRequest) -> Vec<String> {
+    let mut parts = Vec::new();
+    if let Some(ref ctx) = request.context {
+        let counted = |name: &str, n: usize| format!("{}({})", name, n);
+        if ctx.repo_path.is_some() {
+            parts.push("repo_path".to_string());
+        }
+        if ctx.branch.is_some() {
+            parts.push("branch".to_string());
+        }
+        if !ctx.staged_files.is_empty() {
+            parts.push(counted("staged_files", ctx.staged_files.len()));
+        }
+        if !ctx.unstaged_files.is_empty() {
+            parts.push(counted("unstaged_files", ctx.unstaged_files.len()));
+        }
+        if ctx.diff_stats.is_some() {
+            parts.push("diff_stats".to_string());
+        }
+        if ctx.diff.is_some() {
+            parts.push("diff".to_string());
+        }
+        if !ctx.omitted_files.is_empty() {
+            parts.push(counted("omitted_files", ctx.omitted_files.len()));
+        }
+        if !ctx.conflict_files.is_empty() {
+            parts.push(counted("conflict_files", ctx.conflict_files.len()));
+        }
+        if ctx.conflict_diff.is_some() {
+            parts.push("conflict_diff".to_string());
+        }
+        if let Some(ref failure) = ctx.failure {
+            if failure.command.is_some() {
+                parts.push("failed_command".to_string());
+            }
+            parts.push(counted("repo_state", failure.repo_state.len()));
+        }
+        if let Some(ref strategy) = ctx.strategy {
+            parts.push(format!(
+                "branch_comparison({}...{})",
+                strategy.ours_branch, strategy.theirs_branch
+            ));
+        }
+        if let Some(ref project) = ctx.project {
+            if project.default_branch.is_some() {
+                parts.push("default_branch".to_string());
+            }
+            if !project.recent_commits.is_empty() {
+                parts.push(counted("recent_commits", project.recent_commits.len()));
+            }
+            if project.readme.is_some() {
+                parts.push("readme".to_string());
+            }
+            if project.contributing.is_some() {
+                parts.push("contributing".to_string());
+            }
+        }
+    }
+    if request.query.is_some() {
+        parts.push("query".to_string());
+    }
+    if request.error.is_some() {
+        parts.push("error".to_string());
+    }
+    parts
+}
+
+/// Collect the optional project context enabled in `[ai.context]`; `None` if nothing is enabled.
+fn build_project_context(
+    repo_path: Option<&str>,
+    options: &AiContextConfig,
+) -> Option<ProjectContext> {
+    if *options == AiContextConfig::default() {
+        return None;
+    }
+    let root = std::path::Path::new(repo_path.unwrap_or("."));
+
+    let mut project = ProjectContext::default();
+    if options.default_branch {
+        project.default_branch = git::BranchOps::default_branch();
+    }
+    if options.recent_commits > 0 {
+        project.recent_commits =
+            git::log::recent_subjects(options.recent_commits).unwrap_or_default();
+    }
+    if options.readme {
+        project.readme =
+            read_doc_excerpt(root, &["README.md", "README", "README.rst", "README.txt"]);
+    }
+    if options.contributing {
+        project.contributing = read_doc_excerpt(
+            root,
+            &[
+                "CONTRIBUTING.md",
+                ".github/CONTRIBUTING.md",
+                "docs/CONTRIBUTING.md",
+                "CONTRIBUTING",
+            ],
+        );
+    }
+    Some(project)
+}
+
+/// Read the first existing file among `candidates` and return its opening excerpt.
+fn read_doc_excerpt(root: &std::path::Path, candidates: &[&str]) -> Option<String> {
+    let text = candidates
+        .iter()
+        .find_map(|name| std::fs::read_to_string(root.join(name)).ok())?;
+    let text = text.trim();
+    if text.is_empty() {
+        return None;
+    }
+    Some(excerpt(text, PROJECT_DOC_EXCERPT))
+}
+
+/// First `max_chars` characters of `text`, cut at a line break when possible.
+fn excerpt(text: &str, max_chars: usize) -> String {
+    match text.char_indices().nth(max_chars) {
+        None => text.to_string(),
+        Some((end, _)) => {
+            let cut = text[..end].rfind('\n').unwrap_or(end);
+            format!("{}\n...(truncated)", text[..cut].trim_end())
+        }
+    }
+}
+
 // ─── Error Classification ──────────────────────────────────────
 
-/// Classify a reqwest transport-level error into a user-friendly message.
-fn classify_request_error(e: reqwest::Error) -> anyhow::Error {
-    if e.is_timeout() {
-        anyhow::anyhow!(
+/// Classify a transport-level error into a user-friendly message.
+fn classify_request_error(e: http::Error) -> anyhow::Error {
+    match e {
+        http::Error::Timeout => anyhow::anyhow!(
             "Request timed out — the AI service may be under load. Try again in a moment."
-        )
-    } else if e.is_connect() {
-        anyhow::anyhow!(
+        ),
+        http::Error::Connect(_) => anyhow::anyhow!(
             "Cannot connect to AI service — check your internet connection and endpoint URL"
-        )
-    } else if e.is_decode() {
-        anyhow::anyhow!("Received invalid response from AI service")
-    } else {
-        anyhow::anyhow!("Network error: {}", e)
+        ),
+        http::Error::Body(_) => anyhow::anyhow!("Received invalid response from AI service"),
+        http::Error::Other(e) => anyhow::anyhow!("Network error: {}", e),
     }
 }
 
@@ -977,6 +1568,20 @@ fn parse_stat_line(stat: &str) -> (usize, usize, usize) {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_excerpt_cuts_at_line_break() {
+        assert_eq!(excerpt("short", 10), "short");
+        let text = "line one\nline two\nline three";
+        assert_eq!(excerpt(text, 12), "line one\n...(truncated)");
+        // Multi-byte text is cut on a char boundary
+        assert_eq!(excerpt("日本語テキスト", 3), "日本語\n...(truncated)");
+    }
+
+    #[test]
+    fn test_project_context_disabled_by_default() {
+        assert!(build_project_context(None, &AiContextConfig::default()).is_none());
+    }
+
     #[test]
     fn test_parse_stat_line_full() {
         let stat = " 3 files changed, 45 insertions(+), 12 deletions(-)";
@@ -1010,42 +1615,23 @@ mod tests {
             context: Some(RepoContext {
                 repo_path: None,
                 branch: Some("main".to_string()),
-                staged_files: vec![],
+                staged_files: vec!["src/main.rs".to_string()],
                 unstaged_files: vec![],
                 diff_stats: None,
                 diff: None,
+                omitted_files: vec![],
                 conflict_files: vec![],
                 conflict_diff: None,
                 has_conflicts: false,
                 merge_type: None,
                 detached_head: false,
+                project: None,
+                strategy: None,
+                failure: None,
             }),
             query: None,
             error: None,
         };
-        let r2 = MentorRequest {
-            request_type: "explain".to_string(),
-            context: Some(RepoContext {
-                repo_path: None,
-                branch: Some("main".to_string()),
-                staged_files: vec!["src/main.rs".to_string()],
-                unstaged_files: vec![],
-                diff_stats: Some(DiffStats {
-                    files_changed: 1,
-                    insertions: 10,
-                    deletions: 2,
-                }),
-                diff: Some("+fn hello() {}".to_string()),
-                conflict_files: vec![],
-                conflict_diff: None,
-                has_conflicts: false,
-                merge_type: None,
-                detached_head: false,
-            }),
-            query: None,
-            error: None,
-        };
-
         let json = serde_json::to_string(&req).unwrap();
         assert!(json.contains("commit_suggestion"));
         assert!(json.contains("main"));
@@ -1054,6 +1640,25 @@ mod tests {
         assert!(!json.contains("error"));
     }
 
+    #[test]
+    fn test_refinement_query_includes_previous_and_instruction() {
+        let q = refinement_query(
+            "  feat: add login\n",
+            &CommitRefinement::Custom("mention the config change ".to_string()),
+        );
+        assert!(q.starts_with("Previous suggestion:\nfeat: add login\n\n"));
+        assert!(
+            q.contains("Instruction: Revise the previous suggestion: mention the config change\n")
+        );
+
+        let shorter = refinement_query("feat: add login", &CommitRefinement::Shorter);
+        assert!(shorter.contains("shorter"));
+        assert_ne!(
+            refinement_query("x", &CommitRefinement::Retry),
+            refinement_query("x", &CommitRefinement::Longer)
+        );
+    }
+
     // ── cache_key tests ──────────────────────────────────────────
 
     #[test]
@@ -1091,16 +1696,21 @@ mod tests {
         let r1 = MentorRequest {
             request_type: "explain".to_string(),
             context: Some(RepoContext {
+                repo_path: None,
                 branch: Some("main".to_string()),
                 staged_files: vec![],
                 unstaged_files: vec![],
                 diff_stats: None,
                 diff: None,
+                omitted_files: vec![],
                 conflict_files: vec![],
                 conflict_diff: None,
                 has_conflicts: false,
                 merge_type: None,
                 detached_head: false,
+                project: None,
+                strategy: None,
+                failure: None,
             }),
             query: None,
             error: None,
@@ -1108,16 +1718,21 @@ mod tests {
         let r2 = MentorRequest {
             request_type: "explain".to_string(),
             context: Some(RepoContext {
+                repo_path: None,
                 branch: Some("feature".to_string()),
                 staged_files: vec![],
                 unstaged_files: vec![],
                 diff_stats: None,
                 diff: None,
+                omitted_files: vec![],
                 conflict_files: vec![],
                 conflict_diff: None,
                 has_conflicts: false,
                 merge_type: None,
                 detached_head: false,
+                project: None,
+                strategy: None,
+                failure: None,
             }),
             query: None,
             error: None,
@@ -1125,6 +1740,23 @@ mod tests {
         assert_ne!(cache_key(&r1), cache_key(&r2));
     }
 
+    #[test]
+    fn test_cache_key_differs_on_conflict_content() {
+        let request = |region: &str| MentorRequest {
+            request_type: "merge_resolve_region".to_string(),
+            context: Some(RepoContext {
+                conflict_diff: Some(region.to_string()),
+                ..Default::default()
+            }),
+            query: Some("Resolve conflict region 1 of 1".to_string()),
+            error: None,
+        };
+        assert_ne!(
+            cache_key(&request("<<<<<<< HEAD\na")),
+            cache_key(&request("<<<<<<< HEAD\nb"))
+        );
+    }
+
     // ── Cache get/set tests ──────────────────────────────────────
 
     fn make_test_client() -> AiClient {
@@ -1136,6 +1768,9 @@ mod tests {
             endpoint: Some("https://example.com/mentor".to_string()),
             api_key: Some("test-key-12345".to_string()),
             timeout_secs: Some(30),
+            context: Default::default(),
+            redaction: Default::default(),
+            diff_budget: None,
         };
         AiClient::from_config(&config).expect("test client should build")
     }
@@ -1167,6 +1802,106 @@ mod tests {
         assert!(cache.contains_key("overflow"));
     }
 
+    // ── Redaction ────────────────────────────────────────────────
+
+    #[test]
+    fn test_redact_request_masks_diff_and_counts() {
+        let client = make_test_client();
+        let key = format!("{}{}", "AKIA", "IOSFODNN7EXAMPLE");
+        let request = MentorRequest {
+            request_type: "review".to_string(),
+            context: Some(RepoContext {
+                repo_path: None,
+                branch: None,
+                staged_files: vec![],
+                unstaged_files: vec![],
+                diff_stats: None,
+                diff: Some(format!("+aws_key = \"{}\"\n+unchanged", key)),
+                omitted_files: vec![],
+                conflict_files: vec![],
+                conflict_diff: None,
+                has_conflicts: false,
+                merge_type: None,
+                detached_head: false,
+                project: None,
+                strategy: None,
+                failure: None,
+            }),
+            query: Some("Review this".to_string()),
+            error: None,
+        };
+        let redacted = client.redact_request(&request);
+        let diff = redacted.context.unwrap().diff.unwrap();
+        assert!(!diff.contains(&key));
+        assert!(diff.contains("+unchanged"));
+        assert_eq!(redacted.query.as_deref(), Some("Review this"));
+        assert_eq!(client.redacted_lines(), 1);
+
+        let mut with_project = MentorRequest {
+            request_type: "review".to_string(),
+            context: request.context.clone(),
+            query: None,
+            error: None,
+        };
+        with_project.context.as_mut().unwrap().project = Some(ProjectContext {
+            default_branch: Some("main".to_string()),
+            recent_commits: vec![format!("Rotate {}", key), "Fix typo".to_string()],
+            readme: Some(format!("# App\nAWS key: {}\n", key)),
+            contributing: Some(format!("Use {} for CI", key)),
+        });
+        let project = client
+            .redact_request(&with_project)
+            .context
+            .unwrap()
+            .project
+            .unwrap();
+        assert!(!project.readme.unwrap().contains(&key));
+        assert!(!project.contributing.unwrap().contains(&key));
+        assert!(!project.recent_commits[0].contains(&key));
+        assert_eq!(project.recent_commits[1], "Fix typo");
+        assert_eq!(client.redacted_lines(), 4);
+    }
+
+    #[test]
+    fn test_describe_context_lists_included_fields() {
+        let request = MentorRequest {
+            request_type: "commit_suggestion".to_string(),
+            context: Some(RepoContext {
+                repo_path: None,
+                branch: Some("main".to_string()),
+                staged_files: vec!["a.rs".to_string(), "b.rs".to_string()],
+                unstaged_files: vec![],
+                diff_stats: None,
+                diff: Some("+x".to_string()),
+                omitted_files: vec![],
+                conflict_files: vec![],
+                conflict_diff: None,
+                has_conflicts: false,
+                merge_type: None,
+                detached_head: false,
+                project: Some(ProjectContext {
+                    recent_commits: vec!["feat: x".to_string()],
+                    ..ProjectContext::default()
+                }),
+                strategy: None,
+                failure: None,
+            }),
+            query: None,
+            error: None,
+        };
+        assert_eq!(
+            describe_context(&request),
+            vec!["branch", "staged_files(2)", "diff", "recent_commits(1)"]
+        );
+    }
+
+    #[test]
+    fn test_review_diff_refuses_excluded_file() {
+        let client = make_test_client();
+        let err = client.review_diff(".env", "+SECRET=1").unwrap_err();
+        assert!(err.to_string().contains("excluded"));
+    }
+
     // ── classify_http_error tests ────────────────────────────────
 
     #[test]
@@ -1205,6 +1940,68 @@ mod tests {
         assert!(msg.contains("418"));
     }
 
+    #[test]
+    fn test_bedrock_errors_are_classified() {
+        use crate::http::mock::MockTransport;
+
+        let request = MentorRequest {
+            request_type: "explain".to_string(),
+            context: None,
+            query: Some("what is HEAD?".to_string()),
+            error: None,
+        };
+        let ok = Response::new(
+            200,
+            r#"{"success":true,"response":{"content":"The current commit."}}"#,
+        );
+        let mock = Arc::new(
+            MockTransport::new()
+                .on(
+                    Method::Post,
+                    "/mentor",
+                    Response::new(401, r#"{"success":false,"error":"Unknown API key"}"#),
+                )
+                .on(Method::Post, "/mentor", Response::new(429, ""))
+                .on(Method::Post, "/mentor", Response::new(503, ""))
+                .on(Method::Post, "/mentor", ok),
+        );
+        let client = make_test_client();
+        let results: Vec<String> = mock.serve(|| {
+            (0..3)
+                .map(|_| match client.call_bedrock(&request) {
+                    Ok(text) => text,
+                    Err(e) => e.to_string(),
+                })
+                .collect()
+        });
+        assert_eq!(results[0], "Unknown API key");
+        assert_eq!(results[1], classify_http_error(429));
+        // The 503 is retried
+        assert_eq!(results[2], "The current commit.");
+        let sent = &mock.requests()[0];
+        assert_eq!(sent.header_value("x-api-key"), Some("test-key-12345"));
+        assert!(sent.body_text().contains("what is HEAD?"));
+
+        let offline = Arc::new(MockTransport::new().fail(
+            Method::Post,
+            "/mentor",
+            http::Error::Connect("dns error".to_string()),
+        ));
+        let err = offline.serve(|| client.call_bedrock(&request)).unwrap_err();
+        assert!(err.to_string().contains("offline"));
+        assert_eq!(offline.requests().len(), 1);
+
+        assert!(
+            classify_request_error(http::Error::Timeout)
+                .to_string()
+                .starts_with("Request timed out")
+        );
+        assert_eq!(
+            classify_request_error(http::Error::Body("eof".to_string())).to_string(),
+            "Received invalid response from AI service"
+        );
+    }
+
     // ── parse_success_response tests ─────────────────────────────
 
     #[test]
@@ -1256,9 +2053,11 @@ mod tests {
         assert!(AiClient::from_config(&cfg).is_some());
         // But validate() should flag the bad scheme
         let issues = cfg.validate();
-        assert!(issues
-            .iter()
-            .any(|i| i.contains("must start with https://")));
+        assert!(
+            issues
+                .iter()
+                .any(|i| i.contains("must start with https://"))
+        );
     }
 
     // ── is_configured tests ──────────────────────────────────────
@@ -1,6 +1,13 @@
+pub mod audit;
+pub mod budget;
 pub mod client;
+pub mod next_steps;
 pub mod prompts;
 pub mod provider;
+pub mod redact;
+pub mod setup;
+pub mod snippets;
+pub mod strategy;
 
-/// Maximum diff content included in AI context (chars). Truncated beyond this.
+/// Default diff budget for AI context (chars); override with `[ai] diff_budget`.
 pub const DIFF_TRUNCATE_AT: usize = 4000;
@@ -0,0 +1,148 @@
+//! Next steps suggested by an AI answer: views it points at, git commands it
+//! recommends and git concepts worth learning about, offered as follow-ups
+//! after explanations, recommendations and error help.
+
+use super::snippets::{self, Snippet};
+use crate::app::View;
+
+/// Most of each kind offered, so the list stays short.
+const MAX_VIEWS: usize = 3;
+const MAX_COMMANDS: usize = 3;
+const MAX_TOPICS: usize = 2;
+
+/// Phrases that point at a view, checked against the lowercased answer.
+const VIEW_PHRASES: &[(&str, View)] = &[
+    ("conflict", View::MergeResolve),
+    ("unstage", View::Staging),
+    ("stage ", View::Staging),
+    ("staging", View::Staging),
+    ("stash", View::Stash),
+    ("branch", View::Branches),
+    ("commit message", View::Commit),
+    ("reflog", View::Reflog),
+    ("bisect", View::Bisect),
+    ("cherry-pick", View::CherryPick),
+    ("interactive rebase", View::Timeline),
+    ("history", View::Timeline),
+    ("reset", View::TimeTravel),
+    ("pull request", View::GitHub),
+    ("unpushed", View::Outgoing),
+    ("git tag", View::Tags),
+];
+
+/// Concepts the Learn prompt can teach: (phrase in the answer, topic).
+const TOPICS: &[(&str, &str)] = &[
+    ("detached head", "detached HEAD"),
+    ("fast-forward", "fast-forward merges"),
+    ("rebas", "rebasing"),
+    ("cherry-pick", "cherry-picking"),
+    ("reflog", "the reflog"),
+    ("upstream", "upstream branches"),
+    ("force-push", "force-pushing safely"),
+    ("force push", "force-pushing safely"),
+    ("merge conflict", "merge conflicts"),
+    ("squash", "squashing commits"),
+    ("stash", "stashing"),
+    ("bisect", "git bisect"),
+    ("submodule", "submodules"),
+    ("hunk", "staging hunks"),
+    ("tag", "tags"),
+];
+
+/// Something to do after reading an answer.
+#[derive(Debug, Clone, PartialEq)]
+pub enum NextStep {
+    Open(View),
+    Run(Snippet),
+    Learn(&'static str),
+}
+
+/// Next steps for `response`: runnable commands first, then views, then
+/// topics, each in the order the answer mentions them.
+pub fn derive(response: &str) -> Vec<NextStep> {
+    let lower = response.to_lowercase();
+    let mut steps: Vec<NextStep> = snippets::extract(response)
+        .into_iter()
+        .filter(|s| s.commands.is_some())
+        .take(MAX_COMMANDS)
+        .map(NextStep::Run)
+        .collect();
+
+    let mut views: Vec<(usize, View)> = Vec::new();
+    for (phrase, view) in VIEW_PHRASES {
+        if let Some(at) = find_word(&lower, phrase) {
+            match views.iter_mut().find(|(_, v)| v == view) {
+                Some(found) => found.0 = found.0.min(at),
+                None => views.push((at, *view)),
+            }
+        }
+    }
+    views.sort_by_key(|(at, _)| *at);
+    steps.extend(
+        views
+            .into_iter()
+            .take(MAX_VIEWS)
+            .map(|(_, v)| NextStep::Open(v)),
+    );
+
+    let mut topics: Vec<(usize, &'static str)> = Vec::new();
+    for (phrase, topic) in TOPICS {
+        if let Some(at) = find_word(&lower, phrase)
+            && !topics.iter().any(|(_, t)| t == topic)
+        {
+            topics.push((at, topic));
+        }
+    }
+    topics.sort_by_key(|(at, _)| *at);
+    steps.extend(
+        topics
+            .into_iter()
+            .take(MAX_TOPICS)
+            .map(|(_, t)| NextStep::Learn(t)),
+    );
+    steps
+}
+
+/// Where `phrase` first starts a word in `text`.
+fn find_word(text: &str, phrase: &str) -> Option<usize> {
+    text.match_indices(phrase).map(|(at, _)| at).find(|&at| {
+        !text[..at]
+            .chars()
+            .next_back()
+            .is_some_and(|c| c.is_alphanumeric())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_derive_orders_commands_views_and_topics() {
+        let response = "You're in a detached HEAD state. Create a branch to keep \
+                        your work:\n\n```sh\ngit switch -c rescue\n```\n\nThen check \
+                        the reflog if anything looks lost. Avoid `git rebase -i` here.";
+        let steps = derive(response);
+        assert_eq!(steps.len(), 5);
+        assert!(matches!(&steps[0], NextStep::Run(s) if s.code == "git switch -c rescue"));
+        assert_eq!(
+            steps[1..],
+            [
+                NextStep::Open(View::Branches),
+                NextStep::Open(View::Reflog),
+                NextStep::Learn("detached HEAD"),
+                NextStep::Learn("the reflog"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_phrases_match_word_starts_only() {
+        // "restage" and "stagnant" shouldn't point at Staging; "attach" isn't a tag
+        assert_eq!(derive("The restaged files are stagnant; attach them."), []);
+        assert_eq!(
+            derive("Stage the file, then tag it."),
+            [NextStep::Open(View::Staging), NextStep::Learn("tags")]
+        );
+    }
+}
@@ -3,7 +3,6 @@
 //! These are used by direct providers (OpenAI, Anthropic, OpenRouter, Ollama).
 //! The Bedrock provider sends raw requests to Lambda, which has its own copy.
 
-use super::DIFF_TRUNCATE_AT;
 use crate::ai::client::RepoContext;
 
 // ─── System Prompts ────────────────────────────────────────────
@@ -143,11 +142,32 @@ FOLLOW-UP:
 
 Keep responses under 400 words."#;
 
+pub const PROMPT_MERGE_RESOLVE_REGION: &str = r#"You are an expert Git merge conflict resolver. You are given ONE conflict region from a file, with a few unchanged lines around it for context.
+
+Your role:
+- Resolve only this region; do not rewrite the surrounding lines
+- Provide a clear recommendation: ACCEPT_CURRENT, ACCEPT_INCOMING, or MERGE_BOTH
+- Always provide the exact lines that replace the region
+- Warn briefly if the resolution may break code outside the region
+
+Format your response EXACTLY as:
+RECOMMENDATION: <ACCEPT_CURRENT|ACCEPT_INCOMING|MERGE_BOTH>
+
+EXPLANATION:
+<1-2 sentences explaining the reasoning>
+
+RESOLVED CONTENT:
+```
+<only the lines that replace the region from <<<<<<< to >>>>>>>, without conflict markers or context lines>
+```
+
+Keep responses under 200 words."#;
+
 pub const PROMPT_MERGE_STRATEGY: &str = r#"You are a cautious Git merge/rebase advisor helping developers choose the safest integration strategy.
 
 Your role:
-- Analyze the branch topology (ahead/behind counts, conflict potential)
-- Recommend the safest merge strategy: MERGE_NO_FF, REBASE, FAST_FORWARD, or MERGE_SQUASH
+- Analyze the branch topology (ahead/behind counts, commits unique to each side, conflicts)
+- Recommend the safest strategy to integrate the other branch: MERGE_NO_FF, REBASE, FAST_FORWARD, or MERGE_SQUASH
 - Label each option with safety level: ✅ SAFE / ⚠️ CAUTION / 🔴 RISKY
 - Consider: shared branches (never rebase), conflict count, commit history cleanliness
 - Provide the exact git commands to execute
@@ -161,7 +181,7 @@ WHY:
 
 COMMANDS:
 ```
-<exact git commands to run, one per line>
+<exact git commands in the order they must be run, one per line>
 ```
 
 ALTERNATIVES:
@@ -175,6 +195,10 @@ FOLLOW-UP:
 - <what to do after the merge>
 - <how to verify everything is correct>
 
+COMMANDS are offered to the user as runnable steps, so: only `git` commands, real branch names
+(no placeholders), and nothing interactive (always pass -m to commit, never use rebase -i).
+If a merge is already in progress, start from that state (e.g. finish or abort it first).
+
 Keep responses under 300 words."#;
 
 pub const PROMPT_GITIGNORE: &str = r#"You are an expert developer helping create the perfect .gitignore file for a project.
@@ -198,6 +222,68 @@ Use comments (lines starting with #) to organize sections, for example:
 
 Keep the output clean and production-ready."#;
 
+pub const PROMPT_CONTRIBUTING: &str = r#"You are an open-source mentor preparing a developer for their first pull request to a project.
+
+You receive a readiness report (contributing guide, commit convention, DCO sign-off, CLA) detected from the repository, and the project's contributing guide when it has one.
+
+Your role:
+- Summarize what the project expects from a contribution, most important first
+- Turn each requirement into a concrete step (e.g. `git commit -s` for sign-off, the exact commit subject format)
+- Point out anything in the guide the report missed: branch naming, tests to run, changelog entries, issue-first policies
+- Say plainly when the project asks for nothing special
+
+Format:
+CHECKLIST:
+- <step before opening the PR>
+
+NOTES:
+- <anything else worth knowing>
+
+Keep responses under 250 words. Do not invent requirements the guide and report do not support."#;
+
+pub const PROMPT_RELEASE_NOTES: &str = r#"You are a release manager polishing draft release notes for a GitHub release.
+
+You receive a markdown draft listing merged pull requests grouped into sections.
+
+Your role:
+- Open with a 1-3 sentence summary of the release's highlights
+- Rewrite pull request titles into clear, user-facing sentences in the same tense
+- Keep every entry, its section, its @author and its #number; never add or drop entries
+- Keep the "Full Changelog" link unchanged when present
+
+Output ONLY the markdown release notes — no code fences, no commentary before or after."#;
+
+pub const PROMPT_CHANGELOG: &str = r#"You are a release manager writing the changelog for a GitHub release.
+
+You receive the tag being released, the tag before it, and the commit subjects in between.
+
+Your role:
+- Open with a 1-3 sentence summary of the release's highlights
+- Group the changes under short headings such as Features, Bug Fixes and Other Changes, leaving out empty ones
+- Rewrite commit subjects into clear, user-facing sentences, merging commits that belong together
+- Leave out commits that don't matter to users: version bumps, formatting, CI tweaks
+- Only describe what the commits show; do not invent features or issue numbers
+
+Output ONLY the markdown changelog — no code fences, no commentary before or after."#;
+
+pub const PROMPT_PULL_REQUEST: &str = r#"You are a senior developer writing a GitHub pull request for a branch.
+
+You receive the commits the branch adds over its base and a summary of the files changed.
+
+Your role:
+- Write a title under 72 characters in the imperative mood, saying what the change does
+- Open the description with 1-3 sentences on what changed and why
+- Follow with a short bullet list of the notable changes, grouped where commits belong together
+- Mention anything a reviewer should check closely: migrations, config changes, breaking behaviour
+- Only describe what the commits and files show; do not invent tests, issues or motivation
+
+Format:
+<title on the first line>
+
+<markdown description>
+
+Output nothing before the title and no code fences."#;
+
 pub const PROMPT_AGENT: &str = r#"You are a Git operations agent inside the 'zit' terminal tool. The user describes what they want to do in plain English, and you figure out the git commands to make it happen.
 
 Rules:
@@ -230,8 +316,13 @@ pub fn system_prompt_for(request_type: &str) -> &'static str {
         "learn" => PROMPT_LEARN,
         "review" => PROMPT_REVIEW,
         "merge_resolve" => PROMPT_MERGE_RESOLVE,
+        "merge_resolve_region" => PROMPT_MERGE_RESOLVE_REGION,
         "merge_strategy" => PROMPT_MERGE_STRATEGY,
         "generate_gitignore" => PROMPT_GITIGNORE,
+        "contributing" => PROMPT_CONTRIBUTING,
+        "release_notes" => PROMPT_RELEASE_NOTES,
+        "changelog" => PROMPT_CHANGELOG,
+        "pull_request" => PROMPT_PULL_REQUEST,
         "agent" => PROMPT_AGENT,
         _ => PROMPT_EXPLAIN,
     }
@@ -286,8 +377,7 @@ pub fn format_context(ctx: &RepoContext) -> String {
         ));
     }
     if let Some(ref cd) = ctx.conflict_diff {
-        let trimmed: String = cd.chars().take(DIFF_TRUNCATE_AT).collect();
-        lines.push(format!("Conflict Content:\n{}", trimmed));
+        lines.push(format!("Conflict Content:\n{}", cd));
     }
     if let Some(ref mt) = ctx.merge_type {
         lines.push(format!("Merge Type: {}", mt));
@@ -296,8 +386,16 @@ pub fn format_context(ctx: &RepoContext) -> String {
         lines.push("⚠️ DETACHED HEAD STATE".to_string());
     }
     if let Some(ref d) = ctx.diff {
-        let trimmed: String = d.chars().take(DIFF_TRUNCATE_AT).collect();
-        lines.push(format!("Diff:\n{}", trimmed));
+        lines.push(format!("Diff:\n{}", d));
+    }
+    if let Some(omitted) = format_omitted_files(ctx) {
+        lines.push(omitted);
+    }
+    if let Some(strategy) = format_strategy_context(ctx) {
+        lines.push(strategy);
+    }
+    if let Some(project) = format_project_context(ctx) {
+        lines.push(project);
     }
 
     if lines.is_empty() {
@@ -307,6 +405,96 @@ pub fn format_context(ctx: &RepoContext) -> String {
     }
 }
 
+/// Format the branch comparison used by the merge-strategy advisor, if any.
+pub fn format_strategy_context(ctx: &RepoContext) -> Option<String> {
+    let s = ctx.strategy.as_ref()?;
+    let mut lines = vec![format!(
+        "Integrating '{}' into '{}': '{}' is {} commit(s) ahead and {} behind",
+        s.theirs_branch, s.ours_branch, s.ours_branch, s.ahead, s.behind
+    )];
+    for (branch, commits) in [
+        (&s.ours_branch, &s.ours_commits),
+        (&s.theirs_branch, &s.theirs_commits),
+    ] {
+        if !commits.is_empty() {
+            lines.push(format!("Recent commits only on '{}':", branch));
+            lines.extend(commits.iter().map(|c| format!("- {}", c)));
+        }
+    }
+    if !s.conflicts.is_empty() {
+        lines.push("Conflict Summary:".to_string());
+        lines.extend(s.conflicts.iter().map(|c| format!("- {}", c)));
+    }
+    Some(lines.join("\n"))
+}
+
+/// Format the failed command and repository state behind an error, if known.
+pub fn format_failure_context(ctx: &RepoContext) -> Option<String> {
+    let failure = ctx.failure.as_ref()?;
+    let mut lines: Vec<String> = Vec::new();
+
+    if let Some(ref command) = failure.command {
+        lines.push(format!("Failed Command: {}", command));
+    }
+    if let Some(code) = failure.exit_code {
+        lines.push(format!("Exit Code: {}", code));
+    } else if let Some(ref outcome) = failure.outcome {
+        lines.push(format!("Outcome: {}", outcome));
+    }
+    if let Some(ref stderr) = failure.stderr {
+        lines.push(format!("Stderr:\n{}", stderr));
+    }
+    if !failure.repo_state.is_empty() {
+        lines.push("Repository State:".to_string());
+        lines.extend(failure.repo_state.iter().map(|s| format!("- {}", s)));
+    }
+
+    if lines.is_empty() {
+        None
+    } else {
+        Some(lines.join("\n"))
+    }
+}
+
+/// Note which files were left out of the diff to fit the budget, if any.
+pub fn format_omitted_files(ctx: &RepoContext) -> Option<String> {
+    if ctx.omitted_files.is_empty() {
+        return None;
+    }
+    Some(format!(
+        "Not Shown in Diff (over size budget; see statistics): {}",
+        ctx.omitted_files.join(", ")
+    ))
+}
+
+/// Format the optional project context (default branch, recent commits, docs), if any.
+pub fn format_project_context(ctx: &RepoContext) -> Option<String> {
+    let project = ctx.project.as_ref()?;
+    let mut lines: Vec<String> = Vec::new();
+
+    if let Some(ref b) = project.default_branch {
+        lines.push(format!("Default Branch: {}", b));
+    }
+    if !project.recent_commits.is_empty() {
+        lines.push("Recent Commit Subjects (match this style):".to_string());
+        for subject in &project.recent_commits {
+            lines.push(format!("- {}", subject));
+        }
+    }
+    if let Some(ref c) = project.contributing {
+        lines.push(format!("Contributing Guidelines (excerpt):\n{}", c));
+    }
+    if let Some(ref r) = project.readme {
+        lines.push(format!("README (excerpt):\n{}", r));
+    }
+
+    if lines.is_empty() {
+        None
+    } else {
+        Some(lines.join("\n"))
+    }
+}
+
 /// Build a complete user message for a given request type.
 pub fn build_user_message(
     request_type: &str,
@@ -319,9 +507,12 @@ pub fn build_user_message(
     match request_type {
         "error" => {
             let err = error.unwrap_or("Unknown error");
+            let failure = format_failure_context(ctx)
+                .map(|f| format!("\n\n{}", f))
+                .unwrap_or_default();
             format!(
-                "Git Error Message:\n{}\n\nRepository Context:\n{}\n\nPlease explain this error and suggest how to fix it.",
-                err, context_str
+                "Git Error Message:\n{}{}\n\nRepository Context:\n{}\n\nPlease explain this error and suggest how to fix it, referring to the command and state above.",
+                err, failure, context_str
             )
         }
         "commit_suggestion" | "commit" => {
@@ -333,20 +524,28 @@ pub fn build_user_message(
             let diff_preview = ctx
                 .diff
                 .as_ref()
-                .map(|d| {
-                    let trimmed: String = d.chars().take(DIFF_TRUNCATE_AT).collect();
-                    format!("Diff Preview: {}...", trimmed)
-                })
+                .map(|d| format!("Diff Preview:\n{}", d))
+                .unwrap_or_default();
+            let omitted = format_omitted_files(ctx)
+                .map(|o| format!("\n{}", o))
                 .unwrap_or_default();
 
             let ds = ctx.diff_stats.as_ref();
+            let project = format_project_context(ctx)
+                .map(|p| format!("\n\nProject Conventions:\n{}", p))
+                .unwrap_or_default();
+            // A refinement request carries the previous suggestion and the instruction
+            let ask = query.unwrap_or("Suggest a concise, conventional commit message.");
             format!(
-                "Staged Files: {}\nDiff Statistics:\n- Files changed: {}\n- Insertions: {}\n- Deletions: {}\n{}\n\nSuggest a concise, conventional commit message.",
+                "Staged Files: {}\nDiff Statistics:\n- Files changed: {}\n- Insertions: {}\n- Deletions: {}\n{}{}{}\n\n{}",
                 staged,
                 ds.map_or(ctx.staged_files.len(), |s| s.files_changed),
                 ds.map_or(0, |s| s.insertions),
                 ds.map_or(0, |s| s.deletions),
-                diff_preview
+                diff_preview,
+                omitted,
+                project,
+                ask
             )
         }
         "learn" => {
@@ -358,7 +557,6 @@ pub fn build_user_message(
         }
         "review" => {
             let diff = ctx.diff.as_deref().unwrap_or("No diff provided");
-            let trimmed: String = diff.chars().take(DIFF_TRUNCATE_AT).collect();
             let staged = if ctx.staged_files.is_empty() {
                 "Unknown".to_string()
             } else {
@@ -369,7 +567,7 @@ pub fn build_user_message(
                 .unwrap_or_else(|| "Review this diff for issues and improvements.".to_string());
             format!(
                 "Repository Context:\n{}\n\nFiles Under Review: {}\n\nDiff Content:\n{}\n\n{}",
-                context_str, staged, trimmed, notes
+                context_str, staged, diff, notes
             )
         }
         "merge_resolve" => {
@@ -377,7 +575,6 @@ pub fn build_user_message(
                 .conflict_diff
                 .as_deref()
                 .unwrap_or("No conflict content provided");
-            let trimmed: String = conflict_diff.chars().take(DIFF_TRUNCATE_AT).collect();
             let conflict_files = if ctx.conflict_files.is_empty() {
                 "Unknown".to_string()
             } else {
@@ -390,7 +587,18 @@ pub fn build_user_message(
                 });
             format!(
                 "Repository Context:\n{}\n\nConflicted Files: {}\n\nConflict Content (with markers):\n{}\n\n{}",
-                context_str, conflict_files, trimmed, notes
+                context_str, conflict_files, conflict_diff, notes
+            )
+        }
+        "merge_resolve_region" => {
+            let region = ctx
+                .conflict_diff
+                .as_deref()
+                .unwrap_or("No conflict content provided");
+            let notes = query.unwrap_or("Resolve this conflict region.");
+            format!(
+                "Repository Context:\n{}\n\n{}\n\nConflict Region (with surrounding lines):\n{}",
+                context_str, notes, region
             )
         }
         "agent" => {
@@ -423,6 +631,34 @@ pub fn build_user_message(
                 file_listing, existing
             )
         }
+        "release_notes" => {
+            let draft = query.unwrap_or("No draft provided.");
+            format!(
+                "Release Notes Draft:\n{}\n\nPolish these release notes.",
+                draft
+            )
+        }
+        "changelog" => {
+            let commits = query.unwrap_or("No commits provided.");
+            format!("{}\n\nWrite the changelog for this release.", commits)
+        }
+        "pull_request" => {
+            let branch = query.unwrap_or("No commits provided.");
+            format!(
+                "{}\n\nWrite the title and description for this pull request.",
+                branch
+            )
+        }
+        "contributing" => {
+            let report = query.unwrap_or("No readiness report available.");
+            let guide = error
+                .map(|g| format!("\n\nContributing Guide:\n{}", g))
+                .unwrap_or_default();
+            format!(
+                "Readiness Report:\n{}{}\n\nSummarize what I need to do before opening my first PR.",
+                report, guide
+            )
+        }
         _ => {
             // explain, recommend, ask_question
             let q = query.unwrap_or("Explain the current repository state.");
@@ -450,7 +686,12 @@ mod tests {
             "learn",
             "review",
             "merge_resolve",
+            "merge_resolve_region",
             "merge_strategy",
+            "contributing",
+            "release_notes",
+            "changelog",
+            "pull_request",
         ];
         for t in &types {
             let prompt = system_prompt_for(t);
@@ -466,16 +707,21 @@ mod tests {
     #[test]
     fn test_format_context_empty() {
         let ctx = RepoContext {
+            repo_path: None,
             branch: None,
             staged_files: vec![],
             unstaged_files: vec![],
             diff_stats: None,
             diff: None,
+            omitted_files: vec![],
             conflict_files: vec![],
             conflict_diff: None,
             has_conflicts: false,
             merge_type: None,
             detached_head: false,
+            project: None,
+            strategy: None,
+            failure: None,
         };
         assert_eq!(format_context(&ctx), "No context provided");
     }
@@ -483,16 +729,21 @@ mod tests {
     #[test]
     fn test_format_context_with_conflicts() {
         let ctx = RepoContext {
+            repo_path: None,
             branch: Some("main".to_string()),
             staged_files: vec![],
             unstaged_files: vec![],
             diff_stats: None,
             diff: None,
+            omitted_files: vec![],
             conflict_files: vec!["app.py".to_string()],
             conflict_diff: Some("<<<<<<< HEAD\nours\n=======\ntheirs\n>>>>>>>".to_string()),
             has_conflicts: true,
             merge_type: Some("merge".to_string()),
             detached_head: false,
+            project: None,
+            strategy: None,
+            failure: None,
         };
         let out = format_context(&ctx);
         assert!(out.contains("MERGE CONFLICTS"));
@@ -502,19 +753,162 @@ mod tests {
     #[test]
     fn test_build_user_message_commit() {
         let ctx = RepoContext {
+            repo_path: None,
             branch: Some("main".to_string()),
             staged_files: vec!["src/main.rs".to_string()],
             unstaged_files: vec![],
             diff_stats: None,
             diff: Some("+new line".to_string()),
+            omitted_files: vec![],
             conflict_files: vec![],
             conflict_diff: None,
             has_conflicts: false,
             merge_type: None,
             detached_head: false,
+            project: None,
+            strategy: None,
+            failure: None,
         };
         let msg = build_user_message("commit_suggestion", &ctx, None, None);
         assert!(msg.contains("src/main.rs"));
         assert!(msg.contains("commit message"));
+        assert!(!msg.contains("Project Conventions"));
+    }
+
+    #[test]
+    fn test_build_user_message_commit_refinement_replaces_default_ask() {
+        let ctx = RepoContext {
+            staged_files: vec!["src/main.rs".to_string()],
+            ..Default::default()
+        };
+        let msg = build_user_message(
+            "commit_suggestion",
+            &ctx,
+            Some("Previous suggestion:\nfeat: add x\n\nInstruction: make it shorter"),
+            None,
+        );
+        assert!(msg.ends_with("Instruction: make it shorter"));
+        assert!(!msg.contains("Suggest a concise"));
+    }
+
+    #[test]
+    fn test_build_user_message_error_with_failed_command() {
+        let ctx = RepoContext {
+            branch: Some("main".to_string()),
+            failure: Some(crate::ai::client::FailureContext {
+                command: Some("git push origin main".to_string()),
+                exit_code: Some(1),
+                outcome: None,
+                stderr: Some("! [rejected] main -> main (non-fast-forward)".to_string()),
+                repo_state: vec!["2 ahead, 3 behind origin/main".to_string()],
+            }),
+            ..Default::default()
+        };
+        let msg = build_user_message("error", &ctx, None, Some("Push failed"));
+        assert!(msg.starts_with("Git Error Message:\nPush failed\n\nFailed Command: git push origin main\nExit Code: 1\nStderr:\n! [rejected]"));
+        assert!(msg.contains("Repository State:\n- 2 ahead, 3 behind origin/main"));
+
+        // Without a journal entry the message still carries the state
+        let ctx = RepoContext {
+            failure: Some(crate::ai::client::FailureContext {
+                outcome: Some("timed out".to_string()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let msg = build_user_message("error", &ctx, None, Some("boom"));
+        assert!(msg.contains("Outcome: timed out"));
+        assert!(!msg.contains("Failed Command"));
+    }
+
+    #[test]
+    fn test_format_context_with_strategy() {
+        let ctx = RepoContext {
+            repo_path: None,
+            branch: Some("main".to_string()),
+            staged_files: vec![],
+            unstaged_files: vec![],
+            diff_stats: None,
+            diff: None,
+            omitted_files: vec![],
+            conflict_files: vec![],
+            conflict_diff: None,
+            has_conflicts: false,
+            merge_type: None,
+            detached_head: false,
+            project: None,
+            strategy: Some(crate::ai::client::StrategyContext {
+                ours_branch: "main".to_string(),
+                theirs_branch: "feature".to_string(),
+                ahead: 1,
+                behind: 3,
+                ours_commits: vec![],
+                theirs_commits: vec!["abc1234 feat: add login".to_string()],
+                conflicts: vec!["src/app.rs (2 regions)".to_string()],
+            }),
+            failure: None,
+        };
+        let out = build_user_message("merge_strategy", &ctx, None, None);
+        assert!(out.contains("Integrating 'feature' into 'main'"));
+        assert!(out.contains("1 commit(s) ahead and 3 behind"));
+        assert!(out.contains("Recent commits only on 'feature':\n- abc1234 feat: add login"));
+        assert!(!out.contains("only on 'main'"));
+        assert!(out.contains("- src/app.rs (2 regions)"));
+    }
+
+    #[test]
+    fn test_build_user_message_commit_notes_omitted_files() {
+        let ctx = RepoContext {
+            repo_path: None,
+            branch: Some("main".to_string()),
+            staged_files: vec!["src/big.rs".to_string(), "Cargo.lock".to_string()],
+            unstaged_files: vec![],
+            diff_stats: None,
+            diff: Some("--- src/big.rs\n+new line".to_string()),
+            omitted_files: vec!["Cargo.lock".to_string()],
+            conflict_files: vec![],
+            conflict_diff: None,
+            has_conflicts: false,
+            merge_type: None,
+            detached_head: false,
+            project: None,
+            strategy: None,
+            failure: None,
+        };
+        let msg = build_user_message("commit_suggestion", &ctx, None, None);
+        assert!(msg.contains("Not Shown in Diff"));
+        assert!(msg.contains("Cargo.lock"));
+    }
+
+    #[test]
+    fn test_build_user_message_commit_with_project_context() {
+        let ctx = RepoContext {
+            repo_path: None,
+            branch: Some("feature".to_string()),
+            staged_files: vec!["src/lib.rs".to_string()],
+            unstaged_files: vec![],
+            diff_stats: None,
+            diff: None,
+            omitted_files: vec![],
+            conflict_files: vec![],
+            conflict_diff: None,
+            has_conflicts: false,
+            merge_type: None,
+            detached_head: false,
+            project: Some(crate::ai::client::ProjectContext {
+                default_branch: Some("develop".to_string()),
+                recent_commits: vec!["feat(ui): add theme layer".to_string()],
+                readme: None,
+                contributing: Some("Use Conventional Commits.".to_string()),
+            }),
+            strategy: None,
+            failure: None,
+        };
+        let msg = build_user_message("commit_suggestion", &ctx, None, None);
+        assert!(msg.contains("Project Conventions"));
+        assert!(msg.contains("Default Branch: develop"));
+        assert!(msg.contains("- feat(ui): add theme layer"));
+        assert!(msg.contains("Use Conventional Commits."));
+        assert!(!msg.contains("README"));
     }
 }
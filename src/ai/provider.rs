@@ -5,8 +5,10 @@
 
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
 
 use crate::config::AiConfig;
+use crate::http::{Method, Request};
 
 // ─── Provider Trait ────────────────────────────────────────────
 
@@ -46,62 +48,40 @@ pub fn create_provider(config: &AiConfig) -> Option<Box<dyn AiProvider>> {
             if api_key.is_empty() {
                 return None;
             }
-            let client = reqwest::blocking::Client::builder()
-                .timeout(std::time::Duration::from_secs(timeout))
-                .build()
-                .ok()?;
             Some(Box::new(BedrockProvider {
                 endpoint,
                 api_key,
                 timeout,
-                client,
             }))
         }
         "openai" | "openrouter" => {
             if api_key.is_empty() {
                 return None;
             }
-            let client = reqwest::blocking::Client::builder()
-                .timeout(std::time::Duration::from_secs(timeout))
-                .build()
-                .ok()?;
             Some(Box::new(OpenAiCompatibleProvider {
                 endpoint,
                 api_key,
                 model,
                 timeout,
                 provider_name: provider.to_string(),
-                client,
             }))
         }
         "anthropic" => {
             if api_key.is_empty() {
                 return None;
             }
-            let client = reqwest::blocking::Client::builder()
-                .timeout(std::time::Duration::from_secs(timeout))
-                .build()
-                .ok()?;
             Some(Box::new(AnthropicProvider {
                 endpoint,
                 api_key,
                 model,
                 timeout,
-                client,
-            }))
-        }
-        "ollama" => {
-            let client = reqwest::blocking::Client::builder()
-                .timeout(std::time::Duration::from_secs(timeout))
-                .build()
-                .ok()?;
-            Some(Box::new(OllamaProvider {
-                endpoint,
-                model,
-                timeout,
-                client,
             }))
         }
+        "ollama" => Some(Box::new(OllamaProvider {
+            endpoint,
+            model,
+            timeout,
+        })),
         _ => None,
     }
 }
@@ -113,9 +93,7 @@ pub fn create_provider(config: &AiConfig) -> Option<Box<dyn AiProvider>> {
 pub struct BedrockProvider {
     pub endpoint: String,
     pub api_key: String,
-    #[allow(dead_code)]
     pub timeout: u64,
-    client: reqwest::blocking::Client,
 }
 
 /// Bedrock-specific request that mirrors the Lambda's expected input.
@@ -158,18 +136,14 @@ impl AiProvider for BedrockProvider {
         };
 
         let resp = self
-            .client
             .post(&self.endpoint)
-            .header("Content-Type", "application/json")
             .header("x-api-key", &self.api_key)
             .json(&req)
             .send()
             .context("Failed to reach AI backend")?;
 
-        let status = resp.status().as_u16();
-        if status != 200 {
-            let body = resp.text().unwrap_or_default();
-            anyhow::bail!("Bedrock API error (HTTP {}): {}", status, body);
+        if resp.status != 200 {
+            anyhow::bail!("Bedrock API error (HTTP {}): {}", resp.status, resp.text());
         }
 
         let api_resp: BedrockApiResponse =
@@ -186,15 +160,13 @@ impl AiProvider for BedrockProvider {
 
     fn health_check(&self) -> Result<String> {
         let url = self.endpoint.replace("/mentor", "/health");
-        let resp = self
-            .client
-            .get(&url)
+        let resp = Request::new(Method::Get, url)
+            .timeout(Duration::from_secs(self.timeout))
             .header("x-api-key", &self.api_key)
             .send()
             .context("Health check failed")?;
 
-        let body = resp.text().unwrap_or_default();
-        Ok(format!("Bedrock (Lambda): {}", body))
+        Ok(format!("Bedrock (Lambda): {}", resp.text()))
     }
 
     fn name(&self) -> &str {
@@ -207,23 +179,23 @@ impl AiProvider for BedrockProvider {
 }
 
 impl BedrockProvider {
+    fn post(&self, url: &str) -> Request {
+        Request::new(Method::Post, url).timeout(Duration::from_secs(self.timeout))
+    }
+
     /// Send a raw `MentorRequest`-shaped JSON body to the Lambda endpoint.
     /// This is the primary path for Bedrock — keeps full compatibility.
     #[allow(dead_code)]
     pub fn call_raw(&self, body: &serde_json::Value) -> Result<String> {
         let resp = self
-            .client
             .post(&self.endpoint)
-            .header("Content-Type", "application/json")
             .header("x-api-key", &self.api_key)
             .json(body)
             .send()
             .context("Failed to reach AI backend")?;
 
-        let status = resp.status().as_u16();
-        if status != 200 {
-            let body = resp.text().unwrap_or_default();
-            anyhow::bail!("Bedrock API error (HTTP {}): {}", status, body);
+        if resp.status != 200 {
+            anyhow::bail!("Bedrock API error (HTTP {}): {}", resp.status, resp.text());
         }
 
         let api_resp: BedrockApiResponse = resp.json().context("Failed to parse response")?;
@@ -244,10 +216,8 @@ pub struct OpenAiCompatibleProvider {
     pub endpoint: String,
     pub api_key: String,
     pub model: String,
-    #[allow(dead_code)]
     pub timeout: u64,
     pub provider_name: String,
-    client: reqwest::blocking::Client,
 }
 
 #[derive(Serialize)]
@@ -309,11 +279,9 @@ impl AiProvider for OpenAiCompatibleProvider {
             temperature: 0.7,
         };
 
-        let mut builder = self
-            .client
-            .post(&self.endpoint)
-            .header("Content-Type", "application/json")
-            .header("Authorization", format!("Bearer {}", self.api_key));
+        let mut builder = Request::new(Method::Post, &self.endpoint)
+            .timeout(Duration::from_secs(self.timeout))
+            .bearer(&self.api_key);
 
         // OpenRouter wants extra headers
         if self.provider_name == "openrouter" {
@@ -327,8 +295,8 @@ impl AiProvider for OpenAiCompatibleProvider {
             .send()
             .context("Failed to reach AI backend")?;
 
-        let status = resp.status().as_u16();
-        let body_text = resp.text().unwrap_or_default();
+        let status = resp.status;
+        let body_text = resp.text();
         log::debug!(
             "[{}] response: status={} body_len={}",
             self.provider_name,
@@ -392,9 +360,7 @@ pub struct AnthropicProvider {
     pub endpoint: String,
     pub api_key: String,
     pub model: String,
-    #[allow(dead_code)]
     pub timeout: u64,
-    client: reqwest::blocking::Client,
 }
 
 #[derive(Serialize)]
@@ -439,18 +405,16 @@ impl AiProvider for AnthropicProvider {
             }],
         };
 
-        let resp = self
-            .client
-            .post(&self.endpoint)
-            .header("Content-Type", "application/json")
+        let resp = Request::new(Method::Post, &self.endpoint)
+            .timeout(Duration::from_secs(self.timeout))
             .header("x-api-key", &self.api_key)
             .header("anthropic-version", "2023-06-01")
             .json(&req)
             .send()
             .context("Failed to reach Anthropic API")?;
 
-        let status = resp.status().as_u16();
-        let body_text = resp.text().unwrap_or_default();
+        let status = resp.status;
+        let body_text = resp.text();
 
         if status != 200 {
             anyhow::bail!("Anthropic API error (HTTP {}): {}", status, body_text);
@@ -492,9 +456,7 @@ impl AiProvider for AnthropicProvider {
 pub struct OllamaProvider {
     pub endpoint: String,
     pub model: String,
-    #[allow(dead_code)]
     pub timeout: u64,
-    client: reqwest::blocking::Client,
 }
 
 #[derive(Serialize)]
@@ -540,16 +502,14 @@ impl AiProvider for OllamaProvider {
 
         let url = format!("{}/api/chat", self.endpoint.trim_end_matches('/'));
 
-        let resp = self
-            .client
-            .post(&url)
-            .header("Content-Type", "application/json")
+        let resp = Request::new(Method::Post, url)
+            .timeout(Duration::from_secs(self.timeout))
             .json(&req)
             .send()
             .context("Failed to reach Ollama — is it running? (ollama serve)")?;
 
-        let status = resp.status().as_u16();
-        let body_text = resp.text().unwrap_or_default();
+        let status = resp.status;
+        let body_text = resp.text();
 
         if status != 200 {
             anyhow::bail!("Ollama error (HTTP {}): {}", status, body_text);
@@ -571,19 +531,18 @@ impl AiProvider for OllamaProvider {
     fn health_check(&self) -> Result<String> {
         let url = format!("{}/api/tags", self.endpoint.trim_end_matches('/'));
 
-        let resp = self
-            .client
-            .get(&url)
+        let resp = Request::new(Method::Get, url)
+            .timeout(Duration::from_secs(self.timeout))
             .send()
             .context("Cannot reach Ollama — is it running? (ollama serve)")?;
 
-        if resp.status().is_success() {
+        if resp.is_success() {
             Ok(format!(
                 "Ollama ({}) at {} — reachable ✓",
                 self.model, self.endpoint
             ))
         } else {
-            anyhow::bail!("Ollama returned HTTP {}", resp.status().as_u16())
+            anyhow::bail!("Ollama returned HTTP {}", resp.status)
         }
     }
 
@@ -618,6 +577,9 @@ mod tests {
             endpoint: Some("https://example.com/mentor".to_string()),
             api_key: Some("test-key-12345".to_string()),
             timeout_secs: Some(30),
+            context: Default::default(),
+            redaction: Default::default(),
+            diff_budget: None,
         };
         let p = create_provider(&config);
         assert!(p.is_some());
@@ -633,6 +595,9 @@ mod tests {
             endpoint: None,
             api_key: Some("sk-test12345678".to_string()),
             timeout_secs: Some(30),
+            context: Default::default(),
+            redaction: Default::default(),
+            diff_budget: None,
         };
         let p = create_provider(&config);
         assert!(p.is_some());
@@ -648,6 +613,9 @@ mod tests {
             endpoint: None,
             api_key: Some("sk-ant-test1234".to_string()),
             timeout_secs: Some(30),
+            context: Default::default(),
+            redaction: Default::default(),
+            diff_budget: None,
         };
         let p = create_provider(&config);
         assert!(p.is_some());
@@ -656,7 +624,9 @@ mod tests {
 
     #[test]
     fn test_create_provider_ollama_no_key() {
-        std::env::remove_var("ZIT_AI_API_KEY");
+        unsafe {
+            std::env::remove_var("ZIT_AI_API_KEY");
+        }
         let config = AiConfig {
             enabled: true,
             provider: "ollama".to_string(),
@@ -664,6 +634,9 @@ mod tests {
             endpoint: None,
             api_key: None,
             timeout_secs: Some(30),
+            context: Default::default(),
+            redaction: Default::default(),
+            diff_budget: None,
         };
         let p = create_provider(&config);
         assert!(p.is_some());
@@ -679,7 +652,53 @@ mod tests {
             endpoint: None,
             api_key: None,
             timeout_secs: None,
+            context: Default::default(),
+            redaction: Default::default(),
+            diff_budget: None,
         };
         assert!(create_provider(&config).is_none());
     }
+
+    #[test]
+    fn test_openrouter_chat_through_transport() {
+        use crate::http::Response;
+        use crate::http::mock::MockTransport;
+        use std::sync::Arc;
+
+        let provider = OpenAiCompatibleProvider {
+            endpoint: "https://openrouter.ai/api/v1/chat/completions".to_string(),
+            api_key: "sk-or-test".to_string(),
+            model: "some/model".to_string(),
+            timeout: 30,
+            provider_name: "openrouter".to_string(),
+        };
+        let path = "/api/v1/chat/completions";
+        let mock = Arc::new(
+            MockTransport::new()
+                .on(
+                    Method::Post,
+                    path,
+                    Response::new(200, r#"{"choices":[{"message":{"content":"ok"}}]}"#),
+                )
+                .on(
+                    Method::Post,
+                    path,
+                    Response::new(429, r#"{"error":{"message":"rate limited"}}"#),
+                ),
+        );
+
+        let (first, second) =
+            mock.serve(|| (provider.chat("sys", "hi"), provider.chat("sys", "hi")));
+        assert_eq!(first.unwrap(), "ok");
+        assert!(second.unwrap_err().to_string().contains("HTTP 429"));
+
+        let sent = &mock.requests()[0];
+        assert_eq!(
+            sent.header_value("authorization"),
+            Some("Bearer sk-or-test")
+        );
+        assert_eq!(sent.header_value("x-title"), Some("zit"));
+        assert_eq!(sent.timeout, Some(Duration::from_secs(30)));
+        assert!(sent.body_text().contains(r#""model":"some/model""#));
+    }
 }
@@ -0,0 +1,204 @@
+//! Checks for the AI setup wizard, so a mistyped key or an unreachable
+//! endpoint is caught at the step that entered it rather than on the first
+//! question: key format per provider, endpoint reachability, and what a failed
+//! health check says about which step to redo.
+
+use crate::http::{self, Method, Request};
+use std::time::Duration;
+
+/// How long the endpoint probe waits for any answer.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Shortest key any supported provider issues.
+const MIN_KEY_LEN: usize = 20;
+
+/// Bits per character below which a key is more likely typed than generated.
+/// Real keys score 4.5 and up; "sk-aaaa…" or "testtesttest…" score under 3.
+const MIN_KEY_ENTROPY: f64 = 3.0;
+
+/// Words that show up in example keys rather than real ones.
+const PLACEHOLDERS: &[&str] = &["your", "xxxx", "api_key", "apikey", "example", "changeme"];
+
+/// Key prefixes providers issue: (provider, prefix, provider's name).
+const KEY_PREFIXES: &[(&str, &str, &str)] = &[
+    ("openai", "sk-", "OpenAI"),
+    ("anthropic", "sk-ant-", "Anthropic"),
+    ("openrouter", "sk-or-", "OpenRouter"),
+];
+
+/// Shannon entropy of `s` in bits per character.
+fn entropy(s: &str) -> f64 {
+    let mut counts = std::collections::HashMap::new();
+    for c in s.chars() {
+        *counts.entry(c).or_insert(0usize) += 1;
+    }
+    let len = s.chars().count() as f64;
+    counts
+        .values()
+        .map(|&n| {
+            let p = n as f64 / len;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+/// What's wrong with `key` as an API key for `provider`, if anything.
+pub fn check_api_key(provider: &str, key: &str) -> Option<String> {
+    let key = key.trim();
+    if key.chars().any(char::is_whitespace) {
+        return Some("API keys have no spaces — check what was pasted".to_string());
+    }
+    let lower = key.to_lowercase();
+    if key.contains('<') || PLACEHOLDERS.iter().any(|p| lower.contains(p)) {
+        return Some("That looks like a placeholder, not a real key".to_string());
+    }
+    let mut secret = key;
+    if let Some((_, prefix, name)) = KEY_PREFIXES.iter().find(|(p, ..)| *p == provider) {
+        match key.strip_prefix(prefix) {
+            Some(rest) => secret = rest,
+            None => return Some(format!("{} keys start with {}", name, prefix)),
+        }
+    }
+    if key.len() < MIN_KEY_LEN {
+        return Some(format!(
+            "Too short for an API key ({} characters)",
+            key.len()
+        ));
+    }
+    if entropy(secret) < MIN_KEY_ENTROPY {
+        return Some(
+            "Too repetitive to be a generated key — check it was copied whole".to_string(),
+        );
+    }
+    None
+}
+
+/// What's wrong with `url` as an endpoint, if anything.
+pub fn check_endpoint(url: &str) -> Option<String> {
+    let Some(rest) = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))
+    else {
+        return Some("Endpoints start with http:// or https://".to_string());
+    };
+    if url.chars().any(char::is_whitespace) {
+        return Some("URLs have no spaces".to_string());
+    }
+    let host = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    if host.is_empty() || host.starts_with(':') {
+        return Some("The URL needs a host name".to_string());
+    }
+    None
+}
+
+/// Check that something answers at `url`. Any HTTP response counts — the
+/// endpoint may well reject a bare GET — only failing to get one doesn't.
+pub fn probe(url: &str) -> Result<(), String> {
+    let host = url.split("://").nth(1).unwrap_or(url);
+    let host = host.split('/').next().unwrap_or(host);
+    match Request::new(Method::Get, url).timeout(PROBE_TIMEOUT).send() {
+        Ok(_) => Ok(()),
+        Err(http::Error::Timeout) => Err(format!(
+            "No answer from {} within {}s — check the URL",
+            host,
+            PROBE_TIMEOUT.as_secs()
+        )),
+        Err(http::Error::Connect(_)) => Err(format!(
+            "Can't connect to {} — check the URL and that the service is running",
+            host
+        )),
+        Err(e) => Err(format!("Can't reach {}: {}", host, e)),
+    }
+}
+
+/// The setup step a failed health check points at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FailedStep {
+    ApiKey,
+    Endpoint,
+    Model,
+}
+
+/// Which step to redo, judging by the error a health check failed with.
+pub fn diagnose(error: &str) -> Option<FailedStep> {
+    let lower = error.to_lowercase();
+    if lower.contains("api key") || lower.contains("access denied") || lower.contains("http 401") {
+        Some(FailedStep::ApiKey)
+    } else if lower.contains("connect") || lower.contains("timed out") || lower.contains("http 404")
+    {
+        Some(FailedStep::Endpoint)
+    } else if lower.contains("model") {
+        Some(FailedStep::Model)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::{Response, mock::MockTransport};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_api_key_checks() {
+        let real = "sk-ant-REDACTED";
+        assert_eq!(check_api_key("anthropic", real), None);
+        assert_eq!(check_api_key("bedrock", "Xq7vR2mK9pLwT4nB8cYz1dF6"), None);
+
+        let problem = |provider: &str, key: &str| check_api_key(provider, key).unwrap();
+        assert_eq!(
+            problem("openai", real.replace("sk-", "pk-").as_str()),
+            "OpenAI keys start with sk-"
+        );
+        assert_eq!(
+            problem("anthropic", "sk-ant-Xq7vR2"),
+            "Too short for an API key (13 characters)"
+        );
+        assert!(problem("openai", "sk-aaaaaaaaaaaaaaaaaaaaaaaaaaaa").contains("repetitive"));
+        assert!(problem("openrouter", "sk-or-YOUR-KEY-HERE-0000000000").contains("placeholder"));
+        assert!(problem("openai", "sk-Xq7vR2mK9 pLwT4nB8cYz1dF6").contains("spaces"));
+    }
+
+    #[test]
+    fn test_endpoint_checks() {
+        assert_eq!(
+            check_endpoint("https://abc.lambda-url.us-east-1.on.aws/"),
+            None
+        );
+        assert_eq!(check_endpoint("http://localhost:11434"), None);
+        assert!(check_endpoint("localhost:11434").is_some());
+        assert!(check_endpoint("https:///v1").is_some());
+        assert!(check_endpoint("http://:8080").is_some());
+    }
+
+    #[test]
+    fn test_probe_accepts_any_answer() {
+        let mock = Arc::new(MockTransport::new().on(
+            Method::Get,
+            "/",
+            Response::new(403, r#"{"message":"Missing Authentication Token"}"#),
+        ));
+        mock.serve(|| {
+            assert_eq!(probe("https://abc.lambda-url.us-east-1.on.aws/"), Ok(()));
+        });
+        // Unmatched requests fail as if the host were unreachable
+        let err =
+            Arc::new(MockTransport::new()).serve(|| probe("http://localhost:11434").unwrap_err());
+        assert!(err.contains("localhost:11434"), "{}", err);
+    }
+
+    #[test]
+    fn test_diagnose_health_check_errors() {
+        assert_eq!(
+            diagnose("Invalid API key — check [ai] api_key in ~/.config/zit/config.toml"),
+            Some(FailedStep::ApiKey)
+        );
+        assert_eq!(
+            diagnose("Cannot connect to AI service — check your internet connection"),
+            Some(FailedStep::Endpoint)
+        );
+        assert_eq!(diagnose("API error (HTTP 404)"), Some(FailedStep::Endpoint));
+        assert_eq!(diagnose("Rate limited — too many requests"), None);
+    }
+}
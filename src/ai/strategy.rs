@@ -0,0 +1,199 @@
+//! Parse the merge-strategy advisor's response into runnable steps.
+//!
+//! The advisor answers in the `PROMPT_MERGE_STRATEGY` format; the `COMMANDS:` block
+//! becomes an ordered list of git invocations the user can run from a follow-up menu.
+//! Only non-interactive, non-destructive commands are kept.
+
+/// Subcommands the advisor may propose as runnable steps.
+const ALLOWED_SUBCOMMANDS: &[&str] = &[
+    "merge", "rebase", "switch", "checkout", "fetch", "pull", "commit", "add", "status", "log",
+    "diff", "branch", "stash",
+];
+
+/// Flags that make a step destructive or interactive (would block the TUI on an editor).
+const REJECTED_FLAGS: &[&str] = &[
+    "-f",
+    "--force",
+    "--hard",
+    "-D",
+    "-i",
+    "--interactive",
+    "--edit",
+    "-e",
+];
+
+/// The advisor's recommendation plus its runnable order of operations.
+#[derive(Debug, Default, PartialEq)]
+pub struct StrategyAdvice {
+    /// Strategy name as written by the AI, e.g. "REBASE" or "MERGE_NO_FF".
+    pub recommended: Option<String>,
+    /// git arguments (without the leading `git`) in the order they should run.
+    pub steps: Vec<Vec<String>>,
+}
+
+/// Parse an advisor response. Unsafe or unparsable commands are dropped.
+pub fn parse_advice(response: &str) -> StrategyAdvice {
+    let mut advice = StrategyAdvice::default();
+    let mut in_commands = false;
+    let mut in_block = false;
+
+    for line in response.lines() {
+        let trimmed = line.trim();
+        if advice.recommended.is_none()
+            && let Some(rec) = trimmed.strip_prefix("RECOMMENDED:")
+        {
+            advice.recommended = Some(rec.trim().to_string());
+            continue;
+        }
+        if trimmed.starts_with("COMMANDS:") {
+            in_commands = true;
+            continue;
+        }
+        if !in_commands {
+            continue;
+        }
+        if trimmed.starts_with("```") {
+            if in_block {
+                break;
+            }
+            in_block = true;
+            continue;
+        }
+        if !in_block {
+            continue;
+        }
+
+        let command = trimmed.strip_prefix("$ ").unwrap_or(trimmed);
+        let mut words = split_command_line(command);
+        if words.first().map(String::as_str) != Some("git") {
+            continue;
+        }
+        words.remove(0);
+        if is_runnable_step(&words) {
+            advice.steps.push(words);
+        } else {
+            log::debug!("[strategy] Skipping non-runnable step: {}", command);
+        }
+    }
+    advice
+}
+
+/// Whether a proposed step is safe to run without a terminal.
+fn is_runnable_step(args: &[String]) -> bool {
+    let Some(sub) = args.first() else {
+        return false;
+    };
+    if !ALLOWED_SUBCOMMANDS.contains(&sub.as_str()) {
+        return false;
+    }
+    if args.iter().any(|a| REJECTED_FLAGS.contains(&a.as_str())) {
+        return false;
+    }
+    // Placeholders like <branch> mean the AI did not fill in a real name
+    if args.iter().any(|a| a.starts_with('<') && a.ends_with('>')) {
+        return false;
+    }
+    // A commit without a message would open an editor
+    if sub == "commit"
+        && !args
+            .iter()
+            .any(|a| a == "-m" || a.starts_with("--message") || a == "--no-edit" || a == "-F")
+    {
+        return false;
+    }
+    true
+}
+
+/// Split a shell-like command line on whitespace, honouring single and double quotes
+/// and stopping at an unquoted `#` comment.
+pub fn split_command_line(line: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut quote: Option<char> = None;
+    let mut has_word = false;
+
+    for c in line.chars() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => current.push(c),
+            None if c == '"' || c == '\'' => {
+                quote = Some(c);
+                has_word = true;
+            }
+            None if c.is_whitespace() => {
+                if has_word {
+                    words.push(std::mem::take(&mut current));
+                    has_word = false;
+                }
+            }
+            None if c == '#' && !has_word => break,
+            None => {
+                current.push(c);
+                has_word = true;
+            }
+        }
+    }
+    if has_word {
+        words.push(current);
+    }
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RESPONSE: &str = "RECOMMENDED: REBASE
+SAFETY: ⚠️ CAUTION
+
+WHY:
+Feature branch is private.
+
+COMMANDS:
+```
+git merge --abort
+git rebase main   # replay feature on top of main
+git commit -m \"fix: resolve conflicts\"
+git push --force
+git rebase -i main
+git commit
+git switch <branch>
+echo done
+```
+
+ALTERNATIVES:
+1. MERGE_NO_FF (✅ SAFE) - keeps history
+";
+
+    #[test]
+    fn test_parse_advice_keeps_safe_steps_in_order() {
+        let advice = parse_advice(RESPONSE);
+        assert_eq!(advice.recommended.as_deref(), Some("REBASE"));
+        assert_eq!(
+            advice.steps,
+            vec![
+                vec!["merge", "--abort"],
+                vec!["rebase", "main"],
+                vec!["commit", "-m", "fix: resolve conflicts"],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_advice_without_commands() {
+        let advice = parse_advice("RECOMMENDED: FAST_FORWARD\n\nWHY:\nNothing diverged.");
+        assert_eq!(advice.recommended.as_deref(), Some("FAST_FORWARD"));
+        assert!(advice.steps.is_empty());
+        assert_eq!(parse_advice(""), StrategyAdvice::default());
+    }
+
+    #[test]
+    fn test_split_command_line() {
+        assert_eq!(
+            split_command_line("git commit -m 'a b' --no-verify # note"),
+            vec!["git", "commit", "-m", "a b", "--no-verify"]
+        );
+        assert_eq!(split_command_line("git log \"\""), vec!["git", "log", ""]);
+        assert!(split_command_line("   ").is_empty());
+    }
+}
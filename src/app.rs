@@ -1,13 +1,21 @@
 use anyhow::Result;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseEvent, MouseEventKind};
-use std::sync::{mpsc, Arc};
+use std::sync::{Arc, mpsc};
+use std::time::{Duration, Instant};
 
-use crate::ai::client::AiClient;
+use crate::ai::client::{AiClient, CommitRefinement};
 use crate::config::Config;
+use crate::event::AppEvent;
 use crate::git;
+use crate::jobs::{Priority, Scheduler};
+use crate::tasks::{self, Tasks};
+use crate::ui::icons::Icon;
+use crate::ui::pane_search::PaneSearch;
+use crate::ui::spellcheck::SpellChecker;
 use crate::ui::{
-    agent, ai_mentor, bisect, branches, cherry_pick, commit, dashboard, github, merge_resolve,
-    reflog, staging, stash, time_travel, timeline, workflow_builder,
+    agent, ai_mentor, bisect, blame, branches, cherry_pick, commit, dashboard, github,
+    merge_resolve, outgoing, output_pane, rebase, reflog, staging, stash, status_bar, tags,
+    time_travel, timeline, workflow_builder, zen,
 };
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -26,13 +34,24 @@ pub enum View {
     Bisect,
     CherryPick,
     Agent,
+    Outgoing,
+    Rebase,
+    Tags,
+    Blame,
 }
 
 /// Popup dialog state.
 #[derive(Debug, Clone)]
 pub enum Popup {
     None,
-    Help,
+    /// Keybindings for the view, scrolled down `scroll` lines.
+    Help {
+        scroll: u16,
+    },
+    /// F1 key inspector; `key` is the last key pressed, not yet any.
+    KeyInspect {
+        key: Option<KeyEvent>,
+    },
     Confirm {
         title: String,
         message: String,
@@ -50,7 +69,7 @@ pub enum Popup {
     },
     FollowUp {
         title: String,
-        #[allow(dead_code)]
+        /// Shown above the suggestions when not empty.
         context: String,
         suggestions: Vec<FollowUpItem>,
         selected: usize,
@@ -74,27 +93,63 @@ pub struct FollowUpItem {
 #[derive(Debug, Clone)]
 #[allow(dead_code)]
 pub enum FollowUpAction {
-    ApplyResolution(String), // file path
-    StageFile(String),       // file path
+    ApplyResolution(String),      // file path
+    ApplyRegionResolution(usize), // conflict region index in the selected file
+    StageFile(String),            // file path
     CommitNow,
     SetCommitMessage(String),
     AbortMerge,
     ContinueMerge,
     ViewNextConflict,
     AskAiMore(String), // context/question
+    LearnTopic(String),
     SwitchToView(View),
-    RunGitCommand(Vec<String>), // args for git
+    RunGitCommand(Vec<String>),            // args for git
+    RunGitSteps(String, Vec<Vec<String>>), // title, git args per step (run in order)
     EditCommitMessage,
     RegenerateAiSuggestion,
-    WriteGitignore(String), // generated .gitignore content
+    RefineCommitMessage(CommitRefinement),
+    PromptCommitRefinement,      // ask for a custom instruction, then refine
+    FixSpelling(String, String), // misspelled word, replacement
+    AddSpellingWord(String),
+    SetRepoSignOff(bool),
+    SwitchBranch(String),         // bring uncommitted changes along
+    StashAndSwitchBranch(String), // stash, switch, then pop
+    BranchHere,                   // name a branch at the detached HEAD
+    LeaveDetachedHead(String),    // branch to return to
+    RunAlias(git::alias::Alias),
+    WriteGitignore(String),       // generated .gitignore content
+    JumpToConflictMarker(String), // staged file path
+    CommitIgnoringConflictMarkers,
+    TimelineScope(crate::ui::timeline::RefScope),
+    SummarizeContributing(String, String), // readiness report, contributing guide
+    CopyToClipboard(String),
+    ConfirmSnippet(crate::ai::snippets::Snippet), // git commands from an AI answer
+    PolishReleaseNotes(String),                   // markdown draft
+    EditAiKey,
+    EditAiEndpoint,
+    StartAiSetup,
+    ReviewPullRequest(u64, String), // PR number, review event
+    SetStats(bool),
+    ResetStats,
+    RunRecipe(crate::recipes::Recipe),
+    SubmoduleActions(Option<String>), // path, or all
+    RenormalizeLineEndings(String),   // file path
+    RestoreLineEndings(String, git::encoding::Eol),
+    CreateRelease {
+        tag: String,
+        notes: String,
+        draft: bool,
+    },
 }
 
 /// Describes the git action that was pending when secrets were detected.
 #[derive(Debug, Clone)]
 pub enum SecretPendingAction {
-    StageFile(String), // single file path
-    StageAll,          // stage all files
-    Commit,            // commit with current message
+    StageFile(String),       // single file or directory path
+    StageAll,                // stage all files
+    StagePaths(Vec<String>), // marked files (multi-select)
+    Commit,                  // commit with current message
 }
 
 #[derive(Debug, Clone)]
@@ -105,13 +160,37 @@ pub enum ConfirmAction {
     SoftReset(String),
     RemoveCollaborator(String),
     ClearStash,
+    DeleteTag(String),
+    DeleteRemoteTag(String),
     AbortMerge,
     ContinueMerge,
-    MergePullRequest { number: u64, method: String },
+    MergePullRequest {
+        number: u64,
+        method: String,
+    },
     ClosePullRequest(u64),
+    CloseIssue(u64),
+    RerunFailedJobs(u64), // workflow run id
     DiscardFile(String),
+    DiscardFiles(Vec<String>),
     ForceStageWithSecrets(SecretPendingAction),
     ForceCommitWithSecrets,
+    FixAuthorIdentity,
+    PushDespiteChecklist(output_pane::OutputJob),
+    RunSnippet(output_pane::OutputJob),
+    RestoreSnapshot(String),
+    ClearSnapshots,
+    DetachAt(String),
+    RestoreFileFrom {
+        commit: String,
+        path: String,
+    },
+    RunRebase,
+    CherryPickCommits(Vec<String>), // oldest first
+    RunRecipe(crate::recipes::Plan),
+    /// A recipe step marked `confirm`; the answer goes back to the running recipe.
+    RecipeStep(mpsc::Sender<bool>),
+    Quit,
 }
 
 #[derive(Debug, Clone)]
@@ -121,13 +200,73 @@ pub enum InputAction {
     RenameBranch,
     SearchCommits,
     SearchFiles,
+    /// `/` in a scrolling pane (AI result, diff).
+    SearchPane,
     CommitMessage,
     AddCollaborator,
     AiSetupProvider,
     AiSetupModel,
     AiSetupEndpoint,
-    AiSetupApiKey,
-    StashPush,
+    AiSetupApiKey(String), // provider
+    /// Change just the endpoint of a configured provider.
+    AiEditEndpoint,
+    StashPush(git::stash::StashOptions),
+    RefineCommitMessage,
+    CommitAuthor,
+    CommitDate,
+    BranchHere,
+    RestoreFileFrom(String), // commit
+    CreateBranchAt(String),  // commit
+    AliasArgs(String),       // alias name
+    RebaseReword(usize),     // plan entry
+    FilterIssues,
+    IssueTitle,
+    IssueBody(String), // title
+    IssueComment(u64), // issue number
+    IssueLabels(u64),  // issue number
+    PrTitle,
+    PrReview(u64, String), // PR number, review event
+    PrLineComment(u64, git::github_auth::LineComment),
+    RecipeParam(crate::recipes::Recipe, Vec<String>), // values so far
+    PathScope,
+    CreateTag,
+    TagMessage(String), // tag name
+    BlameFile(String),  // commit
+}
+
+impl InputAction {
+    /// Whether what's typed is a secret, shown masked.
+    pub fn is_secret(&self) -> bool {
+        matches!(self, Self::AiSetupApiKey(_))
+    }
+
+    /// What's wrong with `value` for this input, checked as it's typed. Empty
+    /// values are left to `execute_input`, which treats them as cancel or default.
+    pub fn validate(&self, value: &str) -> Option<String> {
+        let value = value.trim();
+        if value.is_empty() {
+            return None;
+        }
+        match self {
+            Self::CreateBranch
+            | Self::RenameBranch
+            | Self::BranchHere
+            | Self::CreateBranchAt(_) => (!git::BranchOps::is_valid_name(value))
+                .then(|| format!("'{}' isn't a valid branch name", value)),
+            Self::CreateTag => (!git::tag::is_valid_name(value))
+                .then(|| format!("'{}' isn't a valid tag name", value)),
+            Self::CommitAuthor => {
+                (!git::commit::is_author(value)).then(|| "Must look like: Name <email>".to_string())
+            }
+            Self::AddCollaborator => {
+                (!value.chars().all(|c| c.is_ascii_alphanumeric() || c == '-'))
+                    .then(|| "GitHub usernames are letters, digits and hyphens".to_string())
+            }
+            Self::AiSetupEndpoint | Self::AiEditEndpoint => crate::ai::setup::check_endpoint(value),
+            Self::AiSetupApiKey(provider) => crate::ai::setup::check_api_key(provider, value),
+            _ => None,
+        }
+    }
 }
 
 /// Describes which AI action is in flight.
@@ -141,26 +280,70 @@ pub enum AiAction {
     ReviewDiff(String), // file path being reviewed
     AskQuestion,
     Learn,
-    MergeResolve(String), // file path being resolved
+    MergeResolve(String),              // file path being resolved
+    MergeResolveRegion(String, usize), // file path, conflict region index
     MergeStrategy,
     ResetSuggest,
     GenerateGitignore,
+    ContributingSummary,
+    PolishReleaseNotes,
+    TagChangelog(String), // tag
+    PullRequestDescription,
     AgentChat,
 }
 
+/// Result of the startup work done after the first frame.
+struct Startup {
+    /// The config after keychain migration.
+    config: Config,
+    migrated: u32,
+    ai_client: Option<AiClient>,
+}
+
 pub struct App {
     pub running: bool,
     pub view: View,
+    /// Views opened on the way to the current one, for Esc to go back through.
+    pub view_stack: Vec<View>,
     pub popup: Popup,
+    /// Cursor in the Input popup, in characters back from the end (0 = end).
+    pub input_cursor_back: usize,
+    /// What's wrong with the Input popup's value, if anything.
+    pub input_error: Option<String>,
     pub config: Config,
     pub status_message: Option<String>,
+    /// Branch, counts and any operation in progress, for the status bar.
+    pub repo_summary: status_bar::RepoSummary,
+    /// Another zit or git process working on the repository, shown as a banner.
+    pub repo_warning: Option<String>,
+    /// Set while HEAD is detached, shown as a banner with ways back to a branch.
+    pub detached_head: Option<git::branch::DetachedHead>,
+    /// File to open in the user's editor; the main loop hands it the terminal.
+    pub editor_request: Option<crate::ui::editor::EditorTarget>,
     pub ai_client: Option<Arc<AiClient>>,
     pub ai_loading: bool,
     ai_receiver: Option<mpsc::Receiver<Result<String, String>>>,
     ai_action: Option<AiAction>,
+    /// Runs network requests in the background, a few at a time.
+    pub jobs: Scheduler,
+    /// Runs the views' git reads in the background.
+    pub tasks: Tasks,
+    /// When the status bar and detached-HEAD banner's data was asked for.
+    common_at: Option<Instant>,
     /// Temporary storage for AI setup wizard.
     ai_setup_endpoint: Option<String>,
     ai_setup_provider: Option<String>,
+    /// Pre-push checklist running in the background, with the push to start afterwards.
+    push_gate: Option<(
+        mpsc::Receiver<Vec<git::prepush::CheckItem>>,
+        output_pane::OutputJob,
+    )>,
+    /// When the last `[snapshot]` safety-net snapshot was started.
+    last_snapshot: std::time::Instant,
+    /// Keychain migration and AI client setup, running after the first frame.
+    startup: Option<mpsc::Receiver<Startup>>,
+    /// Steps of the running recipe waiting to be confirmed, with where to answer.
+    pub recipe_steps: Option<mpsc::Receiver<(String, mpsc::Sender<bool>)>>,
 
     // View states
     pub dashboard_state: dashboard::DashboardState,
@@ -173,57 +356,180 @@ pub struct App {
     pub github_state: github::GitHubState,
     pub ai_mentor_state: ai_mentor::AiMentorState,
     pub stash_state: stash::StashState,
+    pub tags_state: tags::TagsState,
+    pub blame_state: blame::BlameState,
     pub merge_resolve_state: merge_resolve::MergeResolveState,
     pub workflow_builder_state: workflow_builder::WorkflowBuilderState,
     pub bisect_state: bisect::BisectState,
     pub cherry_pick_state: cherry_pick::CherryPickState,
+    pub rebase_state: rebase::RebaseState,
     pub agent_state: agent::AgentState,
+    pub outgoing_state: outgoing::OutgoingState,
+    pub output_pane: output_pane::OutputPaneState,
 }
 
 impl App {
     pub fn new(config: Config) -> Self {
         // Validate AI config and warn about issues
         let ai_issues = config.ai.validate();
-        let ai_client = AiClient::from_config(&config.ai);
-        let status_message = if !ai_issues.is_empty() {
-            Some(format!("⚠ AI config: {}", ai_issues[0]))
-        } else if config.ai.is_ready() && ai_client.is_some() {
-            Some("✓ AI Mentor ready".to_string())
-        } else {
-            None
+        let status_message = ai_issues
+            .first()
+            .map(|issue| format!("{} AI config: {}", Icon::Warning, issue));
+        let commit_state = commit::CommitState {
+            spellchecker: SpellChecker::from_config(&config.spellcheck),
+            ..Default::default()
+        };
+        let timeline_state = timeline::TimelineState {
+            identicons: config.ui.identicons,
+            ..Default::default()
+        };
+        let staging_state = staging::StagingState {
+            image_preview: config.ui.image_preview,
+            sort: staging::SortOrder::from_config(&config.ui.staging_sort),
+            group: config.ui.staging_group,
+            ..Default::default()
         };
         Self {
             running: true,
             view: View::Dashboard,
+            view_stack: Vec::new(),
             popup: Popup::None,
+            input_cursor_back: 0,
+            input_error: None,
             config,
             status_message,
-            ai_client: ai_client.map(Arc::new),
+            repo_summary: status_bar::RepoSummary::default(),
+            repo_warning: None,
+            detached_head: None,
+            editor_request: None,
+            ai_client: None,
             ai_loading: false,
             ai_receiver: None,
             ai_action: None,
+            jobs: Scheduler::default(),
+            tasks: Tasks::default(),
+            common_at: None,
             ai_setup_endpoint: None,
             ai_setup_provider: None,
+            push_gate: None,
+            last_snapshot: std::time::Instant::now(),
+            startup: None,
+            recipe_steps: None,
             dashboard_state: dashboard::DashboardState::default(),
-            staging_state: staging::StagingState::default(),
-            commit_state: commit::CommitState::default(),
+            staging_state,
+            commit_state,
             branches_state: branches::BranchesState::default(),
-            timeline_state: timeline::TimelineState::default(),
+            timeline_state,
             time_travel_state: time_travel::TimeTravelState::default(),
             reflog_state: reflog::ReflogState::default(),
             github_state: github::GitHubState::new(),
             ai_mentor_state: ai_mentor::AiMentorState::default(),
             stash_state: stash::StashState::default(),
+            tags_state: tags::TagsState::default(),
+            blame_state: blame::BlameState::default(),
             merge_resolve_state: merge_resolve::MergeResolveState::default(),
             workflow_builder_state: workflow_builder::WorkflowBuilderState::new(),
             bisect_state: bisect::BisectState::default(),
             cherry_pick_state: cherry_pick::CherryPickState::default(),
+            rebase_state: rebase::RebaseState::default(),
             agent_state: agent::AgentState::default(),
+            outgoing_state: outgoing::OutgoingState::default(),
+            output_pane: output_pane::OutputPaneState::default(),
         }
     }
 
-    /// Refresh data for the current view.
+    /// Refresh data for the current view, reading the repository right away.
     pub fn refresh(&mut self) {
+        self.refresh_view();
+        self.apply_common(tasks::Common::load(Instant::now()));
+    }
+
+    /// Refresh the current view on the git workers without waiting; the data
+    /// is shown by [`poll_refresh`](Self::poll_refresh) once read. Views that
+    /// read little refresh straight away.
+    pub fn refresh_in_background(&mut self, priority: Priority) {
+        let read = self.read();
+        if read.is_none() {
+            self.refresh_view();
+        }
+        self.tasks.submit(read, priority);
+    }
+
+    /// What the current view reads to refresh, if it reads in the background.
+    fn read(&self) -> Option<tasks::Read> {
+        match self.view {
+            View::Dashboard => Some(tasks::Read::Dashboard),
+            View::Staging => Some(tasks::Read::Staging),
+            View::Commit => Some(tasks::Read::Commit),
+            View::Branches => Some(tasks::Read::Branches),
+            View::Timeline => Some(tasks::Read::Timeline(self.timeline_state.query())),
+            _ => None,
+        }
+    }
+
+    /// Scope status, diffs, the log and staging to `dir` (relative to the
+    /// repository root), or to the whole repository.
+    pub fn set_path_scope(&mut self, dir: Option<String>) {
+        match &dir {
+            Some(dir) => self.set_status(format!("Scoped to {}/", dir)),
+            None => self.set_status("Showing the whole repository"),
+        }
+        git::scope::set(dir);
+        self.refresh();
+    }
+
+    /// Ask about the next recipe step waiting to be confirmed, once nothing
+    /// else is asking.
+    pub fn poll_recipe_steps(&mut self) {
+        if !matches!(self.popup, Popup::None) {
+            return;
+        }
+        let Some(steps) = &self.recipe_steps else {
+            return;
+        };
+        match steps.try_recv() {
+            Ok((label, answer)) => {
+                self.popup = Popup::Confirm {
+                    title: "Recipe step".to_string(),
+                    message: format!(
+                        "Run {}?\n\nNo stops the recipe and undoes what it can.",
+                        label
+                    ),
+                    on_confirm: ConfirmAction::RecipeStep(answer),
+                };
+            }
+            Err(mpsc::TryRecvError::Disconnected) => self.recipe_steps = None,
+            Err(mpsc::TryRecvError::Empty) => {}
+        }
+    }
+
+    /// Show the background reads that have finished.
+    pub fn poll_refresh(&mut self) {
+        for refresh in self.tasks.finished() {
+            if let Some(common) = refresh.common {
+                self.apply_common(common);
+            }
+            match refresh.view {
+                Some(tasks::Loaded::Dashboard(loaded)) => self.dashboard_state.apply(loaded),
+                Some(tasks::Loaded::Staging(loaded)) => self.staging_state.apply(loaded),
+                Some(tasks::Loaded::Commit(loaded)) => self.commit_state.apply(loaded),
+                Some(tasks::Loaded::Branches(loaded)) => self.branches_state.apply(loaded),
+                Some(tasks::Loaded::Timeline(loaded)) => self.timeline_state.apply(loaded),
+                Some(tasks::Loaded::Diff(loaded)) => self.staging_state.apply_diff(loaded),
+                Some(tasks::Loaded::Blame(loaded)) => blame::apply(self, loaded),
+                None => {}
+            }
+        }
+    }
+
+    fn apply_common(&mut self, common: tasks::Common) {
+        if tasks::is_newer(&mut self.common_at, common.at) {
+            self.detached_head = common.detached_head;
+            self.repo_summary = common.summary;
+        }
+    }
+
+    fn refresh_view(&mut self) {
         match self.view {
             View::Dashboard => self.dashboard_state.refresh(),
             View::Staging => self.staging_state.refresh(),
@@ -239,6 +545,10 @@ impl App {
             View::Bisect => self.bisect_state.refresh(),
             View::CherryPick => self.cherry_pick_state.refresh(),
             View::Agent => {} // no auto-refresh for agent
+            View::Outgoing => self.outgoing_state.refresh(),
+            View::Rebase => {} // the plan is only loaded when the view opens
+            View::Tags => self.tags_state.refresh(),
+            View::Blame => {} // blamed when the view opens
         }
     }
 
@@ -248,16 +558,75 @@ impl App {
         self.dashboard_state.tick_animations();
     }
 
+    /// Time until a diff reload deferred by list navigation is due.
+    pub fn pending_reload(&self) -> Option<Duration> {
+        [
+            self.staging_state.diff_debounce.remaining(),
+            self.stash_state.diff_debounce.remaining(),
+            self.cherry_pick_state.diff_debounce.remaining(),
+        ]
+        .into_iter()
+        .flatten()
+        .min()
+    }
+
+    /// Run deferred diff reloads that are due, or every pending one with
+    /// `force`, and hand the Staging diff asked for to the git workers.
+    /// Returns whether anything was reloaded.
+    pub fn run_pending_reloads(&mut self, force: bool) -> bool {
+        let staging = self.staging_state.settle_diff(force);
+        let stash = self.stash_state.settle_diff(force);
+        let cherry_pick = self.cherry_pick_state.settle_diff(force);
+        let diff = self.staging_state.diff_read.take();
+        let asked = diff.is_some();
+        if let Some(read) = diff {
+            self.tasks.submit(Some(read), Priority::User);
+        }
+        staging || stash || cherry_pick || asked
+    }
+
     /// Handle a key event. Returns Ok(()) or an error.
     pub fn handle_key(&mut self, key: KeyEvent) -> Result<()> {
+        // Anything but moving the cursor may act on the selection, so a
+        // deferred reload has to land first (e.g. hunks for the selected file)
+        if !matches!(
+            key.code,
+            KeyCode::Up | KeyCode::Down | KeyCode::Char('j') | KeyCode::Char('k')
+        ) {
+            self.run_pending_reloads(true);
+        }
         // Handle popup first
         match &self.popup {
-            Popup::Help => {
-                if key.code == KeyCode::Esc
-                    || key.code == KeyCode::Char('?')
-                    || key.code == KeyCode::Char('q')
-                {
-                    self.popup = Popup::None;
+            Popup::Help { scroll } => {
+                // Scrolling past the end is clamped when the popup is drawn
+                let scroll = match key.code {
+                    KeyCode::Esc | KeyCode::Char('?') | KeyCode::Char('q') => {
+                        self.popup = Popup::None;
+                        return Ok(());
+                    }
+                    KeyCode::Down | KeyCode::Char('j') => scroll.saturating_add(1),
+                    KeyCode::Up | KeyCode::Char('k') => scroll.saturating_sub(1),
+                    KeyCode::PageDown => scroll.saturating_add(10),
+                    KeyCode::PageUp => scroll.saturating_sub(10),
+                    KeyCode::Home => 0,
+                    KeyCode::End => u16::MAX,
+                    _ => *scroll,
+                };
+                self.popup = Popup::Help { scroll };
+                return Ok(());
+            }
+            Popup::KeyInspect { key: inspected } => {
+                let topic = inspected
+                    .as_ref()
+                    .and_then(|k| crate::ui::help::learn_topic(self.view, k));
+                match (key.code, topic) {
+                    (KeyCode::Esc, _) => self.popup = Popup::None,
+                    (KeyCode::F(1), Some(topic)) => {
+                        self.popup = Popup::None;
+                        self.open_learn_topic(topic);
+                    }
+                    // Only looked up, never run
+                    _ => self.popup = Popup::KeyInspect { key: Some(key) },
                 }
                 return Ok(());
             }
@@ -270,6 +639,9 @@ impl App {
                     }
                     KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
                         self.popup = Popup::None;
+                        if let ConfirmAction::RecipeStep(answer) = action {
+                            let _ = answer.send(false);
+                        }
                     }
                     _ => {}
                 }
@@ -281,26 +653,27 @@ impl App {
                 let mut val = value.clone();
                 let action = on_submit.clone();
                 match key.code {
-                    KeyCode::Char(c) => {
-                        val.push(c);
-                        if let Popup::Input { ref mut value, .. } = self.popup {
-                            *value = val;
-                        }
-                    }
-                    KeyCode::Backspace => {
-                        val.pop();
-                        if let Popup::Input { ref mut value, .. } = self.popup {
-                            *value = val;
-                        }
-                    }
                     KeyCode::Enter => {
-                        self.popup = Popup::None;
+                        if let Some(error) = action.validate(&val) {
+                            self.input_error = Some(error);
+                            return Ok(());
+                        }
+                        self.close_input();
                         self.execute_input(action, val)?;
                     }
-                    KeyCode::Esc => {
-                        self.popup = Popup::None;
+                    KeyCode::Esc => self.close_input(),
+                    _ => {
+                        if crate::ui::line_edit::handle_key(
+                            &mut val,
+                            &mut self.input_cursor_back,
+                            key,
+                        ) {
+                            self.input_error = action.validate(&val);
+                            if let Popup::Input { ref mut value, .. } = self.popup {
+                                *value = val;
+                            }
+                        }
                     }
-                    _ => {}
                 }
                 return Ok(());
             }
@@ -328,10 +701,9 @@ impl App {
                         if let Popup::FollowUp {
                             ref mut selected, ..
                         } = self.popup
+                            && *selected > 0
                         {
-                            if *selected > 0 {
-                                *selected -= 1;
-                            }
+                            *selected -= 1;
                         }
                     }
                     KeyCode::Down | KeyCode::Char('j') => {
@@ -340,10 +712,9 @@ impl App {
                             ref suggestions,
                             ..
                         } = self.popup
+                            && *selected + 1 < suggestions.len()
                         {
-                            if *selected + 1 < suggestions.len() {
-                                *selected += 1;
-                            }
+                            *selected += 1;
                         }
                     }
                     KeyCode::Enter => {
@@ -376,18 +747,23 @@ impl App {
                 match key.code {
                     KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('n') => {
                         self.popup = Popup::None;
-                        self.set_status("🛡 Secret scan: operation cancelled");
+                        self.set_status(format!(
+                            "{} Secret scan: operation cancelled",
+                            Icon::Shield
+                        ));
                     }
                     KeyCode::Char('f') | KeyCode::Char('F') => {
                         // Force proceed — show confirmation
                         self.popup = Popup::Confirm {
-                            title: "⚠ Force Proceed with Secrets".to_string(),
+                            title: format!("{} Force Proceed with Secrets", Icon::Warning),
                             message: format!(
                                 "Found {} potential secret(s). Are you sure you want to proceed? (y/n)",
                                 count
                             ),
                             on_confirm: match pending {
-                                SecretPendingAction::Commit => ConfirmAction::ForceCommitWithSecrets,
+                                SecretPendingAction::Commit => {
+                                    ConfirmAction::ForceCommitWithSecrets
+                                }
                                 _ => ConfirmAction::ForceStageWithSecrets(pending),
                             },
                         };
@@ -396,10 +772,9 @@ impl App {
                         if let Popup::SecretWarning {
                             ref mut selected, ..
                         } = self.popup
+                            && *selected > 0
                         {
-                            if *selected > 0 {
-                                *selected -= 1;
-                            }
+                            *selected -= 1;
                         }
                     }
                     KeyCode::Down | KeyCode::Char('j') => {
@@ -408,29 +783,26 @@ impl App {
                             ref findings,
                             ..
                         } = self.popup
+                            && *selected + 1 < findings.len()
                         {
-                            if *selected + 1 < findings.len() {
-                                *selected += 1;
-                            }
+                            *selected += 1;
                         }
                     }
-                    KeyCode::Char('a') => {
-                        // Add the selected finding's file to allowlist
-                        if sel < count {
-                            if let Popup::SecretWarning { ref findings, .. } = self.popup {
-                                let finding = &findings[sel];
-                                let pattern = finding.file.clone();
-                                if !self.config.secrets.allowlist.contains(&pattern) {
-                                    self.config.secrets.allowlist.push(pattern.clone());
-                                    let _ = self.config.save();
-                                    self.set_status(format!(
-                                        "✓ Added '{}' to secret allowlist",
-                                        pattern
-                                    ));
-                                }
+                    // Add the selected finding's file to allowlist
+                    KeyCode::Char('a') if sel < count => {
+                        if let Popup::SecretWarning { ref findings, .. } = self.popup {
+                            let finding = &findings[sel];
+                            let pattern = finding.file.clone();
+                            if !self.config.secrets.allowlist.contains(&pattern) {
+                                self.config.secrets.allowlist.push(pattern.clone());
+                                let _ = self.config.save();
+                                self.set_status(format!(
+                                    "✓ Added '{}' to secret allowlist",
+                                    pattern
+                                ));
                             }
-                            self.popup = Popup::None;
                         }
+                        self.popup = Popup::None;
                     }
                     _ => {}
                 }
@@ -449,23 +821,65 @@ impl App {
                             // When AI panel focused, let dashboard handler decide (returns focus to left)
                             dashboard::handle_key(self, key)?;
                         }
-                        DashboardFocus::Left => {
-                            self.running = false;
-                        }
+                        DashboardFocus::Left => self.quit(),
                     }
                 } else {
-                    self.view = View::Dashboard;
-                    self.dashboard_state.refresh();
+                    self.open_view(View::Dashboard);
+                    self.refresh_in_background(Priority::User);
                     self.clear_status();
                 }
                 return Ok(());
             }
             KeyCode::Char('?') => {
-                self.popup = Popup::Help;
+                self.popup = Popup::Help { scroll: 0 };
+                return Ok(());
+            }
+            KeyCode::Esc if self.esc_leaves_view() => {
+                self.go_back();
+                return Ok(());
+            }
+            KeyCode::F(1) => {
+                self.popup = Popup::KeyInspect { key: None };
                 return Ok(());
             }
             KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                self.running = false;
+                self.quit();
+                return Ok(());
+            }
+            KeyCode::Char('o') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.output_pane.toggle();
+                return Ok(());
+            }
+            KeyCode::Char('z') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                zen::set(!zen::is_on());
+                return Ok(());
+            }
+            KeyCode::Char('b')
+                if key.modifiers.contains(KeyModifiers::CONTROL)
+                    && self.detached_head.is_some() =>
+            {
+                branches::branch_here(self);
+                return Ok(());
+            }
+            KeyCode::Char('p')
+                if key.modifiers.contains(KeyModifiers::CONTROL)
+                    && self.detached_head.is_some() =>
+            {
+                branches::leave_detached_head(self);
+                return Ok(());
+            }
+            KeyCode::Up
+                if key.modifiers.contains(KeyModifiers::CONTROL)
+                    && self.output_pane.mode == output_pane::PaneMode::Expanded =>
+            {
+                self.output_pane.scroll_up();
+                return Ok(());
+            }
+            KeyCode::Down
+                if key.modifiers.contains(KeyModifiers::CONTROL)
+                    && self.output_pane.mode == output_pane::PaneMode::Expanded =>
+            {
+                self.output_pane.scroll_down();
                 return Ok(());
             }
             _ => {}
@@ -482,38 +896,50 @@ impl App {
             }
             match key.code {
                 KeyCode::Char('s') => {
-                    self.view = View::Staging;
-                    self.staging_state.refresh();
+                    self.open_view(View::Staging);
+                    self.refresh_in_background(Priority::User);
                     return Ok(());
                 }
                 KeyCode::Char('c') => {
-                    self.view = View::Commit;
+                    self.open_view(View::Commit);
                     self.commit_state.refresh();
+                    commit::offer_sign_off(self);
                     self.auto_suggest_if_ready();
                     return Ok(());
                 }
                 KeyCode::Char('b') => {
-                    self.view = View::Branches;
-                    self.branches_state.refresh();
+                    self.open_view(View::Branches);
+                    self.refresh_in_background(Priority::User);
                     return Ok(());
                 }
                 KeyCode::Char('l') => {
-                    self.view = View::Timeline;
-                    self.timeline_state.refresh();
+                    self.open_view(View::Timeline);
+                    self.timeline_state.path = None;
+                    self.refresh_in_background(Priority::User);
                     return Ok(());
                 }
                 KeyCode::Char('t') => {
-                    self.view = View::TimeTravel;
+                    self.open_view(View::TimeTravel);
                     self.time_travel_state.refresh();
                     return Ok(());
                 }
                 KeyCode::Char('r') => {
-                    self.view = View::Reflog;
+                    self.open_view(View::Reflog);
                     self.reflog_state.refresh();
                     return Ok(());
                 }
+                KeyCode::Char('o') => {
+                    self.open_view(View::Outgoing);
+                    self.outgoing_state.branch = None;
+                    self.outgoing_state.refresh();
+                    return Ok(());
+                }
+                KeyCode::Char('u') => {
+                    outgoing::open_upstream_compare(self, None);
+                    return Ok(());
+                }
                 KeyCode::Char('g') => {
-                    self.view = View::GitHub;
+                    self.open_view(View::GitHub);
                     return Ok(());
                 }
                 KeyCode::Char('a') => {
@@ -521,32 +947,58 @@ impl App {
                     return Ok(());
                 }
                 KeyCode::Char('x') => {
-                    self.view = View::Stash;
+                    self.open_view(View::Stash);
                     self.stash_state.refresh();
                     return Ok(());
                 }
                 KeyCode::Char('m') => {
                     // Open merge resolve view (only useful when conflicts exist)
-                    self.view = View::MergeResolve;
+                    self.open_view(View::MergeResolve);
                     self.merge_resolve_state.refresh();
                     return Ok(());
                 }
                 KeyCode::Char('w') => {
-                    self.view = View::WorkflowBuilder;
+                    self.open_view(View::WorkflowBuilder);
                     return Ok(());
                 }
                 KeyCode::Char('B') => {
-                    self.view = View::Bisect;
+                    self.open_view(View::Bisect);
                     self.bisect_state.refresh();
                     return Ok(());
                 }
                 KeyCode::Char('p') => {
-                    self.view = View::CherryPick;
+                    self.open_view(View::CherryPick);
                     self.cherry_pick_state.refresh();
                     return Ok(());
                 }
+                KeyCode::Char(':') => {
+                    dashboard::open_alias_picker(self);
+                    return Ok(());
+                }
+                KeyCode::Char('S') => {
+                    dashboard::open_stats(self);
+                    return Ok(());
+                }
+                KeyCode::Char('M') => {
+                    dashboard::open_submodules(self);
+                    return Ok(());
+                }
+                KeyCode::Char('T') => {
+                    self.open_view(View::Tags);
+                    self.tags_state.refresh();
+                    return Ok(());
+                }
+                KeyCode::Char('P') => {
+                    self.popup = Popup::Input {
+                        title: "Path Scope".to_string(),
+                        prompt: "Directory (empty for the whole repository): ".to_string(),
+                        value: git::scope::get().unwrap_or_default(),
+                        on_submit: InputAction::PathScope,
+                    };
+                    return Ok(());
+                }
                 KeyCode::Char('A') => {
-                    self.view = View::Agent;
+                    self.open_view(View::Agent);
                     if self.ai_client.is_none() {
                         self.start_ai_setup();
                     }
@@ -572,6 +1024,10 @@ impl App {
             View::Bisect => bisect::handle_key(self, key)?,
             View::CherryPick => cherry_pick::handle_key(self, key)?,
             View::Agent => agent::handle_key(self, key)?,
+            View::Outgoing => outgoing::handle_key(self, key)?,
+            View::Rebase => rebase::handle_key(self, key)?,
+            View::Tags => tags::handle_key(self, key)?,
+            View::Blame => blame::handle_key(self, key)?,
         }
 
         Ok(())
@@ -657,6 +1113,33 @@ impl App {
                     }
                 }
             }
+            ConfirmAction::FixAuthorIdentity => {
+                // Re-scan so the rewrite acts on the current state, not the one shown
+                let result = git::identity::check_recent_commits(git::identity::SCAN_LIMIT)
+                    .and_then(|report| git::identity::rewrite_unpushed_authors(&report));
+                match result {
+                    Ok(count) => self.set_status(format!(
+                        "✓ Rewrote {} commit(s) with your author identity",
+                        count
+                    )),
+                    Err(e) => {
+                        let err_str = e.to_string();
+                        self.status_message = Some(format!("Error: {}", err_str));
+                        self.start_ai_error_explain(err_str);
+                    }
+                }
+                self.timeline_state.refresh();
+            }
+            ConfirmAction::PushDespiteChecklist(job) | ConfirmAction::RunSnippet(job) => {
+                self.start_output_job(job)
+            }
+            ConfirmAction::RunRebase => rebase::run(self),
+            ConfirmAction::CherryPickCommits(hashes) => timeline::cherry_pick(self, hashes),
+            ConfirmAction::RunRecipe(plan) => dashboard::run_recipe(self, plan),
+            ConfirmAction::RecipeStep(answer) => {
+                let _ = answer.send(true);
+            }
+            ConfirmAction::Quit => self.running = false,
             ConfirmAction::ClearStash => {
                 match git::stash::stash_clear() {
                     Ok(_) => self.status_message = Some("Cleared all stash entries".to_string()),
@@ -668,27 +1151,91 @@ impl App {
                 }
                 self.stash_state.refresh();
             }
+            ConfirmAction::DeleteTag(name) => {
+                match git::tag::delete(&name) {
+                    Ok(_) => self.set_status(format!("Deleted tag '{}'", name)),
+                    Err(e) => self.set_status(format!("Error: {}", e)),
+                }
+                self.tags_state.refresh();
+            }
+            ConfirmAction::DeleteRemoteTag(name) => tags::delete_remote(self, &name),
+            ConfirmAction::RestoreSnapshot(hash) => {
+                match git::snapshot::restore_snapshot(&hash, self.config.snapshot.keep) {
+                    Ok(removed) => self.set_status(format!(
+                        "Restored snapshot {}{} (previous state saved as a snapshot)",
+                        &hash[..hash.len().min(7)],
+                        if removed > 0 {
+                            format!(", removed {} new file(s)", removed)
+                        } else {
+                            String::new()
+                        }
+                    )),
+                    Err(e) => {
+                        let err_str = e.to_string();
+                        self.set_status(format!("Restore failed: {}", err_str));
+                        self.start_ai_error_explain(err_str);
+                    }
+                }
+                self.stash_state.refresh();
+            }
+            ConfirmAction::DetachAt(hash) => {
+                match git::BranchOps::detach(&hash) {
+                    Ok(()) => self.set_status(format!(
+                        "Checked out {} — HEAD is detached",
+                        &hash[..7.min(hash.len())]
+                    )),
+                    Err(e) => self.set_status(format!("Error: {}", e)),
+                }
+                self.refresh();
+            }
+            ConfirmAction::RestoreFileFrom { commit, path } => {
+                time_travel::restore_file(self, &commit, &path)
+            }
+            ConfirmAction::ClearSnapshots => {
+                match git::snapshot::clear_snapshots() {
+                    Ok(()) => self.set_status("Deleted all snapshots"),
+                    Err(e) => self.set_status(format!("Error: {}", e)),
+                }
+                self.stash_state.refresh();
+            }
             ConfirmAction::AbortMerge => match git::merge::abort_merge() {
-                Ok(()) => {
-                    self.set_status("Merge aborted successfully");
-                    self.view = View::Dashboard;
+                Ok(op) => {
+                    self.set_status(format!("{} aborted — back to where it started", op.title()));
+                    self.open_view(View::Dashboard);
                     self.dashboard_state.refresh();
                 }
                 Err(e) => {
                     let err_str = e.to_string();
-                    self.set_status(format!("Error aborting merge: {}", err_str));
+                    self.set_status(format!("Error aborting: {}", err_str));
                     self.start_ai_error_explain(err_str);
                 }
             },
             ConfirmAction::ContinueMerge => match git::merge::continue_merge() {
-                Ok(()) => {
-                    self.set_status("Merge completed successfully!");
-                    self.view = View::Dashboard;
+                Ok(git::merge::ContinueOutcome::Finished(op)) => {
+                    self.set_status(format!("✓ {} completed successfully!", op.title()));
+                    self.open_view(View::Dashboard);
                     self.dashboard_state.refresh();
                 }
+                Ok(git::merge::ContinueOutcome::Stopped(op)) => {
+                    // Stay here for the next commit's conflicts
+                    self.merge_resolve_state.refresh();
+                    let remaining = self.merge_resolve_state.conflicted_files.len();
+                    if remaining > 0 {
+                        self.set_status(format!(
+                            "{} continued — the next commit has {} conflicted file(s)",
+                            op.title(),
+                            remaining
+                        ));
+                    } else {
+                        self.set_status(format!(
+                            "{} continued and stopped again — see git status",
+                            op.title()
+                        ));
+                    }
+                }
                 Err(e) => {
                     let err_str = e.to_string();
-                    self.set_status(format!("Error continuing merge: {}", err_str));
+                    self.set_status(format!("Error continuing: {}", err_str));
                     self.start_ai_error_explain(err_str);
                 }
             },
@@ -696,7 +1243,8 @@ impl App {
                 if let Some(token) = self.config.github.get_token() {
                     self.github_state.pr_state.loading = true;
                     let bg = self.github_state.pr_state.bg_result.clone();
-                    std::thread::spawn(move || {
+                    let key = format!("pr-merge:{}", number);
+                    self.jobs.submit(key, Priority::User, move || {
                         let result = git::github_auth::merge_pull_request(&token, number, &method)
                             .map_err(|e| e.to_string());
                         if let Ok(mut r) = bg.lock() {
@@ -709,7 +1257,8 @@ impl App {
                 if let Some(token) = self.config.github.get_token() {
                     self.github_state.pr_state.loading = true;
                     let bg = self.github_state.pr_state.bg_result.clone();
-                    std::thread::spawn(move || {
+                    let key = format!("pr-close:{}", number);
+                    self.jobs.submit(key, Priority::User, move || {
                         let result = git::github_auth::close_pull_request(&token, number)
                             .map_err(|e| e.to_string());
                         if let Ok(mut r) = bg.lock() {
@@ -718,6 +1267,13 @@ impl App {
                     });
                 }
             }
+            ConfirmAction::CloseIssue(number) => {
+                github::update_issue(self, number, move |token| {
+                    git::github_auth::set_issue_state(token, number, false)?;
+                    Ok(format!("✓ Closed issue #{}", number))
+                });
+            }
+            ConfirmAction::RerunFailedJobs(run_id) => github::rerun_failed_jobs(self, run_id),
             ConfirmAction::DiscardFile(path) => {
                 match git::run_git(&["restore", &path]) {
                     Ok(_) => {
@@ -740,12 +1296,32 @@ impl App {
                     }
                 }
             }
+            ConfirmAction::DiscardFiles(paths) => {
+                let mut args = vec!["restore", "--"];
+                args.extend(paths.iter().map(|p| p.as_str()));
+                match git::run_git(&args) {
+                    Ok(_) => {
+                        self.set_status(format!("Discarded changes to {} file(s)", paths.len()));
+                    }
+                    Err(e) => {
+                        let err_str = e.to_string();
+                        self.set_status(format!("Failed to discard: {}", err_str));
+                        self.start_ai_error_explain(err_str);
+                    }
+                }
+                self.staging_state.exit_multi_select();
+                self.staging_state.refresh();
+            }
             ConfirmAction::ForceStageWithSecrets(pending_action) => {
                 match pending_action {
                     SecretPendingAction::StageFile(path) => {
                         match git::run_git(&["add", &path]) {
                             Ok(_) => {
-                                self.set_status(format!("⚠ Staged with secrets: {}", path));
+                                self.set_status(format!(
+                                    "{} Staged with secrets: {}",
+                                    Icon::Warning,
+                                    path
+                                ));
                             }
                             Err(e) => {
                                 self.set_status(format!("Error staging: {}", e));
@@ -754,14 +1330,38 @@ impl App {
                         self.staging_state.refresh();
                     }
                     SecretPendingAction::StageAll => {
-                        match git::run_git(&["add", "-A"]) {
+                        let scope = git::scope::pathspec();
+                        let mut args = vec!["add", "-A", "--"];
+                        args.extend(scope.iter().map(|s| s.as_str()));
+                        match git::run_git(&args) {
+                            Ok(_) => {
+                                self.set_status(format!(
+                                    "{} All files staged (secrets warning overridden)",
+                                    Icon::Warning
+                                ));
+                            }
+                            Err(e) => {
+                                self.set_status(format!("Failed to stage: {}", e));
+                            }
+                        }
+                        self.staging_state.refresh();
+                    }
+                    SecretPendingAction::StagePaths(paths) => {
+                        let mut args = vec!["add", "-A", "--"];
+                        args.extend(paths.iter().map(|p| p.as_str()));
+                        match git::run_git(&args) {
                             Ok(_) => {
-                                self.set_status("⚠ All files staged (secrets warning overridden)");
+                                self.set_status(format!(
+                                    "{} Staged {} file(s) with secrets",
+                                    Icon::Warning,
+                                    paths.len()
+                                ));
                             }
                             Err(e) => {
                                 self.set_status(format!("Failed to stage: {}", e));
                             }
                         }
+                        self.staging_state.exit_multi_select();
                         self.staging_state.refresh();
                     }
                     SecretPendingAction::Commit => {
@@ -771,19 +1371,30 @@ impl App {
                 }
             }
             ConfirmAction::ForceCommitWithSecrets => {
-                let msg = self.commit_state.message.trim().to_string();
-                match git::run_git(&["commit", "-m", &msg]) {
+                let msg = self
+                    .commit_state
+                    .final_message(self.config.commit.gitmoji_prefix);
+                let options = git::commit::CommitOptions {
+                    sign_off: self.commit_state.sign_off,
+                    ..self.commit_state.options.clone()
+                };
+                let leave_out = self.commit_state.leave_out();
+                match git::commit::commit(&msg, &options, &leave_out) {
                     Ok(output) => {
                         self.set_status(format!(
-                            "⚠ {}",
+                            "{} {}",
+                            Icon::Warning,
                             output
                                 .lines()
                                 .next()
                                 .unwrap_or("Committed (secrets warning overridden)")
                         ));
                         self.commit_state.message.clear();
+                        self.commit_state.ai_previous = None;
+                        self.commit_state.excluded.clear();
+                        self.commit_state.options = Default::default();
                         self.commit_state.editing = true;
-                        self.view = View::Dashboard;
+                        self.open_view(View::Dashboard);
                         self.dashboard_state.refresh();
                     }
                     Err(e) => {
@@ -803,8 +1414,15 @@ impl App {
                 InputAction::AiSetupProvider
                     | InputAction::AiSetupModel
                     | InputAction::AiSetupEndpoint
-                    | InputAction::AiSetupApiKey
-                    | InputAction::StashPush
+                    | InputAction::AiSetupApiKey(_)
+                    | InputAction::StashPush(_)
+                    | InputAction::FilterIssues
+                    | InputAction::IssueBody(_)
+                    | InputAction::IssueLabels(_)
+                    | InputAction::PrReview(..)
+                    | InputAction::RecipeParam(..)
+                    | InputAction::PathScope
+                    | InputAction::TagMessage(_)
             )
         {
             return Ok(());
@@ -820,6 +1438,78 @@ impl App {
                 }
                 self.branches_state.refresh();
             }
+            InputAction::CreateBranchAt(commit) => {
+                let name = value.trim();
+                match git::BranchOps::create(name, Some(&commit)) {
+                    Ok(()) => self.set_status(format!(
+                        "Created branch '{}' at {}",
+                        name,
+                        &commit[..7.min(commit.len())]
+                    )),
+                    Err(e) => self.set_status(format!("Error: {}", e)),
+                }
+                self.branches_state.refresh();
+                if self.reflog_state.lost.is_some() {
+                    self.reflog_state.load_lost();
+                }
+            }
+            InputAction::BlameFile(commit) => blame::open(self, value.trim(), Some(commit), None),
+            InputAction::CreateTag => {
+                self.popup = Popup::Input {
+                    title: format!("New Tag {}", value.trim()),
+                    prompt: "Message (empty for a lightweight tag): ".to_string(),
+                    value: String::new(),
+                    on_submit: InputAction::TagMessage(value.trim().to_string()),
+                };
+            }
+            InputAction::TagMessage(name) => {
+                let message = value.trim();
+                let annotated = !message.is_empty();
+                match git::tag::create(&name, annotated.then_some(message)) {
+                    Ok(_) => self.set_status(format!(
+                        "Created {} tag '{}' — P to push it",
+                        if annotated {
+                            "annotated"
+                        } else {
+                            "lightweight"
+                        },
+                        name
+                    )),
+                    Err(e) => self.set_status(format!("Error: {}", e)),
+                }
+                self.tags_state.refresh();
+            }
+            InputAction::RebaseReword(index) => rebase::reword(self, index, &value),
+            InputAction::AliasArgs(name) => {
+                dashboard::run_alias(self, &name, git::alias::split_args(&value))
+            }
+            InputAction::PathScope => {
+                let scope = match git::run_git(&["rev-parse", "--show-toplevel"]) {
+                    Ok(root) => git::scope::resolve(
+                        std::path::Path::new(value.trim()),
+                        std::path::Path::new(root.trim()),
+                    ),
+                    Err(e) => Err(e),
+                };
+                match scope {
+                    Ok(scope) => self.set_path_scope(scope),
+                    Err(e) => self.set_status(format!("Error: {:#}", e)),
+                }
+            }
+            InputAction::RecipeParam(recipe, mut values) => {
+                let param = &recipe.params[values.len()];
+                match (value.trim(), &param.default) {
+                    ("", None) => self.set_status(format!("Recipe {} cancelled", recipe.name)),
+                    ("", Some(default)) => {
+                        values.push(default.clone());
+                        dashboard::ask_recipe_param(self, recipe, values);
+                    }
+                    (value, _) => {
+                        values.push(value.to_string());
+                        dashboard::ask_recipe_param(self, recipe, values);
+                    }
+                }
+            }
             InputAction::RenameBranch => {
                 match git::BranchOps::rename(value.trim()) {
                     Ok(()) => self.status_message = Some(format!("Renamed to '{}'", value.trim())),
@@ -827,6 +1517,21 @@ impl App {
                 }
                 self.branches_state.refresh();
             }
+            InputAction::SearchPane => {
+                let query = value.trim().to_string();
+                if let Some((lines, scroll, search)) = self.searchable_pane() {
+                    let mut found = PaneSearch::new(&query, *scroll as usize);
+                    let message = match found.first(lines) {
+                        Ok(message) => {
+                            *scroll = found.scroll();
+                            *search = Some(found);
+                            message
+                        }
+                        Err(message) => message,
+                    };
+                    self.set_status(message);
+                }
+            }
             InputAction::SearchCommits => {
                 self.timeline_state.search_query = value;
                 self.timeline_state.do_search();
@@ -837,6 +1542,77 @@ impl App {
             InputAction::CommitMessage => {
                 // Handled in commit view
             }
+            InputAction::CommitAuthor => {
+                let author = value.trim();
+                if author.is_empty() {
+                    self.commit_state.options.author = None;
+                } else if git::commit::is_author(author) {
+                    self.commit_state.options.author = Some(author.to_string());
+                } else {
+                    self.set_status("Author must look like: Name <email>");
+                }
+            }
+            InputAction::CommitDate => {
+                let date = value.trim();
+                self.commit_state.options.date = (!date.is_empty()).then(|| date.to_string());
+            }
+            InputAction::RestoreFileFrom(commit) => {
+                let path = value.trim();
+                if !path.is_empty() {
+                    time_travel::restore_file(self, &commit, path);
+                }
+            }
+            InputAction::BranchHere => {
+                match git::BranchOps::create_and_switch(value.trim()) {
+                    Ok(()) => self.set_status(format!("Switched to new branch '{}'", value.trim())),
+                    Err(e) => self.set_status(format!("Error: {}", e)),
+                }
+                self.refresh();
+            }
+            InputAction::RefineCommitMessage => {
+                if !value.trim().is_empty() {
+                    self.start_ai_refine(CommitRefinement::Custom(value));
+                }
+            }
+            InputAction::FilterIssues => {
+                self.github_state.issues_state.query = value.trim().to_string();
+                self.github_state.issues_state.select(0);
+            }
+            InputAction::IssueTitle => {
+                self.popup = Popup::Input {
+                    title: format!("New Issue: {}", value.trim()),
+                    prompt: "Description (optional): ".to_string(),
+                    value: String::new(),
+                    on_submit: InputAction::IssueBody(value.trim().to_string()),
+                };
+            }
+            InputAction::IssueBody(title) => {
+                github::create_issue(self, title, value.trim().to_string());
+            }
+            InputAction::IssueComment(number) => {
+                let body = value.trim().to_string();
+                github::update_issue(self, number, move |token| {
+                    git::github_auth::comment_on_issue(token, number, &body)?;
+                    Ok(format!("✓ Commented on #{}", number))
+                });
+            }
+            InputAction::IssueLabels(number) => github::set_issue_labels(self, number, &value),
+            InputAction::PrReview(number, event) => {
+                if value.trim().is_empty() && event != "APPROVE" {
+                    self.set_status("Write what the review says — only approvals can be empty");
+                } else {
+                    github::submit_review(self, number, event, value.trim().to_string());
+                }
+            }
+            InputAction::PrLineComment(number, mut comment) => {
+                comment.body = value.trim().to_string();
+                github::comment_on_line(self, number, comment);
+            }
+            InputAction::PrTitle => {
+                if let Some(form) = self.github_state.pr_state.new_pr.as_mut() {
+                    form.title = value.trim().to_string();
+                }
+            }
             InputAction::AddCollaborator => {
                 let username = value.trim().to_string();
                 if let Some(token) = self.config.github.get_token() {
@@ -865,6 +1641,19 @@ impl App {
             InputAction::AiSetupProvider => {
                 // Step 1 — pick provider by number
                 let choice = value.trim();
+                if self.config.ai.enabled {
+                    match choice {
+                        "k" | "K" => {
+                            self.edit_ai_key();
+                            return Ok(());
+                        }
+                        "e" | "E" => {
+                            self.edit_ai_endpoint();
+                            return Ok(());
+                        }
+                        _ => {}
+                    }
+                }
                 let provider = match choice {
                     "1" => "bedrock",
                     "2" => "openai",
@@ -884,7 +1673,7 @@ impl App {
                     "bedrock" => {
                         // Bedrock needs endpoint
                         self.popup = Popup::Input {
-                            title: "🤖 AI Setup — Bedrock (2/3)".to_string(),
+                            title: format!("{} AI Setup — Bedrock (2/3)", Icon::Ai),
                             prompt: "Lambda Endpoint URL: ".to_string(),
                             value: self.config.ai.effective_endpoint().unwrap_or_default(),
                             on_submit: InputAction::AiSetupEndpoint,
@@ -893,7 +1682,7 @@ impl App {
                     "ollama" => {
                         // Ollama: optional endpoint, no key needed
                         self.popup = Popup::Input {
-                            title: "🤖 AI Setup — Ollama (2/2)".to_string(),
+                            title: format!("{} AI Setup — Ollama (2/2)", Icon::Ai),
                             prompt: "Ollama URL (Enter for default): ".to_string(),
                             value: "http://localhost:11434".to_string(),
                             on_submit: InputAction::AiSetupEndpoint,
@@ -904,7 +1693,7 @@ impl App {
                         self.config.ai.endpoint = None;
                         self.ai_setup_endpoint = None;
                         self.popup = Popup::Input {
-                            title: "🤖 AI Setup — OpenRouter (2/3)".to_string(),
+                            title: format!("{} AI Setup — OpenRouter (2/3)", Icon::Ai),
                             prompt: "Model (e.g. anthropic/claude-sonnet-4): ".to_string(),
                             value: self
                                 .config
@@ -920,10 +1709,10 @@ impl App {
                         self.config.ai.endpoint = None;
                         self.ai_setup_endpoint = None;
                         self.popup = Popup::Input {
-                            title: format!("🤖 AI Setup — {} (2/2)", provider),
+                            title: format!("{} AI Setup — {} (2/2)", Icon::Ai, provider),
                             prompt: "API Key: ".to_string(),
                             value: self.config.ai.resolved_api_key().unwrap_or_default(),
-                            on_submit: InputAction::AiSetupApiKey,
+                            on_submit: InputAction::AiSetupApiKey(provider.to_string()),
                         };
                     }
                 }
@@ -935,10 +1724,10 @@ impl App {
                     self.config.ai.model = Some(model);
                 }
                 self.popup = Popup::Input {
-                    title: "🤖 AI Setup — OpenRouter (3/3)".to_string(),
+                    title: format!("{} AI Setup — OpenRouter (3/3)", Icon::Ai),
                     prompt: "API Key (Bearer token): ".to_string(),
                     value: self.config.ai.resolved_api_key().unwrap_or_default(),
-                    on_submit: InputAction::AiSetupApiKey,
+                    on_submit: InputAction::AiSetupApiKey("openrouter".to_string()),
                 };
             }
             InputAction::AiSetupEndpoint => {
@@ -954,35 +1743,29 @@ impl App {
                 }
 
                 if !endpoint.is_empty() {
+                    if let Err(error) = crate::ai::setup::probe(&endpoint) {
+                        self.reopen_input(value, InputAction::AiSetupEndpoint, error);
+                        return Ok(());
+                    }
                     self.ai_setup_endpoint = Some(endpoint.clone());
                     self.config.ai.endpoint = Some(endpoint);
                 }
 
                 if provider == "ollama" {
                     // Ollama doesn't need API key — finish setup
-                    self.config.ai.enabled = true;
-                    match self.config.save() {
-                        Ok(()) => {}
-                        Err(e) => self.set_status(format!("⚠ Config save failed: {}", e)),
-                    }
-                    self.ai_client = AiClient::from_config(&self.config.ai).map(Arc::new);
-                    if self.ai_client.is_some() {
-                        self.set_status("✓ Ollama configured! Testing connection...");
-                        self.start_ai_query("health_check".to_string(), None);
-                    } else {
-                        self.set_status("Ollama setup failed — is Ollama running? (ollama serve)");
-                    }
+                    self.ai_setup_provider = None;
+                    self.finish_ai_setup("Ollama");
                 } else {
                     // Bedrock: now ask for API key
                     self.popup = Popup::Input {
-                        title: "🤖 AI Setup — Bedrock (3/3)".to_string(),
+                        title: format!("{} AI Setup — Bedrock (3/3)", Icon::Ai),
                         prompt: "API Key: ".to_string(),
                         value: self.config.ai.resolved_api_key().unwrap_or_default(),
-                        on_submit: InputAction::AiSetupApiKey,
+                        on_submit: InputAction::AiSetupApiKey(provider),
                     };
                 }
             }
-            InputAction::AiSetupApiKey => {
+            InputAction::AiSetupApiKey(provider) => {
                 let api_key = value.trim().to_string();
                 let endpoint = self.ai_setup_endpoint.take();
 
@@ -991,35 +1774,53 @@ impl App {
                     return Ok(());
                 }
 
-                self.config.ai.enabled = true;
                 if let Some(ep) = endpoint {
                     self.config.ai.endpoint = Some(ep);
                 }
-                self.config.ai.api_key = Some(api_key);
-
-                match self.config.save() {
-                    Ok(()) => {}
-                    Err(e) => self.set_status(format!("⚠ Config in memory but save failed: {}", e)),
+                let in_keychain = crate::keychain::save_ai_api_key(&mut self.config.ai, &api_key);
+                self.ai_setup_provider = None;
+                self.finish_ai_setup(&provider);
+                if !in_keychain && self.ai_client.is_some() {
+                    self.set_status(format!(
+                        "✓ {} configured — keychain unavailable, key saved in config.toml. Testing connection...",
+                        provider
+                    ));
                 }
-                self.ai_client = AiClient::from_config(&self.config.ai).map(Arc::new);
-                if self.ai_client.is_some() {
-                    let pname = self.ai_setup_provider.take().unwrap_or("AI".to_string());
-                    self.set_status(format!("✓ {} configured! Testing connection...", pname));
-                    self.start_ai_query("health_check".to_string(), None);
-                } else {
-                    self.set_status("AI setup failed — could not create client");
+            }
+            InputAction::AiEditEndpoint => {
+                let endpoint = value.trim().to_string();
+                if endpoint.is_empty() {
+                    self.set_status("Endpoint unchanged");
+                    return Ok(());
+                }
+                if let Err(error) = crate::ai::setup::probe(&endpoint) {
+                    self.reopen_input(value, InputAction::AiEditEndpoint, error);
+                    return Ok(());
                 }
+                self.config.ai.endpoint = Some(endpoint);
+                let provider = self.config.ai.effective_provider().to_string();
+                self.finish_ai_setup(&provider);
             }
-            InputAction::StashPush => {
+            InputAction::StashPush(options) => {
                 let msg = if value.trim().is_empty() {
                     None
                 } else {
                     Some(value.trim())
                 };
-                match git::stash::stash_push(msg) {
+                match git::stash::stash_push(msg, &options) {
                     Ok(_) => {
                         let label = msg.unwrap_or("(default)");
-                        self.set_status(format!("Stashed changes: {}", label));
+                        if options.paths.is_empty() {
+                            self.set_status(format!("Stashed changes: {}", label));
+                        } else {
+                            self.set_status(format!(
+                                "Stashed {} file(s): {}",
+                                options.paths.len(),
+                                label
+                            ));
+                            self.staging_state.exit_multi_select();
+                            self.staging_state.refresh();
+                        }
                     }
                     Err(e) => {
                         let err_str = e.to_string();
@@ -1035,29 +1836,104 @@ impl App {
 
     /// Launch the interactive AI setup wizard.
     pub fn start_ai_setup(&mut self) {
+        // Once set up, the endpoint or key can be changed on its own
+        let edit = if self.config.ai.enabled {
+            "  k) Change API key only\n  e) Change endpoint only\n"
+        } else {
+            ""
+        };
         self.popup = Popup::Input {
-            title: "🤖 AI Provider Setup (1/3)".to_string(),
-            prompt: "Choose provider (1-5):\n  1) Bedrock ⭐ (recommended)\n  2) OpenAI\n  3) Anthropic\n  4) OpenRouter\n  5) Ollama (local)\n> ".to_string(),
+            title: format!("{} AI Provider Setup (1/3)", Icon::Ai),
+            prompt: format!(
+                "Choose provider (1-5):\n  1) Bedrock {} (recommended)\n  2) OpenAI\n  3) Anthropic\n  4) OpenRouter\n  5) Ollama (local)\n{}> ",
+                Icon::Star,
+                edit
+            ),
             value: "1".to_string(),
             on_submit: InputAction::AiSetupProvider,
         };
     }
 
-    /// Auto-suggest commit message if AI is available, message is empty, and files are staged.
-    pub fn auto_suggest_if_ready(&mut self) {
-        if self.ai_client.is_some()
-            && self.commit_state.message.is_empty()
-            && !self.commit_state.staged_files.is_empty()
-            && !self.ai_loading
-        {
-            self.start_ai_suggest();
+    /// Ask for a new API key for the configured provider, keeping the rest.
+    pub fn edit_ai_key(&mut self) {
+        let provider = self.config.ai.effective_provider().to_string();
+        if provider == "ollama" {
+            self.set_status("Ollama doesn't use an API key");
+            return;
         }
-    }
+        self.popup = Popup::Input {
+            title: format!("{} Change API Key — {}", Icon::Ai, provider),
+            prompt: "API Key: ".to_string(),
+            value: String::new(),
+            on_submit: InputAction::AiSetupApiKey(provider),
+        };
+    }
+
+    /// Ask for a new endpoint for the configured provider, keeping the rest.
+    pub fn edit_ai_endpoint(&mut self) {
+        self.ai_setup_provider = None;
+        self.popup = Popup::Input {
+            title: format!(
+                "{} Change Endpoint — {}",
+                Icon::Ai,
+                self.config.ai.effective_provider()
+            ),
+            prompt: "Endpoint URL: ".to_string(),
+            value: self.config.ai.effective_endpoint().unwrap_or_default(),
+            on_submit: InputAction::AiEditEndpoint,
+        };
+    }
+
+    /// Show an input again with what was typed and why it was rejected.
+    fn reopen_input(&mut self, value: String, action: InputAction, error: String) {
+        let title = match &self.ai_setup_provider {
+            Some(provider) => format!("AI Setup — {}", provider),
+            None => "Change Endpoint".to_string(),
+        };
+        self.popup = Popup::Input {
+            title: format!("{} {}", Icon::Ai, title),
+            prompt: "Endpoint URL: ".to_string(),
+            value,
+            on_submit: action,
+        };
+        self.input_error = Some(error);
+    }
+
+    /// Save the AI config from the wizard, rebuild the client and check it
+    /// answers; a failed check says which step to redo.
+    fn finish_ai_setup(&mut self, provider: &str) {
+        self.config.ai.enabled = true;
+        if let Err(e) = self.config.save() {
+            self.set_status(format!(
+                "{} Config in memory but save failed: {}",
+                Icon::Warning,
+                e
+            ));
+        }
+        self.ai_client = AiClient::from_config(&self.config.ai).map(Arc::new);
+        if self.ai_client.is_some() {
+            self.set_status(format!("✓ {} configured! Testing connection...", provider));
+            self.start_ai_query("health_check".to_string(), None);
+        } else {
+            self.set_status("AI setup failed — could not create client");
+        }
+    }
+
+    /// Auto-suggest commit message if AI is available, message is empty, and files are staged.
+    pub fn auto_suggest_if_ready(&mut self) {
+        if self.ai_client.is_some()
+            && self.commit_state.message.is_empty()
+            && !self.commit_state.staged_files.is_empty()
+            && !self.ai_loading
+        {
+            self.start_ai_suggest();
+        }
+    }
 
     /// Start an async AI commit message suggestion (non-blocking).
     pub fn start_ai_suggest(&mut self) {
         if self.ai_loading {
-            self.set_status("⏳ AI is already generating...");
+            self.set_status(format!("{} AI is already generating...", Icon::Loading));
             return;
         }
         let client = match self.ai_client {
@@ -1070,21 +1946,64 @@ impl App {
 
         self.ai_loading = true;
         self.ai_action = Some(AiAction::CommitSuggest);
-        self.set_status("⏳ Generating AI commit message...");
+        self.set_status(format!("{} Generating AI commit message...", Icon::Loading));
 
         let (tx, rx) = mpsc::channel();
         self.ai_receiver = Some(rx);
 
-        std::thread::spawn(move || {
+        self.submit_ai(move || {
             let result = client.suggest_commit_message().map_err(|e| e.to_string());
             let _ = tx.send(result);
         });
     }
 
+    /// Regenerate the AI commit message from the previous suggestion — non-blocking.
+    /// The current message (if any) is the suggestion being refined.
+    pub fn start_ai_refine(&mut self, refinement: CommitRefinement) {
+        if self.ai_loading {
+            self.set_status(format!("{} AI is already generating...", Icon::Loading));
+            return;
+        }
+        let client = match self.ai_client {
+            Some(ref c) => Arc::clone(c),
+            None => {
+                self.set_status("AI not configured. Set [ai] in ~/.config/zit/config.toml or export ZIT_AI_API_KEY + ZIT_AI_ENDPOINT");
+                return;
+            }
+        };
+        let previous = if self.commit_state.message.trim().is_empty() {
+            self.commit_state.ai_previous.clone()
+        } else {
+            Some(self.commit_state.message.clone())
+        };
+        let Some(previous) = previous else {
+            self.set_status("No AI suggestion to refine yet — press G first");
+            return;
+        };
+
+        self.ai_loading = true;
+        self.ai_action = Some(AiAction::CommitSuggest);
+        self.set_status(format!(
+            "{} Regenerating commit message ({})...",
+            Icon::Loading,
+            refinement.label()
+        ));
+
+        let (tx, rx) = mpsc::channel();
+        self.ai_receiver = Some(rx);
+
+        self.submit_ai(move || {
+            let result = client
+                .refine_commit_message(&previous, &refinement)
+                .map_err(|e| e.to_string());
+            let _ = tx.send(result);
+        });
+    }
+
     /// Start an async AI query (explain_repo, recommend, health_check) — non-blocking.
     pub fn start_ai_query(&mut self, action_type: String, query: Option<String>) {
         if self.ai_loading {
-            self.set_status("⏳ AI is already generating...");
+            self.set_status(format!("{} AI is already generating...", Icon::Loading));
             return;
         }
         let client = match self.ai_client {
@@ -1104,13 +2023,13 @@ impl App {
 
         self.ai_loading = true;
         self.ai_action = Some(action.clone());
-        self.set_status("⏳ Asking AI mentor...");
+        self.set_status(format!("{} Asking AI mentor...", Icon::Loading));
 
         let (tx, rx) = mpsc::channel();
         self.ai_receiver = Some(rx);
         let query_clone = query;
 
-        std::thread::spawn(move || {
+        self.submit_ai(move || {
             let result = match action {
                 AiAction::ExplainRepo => client
                     .explain_repo(query_clone.as_deref())
@@ -1138,13 +2057,17 @@ impl App {
 
         self.ai_loading = true;
         self.ai_action = Some(AiAction::ExplainError(error_msg.clone()));
-        self.set_status("⏳ AI is analyzing the error...");
+        self.set_status(format!("{} AI is analyzing the error...", Icon::Loading));
+        // Before later commands push it out of the journal
+        let failed = git::runner::failed_command(&error_msg);
 
         let (tx, rx) = mpsc::channel();
         self.ai_receiver = Some(rx);
 
-        std::thread::spawn(move || {
-            let result = client.explain_error(&error_msg).map_err(|e| e.to_string());
+        self.submit_ai(move || {
+            let result = client
+                .explain_error(&error_msg, failed.as_ref())
+                .map_err(|e| e.to_string());
             let _ = tx.send(result);
         });
     }
@@ -1152,7 +2075,7 @@ impl App {
     /// Start an async AI diff review for a specific file — non-blocking.
     pub fn start_ai_diff_review(&mut self, file_path: String, diff_content: String) {
         if self.ai_loading {
-            self.set_status("⏳ AI is already working...");
+            self.set_status(format!("{} AI is already working...", Icon::Loading));
             return;
         }
         let client = match self.ai_client {
@@ -1170,12 +2093,16 @@ impl App {
 
         self.ai_loading = true;
         self.ai_action = Some(AiAction::ReviewDiff(file_path.clone()));
-        self.set_status(format!("⏳ AI reviewing diff for {}...", file_path));
+        self.set_status(format!(
+            "{} AI reviewing diff for {}...",
+            Icon::Loading,
+            file_path
+        ));
 
         let (tx, rx) = mpsc::channel();
         self.ai_receiver = Some(rx);
 
-        std::thread::spawn(move || {
+        self.submit_ai(move || {
             let result = client
                 .review_diff(&file_path, &diff_content)
                 .map_err(|e| e.to_string());
@@ -1186,7 +2113,7 @@ impl App {
     /// Start an async AI free-form question — non-blocking.
     pub fn start_ai_ask(&mut self, question: String) {
         if self.ai_loading {
-            self.set_status("⏳ AI is already working...");
+            self.set_status(format!("{} AI is already working...", Icon::Loading));
             return;
         }
         let client = match self.ai_client {
@@ -1199,21 +2126,29 @@ impl App {
 
         self.ai_loading = true;
         self.ai_action = Some(AiAction::AskQuestion);
-        self.set_status("⏳ Asking AI mentor...");
+        self.set_status(format!("{} Asking AI mentor...", Icon::Loading));
 
         let (tx, rx) = mpsc::channel();
         self.ai_receiver = Some(rx);
 
-        std::thread::spawn(move || {
+        self.submit_ai(move || {
             let result = client.ask(&question).map_err(|e| e.to_string());
             let _ = tx.send(result);
         });
     }
 
+    /// Open the AI Mentor on the dashboard and ask it to teach `topic`.
+    fn open_learn_topic(&mut self, topic: &str) {
+        self.open_view(View::Dashboard);
+        self.dashboard_state.focus = dashboard::DashboardFocus::Right;
+        self.ai_mentor_state.last_action = Some("Learn".to_string());
+        self.start_ai_learn(topic.to_string());
+    }
+
     /// Start an async AI learn query — non-blocking.
     pub fn start_ai_learn(&mut self, topic: String) {
         if self.ai_loading {
-            self.set_status("⏳ AI is already working...");
+            self.set_status(format!("{} AI is already working...", Icon::Loading));
             return;
         }
         let client = match self.ai_client {
@@ -1226,12 +2161,12 @@ impl App {
 
         self.ai_loading = true;
         self.ai_action = Some(AiAction::Learn);
-        self.set_status("⏳ AI is teaching...");
+        self.set_status(format!("{} AI is teaching...", Icon::Loading));
 
         let (tx, rx) = mpsc::channel();
         self.ai_receiver = Some(rx);
 
-        std::thread::spawn(move || {
+        self.submit_ai(move || {
             let result = client.learn(&topic).map_err(|e| e.to_string());
             let _ = tx.send(result);
         });
@@ -1240,7 +2175,7 @@ impl App {
     /// Start an async AI merge conflict resolution — non-blocking.
     pub fn start_ai_merge_resolve(&mut self, file_path: String, conflict_content: String) {
         if self.ai_loading {
-            self.set_status("⏳ AI is already working...");
+            self.set_status(format!("{} AI is already working...", Icon::Loading));
             return;
         }
         let client = match self.ai_client {
@@ -1258,12 +2193,16 @@ impl App {
 
         self.ai_loading = true;
         self.ai_action = Some(AiAction::MergeResolve(file_path.clone()));
-        self.set_status(format!("⏳ AI analyzing conflict in {}...", file_path));
+        self.set_status(format!(
+            "{} AI analyzing conflict in {}...",
+            Icon::Loading,
+            file_path
+        ));
 
         let (tx, rx) = mpsc::channel();
         self.ai_receiver = Some(rx);
 
-        std::thread::spawn(move || {
+        self.submit_ai(move || {
             let result = client
                 .suggest_merge_resolution(&file_path, &conflict_content)
                 .map_err(|e| e.to_string());
@@ -1271,10 +2210,63 @@ impl App {
         });
     }
 
+    /// Start an async AI resolution for one conflict region of the selected file — non-blocking.
+    pub fn start_ai_region_resolve(&mut self, index: usize) {
+        if self.ai_loading {
+            self.set_status(format!("{} AI is already working...", Icon::Loading));
+            return;
+        }
+        let client = match self.ai_client {
+            Some(ref c) => Arc::clone(c),
+            None => {
+                self.set_status("AI not configured — press 'a' to open AI Mentor and set up");
+                return;
+            }
+        };
+
+        let state = &self.merge_resolve_state;
+        let (Some(file_path), Some(content)) = (
+            state
+                .conflicted_files
+                .get(state.selected_file)
+                .map(|f| f.path.clone()),
+            state.raw_conflict_content.clone(),
+        ) else {
+            self.set_status("No conflict content to analyze");
+            return;
+        };
+        if index >= state.conflict_regions.len() {
+            self.set_status("No conflict region selected");
+            return;
+        }
+        let regions = state.conflict_regions.clone();
+
+        self.ai_loading = true;
+        self.ai_action = Some(AiAction::MergeResolveRegion(file_path.clone(), index));
+        self.set_status(format!(
+            "{} AI analyzing region {} of {} in {}...",
+            Icon::Loading,
+            index + 1,
+            regions.len(),
+            file_path
+        ));
+
+        let (tx, rx) = mpsc::channel();
+        self.ai_receiver = Some(rx);
+
+        self.submit_ai(move || {
+            let result = client
+                .suggest_region_resolution(&file_path, &content, &regions, index)
+                .map_err(|e| e.to_string());
+            let _ = tx.send(result);
+        });
+    }
+
     /// Start an async AI merge strategy recommendation — non-blocking.
-    pub fn start_ai_merge_strategy(&mut self, query: Option<String>) {
+    /// `target` is the branch to integrate (defaults to the other side of an in-progress merge).
+    pub fn start_ai_merge_strategy(&mut self, target: Option<String>, query: Option<String>) {
         if self.ai_loading {
-            self.set_status("⏳ AI is already working...");
+            self.set_status(format!("{} AI is already working...", Icon::Loading));
             return;
         }
         let client = match self.ai_client {
@@ -1287,14 +2279,14 @@ impl App {
 
         self.ai_loading = true;
         self.ai_action = Some(AiAction::MergeStrategy);
-        self.set_status("⏳ AI analyzing merge strategy...");
+        self.set_status(format!("{} AI analyzing merge strategy...", Icon::Loading));
 
         let (tx, rx) = mpsc::channel();
         self.ai_receiver = Some(rx);
 
-        std::thread::spawn(move || {
+        self.submit_ai(move || {
             let result = client
-                .suggest_merge_strategy(query.as_deref())
+                .suggest_merge_strategy(target.as_deref(), query.as_deref())
                 .map_err(|e| e.to_string());
             let _ = tx.send(result);
         });
@@ -1310,7 +2302,7 @@ impl App {
     ) {
         if self.ai_loading {
             self.time_travel_state.ai_suggestion = Some(
-                "⏳ AI is already working on another request...\n\nPlease wait for it to finish, then try again."
+                format!("{} AI is already working on another request...\n\nPlease wait for it to finish, then try again.", Icon::Loading)
                     .to_string(),
             );
             return;
@@ -1319,14 +2311,17 @@ impl App {
             Some(ref c) => Arc::clone(c),
             None => {
                 self.time_travel_state.ai_suggestion = Some(
-                    "⚠ AI is not configured.\n\n\
+                    format!(
+                        "{} AI is not configured.\n\n\
                      To set up AI Mentor:\n\
                      1. Press [Esc] to dismiss this panel\n\
                      2. Press [q] to go back to Dashboard\n\
                      3. Press [a] to open AI Mentor\n\
                      4. Follow the setup wizard to add your API endpoint and key\n\n\
-                     Once configured, come back here and press [i] again!"
-                        .to_string(),
+                     Once configured, come back here and press [i] again!",
+                        Icon::Warning
+                    )
+                    .to_string(),
                 );
                 self.time_travel_state.ai_loading = false;
                 return;
@@ -1337,12 +2332,12 @@ impl App {
         self.ai_action = Some(AiAction::ResetSuggest);
         self.time_travel_state.ai_loading = true;
         self.time_travel_state.ai_suggestion = None;
-        self.set_status("⏳ AI analyzing reset options...");
+        self.set_status(format!("{} AI analyzing reset options...", Icon::Loading));
 
         let (tx, rx) = mpsc::channel();
         self.ai_receiver = Some(rx);
 
-        std::thread::spawn(move || {
+        self.submit_ai(move || {
             let result = client
                 .suggest_reset(&current_hash, &target_hash, &target_msg, commits_back)
                 .map_err(|e| e.to_string());
@@ -1353,7 +2348,7 @@ impl App {
     /// Start an async AI .gitignore generation — non-blocking.
     pub fn start_ai_gitignore(&mut self) {
         if self.ai_loading {
-            self.set_status("⏳ AI is already generating...");
+            self.set_status(format!("{} AI is already generating...", Icon::Loading));
             return;
         }
         let client = match self.ai_client {
@@ -1367,23 +2362,146 @@ impl App {
         self.ai_loading = true;
         self.ai_action = Some(AiAction::GenerateGitignore);
         self.ai_mentor_state.last_action = Some("Generate .gitignore".to_string());
-        self.set_status("⏳ AI is analyzing project structure...");
+        self.set_status(format!(
+            "{} AI is analyzing project structure...",
+            Icon::Loading
+        ));
 
         let (tx, rx) = mpsc::channel();
         self.ai_receiver = Some(rx);
 
-        std::thread::spawn(move || {
+        self.submit_ai(move || {
             let result = client.generate_gitignore().map_err(|e| e.to_string());
             let _ = tx.send(result);
         });
     }
 
+    /// Start an async AI summary of a contributing readiness report — non-blocking.
+    pub fn start_ai_contributing(&mut self, report: String, guide: String) {
+        if self.ai_loading {
+            self.set_status(format!("{} AI is already working...", Icon::Loading));
+            return;
+        }
+        let client = match self.ai_client {
+            Some(ref c) => Arc::clone(c),
+            None => {
+                self.set_status("AI not configured — press 'a' to open AI Mentor and set up");
+                return;
+            }
+        };
+
+        self.ai_loading = true;
+        self.ai_action = Some(AiAction::ContributingSummary);
+        self.set_status(format!(
+            "{} AI is reading the contributing guide...",
+            Icon::Loading
+        ));
+
+        let (tx, rx) = mpsc::channel();
+        self.ai_receiver = Some(rx);
+
+        self.submit_ai(move || {
+            let result = client
+                .summarize_contributing(&report, &guide)
+                .map_err(|e| e.to_string());
+            let _ = tx.send(result);
+        });
+    }
+
+    /// Start an async AI polish of drafted release notes — non-blocking.
+    pub fn start_ai_release_notes(&mut self, draft: String) {
+        if self.ai_loading {
+            self.set_status(format!("{} AI is already working...", Icon::Loading));
+            return;
+        }
+        let client = match self.ai_client {
+            Some(ref c) => Arc::clone(c),
+            None => {
+                self.set_status("AI not configured — press 'a' to open AI Mentor and set up");
+                return;
+            }
+        };
+
+        self.ai_loading = true;
+        self.ai_action = Some(AiAction::PolishReleaseNotes);
+        self.set_status(format!(
+            "{} AI is polishing the release notes...",
+            Icon::Loading
+        ));
+
+        let (tx, rx) = mpsc::channel();
+        self.ai_receiver = Some(rx);
+
+        self.submit_ai(move || {
+            let result = client
+                .polish_release_notes(&draft)
+                .map_err(|e| e.to_string());
+            let _ = tx.send(result);
+        });
+    }
+
+    /// Start an async AI changelog for a release of `tag` — non-blocking.
+    pub fn start_ai_changelog(&mut self, tag: String, commits: String) {
+        if self.ai_loading {
+            self.set_status(format!("{} AI is already working...", Icon::Loading));
+            return;
+        }
+        let Some(client) = self.ai_client.as_ref().map(Arc::clone) else {
+            return;
+        };
+
+        self.ai_loading = true;
+        self.ai_action = Some(AiAction::TagChangelog(tag));
+        self.set_status(format!("{} AI is writing the changelog...", Icon::Loading));
+
+        let (tx, rx) = mpsc::channel();
+        self.ai_receiver = Some(rx);
+
+        self.submit_ai(move || {
+            let result = client.draft_changelog(&commits).map_err(|e| e.to_string());
+            let _ = tx.send(result);
+        });
+    }
+
+    /// Start an async AI draft of the new pull request's title and
+    /// description from the commits `head` adds over `base` — non-blocking.
+    pub fn start_ai_pr_description(&mut self, base: String, head: String) {
+        if self.ai_loading {
+            self.set_status(format!("{} AI is already working...", Icon::Loading));
+            return;
+        }
+        let client = match self.ai_client {
+            Some(ref c) => Arc::clone(c),
+            None => {
+                self.set_status("AI not configured — press 'a' to open AI Mentor and set up");
+                return;
+            }
+        };
+
+        self.ai_loading = true;
+        self.ai_action = Some(AiAction::PullRequestDescription);
+        self.set_status(format!(
+            "{} AI is describing the pull request...",
+            Icon::Loading
+        ));
+
+        let (tx, rx) = mpsc::channel();
+        self.ai_receiver = Some(rx);
+
+        self.submit_ai(move || {
+            let result = git::pull_request::describe_input(&base, &head)
+                .and_then(|branch| client.describe_pull_request(&branch))
+                .map_err(|e| e.to_string());
+            let _ = tx.send(result);
+        });
+    }
+
     // ── Agent Mode ─────────────────────────────────────────────
 
     /// Start an async AI agent chat — non-blocking.
     pub fn start_agent_chat(&mut self) {
         if self.ai_loading {
-            self.set_status("⏳ AI is already processing...");
+            self.set_status(format!("{} AI is already processing...", Icon::Loading));
             return;
         }
         let client = match self.ai_client {
@@ -1405,7 +2523,7 @@ impl App {
         let (tx, rx) = mpsc::channel();
         self.ai_receiver = Some(rx);
 
-        std::thread::spawn(move || {
+        self.submit_ai(move || {
             let result = client.agent_chat(&user_message);
             // agent_chat returns a Receiver; wait for the final result
             match result.recv() {
@@ -1553,11 +2671,7 @@ impl App {
 
                     // Show proceed/revise/stop prompt
                     self.agent_state.tool_result_prompt = Some(agent::ToolResultPrompt {
-                        tool_name: format!(
-                            "{} {}",
-                            if cmd_str.starts_with("git ") { "" } else { "" },
-                            cmd_str
-                        ),
+                        tool_name: cmd_str.to_string(),
                         output_preview,
                     });
                 }
@@ -1717,7 +2831,23 @@ impl App {
     }
 
     /// Stop the agent loop and reset agent state.
+    /// Run the AI request `task` in the background. Only the request whose
+    /// receiver is in `ai_receiver` is wanted, so one still waiting in the
+    /// queue is dropped first.
+    fn submit_ai(&mut self, task: impl FnOnce() + Send + 'static) {
+        self.jobs.cancel("ai");
+        if !self.jobs.submit_write("ai", Priority::User, task) {
+            self.ai_loading = false;
+            self.ai_action = None;
+            self.ai_receiver = None;
+            self.time_travel_state.ai_loading = false;
+            self.agent_state.thinking = false;
+            self.set_status("AI request not sent: zit is shutting down");
+        }
+    }
+
     pub fn stop_agent(&mut self) {
+        self.jobs.cancel("ai");
         self.ai_loading = false;
         self.ai_action = None;
         self.ai_receiver = None;
@@ -1731,10 +2861,17 @@ impl App {
         match action {
             FollowUpAction::ApplyResolution(path) => {
                 if let Some(ref content) = self.merge_resolve_state.ai_resolved_content.clone() {
+                    self.merge_resolve_state
+                        .record_resolution(&path, "applied the AI resolution".to_string());
                     match git::merge::resolve_file(&path, content) {
                         Ok(()) => {
-                            self.set_status(format!("✓ Resolved and staged: {}", path));
-                            self.merge_resolve_state.refresh();
+                            let state = &mut self.merge_resolve_state;
+                            state.resolved_regions += state.conflict_regions.len();
+                            state.refresh();
+                            self.set_status(merge_resolve::resolved_file_status(
+                                &self.merge_resolve_state,
+                                &path,
+                            ));
                         }
                         Err(e) => {
                             self.set_status(format!("Error resolving: {}", e));
@@ -1742,34 +2879,34 @@ impl App {
                     }
                 }
             }
+            FollowUpAction::ApplyRegionResolution(index) => {
+                if index < self.merge_resolve_state.conflict_regions.len() {
+                    self.merge_resolve_state.selected_region = index;
+                    if let Err(e) = merge_resolve::apply_region_suggestion(self) {
+                        self.set_status(format!("Error resolving region: {}", e));
+                    }
+                }
+            }
             FollowUpAction::StageFile(path) => match git::run_git(&["add", &path]) {
                 Ok(_) => self.set_status(format!("✓ Staged: {}", path)),
                 Err(e) => self.set_status(format!("Error staging: {}", e)),
             },
             FollowUpAction::CommitNow => {
-                self.view = View::Commit;
+                self.open_view(View::Commit);
                 self.commit_state.refresh();
                 self.auto_suggest_if_ready();
             }
             FollowUpAction::SetCommitMessage(msg) => {
                 self.commit_state.message = msg;
                 self.commit_state.validate();
-                self.view = View::Commit;
+                self.open_view(View::Commit);
                 self.set_status("✓ AI commit message applied");
             }
             FollowUpAction::AbortMerge => {
-                self.popup = Popup::Confirm {
-                    title: "⚠ Abort Merge".to_string(),
-                    message: "This will discard all merge progress. Continue? (y/n)".to_string(),
-                    on_confirm: ConfirmAction::AbortMerge,
-                };
+                self.popup = merge_resolve::confirm_abort(self.merge_resolve_state.operation());
             }
             FollowUpAction::ContinueMerge => {
-                self.popup = Popup::Confirm {
-                    title: "Continue Merge".to_string(),
-                    message: "All conflicts resolved. Continue merge? (y/n)".to_string(),
-                    on_confirm: ConfirmAction::ContinueMerge,
-                };
+                self.popup = merge_resolve::confirm_continue(self.merge_resolve_state.operation());
             }
             FollowUpAction::ViewNextConflict => {
                 if self.merge_resolve_state.selected_file + 1
@@ -1782,8 +2919,36 @@ impl App {
             FollowUpAction::AskAiMore(question) => {
                 self.start_ai_ask(question);
             }
+            FollowUpAction::LearnTopic(topic) => self.open_learn_topic(&topic),
+            FollowUpAction::EditAiKey => self.edit_ai_key(),
+            FollowUpAction::EditAiEndpoint => self.edit_ai_endpoint(),
+            FollowUpAction::StartAiSetup => self.start_ai_setup(),
+            FollowUpAction::ReviewPullRequest(number, event) => {
+                let (title, prompt) = match event.as_str() {
+                    "APPROVE" => ("Approve", "Comment (optional): "),
+                    "REQUEST_CHANGES" => ("Request Changes", "What needs changing: "),
+                    _ => ("Comment", "Comment: "),
+                };
+                self.popup = Popup::Input {
+                    title: format!("{} #{}", title, number),
+                    prompt: prompt.to_string(),
+                    value: String::new(),
+                    on_submit: InputAction::PrReview(number, event),
+                };
+            }
+            FollowUpAction::SummarizeContributing(report, guide) => {
+                self.start_ai_contributing(report, guide);
+            }
+            FollowUpAction::CopyToClipboard(text) => match cli_clipboard::set_contents(text) {
+                Ok(()) => self.set_status("✓ Copied to clipboard"),
+                Err(e) => self.set_status(format!("Clipboard unavailable: {}", e)),
+            },
+            FollowUpAction::ConfirmSnippet(snippet) => ai_mentor::confirm_snippet(self, snippet),
+            FollowUpAction::PolishReleaseNotes(draft) => {
+                self.start_ai_release_notes(draft);
+            }
             FollowUpAction::SwitchToView(view) => {
-                self.view = view;
+                self.open_view(view);
                 self.refresh();
             }
             FollowUpAction::RunGitCommand(args) => {
@@ -1797,8 +2962,86 @@ impl App {
                     }
                 }
             }
+            FollowUpAction::RunGitSteps(title, commands) => {
+                let count = commands.len();
+                self.start_output_job(output_pane::OutputJob {
+                    title: title.clone(),
+                    commands,
+                    success_msg: format!("✓ {} — {} step(s) done", title, count),
+                    failure_msg: format!("{} stopped at a failing step (Ctrl+O for output)", title),
+                });
+            }
+            FollowUpAction::RefineCommitMessage(refinement) => {
+                self.open_view(View::Commit);
+                self.start_ai_refine(refinement);
+            }
+            FollowUpAction::PromptCommitRefinement => {
+                self.open_view(View::Commit);
+                self.popup = Popup::Input {
+                    title: format!("{} Refine Commit Message", Icon::Ai),
+                    prompt: "Instruction (e.g. mention the config change): ".to_string(),
+                    value: String::new(),
+                    on_submit: InputAction::RefineCommitMessage,
+                };
+            }
+            FollowUpAction::FixSpelling(word, replacement) => {
+                self.commit_state.replace_misspelling(&word, &replacement);
+                self.set_status(format!("✓ Replaced '{}' with '{}'", word, replacement));
+            }
+            FollowUpAction::AddSpellingWord(word) => {
+                self.commit_state.spellchecker.add_word(&word);
+                self.commit_state.validate();
+                if !self.config.spellcheck.words.contains(&word) {
+                    self.config.spellcheck.words.push(word.clone());
+                    let _ = self.config.save();
+                }
+                self.set_status(format!("✓ Added '{}' to the spellcheck dictionary", word));
+            }
+            FollowUpAction::SetRepoSignOff(on) => commit::set_sign_off(self, on),
+            FollowUpAction::SetStats(on) => {
+                self.config.stats.enabled = on;
+                crate::stats::set_enabled(on);
+                if let Err(e) = self.config.save() {
+                    self.set_status(format!("Error: {}", e));
+                } else if on {
+                    self.set_status("✓ Counting usage stats (S on the Dashboard to see them)");
+                } else {
+                    self.set_status("Usage stats off");
+                }
+            }
+            FollowUpAction::ResetStats => {
+                crate::stats::reset();
+                self.set_status("✓ Usage stats reset");
+            }
+            FollowUpAction::SubmoduleActions(path) => dashboard::open_submodule_actions(self, path),
+            FollowUpAction::CreateRelease { tag, notes, draft } => {
+                tags::publish_release(self, tag, notes, draft)
+            }
+            FollowUpAction::RenormalizeLineEndings(path) => {
+                match self.staging_state.renormalize_eol(&path) {
+                    Ok(msg) => self.set_status(msg),
+                    Err(e) => self.set_status(format!("Renormalize failed: {}", e)),
+                }
+            }
+            FollowUpAction::RestoreLineEndings(path, eol) => {
+                match self.staging_state.restore_eol(&path, eol) {
+                    Ok(msg) => self.set_status(msg),
+                    Err(e) => self.set_status(format!("Restoring line endings failed: {}", e)),
+                }
+            }
+            FollowUpAction::RunRecipe(recipe) => {
+                dashboard::ask_recipe_param(self, recipe, Vec::new())
+            }
+            FollowUpAction::SwitchBranch(name) => branches::switch_to(self, &name),
+            FollowUpAction::StashAndSwitchBranch(name) => branches::stash_and_switch(self, &name),
+            FollowUpAction::BranchHere => branches::branch_here(self),
+            FollowUpAction::RunAlias(alias) => dashboard::start_alias(self, alias),
+            FollowUpAction::LeaveDetachedHead(name) => {
+                branches::switch_to(self, &name);
+                self.refresh();
+            }
             FollowUpAction::EditCommitMessage => {
-                self.view = View::Commit;
+                self.open_view(View::Commit);
                 self.commit_state.refresh();
             }
             FollowUpAction::RegenerateAiSuggestion => {
@@ -1828,6 +3071,22 @@ impl App {
                     }
                 }
             }
+            FollowUpAction::JumpToConflictMarker(path) => {
+                self.open_view(View::Staging);
+                self.staging_state.refresh();
+                if self.staging_state.jump_to_marker_file(&path) {
+                    self.set_status(format!("Conflict markers in {} — fix and re-stage", path));
+                }
+            }
+            FollowUpAction::CommitIgnoringConflictMarkers => {
+                if let Err(e) = commit::commit_ignoring_conflict_markers(self) {
+                    self.set_status(format!("Commit failed: {}", e));
+                }
+            }
+            FollowUpAction::TimelineScope(scope) => {
+                self.timeline_state.search_query.clear();
+                self.timeline_state.set_scope(scope);
+            }
         }
     }
 
@@ -1866,12 +3125,11 @@ impl App {
                                 // Fallback: numbered list like "1. feat: ..."
                                 if let Some(msg) =
                                     trimmed.strip_prefix(|c: char| c.is_ascii_digit())
+                                    && let Some(msg) = msg.strip_prefix(['.', ')', ':'])
                                 {
-                                    if let Some(msg) = msg.strip_prefix(['.', ')', ':']) {
-                                        let msg = msg.trim();
-                                        if !msg.is_empty() && !msg.starts_with('[') {
-                                            raw_lines.push(msg.to_string());
-                                        }
+                                    let msg = msg.trim();
+                                    if !msg.is_empty() && !msg.starts_with('[') {
+                                        raw_lines.push(msg.to_string());
                                     }
                                 }
 
@@ -1929,8 +3187,17 @@ impl App {
                             if suggestions.is_empty() {
                                 self.set_status("AI returned an empty response. Try again.");
                             } else {
+                                // Remember what was suggested so the next round can build on it
+                                self.commit_state.ai_previous = Some(
+                                    suggestions
+                                        .iter()
+                                        .map(|s| s.label.as_str())
+                                        .collect::<Vec<_>>()
+                                        .join("\n"),
+                                );
+                                suggestions.extend(commit_refinement_follow_ups());
                                 self.popup = Popup::FollowUp {
-                                    title: "🤖 Select Commit Message".to_string(),
+                                    title: format!("{} Select Commit Message", Icon::Ai),
                                     context: "Choose an AI-generated commit message:".to_string(),
                                     suggestions,
                                     selected: 0,
@@ -1949,10 +3216,11 @@ impl App {
                                 "Error: {}\n\n── AI Explanation ──\n\n{}",
                                 original_err, response
                             );
-                            self.popup = Popup::Message {
-                                title: "🤖 AI Error Explanation".to_string(),
-                                message: msg,
-                            };
+                            self.popup = answer_popup(
+                                format!("{} AI Error Explanation", Icon::Ai),
+                                msg,
+                                &response,
+                            );
                             self.set_status("✓ AI explanation ready");
                             // Store in history
                             self.ai_mentor_state.add_history(
@@ -1963,10 +3231,11 @@ impl App {
                         Some(AiAction::ReviewDiff(file_path)) => {
                             let msg =
                                 format!("── AI Diff Review: {} ──\n\n{}", file_path, response);
-                            self.popup = Popup::Message {
-                                title: "🤖 AI Diff Review".to_string(),
-                                message: msg,
-                            };
+                            self.popup = answer_popup(
+                                format!("{} AI Diff Review", Icon::Ai),
+                                msg,
+                                &response,
+                            );
                             self.set_status("✓ AI diff review ready");
                             // Store in history
                             self.ai_mentor_state
@@ -2038,17 +3307,52 @@ impl App {
                             self.ai_mentor_state
                                 .add_history(format!("Merge Resolve: {}", file_path), response);
                         }
+                        Some(AiAction::MergeResolveRegion(file_path, index)) => {
+                            let state = &mut self.merge_resolve_state;
+                            let still_selected = state
+                                .conflicted_files
+                                .get(state.selected_file)
+                                .is_some_and(|f| f.path == file_path);
+                            if still_selected && index < state.conflict_regions.len() {
+                                state.set_region_suggestion(
+                                    index,
+                                    merge_resolve::RegionSuggestion {
+                                        recommendation: parse_ai_recommendation(&response),
+                                        resolved_content: parse_ai_resolved_content(&response),
+                                        response: response.clone(),
+                                    },
+                                );
+                                state.scroll_center = 0;
+                                state.follow_ups =
+                                    generate_region_follow_ups(&file_path, index, state);
+                                self.set_status(format!(
+                                    "✓ AI resolution ready for region {} of {} — press 'm' to apply",
+                                    index + 1,
+                                    file_path
+                                ));
+                            } else {
+                                self.set_status(format!(
+                                    "AI region suggestion for {} discarded (file changed)",
+                                    file_path
+                                ));
+                            }
+                            self.ai_mentor_state.add_history(
+                                format!("Merge Resolve: {} (region {})", file_path, index + 1),
+                                response,
+                            );
+                        }
                         Some(AiAction::MergeStrategy) => {
                             // Show strategy recommendation as popup with follow-ups
-                            let follow_ups = generate_strategy_follow_ups(&response);
+                            let in_merge = git::merge::get_merge_state().is_some();
+                            let follow_ups = generate_strategy_follow_ups(&response, in_merge);
                             if follow_ups.is_empty() {
                                 self.popup = Popup::Message {
-                                    title: "🤖 AI Merge Strategy".to_string(),
+                                    title: format!("{} AI Merge Strategy", Icon::Ai),
                                     message: response.clone(),
                                 };
                             } else {
                                 self.popup = Popup::FollowUp {
-                                    title: "🤖 AI Merge Strategy".to_string(),
+                                    title: format!("{} AI Merge Strategy", Icon::Ai),
                                     context: response.clone(),
                                     suggestions: follow_ups,
                                     selected: 0,
@@ -2068,6 +3372,49 @@ impl App {
                             self.ai_mentor_state
                                 .add_history("Reset Insight".to_string(), response);
                         }
+                        Some(AiAction::PolishReleaseNotes) => {
+                            let notes = response.trim().to_string();
+                            self.popup = Popup::FollowUp {
+                                title: format!("{} Release Notes (AI)", Icon::Ai),
+                                context: notes.clone(),
+                                suggestions: vec![FollowUpItem {
+                                    label: "Copy to clipboard".to_string(),
+                                    description: "Paste into a new GitHub release".to_string(),
+                                    action: FollowUpAction::CopyToClipboard(notes),
+                                }],
+                                selected: 0,
+                            };
+                            self.set_status("✓ AI release notes ready");
+                            self.ai_mentor_state
+                                .add_history("Release Notes".to_string(), response);
+                        }
+                        Some(AiAction::TagChangelog(tag)) => {
+                            tags::offer_release(self, &tag, response.trim().to_string());
+                            self.set_status("✓ AI changelog ready");
+                            self.ai_mentor_state
+                                .add_history(format!("Changelog {}", tag), response);
+                        }
+                        Some(AiAction::PullRequestDescription) => {
+                            let (title, body) = git::pull_request::split_description(&response);
+                            if let Some(form) = self.github_state.pr_state.new_pr.as_mut() {
+                                form.title = title;
+                                form.body = body;
+                                self.set_status("✓ AI drafted the title and description");
+                            }
+                            self.ai_mentor_state
+                                .add_history("Pull Request".to_string(), response);
+                        }
+                        Some(AiAction::ContributingSummary) => {
+                            self.popup = answer_popup(
+                                format!("{} Before Your First PR", Icon::Ai),
+                                response.clone(),
+                                &response,
+                            );
+                            self.set_status("✓ AI contributing summary ready");
+                            // Store in history
+                            self.ai_mentor_state
+                                .add_history("Contributing Readiness".to_string(), response);
+                        }
                         Some(AiAction::GenerateGitignore) => {
                             // Strip markdown code fences if the AI wrapped them
                             let clean = response
@@ -2088,7 +3435,7 @@ impl App {
 
                             // Show follow-up to write to disk
                             self.popup = Popup::FollowUp {
-                                title: "📄 Generated .gitignore".to_string(),
+                                title: format!("{} Generated .gitignore", Icon::File),
                                 context: clean.clone(),
                                 suggestions: vec![
                                     FollowUpItem {
@@ -2157,6 +3504,20 @@ impl App {
                             self.set_status(format!("AI: {}", response));
                         }
                     }
+
+                    // Tell the user when secrets were masked before sending
+                    if let Some(ref client) = self.ai_client {
+                        let redacted = client.redacted_lines();
+                        if redacted > 0
+                            && let Some(ref mut msg) = self.status_message
+                        {
+                            msg.push_str(&format!(
+                                " · {} line{} redacted",
+                                redacted,
+                                if redacted == 1 { "" } else { "s" }
+                            ));
+                        }
+                    }
                 }
                 Ok(Err(e)) => {
                     log::debug!(
@@ -2173,6 +3534,9 @@ impl App {
                         });
                     }
                     self.set_status(format!("AI error: {}", e));
+                    if matches!(self.ai_action, Some(AiAction::HealthCheck)) {
+                        self.popup = health_check_failed_popup(&e);
+                    }
                     self.ai_loading = false;
                     self.ai_receiver = None;
                     self.ai_action = None;
@@ -2194,6 +3558,456 @@ impl App {
         }
     }
 
+    /// The scrolling pane that has focus, if it can be searched with `/`: its
+    /// lines, its scroll offset and its search.
+    fn searchable_pane(&mut self) -> Option<(Vec<&str>, &mut u16, &mut Option<PaneSearch>)> {
+        match self.view {
+            View::Dashboard if self.ai_mentor_state.mode == ai_mentor::AiMode::Result => {
+                let s = &mut self.ai_mentor_state;
+                Some((
+                    s.result_text.lines().collect(),
+                    &mut s.result_scroll,
+                    &mut s.search,
+                ))
+            }
+            View::Staging if self.staging_state.hunk_mode => {
+                let s = &mut self.staging_state;
+                let lines = s.diff_lines.iter().map(|l| l.content.as_str()).collect();
+                Some((lines, &mut s.diff_scroll, &mut s.diff_search))
+            }
+            View::Timeline if self.timeline_state.show_detail => {
+                let s = &mut self.timeline_state;
+                let lines = s.detail_diff.iter().map(|l| l.content.as_str()).collect();
+                Some((lines, &mut s.detail_scroll, &mut s.detail_search))
+            }
+            View::Outgoing => {
+                let s = &mut self.outgoing_state;
+                Some((
+                    s.diff_text.lines().collect(),
+                    &mut s.diff_scroll,
+                    &mut s.diff_search,
+                ))
+            }
+            _ => None,
+        }
+    }
+
+    /// Ask for a query to search the focused pane with.
+    pub fn open_pane_search(&mut self) {
+        let query = self
+            .searchable_pane()
+            .and_then(|(_, _, search)| search.as_ref().map(|s| s.query.clone()))
+            .unwrap_or_default();
+        self.popup = Popup::Input {
+            title: "Search".to_string(),
+            prompt: "Find: ".to_string(),
+            value: query,
+            on_submit: InputAction::SearchPane,
+        };
+    }
+
+    /// Scroll the focused pane to its search's next (or previous) match.
+    pub fn step_pane_search(&mut self, forward: bool) {
+        let Some((lines, scroll, Some(search))) = self.searchable_pane() else {
+            return;
+        };
+        let message = search.step(lines, forward).unwrap_or_else(|e| e);
+        *scroll = search.scroll();
+        self.set_status(message);
+    }
+
+    fn close_input(&mut self) {
+        self.popup = Popup::None;
+        self.input_cursor_back = 0;
+        self.input_error = None;
+    }
+
+    /// Handle pasted text (bracketed paste). The Input popup takes it at the
+    /// cursor in one go; everywhere else it's typed in as keys, as terminals
+    /// without bracketed paste would.
+    pub fn handle_paste(&mut self, text: &str) -> Result<()> {
+        if let Popup::Input {
+            value, on_submit, ..
+        } = &mut self.popup
+        {
+            crate::ui::line_edit::insert(value, self.input_cursor_back, text);
+            self.input_error = on_submit.validate(value);
+            return Ok(());
+        }
+        for c in text.chars() {
+            let code = match c {
+                '\r' => continue,
+                '\n' => KeyCode::Enter,
+                c => KeyCode::Char(c),
+            };
+            self.handle_key(KeyEvent::new(code, KeyModifiers::NONE))?;
+        }
+        Ok(())
+    }
+
+    /// Run git commands in the output drawer, streaming their output as it arrives.
+    pub fn start_output_job(&mut self, job: output_pane::OutputJob) {
+        let title = job.title.clone();
+        if self.output_pane.start(job) {
+            self.set_status(format!("{} {}...", Icon::Loading, title));
+        } else {
+            self.set_status(format!(
+                "{} Another command is still running (Ctrl+O to view)",
+                Icon::Loading
+            ));
+        }
+    }
+
+    /// Quit, asking first when something would be left unfinished (see
+    /// [`App::pending_on_quit`]). zit waits a few seconds for background work
+    /// on the way out.
+    pub fn quit(&mut self) {
+        let pending = self.pending_on_quit();
+        if pending.is_empty() {
+            self.running = false;
+            return;
+        }
+        let mut message = String::from("Still pending:\n\n");
+        for item in &pending {
+            message.push_str(&format!("• {}\n", item));
+        }
+        message.push_str(&format!(
+            "\nQuit anyway? Running commands get up to {}s to finish. (y/n)",
+            crate::jobs::QUIT_TIMEOUT.as_secs()
+        ));
+        self.popup = Popup::Confirm {
+            title: format!("{} Quit zit?", Icon::Warning),
+            message,
+            on_confirm: ConfirmAction::Quit,
+        };
+    }
+
+    /// What quitting now would leave behind: staged but uncommitted changes,
+    /// an unsent commit message, an unfinished merge or rebase, and background
+    /// work still running. Empty when it's safe to just quit.
+    pub fn pending_on_quit(&self) -> Vec<String> {
+        let mut pending = Vec::new();
+        let summary = &self.repo_summary;
+        if summary.staged > 0 {
+            pending.push(format!("{} staged file(s) not committed", summary.staged));
+        }
+        if !self.commit_state.message.trim().is_empty() {
+            pending.push("An unfinished commit message (it isn't saved)".to_string());
+        }
+        if summary.conflicts > 0 {
+            pending.push(format!("{} unresolved conflict(s)", summary.conflicts));
+        }
+        if let Some(op) = &summary.operation {
+            pending.push(format!("A {} in progress", op));
+        }
+        if self.output_pane.running {
+            pending.push(format!("'{}' still running", self.output_pane.title));
+        }
+        for name in crate::jobs::in_flight() {
+            if !(self.output_pane.running && name == self.output_pane.title) {
+                pending.push(format!("'{}' still running", name));
+            }
+        }
+        let requests = self.jobs.running().len();
+        if requests > 0 {
+            pending.push(format!("{} GitHub/AI request(s) in flight", requests));
+        }
+        pending
+    }
+
+    /// Snapshot the working tree in the background when `[snapshot]` is due.
+    pub fn poll_snapshot(&mut self) {
+        let interval = Duration::from_secs(self.config.snapshot.interval_mins * 60);
+        if !self.config.snapshot.enabled || self.last_snapshot.elapsed() < interval {
+            return;
+        }
+        self.last_snapshot = std::time::Instant::now();
+        let keep = self.config.snapshot.keep;
+        crate::jobs::spawn_tracked("Snapshot", move || {
+            match git::snapshot::take_snapshot(keep) {
+                Ok(Some(hash)) => log::info!("Took snapshot {}", hash),
+                Ok(None) => {}
+                Err(e) => log::warn!("Snapshot failed: {}", e),
+            }
+        });
+    }
+
+    /// Start a push job, running the pre-push checklist first when `[push] checklist` is on.
+    pub fn start_push(&mut self, job: output_pane::OutputJob) {
+        if !self.config.push.checklist {
+            self.start_output_job(job);
+            return;
+        }
+        if self.push_gate.is_some() {
+            self.set_status(format!(
+                "{} Pre-push checklist already running",
+                Icon::Loading
+            ));
+            return;
+        }
+        let (tx, rx) = mpsc::channel();
+        let config = self.config.push.clone();
+        std::thread::spawn(move || {
+            let _ = tx.send(git::prepush::run_checklist(&config));
+        });
+        self.push_gate = Some((rx, job));
+        self.set_status(format!("{} Running pre-push checklist...", Icon::Loading));
+    }
+
+    /// Do the slow part of startup in the background, so the first frame shows
+    /// straight away: migrate plaintext secrets to the keychain, then build the
+    /// AI client (which reads its key from the keychain). `waker` redraws once
+    /// it's done.
+    pub fn start_deferred_init(&mut self, waker: mpsc::Sender<AppEvent>) {
+        let mut config = self.config.clone();
+        let (tx, rx) = mpsc::channel();
+        self.startup = Some(rx);
+        std::thread::spawn(move || {
+            let migrated = crate::keychain::migrate_from_config(&mut config);
+            let ai_client = AiClient::from_config(&config.ai);
+            let _ = tx.send(Startup {
+                config,
+                migrated,
+                ai_client,
+            });
+            let _ = waker.send(AppEvent::Redraw);
+        });
+    }
+
+    /// Whether deferred startup work is still running.
+    pub fn starting_up(&self) -> bool {
+        self.startup.is_some()
+    }
+
+    /// Apply the deferred startup work once it has finished.
+    pub fn poll_startup(&mut self) {
+        let Some(rx) = &self.startup else {
+            return;
+        };
+        let startup = match rx.try_recv() {
+            Ok(startup) => startup,
+            Err(mpsc::TryRecvError::Empty) => return,
+            Err(mpsc::TryRecvError::Disconnected) => {
+                self.startup = None;
+                return;
+            }
+        };
+        self.startup = None;
+
+        if startup.migrated > 0 {
+            // Only the secrets moved; keep any settings changed meanwhile
+            self.config.github.oauth_token = startup.config.github.oauth_token;
+            self.config.github.pat = startup.config.github.pat;
+            self.config.ai.api_key = startup.config.ai.api_key;
+            if let Err(e) = self.config.save() {
+                log::warn!("Failed to save config after keychain migration: {}", e);
+            } else {
+                log::info!(
+                    "Migrated {} secret(s) from config to OS keychain",
+                    startup.migrated
+                );
+            }
+        }
+
+        // The AI setup wizard may have built a client in the meantime
+        if self.ai_client.is_none() {
+            self.ai_client = startup.ai_client.map(Arc::new);
+            if self.status_message.is_none()
+                && self.config.ai.is_ready()
+                && self.ai_client.is_some()
+            {
+                self.set_status("✓ AI Mentor ready");
+            }
+        }
+    }
+
+    /// Take on config.toml as reloaded from disk, returning the sections that
+    /// changed. Most settings are read where they're used; the AI client, the
+    /// spellchecker and the display modes are rebuilt here. The color mode and
+    /// tick rate belong to the main loop, which applies them itself.
+    pub fn reload_config(&mut self, config: Config) -> Vec<&'static str> {
+        fn same<T: serde::Serialize>(a: &T, b: &T) -> bool {
+            toml::to_string(a).ok() == toml::to_string(b).ok()
+        }
+        let old = std::mem::replace(&mut self.config, config);
+        let new = &self.config;
+        let changed: Vec<&'static str> = [
+            ("general", same(&old.general, &new.general)),
+            ("github", same(&old.github, &new.github)),
+            ("ui", same(&old.ui, &new.ui)),
+            ("ai", same(&old.ai, &new.ai)),
+            ("secrets", same(&old.secrets, &new.secrets)),
+            ("spellcheck", same(&old.spellcheck, &new.spellcheck)),
+            ("commit", same(&old.commit, &new.commit)),
+            ("push", same(&old.push, &new.push)),
+            ("snapshot", same(&old.snapshot, &new.snapshot)),
+            ("prompt", same(&old.prompt, &new.prompt)),
+            ("stats", same(&old.stats, &new.stats)),
+        ]
+        .into_iter()
+        .filter(|(_, same)| !same)
+        .map(|(section, _)| section)
+        .collect();
+
+        if old.ui.icons != new.ui.icons {
+            crate::ui::icons::set_mode(crate::ui::icons::IconMode::resolve(&new.ui.icons));
+        }
+        // Only when the file changed it, so a Ctrl+Z toggle isn't undone
+        if old.ui.zen != new.ui.zen {
+            zen::set(new.ui.zen);
+        }
+        if old.ui.date_format != new.ui.date_format {
+            crate::ui::datetime::set_format(crate::ui::datetime::DateFormat::from_config(
+                &new.ui.date_format,
+            ));
+        }
+        if old.general.git_backend != new.general.git_backend {
+            git::backend::configure(&new.general.git_backend);
+        }
+        if old.stats.enabled != new.stats.enabled {
+            crate::stats::set_enabled(new.stats.enabled);
+        }
+        if changed.contains(&"spellcheck") {
+            self.commit_state.spellchecker = SpellChecker::from_config(&new.spellcheck);
+        }
+        if changed.contains(&"ai") {
+            self.ai_client = AiClient::from_config(&new.ai).map(Arc::new);
+        }
+        changed
+    }
+
+    /// Start the pending push once the checklist passes, or ask before overriding failures.
+    pub fn poll_push_gate(&mut self) {
+        let items = match self.push_gate {
+            Some((ref rx, _)) => match rx.try_recv() {
+                Ok(items) => items,
+                Err(mpsc::TryRecvError::Empty) => return,
+                Err(mpsc::TryRecvError::Disconnected) => Vec::new(),
+            },
+            None => return,
+        };
+        let Some((_, job)) = self.push_gate.take() else {
+            return;
+        };
+        if git::prepush::all_passed(&items) {
+            self.start_output_job(job);
+            return;
+        }
+
+        let mut message = String::new();
+        for item in &items {
+            let mark = if item.passed { "✓" } else { "✗" };
+            message.push_str(&format!("{} {}\n", mark, item.label));
+            for detail in item.details.iter().take(3) {
+                message.push_str(&format!("    {}\n", detail));
+            }
+            if item.details.len() > 3 {
+                message.push_str(&format!("    ...and {} more\n", item.details.len() - 3));
+            }
+        }
+        message.push_str("\nPush anyway?\n\n[y] Yes  [n] No");
+        let failed = items.iter().filter(|i| !i.passed).count();
+        self.set_status(format!(
+            "{} Pre-push checklist: {} check(s) failed",
+            Icon::Warning,
+            failed
+        ));
+        self.popup = Popup::Confirm {
+            title: format!("{} Pre-push Checklist", Icon::Warning),
+            message,
+            on_confirm: ConfirmAction::PushDespiteChecklist(job),
+        };
+    }
+
+    /// Drain streamed command output and report the result once the job finishes.
+    pub fn poll_output_pane(&mut self) {
+        if let Some(summary) = self.output_pane.poll() {
+            if self.view == View::GitHub {
+                self.github_state.status = Some(summary.clone());
+            }
+            self.set_status(summary);
+            self.refresh();
+        }
+    }
+
+    /// Switch to `view`, remembering the current one to go back to. Opening a
+    /// view already on the way back unwinds to it; the Dashboard starts over.
+    pub fn open_view(&mut self, view: View) {
+        if view == self.view {
+            return;
+        }
+        if view == View::Dashboard {
+            self.view_stack.clear();
+        } else if let Some(pos) = self.view_stack.iter().position(|v| *v == view) {
+            self.view_stack.truncate(pos);
+        } else {
+            self.view_stack.push(self.view);
+        }
+        self.view = view;
+        crate::stats::record_view(crate::ui::help::view_name(view));
+    }
+
+    /// Return to the view this one was opened from (the Dashboard if none).
+    pub fn go_back(&mut self) {
+        self.view = self.view_stack.pop().unwrap_or(View::Dashboard);
+        self.refresh();
+    }
+
+    /// Whether Esc leaves the current view, rather than closing something
+    /// open inside it (a detail pane, a mode, an AI answer).
+    fn esc_leaves_view(&self) -> bool {
+        match self.view {
+            // These decide for themselves (e.g. Esc first stops editing)
+            View::Dashboard | View::Commit | View::Agent => false,
+            View::Staging => !self.staging_state.hunk_mode && !self.staging_state.multi_select,
+            View::Timeline => !self.timeline_state.show_detail,
+            View::TimeTravel => {
+                self.time_travel_state.ai_suggestion.is_none()
+                    && self.time_travel_state.preview.is_none()
+            }
+            View::Reflog => !self.reflog_state.show_diff && self.reflog_state.lost.is_none(),
+            View::GitHub => self.github_state.view == github::GitHubView::Menu,
+            View::WorkflowBuilder => {
+                self.workflow_builder_state.mode == workflow_builder::BuilderMode::Navigate
+            }
+            View::Bisect => !self.bisect_state.esc_steps_back(),
+            View::CherryPick => !self.cherry_pick_state.esc_steps_back(),
+            View::Outgoing => self.outgoing_state.diff_search.is_none(),
+            View::Branches
+            | View::Stash
+            | View::MergeResolve
+            | View::Rebase
+            | View::Tags
+            | View::Blame => true,
+        }
+    }
+
+    /// Where the user is: the views on the way here, then the current one and
+    /// the part of it that's open.
+    pub fn breadcrumb(&self) -> Vec<String> {
+        let mut crumbs: Vec<String> = self
+            .view_stack
+            .iter()
+            .chain([&self.view])
+            .map(|v| crate::ui::help::view_name(*v).to_string())
+            .collect();
+        match self.view {
+            View::GitHub => crumbs.extend(github::location(&self.github_state)),
+            View::Timeline if self.timeline_state.show_detail => {
+                crumbs.push("Commit Detail".to_string())
+            }
+            View::Reflog if self.reflog_state.lost.is_some() => {
+                crumbs.push("Lost Commits".to_string())
+            }
+            View::TimeTravel if self.time_travel_state.preview.is_some() => {
+                crumbs.push("Reset Preview".to_string())
+            }
+            View::Blame => crumbs.push(self.blame_state.path.clone()),
+            _ => {}
+        }
+        crumbs
+    }
+
     /// Set a status message that appears at the bottom.
     pub fn set_status(&mut self, msg: impl Into<String>) {
         self.status_message = Some(msg.into());
@@ -2215,6 +4029,7 @@ impl App {
                         self.staging_state
                             .list_state
                             .select(Some(self.staging_state.selected));
+                        self.staging_state.diff_debounce.schedule();
                     }
                 }
                 View::Timeline => {
@@ -2242,6 +4057,21 @@ impl App {
                         self.stash_state
                             .list_state
                             .select(Some(self.stash_state.selected));
+                        self.stash_state.diff_debounce.schedule();
+                    }
+                }
+                View::Blame => {
+                    let state = &mut self.blame_state;
+                    if state.selected + 1 < state.lines.len() {
+                        state.selected += 1;
+                        state.list_state.select(Some(state.selected));
+                    }
+                }
+                View::Tags => {
+                    let state = &mut self.tags_state;
+                    if state.selected + 1 < state.tags.len() {
+                        state.selected += 1;
+                        state.table_state.select(Some(state.selected));
                     }
                 }
                 View::Agent => {
@@ -2250,36 +4080,40 @@ impl App {
                 _ => {}
             },
             MouseEventKind::ScrollUp => match self.view {
-                View::Staging => {
-                    if self.staging_state.selected > 0 {
-                        self.staging_state.selected -= 1;
-                        self.staging_state
-                            .list_state
-                            .select(Some(self.staging_state.selected));
-                    }
+                View::Staging if self.staging_state.selected > 0 => {
+                    self.staging_state.selected -= 1;
+                    self.staging_state
+                        .list_state
+                        .select(Some(self.staging_state.selected));
+                    self.staging_state.diff_debounce.schedule();
                 }
-                View::Timeline => {
-                    if self.timeline_state.selected > 0 {
-                        self.timeline_state.selected -= 1;
-                    }
+                View::Timeline if self.timeline_state.selected > 0 => {
+                    self.timeline_state.selected -= 1;
                 }
-                View::Branches => {
-                    if self.branches_state.selected > 0 {
-                        self.branches_state.selected -= 1;
-                    }
+                View::Branches if self.branches_state.selected > 0 => {
+                    self.branches_state.selected -= 1;
                 }
-                View::Reflog => {
-                    if self.reflog_state.selected > 0 {
-                        self.reflog_state.selected -= 1;
-                    }
+                View::Reflog if self.reflog_state.selected > 0 => {
+                    self.reflog_state.selected -= 1;
                 }
-                View::Stash => {
-                    if self.stash_state.selected > 0 {
-                        self.stash_state.selected -= 1;
-                        self.stash_state
-                            .list_state
-                            .select(Some(self.stash_state.selected));
-                    }
+                View::Stash if self.stash_state.selected > 0 => {
+                    self.stash_state.selected -= 1;
+                    self.stash_state
+                        .list_state
+                        .select(Some(self.stash_state.selected));
+                    self.stash_state.diff_debounce.schedule();
+                }
+                View::Blame if self.blame_state.selected > 0 => {
+                    self.blame_state.selected -= 1;
+                    self.blame_state
+                        .list_state
+                        .select(Some(self.blame_state.selected));
+                }
+                View::Tags if self.tags_state.selected > 0 => {
+                    self.tags_state.selected -= 1;
+                    self.tags_state
+                        .table_state
+                        .select(Some(self.tags_state.selected));
                 }
                 View::Agent => {
                     agent::handle_mouse(self, mouse);
@@ -2377,19 +4211,20 @@ fn generate_merge_follow_ups(
         });
     }
 
-    // All conflicts resolved? Offer to continue merge
+    // All conflicts resolved? Offer to continue
+    let op = state.operation();
     if state.conflicted_files.len() <= 1 {
         items.push(FollowUpItem {
-            label: "Continue merge".to_string(),
-            description: "All conflicts resolved — finalize the merge".to_string(),
+            label: format!("Continue {}", op),
+            description: format!("All conflicts resolved — run {}", op.continue_command()),
             action: FollowUpAction::ContinueMerge,
         });
     }
 
     // Always offer abort
     items.push(FollowUpItem {
-        label: "Abort merge".to_string(),
-        description: "Discard all merge progress and return to previous state".to_string(),
+        label: format!("Abort {}", op),
+        description: format!("Run git {} --abort and return to the previous state", op),
         action: FollowUpAction::AbortMerge,
     });
 
@@ -2406,63 +4241,229 @@ fn generate_merge_follow_ups(
     items
 }
 
-/// Generate follow-up suggestions after a merge strategy AI response.
-fn generate_strategy_follow_ups(response: &str) -> Vec<FollowUpItem> {
+/// Regeneration options appended to the commit message suggestions.
+fn commit_refinement_follow_ups() -> Vec<FollowUpItem> {
+    let item = |label: &str, description: &str, action: FollowUpAction| FollowUpItem {
+        label: label.to_string(),
+        description: description.to_string(),
+        action,
+    };
+    vec![
+        item(
+            "↻ Try again",
+            "Regenerate with different wording",
+            FollowUpAction::RefineCommitMessage(CommitRefinement::Retry),
+        ),
+        item(
+            "Shorter",
+            "Tighten the suggestion",
+            FollowUpAction::RefineCommitMessage(CommitRefinement::Shorter),
+        ),
+        item(
+            "Longer",
+            "Make the suggestion more descriptive",
+            FollowUpAction::RefineCommitMessage(CommitRefinement::Longer),
+        ),
+        item(
+            "Refine…",
+            "Regenerate with your own instruction",
+            FollowUpAction::PromptCommitRefinement,
+        ),
+    ]
+}
+
+/// Generate follow-up suggestions after a per-region AI resolution.
+fn generate_region_follow_ups(
+    file_path: &str,
+    index: usize,
+    state: &merge_resolve::MergeResolveState,
+) -> Vec<FollowUpItem> {
     let mut items = Vec::new();
 
-    // Parse recommended strategy
-    let rec = parse_ai_recommendation(response);
-    let strategy = rec.as_deref().unwrap_or("");
+    if state
+        .region_suggestions
+        .get(index)
+        .and_then(Option::as_ref)
+        .is_some_and(|s| s.choice().is_some())
+    {
+        items.push(FollowUpItem {
+            label: format!("Apply to region {}", index + 1),
+            description: format!("Replace this conflict region in {}", file_path),
+            action: FollowUpAction::ApplyRegionResolution(index),
+        });
+    }
 
-    if strategy.contains("MERGE") || strategy.contains("merge") {
+    if state.conflict_regions.len() > 1 {
         items.push(FollowUpItem {
-            label: "Run merge --no-ff".to_string(),
-            description: "Execute the recommended merge strategy".to_string(),
-            action: FollowUpAction::AskAiMore(
-                "I chose to merge. What exact commands should I run and what should I watch for?"
-                    .to_string(),
-            ),
+            label: "Resolve whole file with AI".to_string(),
+            description: "Ask the AI to resolve every region in this file at once".to_string(),
+            action: FollowUpAction::RegenerateAiSuggestion,
         });
     }
-    if strategy.contains("REBASE") || strategy.contains("rebase") {
+
+    items.push(FollowUpItem {
+        label: "Ask AI for more detail".to_string(),
+        description: "Get a deeper explanation of why this resolution was suggested".to_string(),
+        action: FollowUpAction::AskAiMore(format!(
+            "Explain in more detail why conflict region {} in {} should be resolved this way",
+            index + 1,
+            file_path
+        )),
+    });
+
+    items
+}
+
+/// Generate follow-up suggestions after a merge strategy AI response: the recommended
+/// order of operations as runnable steps, then alternatives.
+fn generate_strategy_follow_ups(response: &str, in_merge: bool) -> Vec<FollowUpItem> {
+    let mut items = Vec::new();
+    let advice = crate::ai::strategy::parse_advice(response);
+    let strategy = advice
+        .recommended
+        .clone()
+        .unwrap_or_else(|| "recommended strategy".to_string());
+
+    if advice.steps.len() > 1 {
         items.push(FollowUpItem {
-            label: "Run rebase".to_string(),
-            description: "Execute the recommended rebase strategy".to_string(),
-            action: FollowUpAction::AskAiMore(
-                "I chose to rebase. Walk me through the exact steps and what to watch for."
-                    .to_string(),
-            ),
+            label: format!("Run all {} steps ({})", advice.steps.len(), strategy),
+            description: "Run the recommended commands in order, stopping at the first failure"
+                .to_string(),
+            action: FollowUpAction::RunGitSteps(strategy.clone(), advice.steps.clone()),
         });
     }
-    if strategy.contains("FAST") || strategy.contains("fast") {
+    for (i, step) in advice.steps.iter().enumerate() {
         items.push(FollowUpItem {
-            label: "Fast-forward merge".to_string(),
-            description: "Execute fast-forward merge".to_string(),
-            action: FollowUpAction::AskAiMore(
-                "I chose fast-forward. What exact command should I use?".to_string(),
+            label: format!("Step {}: git {}", i + 1, step.join(" ")),
+            description: "Run just this step".to_string(),
+            action: FollowUpAction::RunGitSteps(
+                format!("git {}", step.join(" ")),
+                vec![step.clone()],
             ),
         });
     }
+    if advice.steps.is_empty() && advice.recommended.is_some() {
+        items.push(FollowUpItem {
+            label: format!("How do I {}?", strategy.to_lowercase().replace('_', " ")),
+            description: "Ask for the exact commands for the recommended strategy".to_string(),
+            action: FollowUpAction::AskAiMore(format!(
+                "I chose {}. What exact git commands should I run, in order, and what should I watch for?",
+                strategy
+            )),
+        });
+    }
 
-    // Always offer conflict resolution view
-    items.push(FollowUpItem {
-        label: "View conflicts".to_string(),
-        description: "Open the merge conflict resolution view".to_string(),
-        action: FollowUpAction::SwitchToView(View::MergeResolve),
-    });
+    // Conflict resolution view only makes sense while a merge is in progress
+    if in_merge {
+        items.push(FollowUpItem {
+            label: "View conflicts".to_string(),
+            description: "Open the merge conflict resolution view".to_string(),
+            action: FollowUpAction::SwitchToView(View::MergeResolve),
+        });
+    }
 
     // Ask for alternatives
     items.push(FollowUpItem {
         label: "Ask for alternatives".to_string(),
         description: "Get more detail on alternative strategies".to_string(),
         action: FollowUpAction::AskAiMore(
-            "What are the trade-offs between merge and rebase for my situation?".to_string(),
+            "What are the trade-offs between merge, rebase and squash for my situation?"
+                .to_string(),
         ),
     });
 
     items
 }
 
+/// Follow-ups for the next steps an AI answer suggests (see `ai::next_steps`).
+pub fn generate_answer_follow_ups(response: &str) -> Vec<FollowUpItem> {
+    use crate::ai::next_steps::NextStep;
+
+    crate::ai::next_steps::derive(response)
+        .into_iter()
+        .map(|step| match step {
+            NextStep::Run(snippet) => {
+                let first = snippet.code.lines().next().unwrap_or_default().trim();
+                FollowUpItem {
+                    label: format!("Run: {}", first.trim_start_matches("$ ")),
+                    description: "Asks first, then runs in the output drawer (Ctrl+O)".to_string(),
+                    action: FollowUpAction::ConfirmSnippet(snippet),
+                }
+            }
+            NextStep::Open(view) => FollowUpItem {
+                label: format!("Open {}", crate::ui::help::view_name(view)),
+                description: "The answer refers to it".to_string(),
+                action: FollowUpAction::SwitchToView(view),
+            },
+            NextStep::Learn(topic) => FollowUpItem {
+                label: format!("Learn: {}", topic),
+                description: "Have the AI Mentor teach it with examples".to_string(),
+                action: FollowUpAction::LearnTopic(topic.to_string()),
+            },
+        })
+        .collect()
+}
+
+/// What a failed AI health check means, with the setup step to redo first.
+fn health_check_failed_popup(error: &str) -> Popup {
+    use crate::ai::setup::FailedStep;
+    let key = FollowUpItem {
+        label: "Re-enter the API key".to_string(),
+        description: "Keep the provider and endpoint".to_string(),
+        action: FollowUpAction::EditAiKey,
+    };
+    let endpoint = FollowUpItem {
+        label: "Change the endpoint".to_string(),
+        description: "Keep the provider and API key".to_string(),
+        action: FollowUpAction::EditAiEndpoint,
+    };
+    let setup = FollowUpItem {
+        label: "Run setup again".to_string(),
+        description: "Pick the provider, model and key from the start".to_string(),
+        action: FollowUpAction::StartAiSetup,
+    };
+    let (hint, suggestions) = match crate::ai::setup::diagnose(error) {
+        Some(FailedStep::ApiKey) => (
+            "The endpoint answered but rejected the API key.",
+            vec![key, endpoint, setup],
+        ),
+        Some(FailedStep::Endpoint) => (
+            "The endpoint couldn't be reached, or has nothing at that path.",
+            vec![endpoint, key, setup],
+        ),
+        Some(FailedStep::Model) => (
+            "The provider doesn't know the model — pick another in setup.",
+            vec![setup, key, endpoint],
+        ),
+        None => (
+            "The settings may be fine — try the check again in a moment.",
+            vec![setup, key, endpoint],
+        ),
+    };
+    Popup::FollowUp {
+        title: format!("{} AI Health Check Failed", Icon::Warning),
+        context: format!("{}\n\n{}", error, hint),
+        suggestions,
+        selected: 0,
+    }
+}
+
+/// Show an AI answer in a popup, listing its next steps under it when it has
+/// any. `message` is what to show, `response` the answer they come from.
+fn answer_popup(title: String, message: String, response: &str) -> Popup {
+    let suggestions = generate_answer_follow_ups(response);
+    if suggestions.is_empty() {
+        Popup::Message { title, message }
+    } else {
+        Popup::FollowUp {
+            title,
+            context: message,
+            suggestions,
+            selected: 0,
+        }
+    }
+}
+
 /// Heuristic check: does this string look like a reasonable commit message?
 fn looks_like_commit_msg(s: &str) -> bool {
     if s.len() > 120 || s.len() < 3 {
@@ -0,0 +1,476 @@
+//! Command-line interface: the flags for the TUI and the subcommands that
+//! print something and exit instead of opening it.
+
+use std::path::PathBuf;
+
+use anyhow::{Context, Result, bail};
+use clap::{ArgAction, CommandFactory, Parser, Subcommand};
+
+use crate::config::Config;
+use crate::{git, paths};
+
+const AFTER_HELP: &str = "\
+Environment:
+  ZIT_CONFIG       Config file to use instead of the default (see `zit doctor`)
+  ZIT_LOG          Set log level (error, warn, info, debug, trace)
+  ZIT_AI_ENDPOINT  AI mentor API endpoint URL
+  ZIT_AI_API_KEY   AI mentor API key
+  NO_COLOR         Disable colors when set to any non-empty value
+
+Exit codes:
+  0  Success (for `status --quiet`: the working tree is clean)
+  1  Error
+  2  Invalid arguments
+  3  `status --quiet`: the working tree has changes
+  4  Not a git repository
+  5  Not signed in to GitHub, or the token was rejected
+
+Views:
+  s  Staging     c  Commit      b  Branches
+  l  Timeline    t  Time Travel  r  Reflog
+  g  GitHub      a  AI Mentor    x  Stash
+  ?  Help";
+
+#[derive(Debug, Parser)]
+#[command(
+    name = "zit",
+    version,
+    about,
+    disable_version_flag = true,
+    after_help = AFTER_HELP
+)]
+pub struct Cli {
+    /// Print version information
+    #[arg(short = 'v', long, action = ArgAction::Version)]
+    version: Option<bool>,
+
+    /// Enable verbose logging (ZIT_LOG=debug)
+    #[arg(long, global = true)]
+    pub verbose: bool,
+
+    /// Print nothing but errors; scripts read the exit code
+    #[arg(short, long, global = true)]
+    pub quiet: bool,
+
+    /// Read and save settings in this file instead of the default
+    #[arg(long, global = true, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+
+    /// Only show and stage changes under this directory (monorepos)
+    #[arg(long, global = true, value_name = "DIR")]
+    pub path: Option<PathBuf>,
+
+    /// Disable AI features for this session
+    #[arg(long)]
+    pub no_ai: bool,
+
+    /// Disable colors (same as NO_COLOR=1)
+    #[arg(long)]
+    pub no_color: bool,
+
+    /// Accept `zit remote` commands from editors and tmux
+    #[arg(long)]
+    pub server: bool,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Open the terminal UI (the default)
+    Tui,
+    /// Print the branch, its upstream and the changed files
+    Status,
+    /// Print recent commits on the current branch
+    Log {
+        /// How many commits to print
+        #[arg(short = 'n', long, default_value_t = 20)]
+        count: usize,
+    },
+    /// List the repository's GitHub pull requests
+    Pr {
+        /// Which pull requests to list
+        #[arg(long, default_value = "open", value_parser = ["open", "closed", "all"])]
+        state: String,
+    },
+    /// Clone a repository and open zit in it
+    Clone {
+        /// URL or path of the repository
+        url: String,
+        /// Directory to clone into (default: named after the repository)
+        dir: Option<PathBuf>,
+    },
+    /// Print a one-line repository summary for a shell prompt (PS1, starship)
+    Prompt {
+        /// Format with placeholders, overriding `[prompt] format` in the config
+        #[arg(long)]
+        format: Option<String>,
+    },
+    /// Print where zit keeps its config, history and crash reports, and check them
+    Doctor,
+    /// Print a shell completion script
+    Completions {
+        #[arg(value_enum)]
+        shell: clap_complete::Shell,
+    },
+    /// Tell a running `zit --server` what to show
+    Remote {
+        #[arg(value_parser = ["diff", "blame", "history"])]
+        command: String,
+        /// File to show, optionally with a line: src/app.rs:120
+        target: String,
+    },
+}
+
+/// How a subcommand ended, as the process exit code. Invalid arguments exit
+/// with 2, as clap does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Exit {
+    Ok = 0,
+    Error = 1,
+    /// `status --quiet` found changes in the working tree.
+    Dirty = 3,
+    NotARepo = 4,
+    /// Not signed in to GitHub, or GitHub rejected the token.
+    Auth = 5,
+}
+
+impl Exit {
+    /// The exit code for a failed subcommand.
+    pub fn of_error(err: &anyhow::Error) -> Self {
+        if err.downcast_ref::<NotSignedIn>().is_some()
+            || err
+                .downcast_ref::<git::github_auth::Unauthorized>()
+                .is_some()
+        {
+            Self::Auth
+        } else {
+            Self::Error
+        }
+    }
+}
+
+#[derive(Debug)]
+struct NotSignedIn;
+
+impl std::fmt::Display for NotSignedIn {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(
+            "Not signed in to GitHub. Open zit and connect GitHub from the GitHub view (g).",
+        )
+    }
+}
+
+impl std::error::Error for NotSignedIn {}
+
+/// Check git, the terminal, the keychain, the config, GitHub and the AI
+/// endpoint, and print what failed with how to fix it, then the files zit
+/// reads and writes. Fails if any check did.
+pub fn doctor(quiet: bool) -> Result<Exit> {
+    let checks = crate::doctor::run();
+    let exit = if crate::doctor::failed(&checks) {
+        Exit::Error
+    } else {
+        Exit::Ok
+    };
+    if quiet {
+        return Ok(exit);
+    }
+
+    println!("zit {}\n", env!("CARGO_PKG_VERSION"));
+    println!("{}\n", crate::doctor::render(&checks));
+    let (config_path, source) = paths::config_file();
+    for (label, path, note) in [
+        ("Config", config_path, format!(" ({})", source)),
+        (
+            "AI history",
+            crate::ui::ai_mentor::history_path(),
+            String::new(),
+        ),
+        (
+            "AI audit log",
+            crate::ai::audit::audit_path(),
+            String::new(),
+        ),
+        ("Crash reports", crate::crash::crash_dir(), String::new()),
+        ("Usage stats", crate::stats::path(), String::new()),
+    ] {
+        println!("{:<14}{}{}", label, path.display(), note);
+    }
+    Ok(exit)
+}
+
+/// Print the working tree status, like a short `git status`. With `quiet`,
+/// print nothing and tell clean from dirty by the exit code.
+pub fn status(quiet: bool) -> Result<Exit> {
+    let status = git::status::get_status()?;
+    if quiet {
+        return Ok(if status.is_clean() {
+            Exit::Ok
+        } else {
+            Exit::Dirty
+        });
+    }
+    let mut line = format!("On branch {}", status.branch);
+    if let Some(upstream) = &status.upstream {
+        line.push_str(&format!(" → {}", upstream));
+        match (status.ahead, status.behind) {
+            (0, 0) => line.push_str(" (up to date)"),
+            (ahead, 0) => line.push_str(&format!(" (ahead {})", ahead)),
+            (0, behind) => line.push_str(&format!(" (behind {})", behind)),
+            (ahead, behind) => line.push_str(&format!(" (ahead {}, behind {})", ahead, behind)),
+        }
+    }
+    println!("{}", line);
+    if let Some(merge) = git::merge::get_merge_state() {
+        println!("{} in progress", merge.merge_type);
+    }
+    if status.is_clean() {
+        println!("Nothing to commit, working tree clean");
+        return Ok(Exit::Ok);
+    }
+    for (heading, files) in [
+        ("Conflicts", &status.conflicts),
+        ("Staged", &status.staged),
+        ("Unstaged", &status.unstaged),
+        ("Untracked", &status.untracked),
+    ] {
+        if files.is_empty() {
+            continue;
+        }
+        println!("{} ({}):", heading, files.len());
+        for file in files {
+            println!("  {:<10} {}", format!("{:?}", file.status), file.path);
+        }
+    }
+    Ok(Exit::Ok)
+}
+
+/// What a prompt shows, gathered with as few git calls as possible.
+#[derive(Debug, Default)]
+struct PromptInfo {
+    status: git::status::RepoStatus,
+    stash: usize,
+    operation: Option<String>,
+}
+
+/// Print the compact summary for a shell prompt. Outside a repository it
+/// prints nothing, so prompts can call it anywhere.
+pub fn prompt(format: Option<String>) -> Result<Exit> {
+    if !git::runner::is_git_repo() {
+        return Ok(Exit::NotARepo);
+    }
+    let config = Config::load().unwrap_or_default().prompt;
+    let format = format.unwrap_or(config.format);
+    let untracked = if config.untracked {
+        "--untracked-files=normal"
+    } else {
+        "--untracked-files=no"
+    };
+    let output = git::run_git(&["status", "--porcelain=v2", "--branch", untracked])?;
+    let mut info = PromptInfo {
+        status: git::status::parse_status(&output),
+        ..Default::default()
+    };
+    if format.contains("{stash}") {
+        info.stash = git::run_git(&["stash", "list"])
+            .map(|s| s.lines().count())
+            .unwrap_or(0);
+    }
+    if format.contains("{operation}") {
+        info.operation = git::merge::get_merge_state().map(|m| m.merge_type.to_string());
+    }
+    println!("{}", render_prompt(&format, &info));
+    Ok(Exit::Ok)
+}
+
+/// Fill in `format`'s placeholders, starship-style: `⇡2⇣1 =1 +3 !2 ?1`.
+fn render_prompt(format: &str, info: &PromptInfo) -> String {
+    let count = |symbol: &str, n: usize| {
+        if n > 0 {
+            format!("{}{}", symbol, n)
+        } else {
+            String::new()
+        }
+    };
+    let status = &info.status;
+    let values = [
+        ("{branch}", status.branch.clone()),
+        ("{ahead}", count("⇡", status.ahead as usize)),
+        ("{behind}", count("⇣", status.behind as usize)),
+        ("{conflicts}", count("=", status.conflicts.len())),
+        ("{staged}", count("+", status.staged.len())),
+        ("{unstaged}", count("!", status.unstaged.len())),
+        ("{untracked}", count("?", status.untracked.len())),
+        ("{stash}", count("$", info.stash)),
+        (
+            "{dirty}",
+            if status.is_clean() { "" } else { "*" }.to_string(),
+        ),
+        (
+            "{operation}",
+            info.operation
+                .as_ref()
+                .map(|op| format!("|{}", op.to_uppercase()))
+                .unwrap_or_default(),
+        ),
+    ];
+    let mut out = format.to_string();
+    for (placeholder, value) in values {
+        out = out.replace(placeholder, &value);
+    }
+    out.trim().to_string()
+}
+
+/// Print the last `count` commits, one per line.
+pub fn log(count: usize, quiet: bool) -> Result<Exit> {
+    let commits = git::log::get_commits(&[], count)?;
+    if quiet {
+        return Ok(Exit::Ok);
+    }
+    for commit in commits {
+        println!(
+            "{}  {}  ({}, {})",
+            commit.short_hash, commit.message, commit.author, commit.date
+        );
+    }
+    Ok(Exit::Ok)
+}
+
+/// Print the repository's pull requests in `state`.
+pub fn pr(state: &str, quiet: bool) -> Result<Exit> {
+    let config = Config::load().unwrap_or_default();
+    let Some(token) = config.github.get_token() else {
+        return Err(NotSignedIn.into());
+    };
+    let prs = git::github_auth::list_pull_requests(&token, state)?;
+    if quiet {
+        return Ok(Exit::Ok);
+    }
+    if prs.is_empty() {
+        println!("No {} pull requests", state);
+    }
+    for pr in prs {
+        let draft = if pr.draft { " [draft]" } else { "" };
+        println!(
+            "#{:<5} {}{}  ({} → {}, @{})",
+            pr.number, pr.title, draft, pr.head.ref_name, pr.base.ref_name, pr.user.login
+        );
+    }
+    Ok(Exit::Ok)
+}
+
+/// `git clone` `url`, returning the directory it was cloned into.
+pub fn clone(url: &str, dir: Option<PathBuf>, quiet: bool) -> Result<PathBuf> {
+    let dir = match dir {
+        Some(dir) => dir,
+        None => PathBuf::from(clone_dir(url).context("Can't tell a directory name from the URL")?),
+    };
+    let mut git = std::process::Command::new("git");
+    git.arg("clone");
+    if quiet {
+        git.arg("--quiet");
+    }
+    let status = git
+        .arg(url)
+        .arg(&dir)
+        .status()
+        .context("Failed to run git clone")?;
+    if !status.success() {
+        bail!("git clone failed");
+    }
+    Ok(dir)
+}
+
+/// The directory `git clone` would create for `url`: its last path segment
+/// without `.git`.
+fn clone_dir(url: &str) -> Option<String> {
+    let trimmed = url.trim_end_matches('/');
+    let last = trimmed.rsplit(['/', ':']).next()?;
+    let name = last.strip_suffix(".git").unwrap_or(last);
+    (!name.is_empty()).then(|| name.to_string())
+}
+
+/// Print the completion script for `shell`.
+pub fn completions(shell: clap_complete::Shell) {
+    clap_complete::generate(shell, &mut Cli::command(), "zit", &mut std::io::stdout());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cli_definition() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn test_parse_subcommands() {
+        let cli = Cli::try_parse_from(["zit", "--no-ai"]).unwrap();
+        assert!(cli.no_ai && cli.command.is_none());
+
+        let cli = Cli::try_parse_from(["zit", "log", "-n", "5"]).unwrap();
+        assert!(matches!(cli.command, Some(Command::Log { count: 5 })));
+
+        let cli = Cli::try_parse_from(["zit", "remote", "blame", "src/app.rs:120"]).unwrap();
+        let Some(Command::Remote { command, target }) = cli.command else {
+            panic!("expected remote");
+        };
+        assert_eq!(
+            (command.as_str(), target.as_str()),
+            ("blame", "src/app.rs:120")
+        );
+
+        let cli = Cli::try_parse_from(["zit", "doctor", "--config", "/tmp/zit.toml"]).unwrap();
+        assert!(matches!(cli.command, Some(Command::Doctor)));
+        assert_eq!(cli.config, Some(PathBuf::from("/tmp/zit.toml")));
+
+        assert!(Cli::try_parse_from(["zit", "pr", "--state", "merged"]).is_err());
+        assert!(Cli::try_parse_from(["zit", "--nonexistent"]).is_err());
+    }
+
+    #[test]
+    fn test_render_prompt() {
+        let file = |path: &str| git::FileEntry {
+            status: git::FileStatus::Modified,
+            path: path.to_string(),
+            original_path: None,
+        };
+        let mut info = PromptInfo {
+            status: git::status::RepoStatus {
+                branch: "main".to_string(),
+                ahead: 2,
+                staged: vec![file("a"), file("b")],
+                untracked: vec![file("c")],
+                ..Default::default()
+            },
+            stash: 1,
+            operation: Some("rebase".to_string()),
+        };
+        let default = crate::config::PromptConfig::default().format;
+        assert_eq!(render_prompt(&default, &info), "main ⇡2+2?1|REBASE");
+        assert_eq!(render_prompt("{branch}{dirty} {stash}", &info), "main* $1");
+
+        info.status.ahead = 0;
+        info.status.staged.clear();
+        info.status.untracked.clear();
+        info.operation = None;
+        assert_eq!(render_prompt(&default, &info), "main");
+    }
+
+    #[test]
+    fn test_clone_dir() {
+        assert_eq!(
+            clone_dir("https://github.com/xpushkal/zit.git").as_deref(),
+            Some("zit")
+        );
+        assert_eq!(
+            clone_dir("git@github.com:xpushkal/zit").as_deref(),
+            Some("zit")
+        );
+        assert_eq!(clone_dir("../some/repo/").as_deref(), Some("repo"));
+        assert_eq!(clone_dir("https://example.com/.git"), None);
+    }
+}
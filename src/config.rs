@@ -14,6 +14,18 @@ pub struct Config {
     pub ai: AiConfig,
     #[serde(default)]
     pub secrets: SecretsConfig,
+    #[serde(default)]
+    pub spellcheck: SpellcheckConfig,
+    #[serde(default)]
+    pub commit: CommitConfig,
+    #[serde(default)]
+    pub push: PushConfig,
+    #[serde(default)]
+    pub snapshot: SnapshotConfig,
+    #[serde(default)]
+    pub prompt: PromptConfig,
+    #[serde(default)]
+    pub stats: StatsConfig,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -22,6 +34,10 @@ pub struct GeneralConfig {
     pub tick_rate_ms: u64,
     #[serde(default = "default_true")]
     pub confirm_destructive: bool,
+    /// How status, log, diffs and branches are read: "libgit2" (in-process,
+    /// falling back to git) or "cli" (always run git).
+    #[serde(default = "default_git_backend")]
+    pub git_backend: String,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
@@ -32,6 +48,9 @@ pub struct GithubConfig {
     pub oauth_token: Option<String>,
     #[serde(default)]
     pub username: Option<String>,
+    /// Milestone shown on the dashboard; defaults to the open one due soonest.
+    #[serde(default)]
+    pub milestone: Option<String>,
 }
 
 impl GithubConfig {
@@ -55,12 +74,47 @@ pub struct UiConfig {
     pub color_scheme: String,
     #[serde(default = "default_true")]
     pub show_help_hints: bool,
+    /// Icon style: "auto" (detect), "emoji", "nerd" (Nerd Font glyphs), or "ascii".
+    #[serde(default = "default_icons")]
+    pub icons: String,
+    /// Color support: "auto" (detect), "truecolor", "16" (basic ANSI palette), or "none".
+    #[serde(default = "default_colors")]
+    pub colors: String,
+    /// Date style: "relative" ("2 hours ago"), "absolute", or a strftime pattern like "%d %b %H:%M".
+    #[serde(default = "default_date_format")]
+    pub date_format: String,
+    /// Preview images in the Staging diff pane with unicode blocks (truecolor terminals only).
+    #[serde(default = "default_true")]
+    pub image_preview: bool,
+    /// Staging list order: "path", "status", or "modified" (newest first).
+    #[serde(default = "default_staging_sort")]
+    pub staging_sort: String,
+    /// Split the Staging list into staged / unstaged / untracked sections.
+    #[serde(default = "default_true")]
+    pub staging_group: bool,
+    /// Mark authors with a colored identicon in the Timeline and pull request lists.
+    #[serde(default = "default_true")]
+    pub identicons: bool,
+    /// Download GitHub avatars for pull request authors (truecolor terminals only;
+    /// identicons are shown until they arrive).
+    #[serde(default)]
+    pub github_avatars: bool,
+    /// Also raise a desktop notification when a watched pull request's checks finish.
+    #[serde(default = "default_true")]
+    pub desktop_notifications: bool,
+    /// Start in zen mode: no borders, titles, key hints or emoji (toggled with Ctrl+Z).
+    #[serde(default)]
+    pub zen: bool,
 }
 
 fn default_tick_rate() -> u64 {
     2000
 }
 
+fn default_git_backend() -> String {
+    "libgit2".to_string()
+}
+
 fn default_true() -> bool {
     true
 }
@@ -69,11 +123,28 @@ fn default_color_scheme() -> String {
     "default".to_string()
 }
 
+fn default_icons() -> String {
+    "auto".to_string()
+}
+
+fn default_colors() -> String {
+    "auto".to_string()
+}
+
+fn default_date_format() -> String {
+    "relative".to_string()
+}
+
+fn default_staging_sort() -> String {
+    "path".to_string()
+}
+
 impl Default for GeneralConfig {
     fn default() -> Self {
         Self {
             tick_rate_ms: default_tick_rate(),
             confirm_destructive: true,
+            git_backend: default_git_backend(),
         }
     }
 }
@@ -83,6 +154,16 @@ impl Default for UiConfig {
         Self {
             color_scheme: default_color_scheme(),
             show_help_hints: true,
+            icons: default_icons(),
+            colors: default_colors(),
+            date_format: default_date_format(),
+            image_preview: true,
+            staging_sort: default_staging_sort(),
+            staging_group: true,
+            identicons: true,
+            github_avatars: false,
+            desktop_notifications: true,
+            zen: false,
         }
     }
 }
@@ -118,6 +199,149 @@ impl Default for SecretsConfig {
     }
 }
 
+/// Commit view options.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct CommitConfig {
+    /// Open the gitmoji picker when `:` is typed at the start of a word.
+    #[serde(default)]
+    pub gitmoji: bool,
+    /// Prefix conventional commits with the matching gitmoji (`feat:` → ✨).
+    #[serde(default)]
+    pub gitmoji_prefix: bool,
+    /// Add a `Signed-off-by` trailer to every commit (`git commit -s`). A
+    /// repository's own choice, made when zit detects it asks for DCO
+    /// sign-off, takes precedence.
+    #[serde(default)]
+    pub sign_off: bool,
+}
+
+/// Pre-push checklist run before every push from zit.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PushConfig {
+    /// Run the checklist before pushing (default: true).
+    #[serde(default = "default_true")]
+    pub checklist: bool,
+    /// Command that must succeed before pushing, e.g. "cargo test" (run with `sh -c`).
+    #[serde(default)]
+    pub command: Option<String>,
+    /// Files larger than this (in KiB) fail the large-file check.
+    #[serde(default = "default_large_file_kb")]
+    pub large_file_kb: u64,
+}
+
+fn default_large_file_kb() -> u64 {
+    1024
+}
+
+impl Default for PushConfig {
+    fn default() -> Self {
+        Self {
+            checklist: true,
+            command: None,
+            large_file_kb: default_large_file_kb(),
+        }
+    }
+}
+
+/// Safety-net snapshots of the working tree, browsable from the Stash view.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SnapshotConfig {
+    /// Periodically snapshot uncommitted work under `refs/zit/snapshots` (default: false).
+    #[serde(default)]
+    pub enabled: bool,
+    /// Minutes between snapshots; unchanged trees are skipped.
+    #[serde(default = "default_snapshot_interval")]
+    pub interval_mins: u64,
+    /// Snapshots kept; older ones are pruned (0: keep all).
+    #[serde(default = "default_snapshot_keep")]
+    pub keep: usize,
+}
+
+fn default_snapshot_interval() -> u64 {
+    10
+}
+
+fn default_snapshot_keep() -> usize {
+    50
+}
+
+impl Default for SnapshotConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval_mins: default_snapshot_interval(),
+            keep: default_snapshot_keep(),
+        }
+    }
+}
+
+/// Local usage statistics, shown from the Dashboard (`S`).
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct StatsConfig {
+    /// Count git actions and views visited in a file in the state directory,
+    /// never sent anywhere (default: false).
+    #[serde(default)]
+    pub enabled: bool,
+}
+
+/// `zit prompt`, the repository summary for shell prompts.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PromptConfig {
+    /// Placeholders: {branch} {ahead} {behind} {staged} {unstaged} {untracked}
+    /// {conflicts} {stash} {dirty} {operation}. Empty ones print nothing.
+    #[serde(default = "default_prompt_format")]
+    pub format: String,
+    /// Count untracked files, which can be slow in very large trees.
+    #[serde(default = "default_true")]
+    pub untracked: bool,
+}
+
+fn default_prompt_format() -> String {
+    "{branch} {ahead}{behind}{conflicts}{staged}{unstaged}{untracked}{operation}".to_string()
+}
+
+impl Default for PromptConfig {
+    fn default() -> Self {
+        Self {
+            format: default_prompt_format(),
+            untracked: true,
+        }
+    }
+}
+
+/// Configuration for the offline commit message spellchecker.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SpellcheckConfig {
+    /// Enable/disable spellchecking in the Commit view (default: true).
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Dictionary language, used to find a system hunspell dictionary (e.g. "en_US", "de_DE").
+    #[serde(default = "default_spellcheck_language")]
+    pub language: String,
+    /// Explicit word list or hunspell `.dic` file; overrides the system lookup.
+    #[serde(default)]
+    pub dictionary: Option<String>,
+    /// Extra words to accept (project names, jargon). Words added from the
+    /// suggestion popup are saved here.
+    #[serde(default)]
+    pub words: Vec<String>,
+}
+
+fn default_spellcheck_language() -> String {
+    "en_US".to_string()
+}
+
+impl Default for SpellcheckConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            language: default_spellcheck_language(),
+            dictionary: None,
+            words: Vec::new(),
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct AiConfig {
     /// Enable AI mentor features.
@@ -139,12 +363,88 @@ pub struct AiConfig {
     /// Request timeout in seconds.
     #[serde(default)]
     pub timeout_secs: Option<u64>,
+    /// Optional extra project context sent with AI requests (`[ai.context]`).
+    #[serde(default)]
+    pub context: AiContextConfig,
+    /// Secret redaction applied to every AI payload (`[ai.redaction]`).
+    #[serde(default)]
+    pub redaction: AiRedactionConfig,
+    /// Maximum diff characters sent per request (default: 4000).
+    #[serde(default)]
+    pub diff_budget: Option<usize>,
+}
+
+/// Extra repository context to include in AI requests. Everything is off by default
+/// so nothing beyond the working-tree state leaves the machine unless asked for.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct AiContextConfig {
+    /// Include an excerpt of the README.
+    #[serde(default)]
+    pub readme: bool,
+    /// Include an excerpt of CONTRIBUTING (commit conventions, style rules).
+    #[serde(default)]
+    pub contributing: bool,
+    /// Number of recent commit subjects to include (0 disables).
+    #[serde(default)]
+    pub recent_commits: usize,
+    /// Include the repository's default branch name.
+    #[serde(default)]
+    pub default_branch: bool,
+}
+
+/// Redaction rules for AI payloads. On by default: diffs are masked with the secret
+/// scanner's rules before leaving the machine, and matching files are never sent.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AiRedactionConfig {
+    /// Enable/disable redaction (default: true).
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Additional regex patterns to mask, on top of the secret scanner's rules.
+    #[serde(default)]
+    pub patterns: Vec<String>,
+    /// File globs (matched on path or file name) whose contents are never sent.
+    #[serde(default = "default_redaction_excludes")]
+    pub exclude_files: Vec<String>,
+}
+
+fn default_redaction_excludes() -> Vec<String> {
+    [
+        ".env",
+        ".env.*",
+        "*.pem",
+        "*.key",
+        "*.p12",
+        "*.pfx",
+        "id_rsa",
+        "id_dsa",
+        "id_ecdsa",
+        "id_ed25519",
+        ".npmrc",
+        ".pypirc",
+        ".netrc",
+    ]
+    .iter()
+    .map(|s| s.to_string())
+    .collect()
+}
+
+impl Default for AiRedactionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            patterns: Vec::new(),
+            exclude_files: default_redaction_excludes(),
+        }
+    }
 }
 
 fn default_provider() -> String {
     "bedrock".to_string()
 }
 
+/// Smallest accepted `diff_budget`; anything lower leaves no room for a single hunk.
+const MIN_DIFF_BUDGET: usize = 500;
+
 /// All supported AI provider names.
 pub const VALID_PROVIDERS: &[&str] = &["bedrock", "openai", "anthropic", "openrouter", "ollama"];
 
@@ -160,10 +460,10 @@ impl AiConfig {
 
     /// Get the effective model name, falling back to a per-provider default.
     pub fn effective_model(&self) -> String {
-        if let Some(ref m) = self.model {
-            if !m.is_empty() {
-                return m.clone();
-            }
+        if let Some(ref m) = self.model
+            && !m.is_empty()
+        {
+            return m.clone();
         }
         match self.effective_provider() {
             "openai" => "gpt-4o".to_string(),
@@ -174,6 +474,13 @@ impl AiConfig {
         }
     }
 
+    /// Get the effective diff budget in characters, falling back to the default.
+    pub fn effective_diff_budget(&self) -> usize {
+        self.diff_budget
+            .unwrap_or(crate::ai::DIFF_TRUNCATE_AT)
+            .max(MIN_DIFF_BUDGET)
+    }
+
     /// Get the effective endpoint, falling back to per-provider defaults.
     /// Ignores a saved endpoint if it appears to belong to a different provider.
     pub fn effective_endpoint(&self) -> Option<String> {
@@ -230,6 +537,9 @@ impl Default for AiConfig {
             endpoint: None,
             api_key: None,
             timeout_secs: Some(30),
+            context: AiContextConfig::default(),
+            redaction: AiRedactionConfig::default(),
+            diff_budget: None,
         }
     }
 }
@@ -312,10 +622,10 @@ impl AiConfig {
                     "API key required for '{}' — add 'api_key' to [ai] config or set ZIT_AI_API_KEY",
                     provider
                 ));
-            } else if let Some(ref key) = resolved_key {
-                if key.len() < 8 {
-                    issues.push("AI API key seems too short (< 8 chars)".to_string());
-                }
+            } else if let Some(ref key) = resolved_key
+                && key.len() < 8
+            {
+                issues.push("AI API key seems too short (< 8 chars)".to_string());
             }
         }
 
@@ -338,6 +648,16 @@ impl AiConfig {
             }
         }
 
+        // Check diff budget
+        if let Some(budget) = self.diff_budget
+            && budget < MIN_DIFF_BUDGET
+        {
+            issues.push(format!(
+                "AI diff_budget must be at least {} characters",
+                MIN_DIFF_BUDGET
+            ));
+        }
+
         issues
     }
 
@@ -356,19 +676,20 @@ impl AiConfig {
 }
 
 impl Config {
-    /// Get the config file path (~/.config/zit/config.toml).
+    /// Get the config file path: `--config`, `ZIT_CONFIG`, or config.toml
+    /// in the platform's config directory (see `crate::paths`).
     pub fn path() -> PathBuf {
-        let config_dir = dirs::config_dir()
-            .unwrap_or_else(|| PathBuf::from("."))
-            .join("zit");
-        config_dir.join("config.toml")
+        crate::paths::config_file().0
     }
 
     /// Load config from file, falling back to defaults if file doesn't exist.
     pub fn load() -> Result<Self> {
-        let path = Self::path();
+        Self::load_from(&Self::path())
+    }
+
+    fn load_from(path: &std::path::Path) -> Result<Self> {
         if path.exists() {
-            let content = std::fs::read_to_string(&path)?;
+            let content = std::fs::read_to_string(path)?;
             let config: Config = toml::from_str(&content)?;
             Ok(config)
         } else {
@@ -398,6 +719,36 @@ impl Config {
     }
 }
 
+/// Notices when the config file changes on disk, by its modification time
+/// and size, so edits made while zit runs take effect without a restart.
+pub struct ConfigWatcher {
+    path: PathBuf,
+    stamp: Option<(std::time::SystemTime, u64)>,
+}
+
+impl ConfigWatcher {
+    pub fn new(path: PathBuf) -> Self {
+        let stamp = Self::stamp_of(&path);
+        Self { path, stamp }
+    }
+
+    fn stamp_of(path: &std::path::Path) -> Option<(std::time::SystemTime, u64)> {
+        let meta = std::fs::metadata(path).ok()?;
+        Some((meta.modified().ok()?, meta.len()))
+    }
+
+    /// The config as the file now has it, if the file changed since the last
+    /// call. A deleted file is left alone: zit keeps what it has.
+    pub fn poll(&mut self) -> Option<Result<Config>> {
+        let stamp = Self::stamp_of(&self.path);
+        if stamp == self.stamp {
+            return None;
+        }
+        self.stamp = stamp;
+        stamp.map(|_| Config::load_from(&self.path))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -411,6 +762,9 @@ mod tests {
             endpoint: Some("https://api.example.com/mentor".to_string()),
             api_key: Some("test-api-key-12345".to_string()),
             timeout_secs: Some(30),
+            context: Default::default(),
+            redaction: Default::default(),
+            diff_budget: None,
         }
     }
 
@@ -428,6 +782,9 @@ mod tests {
         let u = UiConfig::default();
         assert_eq!(u.color_scheme, "default");
         assert!(u.show_help_hints);
+        assert_eq!(u.icons, "auto");
+        assert_eq!(u.colors, "auto");
+        assert_eq!(u.date_format, "relative");
     }
 
     // ── AiConfig defaults ───────────────────────────────────────────
@@ -497,7 +854,9 @@ mod tests {
 
     #[test]
     fn test_ai_not_ready_missing_endpoint() {
-        std::env::remove_var("ZIT_AI_ENDPOINT");
+        unsafe {
+            std::env::remove_var("ZIT_AI_ENDPOINT");
+        }
         let a = AiConfig {
             enabled: true,
             provider: "bedrock".to_string(),
@@ -505,13 +864,18 @@ mod tests {
             endpoint: None,
             api_key: Some("key12345".to_string()),
             timeout_secs: Some(30),
+            context: Default::default(),
+            redaction: Default::default(),
+            diff_budget: None,
         };
         assert!(!a.is_ready());
     }
 
     #[test]
     fn test_ai_not_ready_missing_key() {
-        std::env::remove_var("ZIT_AI_API_KEY");
+        unsafe {
+            std::env::remove_var("ZIT_AI_API_KEY");
+        }
         let a = AiConfig {
             enabled: true,
             provider: "bedrock".to_string(),
@@ -519,13 +883,18 @@ mod tests {
             endpoint: Some("https://api.example.com".to_string()),
             api_key: None,
             timeout_secs: Some(30),
+            context: Default::default(),
+            redaction: Default::default(),
+            diff_budget: None,
         };
         assert!(!a.is_ready());
     }
 
     #[test]
     fn test_ollama_ready_without_key() {
-        std::env::remove_var("ZIT_AI_API_KEY");
+        unsafe {
+            std::env::remove_var("ZIT_AI_API_KEY");
+        }
         let a = AiConfig {
             enabled: true,
             provider: "ollama".to_string(),
@@ -533,6 +902,9 @@ mod tests {
             endpoint: None, // will default to localhost
             api_key: None,
             timeout_secs: Some(30),
+            context: Default::default(),
+            redaction: Default::default(),
+            diff_budget: None,
         };
         assert!(a.is_ready());
     }
@@ -546,6 +918,9 @@ mod tests {
             endpoint: None, // has built-in default
             api_key: Some("sk-test12345678".to_string()),
             timeout_secs: Some(30),
+            context: Default::default(),
+            redaction: Default::default(),
+            diff_budget: None,
         };
         assert!(a.is_ready());
     }
@@ -559,7 +934,9 @@ mod tests {
 
     #[test]
     fn test_validate_bedrock_no_endpoint() {
-        std::env::remove_var("ZIT_AI_ENDPOINT");
+        unsafe {
+            std::env::remove_var("ZIT_AI_ENDPOINT");
+        }
         let a = AiConfig {
             enabled: true,
             provider: "bedrock".to_string(),
@@ -567,6 +944,9 @@ mod tests {
             endpoint: None,
             api_key: Some("test-key-1234".to_string()),
             timeout_secs: Some(30),
+            context: Default::default(),
+            redaction: Default::default(),
+            diff_budget: None,
         };
         let issues = a.validate();
         assert!(issues.iter().any(|i| i.contains("endpoint")));
@@ -581,11 +961,16 @@ mod tests {
             endpoint: Some("ftp://example.com".to_string()),
             api_key: Some("test-key-1234".to_string()),
             timeout_secs: Some(30),
+            context: Default::default(),
+            redaction: Default::default(),
+            diff_budget: None,
         };
         let issues = a.validate();
-        assert!(issues
-            .iter()
-            .any(|i| i.contains("must start with https://")));
+        assert!(
+            issues
+                .iter()
+                .any(|i| i.contains("must start with https://"))
+        );
     }
 
     #[test]
@@ -597,6 +982,9 @@ mod tests {
             endpoint: Some("https://api.example.com".to_string()),
             api_key: Some("abc".to_string()),
             timeout_secs: Some(30),
+            context: Default::default(),
+            redaction: Default::default(),
+            diff_budget: None,
         };
         let issues = a.validate();
         assert!(issues.iter().any(|i| i.contains("too short")));
@@ -632,6 +1020,9 @@ mod tests {
             endpoint: None,
             api_key: None,
             timeout_secs: None,
+            context: Default::default(),
+            redaction: Default::default(),
+            diff_budget: None,
         };
         let issues = a.validate();
         assert!(issues.iter().any(|i| i.contains("Unknown AI provider")));
@@ -639,7 +1030,9 @@ mod tests {
 
     #[test]
     fn test_validate_ollama_no_key_ok() {
-        std::env::remove_var("ZIT_AI_API_KEY");
+        unsafe {
+            std::env::remove_var("ZIT_AI_API_KEY");
+        }
         let a = AiConfig {
             enabled: true,
             provider: "ollama".to_string(),
@@ -647,6 +1040,9 @@ mod tests {
             endpoint: None,
             api_key: None,
             timeout_secs: Some(30),
+            context: Default::default(),
+            redaction: Default::default(),
+            diff_budget: None,
         };
         assert!(a.validate().is_empty());
     }
@@ -664,6 +1060,7 @@ mod tests {
             pat: Some("pat-token".to_string()),
             oauth_token: Some("oauth-token".to_string()),
             username: None,
+            milestone: None,
         };
         assert_eq!(g.get_token(), Some("oauth-token".to_string()));
     }
@@ -674,6 +1071,7 @@ mod tests {
             pat: Some("pat-token".to_string()),
             oauth_token: None,
             username: None,
+            milestone: None,
         };
         assert_eq!(g.get_token(), Some("pat-token".to_string()));
     }
@@ -685,15 +1083,27 @@ mod tests {
             general: GeneralConfig {
                 tick_rate_ms: 500,
                 confirm_destructive: false,
+                git_backend: "cli".to_string(),
             },
             github: GithubConfig {
                 pat: Some("ghp_test".to_string()),
                 oauth_token: None,
                 username: Some("user".to_string()),
+                milestone: Some("v1.0".to_string()),
             },
             ui: UiConfig {
                 color_scheme: "dark".to_string(),
                 show_help_hints: false,
+                icons: "ascii".to_string(),
+                colors: "16".to_string(),
+                date_format: "%Y-%m-%d".to_string(),
+                image_preview: false,
+                staging_sort: "modified".to_string(),
+                staging_group: false,
+                identicons: false,
+                github_avatars: true,
+                desktop_notifications: false,
+                zen: true,
             },
             ai: AiConfig {
                 enabled: true,
@@ -702,17 +1112,108 @@ mod tests {
                 endpoint: None,
                 api_key: Some("key123456".to_string()),
                 timeout_secs: Some(60),
+                context: AiContextConfig {
+                    contributing: true,
+                    recent_commits: 10,
+                    ..AiContextConfig::default()
+                },
+                redaction: AiRedactionConfig {
+                    patterns: vec!["INTERNAL-[0-9]+".to_string()],
+                    ..AiRedactionConfig::default()
+                },
+                diff_budget: Some(8000),
+            },
+            secrets: SecretsConfig::default(),
+            spellcheck: SpellcheckConfig {
+                language: "de_DE".to_string(),
+                words: vec!["zit".to_string()],
+                ..SpellcheckConfig::default()
+            },
+            commit: CommitConfig {
+                gitmoji: true,
+                gitmoji_prefix: false,
+                sign_off: true,
             },
+            push: PushConfig {
+                command: Some("cargo test".to_string()),
+                large_file_kb: 512,
+                ..PushConfig::default()
+            },
+            snapshot: SnapshotConfig {
+                enabled: true,
+                interval_mins: 5,
+                keep: 20,
+            },
+            prompt: PromptConfig {
+                format: "{branch}{dirty}".to_string(),
+                untracked: false,
+            },
+            stats: StatsConfig { enabled: true },
         };
         let toml_str = toml::to_string_pretty(&config).unwrap();
         let parsed: Config = toml::from_str(&toml_str).unwrap();
         assert_eq!(parsed.general.tick_rate_ms, 500);
+        assert_eq!(parsed.prompt.format, "{branch}{dirty}");
+        assert!(!parsed.prompt.untracked);
+        assert!(parsed.stats.enabled);
         assert!(!parsed.general.confirm_destructive);
         assert_eq!(parsed.github.pat, Some("ghp_test".to_string()));
+        assert_eq!(parsed.github.milestone.as_deref(), Some("v1.0"));
         assert_eq!(parsed.ui.color_scheme, "dark");
+        assert_eq!(parsed.ui.icons, "ascii");
+        assert!(!parsed.ui.image_preview);
+        assert_eq!(parsed.ui.staging_sort, "modified");
+        assert!(!parsed.ui.staging_group);
+        assert!(!parsed.ui.identicons);
+        assert!(parsed.ui.github_avatars);
+        assert!(!parsed.ui.desktop_notifications);
+        assert!(parsed.ui.zen);
         assert!(parsed.ai.enabled);
         assert_eq!(parsed.ai.provider, "openai");
         assert_eq!(parsed.ai.model, Some("gpt-4o".to_string()));
+        assert!(parsed.ai.context.contributing);
+        assert!(!parsed.ai.context.readme);
+        assert_eq!(parsed.ai.context.recent_commits, 10);
+        assert!(parsed.ai.redaction.enabled);
+        assert_eq!(parsed.ai.redaction.patterns, vec!["INTERNAL-[0-9]+"]);
+        assert!(parsed.spellcheck.enabled);
+        assert_eq!(parsed.spellcheck.language, "de_DE");
+        assert_eq!(parsed.spellcheck.words, vec!["zit"]);
+        assert!(parsed.commit.gitmoji);
+        assert!(!parsed.commit.gitmoji_prefix);
+        assert!(parsed.commit.sign_off);
+        assert!(parsed.push.checklist);
+        assert_eq!(parsed.push.command.as_deref(), Some("cargo test"));
+        assert_eq!(parsed.push.large_file_kb, 512);
+        assert!(parsed.snapshot.enabled);
+        assert_eq!(parsed.snapshot.interval_mins, 5);
+        assert_eq!(parsed.snapshot.keep, 20);
+        assert_eq!(parsed.ai.diff_budget, Some(8000));
+    }
+
+    #[test]
+    fn test_diff_budget_default_and_validation() {
+        let mut a = bedrock_config();
+        assert_eq!(a.effective_diff_budget(), crate::ai::DIFF_TRUNCATE_AT);
+        a.diff_budget = Some(12_000);
+        assert_eq!(a.effective_diff_budget(), 12_000);
+        assert!(a.validate().is_empty());
+        a.diff_budget = Some(10);
+        assert_eq!(a.effective_diff_budget(), MIN_DIFF_BUDGET);
+        assert!(a.validate().iter().any(|i| i.contains("diff_budget")));
+    }
+
+    #[test]
+    fn test_ai_redaction_defaults_when_section_missing() {
+        let parsed: Config = toml::from_str("[ai]\nenabled = true\n").unwrap();
+        assert_eq!(parsed.ai.redaction, AiRedactionConfig::default());
+        assert!(
+            parsed
+                .ai
+                .redaction
+                .exclude_files
+                .contains(&".env".to_string())
+        );
     }
 
     // ── Config::default has expected values ──────────────────────────
@@ -729,29 +1230,39 @@ mod tests {
     // ── AiConfig::resolved_endpoint env fallback ────────────────────
     #[test]
     fn test_resolved_endpoint_prefers_config() {
-        std::env::set_var("ZIT_AI_ENDPOINT", "https://env.example.com");
+        unsafe {
+            std::env::set_var("ZIT_AI_ENDPOINT", "https://env.example.com");
+        }
         let a = AiConfig {
             endpoint: Some("https://config.example.com".to_string()),
             ..AiConfig::default()
         };
         assert_eq!(a.resolved_endpoint().unwrap(), "https://config.example.com");
-        std::env::remove_var("ZIT_AI_ENDPOINT");
+        unsafe {
+            std::env::remove_var("ZIT_AI_ENDPOINT");
+        }
     }
 
     #[test]
     fn test_resolved_endpoint_env_fallback() {
-        std::env::set_var("ZIT_AI_ENDPOINT", "https://env.example.com");
+        unsafe {
+            std::env::set_var("ZIT_AI_ENDPOINT", "https://env.example.com");
+        }
         let a = AiConfig {
             endpoint: None,
             ..AiConfig::default()
         };
         assert_eq!(a.resolved_endpoint().unwrap(), "https://env.example.com");
-        std::env::remove_var("ZIT_AI_ENDPOINT");
+        unsafe {
+            std::env::remove_var("ZIT_AI_ENDPOINT");
+        }
     }
 
     #[test]
     fn test_effective_endpoint_provider_defaults() {
-        std::env::remove_var("ZIT_AI_ENDPOINT");
+        unsafe {
+            std::env::remove_var("ZIT_AI_ENDPOINT");
+        }
         let a = AiConfig {
             provider: "openai".to_string(),
             ..AiConfig::default()
@@ -768,4 +1279,31 @@ mod tests {
         };
         assert!(a.effective_endpoint().is_none()); // bedrock requires explicit
     }
+
+    #[test]
+    fn test_watcher_sees_edits() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        std::fs::write(&path, "[general]\ntick_rate_ms = 2000\n").unwrap();
+        let mut watcher = ConfigWatcher::new(path.clone());
+        assert!(watcher.poll().is_none());
+
+        std::fs::write(&path, "[general]\ntick_rate_ms = 500\n").unwrap();
+        // Same size: only the modification time tells, so make sure it moved
+        let later = std::time::SystemTime::now() + std::time::Duration::from_secs(5);
+        std::fs::File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(later)
+            .unwrap();
+        let config = watcher.poll().unwrap().unwrap();
+        assert_eq!(config.general.tick_rate_ms, 500);
+        assert!(watcher.poll().is_none());
+
+        std::fs::write(&path, "[general\n").unwrap();
+        assert!(watcher.poll().unwrap().is_err());
+        std::fs::remove_file(&path).unwrap();
+        assert!(watcher.poll().is_none());
+    }
 }
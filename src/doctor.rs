@@ -0,0 +1,441 @@
+//! `zit doctor`: checks what zit leans on — git, the terminal, the keychain,
+//! the config, GitHub and the AI endpoint — and says how to fix what doesn't
+//! work, so a bug report can start from the report instead of questions.
+
+use std::io::IsTerminal;
+use std::path::Path;
+
+use crate::config::Config;
+use crate::git;
+use crate::paths::{self, ConfigSource};
+use crate::ui::icons::IconMode;
+use crate::ui::theme::ColorMode;
+
+/// How a check went.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Pass,
+    /// Works, but not as well as it could.
+    Warn,
+    Fail,
+    /// Not set up, so nothing to check.
+    Skip,
+}
+
+#[derive(Debug)]
+pub struct Check {
+    pub name: &'static str,
+    pub outcome: Outcome,
+    pub detail: String,
+    /// What to do about a warning or failure.
+    pub fix: Option<String>,
+}
+
+impl Check {
+    fn new(name: &'static str, outcome: Outcome, detail: impl Into<String>) -> Self {
+        Self {
+            name,
+            outcome,
+            detail: detail.into(),
+            fix: None,
+        }
+    }
+
+    fn fix(mut self, fix: impl Into<String>) -> Self {
+        self.fix = Some(fix.into());
+        self
+    }
+}
+
+/// Run every check, in the order they're reported.
+pub fn run() -> Vec<Check> {
+    let (path, source) = paths::config_file();
+    let (config_check, config) = check_config(&path, source);
+    git::backend::configure(&config.general.git_backend);
+    vec![
+        check_git(),
+        check_repository(),
+        config_check,
+        check_terminal(
+            ColorMode::resolve(&config.ui.colors, false),
+            IconMode::from_config(&config.ui.icons).is_none(),
+            crate::ui::icons::detect(),
+            std::env::var("TERM").ok(),
+            std::io::stdout().is_terminal(),
+            crossterm::terminal::size().ok(),
+        ),
+        check_keychain(),
+        check_github(&config),
+        check_ai(&config),
+    ]
+}
+
+fn check_git() -> Check {
+    let version = match git::run_git(&["--version"]) {
+        Ok(version) => version.trim().to_string(),
+        Err(e) => {
+            return Check::new("git", Outcome::Fail, format!("not found: {}", e))
+                .fix("Install git 2.13 or newer and make sure it's on PATH");
+        }
+    };
+    match git::runner::check_git_version() {
+        Ok(()) => Check::new("git", Outcome::Pass, version),
+        Err(e) => Check::new("git", Outcome::Fail, e.to_string())
+            .fix("Upgrade git; zit needs 2.13 or newer"),
+    }
+}
+
+fn check_repository() -> Check {
+    if !git::runner::is_git_repo() {
+        return Check::new("repository", Outcome::Skip, "not in a git repository");
+    }
+    let root = git::run_git(&["rev-parse", "--show-toplevel"]).unwrap_or_default();
+    match git::backend::check() {
+        Ok(()) => Check::new(
+            "repository",
+            Outcome::Pass,
+            format!("{} (read through libgit2)", root.trim()),
+        ),
+        Err(reason) => Check::new(
+            "repository",
+            Outcome::Pass,
+            format!("{} (read through git: {})", root.trim(), reason),
+        ),
+    }
+}
+
+/// The config check, and the config the other checks use: the file's, or the
+/// defaults when it's missing or broken.
+fn check_config(path: &Path, source: ConfigSource) -> (Check, Config) {
+    let shown = format!("{} ({})", path.display(), source);
+    if !path.exists() {
+        let check = if source == ConfigSource::Default {
+            Check::new(
+                "config",
+                Outcome::Pass,
+                format!("{}: not created yet, using defaults", shown),
+            )
+        } else {
+            Check::new("config", Outcome::Fail, format!("{}: no such file", shown))
+                .fix("Check the path given to --config or ZIT_CONFIG")
+        };
+        return (check, Config::default());
+    }
+    match Config::load() {
+        Ok(config) => (Check::new("config", Outcome::Pass, shown), config),
+        Err(e) => (
+            Check::new(
+                "config",
+                Outcome::Fail,
+                format!(
+                    "{}: {}",
+                    shown,
+                    e.to_string().lines().next().unwrap_or_default()
+                ),
+            )
+            .fix("Fix the line above, or move the file aside to start from the defaults"),
+            Config::default(),
+        ),
+    }
+}
+
+/// Colors and icons as zit will draw them, and whether the terminal fits.
+/// `auto_icons` is whether `[ui] icons` left them to `detected`.
+fn check_terminal(
+    colors: ColorMode,
+    auto_icons: bool,
+    detected: IconMode,
+    term: Option<String>,
+    tty: bool,
+    size: Option<(u16, u16)>,
+) -> Check {
+    let term = term.unwrap_or_default();
+    let mut parts = vec![if term.is_empty() {
+        "TERM unset".to_string()
+    } else {
+        format!("TERM={}", term)
+    }];
+    parts.push(
+        match colors {
+            ColorMode::TrueColor => "truecolor",
+            ColorMode::Ansi16 => "16 colors",
+            ColorMode::NoColor => "no color",
+        }
+        .to_string(),
+    );
+    if let Some((width, height)) = size {
+        parts.push(format!("{}×{}", width, height));
+    }
+    let detail = parts.join(", ");
+
+    if !tty {
+        return Check::new(
+            "terminal",
+            Outcome::Warn,
+            format!("{}; output isn't a terminal", detail),
+        )
+        .fix("Run zit itself in an interactive terminal, not through a pipe");
+    }
+    if term == "dumb" {
+        return Check::new("terminal", Outcome::Fail, detail).fix(
+            "Use a terminal emulator that supports cursor movement (TERM=dumb can't draw the UI)",
+        );
+    }
+    let (min_width, min_height) = (crate::ui::utils::MIN_WIDTH, crate::ui::utils::MIN_HEIGHT);
+    if let Some((width, height)) = size
+        && (width < min_width || height < min_height)
+    {
+        return Check::new("terminal", Outcome::Warn, detail).fix(format!(
+            "Make the window at least {}×{}",
+            min_width, min_height
+        ));
+    }
+    if auto_icons && detected == IconMode::Ascii {
+        return Check::new("terminal", Outcome::Warn, format!("{}, ASCII icons", detail))
+            .fix("Set a UTF-8 locale (e.g. LANG=en_US.UTF-8) for icons, or [ui] icons = \"ascii\" to keep these");
+    }
+    if colors == ColorMode::Ansi16 {
+        return Check::new("terminal", Outcome::Pass, detail)
+            .fix("If your terminal does 24-bit color, set COLORTERM=truecolor or [ui] colors = \"truecolor\"");
+    }
+    Check::new("terminal", Outcome::Pass, detail)
+}
+
+fn check_keychain() -> Check {
+    match crate::keychain::availability() {
+        Ok(()) => Check::new(
+            "keychain",
+            Outcome::Pass,
+            "available for the GitHub token and AI key",
+        ),
+        Err(e) => Check::new("keychain", Outcome::Warn, format!("unavailable: {}", e)).fix(
+            "Secrets will be kept in config.toml (readable only by you); \
+             start or unlock your OS keychain to keep them there instead",
+        ),
+    }
+}
+
+fn check_github(config: &Config) -> Check {
+    let Some(token) = config.github.get_token() else {
+        return Check::new("GitHub", Outcome::Skip, "not signed in")
+            .fix("Sign in from the GitHub view (g) to use pull requests, issues and CI runs");
+    };
+    match git::github_auth::token_scopes(&token) {
+        Ok((login, None)) => Check::new(
+            "GitHub",
+            Outcome::Pass,
+            format!("signed in as {} (fine-grained token)", login),
+        ),
+        Ok((login, Some(scopes))) => {
+            let missing = git::github_auth::missing_scopes(&scopes);
+            if missing.is_empty() {
+                Check::new(
+                    "GitHub",
+                    Outcome::Pass,
+                    format!("signed in as {} (scopes: {})", login, scopes.join(", ")),
+                )
+            } else {
+                Check::new(
+                    "GitHub",
+                    Outcome::Fail,
+                    format!(
+                        "signed in as {}, but the token lacks {}",
+                        login,
+                        missing.join(", ")
+                    ),
+                )
+                .fix("Log out and sign in again from the GitHub view (g) to grant them")
+            }
+        }
+        Err(e) if e.downcast_ref::<git::github_auth::Unauthorized>().is_some() => {
+            Check::new("GitHub", Outcome::Fail, format!("token rejected: {}", e))
+                .fix("It expired or was revoked — sign in again from the GitHub view (g)")
+        }
+        Err(e) => Check::new("GitHub", Outcome::Warn, format!("{:#}", e))
+            .fix("Check your network connection or proxy settings"),
+    }
+}
+
+fn check_ai(config: &Config) -> Check {
+    let ai = &config.ai;
+    if !ai.enabled {
+        return Check::new("AI", Outcome::Skip, "turned off ([ai] enabled = false)");
+    }
+    let provider = ai.effective_provider();
+    let wizard = "Run the setup wizard (p in the AI Mentor)";
+    let Some(endpoint) = ai.effective_endpoint() else {
+        return Check::new(
+            "AI",
+            Outcome::Fail,
+            format!("{}: no endpoint set", provider),
+        )
+        .fix(format!("{}, or set ZIT_AI_ENDPOINT", wizard));
+    };
+    if let Some(problem) = crate::ai::setup::check_endpoint(&endpoint) {
+        return Check::new("AI", Outcome::Fail, format!("{}: {}", endpoint, problem))
+            .fix(format!("{} to enter the endpoint again", wizard));
+    }
+    if provider != "ollama" {
+        match ai.resolved_api_key() {
+            None => {
+                return Check::new("AI", Outcome::Fail, format!("{}: no API key", provider))
+                    .fix(format!("{}, or set ZIT_AI_API_KEY", wizard));
+            }
+            Some(key) => {
+                if let Some(problem) = crate::ai::setup::check_api_key(provider, &key) {
+                    return Check::new("AI", Outcome::Warn, format!("{}: {}", provider, problem))
+                        .fix(format!("{} and press k to replace the key", wizard));
+                }
+            }
+        }
+    }
+    match crate::ai::setup::probe(&endpoint) {
+        Ok(()) => Check::new(
+            "AI",
+            Outcome::Pass,
+            format!("{}, {} answers", provider, endpoint),
+        ),
+        Err(e) => Check::new("AI", Outcome::Fail, e)
+            .fix(format!("{} and press e to change the endpoint", wizard)),
+    }
+}
+
+/// The report: a line per check, each fix under it, then a tally.
+pub fn render(checks: &[Check]) -> String {
+    let mut out = String::new();
+    for check in checks {
+        let mark = match check.outcome {
+            Outcome::Pass => "✓",
+            Outcome::Warn => "!",
+            Outcome::Fail => "✗",
+            Outcome::Skip => "-",
+        };
+        out.push_str(&format!("  {} {:<11} {}\n", mark, check.name, check.detail));
+        if let Some(fix) = &check.fix {
+            out.push_str(&format!("{:<16}→ {}\n", "", fix));
+        }
+    }
+    let count = |outcome| checks.iter().filter(|c| c.outcome == outcome).count();
+    let plural = |n: usize| if n == 1 { "" } else { "s" };
+    out.push('\n');
+    out.push_str(&match (count(Outcome::Fail), count(Outcome::Warn)) {
+        (0, 0) => "Everything looks good.".to_string(),
+        (0, w) => format!("{} warning{}.", w, plural(w)),
+        (f, 0) => format!("{} problem{}.", f, plural(f)),
+        (f, w) => format!("{} problem{}, {} warning{}.", f, plural(f), w, plural(w)),
+    });
+    out
+}
+
+/// Whether any check failed outright.
+pub fn failed(checks: &[Check]) -> bool {
+    checks.iter().any(|c| c.outcome == Outcome::Fail)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::{Method, Response, mock::MockTransport};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_terminal_check() {
+        let term = || Some("xterm-256color".to_string());
+        let check = check_terminal(
+            ColorMode::TrueColor,
+            true,
+            IconMode::Emoji,
+            term(),
+            true,
+            Some((120, 40)),
+        );
+        assert_eq!(check.outcome, Outcome::Pass);
+        assert_eq!(check.detail, "TERM=xterm-256color, truecolor, 120×40");
+        assert!(check.fix.is_none());
+
+        let small = check_terminal(
+            ColorMode::TrueColor,
+            true,
+            IconMode::Emoji,
+            term(),
+            true,
+            Some((30, 10)),
+        );
+        assert_eq!(small.outcome, Outcome::Warn);
+        assert_eq!(small.fix.as_deref(), Some("Make the window at least 40×12"));
+
+        let no_locale =
+            check_terminal(ColorMode::Ansi16, true, IconMode::Ascii, term(), true, None);
+        assert_eq!(no_locale.outcome, Outcome::Warn);
+        assert!(no_locale.fix.unwrap().contains("UTF-8"));
+        // Chosen in the config, ASCII icons are fine
+        let chosen = check_terminal(
+            ColorMode::Ansi16,
+            false,
+            IconMode::Ascii,
+            term(),
+            true,
+            None,
+        );
+        assert_eq!(chosen.outcome, Outcome::Pass);
+
+        let dumb = check_terminal(
+            ColorMode::NoColor,
+            true,
+            IconMode::Ascii,
+            Some("dumb".into()),
+            true,
+            None,
+        );
+        assert_eq!(dumb.outcome, Outcome::Fail);
+    }
+
+    #[test]
+    fn test_github_check() {
+        let mut config = Config::default();
+        assert_eq!(check_github(&config).outcome, Outcome::Skip);
+
+        config.github.oauth_token = Some("gho_x".to_string());
+        let mock = Arc::new(MockTransport::new().on(
+            Method::Get,
+            "/user",
+            Response::new(200, r#"{"login":"mona"}"#).with_header("X-OAuth-Scopes", "read:user"),
+        ));
+        let check = mock.serve(|| check_github(&config));
+        assert_eq!(check.outcome, Outcome::Fail);
+        assert_eq!(check.detail, "signed in as mona, but the token lacks repo");
+
+        let mock = Arc::new(MockTransport::new().on(
+            Method::Get,
+            "/user",
+            Response::new(401, r#"{"message":"Bad credentials"}"#),
+        ));
+        let check = mock.serve(|| check_github(&config));
+        assert_eq!(check.outcome, Outcome::Fail);
+        assert_eq!(check.detail, "token rejected: Bad credentials");
+    }
+
+    #[test]
+    fn test_render_report() {
+        let checks = vec![
+            Check::new("git", Outcome::Pass, "git version 2.45.0"),
+            Check::new("keychain", Outcome::Warn, "unavailable").fix("Unlock it"),
+            Check::new("AI", Outcome::Fail, "no API key").fix("Set ZIT_AI_API_KEY"),
+        ];
+        assert_eq!(
+            render(&checks),
+            "  ✓ git         git version 2.45.0\n\
+             \x20 ! keychain    unavailable\n\
+             \x20               → Unlock it\n\
+             \x20 ✗ AI          no API key\n\
+             \x20               → Set ZIT_AI_API_KEY\n\
+             \n\
+             1 problem, 1 warning."
+        );
+        assert!(failed(&checks));
+        assert_eq!(
+            render(&checks[..1]).lines().last(),
+            Some("Everything looks good.")
+        );
+    }
+}
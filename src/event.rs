@@ -1,5 +1,6 @@
 use crossterm::event::{self, Event, KeyEvent, KeyEventKind, MouseEvent};
-use std::sync::mpsc;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, mpsc};
 use std::thread;
 use std::time::Duration;
 
@@ -7,6 +8,12 @@ pub enum AppEvent {
     Key(KeyEvent),
     Mouse(MouseEvent),
     Tick,
+    /// Background work produced new output; redraw without a full refresh.
+    Redraw,
+    /// Text pasted into the terminal (bracketed paste).
+    Paste(String),
+    /// A command from `zit remote`.
+    Remote(crate::ipc::Request),
     #[allow(dead_code)] // dispatched from event loop but fields not read in match arm
     Resize(u16, u16),
 }
@@ -14,6 +21,10 @@ pub enum AppEvent {
 pub struct EventHandler {
     rx: mpsc::Receiver<AppEvent>,
     _tx: mpsc::Sender<AppEvent>,
+    /// While set, the reader thread leaves the terminal alone (e.g. for an editor).
+    paused: Arc<AtomicBool>,
+    /// Milliseconds between ticks; changeable while running.
+    tick_rate: Arc<AtomicU64>,
 }
 
 impl EventHandler {
@@ -21,27 +32,44 @@ impl EventHandler {
     pub fn new(tick_rate_ms: u64) -> Self {
         let (tx, rx) = mpsc::channel();
         let event_tx = tx.clone();
-        let tick_rate = Duration::from_millis(tick_rate_ms);
+        let tick_rate = Arc::new(AtomicU64::new(tick_rate_ms));
+        let thread_tick_rate = Arc::clone(&tick_rate);
+        let paused = Arc::new(AtomicBool::new(false));
+        let thread_paused = Arc::clone(&paused);
 
         thread::spawn(move || {
             loop {
+                if thread_paused.load(Ordering::SeqCst) {
+                    thread::sleep(Duration::from_millis(20));
+                    continue;
+                }
                 // Poll for crossterm events with tick_rate timeout
+                let tick_rate = Duration::from_millis(thread_tick_rate.load(Ordering::SeqCst));
                 if event::poll(tick_rate).unwrap_or(false) {
+                    if thread_paused.load(Ordering::SeqCst) {
+                        continue;
+                    }
                     match event::read() {
-                        Ok(Event::Key(key)) if key.kind == KeyEventKind::Press => {
-                            if event_tx.send(AppEvent::Key(key)).is_err() {
-                                return;
-                            }
+                        Ok(Event::Key(key))
+                            if key.kind == KeyEventKind::Press
+                                && event_tx.send(AppEvent::Key(key)).is_err() =>
+                        {
+                            return;
                         }
-                        Ok(Event::Mouse(mouse)) => {
-                            if event_tx.send(AppEvent::Mouse(mouse)).is_err() {
-                                return;
-                            }
+                        Ok(Event::Mouse(mouse))
+                            if event_tx.send(AppEvent::Mouse(mouse)).is_err() =>
+                        {
+                            return;
                         }
-                        Ok(Event::Resize(w, h)) => {
-                            if event_tx.send(AppEvent::Resize(w, h)).is_err() {
-                                return;
-                            }
+                        Ok(Event::Paste(text))
+                            if event_tx.send(AppEvent::Paste(text.clone())).is_err() =>
+                        {
+                            return;
+                        }
+                        Ok(Event::Resize(w, h))
+                            if event_tx.send(AppEvent::Resize(w, h)).is_err() =>
+                        {
+                            return;
                         }
                         _ => {}
                     }
@@ -54,11 +82,48 @@ impl EventHandler {
             }
         });
 
-        Self { rx, _tx: tx }
+        Self {
+            rx,
+            _tx: tx,
+            paused,
+            tick_rate,
+        }
+    }
+
+    /// Stop reading terminal input so a child process can take it over. Returns
+    /// once any poll already in flight has timed out.
+    pub fn pause(&self) {
+        self.paused.store(true, Ordering::SeqCst);
+        thread::sleep(Duration::from_millis(self.tick_rate.load(Ordering::SeqCst)));
+    }
+
+    /// Change the time between ticks, from the next one on.
+    pub fn set_tick_rate(&self, tick_rate_ms: u64) {
+        self.tick_rate.store(tick_rate_ms, Ordering::SeqCst);
+    }
+
+    /// Resume reading terminal input after [`pause`](Self::pause).
+    pub fn resume(&self) {
+        self.paused.store(false, Ordering::SeqCst);
+    }
+
+    /// A sender that background threads can use to wake the main loop.
+    pub fn waker(&self) -> mpsc::Sender<AppEvent> {
+        self._tx.clone()
     }
 
     /// Receive the next event (blocking).
     pub fn next(&self) -> Result<AppEvent, mpsc::RecvError> {
         self.rx.recv()
     }
+
+    /// Receive the next event, giving up after `timeout`.
+    pub fn next_timeout(&self, timeout: Duration) -> Result<AppEvent, mpsc::RecvTimeoutError> {
+        self.rx.recv_timeout(timeout)
+    }
+
+    /// An event that's already queued, without waiting.
+    pub fn try_next(&self) -> Option<AppEvent> {
+        self.rx.try_recv().ok()
+    }
 }
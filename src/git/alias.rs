@@ -0,0 +1,140 @@
+//! The user's git aliases (`alias.<name>` in git config), so they can be run
+//! from zit the way they're run from the shell.
+
+use anyhow::Result;
+
+use super::runner::run_git;
+
+/// One `alias.<name>` entry.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Alias {
+    pub name: String,
+    /// What it expands to, e.g. "log --oneline -20" or "!f() { git push origin $1; }; f".
+    pub command: String,
+}
+
+impl Alias {
+    /// How many positional arguments (`$1`, `$2`, ...) a shell alias (`!...`)
+    /// uses. Plain aliases take none; extra arguments can't be placed in them.
+    pub fn placeholders(&self) -> usize {
+        if !self.command.starts_with('!') {
+            return 0;
+        }
+        let bytes = self.command.as_bytes();
+        bytes
+            .windows(2)
+            .filter(|w| w[0] == b'$' && w[1].is_ascii_digit() && w[1] != b'0')
+            .map(|w| (w[1] - b'0') as usize)
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+/// All aliases visible in this repository (system, global and local config),
+/// sorted by name.
+pub fn list() -> Result<Vec<Alias>> {
+    // No aliases at all makes --get-regexp exit with status 1
+    let output = run_git(&["config", "-z", "--get-regexp", r"^alias\."]).unwrap_or_default();
+    Ok(parse_aliases(&output))
+}
+
+/// Split what was typed for an alias's arguments the way a shell would, with
+/// single or double quotes grouping words.
+pub fn split_args(input: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_word = false;
+    let mut quote = None;
+    for c in input.chars() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), c) => current.push(c),
+            (None, '\'' | '"') => {
+                quote = Some(c);
+                in_word = true;
+            }
+            (None, c) if c.is_whitespace() => {
+                if in_word {
+                    args.push(std::mem::take(&mut current));
+                    in_word = false;
+                }
+            }
+            (None, c) => {
+                current.push(c);
+                in_word = true;
+            }
+        }
+    }
+    if in_word {
+        args.push(current);
+    }
+    args
+}
+
+/// Parse `git config -z --get-regexp` output: `alias.<name>`, a newline, then
+/// the value, NUL-terminated. Later entries (more local config) win.
+fn parse_aliases(output: &str) -> Vec<Alias> {
+    let mut aliases: Vec<Alias> = Vec::new();
+    for entry in output.split('\0') {
+        let Some((key, command)) = entry.split_once('\n') else {
+            continue;
+        };
+        let Some(name) = key.strip_prefix("alias.") else {
+            continue;
+        };
+        aliases.retain(|a| a.name != name);
+        aliases.push(Alias {
+            name: name.to_string(),
+            command: command.to_string(),
+        });
+    }
+    aliases.sort_by(|a, b| a.name.cmp(&b.name));
+    aliases
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn alias(command: &str) -> Alias {
+        Alias {
+            name: "x".to_string(),
+            command: command.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_parse_aliases() {
+        let output = "alias.st\nstatus -sb\0alias.lg\nlog --oneline\n--graph\0alias.st\nstatus\0";
+        let aliases = parse_aliases(output);
+        assert_eq!(aliases.len(), 2);
+        assert_eq!(aliases[0].name, "lg");
+        assert_eq!(aliases[0].command, "log --oneline\n--graph");
+        assert_eq!(aliases[1].name, "st");
+        assert_eq!(aliases[1].command, "status");
+        assert!(parse_aliases("").is_empty());
+    }
+
+    #[test]
+    fn test_placeholders() {
+        assert_eq!(alias("log --oneline -20").placeholders(), 0);
+        assert_eq!(alias("!git push origin $1").placeholders(), 1);
+        assert_eq!(
+            alias("!f() { git branch $2 $1 && echo $0; }; f").placeholders(),
+            2
+        );
+        // Not a shell alias: `$1` is passed to git as is
+        assert_eq!(alias("log $1").placeholders(), 0);
+    }
+
+    #[test]
+    fn test_split_args() {
+        assert_eq!(split_args("  main  feature "), ["main", "feature"]);
+        assert_eq!(
+            split_args(r#"-m "fix the bug" 'a b'"#),
+            ["-m", "fix the bug", "a b"]
+        );
+        assert_eq!(split_args(r#""""#), [""]);
+        assert!(split_args("   ").is_empty());
+    }
+}
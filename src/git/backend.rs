@@ -0,0 +1,774 @@
+//! Where the reads every view repeats on each tick come from: status, log,
+//! diffs and branches. libgit2 answers them in-process, so a refresh doesn't
+//! spawn a handful of git processes; the git CLI answers what libgit2 can't —
+//! repositories it fails to open, graphs through merges, revision options —
+//! and everything when `[general] git_backend = "cli"`. Writes always go
+//! through the CLI. Both keep to the path scope (see `scope`), when one is set.
+
+use super::branch::BranchEntry;
+use super::diff::{self, FileDiff};
+use super::log::CommitEntry;
+use super::status::{FileEntry, FileStatus, RepoStatus};
+use anyhow::{Result, bail};
+use git2::{
+    BranchType, Delta, DiffFindOptions, DiffFormat, DiffOptions, IndexEntryExtendedFlag,
+    Repository, Status, StatusOptions,
+};
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// The reads that run on every refresh.
+pub trait GitBackend: Sync {
+    /// Branch, upstream, ahead/behind, changed files and stash count, as
+    /// `git status --porcelain=v2 --branch` reports them.
+    fn status(&self) -> Result<RepoStatus>;
+
+    /// `count` commits after skipping `skip`, with `git log --graph` lines.
+    /// `revs` selects what to walk; empty means HEAD.
+    fn log(&self, count: usize, skip: usize, revs: &[&str]) -> Result<Vec<CommitEntry>>;
+
+    /// Staged (index vs HEAD) or unstaged (worktree vs index) changes, limited
+    /// to exactly `paths` when there are any.
+    fn diff(&self, staged: bool, paths: &[&str]) -> Result<Vec<FileDiff>>;
+
+    /// Local branches, then remote ones, by name.
+    fn branches(&self) -> Result<Vec<BranchEntry>>;
+}
+
+/// Whether `[general] git_backend = "cli"` turned libgit2 off.
+static CLI_ONLY: AtomicBool = AtomicBool::new(false);
+
+/// Apply `[general] git_backend`: "cli" sends every read through git.
+pub fn configure(name: &str) {
+    CLI_ONLY.store(name.eq_ignore_ascii_case("cli"), Ordering::Relaxed);
+}
+
+/// The backend reads go through.
+pub fn current() -> &'static dyn GitBackend {
+    if CLI_ONLY.load(Ordering::Relaxed) {
+        &Cli
+    } else {
+        &Libgit2
+    }
+}
+
+/// Runs `git` for every read.
+pub struct Cli;
+
+impl GitBackend for Cli {
+    fn status(&self) -> Result<RepoStatus> {
+        super::status::status_from_cli(super::scope::get().as_deref())
+    }
+
+    fn log(&self, count: usize, skip: usize, revs: &[&str]) -> Result<Vec<CommitEntry>> {
+        super::log::log_from_cli(count, skip, revs, super::scope::get().as_deref())
+    }
+
+    fn diff(&self, staged: bool, paths: &[&str]) -> Result<Vec<FileDiff>> {
+        let scope = super::scope::get();
+        diff::diff_from_cli(staged, &scoped(paths, &scope))
+    }
+
+    fn branches(&self) -> Result<Vec<BranchEntry>> {
+        super::branch::branches_from_cli()
+    }
+}
+
+/// Reads through libgit2, falling back to `Cli` whenever it fails.
+pub struct Libgit2;
+
+impl GitBackend for Libgit2 {
+    fn status(&self) -> Result<RepoStatus> {
+        let scope = super::scope::get();
+        or_cli(
+            "status",
+            || repo_status(&open()?, scope.as_deref()),
+            || Cli.status(),
+        )
+    }
+
+    fn log(&self, count: usize, skip: usize, revs: &[&str]) -> Result<Vec<CommitEntry>> {
+        // libgit2 walks without path limiting; git simplifies the history
+        if super::scope::get().is_some() {
+            return Cli.log(count, skip, revs);
+        }
+        or_cli(
+            "log",
+            || repo_log(&open()?, count, skip, revs),
+            || Cli.log(count, skip, revs),
+        )
+    }
+
+    fn diff(&self, staged: bool, paths: &[&str]) -> Result<Vec<FileDiff>> {
+        let scope = super::scope::get();
+        or_cli(
+            "diff",
+            || repo_diff(&open()?, staged, &scoped(paths, &scope)),
+            || Cli.diff(staged, paths),
+        )
+    }
+
+    fn branches(&self) -> Result<Vec<BranchEntry>> {
+        or_cli("branches", || repo_branches(&open()?), || Cli.branches())
+    }
+}
+
+/// `paths`, or when there are none, the scope.
+fn scoped<'a>(paths: &[&'a str], scope: &'a Option<String>) -> Vec<&'a str> {
+    match (paths, scope) {
+        ([], Some(scope)) => vec![scope.as_str()],
+        _ => paths.to_vec(),
+    }
+}
+
+/// The repository git would use here, honoring `GIT_DIR` and friends.
+fn open() -> Result<Repository> {
+    Ok(Repository::open_from_env()?)
+}
+
+/// Whether reads here go through libgit2, or why they fall back to git.
+pub fn check() -> Result<(), String> {
+    if CLI_ONLY.load(Ordering::Relaxed) {
+        return Err("[general] git_backend = \"cli\"".to_string());
+    }
+    open().map(|_| ()).map_err(|e| e.to_string())
+}
+
+/// `read`, or `cli` if it fails.
+fn or_cli<T>(
+    what: &str,
+    read: impl FnOnce() -> Result<T>,
+    cli: impl FnOnce() -> Result<T>,
+) -> Result<T> {
+    read().or_else(|e| {
+        log::debug!("libgit2 {}: {:#}; asking git", what, e);
+        cli()
+    })
+}
+
+/// `refs/heads/main` → `main`, `refs/remotes/origin/main` → `origin/main`.
+fn short_ref(name: &str) -> &str {
+    ["refs/heads/", "refs/remotes/", "refs/tags/"]
+        .iter()
+        .find_map(|prefix| name.strip_prefix(prefix))
+        .unwrap_or(name)
+}
+
+/// The branch HEAD points at, checked out or unborn; `None` when detached.
+fn head_branch(repo: &Repository) -> Option<String> {
+    let head = repo.find_reference("HEAD").ok()?;
+    head.symbolic_target()
+        .filter(|t| t.starts_with("refs/heads/"))
+        .map(str::to_string)
+}
+
+/// The upstream `branch` (a full ref name) is configured to track, short.
+fn upstream_of(repo: &Repository, branch: &str) -> Option<String> {
+    let upstream = repo.branch_upstream_name(branch).ok()?;
+    upstream.as_str().map(|u| short_ref(u).to_string())
+}
+
+/// Paths added with `git add -N`. libgit2 calls them staged; git shows them
+/// only as unstaged additions.
+fn intent_to_add(repo: &Repository) -> Result<HashSet<String>> {
+    Ok(repo
+        .index()?
+        .iter()
+        .filter(|e| IndexEntryExtendedFlag::from_bits_truncate(e.flags_extended).is_intent_to_add())
+        .map(|e| String::from_utf8_lossy(&e.path).into_owned())
+        .collect())
+}
+
+fn repo_status(repo: &Repository, scope: Option<&str>) -> Result<RepoStatus> {
+    if repo.is_bare() {
+        bail!("bare repository");
+    }
+    let mut status = RepoStatus::default();
+    match head_branch(repo) {
+        Some(branch) => {
+            status.branch = short_ref(&branch).to_string();
+            if let Ok(upstream) = repo.branch_upstream_name(&branch)
+                && let Some(upstream) = upstream.as_str()
+            {
+                status.upstream = Some(short_ref(upstream).to_string());
+                // No counts when the upstream is gone, as with git
+                if let (Ok(local), Ok(remote)) =
+                    (repo.refname_to_id(&branch), repo.refname_to_id(upstream))
+                {
+                    let (ahead, behind) = repo.graph_ahead_behind(local, remote)?;
+                    status.ahead = ahead as u32;
+                    status.behind = behind as u32;
+                }
+            }
+        }
+        None => status.branch = "(detached)".to_string(),
+    }
+
+    let untracked = repo
+        .config()?
+        .get_string("status.showUntrackedFiles")
+        .unwrap_or_default();
+    let mut options = StatusOptions::new();
+    options
+        .include_untracked(untracked != "no")
+        .recurse_untracked_dirs(untracked == "all")
+        .include_ignored(false)
+        .renames_head_to_index(true);
+    if let Some(scope) = scope {
+        options.pathspec(scope).disable_pathspec_match(true);
+    }
+
+    let intent_to_add = intent_to_add(repo)?;
+    let entry = |status: FileStatus, path: &str, original_path: Option<&str>| FileEntry {
+        status,
+        path: path.to_string(),
+        original_path: original_path.map(str::to_string),
+    };
+    for file in repo.statuses(Some(&mut options))?.iter() {
+        let bits = file.status();
+        let Some(path) = file.path() else {
+            bail!("path is not UTF-8");
+        };
+        if bits.contains(Status::CONFLICTED) {
+            status
+                .conflicts
+                .push(entry(FileStatus::Conflicted, path, None));
+            continue;
+        }
+        if bits.contains(Status::WT_NEW) && !bits.intersects(Status::INDEX_NEW) {
+            status
+                .untracked
+                .push(entry(FileStatus::Untracked, path, None));
+            continue;
+        }
+        if intent_to_add.contains(path) {
+            status.unstaged.push(entry(FileStatus::Added, path, None));
+            continue;
+        }
+
+        let renamed = file
+            .head_to_index()
+            .filter(|_| bits.contains(Status::INDEX_RENAMED));
+        let new_path = renamed
+            .as_ref()
+            .and_then(|d| d.new_file().path())
+            .and_then(|p| p.to_str())
+            .unwrap_or(path);
+        let staged = if bits.contains(Status::INDEX_NEW) {
+            Some(FileStatus::Added)
+        } else if bits.contains(Status::INDEX_MODIFIED) {
+            Some(FileStatus::Modified)
+        } else if bits.contains(Status::INDEX_DELETED) {
+            Some(FileStatus::Deleted)
+        } else if bits.contains(Status::INDEX_RENAMED) {
+            Some(FileStatus::Renamed)
+        } else {
+            None
+        };
+        if let Some(kind) = staged {
+            let original = renamed
+                .as_ref()
+                .and_then(|d| d.old_file().path())
+                .and_then(|p| p.to_str());
+            status.staged.push(entry(kind, new_path, original));
+        }
+
+        let unstaged = if bits.contains(Status::WT_MODIFIED) {
+            Some(FileStatus::Modified)
+        } else if bits.contains(Status::WT_DELETED) {
+            Some(FileStatus::Deleted)
+        } else {
+            None
+        };
+        if let Some(kind) = unstaged {
+            status.unstaged.push(entry(kind, new_path, None));
+        }
+    }
+
+    status.stash_count = match repo.find_reference("refs/stash") {
+        Ok(_) => repo.reflog("refs/stash")?.len() as u32,
+        Err(_) => 0,
+    };
+    Ok(status)
+}
+
+/// Decorations by commit, as `git log --decorate=full` prints them: HEAD
+/// first, then the other branches, remote branches and tags in reverse ref
+/// order.
+fn decorations(repo: &Repository) -> Result<HashMap<git2::Oid, Vec<String>>> {
+    let mut by_commit: HashMap<git2::Oid, Vec<String>> = HashMap::new();
+    let mut names = Vec::new();
+    for reference in repo.references()? {
+        let reference = reference?;
+        if let Some(name) = reference.name()
+            && ["refs/heads/", "refs/remotes/", "refs/tags/"]
+                .iter()
+                .any(|p| name.starts_with(p))
+            && let Ok(commit) = reference.peel_to_commit()
+        {
+            names.push((name.to_string(), commit.id()));
+        }
+    }
+    names.sort();
+    for (name, id) in names.into_iter().rev() {
+        let label = if name.starts_with("refs/tags/") {
+            format!("tag: {}", name)
+        } else {
+            name
+        };
+        by_commit.entry(id).or_default().push(label);
+    }
+
+    if let Ok(head) = repo.head()
+        && let Ok(commit) = head.peel_to_commit()
+    {
+        let refs = by_commit.entry(commit.id()).or_default();
+        match head_branch(repo) {
+            Some(branch) => {
+                refs.retain(|r| *r != branch);
+                refs.insert(0, format!("HEAD -> {}", branch));
+            }
+            None => refs.insert(0, "HEAD".to_string()),
+        }
+    }
+    Ok(by_commit)
+}
+
+/// A commit's time as `git log %aI` writes it: ISO 8601 in its own offset.
+fn iso_date(time: git2::Time) -> String {
+    let offset =
+        jiff::tz::Offset::from_seconds(time.offset_minutes() * 60).unwrap_or(jiff::tz::Offset::UTC);
+    jiff::Timestamp::from_second(time.seconds())
+        .map(|ts| {
+            ts.to_zoned(jiff::tz::TimeZone::fixed(offset))
+                .strftime("%Y-%m-%dT%H:%M:%S%:z")
+                .to_string()
+        })
+        .unwrap_or_default()
+}
+
+/// A commit's age as `git log %ar` writes it: "3 days ago", "2 years, 1 month ago".
+fn relative_date(time: git2::Time) -> String {
+    let now = jiff::Timestamp::now().as_second();
+    age(now - time.seconds())
+}
+
+/// `seconds` as git words an age (git's `show_date_relative`).
+fn age(seconds: i64) -> String {
+    let ago = |n: i64, unit: &str| {
+        let plural = if n == 1 { "" } else { "s" };
+        format!("{} {}{} ago", n, unit, plural)
+    };
+    if seconds < 0 {
+        return "in the future".to_string();
+    }
+    if seconds < 90 {
+        return ago(seconds, "second");
+    }
+    let minutes = (seconds + 30) / 60;
+    if minutes < 90 {
+        return ago(minutes, "minute");
+    }
+    let hours = (minutes + 30) / 60;
+    if hours < 36 {
+        return ago(hours, "hour");
+    }
+    let days = (hours + 12) / 24;
+    if days < 14 {
+        return ago(days, "day");
+    }
+    if days < 70 {
+        return ago((days + 3) / 7, "week");
+    }
+    if days < 365 {
+        return ago((days + 15) / 30, "month");
+    }
+    if days < 1825 {
+        let total_months = (days * 12 * 2 + 365) / (365 * 2);
+        let (years, months) = (total_months / 12, total_months % 12);
+        if months > 0 {
+            let plural = |n: i64| if n == 1 { "" } else { "s" };
+            return format!(
+                "{} year{}, {} month{} ago",
+                years,
+                plural(years),
+                months,
+                plural(months)
+            );
+        }
+        return ago(years, "year");
+    }
+    ago((days + 183) / 365, "year")
+}
+
+/// One revision walked from, or an error for what only git understands
+/// (ranges, `--branches`, several starting points).
+fn start_of(repo: &Repository, revs: &[&str]) -> Result<git2::Oid> {
+    match revs {
+        [] => Ok(repo.head()?.peel_to_commit()?.id()),
+        [rev] if !rev.starts_with('-') && !rev.contains("..") => {
+            Ok(repo.revparse_single(rev)?.peel_to_commit()?.id())
+        }
+        _ => bail!("revisions {:?} need git", revs),
+    }
+}
+
+/// Follows parents from the start commit rather than a revwalk: only straight
+/// history has a graph of one `*` per commit, and there the parent chain is
+/// the order `git log --graph` prints. A merge fails the read as soon as the
+/// chain reaches it, before anything else is looked up.
+fn repo_log(
+    repo: &Repository,
+    count: usize,
+    skip: usize,
+    revs: &[&str],
+) -> Result<Vec<CommitEntry>> {
+    let mut chain = Vec::new();
+    let mut next = Some(repo.find_commit(start_of(repo, revs)?)?);
+    while let Some(commit) = next
+        && chain.len() < skip + count
+    {
+        if commit.parent_count() > 1 {
+            bail!("merge {} needs git's graph", commit.id());
+        }
+        next = commit.parents().next();
+        chain.push(commit);
+    }
+    let decorations = decorations(repo)?;
+
+    let mut entries = Vec::new();
+    for commit in chain.into_iter().skip(skip) {
+        let author = commit.author();
+        let short_hash = commit.as_object().short_id()?;
+        entries.push(CommitEntry {
+            hash: commit.id().to_string(),
+            short_hash: short_hash.as_str().unwrap_or_default().to_string(),
+            message: commit.summary().unwrap_or_default().to_string(),
+            author: author.name().unwrap_or_default().to_string(),
+            author_email: author.email().unwrap_or_default().to_string(),
+            date: relative_date(author.when()),
+            date_iso: iso_date(author.when()),
+            parents: commit.parent_ids().map(|p| p.to_string()).collect(),
+            refs: decorations
+                .get(&commit.id())
+                .map(|refs| refs.join(", "))
+                .unwrap_or_default(),
+            graph: "* ".to_string(),
+        });
+    }
+    Ok(entries)
+}
+
+fn repo_diff(repo: &Repository, staged: bool, paths: &[&str]) -> Result<Vec<FileDiff>> {
+    let mut options = DiffOptions::new();
+    options.disable_pathspec_match(true);
+    for path in paths {
+        options.pathspec(path);
+    }
+    let mut diff = if staged {
+        let head = repo.head().ok().and_then(|h| h.peel_to_tree().ok());
+        repo.diff_tree_to_index(head.as_ref(), None, Some(&mut options))?
+    } else {
+        repo.diff_index_to_workdir(None, Some(&mut options))?
+    };
+
+    // `diff.renames` defaults to on, as it does for `git diff`
+    let renames = repo
+        .config()?
+        .get_string("diff.renames")
+        .unwrap_or_else(|_| "true".to_string())
+        .to_lowercase();
+    if !["false", "no", "off", "0"].contains(&renames.as_str()) {
+        let mut find = DiffFindOptions::new();
+        find.renames(true)
+            .copies(renames.starts_with("cop"))
+            .copies_from_unmodified(false);
+        diff.find_similar(Some(&mut find))?;
+    }
+
+    let mut text = String::new();
+    diff.print(DiffFormat::Patch, |delta, _, line| {
+        // Typechanges aren't shown as their own files by the porcelain
+        if delta.status() == Delta::Typechange {
+            return true;
+        }
+        let content = String::from_utf8_lossy(line.content());
+        match line.origin() {
+            origin @ ('+' | '-' | ' ') => text.push(origin),
+            '=' | '>' | '<' => {
+                // "\ No newline at end of file", after a newline of its own
+                text.push_str(content.trim_start_matches('\n'));
+                return true;
+            }
+            _ => {}
+        }
+        text.push_str(&content);
+        if !content.ends_with('\n') {
+            text.push('\n');
+        }
+        true
+    })?;
+    let mut files = diff::parse_diff_output(&text);
+    if staged {
+        let intent_to_add = intent_to_add(repo)?;
+        files.retain(|f| !intent_to_add.contains(&f.path));
+    }
+    Ok(files)
+}
+
+fn repo_branches(repo: &Repository) -> Result<Vec<BranchEntry>> {
+    let head = head_branch(repo);
+    let descriptions = repo.config()?;
+    let now = jiff::Timestamp::now().as_second();
+
+    let mut refs = Vec::new();
+    for kind in [BranchType::Local, BranchType::Remote] {
+        for branch in repo.branches(Some(kind))? {
+            let (branch, _) = branch?;
+            let reference = branch.get();
+            let Some(name) = reference.name() else {
+                continue;
+            };
+            let Ok(commit) = reference.peel_to_commit() else {
+                continue;
+            };
+            refs.push((name.to_string(), kind, commit));
+        }
+    }
+    refs.sort_by(|a, b| a.0.cmp(&b.0));
+
+    Ok(refs
+        .into_iter()
+        .map(|(refname, kind, commit)| {
+            let name = short_ref(&refname).to_string();
+            let committed = commit.committer().when();
+            let description = descriptions
+                .get_string(&format!("branch.{}.description", name))
+                .ok()
+                .filter(|_| kind == BranchType::Local)
+                .map(|d| d.trim_end().to_string());
+            BranchEntry {
+                is_current: head.as_deref() == Some(refname.as_str()),
+                is_remote: kind == BranchType::Remote,
+                last_commit_msg: commit.summary().unwrap_or_default().to_string(),
+                last_commit_date: age(now - committed.seconds()),
+                last_commit_date_iso: iso_date(committed),
+                last_commit_author: commit.author().name().unwrap_or_default().to_string(),
+                upstream: match kind {
+                    BranchType::Local => upstream_of(repo, &refname).unwrap_or_default(),
+                    BranchType::Remote => String::new(),
+                },
+                description,
+                name,
+            }
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+    use std::process::Command;
+
+    /// Run git in `dir` with a fixed identity and dates.
+    fn git(dir: &Path, args: &[&str]) -> String {
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .env("GIT_AUTHOR_NAME", "Test User")
+            .env("GIT_AUTHOR_EMAIL", "test@example.com")
+            .env("GIT_COMMITTER_NAME", "Test User")
+            .env("GIT_COMMITTER_EMAIL", "test@example.com")
+            .env("GIT_AUTHOR_DATE", "2024-05-01T10:00:00+05:30")
+            .env("GIT_COMMITTER_DATE", "2024-05-01T10:00:00+05:30")
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "git {:?} failed: {}",
+            args,
+            String::from_utf8_lossy(&output.stderr)
+        );
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
+    }
+
+    /// A repository with history, branches, a tag, a fake remote, a stash and
+    /// one change of every kind.
+    fn fixture() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path();
+        git(path, &["init", "-q", "-b", "main"]);
+        std::fs::write(path.join("a.txt"), "one\ntwo\nthree\n").unwrap();
+        std::fs::write(path.join("gone.txt"), "bye\n").unwrap();
+        std::fs::write(path.join("old.txt"), "moved\nas\nis\n").unwrap();
+        git(path, &["add", "."]);
+        git(path, &["commit", "-q", "-m", "First\n\nWith a body"]);
+        git(path, &["tag", "-a", "v1", "-m", "Release"]);
+        git(
+            path,
+            &["remote", "add", "origin", "https://example.com/repo.git"],
+        );
+        git(path, &["update-ref", "refs/remotes/origin/main", "HEAD"]);
+        git(path, &["branch", "--set-upstream-to", "origin/main"]);
+        git(path, &["branch", "topic"]);
+        git(
+            path,
+            &["config", "branch.topic.description", "Work in progress"],
+        );
+        std::fs::write(path.join("a.txt"), "one\n2\nthree\n").unwrap();
+        git(path, &["commit", "-q", "-am", "Second"]);
+
+        std::fs::write(path.join("a.txt"), "stashed\n").unwrap();
+        git(path, &["stash", "-q"]);
+        std::fs::write(path.join("a.txt"), "one\n2\nthree\nfour").unwrap();
+        std::fs::write(path.join("new.txt"), "staged\n").unwrap();
+        std::fs::write(path.join("ita.txt"), "intent\n").unwrap();
+        std::fs::create_dir(path.join("dir")).unwrap();
+        std::fs::write(path.join("dir/untracked.txt"), "?\n").unwrap();
+        std::fs::remove_file(path.join("gone.txt")).unwrap();
+        git(path, &["add", "new.txt"]);
+        git(path, &["add", "-N", "ita.txt"]);
+        git(path, &["mv", "old.txt", "renamed.txt"]);
+        dir
+    }
+
+    #[test]
+    fn test_status_matches_git() {
+        let check = |dir: &Path, stash_count: u32| {
+            let repo = Repository::open(dir).unwrap();
+            let mut expected = super::super::status::parse_status(&git(
+                dir,
+                &["status", "--porcelain=v2", "--branch"],
+            ));
+            expected.stash_count = stash_count;
+            assert_eq!(
+                format!("{:?}", repo_status(&repo, None).unwrap()),
+                format!("{:?}", expected)
+            );
+        };
+        let dir = fixture();
+        check(dir.path(), 1);
+        git(dir.path(), &["checkout", "-q", "--detach"]);
+        check(dir.path(), 1);
+
+        let unborn = tempfile::tempdir().unwrap();
+        git(unborn.path(), &["init", "-q", "-b", "trunk"]);
+        std::fs::write(unborn.path().join("a.txt"), "a\n").unwrap();
+        check(unborn.path(), 0);
+    }
+
+    #[test]
+    fn test_scoped_reads_match_git() {
+        let dir = fixture();
+        std::fs::create_dir(dir.path().join("sub")).unwrap();
+        std::fs::write(dir.path().join("sub/inner.txt"), "in\n").unwrap();
+        git(dir.path(), &["add", "sub"]);
+        let repo = Repository::open(dir.path()).unwrap();
+        for scope in ["dir", "sub"] {
+            let mut expected = super::super::status::parse_status(&git(
+                dir.path(),
+                &["status", "--porcelain=v2", "--branch", "--", scope],
+            ));
+            expected.stash_count = 1;
+            assert_eq!(
+                format!("{:?}", repo_status(&repo, Some(scope)).unwrap()),
+                format!("{:?}", expected)
+            );
+        }
+        let staged = repo_diff(&repo, true, &["sub"]).unwrap();
+        assert_eq!(staged.len(), 1);
+        assert_eq!(staged[0].path, "sub/inner.txt");
+    }
+
+    #[test]
+    fn test_log_matches_git() {
+        let dir = fixture();
+        let repo = Repository::open(dir.path()).unwrap();
+        let format = format!("--format={}", super::super::log::LOG_FORMAT);
+        let mut expected = super::super::log::parse_log_output(&git(
+            dir.path(),
+            &["log", "-5", &format, "--graph", "--decorate=full"],
+        ));
+        let mut entries = repo_log(&repo, 5, 0, &[]).unwrap();
+        // Relative dates move on while the test runs
+        for entry in expected.iter_mut().chain(entries.iter_mut()) {
+            entry.date.clear();
+        }
+        assert_eq!(format!("{:?}", entries), format!("{:?}", expected));
+        assert_eq!(repo_log(&repo, 5, 1, &["main"]).unwrap().len(), 1);
+
+        // Merges and revision options are left to git
+        assert!(repo_log(&repo, 5, 0, &["--branches"]).is_err());
+        let merge = git(
+            dir.path(),
+            &[
+                "commit-tree",
+                "HEAD^{tree}",
+                "-p",
+                "HEAD",
+                "-p",
+                "HEAD~1",
+                "-m",
+                "Merge",
+            ],
+        );
+        git(dir.path(), &["update-ref", "refs/heads/main", &merge]);
+        assert!(repo_log(&repo, 5, 0, &[]).is_err());
+        // A merge further back than the page is never reached
+        git(
+            dir.path(),
+            &["commit", "-q", "--allow-empty", "-m", "After"],
+        );
+        assert_eq!(repo_log(&repo, 1, 0, &[]).unwrap()[0].message, "After");
+        assert!(repo_log(&repo, 1, 1, &[]).is_err());
+    }
+
+    #[test]
+    fn test_diff_matches_git() {
+        let dir = fixture();
+        let repo = Repository::open(dir.path()).unwrap();
+        for (staged, args) in [(false, vec!["diff"]), (true, vec!["diff", "--cached"])] {
+            let expected = diff::parse_diff_output(&git(dir.path(), &args));
+            assert_eq!(
+                format!("{:?}", repo_diff(&repo, staged, &[]).unwrap()),
+                format!("{:?}", expected)
+            );
+        }
+        let only = repo_diff(&repo, false, &["a.txt"]).unwrap();
+        assert_eq!(only.len(), 1);
+        assert_eq!(only[0].path, "a.txt");
+    }
+
+    #[test]
+    fn test_branches_match_git() {
+        let dir = fixture();
+        let repo = Repository::open(dir.path()).unwrap();
+        let branches = repo_branches(&repo).unwrap();
+        let names: Vec<_> = branches.iter().map(|b| b.name.as_str()).collect();
+        assert_eq!(names, ["main", "topic", "origin/main"]);
+        let main = &branches[0];
+        assert!(main.is_current && !main.is_remote);
+        assert_eq!(main.upstream, "origin/main");
+        assert_eq!(main.last_commit_msg, "Second");
+        assert_eq!(main.last_commit_date_iso, "2024-05-01T10:00:00+05:30");
+        assert_eq!(branches[1].description.as_deref(), Some("Work in progress"));
+        assert!(branches[2].is_remote);
+        assert_eq!(branches[2].last_commit_msg, "First");
+    }
+
+    #[test]
+    fn test_age_words_like_git() {
+        assert_eq!(age(1), "1 second ago");
+        assert_eq!(age(89), "89 seconds ago");
+        assert_eq!(age(90), "2 minutes ago");
+        assert_eq!(age(3 * 3600), "3 hours ago");
+        assert_eq!(age(36 * 3600), "2 days ago");
+        assert_eq!(age(20 * 86400), "3 weeks ago");
+        assert_eq!(age(100 * 86400), "3 months ago");
+        assert_eq!(age(400 * 86400), "1 year, 1 month ago");
+        assert_eq!(age(730 * 86400), "2 years ago");
+        assert_eq!(age(3000 * 86400), "8 years ago");
+        assert_eq!(age(-5), "in the future");
+    }
+}
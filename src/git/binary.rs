@@ -0,0 +1,374 @@
+//! Summaries for binary files, whose diff is only "Binary files differ": old and
+//! new size, and dimensions for common image formats.
+
+use super::runner::run_git_bytes;
+
+/// Files larger than this are summarised by size only, without reading them.
+const MAX_INSPECT_BYTES: u64 = 8 * 1024 * 1024;
+
+/// git treats a file as binary if its first 8000 bytes contain a NUL.
+const SNIFF_BYTES: usize = 8000;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ImageFormat {
+    Png,
+    Jpeg,
+    Gif,
+    Bmp,
+    WebP,
+}
+
+impl ImageFormat {
+    pub fn name(self) -> &'static str {
+        match self {
+            ImageFormat::Png => "PNG",
+            ImageFormat::Jpeg => "JPEG",
+            ImageFormat::Gif => "GIF",
+            ImageFormat::Bmp => "BMP",
+            ImageFormat::WebP => "WebP",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ImageInfo {
+    pub format: ImageFormat,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// One side (old or new) of a binary change.
+#[derive(Debug, Clone)]
+pub struct BinaryVersion {
+    pub size: u64,
+    pub image: Option<ImageInfo>,
+    /// File contents, if small enough to inspect.
+    pub bytes: Option<Vec<u8>>,
+}
+
+/// What changed in a binary file.
+#[derive(Debug, Clone)]
+pub struct BinarySummary {
+    /// `None` when the file is newly added.
+    pub old: Option<BinaryVersion>,
+    /// `None` when the file is deleted.
+    pub new: Option<BinaryVersion>,
+}
+
+impl BinarySummary {
+    /// Human-readable summary lines for the diff pane.
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines = vec![match (&self.old, &self.new) {
+            (None, Some(_)) => "Binary file added".to_string(),
+            (Some(_), None) => "Binary file deleted".to_string(),
+            _ => "Binary file differs".to_string(),
+        }];
+        if let Some(kind) = self
+            .new
+            .as_ref()
+            .or(self.old.as_ref())
+            .and_then(|v| v.image)
+        {
+            lines.push(format!("Type: {} image", kind.format.name()));
+        }
+
+        match (&self.old, &self.new) {
+            (Some(old), Some(new)) => {
+                let sign = if new.size >= old.size { "+" } else { "-" };
+                lines.push(format!(
+                    "Size: {} → {} ({}{})",
+                    format_size(old.size),
+                    format_size(new.size),
+                    sign,
+                    format_size(new.size.abs_diff(old.size))
+                ));
+            }
+            (Some(v), None) | (None, Some(v)) => {
+                lines.push(format!("Size: {}", format_size(v.size)))
+            }
+            (None, None) => {}
+        }
+
+        let dims = |v: &Option<BinaryVersion>| {
+            v.as_ref()
+                .and_then(|v| v.image)
+                .map(|i| format!("{}×{}", i.width, i.height))
+        };
+        match (dims(&self.old), dims(&self.new)) {
+            (Some(old), Some(new)) if old != new => {
+                lines.push(format!("Dimensions: {} → {}", old, new))
+            }
+            (Some(d), _) | (None, Some(d)) => lines.push(format!("Dimensions: {}", d)),
+            (None, None) => {}
+        }
+        lines
+    }
+}
+
+/// Summarise a file whose diff is empty, if either side is binary.
+/// `staged` compares HEAD with the index; otherwise the index with the working tree.
+pub fn summarize(path: &str, staged: bool) -> Option<BinarySummary> {
+    let (old, new) = if staged {
+        (
+            blob_version(&format!("HEAD:{}", path)),
+            blob_version(&format!(":{}", path)),
+        )
+    } else {
+        (blob_version(&format!(":{}", path)), file_version(path))
+    };
+    let binary = [&old, &new].iter().any(|v| {
+        v.as_ref().is_some_and(|v| match &v.bytes {
+            Some(bytes) => looks_binary(bytes),
+            // Too large to read — almost certainly not text
+            None => true,
+        })
+    });
+    binary.then_some(BinarySummary { old, new })
+}
+
+/// Both sides of a conflicted file, if either is binary: `old` is ours
+/// (index stage 2) and `new` is theirs (stage 3). A side is `None` when it
+/// deleted the file.
+pub fn conflict_sides(path: &str) -> Option<BinarySummary> {
+    let ours = blob_version(&format!(":2:{}", path));
+    let theirs = blob_version(&format!(":3:{}", path));
+    let binary = [&ours, &theirs].iter().any(|v| {
+        v.as_ref()
+            .is_some_and(|v| v.bytes.as_deref().is_none_or(looks_binary))
+    });
+    binary.then_some(BinarySummary {
+        old: ours,
+        new: theirs,
+    })
+}
+
+/// One line describing a conflict side, e.g. "12.0 KiB, PNG image 64×64".
+pub fn describe_side(version: Option<&BinaryVersion>) -> String {
+    let Some(v) = version else {
+        return "deleted".to_string();
+    };
+    match v.image {
+        Some(i) => format!(
+            "{}, {} image {}×{}",
+            format_size(v.size),
+            i.format.name(),
+            i.width,
+            i.height
+        ),
+        None => format_size(v.size),
+    }
+}
+
+fn version(size: u64, bytes: Option<Vec<u8>>) -> BinaryVersion {
+    BinaryVersion {
+        size,
+        image: bytes.as_deref().and_then(image_info),
+        bytes,
+    }
+}
+
+fn blob_version(spec: &str) -> Option<BinaryVersion> {
+    let size: u64 = String::from_utf8(run_git_bytes(&["cat-file", "-s", spec]).ok()?)
+        .ok()?
+        .trim()
+        .parse()
+        .ok()?;
+    let bytes = if size <= MAX_INSPECT_BYTES {
+        run_git_bytes(&["cat-file", "blob", spec]).ok()
+    } else {
+        None
+    };
+    Some(version(size, bytes))
+}
+
+fn file_version(path: &str) -> Option<BinaryVersion> {
+    let size = std::fs::metadata(path).ok()?.len();
+    let bytes = if size <= MAX_INSPECT_BYTES {
+        std::fs::read(path).ok()
+    } else {
+        None
+    };
+    Some(version(size, bytes))
+}
+
+fn looks_binary(bytes: &[u8]) -> bool {
+    bytes[..bytes.len().min(SNIFF_BYTES)].contains(&0)
+}
+
+/// Format a byte count as B / KiB / MiB / GiB.
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit + 1 < UNITS.len() {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+fn be16(b: &[u8], at: usize) -> Option<u32> {
+    Some(u16::from_be_bytes(b.get(at..at + 2)?.try_into().ok()?) as u32)
+}
+
+fn le16(b: &[u8], at: usize) -> Option<u32> {
+    Some(u16::from_le_bytes(b.get(at..at + 2)?.try_into().ok()?) as u32)
+}
+
+fn be32(b: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_be_bytes(b.get(at..at + 4)?.try_into().ok()?))
+}
+
+fn le32(b: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(b.get(at..at + 4)?.try_into().ok()?))
+}
+
+/// Detect PNG, JPEG, GIF, BMP and WebP images and read their dimensions from the header.
+pub fn image_info(b: &[u8]) -> Option<ImageInfo> {
+    let (format, width, height) = if b.starts_with(b"\x89PNG\r\n\x1a\n") {
+        (ImageFormat::Png, be32(b, 16)?, be32(b, 20)?)
+    } else if b.starts_with(b"GIF87a") || b.starts_with(b"GIF89a") {
+        (ImageFormat::Gif, le16(b, 6)?, le16(b, 8)?)
+    } else if b.starts_with(b"BM") {
+        let height = le32(b, 22)? as i32;
+        (ImageFormat::Bmp, le32(b, 18)?, height.unsigned_abs())
+    } else if b.starts_with(&[0xFF, 0xD8]) {
+        let (w, h) = jpeg_dimensions(b)?;
+        (ImageFormat::Jpeg, w, h)
+    } else if b.starts_with(b"RIFF") && b.get(8..12) == Some(b"WEBP") {
+        let (w, h) = webp_dimensions(b)?;
+        (ImageFormat::WebP, w, h)
+    } else {
+        return None;
+    };
+    Some(ImageInfo {
+        format,
+        width,
+        height,
+    })
+}
+
+/// Walk JPEG segments to the first start-of-frame marker.
+fn jpeg_dimensions(b: &[u8]) -> Option<(u32, u32)> {
+    let mut pos = 2;
+    while pos + 4 <= b.len() {
+        if b[pos] != 0xFF {
+            return None;
+        }
+        let marker = b[pos + 1];
+        // SOF0..SOF15, excluding DHT (C4), JPG (C8) and DAC (CC)
+        if (0xC0..=0xCF).contains(&marker) && ![0xC4, 0xC8, 0xCC].contains(&marker) {
+            return Some((be16(b, pos + 7)?, be16(b, pos + 5)?));
+        }
+        pos += 2 + be16(b, pos + 2)? as usize;
+    }
+    None
+}
+
+fn webp_dimensions(b: &[u8]) -> Option<(u32, u32)> {
+    match b.get(12..16)? {
+        b"VP8 " => Some((le16(b, 26)? & 0x3FFF, le16(b, 28)? & 0x3FFF)),
+        b"VP8L" => {
+            let bits = le32(b, 21)?;
+            Some(((bits & 0x3FFF) + 1, ((bits >> 14) & 0x3FFF) + 1))
+        }
+        b"VP8X" => {
+            let w = le32(b, 24)? & 0xFF_FFFF;
+            let h = le32(b, 27)? & 0xFF_FFFF;
+            Some((w + 1, h + 1))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn png_header(w: u32, h: u32) -> Vec<u8> {
+        let mut b = b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR".to_vec();
+        b.extend_from_slice(&w.to_be_bytes());
+        b.extend_from_slice(&h.to_be_bytes());
+        b
+    }
+
+    #[test]
+    fn test_image_info_formats() {
+        let png = image_info(&png_header(640, 480)).unwrap();
+        assert_eq!(
+            (png.format, png.width, png.height),
+            (ImageFormat::Png, 640, 480)
+        );
+
+        let gif = image_info(b"GIF89a\x20\x00\x10\x00").unwrap();
+        assert_eq!((gif.width, gif.height), (32, 16));
+
+        let mut bmp = vec![0u8; 26];
+        bmp[..2].copy_from_slice(b"BM");
+        bmp[18..22].copy_from_slice(&100u32.to_le_bytes());
+        bmp[22..26].copy_from_slice(&(-50i32).to_le_bytes());
+        let bmp = image_info(&bmp).unwrap();
+        assert_eq!((bmp.width, bmp.height), (100, 50));
+
+        // SOI, APP0 (length 4), SOF0 with height 200 and width 300
+        let jpeg = [
+            0xFF, 0xD8, 0xFF, 0xE0, 0x00, 0x04, 0x00, 0x00, 0xFF, 0xC0, 0x00, 0x11, 0x08, 0x00,
+            0xC8, 0x01, 0x2C,
+        ];
+        let jpeg = image_info(&jpeg).unwrap();
+        assert_eq!(
+            (jpeg.format, jpeg.width, jpeg.height),
+            (ImageFormat::Jpeg, 300, 200)
+        );
+
+        assert!(image_info(b"plain text").is_none());
+    }
+
+    #[test]
+    fn test_summary_lines() {
+        let v = |size, w, h| BinaryVersion {
+            size,
+            image: Some(ImageInfo {
+                format: ImageFormat::Png,
+                width: w,
+                height: h,
+            }),
+            bytes: None,
+        };
+        let summary = BinarySummary {
+            old: Some(v(2048, 16, 16)),
+            new: Some(v(1024, 32, 32)),
+        };
+        assert_eq!(
+            summary.lines(),
+            vec![
+                "Binary file differs",
+                "Type: PNG image",
+                "Size: 2.0 KiB → 1.0 KiB (-1.0 KiB)",
+                "Dimensions: 16×16 → 32×32",
+            ]
+        );
+        let added = BinarySummary {
+            old: None,
+            new: Some(BinaryVersion {
+                size: 10,
+                image: None,
+                bytes: None,
+            }),
+        };
+        assert_eq!(added.lines(), vec!["Binary file added", "Size: 10 B"]);
+    }
+
+    #[test]
+    fn test_format_size_and_sniff() {
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(format_size(1536), "1.5 KiB");
+        assert_eq!(format_size(5 * 1024 * 1024), "5.0 MiB");
+        assert!(looks_binary(b"ab\0cd"));
+        assert!(!looks_binary(b"hello\n"));
+    }
+}
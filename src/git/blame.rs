@@ -0,0 +1,149 @@
+//! `git blame` — who last changed each line of a file, and when, parsed from
+//! `--porcelain` output for the Blame view.
+
+use std::collections::HashMap;
+
+use super::runner::run_git_bytes;
+use anyhow::Result;
+
+/// One line of a file with the commit that last changed it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlameLine {
+    /// Full hash; all zeros for a line that isn't committed yet.
+    pub hash: String,
+    pub author: String,
+    /// Author time, in seconds since the epoch.
+    pub time: i64,
+    /// The commit's subject.
+    pub summary: String,
+    /// Line number in the blamed version of the file, from 1.
+    pub line_no: u32,
+    pub content: String,
+}
+
+impl BlameLine {
+    pub fn is_committed(&self) -> bool {
+        !self.hash.chars().all(|c| c == '0')
+    }
+
+    pub fn short_hash(&self) -> &str {
+        &self.hash[..self.hash.len().min(7)]
+    }
+}
+
+/// Blame `path` as of `rev`, or the working tree when `None`.
+pub fn blame(path: &str, rev: Option<&str>) -> Result<Vec<BlameLine>> {
+    blame_lines(path, rev, None)
+}
+
+/// The commit that last changed `line` of `path` in the working tree, or
+/// `None` if the line isn't committed yet.
+pub fn last_change(path: &str, line: u32) -> Result<Option<String>> {
+    let range = format!("{},{}", line, line);
+    let lines = blame_lines(path, None, Some(&range))?;
+    Ok(lines
+        .into_iter()
+        .next()
+        .filter(BlameLine::is_committed)
+        .map(|l| l.hash))
+}
+
+/// Blame `path` at `rev`, only the lines in `range` (`-L start,end`) if given.
+fn blame_lines(path: &str, rev: Option<&str>, range: Option<&str>) -> Result<Vec<BlameLine>> {
+    let mut args = vec!["blame", "--porcelain"];
+    if let Some(range) = range {
+        args.extend(["-L", range]);
+    }
+    args.extend(rev);
+    args.extend(["--", path]);
+    // Untrimmed, so a blank or indented last line keeps its content
+    let output = run_git_bytes(&args)?;
+    Ok(parse_porcelain(&String::from_utf8_lossy(&output)))
+}
+
+/// What porcelain output says about a commit, the first time it appears.
+#[derive(Default, Clone)]
+struct CommitInfo {
+    author: String,
+    time: i64,
+    summary: String,
+}
+
+fn parse_porcelain(output: &str) -> Vec<BlameLine> {
+    let mut commits: HashMap<String, CommitInfo> = HashMap::new();
+    let mut lines = Vec::new();
+    // The hash and final line number of the entry being read
+    let mut current: Option<(String, u32)> = None;
+
+    for line in output.lines() {
+        if let Some(content) = line.strip_prefix('\t') {
+            if let Some((hash, line_no)) = current.take() {
+                let info = commits.get(&hash).cloned().unwrap_or_default();
+                lines.push(BlameLine {
+                    hash,
+                    author: info.author,
+                    time: info.time,
+                    summary: info.summary,
+                    line_no,
+                    content: content.to_string(),
+                });
+            }
+            continue;
+        }
+        let mut parts = line.split(' ');
+        let first = parts.next().unwrap_or_default();
+        if current.is_none() && first.len() == 40 && first.chars().all(|c| c.is_ascii_hexdigit()) {
+            let line_no = parts.nth(1).and_then(|n| n.parse().ok()).unwrap_or(0);
+            commits.entry(first.to_string()).or_default();
+            current = Some((first.to_string(), line_no));
+            continue;
+        }
+        let Some((hash, _)) = &current else {
+            continue;
+        };
+        let value = line.split_once(' ').map(|(_, v)| v).unwrap_or_default();
+        let info = commits.entry(hash.clone()).or_default();
+        match first {
+            "author" => info.author = value.to_string(),
+            "author-time" => info.time = value.parse().unwrap_or(0),
+            "summary" => info.summary = value.to_string(),
+            _ => {}
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const A: &str = "3f2a9c1d4e5b6a7c8d9e0f1a2b3c4d5e6f7a8b9c";
+    const B: &str = "aaaabbbbccccddddeeeeffff0000111122223333";
+    const ZERO: &str = "0000000000000000000000000000000000000000";
+
+    #[test]
+    fn test_parse_porcelain() {
+        let output = format!(
+            "{A} 1 1 2\nauthor Ada Lovelace\nauthor-mail <ada@example.com>\n\
+             author-time 1700000000\nauthor-tz +0000\nsummary First commit\n\
+             filename src/main.rs\n\tfn main() {{\n\
+             {A} 2 2\n\t}}\n\
+             {B} 1 3 1\nauthor Grace Hopper\nauthor-time 1710000000\n\
+             summary Add a comment\nprevious {A} src/main.rs\nfilename src/main.rs\n\t// done\n\
+             {ZERO} 4 4 1\nauthor Not Committed Yet\nauthor-time 1720000000\n\
+             summary Version of src/main.rs from src/main.rs\nfilename src/main.rs\n\t\n"
+        );
+        let lines = parse_porcelain(&output);
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[0].author, "Ada Lovelace");
+        assert_eq!(lines[0].content, "fn main() {");
+        assert_eq!(lines[1].line_no, 2);
+        assert_eq!(lines[1].summary, "First commit", "info carries over");
+        assert_eq!(lines[2].short_hash(), "aaaabbb");
+        assert_eq!(lines[2].time, 1710000000);
+        assert_eq!(lines[2].content, "// done");
+        assert!(lines[2].is_committed());
+        assert!(!lines[3].is_committed());
+        assert_eq!(lines[3].content, "");
+    }
+}
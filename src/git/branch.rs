@@ -1,5 +1,6 @@
 use super::runner::run_git;
 use anyhow::Result;
+use std::collections::HashMap;
 
 #[derive(Debug, Clone)]
 pub struct BranchEntry {
@@ -8,44 +9,49 @@ pub struct BranchEntry {
     pub is_remote: bool,
     pub last_commit_msg: String,
     pub last_commit_date: String,
+    pub last_commit_date_iso: String,
     pub last_commit_author: String,
     pub upstream: String,
+    /// `branch.<name>.description`, as set by `git branch --edit-description`.
+    pub description: Option<String>,
+}
+
+/// HEAD checked out at a commit rather than a branch.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DetachedHead {
+    pub short_hash: String,
+    /// The last branch checked out before detaching, to return to.
+    pub previous: Option<String>,
+    /// Commits made here that no branch, tag or remote branch contains. They
+    /// are only in the reflog once HEAD moves away.
+    pub orphaned: usize,
 }
 
 pub struct BranchOps;
 
 impl BranchOps {
-    /// List all branches (local + remote).
-    pub fn list() -> Result<Vec<BranchEntry>> {
-        let format = "%(if)%(HEAD)%(then)*%(else) %(end)\x1f%(refname:short)\x1f%(upstream:short)\x1f%(subject)\x1f%(authorname)\x1f%(committerdate:relative)";
-        let output = run_git(&["branch", "-a", "--format", format])?;
-
-        let mut branches = Vec::new();
-        for line in output.lines() {
-            if line.trim().is_empty() {
-                continue;
-            }
-            let parts: Vec<&str> = line.split('\x1f').collect();
-            if parts.len() < 6 {
-                continue;
-            }
+    /// Whether `name` is a legal branch name (`git check-ref-format --branch`).
+    /// Run directly rather than through `run_git`, which would log every
+    /// rejected name as a failure while one is being typed.
+    pub fn is_valid_name(name: &str) -> bool {
+        std::process::Command::new("git")
+            .args(["check-ref-format", "--branch", name])
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .status()
+            .is_ok_and(|s| s.success())
+    }
 
-            let is_current = parts[0].trim() == "*";
-            let name = parts[1].trim().to_string();
-            let is_remote = name.starts_with("remotes/") || name.starts_with("origin/");
-
-            branches.push(BranchEntry {
-                name,
-                is_current,
-                is_remote,
-                upstream: parts[2].trim().to_string(),
-                last_commit_msg: parts[3].trim().to_string(),
-                last_commit_author: parts[4].trim().to_string(),
-                last_commit_date: parts[5].trim().to_string(),
-            });
-        }
+    /// List all branches (local + remote), through libgit2 or git (see `backend`).
+    pub fn list() -> Result<Vec<BranchEntry>> {
+        super::backend::current().branches()
+    }
 
-        Ok(branches)
+    /// Descriptions of local branches, by branch name.
+    fn descriptions() -> HashMap<String, String> {
+        run_git(&["config", "-z", "--get-regexp", r"^branch\..*\.description$"])
+            .map(|output| parse_descriptions(&output))
+            .unwrap_or_default()
     }
 
     /// Create a new branch from HEAD or a specific commit.
@@ -83,6 +89,90 @@ impl BranchOps {
         Ok(output.trim().to_string())
     }
 
+    /// Get the repository's default branch: origin's HEAD if known, else a local main/master.
+    pub fn default_branch() -> Option<String> {
+        if let Ok(head) = run_git(&["symbolic-ref", "--short", "refs/remotes/origin/HEAD"]) {
+            let head = head.trim();
+            let name = head.strip_prefix("origin/").unwrap_or(head);
+            if !name.is_empty() {
+                return Some(name.to_string());
+            }
+        }
+        ["main", "master"]
+            .into_iter()
+            .find(|b| {
+                run_git(&[
+                    "rev-parse",
+                    "--verify",
+                    "--quiet",
+                    &format!("refs/heads/{}", b),
+                ])
+                .is_ok()
+            })
+            .map(|b| b.to_string())
+    }
+
+    /// Uncommitted changes that switching to `target` would overwrite: local
+    /// changes (untracked files included) to paths that differ between HEAD and
+    /// `target`. When empty, `git switch` carries the changes along.
+    pub fn switch_blockers(target: &str) -> Result<Vec<String>> {
+        let status = super::status::get_status()?;
+        let differing = run_git(&["diff", "--name-only", "--no-renames", "HEAD", target])?;
+        let differing: Vec<&str> = differing.lines().collect();
+        let mut blockers: Vec<String> = status
+            .staged
+            .iter()
+            .chain(&status.unstaged)
+            .chain(&status.untracked)
+            .flat_map(|f| std::iter::once(&f.path).chain(f.original_path.as_ref()))
+            .filter(|path| differing.contains(&path.as_str()))
+            .cloned()
+            .collect();
+        blockers.sort();
+        blockers.dedup();
+        Ok(blockers)
+    }
+
+    /// Check out `commit` with HEAD detached, for inspection.
+    pub fn detach(commit: &str) -> Result<()> {
+        run_git(&["switch", "--detach", commit])?;
+        Ok(())
+    }
+
+    /// Create branch `name` at HEAD and switch to it.
+    pub fn create_and_switch(name: &str) -> Result<()> {
+        run_git(&["switch", "-c", name])?;
+        Ok(())
+    }
+
+    /// Where HEAD is detached, or `None` when a branch is checked out.
+    pub fn detached_head() -> Option<DetachedHead> {
+        if run_git(&["symbolic-ref", "-q", "HEAD"]).is_ok() {
+            return None;
+        }
+        let short_hash = run_git(&["rev-parse", "--short", "HEAD"]).ok()?;
+        let reflog = run_git(&["reflog", "-n", "200", "--format=%gs"]).unwrap_or_default();
+        let branches = run_git(&["for-each-ref", "--format=%(refname:short)", "refs/heads"])
+            .unwrap_or_default();
+        let orphaned = run_git(&[
+            "rev-list",
+            "--count",
+            "HEAD",
+            "--not",
+            "--branches",
+            "--tags",
+            "--remotes",
+        ])
+        .ok()
+        .and_then(|n| n.trim().parse().ok())
+        .unwrap_or(0);
+        Some(DetachedHead {
+            short_hash: short_hash.trim().to_string(),
+            previous: previous_branch(&reflog, &branches.lines().collect::<Vec<_>>()),
+            orphaned,
+        })
+    }
+
     /// Check if there are uncommitted changes.
     pub fn has_uncommitted_changes() -> Result<bool> {
         let output = run_git(&["status", "--porcelain"])?;
@@ -90,10 +180,105 @@ impl BranchOps {
     }
 }
 
+/// `BranchOps::list` from `git branch -a`.
+pub(super) fn branches_from_cli() -> Result<Vec<BranchEntry>> {
+    let format = "%(if)%(HEAD)%(then)*%(else) %(end)\x1f%(refname:short)\x1f%(upstream:short)\x1f%(subject)\x1f%(authorname)\x1f%(committerdate:relative)\x1f%(committerdate:iso-strict)";
+    let output = run_git(&["branch", "-a", "--format", format])?;
+    let mut descriptions = BranchOps::descriptions();
+
+    let mut branches = Vec::new();
+    for line in output.lines() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let parts: Vec<&str> = line.split('\x1f').collect();
+        if parts.len() < 6 {
+            continue;
+        }
+
+        let is_current = parts[0].trim() == "*";
+        let name = parts[1].trim().to_string();
+        let is_remote = name.starts_with("remotes/") || name.starts_with("origin/");
+
+        branches.push(BranchEntry {
+            name: name.clone(),
+            is_current,
+            is_remote,
+            upstream: parts[2].trim().to_string(),
+            last_commit_msg: parts[3].trim().to_string(),
+            last_commit_author: parts[4].trim().to_string(),
+            last_commit_date: parts[5].trim().to_string(),
+            last_commit_date_iso: parts.get(6).map_or("", |s| s.trim()).to_string(),
+            description: descriptions.remove(&name),
+        });
+    }
+
+    Ok(branches)
+}
+
+/// Parse `git config -z --get-regexp` output: `branch.<name>.description`,
+/// a newline, then the (possibly multi-line) value, NUL-terminated.
+fn parse_descriptions(output: &str) -> HashMap<String, String> {
+    output
+        .split('\0')
+        .filter_map(|entry| {
+            let (key, value) = entry.split_once('\n')?;
+            let name = key.strip_prefix("branch.")?.strip_suffix(".description")?;
+            let value = value.trim();
+            (!value.is_empty()).then(|| (name.to_string(), value.to_string()))
+        })
+        .collect()
+}
+
+/// The most recent branch HEAD moved away from, from reflog subjects like
+/// "checkout: moving from main to 1a2b3c4", skipping names that are no longer
+/// local branches (commits, deleted branches).
+fn previous_branch(reflog: &str, branches: &[&str]) -> Option<String> {
+    reflog
+        .lines()
+        .filter_map(|line| line.strip_prefix("checkout: moving from "))
+        .filter_map(|moved| moved.rsplit_once(" to ").map(|(from, _)| from))
+        .find(|from| branches.contains(from))
+        .map(str::to_string)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_is_valid_name() {
+        assert!(BranchOps::is_valid_name("feature/login-form"));
+        assert!(!BranchOps::is_valid_name("bad..name"));
+        assert!(!BranchOps::is_valid_name("has space"));
+        assert!(!BranchOps::is_valid_name("ends.lock"));
+    }
+
+    #[test]
+    fn test_previous_branch() {
+        let reflog = "checkout: moving from 1a2b3c4 to 5d6e7f8\n\
+                      commit: Try something\n\
+                      checkout: moving from gone to 1a2b3c4\n\
+                      checkout: moving from main to gone\n";
+        assert_eq!(
+            previous_branch(reflog, &["main", "feature"]),
+            Some("main".to_string())
+        );
+        assert_eq!(previous_branch("commit: Try something\n", &["main"]), None);
+    }
+
+    #[test]
+    fn test_parse_descriptions() {
+        let output = "branch.feature/login.description\nNew login flow\n\nShips with 2.0\n\0\
+                      branch.empty.description\n\n\0";
+        let descriptions = parse_descriptions(output);
+        assert_eq!(descriptions.len(), 1);
+        assert_eq!(
+            descriptions["feature/login"],
+            "New login flow\n\nShips with 2.0"
+        );
+    }
+
     #[test]
     fn test_current_branch() {
         // This test only works inside a git repo
@@ -17,6 +17,19 @@ pub fn cherry_pick_multiple(commit_hashes: &[&str]) -> Result<String> {
     run_git(&args)
 }
 
+/// `hashes` in the order to pick them: oldest commit first.
+pub fn oldest_first(hashes: &[String]) -> Result<Vec<String>> {
+    let mut args = vec!["rev-list", "--no-walk=sorted", "--reverse"];
+    args.extend(hashes.iter().map(String::as_str));
+    let output = run_git(&args)?;
+    Ok(output.lines().map(|l| l.trim().to_string()).collect())
+}
+
+/// Whether `commit` is already in the current branch's history.
+pub fn is_on_current_branch(commit_hash: &str) -> bool {
+    run_git(&["merge-base", "--is-ancestor", commit_hash, "HEAD"]).is_ok()
+}
+
 /// Abort an in-progress cherry-pick.
 pub fn cherry_pick_abort() -> Result<String> {
     run_git(&["cherry-pick", "--abort"])
@@ -65,7 +78,7 @@ pub fn get_current_branch() -> String {
 pub fn get_cherry_candidates(source_branch: &str, count: usize) -> Result<Vec<CommitEntry>> {
     let range = format!("HEAD..{}", source_branch);
     let count_str = format!("-{}", count);
-    let format_str = "--format=%H\x1f%h\x1f%s\x1f%an\x1f%ar";
+    let format_str = "--format=%H\x1f%h\x1f%s\x1f%an\x1f%ar\x1f%aI";
     let output = run_git(&["log", &count_str, format_str, &range])?;
 
     let mut entries = Vec::new();
@@ -81,8 +94,9 @@ pub fn get_cherry_candidates(source_branch: &str, count: usize) -> Result<Vec<Co
                 short_hash: parts[1].to_string(),
                 message: parts[2].to_string(),
                 author: parts[3].to_string(),
+                author_email: String::new(),
                 date: parts[4].to_string(),
-                date_iso: String::new(),
+                date_iso: parts.get(5).unwrap_or(&"").to_string(),
                 parents: Vec::new(),
                 refs: String::new(),
                 graph: String::new(),
@@ -0,0 +1,206 @@
+//! CODEOWNERS support — who owns a path, following GitHub's rules: gitignore-style
+//! patterns, and the last matching line wins.
+
+use super::runner::run_git;
+use regex::Regex;
+
+/// Where GitHub looks for the file, in order.
+const LOCATIONS: [&str; 3] = [".github/CODEOWNERS", "CODEOWNERS", "docs/CODEOWNERS"];
+
+#[derive(Debug, Clone)]
+struct Rule {
+    regex: Regex,
+    owners: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CodeOwners {
+    rules: Vec<Rule>,
+    /// File contents the rules were parsed from.
+    source: String,
+}
+
+impl CodeOwners {
+    pub fn parse(content: &str) -> Self {
+        let rules = content
+            .lines()
+            .filter_map(|line| {
+                let line = line.trim();
+                if line.is_empty() || line.starts_with('#') {
+                    return None;
+                }
+                let mut parts = line.split_whitespace();
+                let regex = pattern_regex(parts.next()?)?;
+                let owners = parts
+                    .take_while(|p| !p.starts_with('#'))
+                    .map(|p| p.to_string())
+                    .collect();
+                Some(Rule { regex, owners })
+            })
+            .collect();
+        Self {
+            rules,
+            source: content.to_string(),
+        }
+    }
+
+    /// Owners of `path` (relative to the repo root). Empty when no rule matches, or
+    /// the last matching rule deliberately lists no owners.
+    pub fn owners_of(&self, path: &str) -> &[String] {
+        self.rules
+            .iter()
+            .rev()
+            .find(|r| r.regex.is_match(path))
+            .map_or(&[], |r| r.owners.as_slice())
+    }
+}
+
+/// Load the repository's CODEOWNERS file, if it has one.
+pub fn load() -> Option<CodeOwners> {
+    read_file().map(|content| CodeOwners::parse(&content))
+}
+
+/// Reload `owners` when the file changed on disk; cheap to call on every refresh.
+pub fn reload(owners: &mut Option<CodeOwners>) {
+    match read_file() {
+        Some(content) => {
+            if owners.as_ref().is_none_or(|o| o.source != content) {
+                *owners = Some(CodeOwners::parse(&content));
+            }
+        }
+        None => *owners = None,
+    }
+}
+
+fn read_file() -> Option<String> {
+    let root = run_git(&["rev-parse", "--show-toplevel"]).ok()?;
+    let root = std::path::Path::new(root.trim());
+    LOCATIONS
+        .iter()
+        .find_map(|location| std::fs::read_to_string(root.join(location)).ok())
+}
+
+/// Owners that should review a PR touching `files` but haven't been asked to.
+/// `requested` holds requested (or already reviewing) users as `@login` and teams
+/// as `@org/slug`; the PR's author is never listed. Email owners are skipped.
+pub fn unrequested_owners(
+    owners: &CodeOwners,
+    files: &[&str],
+    requested: &[String],
+    author: &str,
+) -> Vec<String> {
+    let is_requested = |owner: &str| {
+        requested.iter().any(|r| {
+            r.eq_ignore_ascii_case(owner)
+                // A requested team may be known by its slug only
+                || owner
+                    .rsplit_once('/')
+                    .is_some_and(|(_, slug)| r.strip_prefix('@').is_some_and(|r| r.eq_ignore_ascii_case(slug)))
+        })
+    };
+    let mut missing: Vec<String> = Vec::new();
+    for file in files {
+        for owner in owners.owners_of(file) {
+            if owner.starts_with('@')
+                && !owner[1..].eq_ignore_ascii_case(author)
+                && !is_requested(owner)
+                && !missing.contains(owner)
+            {
+                missing.push(owner.clone());
+            }
+        }
+    }
+    missing
+}
+
+/// Translate a CODEOWNERS pattern into a regex over repo-relative paths.
+fn pattern_regex(pattern: &str) -> Option<Regex> {
+    let dir_only = pattern.ends_with('/');
+    let trimmed = pattern.trim_matches('/');
+    if trimmed.is_empty() {
+        return None;
+    }
+    // Like gitignore: a slash at the start or in the middle anchors to the root
+    let anchored = pattern.starts_with('/') || trimmed.contains('/');
+
+    let mut re = String::from(if anchored { "^" } else { "^(?:.*/)?" });
+    let chars: Vec<char> = trimmed.chars().collect();
+    let mut i = 0;
+    while i < chars.len() {
+        match chars[i] {
+            '*' if chars.get(i + 1) == Some(&'*') => {
+                if chars.get(i + 2) == Some(&'/') {
+                    re.push_str("(?:.*/)?");
+                    i += 3;
+                } else {
+                    re.push_str(".*");
+                    i += 2;
+                }
+                continue;
+            }
+            '*' => re.push_str("[^/]*"),
+            '?' => re.push_str("[^/]"),
+            c => re.push_str(&regex::escape(&c.to_string())),
+        }
+        i += 1;
+    }
+    // A matching directory owns everything beneath it
+    re.push_str(if dir_only { "/.*$" } else { "(?:/.*)?$" });
+    Regex::new(&re).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "\
+# Default owners
+*       @org/core
+*.js    @js-owner   # inline comment
+/docs/  @org/docs docs@example.com
+apps/   @apps-owner
+src/*/gen.rs @gen-owner
+/build/logs/
+";
+
+    #[test]
+    fn test_owners_of_last_match_wins() {
+        let owners = CodeOwners::parse(SAMPLE);
+        assert_eq!(owners.owners_of("README.md"), ["@org/core"]);
+        assert_eq!(owners.owners_of("web/app.js"), ["@js-owner"]);
+        assert_eq!(
+            owners.owners_of("docs/guide/intro.md"),
+            ["@org/docs", "docs@example.com"]
+        );
+        // Unanchored directories match at any depth; anchored ones only at the root
+        assert_eq!(owners.owners_of("x/apps/main.rs"), ["@apps-owner"]);
+        assert_eq!(owners.owners_of("x/docs/readme.md"), ["@org/core"]);
+        assert_eq!(owners.owners_of("src/api/gen.rs"), ["@gen-owner"]);
+        assert_eq!(owners.owners_of("src/api/v1/gen.rs"), ["@org/core"]);
+        // A pattern with no owners leaves the path unowned
+        assert!(owners.owners_of("build/logs/out.txt").is_empty());
+    }
+
+    #[test]
+    fn test_pattern_regex_globs() {
+        let re = pattern_regex("docs/**/*.md").unwrap();
+        assert!(re.is_match("docs/a.md"));
+        assert!(re.is_match("docs/x/y/a.md"));
+        assert!(!re.is_match("other/docs/a.md"));
+        let re = pattern_regex("file?.txt").unwrap();
+        assert!(re.is_match("deep/file1.txt"));
+        assert!(!re.is_match("file10.txt"));
+    }
+
+    #[test]
+    fn test_unrequested_owners() {
+        let owners = CodeOwners::parse(SAMPLE);
+        let files = ["README.md", "web/app.js", "docs/intro.md"];
+        let requested = vec!["@JS-Owner".to_string(), "@docs".to_string()];
+        assert_eq!(
+            unrequested_owners(&owners, &files, &requested, "someone"),
+            vec!["@org/core"]
+        );
+        assert!(unrequested_owners(&owners, &["web/app.js"], &[], "js-owner").is_empty());
+    }
+}
@@ -0,0 +1,209 @@
+//! Creating commits, with the less common `git commit` flags.
+
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+
+use super::runner::{run_git, run_git_with_env};
+
+/// Flags for `git commit`, off by default and set from the commit view's
+/// options panel.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CommitOptions {
+    /// `--no-verify`: skip the pre-commit and commit-msg hooks.
+    pub no_verify: bool,
+    /// `--allow-empty`: commit even when nothing is staged.
+    pub allow_empty: bool,
+    /// `--signoff`: add a `Signed-off-by` trailer, as DCO projects require.
+    pub sign_off: bool,
+    /// `--author`, as "Name <email>".
+    pub author: Option<String>,
+    /// `--date`, in any format git understands ("2024-03-01 12:00", "2 days
+    /// ago"). Also used as the committer date, so the commit is backdated
+    /// rather than only its author date.
+    pub date: Option<String>,
+}
+
+impl CommitOptions {
+    /// Whether any flag differs from a plain `git commit`.
+    pub fn is_set(&self) -> bool {
+        *self != Self::default()
+    }
+
+    /// The flags as they'd appear on the command line, e.g. for a summary.
+    pub fn flags(&self) -> Vec<String> {
+        let mut flags = Vec::new();
+        if self.no_verify {
+            flags.push("--no-verify".to_string());
+        }
+        if self.allow_empty {
+            flags.push("--allow-empty".to_string());
+        }
+        if self.sign_off {
+            flags.push("--signoff".to_string());
+        }
+        if let Some(author) = &self.author {
+            flags.push(format!("--author={}", author));
+        }
+        if let Some(date) = &self.date {
+            flags.push(format!("--date={}", date));
+        }
+        flags
+    }
+}
+
+/// Local git config key holding whether this repository's commits are signed
+/// off, overriding `[commit] sign_off`.
+const SIGN_OFF_KEY: &str = "zit.signOff";
+
+/// This repository's sign-off setting, if one was chosen.
+pub fn repo_sign_off() -> Option<bool> {
+    let value = run_git(&["config", "--local", "--type=bool", "--get", SIGN_OFF_KEY]).ok()?;
+    value.trim().parse().ok()
+}
+
+/// Remember whether to sign off commits in this repository.
+pub fn set_repo_sign_off(on: bool) -> Result<()> {
+    run_git(&[
+        "config",
+        "--local",
+        SIGN_OFF_KEY,
+        if on { "true" } else { "false" },
+    ])?;
+    Ok(())
+}
+
+/// Whether `author` is in the "Name <email>" form `--author` expects.
+/// (git would otherwise look it up among existing authors.)
+pub fn is_author(author: &str) -> bool {
+    let Some((name, rest)) = author.split_once('<') else {
+        return false;
+    };
+    !name.trim().is_empty() && rest.strip_suffix('>').is_some_and(|e| e.contains('@'))
+}
+
+/// Commit the staged changes with `message`, leaving the staged changes of
+/// `leave_out` out of this commit. They stay staged for the next one.
+pub fn commit(message: &str, options: &CommitOptions, leave_out: &[String]) -> Result<String> {
+    let mut options = options.clone();
+    let mut env: Vec<(&str, String)> = Vec::new();
+    if let Some(date) = &options.date {
+        // GIT_COMMITTER_DATE doesn't take relative dates like `--date` does,
+        // so both get the same resolved timestamp
+        let stamp = resolve_date(date)?;
+        env.push(("GIT_COMMITTER_DATE", stamp.clone()));
+        options.date = Some(stamp);
+    }
+    // Kept until the commit is done
+    let partial = if leave_out.is_empty() {
+        None
+    } else {
+        let index = PartialIndex::new(leave_out)?;
+        env.push(("GIT_INDEX_FILE", index.path.to_string_lossy().into_owned()));
+        Some(index)
+    };
+
+    let args = commit_args(message, &options);
+    let args: Vec<&str> = args.iter().map(|a| a.as_str()).collect();
+    let result = if env.is_empty() {
+        run_git(&args)
+    } else {
+        let env: Vec<(&str, &str)> = env.iter().map(|(k, v)| (*k, v.as_str())).collect();
+        run_git_with_env(&args, &env)
+    };
+    drop(partial);
+    result
+}
+
+/// A copy of the index with some paths unstaged, to commit from. The real
+/// index is never touched, so whatever is left out is still staged afterwards
+/// (against the new HEAD). Removed when dropped.
+struct PartialIndex {
+    path: PathBuf,
+}
+
+impl PartialIndex {
+    fn new(leave_out: &[String]) -> Result<Self> {
+        let git_dir = PathBuf::from(run_git(&["rev-parse", "--absolute-git-dir"])?.trim());
+        let index = Self {
+            path: git_dir.join("zit-partial-index"),
+        };
+        fs::copy(git_dir.join("index"), &index.path).context("Failed to copy the index")?;
+
+        let mut args = vec!["reset", "-q", "--"];
+        args.extend(leave_out.iter().map(String::as_str));
+        let path = index.path.to_string_lossy();
+        run_git_with_env(&args, &[("GIT_INDEX_FILE", &path)])?;
+        Ok(index)
+    }
+}
+
+impl Drop for PartialIndex {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+/// `date` as "@<unix seconds>", read the way `--date` and `--since` read it.
+fn resolve_date(date: &str) -> Result<String> {
+    let since = format!("--since={}", date);
+    let output = run_git(&["rev-parse", &since])?;
+    output
+        .trim()
+        .strip_prefix("--max-age=")
+        .map(|secs| format!("@{}", secs))
+        .with_context(|| format!("Couldn't read date: {}", date))
+}
+
+fn commit_args(message: &str, options: &CommitOptions) -> Vec<String> {
+    let mut args = vec!["commit".to_string()];
+    args.extend(options.flags());
+    args.push("-m".to_string());
+    args.push(message.to_string());
+    args
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_commit_args() {
+        assert_eq!(
+            commit_args("Fix bug", &CommitOptions::default()),
+            ["commit", "-m", "Fix bug"]
+        );
+
+        let options = CommitOptions {
+            no_verify: true,
+            allow_empty: true,
+            sign_off: true,
+            author: Some("Ada <ada@example.com>".to_string()),
+            date: Some("2 days ago".to_string()),
+        };
+        assert!(options.is_set());
+        assert!(!CommitOptions::default().is_set());
+        assert_eq!(
+            commit_args("Fix bug", &options),
+            [
+                "commit",
+                "--no-verify",
+                "--allow-empty",
+                "--signoff",
+                "--author=Ada <ada@example.com>",
+                "--date=2 days ago",
+                "-m",
+                "Fix bug",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_is_author() {
+        assert!(is_author("Ada Lovelace <ada@example.com>"));
+        assert!(!is_author("Ada Lovelace"));
+        assert!(!is_author("<ada@example.com>"));
+        assert!(!is_author("Ada <ada>"));
+    }
+}
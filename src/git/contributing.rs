@@ -0,0 +1,292 @@
+//! Contributing readiness — what a project expects from a first PR: its contributing
+//! guide, commit message conventions, DCO sign-off and CLA requirements.
+
+use super::runner::run_git;
+use regex::Regex;
+
+/// Where GitHub looks for a contributing guide, in order.
+const GUIDE_LOCATIONS: [&str; 9] = [
+    ".github/CONTRIBUTING.md",
+    "CONTRIBUTING.md",
+    "docs/CONTRIBUTING.md",
+    ".github/CONTRIBUTING.rst",
+    "CONTRIBUTING.rst",
+    "docs/CONTRIBUTING.rst",
+    ".github/CONTRIBUTING.txt",
+    "CONTRIBUTING.txt",
+    "CONTRIBUTING",
+];
+
+/// Config files of commit message linters and helpers.
+const CONVENTION_FILES: [&str; 12] = [
+    "commitlint.config.js",
+    "commitlint.config.cjs",
+    "commitlint.config.mjs",
+    "commitlint.config.ts",
+    ".commitlintrc",
+    ".commitlintrc.json",
+    ".commitlintrc.yml",
+    ".commitlintrc.yaml",
+    ".commitlintrc.js",
+    ".czrc",
+    ".cz.toml",
+    ".cz.json",
+];
+
+/// Files that switch on the DCO GitHub App, or hold the certificate itself.
+const DCO_FILES: [&str; 3] = [".github/dco.yml", "DCO", "DCO.md"];
+
+/// Logins of bots that ask PR authors to sign a CLA.
+const CLA_BOTS: [&str; 5] = [
+    "claassistant",
+    "cla-assistant",
+    "googlebot",
+    "google-cla",
+    "easycla",
+];
+
+/// Recent commits sampled for conventions and sign-offs.
+const SAMPLE_COMMITS: usize = 50;
+
+/// Fewer sampled commits than this say nothing about the project's habits.
+const MIN_SAMPLE: usize = 5;
+
+/// Share of sampled commits that must follow a habit for it to count as a rule.
+const HABIT_RATIO: f64 = 0.6;
+
+/// A recent commit: subject and body.
+pub struct SampleCommit {
+    pub subject: String,
+    pub body: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Readiness {
+    /// Path of the contributing guide, if the repo has one.
+    pub guide: Option<String>,
+    /// Contents of the guide.
+    pub guide_text: String,
+    /// Required commit message convention, and where it was found.
+    pub convention: Option<String>,
+    /// Why commits need a `Signed-off-by` trailer.
+    pub sign_off: Option<String>,
+    /// Why a Contributor License Agreement is needed.
+    pub cla: Option<String>,
+}
+
+impl Readiness {
+    /// Plain-text summary with a to-do line per requirement.
+    pub fn report(&self) -> String {
+        let mut lines = Vec::new();
+        match &self.guide {
+            Some(path) => lines.push(format!("✓ Contributing guide: {} — read it first", path)),
+            None => lines.push("· No contributing guide found".to_string()),
+        }
+        match &self.convention {
+            Some(why) => lines.push(format!("! Commit convention: {}", why)),
+            None => lines.push("· No commit message convention detected".to_string()),
+        }
+        match &self.sign_off {
+            Some(why) => lines.push(format!(
+                "! DCO sign-off required: {}\n    Sign every commit with `git commit -s`",
+                why
+            )),
+            None => lines.push("· No DCO sign-off requirement detected".to_string()),
+        }
+        match &self.cla {
+            Some(why) => lines.push(format!(
+                "! CLA required: {}\n    Expect a bot to ask you to sign it on your first PR",
+                why
+            )),
+            None => lines.push("· No CLA requirement detected".to_string()),
+        }
+        lines.join("\n")
+    }
+
+    /// Whether anything beyond the usual is asked of contributors.
+    pub fn has_requirements(&self) -> bool {
+        self.convention.is_some() || self.sign_off.is_some() || self.cla.is_some()
+    }
+}
+
+/// Check the current repository. `commenters` are logins of recent PR comment
+/// authors (from the GitHub API), used to spot CLA bots.
+pub fn check(commenters: &[String]) -> Readiness {
+    let root = run_git(&["rev-parse", "--show-toplevel"]).unwrap_or_default();
+    let root = std::path::Path::new(root.trim());
+
+    let guide = GUIDE_LOCATIONS.iter().find_map(|path| {
+        std::fs::read_to_string(root.join(path))
+            .ok()
+            .map(|text| (*path, text))
+    });
+    let present: Vec<&str> = CONVENTION_FILES
+        .iter()
+        .chain(DCO_FILES.iter())
+        .filter(|path| root.join(path).exists())
+        .copied()
+        .collect();
+    let commits = sample_commits().unwrap_or_default();
+
+    analyze(
+        guide.as_ref().map(|(p, t)| (*p, t.as_str())),
+        &present,
+        &commits,
+        commenters,
+    )
+}
+
+fn sample_commits() -> anyhow::Result<Vec<SampleCommit>> {
+    let count = format!("-{}", SAMPLE_COMMITS);
+    let output = run_git(&["log", &count, "--no-merges", "--format=%s%x1f%b%x1e"])?;
+    Ok(output
+        .split('\x1e')
+        .filter_map(|record| {
+            let (subject, body) = record.trim_start_matches('\n').split_once('\x1f')?;
+            Some(SampleCommit {
+                subject: subject.to_string(),
+                body: body.to_string(),
+            })
+        })
+        .collect())
+}
+
+/// Work out the requirements from the guide (path, text), the linter and DCO
+/// config files present, recent commits and recent PR commenters.
+pub fn analyze(
+    guide: Option<(&str, &str)>,
+    present: &[&str],
+    commits: &[SampleCommit],
+    commenters: &[String],
+) -> Readiness {
+    let (guide_path, guide_text) = guide.unwrap_or(("", ""));
+    let lower = guide_text.to_lowercase();
+    let mentions = |needles: &[&str]| needles.iter().any(|n| lower.contains(n));
+    let guide_says = |what: &str| format!("{} {}", guide_path, what);
+
+    // Share of sampled commits matching `f`, when there are enough of them
+    let habit = |f: &dyn Fn(&SampleCommit) -> bool| {
+        if commits.len() < MIN_SAMPLE {
+            return None;
+        }
+        let hits = commits.iter().filter(|c| f(c)).count();
+        (hits as f64 / commits.len() as f64 >= HABIT_RATIO).then_some(hits)
+    };
+
+    let conventional = Regex::new(r"^[a-z]+(\([^)]+\))?!?: \S").unwrap();
+    let convention = if let Some(file) = present.iter().find(|p| CONVENTION_FILES.contains(p)) {
+        Some(format!("Conventional Commits (enforced by {})", file))
+    } else if mentions(&["conventional commits", "conventionalcommits"]) {
+        Some(format!(
+            "Conventional Commits ({})",
+            guide_says("asks for them")
+        ))
+    } else if mentions(&["gitmoji"]) {
+        Some(format!("Gitmoji ({})", guide_says("asks for them")))
+    } else {
+        habit(&|c| conventional.is_match(&c.subject)).map(|hits| {
+            format!(
+                "Conventional Commits ({} of the last {} commits use them)",
+                hits,
+                commits.len()
+            )
+        })
+    };
+
+    let sign_off = if let Some(file) = present.iter().find(|p| DCO_FILES.contains(p)) {
+        Some(if file.ends_with(".yml") {
+            format!("the DCO app is configured ({})", file)
+        } else {
+            format!("the repository has a {} file", file)
+        })
+    } else if mentions(&[
+        "signed-off-by",
+        "developer certificate of origin",
+        "git commit -s",
+        "--signoff",
+    ]) || Regex::new(r"\bDCO\b").unwrap().is_match(guide_text)
+    {
+        Some(guide_says("asks for it"))
+    } else {
+        habit(&|c| c.body.contains("Signed-off-by:")).map(|hits| {
+            format!(
+                "{} of the last {} commits are signed off",
+                hits,
+                commits.len()
+            )
+        })
+    };
+
+    let bot = commenters.iter().find(|login| {
+        let login = login.to_lowercase();
+        let login = login.trim_end_matches("[bot]");
+        CLA_BOTS.contains(&login)
+    });
+    let cla = if let Some(bot) = bot {
+        Some(format!("{} comments on recent PRs", bot))
+    } else if mentions(&["contributor license agreement"])
+        || Regex::new(r"\bCLA\b").unwrap().is_match(guide_text)
+    {
+        Some(guide_says("asks for it"))
+    } else {
+        None
+    };
+
+    Readiness {
+        guide: guide.map(|(p, _)| p.to_string()),
+        guide_text: guide_text.to_string(),
+        convention,
+        sign_off,
+        cla,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commit(subject: &str, body: &str) -> SampleCommit {
+        SampleCommit {
+            subject: subject.to_string(),
+            body: body.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_analyze_guide_history_and_bots() {
+        let guide = "# Contributing\n\nPlease sign off your commits (DCO).\n";
+        let commits: Vec<SampleCommit> = (0..6)
+            .map(|i| commit(&format!("fix(ui): bug {}", i), ""))
+            .collect();
+        let bots = vec!["alice".to_string(), "CLAassistant".to_string()];
+        let r = analyze(Some(("CONTRIBUTING.md", guide)), &[], &commits, &bots);
+        assert_eq!(r.guide.as_deref(), Some("CONTRIBUTING.md"));
+        assert_eq!(
+            r.convention.as_deref(),
+            Some("Conventional Commits (6 of the last 6 commits use them)")
+        );
+        assert_eq!(r.sign_off.as_deref(), Some("CONTRIBUTING.md asks for it"));
+        assert_eq!(
+            r.cla.as_deref(),
+            Some("CLAassistant comments on recent PRs")
+        );
+
+        // Config files win; too few commits say nothing
+        let r = analyze(
+            None,
+            &[".commitlintrc.json", ".github/dco.yml"],
+            &commits[..2],
+            &["google-cla[bot]".to_string()],
+        );
+        assert!(r.guide.is_none());
+        assert!(r.convention.unwrap().contains(".commitlintrc.json"));
+        assert!(r.sign_off.unwrap().contains("dco.yml"));
+        assert!(r.cla.is_some());
+
+        let r = analyze(None, &["DCO"], &commits[..2], &[]);
+        assert_eq!(r.sign_off.as_deref(), Some("the repository has a DCO file"));
+
+        let r = analyze(None, &[], &commits[..2], &[]);
+        assert!(!r.has_requirements());
+    }
+}
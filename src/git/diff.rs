@@ -1,4 +1,4 @@
-use super::runner::run_git;
+use super::runner::{run_git, run_git_lines};
 use anyhow::{Context, Result};
 
 #[derive(Debug, Clone, PartialEq)]
@@ -31,38 +31,167 @@ pub struct FileDiff {
     #[allow(dead_code)]
     pub path: String,
     pub old_path: Option<String>,
+    /// Empty when a [`DiffParser::summary`] parse skipped the hunks.
     pub hunks: Vec<Hunk>,
+    /// Added and removed lines, counted even when the hunks are skipped.
+    pub additions: usize,
+    pub deletions: usize,
 }
 
 /// Get diff of unstaged changes (working tree vs index).
 #[allow(dead_code)]
 pub fn get_unstaged_diff() -> Result<Vec<FileDiff>> {
-    let output = run_git(&["diff"])?;
-    Ok(parse_diff_output(&output))
+    super::backend::current().diff(false, &[])
 }
 
 /// Get diff of staged changes (index vs HEAD).
 pub fn get_staged_diff() -> Result<Vec<FileDiff>> {
-    let output = run_git(&["diff", "--cached"])?;
-    Ok(parse_diff_output(&output))
+    super::backend::current().diff(true, &[])
 }
 
 /// Get unstaged diff for a single file.
 pub fn get_unstaged_diff_for_file(path: &str) -> Result<Vec<FileDiff>> {
-    let output = run_git(&["diff", "--", path])?;
-    Ok(parse_diff_output(&output))
+    super::backend::current().diff(false, &[path])
 }
 
 /// Get staged diff for a single file.
 pub fn get_staged_diff_for_file(path: &str) -> Result<Vec<FileDiff>> {
-    let output = run_git(&["diff", "--cached", "--", path])?;
-    Ok(parse_diff_output(&output))
+    super::backend::current().diff(true, &[path])
+}
+
+/// Staged or unstaged changes to `paths` (all when empty), from `git diff`.
+pub(super) fn diff_from_cli(staged: bool, paths: &[&str]) -> Result<Vec<FileDiff>> {
+    match (staged, paths) {
+        (false, []) => collect_diff(&["diff"]),
+        (true, []) => collect_diff(&["diff", "--cached"]),
+        _ => diff_paths(staged, &[], paths),
+    }
+}
+
+/// Diff limited to exactly `paths`. Pathspec magic is off, so a file named
+/// e.g. `*.rs` doesn't widen the diff to every matching file.
+fn diff_paths(staged: bool, options: &[&str], paths: &[&str]) -> Result<Vec<FileDiff>> {
+    let mut args = vec!["--literal-pathspecs", "diff"];
+    if staged {
+        args.push("--cached");
+    }
+    args.extend(options);
+    args.push("--");
+    args.extend(paths);
+    collect_diff(&args)
+}
+
+/// A rename or copy detected by `git diff -M -C`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RenamePair {
+    /// `true` for a copy, `false` for a rename.
+    pub copy: bool,
+    pub old_path: String,
+    pub new_path: String,
+    /// Similarity index, 0–100.
+    pub similarity: u8,
+}
+
+/// Renames and copies among staged (index vs HEAD) or unstaged changes.
+pub fn get_renames(staged: bool) -> Result<Vec<RenamePair>> {
+    let mut args = vec!["diff", "-M", "-C", "--name-status", "-z"];
+    if staged {
+        args.insert(1, "--cached");
+    }
+    let scope = super::scope::get();
+    if let Some(scope) = &scope {
+        args.extend(["--", scope]);
+    }
+    Ok(parse_name_status(&run_git(&args)?))
+}
+
+/// Diff of a renamed or copied file against its original, so the content changes
+/// show instead of a whole-file delete and add.
+pub fn get_rename_diff(old_path: &str, new_path: &str, staged: bool) -> Result<Vec<FileDiff>> {
+    Ok(diff_paths(staged, &["-M", "-C"], &[old_path, new_path])?
+        .into_iter()
+        .filter(|f| f.path == new_path)
+        .collect())
+}
+
+/// Parse `git diff --name-status -z` output, keeping only renames and copies.
+fn parse_name_status(output: &str) -> Vec<RenamePair> {
+    let mut pairs = Vec::new();
+    let mut fields = output.split('\0').filter(|f| !f.is_empty());
+    while let Some(status) = fields.next() {
+        let copy = status.starts_with('C');
+        if copy || status.starts_with('R') {
+            let (Some(old), Some(new)) = (fields.next(), fields.next()) else {
+                break;
+            };
+            pairs.push(RenamePair {
+                copy,
+                old_path: old.to_string(),
+                new_path: new.to_string(),
+                similarity: status[1..].parse().unwrap_or(0),
+            });
+        } else {
+            // Every other status has a single path
+            fields.next();
+        }
+    }
+    pairs
 }
 
 /// Get diff for a specific commit.
 pub fn get_commit_diff(hash: &str) -> Result<Vec<FileDiff>> {
-    let output = run_git(&["diff", &format!("{}^..{}", hash, hash)])?;
-    Ok(parse_diff_output(&output))
+    collect_diff(&["diff", &format!("{}^..{}", hash, hash)])
+}
+
+/// A file changed between two revisions, with its added/removed line counts
+/// (`None` for binary files).
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileChange {
+    pub path: String,
+    pub added: Option<u32>,
+    pub removed: Option<u32>,
+}
+
+/// Files `to` changed since it forked from `from` (`git diff from...to`): what a
+/// push publishes when `to` is the branch and `from` its upstream, and what a
+/// pull brings in the other way round.
+pub fn range_file_changes(from: &str, to: &str) -> Result<Vec<FileChange>> {
+    let range = format!("{}...{}", from, to);
+    let output = run_git(&["diff", "--numstat", "--no-renames", &range])?;
+    Ok(parse_numstat(&output))
+}
+
+/// The diff of one file in `git diff from...to`.
+pub fn range_file_diff(from: &str, to: &str, path: &str) -> Result<String> {
+    let range = format!("{}...{}", from, to);
+    run_git(&["diff", &range, "--", path])
+}
+
+/// Files that differ between the revisions `from` and `to` (`git diff from to`).
+pub fn file_changes(from: &str, to: &str) -> Result<Vec<FileChange>> {
+    let output = run_git(&["diff", "--numstat", "--no-renames", from, to])?;
+    Ok(parse_numstat(&output))
+}
+
+/// The diff of one file in `git diff from to`.
+pub fn file_diff(from: &str, to: &str, path: &str) -> Result<String> {
+    run_git(&["diff", from, to, "--", path])
+}
+
+fn parse_numstat(output: &str) -> Vec<FileChange> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.splitn(3, '\t');
+            let added = parts.next()?.parse().ok();
+            let removed = parts.next()?.parse().ok();
+            Some(FileChange {
+                path: parts.next()?.to_string(),
+                added,
+                removed,
+            })
+        })
+        .collect()
 }
 
 /// Get diffstat for staged changes (for commit preview).
@@ -70,6 +199,15 @@ pub fn get_staged_stat() -> Result<String> {
     run_git(&["diff", "--cached", "--stat"])
 }
 
+/// Record untracked paths with `git add -N` (intent-to-add), so `git diff` shows
+/// their content as added lines and individual hunks can be staged.
+pub fn intent_to_add(paths: &[&str]) -> Result<()> {
+    let mut args = vec!["add", "--intent-to-add", "--"];
+    args.extend(paths);
+    run_git(&args)?;
+    Ok(())
+}
+
 /// Stage a single hunk by constructing a minimal patch and piping it through `git apply --cached`.
 pub fn stage_hunk(file_path: &str, hunk: &Hunk) -> Result<()> {
     let patch = build_hunk_patch(file_path, hunk);
@@ -154,79 +292,185 @@ fn apply_patch_reverse(patch: &str) -> Result<()> {
     Ok(())
 }
 
-fn parse_diff_output(output: &str) -> Vec<FileDiff> {
-    let mut files: Vec<FileDiff> = Vec::new();
-    let mut current_file: Option<FileDiff> = None;
-    let mut current_hunk: Option<Hunk> = None;
+/// Parse unified diff output (`git diff`, `git show`) into files and hunks.
+pub fn parse_diff_output(output: &str) -> Vec<FileDiff> {
+    let mut parser = DiffParser::full();
+    let mut files: Vec<FileDiff> = output.lines().filter_map(|l| parser.feed(l)).collect();
+    files.extend(parser.finish());
+    files
+}
 
-    for line in output.lines() {
-        if line.starts_with("diff --git") {
-            // Save previous hunk and file
-            if let Some(ref mut f) = current_file {
-                if let Some(h) = current_hunk.take() {
-                    f.hunks.push(h);
-                }
-                files.push(f.clone());
-            }
+/// Incremental unified diff parser: fed one line at a time as git produces
+/// them, it hands back each file as soon as the next one starts.
+#[derive(Debug)]
+pub struct DiffParser {
+    /// Whether hunks are kept, or only added/removed lines counted.
+    keep_hunks: bool,
+    current: Option<FileDiff>,
+    hunk: Option<Hunk>,
+    in_hunk: bool,
+}
+
+impl DiffParser {
+    /// Keep every file's hunks.
+    pub fn full() -> Self {
+        Self::with(true)
+    }
+
+    /// Only count each file's added and removed lines, skipping the hunks.
+    pub fn summary() -> Self {
+        Self::with(false)
+    }
 
+    fn with(keep_hunks: bool) -> Self {
+        Self {
+            keep_hunks,
+            current: None,
+            hunk: None,
+            in_hunk: false,
+        }
+    }
+
+    /// Parse one line (without its newline). Returns the previous file once a
+    /// new `diff --git` header starts.
+    pub fn feed(&mut self, line: &str) -> Option<FileDiff> {
+        if line.starts_with("diff --git") {
+            let done = self.take_file();
             // Parse file path from "diff --git a/path b/path"
             let path = line.rsplit(" b/").next().unwrap_or("").to_string();
-
-            current_file = Some(FileDiff {
+            self.current = Some(FileDiff {
                 path,
                 old_path: None,
                 hunks: Vec::new(),
+                additions: 0,
+                deletions: 0,
             });
-            current_hunk = None;
-        } else if line.starts_with("rename from ") {
-            if let Some(ref mut f) = current_file {
-                f.old_path = Some(line.strip_prefix("rename from ").unwrap_or("").to_string());
-            }
+            return done;
+        }
+        let file = self.current.as_mut()?;
+        if let Some(from) = line
+            .strip_prefix("rename from ")
+            .or_else(|| line.strip_prefix("copy from "))
+        {
+            file.old_path = Some(from.to_string());
         } else if line.starts_with("@@") {
-            // Save previous hunk
-            if let Some(ref mut f) = current_file {
-                if let Some(h) = current_hunk.take() {
-                    f.hunks.push(h);
-                }
+            if let Some(h) = self.hunk.take() {
+                file.hunks.push(h);
             }
-
-            let (old_start, old_count, new_start, new_count) = parse_hunk_header(line);
-            current_hunk = Some(Hunk {
-                header: line.to_string(),
-                old_start,
-                old_count,
-                new_start,
-                new_count,
-                lines: vec![DiffLine {
-                    line_type: DiffLineType::Header,
-                    content: line.to_string(),
-                }],
-            });
-        } else if let Some(ref mut hunk) = current_hunk {
+            self.in_hunk = true;
+            if self.keep_hunks {
+                let (old_start, old_count, new_start, new_count) = parse_hunk_header(line);
+                self.hunk = Some(Hunk {
+                    header: line.to_string(),
+                    old_start,
+                    old_count,
+                    new_start,
+                    new_count,
+                    lines: vec![DiffLine {
+                        line_type: DiffLineType::Header,
+                        content: line.to_string(),
+                    }],
+                });
+            }
+        } else if self.in_hunk {
             let line_type = if line.starts_with('+') {
+                file.additions += 1;
                 DiffLineType::Added
             } else if line.starts_with('-') {
+                file.deletions += 1;
                 DiffLineType::Removed
             } else {
                 DiffLineType::Context
             };
+            if let Some(hunk) = self.hunk.as_mut() {
+                hunk.lines.push(DiffLine {
+                    line_type,
+                    content: line.to_string(),
+                });
+            }
+        }
+        None
+    }
 
-            hunk.lines.push(DiffLine {
-                line_type,
-                content: line.to_string(),
-            });
+    /// The last file, once the output has ended.
+    pub fn finish(mut self) -> Option<FileDiff> {
+        self.take_file()
+    }
+
+    fn take_file(&mut self) -> Option<FileDiff> {
+        self.in_hunk = false;
+        let mut file = self.current.take()?;
+        if let Some(h) = self.hunk.take() {
+            file.hunks.push(h);
         }
+        Some(file)
     }
+}
 
-    // Save last hunk and file
-    if let Some(ref mut f) = current_file {
-        if let Some(h) = current_hunk.take() {
-            f.hunks.push(h);
+/// Run a diff command and hand each file to `on_file` as soon as it's parsed,
+/// without holding the whole output in memory. `on_file` returns `false` to stop
+/// early.
+pub fn stream_diff(
+    args: &[&str],
+    mut parser: DiffParser,
+    mut on_file: impl FnMut(FileDiff) -> bool,
+) -> Result<()> {
+    let mut stopped = false;
+    run_git_lines(args, |line| match parser.feed(line) {
+        Some(file) => {
+            stopped = !on_file(file);
+            !stopped
         }
-        files.push(f.clone());
+        None => true,
+    })?;
+    if !stopped && let Some(file) = parser.finish() {
+        on_file(file);
     }
+    Ok(())
+}
 
-    files
+/// Run a diff command and collect every file with its hunks.
+fn collect_diff(args: &[&str]) -> Result<Vec<FileDiff>> {
+    let mut files = Vec::new();
+    stream_diff(args, DiffParser::full(), |f| {
+        files.push(f);
+        true
+    })?;
+    Ok(files)
+}
+
+/// Rows of a side-by-side view of diff lines of kinds `kinds`: the line shown
+/// on the old (left) and new (right) side of each row, as indices into
+/// `kinds`. Context and headers sit on both sides; a run of removed lines is
+/// paired up with the added lines that follow it.
+pub fn side_by_side(kinds: &[DiffLineType]) -> Vec<(Option<usize>, Option<usize>)> {
+    let mut rows = Vec::new();
+    let mut i = 0;
+    while i < kinds.len() {
+        match kinds[i] {
+            DiffLineType::Removed | DiffLineType::Added => {
+                let removed_end = (i..kinds.len())
+                    .find(|&j| kinds[j] != DiffLineType::Removed)
+                    .unwrap_or(kinds.len());
+                let added_end = (removed_end..kinds.len())
+                    .find(|&j| kinds[j] != DiffLineType::Added)
+                    .unwrap_or(kinds.len());
+                let (removed, added) = (removed_end - i, added_end - removed_end);
+                for row in 0..removed.max(added) {
+                    rows.push((
+                        (row < removed).then_some(i + row),
+                        (row < added).then_some(removed_end + row),
+                    ));
+                }
+                i = added_end;
+            }
+            DiffLineType::Context | DiffLineType::Header => {
+                rows.push((Some(i), Some(i)));
+                i += 1;
+            }
+        }
+    }
+    rows
 }
 
 fn parse_hunk_header(header: &str) -> (u32, u32, u32, u32) {
@@ -264,6 +508,66 @@ fn parse_hunk_header(header: &str) -> (u32, u32, u32, u32) {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_side_by_side_pairs_changes() {
+        use DiffLineType::*;
+        let kinds = [Header, Context, Removed, Removed, Added, Context, Added];
+        assert_eq!(
+            side_by_side(&kinds),
+            [
+                (Some(0), Some(0)),
+                (Some(1), Some(1)),
+                (Some(2), Some(4)),
+                (Some(3), None),
+                (Some(5), Some(5)),
+                (None, Some(6)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_numstat() {
+        let changes = parse_numstat("3\t1\tsrc/a b.rs\n-\t-\tlogo.png\n");
+        assert_eq!(
+            changes,
+            [
+                FileChange {
+                    path: "src/a b.rs".to_string(),
+                    added: Some(3),
+                    removed: Some(1),
+                },
+                FileChange {
+                    path: "logo.png".to_string(),
+                    added: None,
+                    removed: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_name_status() {
+        let out = "M\0src/lib.rs\0R087\0old name.rs\0new name.rs\0C100\0a.txt\0b.txt\0D\0gone.rs\0";
+        assert_eq!(
+            parse_name_status(out),
+            vec![
+                RenamePair {
+                    copy: false,
+                    old_path: "old name.rs".to_string(),
+                    new_path: "new name.rs".to_string(),
+                    similarity: 87,
+                },
+                RenamePair {
+                    copy: true,
+                    old_path: "a.txt".to_string(),
+                    new_path: "b.txt".to_string(),
+                    similarity: 100,
+                },
+            ]
+        );
+        assert!(parse_name_status("").is_empty());
+    }
+
     #[test]
     fn test_parse_diff_output() {
         let sample = "\
@@ -392,4 +696,40 @@ index abc..def 100644
         assert_eq!(files[0].hunks[0].old_start, 1);
         assert_eq!(files[0].hunks[1].old_start, 10);
     }
+
+    #[test]
+    fn test_diff_parser_streams_files_and_counts_lines() {
+        let sample = "\
+diff --git a/a.rs b/a.rs
+--- a/a.rs
++++ b/a.rs
+@@ -1,2 +1,3 @@
+ keep
+-old
++new
++more
+diff --git a/b.rs b/b.rs
+--- a/b.rs
++++ b/b.rs
+@@ -1 +1 @@
+--- not a header
++++ not a header either
+";
+        let mut parser = DiffParser::summary();
+        let mut lines = sample.lines();
+        // a.rs is handed back as soon as b.rs starts
+        let a = lines.by_ref().find_map(|l| parser.feed(l)).unwrap();
+        assert_eq!((a.path.as_str(), a.additions, a.deletions), ("a.rs", 2, 1));
+        assert!(a.hunks.is_empty());
+
+        assert!(lines.all(|l| parser.feed(l).is_none()));
+        let b = parser.finish().unwrap();
+        assert_eq!((b.additions, b.deletions), (1, 1));
+
+        let full = parse_diff_output(sample);
+        assert_eq!(full.len(), 2);
+        assert_eq!(full[0].hunks[0].lines.len(), 5);
+        assert_eq!((full[1].additions, full[1].deletions), (1, 1));
+        assert_eq!(full[1].hunks[0].lines.len(), 3);
+    }
 }
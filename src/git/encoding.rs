@@ -0,0 +1,228 @@
+//! A file's text encoding and line-ending style, as the Staging diff header
+//! shows them, and what to do when an editor flipped every line between CRLF
+//! and LF: store LF via `.gitattributes`, or put the old endings back.
+
+use anyhow::{Context, Result, bail};
+
+use super::runner::run_git;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Encoding {
+    Ascii,
+    Utf8,
+    Utf8Bom,
+    Utf16Le,
+    Utf16Be,
+    /// Not valid UTF-8 (Latin-1, Windows-1252, Shift-JIS...).
+    Other,
+}
+
+impl Encoding {
+    pub fn label(self) -> &'static str {
+        match self {
+            Encoding::Ascii => "ASCII",
+            Encoding::Utf8 => "UTF-8",
+            Encoding::Utf8Bom => "UTF-8 BOM",
+            Encoding::Utf16Le => "UTF-16 LE",
+            Encoding::Utf16Be => "UTF-16 BE",
+            Encoding::Other => "non-UTF-8",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Eol {
+    /// A single line without a line break.
+    None,
+    Lf,
+    Crlf,
+    Mixed,
+}
+
+impl Eol {
+    pub fn label(self) -> &'static str {
+        match self {
+            Eol::None => "no EOL",
+            Eol::Lf => "LF",
+            Eol::Crlf => "CRLF",
+            Eol::Mixed => "mixed EOL",
+        }
+    }
+}
+
+/// A text file's encoding and line endings.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FileFormat {
+    pub encoding: Encoding,
+    pub eol: Eol,
+}
+
+impl FileFormat {
+    /// Detect from the file's bytes; `None` for binary files.
+    pub fn detect(bytes: &[u8]) -> Option<FileFormat> {
+        let (encoding, text) = if let Some(rest) = bytes.strip_prefix(b"\xff\xfe") {
+            (Encoding::Utf16Le, utf16(rest, u16::from_le_bytes))
+        } else if let Some(rest) = bytes.strip_prefix(b"\xfe\xff") {
+            (Encoding::Utf16Be, utf16(rest, u16::from_be_bytes))
+        } else if bytes.contains(&0) {
+            return None;
+        } else if bytes.starts_with(b"\xef\xbb\xbf") {
+            (Encoding::Utf8Bom, bytes.to_vec())
+        } else if bytes.is_ascii() {
+            (Encoding::Ascii, bytes.to_vec())
+        } else if std::str::from_utf8(bytes).is_ok() {
+            (Encoding::Utf8, bytes.to_vec())
+        } else {
+            (Encoding::Other, bytes.to_vec())
+        };
+        Some(FileFormat {
+            encoding,
+            eol: detect_eol(&text),
+        })
+    }
+
+    /// "UTF-8 · LF".
+    pub fn label(&self) -> String {
+        format!("{} · {}", self.encoding.label(), self.eol.label())
+    }
+}
+
+/// UTF-16 code units that fit in a byte (all line breaks do) as bytes, the
+/// rest as a placeholder, so line endings can be counted.
+fn utf16(bytes: &[u8], unit: fn([u8; 2]) -> u16) -> Vec<u8> {
+    bytes
+        .chunks_exact(2)
+        .map(|pair| unit([pair[0], pair[1]]))
+        .map(|u| u8::try_from(u).unwrap_or(b'?'))
+        .collect()
+}
+
+fn detect_eol(text: &[u8]) -> Eol {
+    let crlf = text.windows(2).filter(|w| w == b"\r\n").count();
+    let lf = text.iter().filter(|&&b| b == b'\n').count() - crlf;
+    match (lf, crlf) {
+        (0, 0) => Eol::None,
+        (_, 0) => Eol::Lf,
+        (0, _) => Eol::Crlf,
+        _ => Eol::Mixed,
+    }
+}
+
+/// The old and new line endings when a change switched every line from one
+/// to the other, which makes the whole file show as changed.
+pub fn eol_flip(old: &FileFormat, new: &FileFormat) -> Option<(Eol, Eol)> {
+    let byte_oriented =
+        |f: &FileFormat| !matches!(f.encoding, Encoding::Utf16Le | Encoding::Utf16Be);
+    match (old.eol, new.eol) {
+        (Eol::Lf, Eol::Crlf) | (Eol::Crlf, Eol::Lf) if byte_oriented(old) && byte_oriented(new) => {
+            Some((old.eol, new.eol))
+        }
+        _ => None,
+    }
+}
+
+/// `content` with every line ending switched to `eol` (LF or CRLF).
+pub fn convert_eol(content: &[u8], eol: Eol) -> Vec<u8> {
+    let mut out = Vec::with_capacity(content.len());
+    for (i, &b) in content.iter().enumerate() {
+        match b {
+            b'\r' if content.get(i + 1) == Some(&b'\n') => {}
+            b'\n' if eol == Eol::Crlf => out.extend(b"\r\n"),
+            _ => out.push(b),
+        }
+    }
+    out
+}
+
+/// Put `eol` back on every line of the working-tree file.
+pub fn restore_file(path: &str, eol: Eol) -> Result<()> {
+    if !matches!(eol, Eol::Lf | Eol::Crlf) {
+        bail!("Can only restore LF or CRLF line endings");
+    }
+    let content = std::fs::read(path).with_context(|| format!("reading {}", path))?;
+    std::fs::write(path, convert_eol(&content, eol)).with_context(|| format!("writing {}", path))
+}
+
+/// The `.gitattributes` line that makes git store text files with LF.
+pub const TEXT_AUTO: &str = "* text=auto";
+
+/// `.gitattributes` with [`TEXT_AUTO`] added, or `None` when it's already there.
+fn with_text_auto(existing: &str) -> Option<String> {
+    if existing.lines().any(|l| l.trim() == TEXT_AUTO) {
+        return None;
+    }
+    let mut content = existing.to_string();
+    if !content.is_empty() && !content.ends_with('\n') {
+        content.push('\n');
+    }
+    content.push_str(TEXT_AUTO);
+    content.push('\n');
+    Some(content)
+}
+
+/// Store `path` with LF line endings: add [`TEXT_AUTO`] to `.gitattributes`
+/// (staging it), then re-stage the file through the new attributes. Returns
+/// whether `.gitattributes` changed.
+pub fn renormalize(path: &str) -> Result<bool> {
+    let existing = std::fs::read_to_string(".gitattributes").unwrap_or_default();
+    let changed = match with_text_auto(&existing) {
+        Some(content) => {
+            std::fs::write(".gitattributes", content).context("writing .gitattributes")?;
+            run_git(&["add", "--", ".gitattributes"])?;
+            true
+        }
+        None => false,
+    };
+    run_git(&["add", "--renormalize", "--", path])?;
+    Ok(changed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn format(bytes: &[u8]) -> String {
+        FileFormat::detect(bytes)
+            .map(|f| f.label())
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn test_detect() {
+        assert_eq!(format(b"a\nb\n"), "ASCII · LF");
+        assert_eq!(format("é\r\nb\r\n".as_bytes()), "UTF-8 · CRLF");
+        assert_eq!(format(b"\xef\xbb\xbfa\r\nb\n"), "UTF-8 BOM · mixed EOL");
+        assert_eq!(format(b"caf\xe9"), "non-UTF-8 · no EOL");
+        assert_eq!(format(b"\xff\xfea\0\r\0\n\0"), "UTF-16 LE · CRLF");
+        assert_eq!(format(b"\xfe\xff\0a\0\n"), "UTF-16 BE · LF");
+        assert_eq!(FileFormat::detect(b"PK\x03\x04\0\0"), None);
+    }
+
+    #[test]
+    fn test_eol_flip() {
+        let lf = FileFormat::detect(b"a\nb\n").unwrap();
+        let crlf = FileFormat::detect(b"a\r\nb\r\n").unwrap();
+        let mixed = FileFormat::detect(b"a\r\nb\n").unwrap();
+        assert_eq!(eol_flip(&crlf, &lf), Some((Eol::Crlf, Eol::Lf)));
+        assert_eq!(eol_flip(&lf, &crlf), Some((Eol::Lf, Eol::Crlf)));
+        assert_eq!(eol_flip(&lf, &lf), None);
+        assert_eq!(eol_flip(&crlf, &mixed), None);
+    }
+
+    #[test]
+    fn test_convert_eol() {
+        assert_eq!(convert_eol(b"a\r\nb\nc", Eol::Lf), b"a\nb\nc");
+        assert_eq!(convert_eol(b"a\r\nb\nc", Eol::Crlf), b"a\r\nb\r\nc");
+        assert_eq!(convert_eol(b"a\rb\n", Eol::Crlf), b"a\rb\r\n");
+    }
+
+    #[test]
+    fn test_with_text_auto() {
+        assert_eq!(with_text_auto("").as_deref(), Some("* text=auto\n"));
+        assert_eq!(
+            with_text_auto("*.png binary").as_deref(),
+            Some("*.png binary\n* text=auto\n")
+        );
+        assert_eq!(with_text_auto("* text=auto\n*.sh eol=lf\n"), None);
+    }
+}
@@ -1,6 +1,9 @@
 use anyhow::{Context, Result};
 use serde::Deserialize;
 
+use crate::git::diff::{DiffLineType, DiffParser};
+use crate::http::{Method, Request, Response};
+
 /// GitHub OAuth App Client ID for zit.
 pub const CLIENT_ID: &str = "Ov23liMBOn6cAuIPFslq";
 
@@ -56,32 +59,26 @@ pub enum PollResult {
 
 /// Step 1: Request device and user verification codes from GitHub.
 pub fn request_device_code() -> Result<DeviceCodeResponse> {
-    let client = reqwest::blocking::Client::new();
-    let resp = client
-        .post("https://github.com/login/device/code")
+    let resp = Request::new(Method::Post, "https://github.com/login/device/code")
         .header("Accept", "application/json")
         .form(&[("client_id", CLIENT_ID), ("scope", SCOPES)])
         .send()
         .context("Failed to contact GitHub for device code")?;
 
-    let status = resp.status();
-    let body = resp.text().context("Failed to read GitHub response")?;
-
-    if !status.is_success() {
-        anyhow::bail!("GitHub returned {}: {}", status, body);
+    if !resp.is_success() {
+        anyhow::bail!("GitHub returned {}: {}", resp.status, resp.text());
     }
 
-    let response: DeviceCodeResponse =
-        serde_json::from_str(&body).context("Failed to parse device code response")?;
+    let response: DeviceCodeResponse = resp
+        .json()
+        .context("Failed to parse device code response")?;
 
     Ok(response)
 }
 
 /// Step 3: Poll GitHub to check if the user has authorized the device.
 pub fn poll_for_token(device_code: &str) -> PollResult {
-    let client = reqwest::blocking::Client::new();
-    let resp = client
-        .post("https://github.com/login/oauth/access_token")
+    let resp = Request::new(Method::Post, "https://github.com/login/oauth/access_token")
         .header("Accept", "application/json")
         .form(&[
             ("client_id", CLIENT_ID),
@@ -95,20 +92,15 @@ pub fn poll_for_token(device_code: &str) -> PollResult {
         Err(e) => return PollResult::Error(format!("Network error: {}", e)),
     };
 
-    let body = match resp.text() {
-        Ok(b) => b,
-        Err(e) => return PollResult::Error(format!("Read error: {}", e)),
-    };
-
     // Try parsing as success first
-    if let Ok(token) = serde_json::from_str::<TokenResponse>(&body) {
-        if !token.access_token.is_empty() {
-            return PollResult::Success(token);
-        }
+    if let Ok(token) = resp.json::<TokenResponse>()
+        && !token.access_token.is_empty()
+    {
+        return PollResult::Success(token);
     }
 
     // Try parsing as error
-    if let Ok(err) = serde_json::from_str::<ErrorResponse>(&body) {
+    if let Ok(err) = resp.json::<ErrorResponse>() {
         return match err.error.as_str() {
             "authorization_pending" => PollResult::Pending,
             "slow_down" => PollResult::SlowDown(err.interval.unwrap_or(10)),
@@ -118,21 +110,12 @@ pub fn poll_for_token(device_code: &str) -> PollResult {
         };
     }
 
-    PollResult::Error(format!("Unexpected response: {}", body))
+    PollResult::Error(format!("Unexpected response: {}", resp.text()))
 }
 
 /// Fetch the authenticated user's username.
 pub fn get_username(token: &str) -> Result<String> {
-    let client = reqwest::blocking::Client::new();
-    let resp = client
-        .get("https://api.github.com/user")
-        .header("Authorization", format!("Bearer {}", token))
-        .header("User-Agent", "zit-cli")
-        .header("Accept", "application/vnd.github+json")
-        .send()
-        .context("Failed to fetch user info")?;
-
-    let body: serde_json::Value = resp.json().context("Failed to parse user response")?;
+    let body = get_json(token, "https://api.github.com/user", "user info")?;
     let login = body["login"]
         .as_str()
         .context("Missing login field")?
@@ -141,9 +124,52 @@ pub fn get_username(token: &str) -> Result<String> {
     Ok(login)
 }
 
+/// Who `token` belongs to and the OAuth scopes it grants. Fine-grained
+/// tokens don't list their permissions, so theirs come back `None`.
+pub fn token_scopes(token: &str) -> Result<(String, Option<Vec<String>>)> {
+    let resp = github(Method::Get, token, "https://api.github.com/user")
+        .send()
+        .context("Failed to reach GitHub for user info")?;
+    if !resp.is_success() {
+        return Err(api_error(&resp));
+    }
+    let scopes = resp.header("x-oauth-scopes").map(|s| {
+        s.split(',')
+            .map(|scope| scope.trim().to_string())
+            .filter(|scope| !scope.is_empty())
+            .collect()
+    });
+    let body: serde_json::Value = resp.json().context("Failed to parse user info response")?;
+    let login = body["login"].as_str().context("Missing login field")?;
+    Ok((login.to_string(), scopes))
+}
+
+/// The scopes zit signs in with that `granted` lacks.
+pub fn missing_scopes(granted: &[String]) -> Vec<&'static str> {
+    SCOPES
+        .split(',')
+        .filter(|wanted| !granted.iter().any(|g| g == wanted))
+        .collect()
+}
+
+/// Download a user's avatar image, scaled by GitHub to `size` pixels square.
+pub fn get_avatar(avatar_url: &str, size: u32) -> Result<Vec<u8>> {
+    let separator = if avatar_url.contains('?') { '&' } else { '?' };
+    let resp = Request::new(
+        Method::Get,
+        format!("{}{}s={}", avatar_url, separator, size),
+    )
+    .header("User-Agent", "zit-cli")
+    .send()
+    .context("Failed to fetch avatar")?;
+    if !resp.is_success() {
+        anyhow::bail!("Avatar request failed: {}", resp.status);
+    }
+    Ok(resp.body)
+}
+
 /// Create a GitHub repository using the API.
 pub fn create_repo(token: &str, name: &str, description: &str, private: bool) -> Result<String> {
-    let client = reqwest::blocking::Client::new();
     let body = serde_json::json!({
         "name": name,
         "description": description,
@@ -151,33 +177,187 @@ pub fn create_repo(token: &str, name: &str, description: &str, private: bool) ->
         "auto_init": false,
     });
 
-    let resp = client
-        .post("https://api.github.com/user/repos")
-        .header("Authorization", format!("Bearer {}", token))
+    let resp_body = send_json(
+        Method::Post,
+        token,
+        "https://api.github.com/user/repos",
+        &body,
+        "repository",
+    )?;
+    Ok(resp_body["clone_url"]
+        .as_str()
+        .unwrap_or("unknown")
+        .to_string())
+}
+
+// ─── API Requests ──────────────────────────────────────────────
+
+/// A request to the GitHub REST API, authenticated with `token`.
+fn github(method: Method, token: &str, url: &str) -> Request {
+    Request::new(method, url)
+        .bearer(token)
         .header("User-Agent", "zit-cli")
         .header("Accept", "application/vnd.github+json")
-        .json(&body)
-        .send()
-        .context("Failed to create repository")?;
+}
 
-    let status = resp.status();
-    let resp_body: serde_json::Value = resp.json().context("Failed to parse response")?;
+/// The error for a failed API response: GitHub's own message, or a note on
+/// when to retry if the rate limit was hit.
+fn api_error(resp: &Response) -> anyhow::Error {
+    let rate_limited = matches!(resp.status, 403 | 429)
+        && (resp.header("x-ratelimit-remaining") == Some("0")
+            || resp.header("retry-after").is_some());
+    if rate_limited {
+        let retry = match (resp.header("retry-after"), resp.header("x-ratelimit-reset")) {
+            (Some(secs), _) => format!("in {}s", secs),
+            (None, Some(reset)) => reset
+                .parse::<i64>()
+                .ok()
+                .and_then(|s| jiff::Timestamp::from_second(s).ok())
+                .map(|t| {
+                    format!(
+                        "at {}",
+                        t.to_zoned(jiff::tz::TimeZone::system()).strftime("%H:%M")
+                    )
+                })
+                .unwrap_or_else(|| "later".to_string()),
+            (None, None) => "later".to_string(),
+        };
+        return anyhow::anyhow!("GitHub API rate limit exceeded — try again {}", retry);
+    }
+    let body: serde_json::Value = resp.json().unwrap_or_default();
+    let message = match body["message"].as_str() {
+        Some(msg) => msg.to_string(),
+        None => format!("GitHub returned HTTP {}", resp.status),
+    };
+    match resp.status {
+        401 => Unauthorized(message).into(),
+        409 => Conflict(message).into(),
+        _ => anyhow::anyhow!("{}", message),
+    }
+}
 
-    if status.is_success() {
-        let clone_url = resp_body["clone_url"]
-            .as_str()
-            .unwrap_or("unknown")
-            .to_string();
-        Ok(clone_url)
-    } else {
-        let msg = resp_body["message"]
-            .as_str()
-            .unwrap_or("Unknown error")
-            .to_string();
-        anyhow::bail!("{}", msg)
+/// GitHub rejected the token (HTTP 401): expired, revoked or mistyped.
+#[derive(Debug)]
+pub struct Unauthorized(pub String);
+
+impl std::fmt::Display for Unauthorized {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for Unauthorized {}
+
+/// GitHub refused a change that conflicts with the resource's current state
+/// (HTTP 409), e.g. a merge whose expected head commit is no longer the head.
+#[derive(Debug)]
+pub struct Conflict(pub String);
+
+impl std::fmt::Display for Conflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
     }
 }
 
+impl std::error::Error for Conflict {}
+
+/// Send `request` and parse the JSON body of a successful response.
+fn read_json(request: Request, what: &str) -> Result<serde_json::Value> {
+    let resp = request
+        .send()
+        .with_context(|| format!("Failed to reach GitHub for {}", what))?;
+    if !resp.is_success() {
+        return Err(api_error(&resp));
+    }
+    resp.json()
+        .with_context(|| format!("Failed to parse {} response", what))
+}
+
+fn get_json(token: &str, url: &str, what: &str) -> Result<serde_json::Value> {
+    read_json(github(Method::Get, token, url), what)
+}
+
+fn send_json(
+    method: Method,
+    token: &str,
+    url: &str,
+    body: &serde_json::Value,
+    what: &str,
+) -> Result<serde_json::Value> {
+    read_json(github(method, token, url).json(body), what)
+}
+
+// ─── Repository Templates ──────────────────────────────────────
+
+/// A license GitHub can generate, e.g. key `mit`, name "MIT License".
+#[derive(Debug, Clone, Deserialize)]
+pub struct LicenseInfo {
+    pub key: String,
+    pub name: String,
+}
+
+/// Names of GitHub's .gitignore templates ("Rust", "Node", ...).
+pub fn list_gitignore_templates(token: &str) -> Result<Vec<String>> {
+    let body = get_json(
+        token,
+        "https://api.github.com/gitignore/templates",
+        "gitignore templates",
+    )?;
+    serde_json::from_value(body).context("Expected a list of template names")
+}
+
+/// Contents of one .gitignore template.
+pub fn get_gitignore_template(token: &str, name: &str) -> Result<String> {
+    let body = get_json(
+        token,
+        &format!("https://api.github.com/gitignore/templates/{}", name),
+        "gitignore template",
+    )?;
+    Ok(body["source"]
+        .as_str()
+        .context("Missing template source")?
+        .to_string())
+}
+
+/// Commonly used licenses.
+pub fn list_licenses(token: &str) -> Result<Vec<LicenseInfo>> {
+    let body = get_json(token, "https://api.github.com/licenses", "licenses")?;
+    serde_json::from_value(body).context("Expected a list of licenses")
+}
+
+/// License text, with placeholders such as `[year]` and `[fullname]` left in.
+pub fn get_license_body(token: &str, key: &str) -> Result<String> {
+    let body = get_json(
+        token,
+        &format!("https://api.github.com/licenses/{}", key),
+        "license",
+    )?;
+    Ok(body["body"]
+        .as_str()
+        .context("Missing license text")?
+        .to_string())
+}
+
+/// Logins of the authors of the latest issue and PR comments in the repo, newest
+/// first — enough to spot bots (CLA assistants and the like) that comment on PRs.
+pub fn recent_comment_authors(token: &str) -> Result<Vec<String>> {
+    let (owner, repo) = parse_repo_from_remote()?;
+    let body = get_json(
+        token,
+        &format!(
+            "https://api.github.com/repos/{}/{}/issues/comments?sort=created&direction=desc&per_page=100",
+            owner, repo
+        ),
+        "comments",
+    )?;
+    Ok(body
+        .as_array()
+        .context("Expected a list of comments")?
+        .iter()
+        .filter_map(|c| c["user"]["login"].as_str().map(|s| s.to_string()))
+        .collect())
+}
+
 /// A GitHub collaborator entry.
 #[derive(Debug, Clone)]
 pub struct Collaborator {
@@ -220,27 +400,14 @@ pub fn parse_repo_from_remote() -> Result<(String, String)> {
 /// List collaborators for the current repository.
 pub fn list_collaborators(token: &str) -> Result<Vec<Collaborator>> {
     let (owner, repo) = parse_repo_from_remote()?;
-    let client = reqwest::blocking::Client::new();
-    let resp = client
-        .get(format!(
+    let body = get_json(
+        token,
+        &format!(
             "https://api.github.com/repos/{}/{}/collaborators",
             owner, repo
-        ))
-        .header("Authorization", format!("Bearer {}", token))
-        .header("User-Agent", "zit-cli")
-        .header("Accept", "application/vnd.github+json")
-        .send()
-        .context("Failed to fetch collaborators")?;
-
-    let status = resp.status();
-    let body: serde_json::Value = resp
-        .json()
-        .context("Failed to parse collaborators response")?;
-
-    if !status.is_success() {
-        let msg = body["message"].as_str().unwrap_or("Unknown error");
-        anyhow::bail!("{}", msg);
-    }
+        ),
+        "collaborators",
+    )?;
 
     let collabs = body
         .as_array()
@@ -262,51 +429,43 @@ pub fn list_collaborators(token: &str) -> Result<Vec<Collaborator>> {
 /// Add a collaborator to the current repository.
 pub fn add_collaborator(token: &str, username: &str) -> Result<String> {
     let (owner, repo) = parse_repo_from_remote()?;
-    let client = reqwest::blocking::Client::new();
-    let resp = client
-        .put(format!(
+    let resp = github(
+        Method::Put,
+        token,
+        &format!(
             "https://api.github.com/repos/{}/{}/collaborators/{}",
             owner, repo, username
-        ))
-        .header("Authorization", format!("Bearer {}", token))
-        .header("User-Agent", "zit-cli")
-        .header("Accept", "application/vnd.github+json")
-        .json(&serde_json::json!({"permission": "push"}))
-        .send()
-        .context("Failed to add collaborator")?;
+        ),
+    )
+    .json(&serde_json::json!({"permission": "push"}))
+    .send()
+    .context("Failed to add collaborator")?;
 
-    let status = resp.status();
-    if status.is_success() || status.as_u16() == 201 || status.as_u16() == 204 {
+    if resp.is_success() {
         Ok(format!("Invited '{}' as collaborator", username))
     } else {
-        let body: serde_json::Value = resp.json().unwrap_or_default();
-        let msg = body["message"].as_str().unwrap_or("Unknown error");
-        anyhow::bail!("{}", msg)
+        Err(api_error(&resp))
     }
 }
 
 /// Remove a collaborator from the current repository.
 pub fn remove_collaborator(token: &str, username: &str) -> Result<()> {
     let (owner, repo) = parse_repo_from_remote()?;
-    let client = reqwest::blocking::Client::new();
-    let resp = client
-        .delete(format!(
+    let resp = github(
+        Method::Delete,
+        token,
+        &format!(
             "https://api.github.com/repos/{}/{}/collaborators/{}",
             owner, repo, username
-        ))
-        .header("Authorization", format!("Bearer {}", token))
-        .header("User-Agent", "zit-cli")
-        .header("Accept", "application/vnd.github+json")
-        .send()
-        .context("Failed to remove collaborator")?;
+        ),
+    )
+    .send()
+    .context("Failed to remove collaborator")?;
 
-    let status = resp.status();
-    if status.is_success() || status.as_u16() == 204 {
+    if resp.is_success() {
         Ok(())
     } else {
-        let body: serde_json::Value = resp.json().unwrap_or_default();
-        let msg = body["message"].as_str().unwrap_or("Unknown error");
-        anyhow::bail!("{}", msg)
+        Err(api_error(&resp))
     }
 }
 
@@ -334,6 +493,8 @@ pub struct PullRequest {
     pub labels: Vec<GhLabel>,
     #[serde(default)]
     pub requested_reviewers: Vec<GhUser>,
+    #[serde(default)]
+    pub requested_teams: Vec<GhTeam>,
     pub additions: Option<u64>,
     pub deletions: Option<u64>,
     pub changed_files: Option<u64>,
@@ -354,6 +515,13 @@ pub struct PrBranch {
 #[derive(Debug, Clone, Deserialize)]
 pub struct GhUser {
     pub login: String,
+    #[serde(default)]
+    pub avatar_url: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct GhTeam {
+    pub slug: String,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -380,6 +548,95 @@ pub struct CheckRun {
     pub html_url: String,
 }
 
+/// Overall state of a commit's checks.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChecksOutcome {
+    /// No checks reported (yet).
+    None,
+    /// Some checks are still queued or running, and none has failed.
+    Pending,
+    /// Names of the checks that failed; reported as soon as one does.
+    Failed(Vec<String>),
+    /// Every check completed successfully (or was skipped / neutral).
+    Passed,
+}
+
+impl CheckRunsResponse {
+    pub fn outcome(&self) -> ChecksOutcome {
+        if self.check_runs.is_empty() {
+            return ChecksOutcome::None;
+        }
+        let failed: Vec<String> = self
+            .check_runs
+            .iter()
+            .filter(|r| {
+                matches!(
+                    r.conclusion.as_deref(),
+                    Some("failure" | "timed_out" | "cancelled" | "action_required")
+                )
+            })
+            .map(|r| r.name.clone())
+            .collect();
+        if !failed.is_empty() {
+            ChecksOutcome::Failed(failed)
+        } else if self.check_runs.iter().any(|r| r.status != "completed") {
+            ChecksOutcome::Pending
+        } else {
+            ChecksOutcome::Passed
+        }
+    }
+
+    /// Whether GitHub reported more runs than were read.
+    pub fn is_incomplete(&self) -> bool {
+        (self.check_runs.len() as u64) < self.total_count
+    }
+}
+
+/// Where a watched pull request stands after a poll.
+#[derive(Debug, Clone)]
+pub enum WatchStep {
+    /// Checks are still running (or none reported yet), or GitHub is still
+    /// working out whether the PR can merge: poll again.
+    Wait,
+    /// The PR was merged (`true`) or closed while watched.
+    Closed(bool),
+    /// Names of the checks that failed.
+    Failed(Vec<String>),
+    /// Every check passed; auto-merge wasn't asked for.
+    Passed,
+    /// Every check passed but the PR isn't merged automatically: "draft", or
+    /// GitHub's `mergeable_state` (e.g. "blocked").
+    NotMerging(String),
+    /// Every check passed and the PR is cleanly mergeable.
+    Merge,
+    /// What GitHub said to the auto-merge.
+    Merged(Result<MergeResponse, String>),
+}
+
+/// The next step for a watched `pr` whose head commit has `checks`.
+pub fn watch_step(pr: &PullRequest, checks: &CheckRunsResponse, auto_merge: bool) -> WatchStep {
+    if pr.state != "open" {
+        return WatchStep::Closed(pr.merged_at.is_some());
+    }
+    match checks.outcome() {
+        ChecksOutcome::None | ChecksOutcome::Pending => WatchStep::Wait,
+        ChecksOutcome::Failed(names) => WatchStep::Failed(names),
+        ChecksOutcome::Passed if !auto_merge => WatchStep::Passed,
+        ChecksOutcome::Passed if pr.draft => WatchStep::NotMerging("draft".to_string()),
+        ChecksOutcome::Passed if checks.is_incomplete() => WatchStep::NotMerging(format!(
+            "only {} of {} checks read",
+            checks.check_runs.len(),
+            checks.total_count
+        )),
+        ChecksOutcome::Passed => match pr.mergeable_state.as_deref() {
+            Some("clean") => WatchStep::Merge,
+            // GitHub computes mergeability lazily: ask again next poll
+            None | Some("unknown") => WatchStep::Wait,
+            Some(state) => WatchStep::NotMerging(state.to_string()),
+        },
+    }
+}
+
 #[derive(Debug, Clone, Deserialize)]
 #[allow(dead_code)]
 pub struct PrFile {
@@ -391,6 +648,70 @@ pub struct PrFile {
     pub patch: Option<String>,
 }
 
+/// One line of a changed file's patch, with where it sits in the old and the
+/// new version of the file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PatchLine {
+    pub kind: DiffLineType,
+    pub text: String,
+    pub old: Option<u32>,
+    pub new: Option<u32>,
+}
+
+impl PatchLine {
+    /// Where a review comment on this line goes: the line number and the side
+    /// of the diff. Hunk headers can't be commented on.
+    pub fn anchor(&self) -> Option<(u32, &'static str)> {
+        match self.kind {
+            DiffLineType::Removed => self.old.map(|n| (n, "LEFT")),
+            DiffLineType::Added | DiffLineType::Context => self.new.map(|n| (n, "RIGHT")),
+            DiffLineType::Header => None,
+        }
+    }
+}
+
+impl PrFile {
+    /// The patch line by line. Empty when GitHub sent none (binary files and
+    /// very large diffs).
+    pub fn patch_lines(&self) -> Vec<PatchLine> {
+        let Some(patch) = self.patch.as_deref() else {
+            return Vec::new();
+        };
+        let mut parser = DiffParser::full();
+        parser.feed(&format!("diff --git a/{0} b/{0}", self.filename));
+        for line in patch.lines() {
+            parser.feed(line);
+        }
+        let mut lines = Vec::new();
+        for hunk in parser.finish().map(|f| f.hunks).unwrap_or_default() {
+            let (mut old, mut new) = (hunk.old_start, hunk.new_start);
+            for line in hunk.lines {
+                let (at_old, at_new) = match line.line_type {
+                    // "\ No newline at end of file" belongs to the line before
+                    _ if line.content.starts_with('\\') => (None, None),
+                    DiffLineType::Header => (None, None),
+                    DiffLineType::Context => (Some(old), Some(new)),
+                    DiffLineType::Added => (None, Some(new)),
+                    DiffLineType::Removed => (Some(old), None),
+                };
+                old += at_old.is_some() as u32;
+                new += at_new.is_some() as u32;
+                lines.push(PatchLine {
+                    kind: if at_old.is_none() && at_new.is_none() {
+                        DiffLineType::Header
+                    } else {
+                        line.line_type
+                    },
+                    text: line.content,
+                    old: at_old,
+                    new: at_new,
+                });
+            }
+        }
+        lines
+    }
+}
+
 #[derive(Debug, Clone, Deserialize)]
 #[allow(dead_code)]
 pub struct PrReview {
@@ -409,102 +730,162 @@ pub struct MergeResponse {
 
 // ─── Pull Request API Functions ────────────────────────────────
 
-fn gh_get(token: &str, url: &str) -> Result<reqwest::blocking::Response> {
-    let client = reqwest::blocking::Client::new();
-    client
-        .get(url)
-        .header("Authorization", format!("Bearer {}", token))
-        .header("User-Agent", "zit-cli")
-        .header("Accept", "application/vnd.github+json")
-        .send()
-        .context("GitHub API request failed")
+/// List pull requests. `state` is "open", "closed", or "all".
+pub fn list_pull_requests(token: &str, state: &str) -> Result<Vec<PullRequest>> {
+    let (owner, repo) = parse_repo_from_remote()?;
+    let url = format!(
+        "https://api.github.com/repos/{}/{}/pulls?state={}&per_page=50&sort=updated&direction=desc",
+        owner, repo, state
+    );
+    let body = get_json(token, &url, "PR list")?;
+    let prs: Vec<PullRequest> =
+        serde_json::from_value(body).context("Failed to deserialize PR list")?;
+    Ok(prs)
 }
 
-fn gh_put_json(
-    token: &str,
-    url: &str,
-    body: &serde_json::Value,
-) -> Result<reqwest::blocking::Response> {
-    let client = reqwest::blocking::Client::new();
-    client
-        .put(url)
-        .header("Authorization", format!("Bearer {}", token))
-        .header("User-Agent", "zit-cli")
-        .header("Accept", "application/vnd.github+json")
-        .json(body)
-        .send()
-        .context("GitHub API request failed")
+/// A merged pull request as returned by the search API.
+#[derive(Debug, Clone, Deserialize)]
+pub struct MergedPullRequest {
+    pub number: u64,
+    pub title: String,
+    pub user: GhUser,
+    #[serde(default)]
+    pub labels: Vec<GhLabel>,
 }
 
-fn gh_patch_json(
+/// Pull requests merged after `since` (ISO 8601), into `base` when given.
+/// Reads at most 300 results.
+pub fn search_merged_pull_requests(
     token: &str,
-    url: &str,
-    body: &serde_json::Value,
-) -> Result<reqwest::blocking::Response> {
-    let client = reqwest::blocking::Client::new();
-    client
-        .patch(url)
-        .header("Authorization", format!("Bearer {}", token))
-        .header("User-Agent", "zit-cli")
-        .header("Accept", "application/vnd.github+json")
-        .json(body)
-        .send()
-        .context("GitHub API request failed")
+    since: &str,
+    base: Option<&str>,
+) -> Result<Vec<MergedPullRequest>> {
+    let (owner, repo) = parse_repo_from_remote()?;
+    let mut query = format!("repo:{}/{} is:pr is:merged merged:>{}", owner, repo, since);
+    if let Some(base) = base {
+        query.push_str(&format!(" base:{}", base));
+    }
+
+    let mut prs = Vec::new();
+    for page in 1..=3 {
+        let url = reqwest::Url::parse_with_params(
+            "https://api.github.com/search/issues",
+            &[
+                ("q", query.as_str()),
+                ("per_page", "100"),
+                ("page", &page.to_string()),
+            ],
+        )
+        .context("Invalid search URL")?;
+        let body = get_json(token, url.as_str(), "merged pull requests")?;
+        let items: Vec<MergedPullRequest> = serde_json::from_value(body["items"].clone())
+            .context("Failed to deserialize search results")?;
+        let done = items.len() < 100;
+        prs.extend(items);
+        if done {
+            break;
+        }
+    }
+    Ok(prs)
 }
 
-/// List pull requests. `state` is "open", "closed", or "all".
-pub fn list_pull_requests(token: &str, state: &str) -> Result<Vec<PullRequest>> {
+/// A repository milestone.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Milestone {
+    pub number: u64,
+    pub title: String,
+    pub state: String,
+    pub due_on: Option<String>,
+}
+
+/// Milestones of the current repository, soonest due first. Closed ones are
+/// included only with `all`.
+pub fn list_milestones(token: &str, all: bool) -> Result<Vec<Milestone>> {
     let (owner, repo) = parse_repo_from_remote()?;
     let url = format!(
-        "https://api.github.com/repos/{}/{}/pulls?state={}&per_page=50&sort=updated&direction=desc",
-        owner, repo, state
+        "https://api.github.com/repos/{}/{}/milestones?state={}&sort=due_on&direction=asc&per_page=100",
+        owner,
+        repo,
+        if all { "all" } else { "open" }
     );
-    let resp = gh_get(token, &url)?;
-    let status = resp.status();
-    let body: serde_json::Value = resp.json().context("Failed to parse PR list")?;
-    if !status.is_success() {
-        let msg = body["message"].as_str().unwrap_or("Unknown error");
-        anyhow::bail!("{}", msg);
+    let body = get_json(token, &url, "milestones")?;
+    serde_json::from_value(body).context("Failed to deserialize milestones")
+}
+
+/// An issue or pull request assigned to a milestone.
+#[derive(Debug, Clone, Deserialize)]
+pub struct MilestoneItem {
+    pub state: String,
+    /// Present only on pull requests.
+    #[serde(default)]
+    pub pull_request: Option<serde_json::Value>,
+}
+
+/// Issues and pull requests in milestone `number`, open and closed.
+/// Reads at most 500 items.
+pub fn list_milestone_items(token: &str, number: u64) -> Result<Vec<MilestoneItem>> {
+    let (owner, repo) = parse_repo_from_remote()?;
+    let mut items = Vec::new();
+    for page in 1..=5 {
+        let url = format!(
+            "https://api.github.com/repos/{}/{}/issues?milestone={}&state=all&per_page=100&page={}",
+            owner, repo, number, page
+        );
+        let body = get_json(token, &url, "milestone issues")?;
+        let batch: Vec<MilestoneItem> =
+            serde_json::from_value(body).context("Failed to deserialize milestone issues")?;
+        let done = batch.len() < 100;
+        items.extend(batch);
+        if done {
+            break;
+        }
     }
-    let prs: Vec<PullRequest> =
-        serde_json::from_value(body).context("Failed to deserialize PR list")?;
-    Ok(prs)
+    Ok(items)
 }
 
 /// Get a single pull request with full detail (includes mergeable, additions/deletions).
 pub fn get_pull_request(token: &str, number: u64) -> Result<PullRequest> {
     let (owner, repo) = parse_repo_from_remote()?;
+    get_pull_request_in(&owner, &repo, token, number)
+}
+
+fn get_pull_request_in(owner: &str, repo: &str, token: &str, number: u64) -> Result<PullRequest> {
     let url = format!(
         "https://api.github.com/repos/{}/{}/pulls/{}",
         owner, repo, number
     );
-    let resp = gh_get(token, &url)?;
-    let status = resp.status();
-    let body: serde_json::Value = resp.json().context("Failed to parse PR detail")?;
-    if !status.is_success() {
-        let msg = body["message"].as_str().unwrap_or("Unknown error");
-        anyhow::bail!("{}", msg);
-    }
+    let body = get_json(token, &url, "PR detail")?;
     let pr: PullRequest = serde_json::from_value(body).context("Failed to deserialize PR")?;
     Ok(pr)
 }
 
-/// Get CI check runs for a commit SHA.
+/// Get CI check runs for a commit SHA. Reads at most 500 runs; compare
+/// `check_runs` with `total_count` to tell whether some were left out.
 pub fn get_check_runs(token: &str, sha: &str) -> Result<CheckRunsResponse> {
     let (owner, repo) = parse_repo_from_remote()?;
-    let url = format!(
-        "https://api.github.com/repos/{}/{}/commits/{}/check-runs",
-        owner, repo, sha
-    );
-    let resp = gh_get(token, &url)?;
-    let status = resp.status();
-    let body: serde_json::Value = resp.json().context("Failed to parse check runs")?;
-    if !status.is_success() {
-        let msg = body["message"].as_str().unwrap_or("Unknown error");
-        anyhow::bail!("{}", msg);
-    }
-    let runs: CheckRunsResponse =
-        serde_json::from_value(body).context("Failed to deserialize check runs")?;
+    get_check_runs_in(&owner, &repo, token, sha)
+}
+
+fn get_check_runs_in(owner: &str, repo: &str, token: &str, sha: &str) -> Result<CheckRunsResponse> {
+    let mut runs = CheckRunsResponse {
+        total_count: 0,
+        check_runs: Vec::new(),
+    };
+    for page in 1..=5 {
+        let url = format!(
+            "https://api.github.com/repos/{}/{}/commits/{}/check-runs?per_page=100&page={}",
+            owner, repo, sha, page
+        );
+        let body = get_json(token, &url, "check runs")?;
+        let batch: CheckRunsResponse =
+            serde_json::from_value(body).context("Failed to deserialize check runs")?;
+        let done = batch.check_runs.len() < 100;
+        runs.total_count = batch.total_count;
+        runs.check_runs.extend(batch.check_runs);
+        if done || !runs.is_incomplete() {
+            break;
+        }
+    }
     Ok(runs)
 }
 
@@ -515,13 +896,7 @@ pub fn get_pr_files(token: &str, number: u64) -> Result<Vec<PrFile>> {
         "https://api.github.com/repos/{}/{}/pulls/{}/files?per_page=100",
         owner, repo, number
     );
-    let resp = gh_get(token, &url)?;
-    let status = resp.status();
-    let body: serde_json::Value = resp.json().context("Failed to parse PR files")?;
-    if !status.is_success() {
-        let msg = body["message"].as_str().unwrap_or("Unknown error");
-        anyhow::bail!("{}", msg);
-    }
+    let body = get_json(token, &url, "PR files")?;
     let files: Vec<PrFile> =
         serde_json::from_value(body).context("Failed to deserialize PR files")?;
     Ok(files)
@@ -534,38 +909,172 @@ pub fn get_pr_reviews(token: &str, number: u64) -> Result<Vec<PrReview>> {
         "https://api.github.com/repos/{}/{}/pulls/{}/reviews",
         owner, repo, number
     );
-    let resp = gh_get(token, &url)?;
-    let status = resp.status();
-    let body: serde_json::Value = resp.json().context("Failed to parse PR reviews")?;
-    if !status.is_success() {
-        let msg = body["message"].as_str().unwrap_or("Unknown error");
-        anyhow::bail!("{}", msg);
-    }
+    let body = get_json(token, &url, "PR reviews")?;
     let reviews: Vec<PrReview> =
         serde_json::from_value(body).context("Failed to deserialize PR reviews")?;
     Ok(reviews)
 }
 
+/// Submit a review: `event` is "APPROVE", "REQUEST_CHANGES" or "COMMENT".
+pub fn submit_pr_review(token: &str, number: u64, event: &str, body: &str) -> Result<PrReview> {
+    let (owner, repo) = parse_repo_from_remote()?;
+    submit_pr_review_in(&owner, &repo, token, number, event, body)
+}
+
+fn submit_pr_review_in(
+    owner: &str,
+    repo: &str,
+    token: &str,
+    number: u64,
+    event: &str,
+    body: &str,
+) -> Result<PrReview> {
+    let url = format!(
+        "https://api.github.com/repos/{}/{}/pulls/{}/reviews",
+        owner, repo, number
+    );
+    let request = serde_json::json!({ "event": event, "body": body });
+    let resp_body = send_json(Method::Post, token, &url, &request, "review")?;
+    serde_json::from_value(resp_body).context("Failed to deserialize review")
+}
+
+/// A comment on one line of a pull request's diff, at the commit `commit_id`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LineComment {
+    pub commit_id: String,
+    pub path: String,
+    pub line: u32,
+    /// "LEFT" for a removed line, "RIGHT" otherwise (see `PatchLine::anchor`).
+    pub side: &'static str,
+    pub body: String,
+}
+
+/// Comment on a line of a pull request's diff.
+pub fn comment_on_pr_line(token: &str, number: u64, comment: &LineComment) -> Result<()> {
+    let (owner, repo) = parse_repo_from_remote()?;
+    comment_on_pr_line_in(&owner, &repo, token, number, comment)
+}
+
+fn comment_on_pr_line_in(
+    owner: &str,
+    repo: &str,
+    token: &str,
+    number: u64,
+    comment: &LineComment,
+) -> Result<()> {
+    let url = format!(
+        "https://api.github.com/repos/{}/{}/pulls/{}/comments",
+        owner, repo, number
+    );
+    let request = serde_json::json!({
+        "body": comment.body,
+        "commit_id": comment.commit_id,
+        "path": comment.path,
+        "line": comment.line,
+        "side": comment.side,
+    });
+    send_json(Method::Post, token, &url, &request, "line comment")?;
+    Ok(())
+}
+
 /// Merge a pull request. `merge_method` is "merge", "squash", or "rebase".
 pub fn merge_pull_request(token: &str, number: u64, merge_method: &str) -> Result<MergeResponse> {
     let (owner, repo) = parse_repo_from_remote()?;
+    merge_pull_request_in(&owner, &repo, token, number, merge_method, None)
+}
+
+/// With `sha`, GitHub merges only if that is still the PR's head commit and
+/// answers 409 (`Conflict`) otherwise.
+fn merge_pull_request_in(
+    owner: &str,
+    repo: &str,
+    token: &str,
+    number: u64,
+    merge_method: &str,
+    sha: Option<&str>,
+) -> Result<MergeResponse> {
     let url = format!(
         "https://api.github.com/repos/{}/{}/pulls/{}/merge",
         owner, repo, number
     );
-    let body = serde_json::json!({ "merge_method": merge_method });
-    let resp = gh_put_json(token, &url, &body)?;
-    let status = resp.status();
-    let resp_body: serde_json::Value = resp.json().context("Failed to parse merge response")?;
-    if !status.is_success() {
-        let msg = resp_body["message"].as_str().unwrap_or("Merge failed");
-        anyhow::bail!("{}", msg);
+    let mut body = serde_json::json!({ "merge_method": merge_method });
+    if let Some(sha) = sha {
+        body["sha"] = sha.into();
     }
+    let resp_body = send_json(Method::Put, token, &url, &body, "merge")?;
     let merge: MergeResponse =
         serde_json::from_value(resp_body).context("Failed to deserialize merge response")?;
     Ok(merge)
 }
 
+/// One poll of a watched pull request: the PR, its head commit's checks and
+/// what that means. With `merge_method`, a PR ready to merge is merged.
+pub fn poll_watched_pr(
+    token: &str,
+    number: u64,
+    merge_method: Option<&str>,
+) -> Result<(PullRequest, CheckRunsResponse, WatchStep)> {
+    let (owner, repo) = parse_repo_from_remote()?;
+    poll_watched_pr_in(&owner, &repo, token, number, merge_method)
+}
+
+fn poll_watched_pr_in(
+    owner: &str,
+    repo: &str,
+    token: &str,
+    number: u64,
+    merge_method: Option<&str>,
+) -> Result<(PullRequest, CheckRunsResponse, WatchStep)> {
+    let pr = get_pull_request_in(owner, repo, token, number)?;
+    let checks = get_check_runs_in(owner, repo, token, &pr.head.sha)?;
+    let mut step = watch_step(&pr, &checks, merge_method.is_some());
+    if let (WatchStep::Merge, Some(method)) = (&step, merge_method) {
+        // Merge only the commit whose checks were read; a newer push is
+        // watched again on the next poll
+        step = match merge_pull_request_in(owner, repo, token, number, method, Some(&pr.head.sha)) {
+            Err(e) if e.downcast_ref::<Conflict>().is_some() => WatchStep::Wait,
+            merged => WatchStep::Merged(merged.map_err(|e| e.to_string())),
+        };
+    }
+    Ok((pr, checks, step))
+}
+
+/// Open a pull request from `head` into `base`.
+pub fn create_pull_request(
+    token: &str,
+    head: &str,
+    base: &str,
+    title: &str,
+    body: &str,
+    draft: bool,
+) -> Result<PullRequest> {
+    let (owner, repo) = parse_repo_from_remote()?;
+    create_pull_request_in(&owner, &repo, token, head, base, title, body, draft)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn create_pull_request_in(
+    owner: &str,
+    repo: &str,
+    token: &str,
+    head: &str,
+    base: &str,
+    title: &str,
+    body: &str,
+    draft: bool,
+) -> Result<PullRequest> {
+    let url = format!("https://api.github.com/repos/{}/{}/pulls", owner, repo);
+    let request = serde_json::json!({
+        "title": title,
+        "head": head,
+        "base": base,
+        "body": body,
+        "draft": draft,
+    });
+    let resp_body = send_json(Method::Post, token, &url, &request, "new pull request")?;
+    serde_json::from_value(resp_body).context("Failed to deserialize PR")
+}
+
 /// Close a pull request.
 pub fn close_pull_request(token: &str, number: u64) -> Result<PullRequest> {
     let (owner, repo) = parse_repo_from_remote()?;
@@ -574,17 +1083,231 @@ pub fn close_pull_request(token: &str, number: u64) -> Result<PullRequest> {
         owner, repo, number
     );
     let body = serde_json::json!({ "state": "closed" });
-    let resp = gh_patch_json(token, &url, &body)?;
-    let status = resp.status();
-    let resp_body: serde_json::Value = resp.json().context("Failed to parse close response")?;
-    if !status.is_success() {
-        let msg = resp_body["message"].as_str().unwrap_or("Close failed");
-        anyhow::bail!("{}", msg);
-    }
+    let resp_body = send_json(Method::Patch, token, &url, &body, "close")?;
     let pr: PullRequest = serde_json::from_value(resp_body).context("Failed to deserialize PR")?;
     Ok(pr)
 }
 
+// ─── Issue Types ───────────────────────────────────────────────
+
+#[derive(Debug, Clone, Deserialize)]
+#[allow(dead_code)]
+pub struct Issue {
+    pub number: u64,
+    pub title: String,
+    pub state: String,
+    pub body: Option<String>,
+    pub html_url: String,
+    pub created_at: String,
+    pub updated_at: String,
+    pub user: GhUser,
+    #[serde(default)]
+    pub labels: Vec<GhLabel>,
+    #[serde(default)]
+    pub assignees: Vec<GhUser>,
+    #[serde(default)]
+    pub comments: u64,
+    /// Present when the entry is a pull request; the issues API lists both.
+    pub pull_request: Option<serde_json::Value>,
+}
+
+impl Issue {
+    /// Whether the issue matches a filter typed in the list: every word must
+    /// appear in the title, or name the number (`#12`), author or an assignee
+    /// (`@login`), or a label. Case-insensitive.
+    pub fn matches(&self, query: &str) -> bool {
+        let title = self.title.to_lowercase();
+        query.split_whitespace().all(|word| {
+            let word = word.to_lowercase();
+            if let Some(number) = word.strip_prefix('#') {
+                return number == self.number.to_string();
+            }
+            if let Some(login) = word.strip_prefix('@') {
+                return std::iter::once(&self.user)
+                    .chain(&self.assignees)
+                    .any(|u| u.login.eq_ignore_ascii_case(login));
+            }
+            title.contains(&word)
+                || self
+                    .labels
+                    .iter()
+                    .any(|l| l.name.eq_ignore_ascii_case(&word))
+        })
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct IssueComment {
+    pub user: GhUser,
+    pub body: Option<String>,
+    pub created_at: String,
+}
+
+/// Label names typed as a comma-separated list, spelled as the repository
+/// spells them; an error names the first one it doesn't have.
+pub fn match_labels(typed: &str, known: &[GhLabel]) -> Result<Vec<String>> {
+    let mut names: Vec<String> = Vec::new();
+    for name in typed.split(',').map(str::trim).filter(|n| !n.is_empty()) {
+        let Some(label) = known.iter().find(|l| l.name.eq_ignore_ascii_case(name)) else {
+            anyhow::bail!("No label '{}' in this repository", name);
+        };
+        if !names.contains(&label.name) {
+            names.push(label.name.clone());
+        }
+    }
+    Ok(names)
+}
+
+// ─── Issue API Functions ───────────────────────────────────────
+
+/// List issues, without the pull requests the issues API mixes in. `state` is
+/// "open", "closed", or "all".
+pub fn list_issues(token: &str, state: &str) -> Result<Vec<Issue>> {
+    let (owner, repo) = parse_repo_from_remote()?;
+    list_issues_in(&owner, &repo, token, state)
+}
+
+fn list_issues_in(owner: &str, repo: &str, token: &str, state: &str) -> Result<Vec<Issue>> {
+    let url = format!(
+        "https://api.github.com/repos/{}/{}/issues?state={}&per_page=50&sort=updated&direction=desc",
+        owner, repo, state
+    );
+    let body = get_json(token, &url, "issue list")?;
+    let issues: Vec<Issue> =
+        serde_json::from_value(body).context("Failed to deserialize issue list")?;
+    Ok(issues
+        .into_iter()
+        .filter(|i| i.pull_request.is_none())
+        .collect())
+}
+
+/// Get a single issue.
+pub fn get_issue(token: &str, number: u64) -> Result<Issue> {
+    let (owner, repo) = parse_repo_from_remote()?;
+    let url = format!(
+        "https://api.github.com/repos/{}/{}/issues/{}",
+        owner, repo, number
+    );
+    let body = get_json(token, &url, "issue")?;
+    serde_json::from_value(body).context("Failed to deserialize issue")
+}
+
+/// Get the comments on an issue, oldest first.
+pub fn get_issue_comments(token: &str, number: u64) -> Result<Vec<IssueComment>> {
+    let (owner, repo) = parse_repo_from_remote()?;
+    let url = format!(
+        "https://api.github.com/repos/{}/{}/issues/{}/comments?per_page=100",
+        owner, repo, number
+    );
+    let body = get_json(token, &url, "issue comments")?;
+    serde_json::from_value(body).context("Failed to deserialize issue comments")
+}
+
+/// The labels defined in the repository.
+pub fn list_labels(token: &str) -> Result<Vec<GhLabel>> {
+    let (owner, repo) = parse_repo_from_remote()?;
+    let url = format!(
+        "https://api.github.com/repos/{}/{}/labels?per_page=100",
+        owner, repo
+    );
+    let body = get_json(token, &url, "labels")?;
+    serde_json::from_value(body).context("Failed to deserialize labels")
+}
+
+/// Open a new issue.
+pub fn create_issue(token: &str, title: &str, body: &str) -> Result<Issue> {
+    let (owner, repo) = parse_repo_from_remote()?;
+    let url = format!("https://api.github.com/repos/{}/{}/issues", owner, repo);
+    let request = serde_json::json!({ "title": title, "body": body });
+    let resp_body = send_json(Method::Post, token, &url, &request, "new issue")?;
+    serde_json::from_value(resp_body).context("Failed to deserialize issue")
+}
+
+/// Comment on an issue.
+pub fn comment_on_issue(token: &str, number: u64, body: &str) -> Result<IssueComment> {
+    let (owner, repo) = parse_repo_from_remote()?;
+    let url = format!(
+        "https://api.github.com/repos/{}/{}/issues/{}/comments",
+        owner, repo, number
+    );
+    let request = serde_json::json!({ "body": body });
+    let resp_body = send_json(Method::Post, token, &url, &request, "comment")?;
+    serde_json::from_value(resp_body).context("Failed to deserialize comment")
+}
+
+/// Replace an issue's labels.
+pub fn set_issue_labels(token: &str, number: u64, labels: &[String]) -> Result<Vec<GhLabel>> {
+    let (owner, repo) = parse_repo_from_remote()?;
+    let url = format!(
+        "https://api.github.com/repos/{}/{}/issues/{}/labels",
+        owner, repo, number
+    );
+    let request = serde_json::json!({ "labels": labels });
+    let resp_body = send_json(Method::Put, token, &url, &request, "labels")?;
+    serde_json::from_value(resp_body).context("Failed to deserialize labels")
+}
+
+/// Close or reopen an issue.
+pub fn set_issue_state(token: &str, number: u64, open: bool) -> Result<Issue> {
+    let (owner, repo) = parse_repo_from_remote()?;
+    set_issue_state_in(&owner, &repo, token, number, open)
+}
+
+fn set_issue_state_in(
+    owner: &str,
+    repo: &str,
+    token: &str,
+    number: u64,
+    open: bool,
+) -> Result<Issue> {
+    let url = format!(
+        "https://api.github.com/repos/{}/{}/issues/{}",
+        owner, repo, number
+    );
+    let request = serde_json::json!({ "state": if open { "open" } else { "closed" } });
+    let resp_body = send_json(Method::Patch, token, &url, &request, "issue state")?;
+    serde_json::from_value(resp_body).context("Failed to deserialize issue")
+}
+
+// ─── Releases ──────────────────────────────────────────────────
+
+/// Publish a GitHub release for `tag`, returning its page. Empty `notes`
+/// let GitHub write them from the merged pull requests.
+pub fn create_release(
+    token: &str,
+    tag: &str,
+    title: &str,
+    notes: &str,
+    draft: bool,
+) -> Result<String> {
+    let (owner, repo) = parse_repo_from_remote()?;
+    create_release_in(&owner, &repo, token, tag, title, notes, draft)
+}
+
+fn create_release_in(
+    owner: &str,
+    repo: &str,
+    token: &str,
+    tag: &str,
+    title: &str,
+    notes: &str,
+    draft: bool,
+) -> Result<String> {
+    let url = format!("https://api.github.com/repos/{}/{}/releases", owner, repo);
+    let request = serde_json::json!({
+        "tag_name": tag,
+        "name": title,
+        "body": notes,
+        "draft": draft,
+        "generate_release_notes": notes.is_empty(),
+    });
+    let resp_body = send_json(Method::Post, token, &url, &request, "new release")?;
+    resp_body["html_url"]
+        .as_str()
+        .map(String::from)
+        .context("GitHub's reply had no release URL")
+}
+
 // ─── GitHub Actions Types ────────────────────────────────────────
 
 #[derive(Debug, Clone, Deserialize)]
@@ -639,22 +1362,48 @@ pub struct WorkflowJobsResponse {
     pub jobs: Vec<WorkflowJob>,
 }
 
+/// A check run annotation: a message pinned to a file and line range.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CheckAnnotation {
+    pub path: String,
+    pub start_line: u32,
+    #[allow(dead_code)]
+    pub end_line: u32,
+    /// "failure", "warning" or "notice".
+    pub annotation_level: String,
+    pub message: String,
+    #[serde(default)]
+    pub title: Option<String>,
+}
+
 // ─── GitHub Actions API Functions ────────────────────────────────
 
-/// List workflow runs for the repository, sorted newest first.
-pub fn list_workflow_runs(token: &str) -> Result<WorkflowRunsResponse> {
+/// List workflow runs for the repository, newest first; only those of
+/// `branch` when one is given.
+pub fn list_workflow_runs(token: &str, branch: Option<&str>) -> Result<WorkflowRunsResponse> {
     let (owner, repo) = parse_repo_from_remote()?;
-    let url = format!(
-        "https://api.github.com/repos/{}/{}/actions/runs?per_page=30&sort=created&direction=desc",
-        owner, repo
-    );
-    let resp = gh_get(token, &url)?;
-    let status = resp.status();
-    let body: serde_json::Value = resp.json().context("Failed to parse workflow runs")?;
-    if !status.is_success() {
-        let msg = body["message"].as_str().unwrap_or("Unknown error");
-        anyhow::bail!("{}", msg);
+    list_workflow_runs_in(&owner, &repo, token, branch)
+}
+
+fn list_workflow_runs_in(
+    owner: &str,
+    repo: &str,
+    token: &str,
+    branch: Option<&str>,
+) -> Result<WorkflowRunsResponse> {
+    let mut params = vec![("per_page", "30")];
+    if let Some(branch) = branch {
+        params.push(("branch", branch));
     }
+    let url = reqwest::Url::parse_with_params(
+        &format!(
+            "https://api.github.com/repos/{}/{}/actions/runs",
+            owner, repo
+        ),
+        &params,
+    )
+    .context("Invalid workflow runs URL")?;
+    let body = get_json(token, url.as_str(), "workflow runs")?;
     let runs: WorkflowRunsResponse =
         serde_json::from_value(body).context("Failed to deserialize workflow runs")?;
     Ok(runs)
@@ -667,18 +1416,45 @@ pub fn list_run_jobs(token: &str, run_id: u64) -> Result<WorkflowJobsResponse> {
         "https://api.github.com/repos/{}/{}/actions/runs/{}/jobs",
         owner, repo, run_id
     );
-    let resp = gh_get(token, &url)?;
-    let status = resp.status();
-    let body: serde_json::Value = resp.json().context("Failed to parse workflow jobs")?;
-    if !status.is_success() {
-        let msg = body["message"].as_str().unwrap_or("Unknown error");
-        anyhow::bail!("{}", msg);
-    }
+    let body = get_json(token, &url, "workflow jobs")?;
     let jobs: WorkflowJobsResponse =
         serde_json::from_value(body).context("Failed to deserialize workflow jobs")?;
     Ok(jobs)
 }
 
+/// Re-run the failed jobs of a completed workflow run (and the jobs that
+/// depend on them).
+pub fn rerun_failed_jobs(token: &str, run_id: u64) -> Result<()> {
+    let (owner, repo) = parse_repo_from_remote()?;
+    rerun_failed_jobs_in(&owner, &repo, token, run_id)
+}
+
+fn rerun_failed_jobs_in(owner: &str, repo: &str, token: &str, run_id: u64) -> Result<()> {
+    let url = format!(
+        "https://api.github.com/repos/{}/{}/actions/runs/{}/rerun-failed-jobs",
+        owner, repo, run_id
+    );
+    let resp = github(Method::Post, token, &url)
+        .send()
+        .context("Failed to reach GitHub to re-run jobs")?;
+    if resp.is_success() {
+        Ok(())
+    } else {
+        Err(api_error(&resp))
+    }
+}
+
+/// Annotations of a check run. A workflow job's id doubles as its check run id.
+pub fn list_check_annotations(token: &str, check_run_id: u64) -> Result<Vec<CheckAnnotation>> {
+    let (owner, repo) = parse_repo_from_remote()?;
+    let url = format!(
+        "https://api.github.com/repos/{}/{}/check-runs/{}/annotations?per_page=100",
+        owner, repo, check_run_id
+    );
+    let body = get_json(token, &url, "annotations")?;
+    serde_json::from_value(body).context("Failed to deserialize annotations")
+}
+
 /// Download logs for a specific job. Returns the log text.
 pub fn get_job_logs(token: &str, job_id: u64) -> Result<String> {
     let (owner, repo) = parse_repo_from_remote()?;
@@ -686,30 +1462,733 @@ pub fn get_job_logs(token: &str, job_id: u64) -> Result<String> {
         "https://api.github.com/repos/{}/{}/actions/jobs/{}/logs",
         owner, repo, job_id
     );
-    let client = reqwest::blocking::Client::new();
-    let resp = client
-        .get(&url)
-        .header("Authorization", format!("Bearer {}", token))
-        .header("User-Agent", "zit-cli")
-        .header("Accept", "application/vnd.github+json")
+    let resp = github(Method::Get, token, &url)
         .send()
         .context("Failed to fetch job logs")?;
 
-    let status = resp.status();
-    if status.is_redirection() {
+    if resp.is_redirection() {
         // GitHub redirects to a temporary URL for log downloads
-        if let Some(location) = resp.headers().get("location") {
-            let redirect_url = location.to_str().unwrap_or("");
-            let log_resp =
-                reqwest::blocking::get(redirect_url).context("Failed to follow log redirect")?;
-            return log_resp.text().context("Failed to read log text");
+        if let Some(location) = resp.header("location") {
+            let log_resp = Request::new(Method::Get, location)
+                .send()
+                .context("Failed to follow log redirect")?;
+            return Ok(log_resp.text());
         }
     }
 
-    if !status.is_success() {
-        let body = resp.text().unwrap_or_default();
-        anyhow::bail!("Failed to fetch logs ({}): {}", status, body);
+    if !resp.is_success() {
+        anyhow::bail!("Failed to fetch logs ({}): {}", resp.status, resp.text());
     }
 
-    resp.text().context("Failed to read log text")
+    Ok(resp.text())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::mock::MockTransport;
+    use serde_json::json;
+    use std::sync::Arc;
+
+    fn json_response(status: u16, body: serde_json::Value) -> Response {
+        Response::new(status, body.to_string())
+    }
+
+    #[test]
+    fn test_token_scopes() {
+        let mock = Arc::new(
+            MockTransport::new().on(
+                Method::Get,
+                "/user",
+                json_response(200, json!({ "login": "mona" }))
+                    .with_header("X-OAuth-Scopes", "repo, gist"),
+            ),
+        );
+        let (login, scopes) = mock.serve(|| token_scopes("tok").unwrap());
+        assert_eq!(login, "mona");
+        let scopes = scopes.unwrap();
+        assert_eq!(scopes, ["repo", "gist"]);
+        assert_eq!(missing_scopes(&scopes), ["read:user"]);
+
+        // Fine-grained tokens send no scopes header
+        let mock = Arc::new(MockTransport::new().on(
+            Method::Get,
+            "/user",
+            json_response(200, json!({ "login": "mona" })),
+        ));
+        assert_eq!(mock.serve(|| token_scopes("tok").unwrap()).1, None);
+    }
+
+    #[test]
+    fn test_device_flow() {
+        let token_path = "/login/oauth/access_token";
+        let mock = Arc::new(
+            MockTransport::new()
+                .on(
+                    Method::Post,
+                    "/login/device/code",
+                    json_response(
+                        200,
+                        json!({
+                            "device_code": "dev123",
+                            "user_code": "ABCD-1234",
+                            "verification_uri": "https://github.com/login/device",
+                            "expires_in": 900,
+                            "interval": 5
+                        }),
+                    ),
+                )
+                .on(
+                    Method::Post,
+                    token_path,
+                    json_response(200, json!({ "error": "authorization_pending" })),
+                )
+                .on(
+                    Method::Post,
+                    token_path,
+                    json_response(200, json!({ "error": "slow_down", "interval": 10 })),
+                )
+                .on(
+                    Method::Post,
+                    token_path,
+                    json_response(
+                        200,
+                        json!({ "access_token": "gho_x", "token_type": "bearer", "scope": "repo" }),
+                    ),
+                ),
+        );
+
+        mock.serve(|| {
+            let code = request_device_code().unwrap();
+            assert_eq!((code.user_code.as_str(), code.interval), ("ABCD-1234", 5));
+            assert!(matches!(poll_for_token("dev123"), PollResult::Pending));
+            assert!(matches!(poll_for_token("dev123"), PollResult::SlowDown(10)));
+            match poll_for_token("dev123") {
+                PollResult::Success(token) => assert_eq!(token.access_token, "gho_x"),
+                other => panic!("expected a token, got {:?}", other),
+            }
+        });
+
+        let requests = mock.requests();
+        assert_eq!(requests.len(), 4);
+        assert!(requests[0].body_text().contains("scope=repo%2Cread%3Auser"));
+        assert!(requests[1].body_text().contains("device_code=dev123"));
+    }
+
+    #[test]
+    fn test_merge_pull_request() {
+        let path = "/repos/octo/app/pulls/7/merge";
+        let mock = Arc::new(
+            MockTransport::new()
+                .on(
+                    Method::Put,
+                    path,
+                    json_response(
+                        200,
+                        json!({ "sha": "abc123", "merged": true, "message": "Pull Request successfully merged" }),
+                    ),
+                )
+                .on(
+                    Method::Put,
+                    path,
+                    json_response(405, json!({ "message": "Pull Request is not mergeable" })),
+                ),
+        );
+
+        mock.serve(|| {
+            let merged = merge_pull_request_in("octo", "app", "tok", 7, "squash", None).unwrap();
+            assert!(merged.merged);
+            assert_eq!(merged.sha, "abc123");
+            let err = merge_pull_request_in("octo", "app", "tok", 7, "squash", None).unwrap_err();
+            assert_eq!(err.to_string(), "Pull Request is not mergeable");
+        });
+
+        let request = &mock.requests()[0];
+        assert_eq!(request.header_value("authorization"), Some("Bearer tok"));
+        assert_eq!(request.body_text(), r#"{"merge_method":"squash"}"#);
+    }
+
+    fn check(status: &str, conclusion: Option<&str>) -> CheckRun {
+        CheckRun {
+            name: format!("{}-{}", status, conclusion.unwrap_or("none")),
+            status: status.to_string(),
+            conclusion: conclusion.map(str::to_string),
+            html_url: String::new(),
+        }
+    }
+
+    fn checks(runs: Vec<CheckRun>) -> CheckRunsResponse {
+        CheckRunsResponse {
+            total_count: runs.len() as u64,
+            check_runs: runs,
+        }
+    }
+
+    fn watched_pr(
+        state: &str,
+        merged: bool,
+        draft: bool,
+        mergeable_state: Option<&str>,
+    ) -> serde_json::Value {
+        let user = json!({ "login": "octocat", "avatar_url": "" });
+        let branch =
+            |name: &str| json!({ "label": format!("octo:{}", name), "ref": name, "sha": "abc123" });
+        json!({
+            "number": 7, "title": "Retry on timeout", "state": state, "body": null,
+            "html_url": "https://github.com/octo/app/pull/7",
+            "created_at": "2024-05-01T10:00:00Z", "updated_at": "2024-05-01T10:00:00Z",
+            "merged_at": if merged { json!("2024-05-02T10:00:00Z") } else { json!(null) },
+            "draft": draft, "mergeable": null, "mergeable_state": mergeable_state,
+            "head": branch("feature/retry"), "base": branch("main"), "user": user,
+        })
+    }
+
+    #[test]
+    fn test_checks_outcome() {
+        let done = |conclusion| check("completed", Some(conclusion));
+        let cases = [
+            (vec![], ChecksOutcome::None),
+            (
+                vec![done("success"), check("in_progress", None)],
+                ChecksOutcome::Pending,
+            ),
+            (vec![check("queued", None)], ChecksOutcome::Pending),
+            (
+                vec![done("success"), done("skipped"), done("neutral")],
+                ChecksOutcome::Passed,
+            ),
+            // A failure is reported before the rest finish
+            (
+                vec![
+                    done("failure"),
+                    check("in_progress", None),
+                    done("timed_out"),
+                ],
+                ChecksOutcome::Failed(vec![
+                    "completed-failure".to_string(),
+                    "completed-timed_out".to_string(),
+                ]),
+            ),
+            (
+                vec![done("cancelled"), done("action_required")],
+                ChecksOutcome::Failed(vec![
+                    "completed-cancelled".to_string(),
+                    "completed-action_required".to_string(),
+                ]),
+            ),
+        ];
+        for (runs, expected) in cases {
+            assert_eq!(checks(runs).outcome(), expected);
+        }
+    }
+
+    #[test]
+    fn test_watch_step() {
+        let pr = |state, merged, draft, mergeable: Option<&str>| -> PullRequest {
+            serde_json::from_value(watched_pr(state, merged, draft, mergeable)).unwrap()
+        };
+        let passed = checks(vec![check("completed", Some("success"))]);
+        let running = checks(vec![check("in_progress", None)]);
+        let step = |pr: &PullRequest, checks: &CheckRunsResponse, auto_merge| {
+            format!("{:?}", watch_step(pr, checks, auto_merge))
+        };
+
+        let open = pr("open", false, false, Some("clean"));
+        assert_eq!(step(&open, &running, true), "Wait");
+        assert_eq!(step(&open, &passed, false), "Passed");
+        assert_eq!(step(&open, &passed, true), "Merge");
+        assert_eq!(
+            step(&pr("open", false, false, Some("unknown")), &passed, true),
+            "Wait"
+        );
+        assert_eq!(step(&pr("open", false, false, None), &passed, true), "Wait");
+        assert_eq!(
+            step(&pr("open", false, false, Some("blocked")), &passed, true),
+            r#"NotMerging("blocked")"#
+        );
+        assert_eq!(
+            step(&pr("open", false, true, Some("clean")), &passed, true),
+            r#"NotMerging("draft")"#
+        );
+        assert_eq!(
+            step(&pr("closed", true, false, None), &running, true),
+            "Closed(true)"
+        );
+        assert_eq!(
+            step(&pr("closed", false, false, None), &passed, true),
+            "Closed(false)"
+        );
+    }
+
+    #[test]
+    fn test_poll_watched_pr_merges_only_when_clean() {
+        let pr_path = "/repos/octo/app/pulls/7";
+        let merge_path = "/repos/octo/app/pulls/7/merge";
+        let runs = json!({ "total_count": 1, "check_runs": [
+            { "name": "ci", "status": "completed", "conclusion": "success" }
+        ]});
+        let mock = Arc::new(
+            MockTransport::new()
+                .on(
+                    Method::Get,
+                    pr_path,
+                    json_response(200, watched_pr("open", false, false, Some("unknown"))),
+                )
+                .on(
+                    Method::Get,
+                    pr_path,
+                    json_response(200, watched_pr("open", false, false, Some("blocked"))),
+                )
+                .on(
+                    Method::Get,
+                    pr_path,
+                    json_response(200, watched_pr("open", false, true, Some("clean"))),
+                )
+                .on(
+                    Method::Get,
+                    pr_path,
+                    json_response(200, watched_pr("closed", true, false, None)),
+                )
+                .on(
+                    Method::Get,
+                    pr_path,
+                    json_response(200, watched_pr("open", false, false, Some("clean"))),
+                )
+                .on(
+                    Method::Get,
+                    "/repos/octo/app/commits/abc123/check-runs",
+                    json_response(200, runs),
+                )
+                .on(
+                    Method::Put,
+                    merge_path,
+                    json_response(
+                        200,
+                        json!({ "sha": "def456", "merged": true, "message": "Merged" }),
+                    ),
+                ),
+        );
+
+        let steps: Vec<String> = mock.serve(|| {
+            (0..5)
+                .map(|_| {
+                    let (_, _, step) =
+                        poll_watched_pr_in("octo", "app", "tok", 7, Some("squash")).unwrap();
+                    format!("{:?}", step)
+                })
+                .collect()
+        });
+        assert_eq!(
+            steps[..4],
+            [
+                "Wait",
+                r#"NotMerging("blocked")"#,
+                r#"NotMerging("draft")"#,
+                "Closed(true)"
+            ]
+        );
+        assert!(
+            steps[4].starts_with("Merged(Ok(MergeResponse"),
+            "{}",
+            steps[4]
+        );
+
+        // Only the clean, open, ready PR was merged
+        let merges: Vec<_> = mock
+            .requests()
+            .into_iter()
+            .filter(|r| r.method == Method::Put)
+            .collect();
+        assert_eq!(merges.len(), 1);
+        assert!(merges[0].url.ends_with(merge_path));
+        assert_eq!(
+            merges[0].body_text(),
+            r#"{"merge_method":"squash","sha":"abc123"}"#
+        );
+    }
+
+    #[test]
+    fn test_poll_watched_pr_keeps_watching_a_moved_head() {
+        let merge_path = "/repos/octo/app/pulls/7/merge";
+        let runs = json!({ "total_count": 1, "check_runs": [
+            { "name": "ci", "status": "completed", "conclusion": "success" }
+        ]});
+        let mock = Arc::new(
+            MockTransport::new()
+                .on(
+                    Method::Get,
+                    "/repos/octo/app/pulls/7",
+                    json_response(200, watched_pr("open", false, false, Some("clean"))),
+                )
+                .on(
+                    Method::Get,
+                    "/repos/octo/app/commits/abc123/check-runs",
+                    json_response(200, runs),
+                )
+                .on(
+                    Method::Put,
+                    merge_path,
+                    json_response(409, json!({ "message": "Head branch was modified. Review and try the merge again." })),
+                ),
+        );
+
+        let (_, _, step) = mock
+            .serve(|| poll_watched_pr_in("octo", "app", "tok", 7, Some("merge")))
+            .unwrap();
+        assert_eq!(format!("{:?}", step), "Wait");
+    }
+
+    #[test]
+    fn test_poll_watched_pr_wont_merge_on_unread_checks() {
+        let checks_path = "/repos/octo/app/commits/abc123/check-runs";
+        // GitHub counts 3 runs but the pages only hold one
+        let runs = json!({ "total_count": 3, "check_runs": [
+            { "name": "ci", "status": "completed", "conclusion": "success" }
+        ]});
+        let mock = Arc::new(
+            MockTransport::new()
+                .on(
+                    Method::Get,
+                    "/repos/octo/app/pulls/7",
+                    json_response(200, watched_pr("open", false, false, Some("clean"))),
+                )
+                .on(Method::Get, checks_path, json_response(200, runs)),
+        );
+
+        let (_, checks, step) = mock
+            .serve(|| poll_watched_pr_in("octo", "app", "tok", 7, Some("squash")))
+            .unwrap();
+        assert!(checks.is_incomplete());
+        assert_eq!(
+            format!("{:?}", step),
+            r#"NotMerging("only 1 of 3 checks read")"#
+        );
+        let requests = mock.requests();
+        assert!(requests.iter().all(|r| r.method != Method::Put));
+        assert!(
+            requests
+                .iter()
+                .any(|r| r.url.ends_with("check-runs?per_page=100&page=1"))
+        );
+    }
+
+    #[test]
+    fn test_patch_lines_and_anchors() {
+        let file = PrFile {
+            filename: "src/lib.rs".to_string(),
+            status: "modified".to_string(),
+            additions: 2,
+            deletions: 1,
+            changes: 3,
+            patch: Some(
+                "@@ -10,3 +10,4 @@ fn main() {\n     let a = 1;\n-    let b = 2;\n+    let b = 3;\n+    let c = 4;\n     a + b\n\\ No newline at end of file"
+                    .to_string(),
+            ),
+        };
+        let lines = file.patch_lines();
+        let anchors: Vec<_> = lines.iter().map(PatchLine::anchor).collect();
+        assert_eq!(
+            anchors,
+            [
+                None,
+                Some((10, "RIGHT")),
+                Some((11, "LEFT")),
+                Some((11, "RIGHT")),
+                Some((12, "RIGHT")),
+                Some((13, "RIGHT")),
+                None,
+            ]
+        );
+        assert_eq!(lines[2].text, "-    let b = 2;");
+    }
+
+    #[test]
+    fn test_review_and_line_comment() {
+        let review = json!({
+            "user": { "login": "mona", "avatar_url": "" },
+            "state": "APPROVED", "body": "", "submitted_at": "2024-05-01T10:00:00Z"
+        });
+        let mock = Arc::new(
+            MockTransport::new()
+                .on(
+                    Method::Post,
+                    "/repos/octo/app/pulls/7/reviews",
+                    json_response(200, review),
+                )
+                .on(
+                    Method::Post,
+                    "/repos/octo/app/pulls/7/comments",
+                    json_response(201, json!({ "id": 1 })),
+                ),
+        );
+
+        mock.serve(|| {
+            let review = submit_pr_review_in("octo", "app", "tok", 7, "APPROVE", "").unwrap();
+            assert_eq!(review.state, "APPROVED");
+            let comment = LineComment {
+                commit_id: "abc123".to_string(),
+                path: "src/lib.rs".to_string(),
+                line: 11,
+                side: "LEFT",
+                body: "Why 2?".to_string(),
+            };
+            comment_on_pr_line_in("octo", "app", "tok", 7, &comment).unwrap();
+        });
+
+        let requests = mock.requests();
+        assert_eq!(requests[0].body_text(), r#"{"body":"","event":"APPROVE"}"#);
+        let sent: serde_json::Value = serde_json::from_str(&requests[1].body_text()).unwrap();
+        assert_eq!(sent["line"], 11);
+        assert_eq!(sent["side"], "LEFT");
+        assert_eq!(sent["commit_id"], "abc123");
+    }
+
+    #[test]
+    fn test_create_release() {
+        let mock = Arc::new(MockTransport::new().on(
+            Method::Post,
+            "/repos/octo/app/releases",
+            json_response(
+                201,
+                json!({ "html_url": "https://github.com/octo/app/releases/tag/v1.2.0" }),
+            ),
+        ));
+        let url = mock
+            .serve(|| create_release_in("octo", "app", "tok", "v1.2.0", "v1.2.0", "", false))
+            .unwrap();
+        assert_eq!(url, "https://github.com/octo/app/releases/tag/v1.2.0");
+        let body = mock.requests()[0].body_text();
+        assert!(body.contains(r#""tag_name":"v1.2.0""#));
+        assert!(body.contains(r#""generate_release_notes":true"#));
+    }
+
+    #[test]
+    fn test_create_pull_request() {
+        let user = json!({ "login": "octocat", "avatar_url": "" });
+        let branch =
+            |name: &str| json!({ "label": format!("octo:{}", name), "ref": name, "sha": "abc123" });
+        let pr = json!({
+            "number": 12, "title": "Retry on timeout", "state": "open", "body": null,
+            "html_url": "https://github.com/octo/app/pull/12",
+            "created_at": "2024-05-01T10:00:00Z", "updated_at": "2024-05-01T10:00:00Z",
+            "merged_at": null, "draft": true, "mergeable": null, "mergeable_state": null,
+            "head": branch("feature/retry"), "base": branch("main"), "user": user,
+        });
+        let mock = Arc::new(
+            MockTransport::new()
+                .on(Method::Post, "/repos/octo/app/pulls", json_response(201, pr))
+                .on(
+                    Method::Post,
+                    "/repos/octo/app/pulls",
+                    json_response(
+                        422,
+                        json!({ "message": "A pull request already exists for octo:feature/retry." }),
+                    ),
+                ),
+        );
+
+        mock.serve(|| {
+            let created = create_pull_request_in(
+                "octo",
+                "app",
+                "tok",
+                "feature/retry",
+                "main",
+                "Retry on timeout",
+                "",
+                true,
+            )
+            .unwrap();
+            assert_eq!(created.number, 12);
+            assert!(created.draft);
+            let err = create_pull_request_in(
+                "octo",
+                "app",
+                "tok",
+                "feature/retry",
+                "main",
+                "Again",
+                "",
+                false,
+            )
+            .unwrap_err();
+            assert!(err.to_string().contains("already exists"), "{}", err);
+        });
+
+        let request: serde_json::Value =
+            serde_json::from_str(&mock.requests()[0].body_text()).unwrap();
+        assert_eq!(request["head"], "feature/retry");
+        assert_eq!(request["base"], "main");
+        assert_eq!(request["draft"], true);
+    }
+
+    fn issue_json(number: u64, title: &str, state: &str) -> serde_json::Value {
+        json!({
+            "number": number,
+            "title": title,
+            "state": state,
+            "body": null,
+            "html_url": format!("https://github.com/octo/app/issues/{}", number),
+            "created_at": "2026-01-02T03:04:05Z",
+            "updated_at": "2026-01-02T03:04:05Z",
+            "user": { "login": "mona" },
+            "labels": [{ "name": "bug", "color": "d73a4a" }],
+            "comments": 2
+        })
+    }
+
+    #[test]
+    fn test_issues_skip_pull_requests_and_change_state() {
+        let mut pr = issue_json(8, "A pull request", "open");
+        pr["pull_request"] = json!({ "url": "https://api.github.com/repos/octo/app/pulls/8" });
+        let mock = Arc::new(
+            MockTransport::new()
+                .on(
+                    Method::Get,
+                    "/repos/octo/app/issues",
+                    json_response(200, json!([issue_json(9, "Crash on start", "open"), pr])),
+                )
+                .on(
+                    Method::Patch,
+                    "/repos/octo/app/issues/9",
+                    json_response(200, issue_json(9, "Crash on start", "closed")),
+                ),
+        );
+
+        mock.serve(|| {
+            let issues = list_issues_in("octo", "app", "tok", "open").unwrap();
+            let numbers: Vec<u64> = issues.iter().map(|i| i.number).collect();
+            assert_eq!(numbers, [9]);
+            assert_eq!(issues[0].labels[0].name, "bug");
+            let closed = set_issue_state_in("octo", "app", "tok", 9, false).unwrap();
+            assert_eq!(closed.state, "closed");
+        });
+
+        let requests = mock.requests();
+        assert!(requests[0].url.contains("state=open"));
+        assert_eq!(requests[1].body_text(), r#"{"state":"closed"}"#);
+    }
+
+    #[test]
+    fn test_workflow_runs_for_a_branch_and_rerun() {
+        let run = json!({
+            "id": 7, "name": "CI", "status": "completed", "conclusion": "failure",
+            "head_branch": "feature/ci", "event": "push",
+            "created_at": "2024-05-01T10:00:00Z", "updated_at": "2024-05-01T10:05:00Z",
+            "html_url": "https://github.com/octo/app/actions/runs/7", "run_number": 42,
+            "display_title": "Fix the build"
+        });
+        let mock = Arc::new(
+            MockTransport::new()
+                .on(
+                    Method::Get,
+                    "/repos/octo/app/actions/runs",
+                    json_response(200, json!({ "total_count": 1, "workflow_runs": [run] })),
+                )
+                .on(
+                    Method::Post,
+                    "/repos/octo/app/actions/runs/7/rerun-failed-jobs",
+                    Response::new(201, ""),
+                ),
+        );
+
+        mock.serve(|| {
+            let runs = list_workflow_runs_in("octo", "app", "tok", Some("feature/ci")).unwrap();
+            assert_eq!(runs.workflow_runs[0].run_number, 42);
+            rerun_failed_jobs_in("octo", "app", "tok", 7).unwrap();
+        });
+
+        let requests = mock.requests();
+        assert!(
+            requests[0]
+                .url
+                .ends_with("?per_page=30&branch=feature%2Fci")
+        );
+        assert_eq!(
+            requests[1].url,
+            "https://api.github.com/repos/octo/app/actions/runs/7/rerun-failed-jobs"
+        );
+    }
+
+    #[test]
+    fn test_issue_filter_and_labels() {
+        let issue: Issue =
+            serde_json::from_value(issue_json(12, "Crash on start", "open")).unwrap();
+        assert!(issue.matches("crash"));
+        assert!(issue.matches("BUG start"));
+        assert!(issue.matches("#12 @Mona"));
+        assert!(!issue.matches("#1"));
+        assert!(!issue.matches("crash docs"));
+
+        let known = vec![
+            GhLabel {
+                name: "bug".to_string(),
+                color: String::new(),
+            },
+            GhLabel {
+                name: "Good First Issue".to_string(),
+                color: String::new(),
+            },
+        ];
+        assert_eq!(
+            match_labels("good first issue, Bug,bug", &known).unwrap(),
+            ["Good First Issue", "bug"]
+        );
+        assert!(match_labels(" , ", &known).unwrap().is_empty());
+        assert_eq!(
+            match_labels("bug, wontfix", &known)
+                .unwrap_err()
+                .to_string(),
+            "No label 'wontfix' in this repository"
+        );
+    }
+
+    #[test]
+    fn test_api_errors_explain_rate_limits() {
+        let mock = Arc::new(
+            MockTransport::new()
+                .on(
+                    Method::Get,
+                    "/user",
+                    json_response(
+                        403,
+                        json!({ "message": "API rate limit exceeded for user" }),
+                    )
+                    .with_header("X-RateLimit-Remaining", "0")
+                    .with_header("X-RateLimit-Reset", "1792108800"),
+                )
+                .on(
+                    Method::Get,
+                    "/user",
+                    json_response(429, json!({})).with_header("Retry-After", "30"),
+                )
+                .on(
+                    Method::Get,
+                    "/user",
+                    json_response(401, json!({ "message": "Bad credentials" })),
+                )
+                .on(Method::Get, "/user", Response::new(502, "<html>")),
+        );
+
+        let errors: Vec<anyhow::Error> =
+            mock.serve(|| (0..4).map(|_| get_username("tok").unwrap_err()).collect());
+        // Only a rejected token is reported as one, for `zit pr`'s exit code
+        let unauthorized: Vec<bool> = errors
+            .iter()
+            .map(|e| e.downcast_ref::<Unauthorized>().is_some())
+            .collect();
+        assert_eq!(unauthorized, [false, false, true, false]);
+        let errors: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+        assert!(
+            errors[0].starts_with("GitHub API rate limit exceeded — try again at "),
+            "{}",
+            errors[0]
+        );
+        assert_eq!(
+            errors[1],
+            "GitHub API rate limit exceeded — try again in 30s"
+        );
+        assert_eq!(errors[2], "Bad credentials");
+        assert_eq!(errors[3], "GitHub returned HTTP 502");
+    }
 }
@@ -0,0 +1,270 @@
+//! Author identity checks — find commits authored with an email other than the
+//! configured `user.email`, and rewrite the unpushed ones with `--reset-author`.
+
+use super::runner::run_git;
+use anyhow::Result;
+use std::collections::HashSet;
+
+/// Number of recent commits checked for a mismatching author.
+pub const SCAN_LIMIT: usize = 200;
+
+/// The identity git uses for new commits (`user.name` / `user.email`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Identity {
+    pub name: String,
+    pub email: String,
+}
+
+/// A commit whose author email differs from the configured one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuthorMismatch {
+    pub hash: String,
+    pub short_hash: String,
+    pub author_name: String,
+    pub author_email: String,
+    pub subject: String,
+    /// Reachable from a remote-tracking branch, so rewriting it needs a force push.
+    pub pushed: bool,
+}
+
+/// Result of scanning recent commits on HEAD.
+#[derive(Debug, Clone)]
+pub struct IdentityReport {
+    pub identity: Identity,
+    /// Number of commits scanned.
+    pub scanned: usize,
+    /// Mismatching commits, newest first.
+    pub mismatches: Vec<AuthorMismatch>,
+}
+
+impl IdentityReport {
+    pub fn unpushed_count(&self) -> usize {
+        self.mismatches.iter().filter(|m| !m.pushed).count()
+    }
+
+    pub fn pushed_count(&self) -> usize {
+        self.mismatches.iter().filter(|m| m.pushed).count()
+    }
+
+    /// Oldest mismatching commit that has not been pushed — the rewrite starts here.
+    pub fn oldest_unpushed(&self) -> Option<&AuthorMismatch> {
+        self.mismatches.iter().rev().find(|m| !m.pushed)
+    }
+}
+
+/// Read the configured identity. Fails if `user.email` is not set.
+pub fn configured_identity() -> Result<Identity> {
+    let email = run_git(&["config", "user.email"])
+        .map(|s| s.trim().to_string())
+        .unwrap_or_default();
+    if email.is_empty() {
+        anyhow::bail!("user.email is not configured — run: git config user.email you@example.com");
+    }
+    let name = run_git(&["config", "user.name"])
+        .map(|s| s.trim().to_string())
+        .unwrap_or_default();
+    Ok(Identity { name, email })
+}
+
+/// Scan the last `limit` commits on HEAD for authors not matching the configured email.
+pub fn check_recent_commits(limit: usize) -> Result<IdentityReport> {
+    let identity = configured_identity()?;
+    let count = format!("-{}", limit);
+    let log = run_git(&[
+        "log",
+        &count,
+        "--format=%H\x1f%h\x1f%an\x1f%ae\x1f%s",
+        "HEAD",
+    ])?;
+    // Commits not reachable from any remote-tracking branch are unpublished
+    let unpushed: HashSet<String> = run_git(&["rev-list", "HEAD", "--not", "--remotes"])
+        .unwrap_or_default()
+        .lines()
+        .map(|l| l.trim().to_string())
+        .collect();
+
+    Ok(IdentityReport {
+        scanned: log.lines().filter(|l| !l.trim().is_empty()).count(),
+        mismatches: parse_mismatches(&log, &identity.email, &unpushed),
+        identity,
+    })
+}
+
+/// Parse `git log --format=%H%x1f%h%x1f%an%x1f%ae%x1f%s` output, keeping commits
+/// whose author email is not `email` (compared case-insensitively).
+fn parse_mismatches(log: &str, email: &str, unpushed: &HashSet<String>) -> Vec<AuthorMismatch> {
+    log.lines()
+        .filter_map(|line| {
+            let parts: Vec<&str> = line.trim().splitn(5, '\x1f').collect();
+            if parts.len() < 5 || parts[3].eq_ignore_ascii_case(email) {
+                return None;
+            }
+            Some(AuthorMismatch {
+                hash: parts[0].to_string(),
+                short_hash: parts[1].to_string(),
+                author_name: parts[2].to_string(),
+                author_email: parts[3].to_string(),
+                subject: parts[4].to_string(),
+                pushed: !unpushed.contains(parts[0]),
+            })
+        })
+        .collect()
+}
+
+/// Shell command run after each replayed commit: reset the author unless it
+/// already matches `email`, so correctly attributed commits keep their metadata.
+/// Emails compare ignoring ASCII case, as in [`parse_mismatches`]. The author
+/// date is kept, and hooks are skipped so they can neither stop the rebase
+/// halfway nor rewrite the messages.
+fn reset_author_exec(email: &str) -> Result<String> {
+    if email.contains('\'') {
+        anyhow::bail!("Cannot rewrite with an email containing a quote: {}", email);
+    }
+    Ok(format!(
+        "[ \"$(git log -1 --format=%ae | tr A-Z a-z)\" = '{}' ] || git commit --amend --no-edit --no-verify --reset-author --date=\"$(git log -1 --format=%aD)\"",
+        email.to_ascii_lowercase()
+    ))
+}
+
+/// Rewrite the unpushed commits from the oldest mismatch up to HEAD so they are
+/// authored by the configured identity. Pushed commits are never touched.
+/// Returns the number of commits replayed.
+pub fn rewrite_unpushed_authors(report: &IdentityReport) -> Result<usize> {
+    let Some(oldest) = report.oldest_unpushed() else {
+        anyhow::bail!("No unpushed commits with a different author email");
+    };
+    let exec = reset_author_exec(&report.identity.email)?;
+
+    // Parent of the oldest commit; a root commit has none and needs --root
+    let base = run_git(&[
+        "rev-parse",
+        "--verify",
+        "--quiet",
+        &format!("{}^", oldest.hash),
+    ])
+    .ok()
+    .map(|s| s.trim().to_string())
+    .filter(|s| !s.is_empty());
+    let range = match base {
+        Some(ref b) => format!("{}..HEAD", b),
+        None => "HEAD".to_string(),
+    };
+
+    // A plain rebase would flatten merges in the range
+    let merges = run_git(&["rev-list", "--merges", &range])?;
+    if !merges.trim().is_empty() {
+        anyhow::bail!("The commits to rewrite include merge commits — rewrite them manually");
+    }
+    let replayed = run_git(&["rev-list", "--count", &range])?
+        .trim()
+        .parse()
+        .unwrap_or(0);
+
+    let mut args = vec![
+        "-c",
+        "sequence.editor=true",
+        "rebase",
+        "--autostash",
+        "--exec",
+        exec.as_str(),
+    ];
+    match base {
+        Some(ref b) => args.push(b),
+        None => args.push("--root"),
+    }
+    if let Err(e) = run_git(&args) {
+        let _ = run_git(&["rebase", "--abort"]);
+        anyhow::bail!("Author rewrite failed and was rolled back: {}", e);
+    }
+    Ok(replayed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_mismatches() {
+        let log = "aaa\x1fa1\x1fMe\x1fme@work.com\x1fgood\n\
+                   bbb\x1fb1\x1fMe\x1fme@home.org\x1fwrong: email\n\
+                   ccc\x1fc1\x1fMe\x1fME@WORK.COM\x1fcase differs\n\
+                   ddd\x1fd1\x1fMe\x1fold@home.org\x1fpushed one\n";
+        let unpushed: HashSet<String> = ["aaa", "bbb", "ccc"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let found = parse_mismatches(log, "me@work.com", &unpushed);
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[0].short_hash, "b1");
+        assert_eq!(found[0].subject, "wrong: email");
+        assert!(!found[0].pushed);
+        assert!(found[1].pushed);
+    }
+
+    #[test]
+    fn test_report_counts_and_oldest_unpushed() {
+        let m = |hash: &str, pushed: bool| AuthorMismatch {
+            hash: hash.to_string(),
+            short_hash: hash.to_string(),
+            author_name: String::new(),
+            author_email: "old@x".to_string(),
+            subject: String::new(),
+            pushed,
+        };
+        let report = IdentityReport {
+            identity: Identity {
+                name: "Me".to_string(),
+                email: "me@x".to_string(),
+            },
+            scanned: 10,
+            mismatches: vec![m("new", false), m("mid", false), m("old", true)],
+        };
+        assert_eq!(report.unpushed_count(), 2);
+        assert_eq!(report.pushed_count(), 1);
+        assert_eq!(report.oldest_unpushed().unwrap().hash, "mid");
+    }
+
+    #[test]
+    fn test_reset_author_exec() {
+        let cmd = reset_author_exec("Me@X.com").unwrap();
+        assert!(cmd.contains("= 'me@x.com' ]"));
+        assert!(cmd.contains("--no-verify --reset-author --date="));
+        assert!(reset_author_exec("o'neil@x.com").is_err());
+    }
+
+    #[test]
+    fn test_reset_author_exec_ignores_case() {
+        let dir = tempfile::tempdir().unwrap();
+        let git = |args: &[&str]| {
+            let out = std::process::Command::new("git")
+                .current_dir(dir.path())
+                .args(args)
+                .output()
+                .unwrap();
+            assert!(out.status.success(), "git {:?}", args);
+            String::from_utf8_lossy(&out.stdout).trim().to_string()
+        };
+        git(&["init", "-q"]);
+        git(&["config", "user.name", "Me"]);
+        git(&["config", "user.email", "me@work.com"]);
+        git(&[
+            "-c",
+            "user.email=ME@WORK.COM",
+            "commit",
+            "-q",
+            "--allow-empty",
+            "-m",
+            "Counted as mine",
+        ]);
+        let before = git(&["rev-parse", "HEAD"]);
+
+        let cmd = reset_author_exec("me@work.com").unwrap();
+        let status = std::process::Command::new("sh")
+            .current_dir(dir.path())
+            .args(["-c", &cmd])
+            .status()
+            .unwrap();
+        assert!(status.success());
+        assert_eq!(git(&["rev-parse", "HEAD"]), before, "left as it was");
+    }
+}
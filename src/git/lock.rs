@@ -0,0 +1,157 @@
+//! Noticing other processes working on the same repository: other zit
+//! instances, which each register a file under `.git/zit-instances/`, and git
+//! commands holding `.git/index.lock`.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use super::runner::run_git;
+
+/// Directory under the git dir with one file per running zit, named by pid.
+const INSTANCES_DIR: &str = "zit-instances";
+
+/// How long `index.lock` has to exist before it's reported. zit's own commands
+/// take it briefly all the time.
+const INDEX_LOCK_GRACE: Duration = Duration::from_secs(1);
+
+fn git_dir() -> Option<&'static Path> {
+    static GIT_DIR: OnceLock<Option<PathBuf>> = OnceLock::new();
+    GIT_DIR
+        .get_or_init(|| {
+            run_git(&["rev-parse", "--absolute-git-dir"])
+                .ok()
+                .map(|dir| PathBuf::from(dir.trim()))
+        })
+        .as_deref()
+}
+
+/// This process's registration on the repository, removed when dropped.
+pub struct Instance {
+    path: PathBuf,
+}
+
+impl Drop for Instance {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+/// Register this zit process on the repository, so other instances can warn
+/// about it.
+pub fn register() -> Option<Instance> {
+    let dir = git_dir()?.join(INSTANCES_DIR);
+    fs::create_dir_all(&dir).ok()?;
+    let path = dir.join(std::process::id().to_string());
+    fs::write(&path, "").ok()?;
+    Some(Instance { path })
+}
+
+/// Pids of the other zit processes open on this repository.
+pub fn other_instances() -> Vec<u32> {
+    git_dir()
+        .map(|dir| live_instances(&dir.join(INSTANCES_DIR), std::process::id()))
+        .unwrap_or_default()
+}
+
+/// Registered pids in `dir` other than `own`. Files left behind by processes
+/// that died (e.g. killed with SIGKILL) are cleaned up.
+fn live_instances(dir: &Path, own: u32) -> Vec<u32> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut pids = Vec::new();
+    for entry in entries.flatten() {
+        let Some(pid) = entry
+            .file_name()
+            .to_str()
+            .and_then(|name| name.parse::<u32>().ok())
+        else {
+            continue;
+        };
+        if pid == own {
+            continue;
+        }
+        if pid_alive(pid) {
+            pids.push(pid);
+        } else {
+            let _ = fs::remove_file(entry.path());
+        }
+    }
+    pids.sort_unstable();
+    pids
+}
+
+fn pid_alive(pid: u32) -> bool {
+    #[cfg(unix)]
+    {
+        let Ok(pid) = libc::pid_t::try_from(pid) else {
+            return false;
+        };
+        // Signal 0 only checks that the process exists
+        let exists = unsafe { libc::kill(pid, 0) } == 0;
+        exists || std::io::Error::last_os_error().raw_os_error() == Some(libc::EPERM)
+    }
+    #[cfg(not(unix))]
+    {
+        // No cheap check; keep the registration
+        let _ = pid;
+        true
+    }
+}
+
+/// Whether another git process has held `.git/index.lock` for a while.
+pub fn index_locked() -> bool {
+    git_dir()
+        .and_then(|dir| fs::metadata(dir.join("index.lock")).ok())
+        .and_then(|meta| meta.modified().ok())
+        .and_then(|modified| modified.elapsed().ok())
+        .is_some_and(|age| age >= INDEX_LOCK_GRACE)
+}
+
+/// Banner text when something else is working on the repository.
+pub fn warning() -> Option<String> {
+    if index_locked() {
+        return Some(
+            "Another git process holds .git/index.lock — changes will wait for it".to_string(),
+        );
+    }
+    let others = other_instances();
+    if others.is_empty() {
+        return None;
+    }
+    let pids: Vec<String> = others.iter().map(u32::to_string).collect();
+    Some(format!(
+        "Another zit is open on this repository (pid {}) — its changes show up here on refresh",
+        pids.join(", ")
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_live_instances_skips_own_and_removes_stale() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let own = std::process::id();
+        // Past any real pid_max, so never a live process
+        let dead = 2_000_000_000u32;
+        for name in [own.to_string(), dead.to_string(), "1".to_string()] {
+            fs::write(dir.path().join(name), "").unwrap();
+        }
+        fs::write(dir.path().join("notes.txt"), "").unwrap();
+
+        let live = live_instances(dir.path(), own);
+        assert!(!live.contains(&own) && !live.contains(&dead));
+        #[cfg(unix)]
+        {
+            // pid 1 (init) is always running
+            assert_eq!(live, vec![1]);
+            assert!(!dir.path().join(dead.to_string()).exists());
+        }
+        assert!(dir.path().join(own.to_string()).exists());
+        assert!(dir.path().join("notes.txt").exists());
+    }
+}
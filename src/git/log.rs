@@ -14,20 +14,59 @@ pub struct CommitEntry {
     pub short_hash: String,
     pub message: String,
     pub author: String,
-    pub date: String, // relative date like "2 hours ago"
-    #[allow(dead_code)]
-    pub date_iso: String, // ISO format for sorting
+    pub author_email: String,
+    pub date: String,     // relative date like "2 hours ago"
+    pub date_iso: String, // ISO format for sorting and local formatting
     #[allow(dead_code)]
     pub parents: Vec<String>,
-    pub refs: String,  // decorated refs (HEAD -> main, origin/main, tag: v1.0)
+    pub refs: String, // decorated refs (HEAD -> refs/heads/main, tag: refs/tags/v1.0)
     pub graph: String, // graph characters for this line
 }
 
-const LOG_FORMAT: &str = "%H\x1f%h\x1f%s\x1f%an\x1f%ar\x1f%aI\x1f%P\x1f%D";
+impl CommitEntry {
+    /// Branches, tags and HEAD pointing at this commit.
+    pub fn decorations(&self) -> Vec<Decoration> {
+        parse_decorations(&self.refs)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RefKind {
+    /// Detached HEAD
+    Head,
+    Branch,
+    Remote,
+    Tag,
+    Other,
+}
+
+/// One ref from a commit's `%D` decoration.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Decoration {
+    pub kind: RefKind,
+    /// Short name, e.g. `main`, `origin/main`, `v1.0`
+    pub name: String,
+    /// HEAD points at this ref (or is detached here, for `RefKind::Head`).
+    pub is_head: bool,
+}
+
+pub const LOG_FORMAT: &str = "%H\x1f%h\x1f%s\x1f%an\x1f%ar\x1f%aI\x1f%P\x1f%D\x1f%ae";
 const SEPARATOR: char = '\x1f';
 
-/// Fetch commit log entries with optional pagination.
-pub fn get_log(count: usize, skip: usize, branch: Option<&str>) -> Result<Vec<CommitEntry>> {
+/// Fetch commit log entries with optional pagination. `revs` selects what to walk
+/// (e.g. a branch name, or `--branches --tags`); empty means HEAD.
+pub fn get_log(count: usize, skip: usize, revs: &[&str]) -> Result<Vec<CommitEntry>> {
+    super::backend::current().log(count, skip, revs)
+}
+
+/// `get_log` by parsing `git log --graph`, limited to commits touching `scope`
+/// when given.
+pub(super) fn log_from_cli(
+    count: usize,
+    skip: usize,
+    revs: &[&str],
+    scope: Option<&str>,
+) -> Result<Vec<CommitEntry>> {
     let count_str = format!("-{}", count);
     let skip_str = format!("--skip={}", skip);
     let format_str = format!("--format={}", LOG_FORMAT);
@@ -40,23 +79,36 @@ pub fn get_log(count: usize, skip: usize, branch: Option<&str>) -> Result<Vec<Co
         &format_str,
         "--graph",
         "--color=never",
+        // Full ref names tell local branches apart from remote ones
+        "--decorate=full",
     ];
-
-    if let Some(b) = branch {
-        args.push(b);
-    }
+    args.extend(scoped_revs(revs, scope));
 
     let output = run_git(&args)?;
     let entries = parse_log_output(&output);
     Ok(entries)
 }
 
+/// `revs` limited to `scope`. A path already given after `--` (a single
+/// file's history) is narrower, so the scope isn't added to it: git would
+/// list commits touching either path.
+fn scoped_revs<'a>(revs: &[&'a str], scope: Option<&'a str>) -> Vec<&'a str> {
+    let mut args = revs.to_vec();
+    if let Some(scope) = scope
+        && !revs.contains(&"--")
+    {
+        args.extend(["--", scope]);
+    }
+    args
+}
+
 /// Get the last N commits (shorthand for dashboard use).
 pub fn get_recent_commits(count: usize) -> Result<Vec<CommitEntry>> {
-    get_log(count, 0, None)
+    get_log(count, 0, &[])
 }
 
-fn parse_log_output(output: &str) -> Vec<CommitEntry> {
+/// Parse `git log --graph` output written with `LOG_FORMAT`.
+pub fn parse_log_output(output: &str) -> Vec<CommitEntry> {
     let mut entries = Vec::new();
     let re = commit_regex();
 
@@ -79,6 +131,7 @@ fn parse_log_output(output: &str) -> Vec<CommitEntry> {
                 short_hash: parts[1].to_string(),
                 message: parts[2].to_string(),
                 author: parts[3].to_string(),
+                author_email: parts.get(8).unwrap_or(&"").to_string(),
                 date: parts[4].to_string(),
                 date_iso: parts[5].to_string(),
                 parents,
@@ -94,6 +147,7 @@ fn parse_log_output(output: &str) -> Vec<CommitEntry> {
                     short_hash: String::new(),
                     message: String::new(),
                     author: String::new(),
+                    author_email: String::new(),
                     date: String::new(),
                     date_iso: String::new(),
                     parents: Vec::new(),
@@ -107,12 +161,59 @@ fn parse_log_output(output: &str) -> Vec<CommitEntry> {
     entries
 }
 
+/// Commits selected by `revs`, newest first, without graph lines.
+pub fn get_commits(revs: &[&str], count: usize) -> Result<Vec<CommitEntry>> {
+    let count_str = format!("-{}", count);
+    let format_str = format!("--format={}", LOG_FORMAT);
+    let mut args = vec!["log", &count_str, &format_str, "--decorate=full"];
+    args.extend_from_slice(revs);
+    Ok(parse_log_output(&run_git(&args)?))
+}
+
+/// The upstream of `branch`, e.g. `origin/main`; `HEAD` for the current branch.
+pub fn upstream_of(branch: &str) -> Option<String> {
+    let spec = format!("{}@{{u}}", branch);
+    run_git(&["rev-parse", "--abbrev-ref", "--symbolic-full-name", &spec])
+        .ok()
+        .map(|u| u.trim().to_string())
+        .filter(|u| !u.is_empty())
+}
+
+/// Revision range of the commits a push would send: everything after the upstream,
+/// or everything not on any remote when the branch has no upstream yet.
+pub fn outgoing_range() -> Vec<String> {
+    outgoing_range_of("HEAD")
+}
+
+/// `outgoing_range` for `branch` instead of `HEAD`.
+pub fn outgoing_range_of(branch: &str) -> Vec<String> {
+    match upstream_of(branch) {
+        Some(u) => vec![format!("{}..{}", u, branch)],
+        None => vec![
+            branch.to_string(),
+            "--not".to_string(),
+            "--remotes".to_string(),
+        ],
+    }
+}
+
 /// Get the total number of commits in the current branch.
 pub fn commit_count() -> Result<usize> {
     let output = run_git(&["rev-list", "--count", "HEAD"])?;
     Ok(output.trim().parse().unwrap_or(0))
 }
 
+/// Get the subjects of the last N commits on HEAD, newest first.
+pub fn recent_subjects(count: usize) -> Result<Vec<String>> {
+    let count_str = format!("-{}", count);
+    let output = run_git(&["log", &count_str, "--format=%s"])?;
+    Ok(output
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| l.to_string())
+        .collect())
+}
+
 /// Search commits by message text.
 pub fn search_commits(query: &str, count: usize) -> Result<Vec<CommitEntry>> {
     let count_str = format!("-{}", count);
@@ -121,14 +222,75 @@ pub fn search_commits(query: &str, count: usize) -> Result<Vec<CommitEntry>> {
 
     // Note: search doesn't use --graph usually, but if we want consistent return type,
     // we can parse it. Without --graph, the regex matches at index 0, so graph is empty string.
-    let output = run_git(&["log", &count_str, &format_str, &grep_str, "-i"])?;
+    let output = run_git(&[
+        "log",
+        &count_str,
+        &format_str,
+        &grep_str,
+        "-i",
+        "--decorate=full",
+    ])?;
     Ok(parse_log_output(&output))
 }
 
+/// Parse a `%D` decoration list, as printed with `--decorate=full`. Short names
+/// without a `refs/` prefix are taken to be local branches.
+pub fn parse_decorations(refs: &str) -> Vec<Decoration> {
+    refs.split(", ")
+        .filter(|r| !r.is_empty())
+        .map(|r| {
+            if r == "HEAD" {
+                return Decoration {
+                    kind: RefKind::Head,
+                    name: "HEAD".to_string(),
+                    is_head: true,
+                };
+            }
+            let (is_head, r) = match r.strip_prefix("HEAD -> ") {
+                Some(rest) => (true, rest),
+                None => (false, r),
+            };
+            let r = r.strip_prefix("tag: ").unwrap_or(r);
+            let (kind, name) = if let Some(name) = r.strip_prefix("refs/tags/") {
+                (RefKind::Tag, name)
+            } else if let Some(name) = r.strip_prefix("refs/heads/") {
+                (RefKind::Branch, name)
+            } else if let Some(name) = r.strip_prefix("refs/remotes/") {
+                (RefKind::Remote, name)
+            } else if r.starts_with("refs/") {
+                (RefKind::Other, r)
+            } else {
+                (RefKind::Branch, r)
+            };
+            Decoration {
+                kind,
+                name: name.to_string(),
+                is_head,
+            }
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_scope_is_not_added_to_a_path() {
+        assert_eq!(
+            scoped_revs(&["HEAD"], Some("services/api")),
+            ["HEAD", "--", "services/api"]
+        );
+        assert_eq!(
+            scoped_revs(
+                &["HEAD", "--", "services/api/main.rs"],
+                Some("services/api")
+            ),
+            ["HEAD", "--", "services/api/main.rs"]
+        );
+        assert_eq!(scoped_revs(&["--branches"], None), ["--branches"]);
+    }
+
     #[test]
     fn test_parse_log_output() {
         // Hash must be exactly 40 chars for regex to match correctly at start
@@ -139,9 +301,34 @@ mod tests {
         assert_eq!(entries[0].message, "feat: add login");
         assert_eq!(entries[0].author, "John");
         assert_eq!(entries[0].refs, "HEAD -> main");
+        assert_eq!(entries[0].author_email, "");
         assert_eq!(entries[0].graph, "* ");
     }
 
+    #[test]
+    fn test_parse_decorations() {
+        let decorations = parse_decorations(
+            "HEAD -> refs/heads/main, tag: refs/tags/v1.0, refs/remotes/origin/main, refs/stash",
+        );
+        let kinds: Vec<(RefKind, &str, bool)> = decorations
+            .iter()
+            .map(|d| (d.kind, d.name.as_str(), d.is_head))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (RefKind::Branch, "main", true),
+                (RefKind::Tag, "v1.0", false),
+                (RefKind::Remote, "origin/main", false),
+                (RefKind::Other, "refs/stash", false),
+            ]
+        );
+        let detached = parse_decorations("HEAD, refs/heads/feature/x");
+        assert_eq!(detached[0].kind, RefKind::Head);
+        assert_eq!(detached[1].name, "feature/x");
+        assert!(parse_decorations("").is_empty());
+    }
+
     #[test]
     fn test_parse_graph_only_line() {
         let sample = "| \\ \n";
@@ -159,7 +346,7 @@ mod tests {
 
     #[test]
     fn test_parse_multiple_commits() {
-        let line1 = "* abc123def456abc123def456abc123def456abc1\x1fabc123d\x1ffeat: first\x1fAlice\x1f1 hour ago\x1f2026-01-01T00:00:00+00:00\x1f\x1fHEAD -> main\n";
+        let line1 = "* abc123def456abc123def456abc123def456abc1\x1fabc123d\x1ffeat: first\x1fAlice\x1f1 hour ago\x1f2026-01-01T00:00:00+00:00\x1f\x1fHEAD -> main\x1falice@example.com\n";
         let line2 = "* def456abc123def456abc123def456abc123def4\x1fdef456a\x1ffix: second\x1fBob\x1f2 hours ago\x1f2026-01-01T00:00:00+00:00\x1f\x1f\n";
         let sample = format!("{}{}", line1, line2);
         let entries = parse_log_output(&sample);
@@ -168,6 +355,7 @@ mod tests {
         assert_eq!(entries[1].message, "fix: second");
         assert_eq!(entries[0].author, "Alice");
         assert_eq!(entries[1].author, "Bob");
+        assert_eq!(entries[0].author_email, "alice@example.com");
     }
 
     #[test]
@@ -1,18 +1,19 @@
 //! Merge conflict detection, parsing, and resolution helpers.
 
-use super::runner::run_git;
-use anyhow::{bail, Result};
+use super::runner::{run_git, run_git_with_env};
+use anyhow::{Result, bail};
 use std::fs;
 use std::path::Path;
 
 // ─── Types ─────────────────────────────────────────────────────
 
 /// Describes which kind of merge operation is currently in progress.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum MergeType {
     Merge,
     Rebase,
     CherryPick,
+    Revert,
 }
 
 impl std::fmt::Display for MergeType {
@@ -21,10 +22,102 @@ impl std::fmt::Display for MergeType {
             MergeType::Merge => write!(f, "merge"),
             MergeType::Rebase => write!(f, "rebase"),
             MergeType::CherryPick => write!(f, "cherry-pick"),
+            MergeType::Revert => write!(f, "revert"),
+        }
+    }
+}
+
+impl MergeType {
+    /// Capitalized name for titles, e.g. "Cherry-pick".
+    pub fn title(&self) -> &'static str {
+        match self {
+            MergeType::Merge => "Merge",
+            MergeType::Rebase => "Rebase",
+            MergeType::CherryPick => "Cherry-pick",
+            MergeType::Revert => "Revert",
+        }
+    }
+
+    /// What the "current" side (`<<<<<<<`) of a conflict holds. During a
+    /// rebase that's the branch being rebased onto, not your work.
+    pub fn ours_label(&self) -> &'static str {
+        match self {
+            MergeType::Rebase => "Upstream (rebasing onto)",
+            _ => "Current (HEAD)",
+        }
+    }
+
+    /// What the "incoming" side (`>>>>>>>`) of a conflict holds.
+    pub fn theirs_label(&self) -> &'static str {
+        match self {
+            MergeType::Merge => "Incoming branch",
+            MergeType::Rebase => "Your commit (being replayed)",
+            MergeType::CherryPick => "Picked commit",
+            MergeType::Revert => "Reverted change",
+        }
+    }
+
+    /// Git arguments that finish the current step once every conflict is
+    /// resolved and staged.
+    pub fn continue_args(&self) -> Vec<&'static str> {
+        match self {
+            MergeType::Merge => vec!["commit", "--no-edit"],
+            MergeType::Rebase => vec!["rebase", "--continue"],
+            MergeType::CherryPick => vec!["cherry-pick", "--continue"],
+            MergeType::Revert => vec!["revert", "--continue"],
+        }
+    }
+
+    /// The continue command as a user would type it.
+    pub fn continue_command(&self) -> String {
+        format!("git {}", self.continue_args().join(" "))
+    }
+
+    /// What continuing does, for the confirmation prompt.
+    pub fn continue_prompt(&self) -> &'static str {
+        match self {
+            MergeType::Merge => "Commit the merge (git commit --no-edit)?",
+            MergeType::Rebase => {
+                "Commit this step and replay the remaining commits (git rebase --continue)?"
+            }
+            MergeType::CherryPick => {
+                "Commit the picked change and pick any remaining commits (git cherry-pick --continue)?"
+            }
+            MergeType::Revert => {
+                "Commit the revert and revert any remaining commits (git revert --continue)?"
+            }
+        }
+    }
+
+    /// What aborting throws away, for the confirmation prompt.
+    pub fn abort_prompt(&self) -> &'static str {
+        match self {
+            MergeType::Merge => {
+                "Discard the merge and every resolution so far (git merge --abort)?"
+            }
+            MergeType::Rebase => {
+                "Stop the rebase and put the branch back where it started, dropping resolutions (git rebase --abort)?"
+            }
+            MergeType::CherryPick => {
+                "Stop cherry-picking and return to the commit before it started (git cherry-pick --abort)?"
+            }
+            MergeType::Revert => {
+                "Stop reverting and return to the commit before it started (git revert --abort)?"
+            }
         }
     }
 }
 
+/// Where `continue_merge` left the repository.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ContinueOutcome {
+    /// The operation is complete.
+    Finished(MergeType),
+    /// A rebase or multi-commit pick/revert stopped again, usually on the next
+    /// commit's conflicts.
+    Stopped(MergeType),
+}
+
 /// The current merge state of the repository (if any).
 #[derive(Debug, Clone)]
 #[allow(dead_code)]
@@ -54,6 +147,27 @@ pub struct ConflictRegion {
     pub incoming_label: String,
 }
 
+/// How our branch and theirs have diverged, as input for the merge-strategy advisor.
+#[derive(Debug, Clone, Default)]
+pub struct BranchComparison {
+    /// Commits on our side that are not on theirs.
+    pub ahead: usize,
+    /// Commits on their side that are not on ours.
+    pub behind: usize,
+    /// Most recent commits unique to our side ("<short hash> <subject>").
+    pub ours_commits: Vec<String>,
+    /// Most recent commits unique to their side ("<short hash> <subject>").
+    pub theirs_commits: Vec<String>,
+}
+
+/// A leftover conflict marker (`<<<<<<<` or `>>>>>>>`) found in a file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MarkerHit {
+    pub path: String,
+    /// 1-based line number.
+    pub line: usize,
+}
+
 /// Parsed conflict data for a single file.
 #[derive(Debug, Clone)]
 #[allow(dead_code)]
@@ -118,6 +232,19 @@ pub fn get_merge_state() -> Option<MergeState> {
         });
     }
 
+    // Check for revert in progress
+    let revert_head = Path::new(git_dir).join("REVERT_HEAD");
+    if revert_head.exists() {
+        let reverted = fs::read_to_string(&revert_head)
+            .ok()
+            .map(|s| s.trim().to_string());
+        return Some(MergeState {
+            merge_type: MergeType::Revert,
+            head_name,
+            merge_head: reverted,
+        });
+    }
+
     None
 }
 
@@ -215,8 +342,93 @@ pub fn parse_conflict_markers(content: &str) -> Vec<ConflictRegion> {
     regions
 }
 
+/// Extract one conflict region (markers included) with up to `context_lines`
+/// unchanged lines before and after it, for sending a single region to the AI.
+pub fn region_with_context(content: &str, region: &ConflictRegion, context_lines: usize) -> String {
+    let lines: Vec<&str> = content.lines().collect();
+    let start = region
+        .start_line
+        .saturating_sub(1)
+        .saturating_sub(context_lines);
+    let end = (region.end_line + context_lines).min(lines.len());
+    lines[start.min(end)..end].join("\n")
+}
+
 // ─── Conflict Resolution ───────────────────────────────────────
 
+/// The unmerged index entries of `path` (stages 1–3, as `git ls-files -s`
+/// prints them), so its conflict can be brought back after it was staged.
+/// Empty if the file isn't conflicted.
+pub fn unmerged_entries(path: &str) -> Result<String> {
+    let output = run_git(&["ls-files", "-s", "--", path])?;
+    let entries: Vec<&str> = output
+        .lines()
+        .filter(|line| {
+            line.split_whitespace()
+                .nth(2)
+                .is_some_and(|stage| stage != "0")
+        })
+        .collect();
+    Ok(entries.join("\n"))
+}
+
+/// Mark `path` conflicted again by putting back the index entries saved by
+/// `unmerged_entries`, replacing the staged resolution.
+pub fn restore_unmerged(path: &str, entries: &str) -> Result<()> {
+    use std::io::Write;
+    use std::process::{Command, Stdio};
+
+    let Some(oid) = entries.split_whitespace().nth(1) else {
+        return Ok(());
+    };
+    // A zero id removes the stage 0 entry before the stages go back in
+    let input = format!("0 {}\t{}\n{}\n", "0".repeat(oid.len()), path, entries);
+
+    let mut child = Command::new("git")
+        .args(["update-index", "--index-info"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| anyhow::anyhow!("Failed to run git update-index: {}", e))?;
+    if let Some(ref mut stdin) = child.stdin {
+        stdin
+            .write_all(input.as_bytes())
+            .map_err(|e| anyhow::anyhow!("Failed to write index entries: {}", e))?;
+    }
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!("git update-index failed: {}", stderr.trim());
+    }
+    Ok(())
+}
+
+/// Which side of a conflict to keep.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Side {
+    Ours,
+    Theirs,
+}
+
+/// Resolve a whole file with one side's version (`git checkout --ours` or
+/// `--theirs`) and stage it. Works for binary files; if that side deleted the
+/// file, it's removed instead.
+pub fn take_side(path: &str, side: Side) -> Result<()> {
+    let (stage, flag) = match side {
+        Side::Ours => ("2", "--ours"),
+        Side::Theirs => ("3", "--theirs"),
+    };
+    let spec = format!(":{}:{}", stage, path);
+    if run_git(&["rev-parse", "-q", "--verify", &spec]).is_err() {
+        run_git(&["rm", "--quiet", "--", path])?;
+    } else {
+        run_git(&["checkout", flag, "--", path])?;
+        run_git(&["add", "--", path])?;
+    }
+    Ok(())
+}
+
 /// Resolve a conflict in a file by replacing its content and staging it.
 pub fn resolve_file(file_path: &str, resolved_content: &str) -> Result<()> {
     fs::write(file_path, resolved_content)
@@ -283,49 +495,54 @@ pub fn resolve_region(file_path: &str, region: &ConflictRegion, choice: &str) ->
 
 // ─── Merge Operations ──────────────────────────────────────────
 
-/// Abort the current merge operation.
-pub fn abort_merge() -> Result<()> {
-    match get_merge_state() {
-        Some(state) => match state.merge_type {
-            MergeType::Merge => {
-                run_git(&["merge", "--abort"])?;
-                Ok(())
-            }
-            MergeType::Rebase => {
-                run_git(&["rebase", "--abort"])?;
-                Ok(())
-            }
-            MergeType::CherryPick => {
-                run_git(&["cherry-pick", "--abort"])?;
-                Ok(())
-            }
-        },
-        None => bail!("No merge operation in progress"),
-    }
+/// Abort the merge, rebase, cherry-pick or revert in progress, returning
+/// which one it was.
+pub fn abort_merge() -> Result<MergeType> {
+    let Some(state) = get_merge_state() else {
+        bail!("No merge operation in progress");
+    };
+    let op = state.merge_type;
+    run_git(&[&op.to_string(), "--abort"])?;
+    Ok(op)
 }
 
-/// Continue the current merge operation (after resolving all conflicts).
-pub fn continue_merge() -> Result<()> {
-    match get_merge_state() {
-        Some(state) => match state.merge_type {
-            MergeType::Merge => {
-                // For merge, just commit (all conflicts must be resolved and staged)
-                run_git(&["commit", "--no-edit"])?;
-                Ok(())
-            }
-            MergeType::Rebase => {
-                run_git(&["rebase", "--continue"])?;
-                Ok(())
-            }
-            MergeType::CherryPick => {
-                run_git(&["cherry-pick", "--continue"])?;
-                Ok(())
-            }
-        },
-        None => bail!("No merge operation in progress"),
+/// Continue the operation in progress after its conflicts are resolved and
+/// staged, with the command that operation needs.
+pub fn continue_merge() -> Result<ContinueOutcome> {
+    let Some(state) = get_merge_state() else {
+        bail!("No merge operation in progress");
+    };
+    let op = state.merge_type;
+    // There's no terminal for an editor; keep the message git prepared
+    let result = run_git_with_env(&op.continue_args(), &[("GIT_EDITOR", "true")]);
+    let in_progress = get_merge_state().is_some();
+    match result {
+        Ok(_) if in_progress => Ok(ContinueOutcome::Stopped(op)),
+        Ok(_) => Ok(ContinueOutcome::Finished(op)),
+        // The next commit of a rebase or sequence hit conflicts
+        Err(_) if in_progress && has_unmerged_paths() => Ok(ContinueOutcome::Stopped(op)),
+        Err(e) if op != MergeType::Merge && left_commit_empty(&e.to_string()) => bail!(
+            "{}\nThe resolution leaves this commit with no changes — drop it with git {} --skip, \
+             or abort with git {} --abort",
+            e,
+            op,
+            op
+        ),
+        Err(e) => Err(e),
     }
 }
 
+fn has_unmerged_paths() -> bool {
+    run_git(&["diff", "--name-only", "--diff-filter=U"]).is_ok_and(|out| !out.trim().is_empty())
+}
+
+/// Whether git refused to continue because the resolved commit is empty.
+fn left_commit_empty(error: &str) -> bool {
+    ["nothing to commit", "is now empty", "No changes"]
+        .iter()
+        .any(|m| error.contains(m))
+}
+
 /// Get the merge base between HEAD and another branch/ref.
 #[allow(dead_code)]
 pub fn get_merge_base(other_ref: &str) -> Result<String> {
@@ -333,6 +550,53 @@ pub fn get_merge_base(other_ref: &str) -> Result<String> {
     Ok(output.trim().to_string())
 }
 
+/// Compare two refs: divergence counts plus up to `limit` recent commits unique to each side.
+pub fn compare_branches(ours: &str, theirs: &str, limit: usize) -> Result<BranchComparison> {
+    let counts = run_git(&[
+        "rev-list",
+        "--left-right",
+        "--count",
+        &format!("{}...{}", ours, theirs),
+    ])?;
+    let (ahead, behind) = parse_left_right_counts(&counts);
+    Ok(BranchComparison {
+        ahead,
+        behind,
+        ours_commits: unique_commits(theirs, ours, limit)?,
+        theirs_commits: unique_commits(ours, theirs, limit)?,
+    })
+}
+
+/// Commits reachable from `to` but not from `from`, newest first.
+fn unique_commits(from: &str, to: &str, limit: usize) -> Result<Vec<String>> {
+    let output = run_git(&[
+        "log",
+        "--format=%h %s",
+        &format!("--max-count={}", limit),
+        &format!("{}..{}", from, to),
+    ])?;
+    Ok(output
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| l.to_string())
+        .collect())
+}
+
+/// Parse `git rev-list --left-right --count` output ("3\t5") into (left, right).
+fn parse_left_right_counts(output: &str) -> (usize, usize) {
+    let mut parts = output.split_whitespace().map(|n| n.parse().unwrap_or(0));
+    (parts.next().unwrap_or(0), parts.next().unwrap_or(0))
+}
+
+/// Best-effort branch name for a commit (e.g. MERGE_HEAD), falling back to its short hash.
+pub fn ref_label(commit: &str) -> String {
+    run_git(&["name-rev", "--name-only", "--exclude=tags/*", commit])
+        .ok()
+        .map(|n| n.trim().to_string())
+        .filter(|n| !n.is_empty() && n != "undefined")
+        .unwrap_or_else(|| commit.chars().take(7).collect())
+}
+
 /// Get the diff between two refs to preview what a merge would bring in.
 #[allow(dead_code)]
 pub fn get_merge_preview_diff(other_ref: &str) -> Result<String> {
@@ -426,10 +690,124 @@ fn check_merge_conflicts_legacy(other_ref: &str) -> Result<Vec<String>> {
     }
 }
 
+// ─── Leftover Marker Detection ─────────────────────────────────
+
+/// Start-of-ours and end-of-theirs markers. `=======` alone is too common
+/// (Markdown/RST underlines) to flag on its own.
+const MARKER_PATTERN: &str = "^(<<<<<<<|>>>>>>>)( |$)";
+
+/// Find leftover conflict markers in `files`, as stored in the index (`tree = None`)
+/// or in a commit such as `Some("HEAD")`.
+pub fn find_conflict_markers(files: &[&str], tree: Option<&str>) -> Vec<MarkerHit> {
+    if files.is_empty() {
+        return Vec::new();
+    }
+    let mut args = vec!["grep", "-I", "-n", "-E", MARKER_PATTERN];
+    match tree {
+        Some(t) => args.push(t),
+        None => args.push("--cached"),
+    }
+    args.push("--");
+    args.extend(files);
+    // git grep exits non-zero when nothing matches
+    parse_marker_hits(&run_git(&args).unwrap_or_default(), tree)
+}
+
+/// Conflict markers in files with staged additions or modifications.
+pub fn staged_conflict_markers() -> Vec<MarkerHit> {
+    let staged =
+        run_git(&["diff", "--cached", "--name-only", "--diff-filter=ACMR"]).unwrap_or_default();
+    let files: Vec<&str> = staged
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .collect();
+    find_conflict_markers(&files, None)
+}
+
+/// Parse `git grep -n` output ("path:line:text", prefixed with "tree:" when
+/// searching a commit).
+fn parse_marker_hits(output: &str, tree: Option<&str>) -> Vec<MarkerHit> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let rest = match tree {
+                Some(t) => line.strip_prefix(t)?.strip_prefix(':')?,
+                None => line,
+            };
+            let mut parts = rest.splitn(3, ':');
+            let path = parts.next()?.to_string();
+            let line = parts.next()?.parse().ok()?;
+            Some(MarkerHit { path, line })
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_merge_type_commands() {
+        assert_eq!(MergeType::Merge.continue_args(), ["commit", "--no-edit"]);
+        for op in [MergeType::Rebase, MergeType::CherryPick, MergeType::Revert] {
+            assert_eq!(op.continue_args(), [&op.to_string(), "--continue"]);
+        }
+        assert_eq!(
+            MergeType::CherryPick.continue_command(),
+            "git cherry-pick --continue"
+        );
+        assert_eq!(
+            MergeType::Rebase.theirs_label(),
+            "Your commit (being replayed)"
+        );
+        assert!(left_commit_empty(
+            "git cherry-pick --continue failed: The previous cherry-pick is now empty"
+        ));
+        assert!(!left_commit_empty("error: could not apply d37f3af"));
+    }
+
+    #[test]
+    fn test_region_with_context() {
+        let content = "a\nb\n<<<<<<< HEAD\nours\n=======\ntheirs\n>>>>>>> feature\nc\nd\ne\n";
+        let regions = parse_conflict_markers(content);
+        assert_eq!(
+            region_with_context(content, &regions[0], 1),
+            "b\n<<<<<<< HEAD\nours\n=======\ntheirs\n>>>>>>> feature\nc"
+        );
+        // Context is clamped to the file bounds
+        assert!(region_with_context(content, &regions[0], 50).starts_with("a\nb\n<<<<<<<"));
+        assert!(region_with_context(content, &regions[0], 50).ends_with("d\ne"));
+    }
+
+    #[test]
+    fn test_parse_marker_hits() {
+        let index = "src/main.rs:12:<<<<<<< HEAD\nsrc/main.rs:20:>>>>>>> feature\n";
+        assert_eq!(
+            parse_marker_hits(index, None),
+            vec![
+                MarkerHit {
+                    path: "src/main.rs".to_string(),
+                    line: 12
+                },
+                MarkerHit {
+                    path: "src/main.rs".to_string(),
+                    line: 20
+                },
+            ]
+        );
+        let commit = "HEAD:a.txt:3:<<<<<<< ours\n";
+        assert_eq!(parse_marker_hits(commit, Some("HEAD"))[0].line, 3);
+        assert!(parse_marker_hits("", None).is_empty());
+    }
+
+    #[test]
+    fn test_parse_left_right_counts() {
+        assert_eq!(parse_left_right_counts("3\t5\n"), (3, 5));
+        assert_eq!(parse_left_right_counts("0\t12"), (0, 12));
+        assert_eq!(parse_left_right_counts(""), (0, 0));
+    }
+
     #[test]
     fn test_parse_conflict_markers_standard() {
         let content = "\
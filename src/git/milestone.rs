@@ -0,0 +1,132 @@
+//! Milestone progress — open and closed issue / pull request counts for the
+//! milestone the team is currently working towards.
+
+use anyhow::Result;
+
+use super::github_auth::{self, Milestone, MilestoneItem};
+
+/// Issue and pull request counts for one milestone.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct MilestoneProgress {
+    pub title: String,
+    /// Due date (ISO 8601), if the milestone has one.
+    pub due_on: Option<String>,
+    pub open_issues: usize,
+    pub closed_issues: usize,
+    pub open_prs: usize,
+    pub closed_prs: usize,
+}
+
+impl MilestoneProgress {
+    /// Count `items` by kind and state.
+    pub fn tally(milestone: &Milestone, items: &[MilestoneItem]) -> Self {
+        let mut progress = Self {
+            title: milestone.title.clone(),
+            due_on: milestone.due_on.clone(),
+            ..Self::default()
+        };
+        for item in items {
+            let open = item.state == "open";
+            match (item.pull_request.is_some(), open) {
+                (true, true) => progress.open_prs += 1,
+                (true, false) => progress.closed_prs += 1,
+                (false, true) => progress.open_issues += 1,
+                (false, false) => progress.closed_issues += 1,
+            }
+        }
+        progress
+    }
+
+    pub fn total(&self) -> usize {
+        self.open_issues + self.closed_issues + self.open_prs + self.closed_prs
+    }
+
+    pub fn closed(&self) -> usize {
+        self.closed_issues + self.closed_prs
+    }
+
+    /// Share of items closed, 0.0–1.0 (0.0 for an empty milestone).
+    pub fn ratio(&self) -> f64 {
+        match self.total() {
+            0 => 0.0,
+            total => self.closed() as f64 / total as f64,
+        }
+    }
+}
+
+/// The milestone to track: the one titled `name` (case-insensitive) when given,
+/// else the open milestone due soonest, else the first open one.
+pub fn pick<'a>(milestones: &'a [Milestone], name: Option<&str>) -> Option<&'a Milestone> {
+    match name {
+        Some(name) => milestones
+            .iter()
+            .find(|m| m.title.eq_ignore_ascii_case(name.trim())),
+        None => {
+            let open = || milestones.iter().filter(|m| m.state == "open");
+            open()
+                .filter(|m| m.due_on.is_some())
+                .min_by(|a, b| a.due_on.cmp(&b.due_on))
+                .or_else(|| open().next())
+        }
+    }
+}
+
+/// Fetch progress for the configured (or current) milestone. `Ok(None)` when
+/// the repository has no matching milestone.
+pub fn fetch(token: &str, name: Option<&str>) -> Result<Option<MilestoneProgress>> {
+    let milestones = github_auth::list_milestones(token, name.is_some())?;
+    let Some(milestone) = pick(&milestones, name) else {
+        return Ok(None);
+    };
+    let items = github_auth::list_milestone_items(token, milestone.number)?;
+    Ok(Some(MilestoneProgress::tally(milestone, &items)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn milestone(number: u64, title: &str, state: &str, due_on: Option<&str>) -> Milestone {
+        Milestone {
+            number,
+            title: title.to_string(),
+            state: state.to_string(),
+            due_on: due_on.map(|d| d.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_pick_and_tally() {
+        let milestones = vec![
+            milestone(1, "v1.0", "closed", Some("2026-01-01T00:00:00Z")),
+            milestone(2, "Backlog", "open", None),
+            milestone(3, "v1.2", "open", Some("2026-12-01T00:00:00Z")),
+            milestone(4, "v1.1", "open", Some("2026-11-01T00:00:00Z")),
+        ];
+        assert_eq!(pick(&milestones, None).unwrap().number, 4);
+        assert_eq!(pick(&milestones, Some("v1.0")).unwrap().number, 1);
+        assert_eq!(pick(&milestones, Some(" backlog ")).unwrap().number, 2);
+        assert!(pick(&milestones, Some("v9")).is_none());
+        assert_eq!(pick(&milestones[1..2], None).unwrap().number, 2);
+
+        let items: Vec<MilestoneItem> = serde_json::from_value(serde_json::json!([
+            { "state": "open" },
+            { "state": "closed" },
+            { "state": "closed" },
+            { "state": "open", "pull_request": { "url": "" } },
+            { "state": "closed", "pull_request": { "url": "" } },
+        ]))
+        .unwrap();
+        let progress = MilestoneProgress::tally(&milestones[3], &items);
+        assert_eq!(
+            (
+                progress.open_issues,
+                progress.closed_issues,
+                progress.open_prs,
+                progress.closed_prs
+            ),
+            (1, 2, 1, 1)
+        );
+        assert_eq!(progress.ratio(), 0.6);
+    }
+}
@@ -1,16 +1,38 @@
+pub mod alias;
+pub mod backend;
+pub mod binary;
 pub mod bisect;
+pub mod blame;
 pub mod branch;
 pub mod cherry_pick;
+pub mod codeowners;
+pub mod commit;
+pub mod contributing;
 pub mod diff;
+pub mod encoding;
 pub mod github_auth;
+pub mod identity;
+pub mod lock;
 pub mod log;
 pub mod merge;
+pub mod milestone;
+pub mod notes;
+pub mod prepush;
+pub mod pull_request;
+pub mod rebase;
 pub mod reflog;
+pub mod release_notes;
 pub mod remote;
 pub mod runner;
+pub mod scaffold;
+pub mod scope;
 pub mod secrets;
+pub mod snapshot;
 pub mod stash;
 pub mod status;
+pub mod submodule;
+pub mod tag;
+pub mod whitespace;
 
 pub use branch::{BranchEntry, BranchOps};
 pub use diff::{DiffLine, DiffLineType};
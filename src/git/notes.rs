@@ -0,0 +1,11 @@
+//! Commit notes (`git notes`) — text attached to a commit after the fact,
+//! without rewriting it.
+
+use super::runner::run_git;
+
+/// The note on `commit` in the default notes ref, if it has one.
+pub fn show(commit: &str) -> Option<String> {
+    let note = run_git(&["notes", "show", commit]).ok()?;
+    let note = note.trim_end();
+    (!note.is_empty()).then(|| note.to_string())
+}
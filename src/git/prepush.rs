@@ -0,0 +1,188 @@
+//! Pre-push checklist (`[push]` config) — checks the commits about to be pushed for
+//! WIP/fixup subjects, large files and leftover conflict markers, and optionally runs
+//! a user-defined command such as the test suite.
+
+use super::runner::run_git;
+use crate::config::PushConfig;
+use std::collections::BTreeSet;
+use std::process::Command;
+
+/// Output lines of a failing command shown in the checklist.
+const COMMAND_TAIL_LINES: usize = 5;
+
+/// One line of the checklist.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CheckItem {
+    pub label: String,
+    pub passed: bool,
+    /// What failed (commits, files, command output); empty when passed.
+    pub details: Vec<String>,
+}
+
+impl CheckItem {
+    fn new(label: impl Into<String>, details: Vec<String>) -> Self {
+        Self {
+            label: label.into(),
+            passed: details.is_empty(),
+            details,
+        }
+    }
+}
+
+/// Run every enabled check against the commits not yet on the upstream branch.
+pub fn run_checklist(config: &PushConfig) -> Vec<CheckItem> {
+    let range = super::log::outgoing_range();
+    let range: Vec<&str> = range.iter().map(|s| s.as_str()).collect();
+    let mut items = Vec::new();
+
+    let mut log_args = vec!["log", "--format=%h %s"];
+    log_args.extend(&range);
+    let wip: Vec<String> = run_git(&log_args)
+        .unwrap_or_default()
+        .lines()
+        .filter(|l| is_wip_subject(l.split_once(' ').map_or("", |(_, s)| s)))
+        .map(|l| l.to_string())
+        .collect();
+    items.push(CheckItem::new("No WIP / fixup commits", wip));
+
+    let mut files_args = vec!["log", "--format=", "--name-only", "--diff-filter=AMR"];
+    files_args.extend(&range);
+    let files: BTreeSet<String> = run_git(&files_args)
+        .unwrap_or_default()
+        .lines()
+        .map(|l| l.trim().to_string())
+        .filter(|l| !l.is_empty())
+        .collect();
+    let files: Vec<&str> = files.iter().map(|s| s.as_str()).collect();
+
+    let large = if files.is_empty() {
+        Vec::new()
+    } else {
+        let mut args = vec!["ls-tree", "-r", "-l", "HEAD", "--"];
+        args.extend(&files);
+        large_files(
+            &run_git(&args).unwrap_or_default(),
+            config.large_file_kb * 1024,
+        )
+    };
+    items.push(CheckItem::new(
+        format!("No files over {} KiB", config.large_file_kb),
+        large,
+    ));
+
+    let markers = super::merge::find_conflict_markers(&files, Some("HEAD"))
+        .into_iter()
+        .map(|hit| format!("{}:{}", hit.path, hit.line))
+        .collect();
+    items.push(CheckItem::new("No conflict markers", markers));
+
+    if let Some(command) = config.command.as_deref().filter(|c| !c.trim().is_empty()) {
+        items.push(CheckItem::new(
+            format!("`{}` succeeds", command),
+            run_command(command),
+        ));
+    }
+
+    items
+}
+
+/// Whether every check passed.
+pub fn all_passed(items: &[CheckItem]) -> bool {
+    items.iter().all(|i| i.passed)
+}
+
+/// Subjects that should not be pushed as-is: `WIP`, `[wip]`, and autosquash commits.
+fn is_wip_subject(subject: &str) -> bool {
+    let lower = subject.trim().to_lowercase();
+    ["fixup!", "squash!", "amend!"]
+        .iter()
+        .any(|p| lower.starts_with(p))
+        || lower.contains("[wip]")
+        || lower
+            .strip_prefix("wip")
+            .is_some_and(|rest| !rest.starts_with(|c: char| c.is_alphanumeric()))
+}
+
+/// Parse `git ls-tree -r -l` output into "path (size)" for blobs over `limit` bytes.
+fn large_files(ls_tree: &str, limit: u64) -> Vec<String> {
+    ls_tree
+        .lines()
+        .filter_map(|line| {
+            let (meta, path) = line.split_once('\t')?;
+            let size: u64 = meta.split_whitespace().nth(3)?.parse().ok()?;
+            (size > limit).then(|| format!("{} ({} KiB)", path, size / 1024))
+        })
+        .collect()
+}
+
+/// Run the user's command from the repo root. Returns the tail of its output on failure.
+fn run_command(command: &str) -> Vec<String> {
+    let mut cmd = if cfg!(target_os = "windows") {
+        let mut c = Command::new("cmd");
+        c.args(["/C", command]);
+        c
+    } else {
+        let mut c = Command::new("sh");
+        c.args(["-c", command]);
+        c
+    };
+    if let Ok(root) = run_git(&["rev-parse", "--show-toplevel"]) {
+        cmd.current_dir(root.trim());
+    }
+    match cmd.output() {
+        Ok(output) if output.status.success() => Vec::new(),
+        Ok(output) => {
+            let text = format!(
+                "{}{}",
+                String::from_utf8_lossy(&output.stdout),
+                String::from_utf8_lossy(&output.stderr)
+            );
+            let lines: Vec<&str> = text.lines().filter(|l| !l.trim().is_empty()).collect();
+            let mut tail: Vec<String> = lines[lines.len().saturating_sub(COMMAND_TAIL_LINES)..]
+                .iter()
+                .map(|l| l.to_string())
+                .collect();
+            if tail.is_empty() {
+                tail.push(format!("exited with {}", output.status));
+            }
+            tail
+        }
+        Err(e) => vec![format!("could not run: {}", e)],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_wip_subject() {
+        assert!(is_wip_subject("WIP"));
+        assert!(is_wip_subject("wip: half done"));
+        assert!(is_wip_subject("fixup! feat: add picker"));
+        assert!(is_wip_subject("squash! fix: typo"));
+        assert!(is_wip_subject("feat: [WIP] new parser"));
+        assert!(!is_wip_subject("wipe cache on logout"));
+        assert!(!is_wip_subject("feat: add wip detection"));
+    }
+
+    #[test]
+    fn test_large_files() {
+        let out = "100644 blob aaa     2048\tsmall.txt\n\
+                   100644 blob bbb  5242880\tassets/big video.mp4\n";
+        assert_eq!(
+            large_files(out, 1024 * 1024),
+            vec!["assets/big video.mp4 (5120 KiB)"]
+        );
+        assert!(large_files(out, 10 * 1024 * 1024).is_empty());
+    }
+
+    #[test]
+    fn test_check_item_and_all_passed() {
+        let ok = CheckItem::new("a", Vec::new());
+        let bad = CheckItem::new("b", vec!["x".to_string()]);
+        assert!(ok.passed && !bad.passed);
+        assert!(all_passed(std::slice::from_ref(&ok)));
+        assert!(!all_passed(&[ok, bad]));
+    }
+}
@@ -0,0 +1,183 @@
+//! The local side of opening a pull request: what the head branch adds over
+//! the base, whether it still has to be pushed, and a title and description
+//! drafted from its commits.
+
+use super::runner::run_git;
+use anyhow::Result;
+
+/// The remote pull requests are opened against (see `github_auth::parse_repo_from_remote`).
+pub const REMOTE: &str = "origin";
+
+/// Branches a pull request can come from (the local ones) and go into (those
+/// and the remote's), by name.
+pub fn branches() -> Result<(Vec<String>, Vec<String>)> {
+    let remote_prefix = format!("refs/remotes/{}/", REMOTE);
+    let output = run_git(&[
+        "for-each-ref",
+        "--format=%(refname)",
+        "refs/heads",
+        remote_prefix.trim_end_matches('/'),
+    ])?;
+    let mut heads = Vec::new();
+    let mut bases = Vec::new();
+    for refname in output.lines() {
+        if let Some(name) = refname.strip_prefix("refs/heads/") {
+            heads.push(name.to_string());
+            bases.push(name.to_string());
+        } else if let Some(name) = refname.strip_prefix(&remote_prefix)
+            && name != "HEAD"
+        {
+            bases.push(name.to_string());
+        }
+    }
+    bases.sort();
+    bases.dedup();
+    Ok((heads, bases))
+}
+
+/// What has to happen to `head` on the remote before a pull request can use it.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PushNeed {
+    /// The remote branch has every commit.
+    UpToDate,
+    /// Not on the remote yet; pushed with upstream tracking.
+    New,
+    /// The remote branch is this many commits behind.
+    Ahead(usize),
+}
+
+/// Whether `head` has to be pushed first.
+pub fn push_need(head: &str) -> PushNeed {
+    let remote_ref = format!("refs/remotes/{}/{}", REMOTE, head);
+    if run_git(&["rev-parse", "--verify", "--quiet", &remote_ref]).is_err() {
+        return PushNeed::New;
+    }
+    let range = format!("{}..refs/heads/{}", remote_ref, head);
+    match run_git(&["rev-list", "--count", &range])
+        .ok()
+        .and_then(|n| n.trim().parse().ok())
+    {
+        Some(0) | None => PushNeed::UpToDate,
+        Some(n) => PushNeed::Ahead(n),
+    }
+}
+
+/// The commits `head` adds over `base`, with `sep` between them: `..` for the
+/// log, `...` for the diff from where they forked. The base is compared as the
+/// remote has it when it's there, since that's what GitHub sees.
+fn range(base: &str, sep: &str, head: &str) -> String {
+    let remote_base = format!("refs/remotes/{}/{}", REMOTE, base);
+    let base_ref = if run_git(&["rev-parse", "--verify", "--quiet", &remote_base]).is_ok() {
+        remote_base
+    } else {
+        format!("refs/heads/{}", base)
+    };
+    format!("{}{}refs/heads/{}", base_ref, sep, head)
+}
+
+/// Subjects of the commits `head` adds over `base`, oldest first.
+pub fn commits_between(base: &str, head: &str) -> Result<Vec<String>> {
+    let output = run_git(&[
+        "log",
+        "--reverse",
+        "--no-merges",
+        "--format=%s",
+        &range(base, "..", head),
+    ])?;
+    Ok(output
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(str::to_string)
+        .collect())
+}
+
+/// A title for the pull request: the commit's subject when there's just one,
+/// otherwise the branch name as a sentence ("fix/login-redirect" → "Login redirect").
+pub fn draft_title(head: &str, subjects: &[String]) -> String {
+    if let [only] = subjects {
+        return only.clone();
+    }
+    let name = head.rsplit('/').next().unwrap_or(head);
+    let words = name.replace(['-', '_'], " ");
+    let mut chars = words.trim().chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => head.to_string(),
+    }
+}
+
+/// A description listing the commits, when there's more than one.
+pub fn draft_body(subjects: &[String]) -> String {
+    if subjects.len() < 2 {
+        return String::new();
+    }
+    subjects
+        .iter()
+        .map(|s| format!("- {}", s))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// What the AI gets to describe the pull request from: the full commit
+/// messages and the diffstat.
+pub fn describe_input(base: &str, head: &str) -> Result<String> {
+    let messages = run_git(&[
+        "log",
+        "--reverse",
+        "--no-merges",
+        "--format=- %s%n%b",
+        &range(base, "..", head),
+    ])?;
+    let stat = run_git(&["diff", "--stat", &range(base, "...", head)])?;
+    Ok(format!(
+        "Branch: {} into {}\n\nCommits:\n{}\n\nFiles changed:\n{}",
+        head,
+        base,
+        messages.trim(),
+        stat.trim()
+    ))
+}
+
+/// Split an AI answer into the title (its first line) and the description.
+pub fn split_description(text: &str) -> (String, String) {
+    let text = text.trim();
+    let (title, body) = text.split_once('\n').unwrap_or((text, ""));
+    let title = title.trim().trim_start_matches('#').trim();
+    let title = title.strip_prefix("Title:").unwrap_or(title).trim();
+    (title.to_string(), body.trim().to_string())
+}
+
+/// The file the description is edited in, like `COMMIT_EDITMSG` for commits.
+pub fn body_file() -> Result<std::path::PathBuf> {
+    let path = run_git(&["rev-parse", "--git-path", "PULLREQ_EDITMSG"])?;
+    Ok(std::path::PathBuf::from(path.trim()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_drafts_from_commits() {
+        let one = vec!["Fix the login redirect".to_string()];
+        assert_eq!(draft_title("fix/login", &one), "Fix the login redirect");
+        assert_eq!(draft_body(&one), "");
+
+        let two = vec!["Add a retry".to_string(), "Log failures".to_string()];
+        assert_eq!(
+            draft_title("feature/retry-on_timeout", &two),
+            "Retry on timeout"
+        );
+        assert_eq!(draft_body(&two), "- Add a retry\n- Log failures");
+        assert_eq!(draft_title("wip", &[]), "Wip");
+
+        assert_eq!(
+            split_description("Title: Retry on timeout\n\nRetries twice.\n"),
+            ("Retry on timeout".to_string(), "Retries twice.".to_string())
+        );
+        assert_eq!(
+            split_description("# Retry"),
+            ("Retry".to_string(), String::new())
+        );
+    }
+}
@@ -0,0 +1,314 @@
+//! Interactive rebase without an editor: the plan is built in zit, written as
+//! a todo file, and handed to `git rebase -i` through a `GIT_SEQUENCE_EDITOR`
+//! shim that copies it over the todo git generated.
+//!
+//! Rewording runs `git commit --amend -F <file>` as an `exec` step right after
+//! the commit is picked, so no editor ever opens; squashes keep both messages
+//! (`GIT_EDITOR=true` accepts git's combined message).
+
+use super::log::CommitEntry;
+use super::runner::{run_git, run_git_with_env};
+use anyhow::{Context, Result, bail};
+use std::path::PathBuf;
+
+/// What to do with a commit in the rebase plan.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TodoAction {
+    Pick,
+    Reword,
+    Squash,
+    Fixup,
+    Drop,
+}
+
+impl TodoAction {
+    /// The todo-file command.
+    pub fn command(self) -> &'static str {
+        match self {
+            Self::Pick | Self::Reword => "pick",
+            Self::Squash => "squash",
+            Self::Fixup => "fixup",
+            Self::Drop => "drop",
+        }
+    }
+
+    /// Label shown in the plan.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Pick => "pick",
+            Self::Reword => "reword",
+            Self::Squash => "squash",
+            Self::Fixup => "fixup",
+            Self::Drop => "drop",
+        }
+    }
+
+    /// Folds into the commit before it.
+    pub fn melds(self) -> bool {
+        matches!(self, Self::Squash | Self::Fixup)
+    }
+}
+
+/// One commit in the plan.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TodoEntry {
+    pub action: TodoAction,
+    pub hash: String,
+    pub short_hash: String,
+    pub subject: String,
+    /// Message body, kept when the subject is reworded.
+    pub body: String,
+    /// New subject for `Reword`.
+    pub new_subject: Option<String>,
+}
+
+impl TodoEntry {
+    /// The full message to reword to, if it's being reworded.
+    pub fn reworded_message(&self) -> Option<String> {
+        let subject = self.new_subject.as_deref()?;
+        if self.action != TodoAction::Reword {
+            return None;
+        }
+        Some(if self.body.trim().is_empty() {
+            format!("{}\n", subject)
+        } else {
+            format!("{}\n\n{}\n", subject, self.body.trim_end())
+        })
+    }
+}
+
+/// Commits from `from` (inclusive) up to HEAD, oldest first, as an all-`pick`
+/// plan, plus the base to rebase onto (`None` when `from` is the root commit).
+pub fn plan_from(from: &CommitEntry) -> Result<(Option<String>, Vec<TodoEntry>)> {
+    if let Some(op) = super::merge::get_merge_state() {
+        bail!("Finish the {} in progress first", op.merge_type);
+    }
+    if !run_git(&["status", "--porcelain", "--untracked-files=no"])?
+        .trim()
+        .is_empty()
+    {
+        bail!("Commit or stash your changes first");
+    }
+    if run_git(&["merge-base", "--is-ancestor", &from.hash, "HEAD"]).is_err() {
+        bail!("{} isn't in the current branch's history", from.short_hash);
+    }
+    // No parent: `from` is the root commit
+    let base = run_git(&[
+        "rev-parse",
+        "--verify",
+        "--quiet",
+        &format!("{}^", from.hash),
+    ])
+    .ok()
+    .map(|h| h.trim().to_string());
+    let range = match &base {
+        Some(base) => format!("{}..HEAD", base),
+        None => "HEAD".to_string(),
+    };
+    if !run_git(&["rev-list", "--merges", &range])?
+        .trim()
+        .is_empty()
+    {
+        bail!(
+            "There are merge commits after {}; rebasing would flatten them",
+            from.short_hash
+        );
+    }
+    let output = run_git(&[
+        "log",
+        "--reverse",
+        "--format=%H%x1f%h%x1f%s%x1f%b%x1e",
+        &range,
+    ])?;
+    let entries = parse_log(&output);
+    if entries.is_empty() {
+        bail!("Nothing to rebase");
+    }
+    Ok((base, entries))
+}
+
+fn parse_log(output: &str) -> Vec<TodoEntry> {
+    output
+        .split('\x1e')
+        .filter_map(|record| {
+            let mut parts = record.trim_start_matches('\n').splitn(4, '\x1f');
+            let hash = parts.next()?.trim();
+            if hash.is_empty() {
+                return None;
+            }
+            Some(TodoEntry {
+                action: TodoAction::Pick,
+                hash: hash.to_string(),
+                short_hash: parts.next()?.to_string(),
+                subject: parts.next()?.to_string(),
+                body: parts.next().unwrap_or_default().trim().to_string(),
+                new_subject: None,
+            })
+        })
+        .collect()
+}
+
+/// What's wrong with the plan, if anything.
+pub fn validate(entries: &[TodoEntry]) -> Option<String> {
+    let first = entries.iter().find(|e| e.action != TodoAction::Drop);
+    match first {
+        None => Some("Every commit is dropped — use Time Travel to reset instead".to_string()),
+        Some(e) if e.action.melds() => Some(format!(
+            "{} can't {} into nothing: it's the first commit kept",
+            e.short_hash,
+            e.action.label()
+        )),
+        _ => None,
+    }
+}
+
+/// Quote `s` for `sh`.
+fn shell_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', r"'\''"))
+}
+
+/// The todo file for `entries`; reworded messages are read from
+/// `message_file(i)` for entry `i`.
+fn todo(entries: &[TodoEntry], message_file: impl Fn(usize) -> String) -> String {
+    let mut out = String::new();
+    for (i, e) in entries.iter().enumerate() {
+        out.push_str(&format!(
+            "{} {} {}\n",
+            e.action.command(),
+            e.hash,
+            e.subject
+        ));
+        if e.reworded_message().is_some() {
+            out.push_str(&format!(
+                "exec git commit --amend --allow-empty --quiet -F {}\n",
+                shell_quote(&message_file(i))
+            ));
+        }
+    }
+    out
+}
+
+/// Commits in the plan that are already on the upstream branch, so rewriting
+/// them will need a force push. 0 without an upstream.
+pub fn published_count(base: Option<&str>) -> usize {
+    let Ok(merge_base) = run_git(&["merge-base", "HEAD", "@{upstream}"]) else {
+        return 0;
+    };
+    let range = match base {
+        Some(base) => format!("{}..{}", base, merge_base.trim()),
+        None => merge_base.trim().to_string(),
+    };
+    run_git(&["rev-list", "--count", &range])
+        .ok()
+        .and_then(|n| n.trim().parse().ok())
+        .unwrap_or(0)
+}
+
+/// Run the plan. An `Err` with a rebase still in progress means it stopped on
+/// a conflict (or a failed step) and is waiting for `git rebase --continue`.
+pub fn run(base: Option<&str>, entries: &[TodoEntry]) -> Result<()> {
+    if let Some(problem) = validate(entries) {
+        bail!(problem);
+    }
+    let git_dir = PathBuf::from(run_git(&["rev-parse", "--absolute-git-dir"])?.trim());
+    let dir = git_dir.join("zit-rebase");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).context("Failed to create the rebase plan directory")?;
+
+    let message_file = |i: usize| dir.join(format!("message-{}", i)).display().to_string();
+    for (i, e) in entries.iter().enumerate() {
+        if let Some(message) = e.reworded_message() {
+            std::fs::write(message_file(i), message).context("Failed to write a new message")?;
+        }
+    }
+    let todo_path = dir.join("todo");
+    std::fs::write(&todo_path, todo(entries, message_file))
+        .context("Failed to write the rebase plan")?;
+
+    let editor = format!("cp {}", shell_quote(&todo_path.display().to_string()));
+    let mut args = vec!["rebase", "-i"];
+    args.push(base.unwrap_or("--root"));
+    run_git_with_env(
+        &args,
+        &[("GIT_SEQUENCE_EDITOR", &editor), ("GIT_EDITOR", "true")],
+    )?;
+    // Reword messages are still needed if it stopped; only clean up when done
+    let _ = std::fs::remove_dir_all(&dir);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(short: &str, action: TodoAction) -> TodoEntry {
+        TodoEntry {
+            action,
+            hash: format!("{}000", short),
+            short_hash: short.to_string(),
+            subject: format!("change {}", short),
+            body: String::new(),
+            new_subject: None,
+        }
+    }
+
+    #[test]
+    fn test_parse_log_keeps_bodies() {
+        let output =
+            "aaa\x1fa\x1fFirst\x1f\x1e\nbbb\x1fb\x1fSecond\x1fWhy it's needed\n\nMore.\n\x1e\n";
+        let entries = parse_log(output);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].subject, "First");
+        assert_eq!(entries[0].body, "");
+        assert_eq!(entries[1].short_hash, "b");
+        assert_eq!(entries[1].body, "Why it's needed\n\nMore.");
+    }
+
+    #[test]
+    fn test_todo_rewords_with_exec_steps() {
+        let mut reworded = entry("b", TodoAction::Reword);
+        reworded.new_subject = Some("Better subject".to_string());
+        reworded.body = "Body".to_string();
+        assert_eq!(
+            reworded.reworded_message().as_deref(),
+            Some("Better subject\n\nBody\n")
+        );
+        let entries = vec![
+            entry("a", TodoAction::Pick),
+            reworded,
+            entry("c", TodoAction::Fixup),
+            entry("d", TodoAction::Drop),
+        ];
+        assert_eq!(
+            todo(&entries, |i| format!("/tmp/it's/message-{}", i)),
+            "pick a000 change a\n\
+             pick b000 change b\n\
+             exec git commit --amend --allow-empty --quiet -F '/tmp/it'\\''s/message-1'\n\
+             fixup c000 change c\n\
+             drop d000 change d\n"
+        );
+    }
+
+    #[test]
+    fn test_validate_plan() {
+        let plan = |actions: &[TodoAction]| -> Vec<TodoEntry> {
+            actions
+                .iter()
+                .enumerate()
+                .map(|(i, a)| entry(&i.to_string(), *a))
+                .collect()
+        };
+        use TodoAction::*;
+        assert_eq!(validate(&plan(&[Pick, Squash, Fixup, Drop])), None);
+        assert!(
+            validate(&plan(&[Drop, Squash, Pick]))
+                .unwrap()
+                .contains("first commit kept")
+        );
+        assert!(
+            validate(&plan(&[Drop, Drop]))
+                .unwrap()
+                .contains("Every commit")
+        );
+    }
+}
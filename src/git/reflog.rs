@@ -8,10 +8,11 @@ pub struct ReflogEntry {
     pub short_hash: String,
     pub operation: String, // commit, reset, checkout, merge, rebase, etc.
     pub message: String,
-    pub date: String,
+    pub date: String,     // relative date like "2 hours ago"
+    pub date_iso: String, // ISO format for local formatting
 }
 
-const REFLOG_FORMAT: &str = "%H\x1f%h\x1f%gs\x1f%ar";
+const REFLOG_FORMAT: &str = "%H\x1f%h\x1f%gs\x1f%ar\x1f%aI";
 
 /// Fetch reflog entries.
 pub fn get_reflog(count: usize) -> Result<Vec<ReflogEntry>> {
@@ -42,6 +43,7 @@ pub fn get_reflog(count: usize) -> Result<Vec<ReflogEntry>> {
             operation,
             message,
             date: parts[3].to_string(),
+            date_iso: parts.get(4).unwrap_or(&"").to_string(),
         });
     }
 
@@ -61,10 +63,97 @@ pub fn filter_reflog(entries: &[ReflogEntry], operation: &str) -> Vec<ReflogEntr
         .collect()
 }
 
+/// A commit no branch, tag or other ref reaches any more: reset away, left
+/// behind on a detached HEAD, or a dropped stash.
+#[derive(Debug, Clone)]
+pub struct LostCommit {
+    pub hash: String,
+    pub short_hash: String,
+    pub message: String,
+    pub author: String,
+    pub date: String,
+    pub date_iso: String,
+    /// Still listed in a reflog. Commits that aren't can be pruned by the
+    /// next `git gc`.
+    pub in_reflog: bool,
+    /// A stash entry (a merge commit with a "WIP on"/"On <branch>:" subject).
+    pub is_stash: bool,
+}
+
+const LOST_FORMAT: &str = "%H\x1f%h\x1f%s\x1f%an\x1f%ar\x1f%aI\x1f%P";
+
+/// Find lost commits: the tips `git fsck` reports as dangling when reflogs
+/// don't count as references, newest first. Stash entries still in the stash
+/// list aren't lost and are left out.
+pub fn lost_commits() -> Result<Vec<LostCommit>> {
+    let fsck = run_git(&["fsck", "--no-reflogs", "--no-progress"])?;
+    let stashed = run_git(&["stash", "list", "--format=%H"]).unwrap_or_default();
+    let stashed: Vec<&str> = stashed.lines().collect();
+    let dangling: Vec<&str> = parse_dangling(&fsck)
+        .into_iter()
+        .filter(|hash| !stashed.contains(hash))
+        .collect();
+    if dangling.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let reflog = run_git(&["reflog", "show", "--all", "--format=%H"]).unwrap_or_default();
+    let reflog: Vec<&str> = reflog.lines().collect();
+    let format = format!("--format={}", LOST_FORMAT);
+    let mut args = vec!["log", "--no-walk", &format];
+    args.extend(&dangling);
+    let output = run_git(&args)?;
+    Ok(output
+        .lines()
+        .filter_map(|line| {
+            let parts: Vec<&str> = line.split('\x1f').collect();
+            let [hash, short_hash, message, author, date, date_iso, parents] = parts[..] else {
+                return None;
+            };
+            let is_stash = parents.split(' ').count() >= 2
+                && (message.starts_with("WIP on ") || message.starts_with("On "));
+            Some(LostCommit {
+                hash: hash.to_string(),
+                short_hash: short_hash.to_string(),
+                message: message.to_string(),
+                author: author.to_string(),
+                date: date.to_string(),
+                date_iso: date_iso.to_string(),
+                in_reflog: reflog.contains(&hash),
+                is_stash,
+            })
+        })
+        .collect())
+}
+
+/// Hashes of the "dangling commit <hash>" lines in `git fsck` output.
+fn parse_dangling(fsck: &str) -> Vec<&str> {
+    fsck.lines()
+        .filter_map(|line| line.strip_prefix("dangling commit "))
+        .map(str::trim)
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parse_dangling() {
+        let fsck = "dangling blob 9f8e7d6c5b4a39281706f5e4d3c2b1a098765432\n\
+                    dangling commit 1257b0860cee57d57c68dc1d159d50974f9f7d8d\n\
+                    dangling tree 0a1b2c3d4e5f60718293a4b5c6d7e8f901234567\n\
+                    dangling commit 323fae167aaaec4358e0add3deba5e9cb2bcd81c\n";
+        assert_eq!(
+            parse_dangling(fsck),
+            [
+                "1257b0860cee57d57c68dc1d159d50974f9f7d8d",
+                "323fae167aaaec4358e0add3deba5e9cb2bcd81c"
+            ]
+        );
+        assert!(parse_dangling("").is_empty());
+    }
+
     #[test]
     fn test_parse_reflog() {
         let sample = "abc123def456abc123def456abc123def456abc123\x1fabc123d\x1fcommit: initial commit\x1f2 hours ago\n\
@@ -90,6 +179,7 @@ mod tests {
                 operation,
                 message,
                 date: parts[3].to_string(),
+                date_iso: String::new(),
             });
         }
 
@@ -110,6 +200,7 @@ mod tests {
                 operation: "commit".to_string(),
                 message: "test".to_string(),
                 date: "now".to_string(),
+                date_iso: String::new(),
             },
             ReflogEntry {
                 index: 1,
@@ -118,6 +209,7 @@ mod tests {
                 operation: "reset".to_string(),
                 message: "test".to_string(),
                 date: "now".to_string(),
+                date_iso: String::new(),
             },
         ];
 
@@ -135,6 +227,7 @@ mod tests {
             operation: "commit".to_string(),
             message: "test".to_string(),
             date: "now".to_string(),
+            date_iso: String::new(),
         }];
         // Empty string matches everything (contains(""))
         let filtered = filter_reflog(&entries, "");
@@ -150,6 +243,7 @@ mod tests {
             operation: "COMMIT".to_string(),
             message: "test".to_string(),
             date: "now".to_string(),
+            date_iso: String::new(),
         }];
         let filtered = filter_reflog(&entries, "commit");
         assert_eq!(filtered.len(), 1);
@@ -164,6 +258,7 @@ mod tests {
             operation: "commit".to_string(),
             message: "test".to_string(),
             date: "now".to_string(),
+            date_iso: String::new(),
         }];
         let filtered = filter_reflog(&entries, "checkout");
         assert!(filtered.is_empty());
@@ -193,6 +288,7 @@ mod tests {
                 operation,
                 message,
                 date: parts[3].to_string(),
+                date_iso: String::new(),
             });
         }
         assert_eq!(entries.len(), 1);
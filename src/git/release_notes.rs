@@ -0,0 +1,142 @@
+//! Release notes drafts — pull requests merged since the last tag, grouped by label
+//! into markdown ready for a GitHub release.
+
+use super::github_auth::MergedPullRequest;
+use super::runner::run_git;
+
+/// Sections for well-known labels, in the order they appear in the notes.
+const SECTIONS: [(&str, &[&str]); 6] = [
+    (
+        "⚠️ Breaking Changes",
+        &["breaking", "breaking change", "breaking-change"],
+    ),
+    ("Features", &["feature", "enhancement", "feat"]),
+    ("Bug Fixes", &["bug", "fix", "bugfix"]),
+    ("Performance", &["performance", "perf"]),
+    ("Documentation", &["documentation", "docs"]),
+    ("Dependencies", &["dependencies", "deps"]),
+];
+
+/// Pull requests with one of these labels are left out.
+const SKIP_LABELS: [&str; 4] = [
+    "skip-changelog",
+    "no-changelog",
+    "ignore-for-release",
+    "skip-release-notes",
+];
+
+/// Heading for pull requests without labels.
+const OTHER: &str = "Other Changes";
+
+/// The latest tag reachable from HEAD and its commit date (ISO 8601).
+pub fn last_tag() -> Option<(String, String)> {
+    let tag = run_git(&["describe", "--tags", "--abbrev=0"]).ok()?;
+    let tag = tag.trim().to_string();
+    let date = run_git(&["log", "-1", "--format=%cI", &tag]).ok()?;
+    Some((tag, date.trim().to_string()))
+}
+
+/// Section a pull request belongs to: the first well-known section any of its
+/// labels maps to, else its first label, else "Other Changes".
+fn section_of(pr: &MergedPullRequest) -> String {
+    SECTIONS
+        .iter()
+        .find(|(_, names)| {
+            pr.labels
+                .iter()
+                .any(|l| names.contains(&l.name.to_lowercase().as_str()))
+        })
+        .map(|(title, _)| title.to_string())
+        .or_else(|| pr.labels.first().map(|l| l.name.clone()))
+        .unwrap_or_else(|| OTHER.to_string())
+}
+
+/// Markdown release notes for `prs`. `compare_url` links the full changelog.
+pub fn draft(prs: &[MergedPullRequest], compare_url: Option<&str>) -> String {
+    let mut prs: Vec<&MergedPullRequest> = prs
+        .iter()
+        .filter(|pr| {
+            !pr.labels
+                .iter()
+                .any(|l| SKIP_LABELS.contains(&l.name.to_lowercase().as_str()))
+        })
+        .collect();
+    prs.sort_by_key(|pr| pr.number);
+
+    // Well-known sections first, then other labels alphabetically, "Other" last
+    let mut sections: Vec<(String, Vec<&MergedPullRequest>)> = Vec::new();
+    for pr in prs {
+        let section = section_of(pr);
+        match sections.iter_mut().find(|(title, _)| *title == section) {
+            Some((_, list)) => list.push(pr),
+            None => sections.push((section, vec![pr])),
+        }
+    }
+    let rank = |title: &str| {
+        SECTIONS
+            .iter()
+            .position(|(t, _)| *t == title)
+            .unwrap_or(if title == OTHER {
+                SECTIONS.len() + 1
+            } else {
+                SECTIONS.len()
+            })
+    };
+    sections.sort_by(|(a, _), (b, _)| rank(a).cmp(&rank(b)).then_with(|| a.cmp(b)));
+
+    let mut out = String::from("## What's Changed\n");
+    if sections.is_empty() {
+        out.push_str("\nNo pull requests merged since the last release.\n");
+    }
+    for (title, list) in sections {
+        out.push_str(&format!("\n### {}\n\n", title));
+        for pr in list {
+            out.push_str(&format!(
+                "- {} by @{} in #{}\n",
+                pr.title.trim(),
+                pr.user.login,
+                pr.number
+            ));
+        }
+    }
+    if let Some(url) = compare_url {
+        out.push_str(&format!("\n**Full Changelog**: {}\n", url));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pr(number: u64, title: &str, labels: &[&str]) -> MergedPullRequest {
+        serde_json::from_value(serde_json::json!({
+            "number": number,
+            "title": title,
+            "user": { "login": "dev" },
+            "labels": labels.iter().map(|l| serde_json::json!({ "name": l })).collect::<Vec<_>>(),
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_draft_groups_by_label() {
+        let prs = vec![
+            pr(7, "Fix crash", &["Bug"]),
+            pr(3, "Add watch mode", &["enhancement", "ui"]),
+            pr(5, "Tweak CI", &[]),
+            pr(4, "Refresh icons", &["ui"]),
+            pr(6, "Bump deps", &["dependencies", "skip-changelog"]),
+        ];
+        let notes = draft(&prs, Some("https://github.com/o/r/compare/v1.0...main"));
+        assert_eq!(
+            notes,
+            "## What's Changed\n\
+             \n### Features\n\n- Add watch mode by @dev in #3\n\
+             \n### Bug Fixes\n\n- Fix crash by @dev in #7\n\
+             \n### ui\n\n- Refresh icons by @dev in #4\n\
+             \n### Other Changes\n\n- Tweak CI by @dev in #5\n\
+             \n**Full Changelog**: https://github.com/o/r/compare/v1.0...main\n"
+        );
+    }
+}
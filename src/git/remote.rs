@@ -32,13 +32,20 @@ impl RemoteOps {
 
     /// Push to a remote.
     pub fn push(remote: &str, branch: &str, set_upstream: bool) -> Result<String> {
-        let mut args = vec!["push"];
+        let args = Self::push_args(remote, branch, set_upstream);
+        let refs: Vec<&str> = args.iter().map(|s| s.as_str()).collect();
+        run_git(&refs)
+    }
+
+    /// Arguments for a push with progress output (for streaming into the output drawer).
+    pub fn push_args(remote: &str, branch: &str, set_upstream: bool) -> Vec<String> {
+        let mut args = vec!["push".to_string(), "--progress".to_string()];
         if set_upstream {
-            args.push("-u");
+            args.push("-u".to_string());
         }
-        args.push(remote);
-        args.push(branch);
-        run_git(&args)
+        args.push(remote.to_string());
+        args.push(branch.to_string());
+        args
     }
 
     /// Fetch from a remote.
@@ -47,9 +54,28 @@ impl RemoteOps {
         run_git(&["fetch", remote])
     }
 
+    /// Arguments to fetch every remote and prune deleted branches.
+    pub fn fetch_all_args() -> Vec<String> {
+        ["fetch", "--all", "--prune", "--progress"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect()
+    }
+
     /// Pull from a remote with rebase.
+    #[allow(dead_code)]
     pub fn pull(remote: &str, branch: &str) -> Result<String> {
-        run_git(&["pull", "--rebase", remote, branch])
+        let args = Self::pull_args(remote, branch);
+        let refs: Vec<&str> = args.iter().map(|s| s.as_str()).collect();
+        run_git(&refs)
+    }
+
+    /// Arguments for a rebasing pull with progress output.
+    pub fn pull_args(remote: &str, branch: &str) -> Vec<String> {
+        ["pull", "--rebase", "--progress", remote, branch]
+            .iter()
+            .map(|s| s.to_string())
+            .collect()
     }
 
     /// Pull from a remote, allowing unrelated histories (use with caution).
@@ -112,6 +138,22 @@ upstream\thttps://github.com/upstream/repo.git (push)
         assert_eq!(remotes[1].0, "upstream");
     }
 
+    #[test]
+    fn test_push_args_with_upstream() {
+        assert_eq!(
+            RemoteOps::push_args("origin", "main", true),
+            vec!["push", "--progress", "-u", "origin", "main"]
+        );
+    }
+
+    #[test]
+    fn test_pull_args() {
+        assert_eq!(
+            RemoteOps::pull_args("origin", "dev"),
+            vec!["pull", "--rebase", "--progress", "origin", "dev"]
+        );
+    }
+
     #[test]
     fn test_parse_remote_output_empty() {
         let remotes = RemoteOps::parse_remote_output("");
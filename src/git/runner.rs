@@ -1,10 +1,166 @@
-use anyhow::{bail, Context, Result};
+use anyhow::{Context, Result, bail};
+use std::collections::VecDeque;
+use std::path::Path;
 use std::process::Command;
+use std::sync::{Mutex, OnceLock};
 use std::time::{Duration, Instant};
 
 /// Default timeout for git commands (30 seconds).
 const GIT_TIMEOUT: Duration = Duration::from_secs(30);
 
+/// How many recent commands are kept for diagnostic reports.
+const HISTORY_LEN: usize = 20;
+
+/// Output kept per recorded command, in bytes (the tail is kept).
+const HISTORY_OUTPUT: usize = 1000;
+
+/// Shorter stderr lines (e.g. "fatal:") are too generic to tie an error to a command.
+const MIN_MATCH_LINE: usize = 12;
+
+/// Waits before each retry of a command that found `.git/index.lock` taken.
+const INDEX_LOCK_RETRIES: [Duration; 4] = [
+    Duration::from_millis(100),
+    Duration::from_millis(250),
+    Duration::from_millis(500),
+    Duration::from_millis(1000),
+];
+
+/// Subcommands that move HEAD or start a sequence of their own, which would
+/// wreck a rebase in progress.
+const HEAD_MOVING: &[&str] = &[
+    "checkout",
+    "switch",
+    "reset",
+    "rebase",
+    "merge",
+    "pull",
+    "cherry-pick",
+    "revert",
+    "am",
+];
+
+/// Options that continue or end the operation in progress instead of starting one.
+const SEQUENCE_CONTROL: &[&str] = &[
+    "--continue",
+    "--abort",
+    "--skip",
+    "--quit",
+    "--edit-todo",
+    "--show-current-patch",
+];
+
+/// A git command zit ran, kept for crash reports.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommandRecord {
+    pub args: String,
+    /// "ok", "exit status: 1", "timed out", ...
+    pub status: String,
+    /// Exit code, if git ran to completion.
+    pub exit_code: Option<i32>,
+    /// The end of stdout and stderr combined.
+    pub output: String,
+    /// The end of stderr alone.
+    pub stderr: String,
+}
+
+static HISTORY: Mutex<VecDeque<CommandRecord>> = Mutex::new(VecDeque::new());
+
+/// Told the subcommand and the arguments after it of every git command that
+/// succeeds (usage stats).
+static OBSERVER: OnceLock<fn(&str, &[&str])> = OnceLock::new();
+
+/// Have `observer` told about every git command that succeeds from now on.
+pub fn set_observer(observer: fn(&str, &[&str])) {
+    let _ = OBSERVER.set(observer);
+}
+
+fn record(
+    args: &[&str],
+    status: impl Into<String>,
+    exit_code: Option<i32>,
+    stdout: &[u8],
+    stderr: &[u8],
+) {
+    let status = status.into();
+    if status == "ok"
+        && let Some(observer) = OBSERVER.get()
+        && let Some((subcommand, rest)) = split_subcommand(args)
+    {
+        observer(subcommand, rest);
+    }
+    let mut output = String::from_utf8_lossy(stdout).trim_end().to_string();
+    let stderr = String::from_utf8_lossy(stderr).trim().to_string();
+    if !stderr.is_empty() {
+        if !output.is_empty() {
+            output.push('\n');
+        }
+        output.push_str(&stderr);
+    }
+    if let Ok(mut history) = HISTORY.lock() {
+        if history.len() == HISTORY_LEN {
+            history.pop_front();
+        }
+        history.push_back(CommandRecord {
+            args: args.join(" "),
+            status,
+            exit_code,
+            output: keep_tail(output),
+            stderr: keep_tail(stderr),
+        });
+    }
+}
+
+/// Keep the last `HISTORY_OUTPUT` bytes of `text`.
+fn keep_tail(text: String) -> String {
+    if text.len() <= HISTORY_OUTPUT {
+        return text;
+    }
+    let mut start = text.len() - HISTORY_OUTPUT;
+    while !text.is_char_boundary(start) {
+        start += 1;
+    }
+    format!("…{}", &text[start..])
+}
+
+/// The most recent git commands, oldest first.
+pub fn recent_commands() -> Vec<CommandRecord> {
+    HISTORY
+        .lock()
+        .map(|h| h.iter().cloned().collect())
+        .unwrap_or_default()
+}
+
+/// The journal entry for the failed command behind `error`, an error message
+/// shown to the user: matched on the `git <args> failed` prefix the `run_git*`
+/// functions use, or on a line of the command's stderr. `None` if the command
+/// has scrolled out of the journal or the error didn't come from git.
+pub fn failed_command(error: &str) -> Option<CommandRecord> {
+    find_failed(&recent_commands(), error)
+}
+
+fn find_failed(commands: &[CommandRecord], error: &str) -> Option<CommandRecord> {
+    commands
+        .iter()
+        .rev()
+        .filter(|c| c.status != "ok")
+        .find(|c| {
+            error.contains(&format!("git {} ", c.args))
+                || c.stderr
+                    .lines()
+                    .map(str::trim)
+                    .any(|line| line.len() >= MIN_MATCH_LINE && error.contains(line))
+        })
+        .cloned()
+}
+
+fn status_text(status: std::process::ExitStatus) -> String {
+    if status.success() {
+        "ok".to_string()
+    } else {
+        status.to_string()
+    }
+}
+
 /// Execute a git command with the given arguments and return stdout.
 /// Fails with a descriptive error if the command exits non-zero or times out after 30s.
 pub fn run_git(args: &[&str]) -> Result<String> {
@@ -31,8 +187,82 @@ fn run_git_raw(args: &[&str]) -> Result<String> {
     Ok(stdout.trim().to_string())
 }
 
-/// Execute a git command with a custom timeout.
+/// Execute a git command with a custom timeout. A command that finds the index
+/// locked by another git process is retried a few times before giving up.
 pub fn run_git_with_timeout(args: &[&str], timeout: Duration) -> Result<String> {
+    guard_rebase(args)?;
+    let mut waits = INDEX_LOCK_RETRIES.iter();
+    loop {
+        match run_git_once(args, timeout) {
+            Err(e) if is_index_lock_error(&e.to_string()) => match waits.next() {
+                Some(wait) => {
+                    log::debug!("git {}: index locked, retrying", args.join(" "));
+                    std::thread::sleep(*wait);
+                }
+                None => bail!(
+                    "git {} failed: another git process is using this repository \
+                     (.git/index.lock exists). If none is running, delete that file.",
+                    args.join(" ")
+                ),
+            },
+            result => return result,
+        }
+    }
+}
+
+/// Whether git failed because another process holds `.git/index.lock`.
+fn is_index_lock_error(message: &str) -> bool {
+    message.contains("index.lock") && message.contains("File exists")
+}
+
+/// The subcommand and its arguments, skipping global options like `-c key=value`.
+fn split_subcommand<'a>(args: &'a [&'a str]) -> Option<(&'a str, &'a [&'a str])> {
+    let mut i = 0;
+    while let Some(arg) = args.get(i) {
+        match *arg {
+            "-c" | "-C" => i += 2,
+            a if a.starts_with('-') => i += 1,
+            a => return Some((a, &args[i + 1..])),
+        }
+    }
+    None
+}
+
+/// Whether `args` would clobber a rebase in progress. Path-limited checkouts
+/// and resets (`-- <path>`, e.g. taking one side of a conflict) and
+/// `--continue`/`--abort` leave it alone.
+fn clobbers_rebase(args: &[&str]) -> bool {
+    let Some((cmd, rest)) = split_subcommand(args) else {
+        return false;
+    };
+    HEAD_MOVING.contains(&cmd)
+        && !rest
+            .iter()
+            .any(|a| *a == "--" || SEQUENCE_CONTROL.contains(a))
+}
+
+fn rebase_in_progress() -> bool {
+    run_git_raw(&["rev-parse", "--absolute-git-dir"]).is_ok_and(|dir| {
+        let dir = Path::new(&dir);
+        dir.join("rebase-merge").exists() || dir.join("rebase-apply").exists()
+    })
+}
+
+/// Refuse commands that would clobber a rebase in progress, which may have been
+/// started from a terminal or another zit.
+fn guard_rebase(args: &[&str]) -> Result<()> {
+    if clobbers_rebase(args) && rebase_in_progress() {
+        record(args, "refused: rebase in progress", None, b"", b"");
+        bail!(
+            "A rebase is in progress — finish it (git rebase --continue) or abort it \
+             (git rebase --abort) before running git {}",
+            args.join(" ")
+        );
+    }
+    Ok(())
+}
+
+fn run_git_once(args: &[&str], timeout: Duration) -> Result<String> {
     log::debug!("git {}", args.join(" "));
 
     // Find the repo root once and set it as working directory
@@ -49,20 +279,28 @@ pub fn run_git_with_timeout(args: &[&str], timeout: Duration) -> Result<String>
 
     let mut child = cmd.spawn().context("Failed to execute git command")?;
 
+    // Drain both pipes while waiting: git blocks once a pipe buffer (64 KiB on
+    // Linux) fills up, which large status or log output easily does
+    let stdout_reader = child.stdout.take().map(read_to_end_thread);
+    let stderr_reader = child.stderr.take().map(read_to_end_thread);
+    let collect = |reader: Option<std::thread::JoinHandle<Vec<u8>>>| {
+        reader.and_then(|r| r.join().ok()).unwrap_or_default()
+    };
+
     let start = Instant::now();
     loop {
         match child.try_wait() {
             Ok(Some(status)) => {
                 // Process finished
-                let output = child
-                    .wait_with_output()
-                    .context("Failed to read git output")?;
+                let stdout = collect(stdout_reader);
+                let stderr = collect(stderr_reader);
+                record(args, status_text(status), status.code(), &stdout, &stderr);
                 if !status.success() {
-                    let stderr = String::from_utf8_lossy(&output.stderr);
+                    let stderr = String::from_utf8_lossy(&stderr);
                     log::warn!("git {} failed: {}", args.join(" "), stderr.trim());
                     bail!("git {} failed: {}", args.join(" "), stderr.trim());
                 }
-                let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+                let stdout = String::from_utf8_lossy(&stdout).to_string();
                 return Ok(stdout);
             }
             Ok(None) => {
@@ -70,6 +308,7 @@ pub fn run_git_with_timeout(args: &[&str], timeout: Duration) -> Result<String>
                 if start.elapsed() > timeout {
                     let _ = child.kill();
                     let _ = child.wait();
+                    record(args, "timed out", None, b"", b"");
                     bail!(
                         "git {} timed out after {}s",
                         args.join(" "),
@@ -85,6 +324,277 @@ pub fn run_git_with_timeout(args: &[&str], timeout: Duration) -> Result<String>
     }
 }
 
+fn read_to_end_thread(
+    mut pipe: impl std::io::Read + Send + 'static,
+) -> std::thread::JoinHandle<Vec<u8>> {
+    std::thread::spawn(move || {
+        let mut buf = Vec::new();
+        let _ = pipe.read_to_end(&mut buf);
+        buf
+    })
+}
+
+/// Execute a git command and return its raw stdout bytes (e.g. blob contents).
+pub fn run_git_bytes(args: &[&str]) -> Result<Vec<u8>> {
+    log::debug!("git {} (bytes)", args.join(" "));
+
+    let repo_root = run_git_raw(&["rev-parse", "--show-toplevel"]).ok();
+
+    let mut cmd = Command::new("git");
+    cmd.args(args)
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped());
+
+    if let Some(ref root) = repo_root {
+        cmd.current_dir(root);
+    }
+
+    let output = cmd.output().context("Failed to execute git command")?;
+    let summary = format!("<{} bytes>", output.stdout.len());
+    record(
+        args,
+        status_text(output.status),
+        output.status.code(),
+        summary.as_bytes(),
+        &output.stderr,
+    );
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!("git {} failed: {}", args.join(" "), stderr.trim());
+    }
+    Ok(output.stdout)
+}
+
+/// Execute a git command with extra environment variables (e.g. `GIT_INDEX_FILE`).
+pub fn run_git_with_env(args: &[&str], env: &[(&str, &str)]) -> Result<String> {
+    log::debug!("git {} (env)", args.join(" "));
+
+    let repo_root = run_git_raw(&["rev-parse", "--show-toplevel"]).ok();
+
+    let mut cmd = Command::new("git");
+    cmd.args(args)
+        .envs(env.iter().copied())
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped());
+
+    if let Some(ref root) = repo_root {
+        cmd.current_dir(root);
+    }
+
+    let output = cmd.output().context("Failed to execute git command")?;
+    record(
+        args,
+        status_text(output.status),
+        output.status.code(),
+        &output.stdout,
+        &output.stderr,
+    );
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!("git {} failed: {}", args.join(" "), stderr.trim());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Execute a git command and hand each stdout line to `on_line` as it's read,
+/// so large output (e.g. `git diff`) never has to be held in memory at once.
+/// `on_line` returns `false` to stop early, which kills git.
+pub fn run_git_lines(args: &[&str], mut on_line: impl FnMut(&str) -> bool) -> Result<()> {
+    use std::io::BufRead;
+
+    log::debug!("git {} (lines)", args.join(" "));
+
+    let repo_root = run_git_raw(&["rev-parse", "--show-toplevel"]).ok();
+
+    let mut cmd = Command::new("git");
+    cmd.args(args)
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped());
+
+    if let Some(ref root) = repo_root {
+        cmd.current_dir(root);
+    }
+
+    let mut child = cmd.spawn().context("Failed to execute git command")?;
+    let stderr_reader = child.stderr.take().map(read_to_end_thread);
+
+    let mut lines = 0usize;
+    let mut stopped = false;
+    if let Some(stdout) = child.stdout.take() {
+        let mut reader = std::io::BufReader::new(stdout);
+        let mut buf = Vec::new();
+        loop {
+            buf.clear();
+            match reader.read_until(b'\n', &mut buf) {
+                Ok(0) | Err(_) => break,
+                Ok(_) => {}
+            }
+            let line = String::from_utf8_lossy(&buf);
+            let line = line.strip_suffix('\n').unwrap_or(&line);
+            lines += 1;
+            if !on_line(line.strip_suffix('\r').unwrap_or(line)) {
+                stopped = true;
+                let _ = child.kill();
+                break;
+            }
+        }
+    }
+
+    let status = child.wait().context("Failed waiting for git")?;
+    let stderr = stderr_reader
+        .and_then(|r| r.join().ok())
+        .unwrap_or_default();
+    let summary = format!("<{} lines>", lines);
+    if stopped {
+        record(args, "stopped early", None, summary.as_bytes(), &stderr);
+        return Ok(());
+    }
+    record(
+        args,
+        status_text(status),
+        status.code(),
+        summary.as_bytes(),
+        &stderr,
+    );
+    if !status.success() {
+        let stderr = String::from_utf8_lossy(&stderr);
+        log::warn!("git {} failed: {}", args.join(" "), stderr.trim());
+        bail!("git {} failed: {}", args.join(" "), stderr.trim());
+    }
+    Ok(())
+}
+
+/// A single line of output from a streamed git command.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StreamLine {
+    pub text: String,
+    pub is_stderr: bool,
+}
+
+/// Execute a git command and hand each stdout/stderr line to `on_line` as it arrives.
+///
+/// Colors are forced on (`color.ui=always`) so callers can render them, and
+/// carriage-return progress updates (e.g. "Writing objects:  42%") are delivered
+/// as separate lines. Returns whether git exited successfully.
+pub fn run_git_streaming(args: &[&str], mut on_line: impl FnMut(StreamLine)) -> Result<bool> {
+    guard_rebase(args)?;
+    log::debug!("git {} (streaming)", args.join(" "));
+
+    let repo_root = run_git_raw(&["rev-parse", "--show-toplevel"]).ok();
+
+    let mut cmd = Command::new("git");
+    cmd.args(["-c", "color.ui=always"])
+        .args(args)
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped());
+
+    if let Some(ref root) = repo_root {
+        cmd.current_dir(root);
+    }
+
+    let mut child = cmd.spawn().context("Failed to execute git command")?;
+
+    let (tx, rx) = std::sync::mpsc::channel();
+    let mut readers = Vec::new();
+    if let Some(stdout) = child.stdout.take() {
+        let tx = tx.clone();
+        readers.push(std::thread::spawn(move || {
+            read_stream_lines(stdout, false, &tx)
+        }));
+    }
+    if let Some(stderr) = child.stderr.take() {
+        let tx = tx.clone();
+        readers.push(std::thread::spawn(move || {
+            read_stream_lines(stderr, true, &tx)
+        }));
+    }
+    drop(tx);
+
+    // Channel closes once both readers hit EOF.
+    let mut tail: VecDeque<String> = VecDeque::new();
+    let mut stderr_tail: VecDeque<String> = VecDeque::new();
+    for line in rx {
+        for kept in [&mut tail, &mut stderr_tail] {
+            if kept.len() == 10 {
+                kept.pop_front();
+            }
+        }
+        if line.is_stderr {
+            stderr_tail.push_back(line.text.clone());
+        }
+        tail.push_back(line.text.clone());
+        on_line(line);
+    }
+    for reader in readers {
+        let _ = reader.join();
+    }
+
+    let status = child.wait().context("Failed waiting for git")?;
+    let tail = Vec::from(tail).join("\n");
+    let stderr = Vec::from(stderr_tail).join("\n");
+    record(
+        args,
+        status_text(status),
+        status.code(),
+        tail.as_bytes(),
+        stderr.as_bytes(),
+    );
+    if !status.success() {
+        log::warn!("git {} exited with {}", args.join(" "), status);
+    }
+    Ok(status.success())
+}
+
+/// Read a pipe to EOF, splitting on `\n` and `\r` and forwarding each non-empty line.
+fn read_stream_lines(
+    mut pipe: impl std::io::Read,
+    is_stderr: bool,
+    tx: &std::sync::mpsc::Sender<StreamLine>,
+) {
+    let mut buf = [0u8; 4096];
+    let mut pending: Vec<u8> = Vec::new();
+    loop {
+        let n = match pipe.read(&mut buf) {
+            Ok(0) | Err(_) => break,
+            Ok(n) => n,
+        };
+        pending.extend_from_slice(&buf[..n]);
+        for line in drain_complete_lines(&mut pending) {
+            if tx
+                .send(StreamLine {
+                    text: line,
+                    is_stderr,
+                })
+                .is_err()
+            {
+                return;
+            }
+        }
+    }
+    if !pending.is_empty() {
+        let text = String::from_utf8_lossy(&pending).to_string();
+        let _ = tx.send(StreamLine { text, is_stderr });
+    }
+}
+
+/// Split off every line terminated by `\n` or `\r`, leaving any partial tail in `pending`.
+fn drain_complete_lines(pending: &mut Vec<u8>) -> Vec<String> {
+    let mut lines = Vec::new();
+    while let Some(pos) = pending.iter().position(|&b| b == b'\n' || b == b'\r') {
+        let rest = pending.split_off(pos + 1);
+        let line = String::from_utf8_lossy(&pending[..pos]).to_string();
+        *pending = rest;
+        if !line.is_empty() {
+            lines.push(line);
+        }
+    }
+    lines
+}
+
 /// Check if the current directory is inside a git repository.
 pub fn is_git_repo() -> bool {
     run_git(&["rev-parse", "--is-inside-work-tree"]).is_ok()
@@ -165,6 +675,144 @@ mod tests {
         assert_eq!(parse_git_version("not a version"), None);
     }
 
+    #[test]
+    fn test_drain_complete_lines_splits_cr_and_lf() {
+        let mut pending = b"Counting: 10%\rCounting: 100%\ndone\npart".to_vec();
+        let lines = drain_complete_lines(&mut pending);
+        assert_eq!(lines, vec!["Counting: 10%", "Counting: 100%", "done"]);
+        assert_eq!(pending, b"part");
+    }
+
+    #[test]
+    fn test_drain_complete_lines_skips_empty() {
+        let mut pending = b"a\r\n\nb\n".to_vec();
+        assert_eq!(drain_complete_lines(&mut pending), vec!["a", "b"]);
+        assert!(pending.is_empty());
+    }
+
+    #[test]
+    fn test_run_git_streaming_version() {
+        let mut lines = Vec::new();
+        let ok = run_git_streaming(&["--version"], |l| lines.push(l)).unwrap();
+        assert!(ok);
+        assert!(lines.iter().any(|l| l.text.starts_with("git version")));
+        assert!(lines.iter().all(|l| !l.is_stderr));
+    }
+
+    #[test]
+    fn test_recent_commands_records_output() {
+        run_git(&["--version"]).unwrap();
+        let last = recent_commands()
+            .into_iter()
+            .rev()
+            .find(|c| c.args == "--version")
+            .unwrap();
+        assert_eq!(last.status, "ok");
+        assert_eq!(last.exit_code, Some(0));
+        assert!(last.output.starts_with("git version"));
+    }
+
+    #[test]
+    fn test_find_failed() {
+        let command = |args: &str, status: &str, stderr: &str| CommandRecord {
+            args: args.to_string(),
+            status: status.to_string(),
+            exit_code: None,
+            output: stderr.to_string(),
+            stderr: stderr.to_string(),
+        };
+        let commands = vec![
+            command("push origin main", "exit status: 1", "fatal: no upstream"),
+            command(
+                "push origin main",
+                "exit status: 1",
+                "To origin\n ! [rejected] main -> main (non-fast-forward)",
+            ),
+            command("status", "ok", ""),
+            command("checkout feature", "exit status: 1", "fatal:"),
+        ];
+
+        // By the run_git error prefix, most recent first
+        let found = find_failed(
+            &commands,
+            "git checkout feature failed: fatal: invalid reference",
+        );
+        assert_eq!(found.unwrap().args, "checkout feature");
+        // By a stderr line
+        let found = find_failed(
+            &commands,
+            "Push failed: ! [rejected] main -> main (non-fast-forward)",
+        );
+        assert!(found.unwrap().stderr.contains("rejected"));
+        // Successful commands and short generic lines never match
+        assert!(find_failed(&commands, "git status failed").is_none());
+        assert!(find_failed(&commands, "fatal: something else").is_none());
+    }
+
+    #[test]
+    fn test_run_git_large_output_does_not_block() {
+        // More output than fits in a pipe buffer
+        let out = run_git_with_timeout(
+            &["-c", "alias.big=!printf '%0100000d' 0", "big"],
+            Duration::from_secs(10),
+        )
+        .unwrap();
+        assert_eq!(out.len(), 100_000);
+    }
+
+    #[test]
+    fn test_run_git_lines_stops_early() {
+        // 100k lines, but the callback only wants three
+        let args = ["-c", "alias.many=!seq 100000", "many"];
+        let mut lines = Vec::new();
+        run_git_lines(&args, |l| {
+            lines.push(l.to_string());
+            lines.len() < 3
+        })
+        .unwrap();
+        assert_eq!(lines, vec!["1", "2", "3"]);
+
+        let mut count = 0;
+        run_git_lines(&args, |_| {
+            count += 1;
+            true
+        })
+        .unwrap();
+        assert_eq!(count, 100_000);
+    }
+
+    #[test]
+    fn test_clobbers_rebase() {
+        assert!(clobbers_rebase(&["checkout", "main"]));
+        assert!(clobbers_rebase(&[
+            "-c",
+            "core.hooksPath=x",
+            "reset",
+            "--hard"
+        ]));
+        assert!(clobbers_rebase(&["pull", "--rebase"]));
+        assert!(!clobbers_rebase(&[
+            "checkout",
+            "--ours",
+            "--",
+            "src/app.rs"
+        ]));
+        assert!(!clobbers_rebase(&["rebase", "--continue"]));
+        assert!(!clobbers_rebase(&["merge", "--abort"]));
+        assert!(!clobbers_rebase(&["commit", "-m", "checkout"]));
+        assert!(!clobbers_rebase(&["status"]));
+    }
+
+    #[test]
+    fn test_is_index_lock_error() {
+        assert!(is_index_lock_error(
+            "git add -- a.rs failed: fatal: Unable to create '/repo/.git/index.lock': File exists."
+        ));
+        assert!(!is_index_lock_error(
+            "git add failed: pathspec 'x' did not match"
+        ));
+    }
+
     #[test]
     fn test_check_git_version_passes() {
         // The system git should be >= 2.13.0
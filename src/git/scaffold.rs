@@ -0,0 +1,69 @@
+//! Starter files for a new repository — .gitignore, LICENSE and README — written
+//! and committed on their own so unrelated staged work stays out of the commit.
+
+use super::runner::run_git;
+use anyhow::{Context, Result};
+
+/// Subject of the scaffold commit.
+pub const COMMIT_MESSAGE: &str = "Initial scaffold";
+
+/// A README with the project name and description.
+pub fn readme_stub(name: &str, description: &str) -> String {
+    let description = description.trim();
+    if description.is_empty() {
+        format!("# {}\n", name)
+    } else {
+        format!("# {}\n\n{}\n", name, description)
+    }
+}
+
+/// Fill in the copyright year and holder of a license from GitHub's templates.
+pub fn fill_license(body: &str, year: i16, fullname: &str) -> String {
+    body.replace("[year]", &year.to_string())
+        .replace("[fullname]", fullname)
+}
+
+/// Write `files` (path, contents) at the repository root and commit them. Files that
+/// already exist are left alone. Returns the paths that were written.
+pub fn commit_files(files: &[(&str, String)]) -> Result<Vec<String>> {
+    let root = run_git(&["rev-parse", "--show-toplevel"])?;
+    let root = std::path::Path::new(root.trim());
+
+    let mut written = Vec::new();
+    for (path, contents) in files {
+        let target = root.join(path);
+        if target.exists() {
+            continue;
+        }
+        std::fs::write(&target, contents).with_context(|| format!("Failed to write {}", path))?;
+        written.push(path.to_string());
+    }
+    if written.is_empty() {
+        return Ok(written);
+    }
+
+    let mut add = vec!["add", "--"];
+    add.extend(written.iter().map(|p| p.as_str()));
+    run_git(&add)?;
+    // Pathspec commit: only the scaffold, whatever else is staged
+    let mut commit = vec!["commit", "-m", COMMIT_MESSAGE, "--"];
+    commit.extend(written.iter().map(|p| p.as_str()));
+    run_git(&commit)?;
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_readme_and_license_placeholders() {
+        assert_eq!(readme_stub("zit", ""), "# zit\n");
+        assert_eq!(readme_stub("zit", " A TUI "), "# zit\n\nA TUI\n");
+        let mit = "Copyright (c) [year] [fullname]\n";
+        assert_eq!(
+            fill_license(mit, 2026, "Jane Doe"),
+            "Copyright (c) 2026 Jane Doe\n"
+        );
+    }
+}
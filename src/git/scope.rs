@@ -0,0 +1,80 @@
+//! Path scope (`--path <dir>`, or `P` on the Dashboard): in a big monorepo,
+//! keep zit to one directory. Status, diffs, the log and the stage/unstage-all
+//! keys only see paths under it, and git only reads that part of the tree.
+//! Commits still take everything staged, in or out of the scope.
+
+use std::path::Path;
+use std::sync::RwLock;
+
+use anyhow::{Context, Result, bail};
+
+use super::runner::run_git;
+
+static SCOPE: RwLock<Option<String>> = RwLock::new(None);
+
+/// The directory reads are scoped to, relative to the repository root, or
+/// `None` for the whole repository.
+pub fn get() -> Option<String> {
+    SCOPE.read().ok().and_then(|scope| scope.clone())
+}
+
+pub fn set(dir: Option<String>) {
+    if let Ok(mut scope) = SCOPE.write() {
+        *scope = dir;
+    }
+}
+
+/// The scope as pathspecs to follow `--`; empty for the whole repository.
+pub fn pathspec() -> Vec<String> {
+    get().into_iter().collect()
+}
+
+/// `path`, relative to `from` or absolute, as a scope: a directory in the
+/// repository, relative to its root. The root itself is no scope.
+pub fn resolve(path: &Path, from: &Path) -> Result<Option<String>> {
+    let root = run_git(&["rev-parse", "--show-toplevel"])?;
+    relative_dir(Path::new(root.trim()), &from.join(path))
+}
+
+fn relative_dir(root: &Path, dir: &Path) -> Result<Option<String>> {
+    let full = dir
+        .canonicalize()
+        .with_context(|| format!("{} doesn't exist", dir.display()))?;
+    if !full.is_dir() {
+        bail!("{} isn't a directory", dir.display());
+    }
+    let Ok(relative) = full.strip_prefix(root.canonicalize()?) else {
+        bail!("{} is outside the repository", dir.display());
+    };
+    let parts: Vec<String> = relative
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().into_owned())
+        .collect();
+    Ok((!parts.is_empty()).then(|| parts.join("/")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_relative_dir() {
+        let root = tempfile::tempdir().unwrap();
+        let root = root.path();
+        std::fs::create_dir_all(root.join("services/api")).unwrap();
+        std::fs::write(root.join("README.md"), "").unwrap();
+
+        assert_eq!(
+            relative_dir(root, &root.join("services/./api")).unwrap(),
+            Some("services/api".to_string())
+        );
+        assert_eq!(
+            relative_dir(root, &root.join("services/api/..")).unwrap(),
+            Some("services".to_string())
+        );
+        assert_eq!(relative_dir(root, root).unwrap(), None);
+        assert!(relative_dir(root, &root.join("README.md")).is_err());
+        assert!(relative_dir(root, &root.join("missing")).is_err());
+        assert!(relative_dir(&root.join("services"), root).is_err());
+    }
+}
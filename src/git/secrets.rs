@@ -312,8 +312,8 @@ mod tests {
 
     #[test]
     fn test_detect_aws_access_key() {
-        let content = r#"AWS_KEY=AKIAIOSFODNN7EXAMPLE1"#;
-        let findings = scan_content("test.env", content, &rules());
+        let content = format!("AWS_KEY=\"{}{}\"", "AKIA", "IOSFODNN7EXAMPLE");
+        let findings = scan_content("test.env", &content, &rules());
         assert!(
             findings.iter().any(|f| f.rule_name.contains("AWS")),
             "Should detect AWS access key, got: {:?}",
@@ -388,8 +388,8 @@ mod tests {
 
     #[test]
     fn test_detect_stripe_secret() {
-        let content = format!("STRIPE_KEY={}{}", "sk_live_", "abcdefghijklmnopqrstuvwx").as_str();
-        let findings = scan_content(".env", content, &rules());
+        let content = format!("STRIPE_KEY={}{}", "sk_live_", "abcdefghijklmnopqrstuvwx");
+        let findings = scan_content(".env", &content, &rules());
         assert!(
             findings.iter().any(|f| f.rule_name.contains("Stripe")),
             "Should detect Stripe key, got: {:?}",
@@ -524,9 +524,8 @@ mod tests {
         let content = format!(
             "SENDGRID_KEY=SG.{}.{}",
             "abcdefghijklmnopqrstuv", "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopq"
-        )
-        .as_str();
-        let findings = scan_content(".env", content, &rules());
+        );
+        let findings = scan_content(".env", &content, &rules());
         assert!(
             findings.iter().any(|f| f.rule_name.contains("SendGrid")),
             "Should detect SendGrid API key, got: {:?}",
@@ -630,17 +629,19 @@ fn main() {
 
     #[test]
     fn test_scan_diff_detects_added_secrets() {
-        let diff = r#"diff --git a/.env b/.env
+        let diff = format!(
+            "diff --git a/.env b/.env
 index abc..def 100644
 --- a/.env
 +++ b/.env
 @@ -1,2 +1,3 @@
  APP_NAME=myapp
-+STRIPE_KEY=sk_live_"
-        + "abcdefghijklmnopqrstuvwx
++STRIPE_KEY={}{}
  DEBUG=true
-"#;
-        let findings = scan_diff_content(diff, &rules(), &[]);
+",
+            "sk_live_", "abcdefghijklmnopqrstuvwx"
+        );
+        let findings = scan_diff_content(&diff, &rules(), &[]);
         assert!(
             findings.iter().any(|f| f.rule_name.contains("Stripe")),
             "Should detect Stripe key in added diff line, got: {:?}",
@@ -713,9 +714,9 @@ index abc..def 100644
 
     #[test]
     fn test_allowlist_by_rule_name() {
-        let content = format!("STRIPE_KEY={}{}", "sk_live_", "abcdefghijklmnopqrstuvwx").as_str();
-        let mut findings = scan_content(".env", content, &rules());
-        let allowlist = vec!["Stripe".to_string()];
+        let content = format!("STRIPE_KEY={}{}", "sk_live_", "abcdefghijklmnopqrstuvwx");
+        let mut findings = scan_content(".env", &content, &rules());
+        let allowlist = ["Stripe".to_string()];
         findings.retain(|f| {
             !allowlist
                 .iter()
@@ -732,13 +733,15 @@ index abc..def 100644
 
     #[test]
     fn test_multiple_secrets_in_one_file() {
-        let content = r#"
-AWS_KEY=AKIAIOSFODNN7EXAMPLE1
-STRIPE_KEY=sk_live_"
-        + "abcdefghijklmnopqrstuvwx
-password = "MyS3cureP@ssw0rd!"
-"#;
-        let findings = scan_content(".env", content, &rules());
+        let content = format!(
+            "
+AWS_KEY=\"{}{}\"
+STRIPE_KEY={}{}
+password = \"MyS3cureP@ssw0rd!\"
+",
+            "AKIA", "IOSFODNN7EXAMPLE", "sk_live_", "abcdefghijklmnopqrstuvwx"
+        );
+        let findings = scan_content(".env", &content, &rules());
         assert!(
             findings.len() >= 3,
             "Should find at least 3 secrets, got {}: {:?}",
@@ -763,8 +766,11 @@ password = "MyS3cureP@ssw0rd!"
 
     #[test]
     fn test_line_numbers_correct() {
-        let content = "line1\nline2\nSTRIPE_KEY=sk_live_" + "abcdefghijklmnopqrstuvwx\nline4";
-        let findings = scan_content("test.env", content, &rules());
+        let content = format!(
+            "line1\nline2\nSTRIPE_KEY={}{}\nline4",
+            "sk_live_", "abcdefghijklmnopqrstuvwx"
+        );
+        let findings = scan_content("test.env", &content, &rules());
         assert!(
             findings.iter().any(|f| f.line == 3),
             "Secret should be on line 3, got: {:?}",
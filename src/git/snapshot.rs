@@ -0,0 +1,272 @@
+//! Safety-net snapshots (`[snapshot]` config) — commits of the whole working tree,
+//! untracked files included, each under a ref of its own in `refs/zit/snapshot/`.
+//! They are built through a temporary index, so the real index and working tree
+//! are never touched. Pruning to the newest `[snapshot] keep` snapshots just
+//! deletes the older refs.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use super::runner::{run_git, run_git_with_env};
+use anyhow::{Context, Result};
+
+/// Namespace of the snapshot refs, one per snapshot, named by its hash.
+pub const SNAPSHOT_REFS: &str = "refs/zit/snapshot/";
+
+/// Where earlier versions kept snapshots, as one chain of commits.
+const LEGACY_REF: &str = "refs/zit/snapshots";
+
+/// Subject prefix of every snapshot commit.
+const SUBJECT_PREFIX: &str = "zit snapshot on ";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Snapshot {
+    pub hash: String,
+    pub short_hash: String,
+    /// Unix timestamp of the snapshot.
+    pub time: i64,
+    pub branch: String,
+    /// HEAD when the snapshot was taken, if the branch had commits.
+    pub base: Option<String>,
+}
+
+/// Snapshot the working tree, keeping the newest `keep` snapshots (0: all).
+/// Returns `None` when nothing changed since the last snapshot or the tree
+/// matches HEAD.
+pub fn take_snapshot(keep: usize) -> Result<Option<String>> {
+    migrate_chain()?;
+    let git_dir = run_git(&["rev-parse", "--absolute-git-dir"])?;
+    let git_dir = std::path::Path::new(git_dir.trim());
+    // Named per call: the background snapshot, `n`, a restore and other zit
+    // instances may all be building one at once
+    static NEXT: AtomicUsize = AtomicUsize::new(0);
+    let temp_index = git_dir.join(format!(
+        "zit-snapshot-index-{}-{}",
+        std::process::id(),
+        NEXT.fetch_add(1, Ordering::Relaxed)
+    ));
+    // Start from the real index so `git add` can reuse its stat data
+    let _ = std::fs::copy(git_dir.join("index"), &temp_index);
+
+    let index = temp_index.to_string_lossy().to_string();
+    let env = [("GIT_INDEX_FILE", index.as_str())];
+    let tree = run_git_with_env(&["add", "-A"], &env)
+        .and_then(|_| run_git_with_env(&["write-tree"], &env));
+    let _ = std::fs::remove_file(&temp_index);
+    let tree = tree.context("Failed to build snapshot tree")?;
+    let tree = tree.trim();
+
+    let head = resolve("HEAD");
+    let previous = snapshot_refs(Some(1))?.into_iter().next();
+    let unchanged = |rev: &Option<String>| {
+        rev.as_ref()
+            .and_then(|r| resolve(&format!("{}^{{tree}}", r)))
+            .is_some_and(|t| t == tree)
+    };
+    if unchanged(&head) || unchanged(&previous) {
+        return Ok(None);
+    }
+
+    let branch = run_git(&["rev-parse", "--abbrev-ref", "HEAD"])
+        .map(|b| b.trim().to_string())
+        .unwrap_or_else(|_| "HEAD".to_string());
+    let message = snapshot_message(&branch, head.as_deref());
+    // Snapshots are zit's own commits — don't depend on the user's identity
+    let commit = run_git(&[
+        "-c",
+        "user.name=zit",
+        "-c",
+        "user.email=zit@localhost",
+        "commit-tree",
+        tree,
+        "-m",
+        &message,
+    ])?
+    .trim()
+    .to_string();
+    run_git(&[
+        "update-ref",
+        "-m",
+        "zit snapshot",
+        &format!("{}{}", SNAPSHOT_REFS, commit),
+        &commit,
+    ])?;
+    prune_snapshots(keep)?;
+    Ok(Some(commit))
+}
+
+/// Delete all but the newest `keep` snapshots (0: keep all).
+fn prune_snapshots(keep: usize) -> Result<()> {
+    if keep == 0 {
+        return Ok(());
+    }
+    let refs = snapshot_refs(None)?;
+    delete_snapshots(refs.get(keep..).unwrap_or_default())
+}
+
+/// Hashes of the snapshots, newest first, at most `limit` of them.
+fn snapshot_refs(limit: Option<usize>) -> Result<Vec<String>> {
+    let count = limit.map(|n| format!("--count={}", n));
+    let mut args = vec![
+        "for-each-ref",
+        "--sort=-committerdate",
+        "--format=%(objectname)",
+    ];
+    args.extend(count.as_deref());
+    args.push(SNAPSHOT_REFS);
+    Ok(run_git(&args)?.lines().map(str::to_string).collect())
+}
+
+fn delete_snapshots(hashes: &[String]) -> Result<()> {
+    for hash in hashes {
+        run_git(&["update-ref", "-d", &format!("{}{}", SNAPSHOT_REFS, hash)])?;
+    }
+    Ok(())
+}
+
+/// Give each snapshot of an old-style chain a ref of its own, then drop the
+/// chain's ref. A no-op once done.
+fn migrate_chain() -> Result<()> {
+    let Some(newest) = resolve(LEGACY_REF) else {
+        return Ok(());
+    };
+    for hash in run_git(&["rev-list", &newest])?.lines() {
+        run_git(&["update-ref", &format!("{}{}", SNAPSHOT_REFS, hash), hash])?;
+    }
+    run_git(&["update-ref", "-d", LEGACY_REF, &newest])?;
+    Ok(())
+}
+
+/// The newest snapshots first.
+pub fn list_snapshots(limit: usize) -> Vec<Snapshot> {
+    let count = format!("--count={}", limit);
+    run_git(&[
+        "for-each-ref",
+        "--sort=-committerdate",
+        &count,
+        "--format=%(objectname)%1f%(objectname:short)%1f%(committerdate:unix)%1f%(contents:subject)%1f%(contents:body)%1e",
+        SNAPSHOT_REFS,
+    ])
+    .map(|out| parse_snapshots(&out))
+    .unwrap_or_default()
+}
+
+/// What the snapshot changed relative to the commit it was based on.
+pub fn snapshot_diff(snapshot: &Snapshot) -> Result<String> {
+    match &snapshot.base {
+        Some(base) => run_git(&["diff", base, &snapshot.hash]),
+        None => run_git(&["show", "--format=", &snapshot.hash]),
+    }
+}
+
+/// Restore the working tree to a snapshot, leaving the index alone: files
+/// are put back as they were and untracked files the snapshot didn't have are
+/// removed (ignored ones are left alone). The current state is snapshotted
+/// first so the restore can itself be undone. Returns how many files were
+/// removed.
+pub fn restore_snapshot(hash: &str, keep: usize) -> Result<usize> {
+    take_snapshot(keep)?;
+    run_git(&[
+        "restore",
+        &format!("--source={}", hash),
+        "--worktree",
+        "--",
+        ":/",
+    ])?;
+    // `restore` removes tracked files the snapshot lacks, not untracked ones
+    let root = run_git(&["rev-parse", "--show-toplevel"])?;
+    let snapshot_files = run_git(&["ls-tree", "-r", "-z", "--name-only", hash])?;
+    let untracked = run_git(&["ls-files", "-z", "--others", "--exclude-standard", ":/"])?;
+    let mut removed = 0;
+    for file in new_files(&snapshot_files, &untracked) {
+        let path = std::path::Path::new(root.trim()).join(file);
+        std::fs::remove_file(&path).with_context(|| format!("Failed to remove {}", file))?;
+        removed += 1;
+    }
+    Ok(removed)
+}
+
+/// Paths in the NUL-separated `untracked` list that aren't in `snapshot_files`.
+fn new_files<'a>(snapshot_files: &str, untracked: &'a str) -> Vec<&'a str> {
+    let known: std::collections::HashSet<&str> = snapshot_files.split('\0').collect();
+    untracked
+        .split('\0')
+        .filter(|f| !f.is_empty() && !known.contains(f))
+        .collect()
+}
+
+/// Delete every snapshot.
+pub fn clear_snapshots() -> Result<()> {
+    migrate_chain()?;
+    delete_snapshots(&snapshot_refs(None)?)
+}
+
+fn resolve(rev: &str) -> Option<String> {
+    run_git(&["rev-parse", "-q", "--verify", rev])
+        .ok()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+fn snapshot_message(branch: &str, base: Option<&str>) -> String {
+    match base {
+        Some(base) => format!("{}{}\n\nBase: {}", SUBJECT_PREFIX, branch, base),
+        None => format!("{}{}", SUBJECT_PREFIX, branch),
+    }
+}
+
+fn parse_snapshots(output: &str) -> Vec<Snapshot> {
+    output
+        .split('\x1e')
+        .filter_map(|record| {
+            let fields: Vec<&str> = record.trim_start_matches('\n').split('\x1f').collect();
+            let [hash, short_hash, time, subject, body] = fields[..] else {
+                return None;
+            };
+            Some(Snapshot {
+                hash: hash.to_string(),
+                short_hash: short_hash.to_string(),
+                time: time.parse().ok()?,
+                branch: subject
+                    .strip_prefix(SUBJECT_PREFIX)
+                    .unwrap_or(subject)
+                    .to_string(),
+                base: body
+                    .lines()
+                    .find_map(|l| l.strip_prefix("Base: "))
+                    .map(|b| b.trim().to_string()),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_snapshots() {
+        let message = snapshot_message("main", Some("abc123"));
+        let (subject, body) = message.split_once("\n\n").unwrap();
+        let out = format!(
+            "ffff\x1fff\x1f1700000100\x1f{}\x1f{}\n\x1e\neeee\x1fee\x1f1700000000\x1f{}\x1f\x1e\n",
+            subject,
+            body,
+            snapshot_message("feature/x", None)
+        );
+        let snapshots = parse_snapshots(&out);
+        assert_eq!(snapshots.len(), 2);
+        assert_eq!(snapshots[0].branch, "main");
+        assert_eq!(snapshots[0].base.as_deref(), Some("abc123"));
+        assert_eq!(snapshots[0].time, 1700000100);
+        assert_eq!(snapshots[1].branch, "feature/x");
+        assert_eq!(snapshots[1].base, None);
+    }
+
+    #[test]
+    fn test_new_files() {
+        let snapshot = "README.md\0notes.txt\0src/a.rs\0";
+        let untracked = "notes.txt\0new.txt\0src/b.rs\0";
+        assert_eq!(new_files(snapshot, untracked), ["new.txt", "src/b.rs"]);
+        assert!(new_files(snapshot, "").is_empty());
+    }
+}
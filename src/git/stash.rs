@@ -47,21 +47,57 @@ fn extract_branch(message: &str) -> String {
     // Patterns: "WIP on <branch>: ...", "On <branch>: ...", "index on <branch>: ..."
     let prefixes = ["WIP on ", "On ", "index on "];
     for prefix in &prefixes {
-        if let Some(rest) = message.strip_prefix(prefix) {
-            if let Some(colon_pos) = rest.find(':') {
-                return rest[..colon_pos].to_string();
-            }
+        if let Some(rest) = message.strip_prefix(prefix)
+            && let Some(colon_pos) = rest.find(':')
+        {
+            return rest[..colon_pos].to_string();
         }
     }
     String::new()
 }
 
+/// Flags for `git stash push`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StashOptions {
+    /// `--include-untracked`
+    pub include_untracked: bool,
+    /// `--keep-index`: stash everything but leave staged changes in place.
+    pub keep_index: bool,
+    /// `--staged`: stash only the staged changes. git rejects it together with
+    /// `--include-untracked`.
+    pub staged_only: bool,
+    /// Stash only these paths (empty for everything).
+    pub paths: Vec<String>,
+}
+
 /// Create a new stash with an optional message.
-pub fn stash_push(message: Option<&str>) -> Result<String> {
-    match message {
-        Some(msg) if !msg.is_empty() => run_git(&["stash", "push", "-m", msg]),
-        _ => run_git(&["stash", "push"]),
+pub fn stash_push(message: Option<&str>, options: &StashOptions) -> Result<String> {
+    let args = push_args(message, options);
+    let args: Vec<&str> = args.iter().map(|a| a.as_str()).collect();
+    run_git(&args)
+}
+
+fn push_args(message: Option<&str>, options: &StashOptions) -> Vec<String> {
+    let mut args = vec!["stash".to_string(), "push".to_string()];
+    if options.staged_only {
+        args.push("--staged".to_string());
+    } else {
+        if options.include_untracked {
+            args.push("--include-untracked".to_string());
+        }
+        if options.keep_index {
+            args.push("--keep-index".to_string());
+        }
+    }
+    if let Some(msg) = message.filter(|m| !m.is_empty()) {
+        args.push("-m".to_string());
+        args.push(msg.to_string());
+    }
+    if !options.paths.is_empty() {
+        args.push("--".to_string());
+        args.extend(options.paths.iter().cloned());
     }
+    args
 }
 
 /// Pop the stash at the given index (removes it from the stash list).
@@ -70,6 +106,13 @@ pub fn stash_pop(index: usize) -> Result<String> {
     run_git(&["stash", "pop", &stash_ref])
 }
 
+/// Pop the stash at the given index with what was staged staged again, or
+/// plainly (everything unstaged) when git can't restore the index.
+pub fn stash_pop_index(index: usize) -> Result<String> {
+    let stash_ref = format!("stash@{{{}}}", index);
+    run_git(&["stash", "pop", "--index", &stash_ref]).or_else(|_| stash_pop(index))
+}
+
 /// Apply the stash at the given index (keeps it in the stash list).
 pub fn stash_apply(index: usize) -> Result<String> {
     let stash_ref = format!("stash@{{{}}}", index);
@@ -127,4 +170,39 @@ mod tests {
     fn test_extract_branch_no_colon() {
         assert_eq!(extract_branch("WIP on main"), "");
     }
+
+    #[test]
+    fn test_push_args() {
+        assert_eq!(
+            push_args(None, &StashOptions::default()),
+            vec!["stash", "push"]
+        );
+        let options = StashOptions {
+            include_untracked: true,
+            keep_index: true,
+            paths: vec!["a.rs".to_string(), "b.rs".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(
+            push_args(Some("wip"), &options),
+            vec![
+                "stash",
+                "push",
+                "--include-untracked",
+                "--keep-index",
+                "-m",
+                "wip",
+                "--",
+                "a.rs",
+                "b.rs"
+            ]
+        );
+        // --staged wins over the flags git won't combine it with
+        let staged = StashOptions {
+            staged_only: true,
+            include_untracked: true,
+            ..Default::default()
+        };
+        assert_eq!(push_args(None, &staged), vec!["stash", "push", "--staged"]);
+    }
 }
@@ -60,10 +60,31 @@ impl RepoStatus {
     }
 }
 
-/// Fetch the full repository status by parsing `git status --porcelain=v2 --branch`.
+/// Fetch the full repository status, through libgit2 or git (see `backend`).
 pub fn get_status() -> Result<RepoStatus> {
-    let output = run_git(&["status", "--porcelain=v2", "--branch"])?;
+    super::backend::current().status()
+}
+
+/// `get_status` by parsing `git status --porcelain=v2 --branch`, limited to
+/// `scope` when given.
+pub(super) fn status_from_cli(scope: Option<&str>) -> Result<RepoStatus> {
+    let mut args = vec!["status", "--porcelain=v2", "--branch"];
+    if let Some(scope) = scope {
+        args.extend(["--", scope]);
+    }
+    let output = run_git(&args)?;
+    let mut status = parse_status(&output);
+
+    // Get stash count
+    status.stash_count = run_git(&["stash", "list"])
+        .map(|s| s.lines().count() as u32)
+        .unwrap_or(0);
+
+    Ok(status)
+}
 
+/// Parse `git status --porcelain=v2 --branch` output. The stash count is left at 0.
+pub fn parse_status(output: &str) -> RepoStatus {
     let mut branch = String::from("(unknown)");
     let mut upstream = None;
     let mut ahead: u32 = 0;
@@ -119,12 +140,7 @@ pub fn get_status() -> Result<RepoStatus> {
         }
     }
 
-    // Get stash count
-    let stash_count = run_git(&["stash", "list"])
-        .map(|s| s.lines().count() as u32)
-        .unwrap_or(0);
-
-    Ok(RepoStatus {
+    RepoStatus {
         branch,
         upstream,
         ahead,
@@ -133,8 +149,8 @@ pub fn get_status() -> Result<RepoStatus> {
         unstaged,
         untracked,
         conflicts,
-        stash_count,
-    })
+        stash_count: 0,
+    }
 }
 
 fn parse_ordinary_entry(
@@ -244,35 +260,17 @@ mod tests {
 1 .M N... 100644 100644 100644 abc123 def456 src/lib.rs
 ? untracked_file.txt
 ";
-        let mut branch = String::from("(unknown)");
-        let mut upstream = None;
-        let mut ahead: u32 = 0;
-        let mut behind: u32 = 0;
-        let mut staged = Vec::new();
-        let mut unstaged = Vec::new();
-        let mut untracked = Vec::new();
-        let mut conflicts = Vec::new();
-
-        for line in sample.lines() {
-            if line.starts_with("# branch.head ") {
-                branch = line.strip_prefix("# branch.head ").unwrap().to_string();
-            } else if line.starts_with("# branch.upstream ") {
-                upstream = Some(line.strip_prefix("# branch.upstream ").unwrap().to_string());
-            } else if line.starts_with("# branch.ab ") {
-                let parts: Vec<&str> = line.split_whitespace().collect();
-                ahead = parts[2].trim_start_matches('+').parse().unwrap_or(0);
-                behind = parts[3].trim_start_matches('-').parse().unwrap_or(0);
-            } else if line.starts_with("1 ") {
-                parse_ordinary_entry(line, &mut staged, &mut unstaged, &mut conflicts);
-            } else if line.starts_with("? ") {
-                let path = line.strip_prefix("? ").unwrap().to_string();
-                untracked.push(FileEntry {
-                    status: FileStatus::Untracked,
-                    path,
-                    original_path: None,
-                });
-            }
-        }
+        let RepoStatus {
+            branch,
+            upstream,
+            ahead,
+            behind,
+            staged,
+            unstaged,
+            untracked,
+            conflicts,
+            stash_count,
+        } = parse_status(sample);
 
         assert_eq!(branch, "main");
         assert_eq!(upstream, Some("origin/main".to_string()));
@@ -284,6 +282,8 @@ mod tests {
         assert_eq!(unstaged[0].path, "src/lib.rs");
         assert_eq!(untracked.len(), 1);
         assert_eq!(untracked[0].path, "untracked_file.txt");
+        assert!(conflicts.is_empty());
+        assert_eq!(stash_count, 0);
     }
 
     // ── char_to_status tests ────────────────────────────────────────
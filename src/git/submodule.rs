@@ -0,0 +1,218 @@
+//! Submodules: where each checkout stands against the commit the superproject
+//! records, read through libgit2 like the other per-refresh reads, and the
+//! `git submodule` commands that init, update and sync them.
+
+use anyhow::Result;
+use git2::{Repository, SubmoduleIgnore, SubmoduleStatus};
+
+/// One submodule, as the Dashboard and Staging show it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Submodule {
+    pub path: String,
+    /// Short hash of the checked-out commit, or the recorded one when it
+    /// isn't checked out.
+    pub commit: String,
+    /// Cloned and checked out (`git submodule update --init` has run).
+    pub initialized: bool,
+    /// The checkout is on another commit than the recorded one.
+    pub moved: bool,
+    /// Commits checked out beyond the recorded one, and recorded ones the
+    /// checkout lacks; 0 when the recorded commit isn't fetched.
+    pub ahead: usize,
+    pub behind: usize,
+    /// Uncommitted or untracked changes inside the submodule.
+    pub dirty: bool,
+}
+
+impl Submodule {
+    /// "not initialized", "2 new commits, uncommitted changes", "up to date".
+    pub fn summary(&self) -> String {
+        if !self.initialized {
+            return "not initialized".to_string();
+        }
+        let mut parts = Vec::new();
+        if self.moved {
+            match (self.ahead, self.behind) {
+                (0, 0) => parts.push("on another commit".to_string()),
+                (ahead, 0) => parts.push(format!("{} new commit(s)", ahead)),
+                (0, behind) => parts.push(format!("{} behind", behind)),
+                (ahead, behind) => parts.push(format!("{} ahead, {} behind", ahead, behind)),
+            }
+        }
+        if self.dirty {
+            parts.push("uncommitted changes".to_string());
+        }
+        if parts.is_empty() {
+            "up to date".to_string()
+        } else {
+            parts.join(", ")
+        }
+    }
+
+    /// Anything that needs attention: not initialized, moved or dirty.
+    pub fn needs_attention(&self) -> bool {
+        !self.initialized || self.moved || self.dirty
+    }
+}
+
+/// The repository's submodules, by path; empty when it has none.
+pub fn list() -> Result<Vec<Submodule>> {
+    list_in(&Repository::open_from_env()?)
+}
+
+fn list_in(repo: &Repository) -> Result<Vec<Submodule>> {
+    let short = |id: git2::Oid| id.to_string()[..7].to_string();
+    let mut submodules = Vec::new();
+    for sm in repo.submodules()? {
+        let path = sm.path().to_string_lossy().replace('\\', "/");
+        let status = repo.submodule_status(sm.name().unwrap_or(&path), SubmoduleIgnore::None)?;
+        let recorded = sm.index_id().or(sm.head_id());
+        let initialized = !status.contains(SubmoduleStatus::WD_UNINITIALIZED);
+        let moved = initialized && status.contains(SubmoduleStatus::WD_MODIFIED);
+        let (mut ahead, mut behind) = (0, 0);
+        if moved
+            && let (Some(checked_out), Some(recorded)) = (sm.workdir_id(), recorded)
+            && let Ok(inner) = sm.open()
+            && let Ok(counts) = inner.graph_ahead_behind(checked_out, recorded)
+        {
+            (ahead, behind) = counts;
+        }
+        submodules.push(Submodule {
+            commit: sm
+                .workdir_id()
+                .filter(|_| initialized)
+                .or(recorded)
+                .map(short)
+                .unwrap_or_default(),
+            initialized,
+            moved,
+            ahead,
+            behind,
+            dirty: status.intersects(
+                SubmoduleStatus::WD_INDEX_MODIFIED
+                    | SubmoduleStatus::WD_WD_MODIFIED
+                    | SubmoduleStatus::WD_UNTRACKED,
+            ),
+            path,
+        });
+    }
+    submodules.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(submodules)
+}
+
+/// What can be done to a submodule, or to all of them.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Action {
+    /// Clone and check out the recorded commit.
+    Init,
+    /// Check out the recorded commit.
+    Update,
+    /// Copy the URL from `.gitmodules` into the submodule's config.
+    Sync,
+}
+
+impl Action {
+    pub const ALL: [Action; 3] = [Action::Init, Action::Update, Action::Sync];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Action::Init => "Init",
+            Action::Update => "Update",
+            Action::Sync => "Sync",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            Action::Init => "Clone it and check out the recorded commit",
+            Action::Update => "Check out the recorded commit (new commits stay on their branch)",
+            Action::Sync => "Take the URL from .gitmodules after it changed",
+        }
+    }
+
+    /// The git arguments, for `path` or every submodule; recursive, so
+    /// nested submodules come along.
+    pub fn args(self, path: Option<&str>) -> Vec<String> {
+        let mut args: Vec<&str> = match self {
+            Action::Init => vec!["submodule", "update", "--init", "--recursive"],
+            Action::Update => vec!["submodule", "update", "--recursive"],
+            Action::Sync => vec!["submodule", "sync", "--recursive"],
+        };
+        if let Some(path) = path {
+            args.extend(["--", path]);
+        }
+        args.into_iter().map(String::from).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+    use std::process::Command;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let output = Command::new("git")
+            .args(["-c", "protocol.file.allow=always"])
+            .args(args)
+            .current_dir(dir)
+            .env("GIT_AUTHOR_NAME", "Test User")
+            .env("GIT_AUTHOR_EMAIL", "test@example.com")
+            .env("GIT_COMMITTER_NAME", "Test User")
+            .env("GIT_COMMITTER_EMAIL", "test@example.com")
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "git {:?} failed: {}",
+            args,
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    #[test]
+    fn test_list_submodules() {
+        let dir = tempfile::tempdir().unwrap();
+        let lib = dir.path().join("lib");
+        let app = dir.path().join("app");
+        for repo in [&lib, &app] {
+            std::fs::create_dir(repo).unwrap();
+            git(repo, &["init", "-q", "-b", "main"]);
+            std::fs::write(repo.join("README"), "hi\n").unwrap();
+            git(repo, &["add", "."]);
+            git(repo, &["commit", "-q", "-m", "First"]);
+        }
+        let url = lib.to_string_lossy().to_string();
+        git(&app, &["submodule", "add", "-q", &url, "vendor/lib"]);
+        git(&app, &["submodule", "add", "-q", &url, "vendor/other"]);
+        git(&app, &["commit", "-q", "-m", "Add submodules"]);
+        git(&app, &["submodule", "deinit", "-q", "vendor/other"]);
+
+        let repo = Repository::open(&app).unwrap();
+        let subs = list_in(&repo).unwrap();
+        assert_eq!(subs.len(), 2);
+        assert_eq!(subs[0].summary(), "up to date");
+        assert!(!subs[0].needs_attention());
+        assert_eq!(subs[1].summary(), "not initialized");
+
+        let inner = app.join("vendor/lib");
+        std::fs::write(inner.join("README"), "changed\n").unwrap();
+        git(&inner, &["commit", "-q", "-am", "Second"]);
+        std::fs::write(inner.join("new.txt"), "?\n").unwrap();
+        let subs = list_in(&repo).unwrap();
+        assert_eq!(subs[0].path, "vendor/lib");
+        assert_eq!(subs[0].summary(), "1 new commit(s), uncommitted changes");
+    }
+
+    #[test]
+    fn test_action_args() {
+        assert_eq!(
+            Action::Init.args(Some("vendor/lib")).join(" "),
+            "submodule update --init --recursive -- vendor/lib"
+        );
+        assert_eq!(
+            Action::Sync.args(None).join(" "),
+            "submodule sync --recursive"
+        );
+    }
+}
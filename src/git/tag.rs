@@ -0,0 +1,133 @@
+//! Tags — list, create, delete and push them, and the commits a tag adds
+//! over the one before it, for release changelogs.
+
+use super::runner::run_git;
+use anyhow::Result;
+
+/// A single tag.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tag {
+    pub name: String,
+    /// Annotated (`git tag -a`), with its own message, tagger and date;
+    /// otherwise lightweight, a plain name for a commit.
+    pub annotated: bool,
+    /// Short hash of the commit it points at.
+    pub commit: String,
+    /// When it was tagged, or committed for a lightweight tag (ISO 8601).
+    pub date_iso: String,
+    /// The tag message's first line, or the commit's for a lightweight tag.
+    pub subject: String,
+}
+
+const FORMAT: &str = "--format=%(refname:short)%00%(objecttype)%00%(objectname:short)%00\
+%(*objectname:short)%00%(creatordate:iso-strict)%00%(contents:subject)";
+
+/// All tags, newest first.
+pub fn list() -> Result<Vec<Tag>> {
+    let output = run_git(&["for-each-ref", "--sort=-creatordate", FORMAT, "refs/tags"])?;
+    Ok(parse_tags(&output))
+}
+
+fn parse_tags(output: &str) -> Vec<Tag> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split('\0').collect();
+            let [name, kind, object, peeled, date, subject] = fields[..] else {
+                return None;
+            };
+            let annotated = kind == "tag";
+            Some(Tag {
+                name: name.to_string(),
+                annotated,
+                commit: if annotated { peeled } else { object }.to_string(),
+                date_iso: date.to_string(),
+                subject: subject.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Whether `name` is a valid tag name.
+pub fn is_valid_name(name: &str) -> bool {
+    std::process::Command::new("git")
+        .args(["check-ref-format", &format!("refs/tags/{}", name)])
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .status()
+        .is_ok_and(|s| s.success())
+}
+
+/// Tag HEAD: annotated when there's a message, lightweight otherwise.
+pub fn create(name: &str, message: Option<&str>) -> Result<String> {
+    match message {
+        Some(message) => run_git(&["tag", "-a", name, "-m", message]),
+        None => run_git(&["tag", name]),
+    }
+}
+
+/// Delete a local tag.
+pub fn delete(name: &str) -> Result<String> {
+    run_git(&["tag", "-d", name])
+}
+
+/// `git push` arguments that publish the tag to `remote`.
+pub fn push_args(remote: &str, name: &str) -> Vec<String> {
+    ["push", remote, &format!("refs/tags/{}", name)]
+        .map(String::from)
+        .to_vec()
+}
+
+/// `git push` arguments that delete the tag from `remote`.
+pub fn delete_remote_args(remote: &str, name: &str) -> Vec<String> {
+    ["push", remote, "--delete", &format!("refs/tags/{}", name)]
+        .map(String::from)
+        .to_vec()
+}
+
+/// The tag before `name` in its history, if any.
+pub fn previous(name: &str) -> Option<String> {
+    run_git(&["describe", "--tags", "--abbrev=0", &format!("{}^", name)]).ok()
+}
+
+/// The commits `name` adds over `since` (or its whole history), one
+/// "- subject (hash)" line each, merges left out.
+pub fn commits_since(since: Option<&str>, name: &str) -> Result<String> {
+    let range = match since {
+        Some(since) => format!("{}..{}", since, name),
+        None => name.to_string(),
+    };
+    run_git(&["log", "--no-merges", "--format=- %s (%h)", &range])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_tags() {
+        let output = "v1.1\0tag\0aaa1111\0bbb2222\x002026-10-01T10:00:00+00:00\0Release 1.1\n\
+                      v1.0\0commit\0ccc3333\0\x002026-09-01T10:00:00+00:00\0Initial commit\n\
+                      broken line";
+        let tags = parse_tags(output);
+        assert_eq!(tags.len(), 2);
+        assert!(tags[0].annotated);
+        assert_eq!(tags[0].commit, "bbb2222");
+        assert_eq!(tags[0].subject, "Release 1.1");
+        assert!(!tags[1].annotated);
+        assert_eq!(tags[1].commit, "ccc3333");
+        assert_eq!(tags[1].date_iso, "2026-09-01T10:00:00+00:00");
+    }
+
+    #[test]
+    fn test_push_args() {
+        assert_eq!(
+            push_args("origin", "v1.0").join(" "),
+            "push origin refs/tags/v1.0"
+        );
+        assert_eq!(
+            delete_remote_args("origin", "v1.0").join(" "),
+            "push origin --delete refs/tags/v1.0"
+        );
+    }
+}
@@ -0,0 +1,161 @@
+//! `git diff --check` style whitespace problems: trailing whitespace and space
+//! before tab on added lines, plus mixed line endings and a missing final newline
+//! in the file itself.
+
+use anyhow::{Result, bail};
+
+/// Whitespace problems in one file's changes.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct WhitespaceReport {
+    /// Added lines ending in spaces or tabs.
+    pub trailing: usize,
+    /// Added lines whose indent has a space followed by a tab.
+    pub space_before_tab: usize,
+    /// The file mixes CRLF and LF line endings.
+    pub mixed_eol: bool,
+    /// The file does not end with a newline.
+    pub missing_final_newline: bool,
+}
+
+impl WhitespaceReport {
+    pub fn is_clean(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Short description, e.g. "2 trailing whitespace · no final newline".
+    pub fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        if self.trailing > 0 {
+            parts.push(format!("{} trailing whitespace", self.trailing));
+        }
+        if self.space_before_tab > 0 {
+            parts.push(format!("{} space before tab", self.space_before_tab));
+        }
+        if self.mixed_eol {
+            parts.push("mixed CRLF/LF".to_string());
+        }
+        if self.missing_final_newline {
+            parts.push("no final newline".to_string());
+        }
+        parts.join(" · ")
+    }
+}
+
+/// Byte offset where trailing spaces/tabs start, if the line has any.
+pub fn trailing_whitespace_start(line: &str) -> Option<usize> {
+    let trimmed = line.trim_end_matches([' ', '\t']).len();
+    (trimmed < line.len()).then_some(trimmed)
+}
+
+/// Whether the line's indentation has a space immediately followed by a tab.
+fn has_space_before_tab(line: &str) -> bool {
+    let indent_len = line.len() - line.trim_start_matches([' ', '\t']).len();
+    line[..indent_len].contains(" \t")
+}
+
+/// Check the added lines of a diff (with their leading `+`) and the full new
+/// file content. `content` is `None` when it is unavailable or binary.
+pub fn check<'a>(
+    added_lines: impl IntoIterator<Item = &'a str>,
+    content: Option<&str>,
+) -> WhitespaceReport {
+    let mut report = WhitespaceReport::default();
+    for line in added_lines {
+        let line = line.strip_prefix('+').unwrap_or(line);
+        if trailing_whitespace_start(line).is_some() {
+            report.trailing += 1;
+        }
+        if has_space_before_tab(line) {
+            report.space_before_tab += 1;
+        }
+    }
+    if let Some(content) = content.filter(|c| !c.contains('\0')) {
+        let (crlf, lf) = count_line_endings(content);
+        report.mixed_eol = crlf > 0 && lf > 0;
+        report.missing_final_newline = !content.is_empty() && !content.ends_with('\n');
+    }
+    report
+}
+
+/// Number of CRLF and bare LF line endings.
+fn count_line_endings(content: &str) -> (usize, usize) {
+    let total = content.matches('\n').count();
+    let crlf = content.matches("\r\n").count();
+    (crlf, total - crlf)
+}
+
+/// Strip trailing whitespace, use the file's predominant line ending throughout,
+/// and end with a newline.
+pub fn fix_content(content: &str) -> String {
+    if content.is_empty() {
+        return String::new();
+    }
+    let (crlf, lf) = count_line_endings(content);
+    let eol = if crlf > lf { "\r\n" } else { "\n" };
+    let body = content.strip_suffix('\n').unwrap_or(content);
+    let mut out: String = body
+        .split('\n')
+        .map(|l| {
+            l.strip_suffix('\r')
+                .unwrap_or(l)
+                .trim_end_matches([' ', '\t'])
+        })
+        .collect::<Vec<_>>()
+        .join(eol);
+    out.push_str(eol);
+    out
+}
+
+/// Fix whitespace in a working-tree file. Returns whether the file changed.
+pub fn fix_file(path: &str) -> Result<bool> {
+    let bytes = std::fs::read(path)?;
+    if bytes.contains(&0) {
+        bail!("{} looks like a binary file", path);
+    }
+    let content = String::from_utf8(bytes).map_err(|_| anyhow::anyhow!("{} is not UTF-8", path))?;
+    let fixed = fix_content(&content);
+    if fixed == content {
+        return Ok(false);
+    }
+    std::fs::write(path, fixed)?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trailing_whitespace_start() {
+        assert_eq!(trailing_whitespace_start("let x = 1;  "), Some(10));
+        assert_eq!(trailing_whitespace_start("\t"), Some(0));
+        assert_eq!(trailing_whitespace_start("clean"), None);
+        assert_eq!(trailing_whitespace_start(""), None);
+    }
+
+    #[test]
+    fn test_check_added_lines_and_content() {
+        let added = ["+fn main() { ", "+ \tindent", "+ok"];
+        let report = check(added, Some("a\r\nb\nc"));
+        assert_eq!(report.trailing, 1);
+        assert_eq!(report.space_before_tab, 1);
+        assert!(report.mixed_eol);
+        assert!(report.missing_final_newline);
+        assert_eq!(
+            report.summary(),
+            "1 trailing whitespace · 1 space before tab · mixed CRLF/LF · no final newline"
+        );
+        assert!(check(["+ok"], Some("ok\n")).is_clean());
+        assert!(check(["+ok"], Some("bin\0ary")).is_clean());
+    }
+
+    #[test]
+    fn test_fix_content() {
+        assert_eq!(fix_content("a  \nb\t\nc"), "a\nb\nc\n");
+        // Predominant line ending wins
+        assert_eq!(fix_content("a\r\nb\r\nc\n"), "a\r\nb\r\nc\r\n");
+        assert_eq!(fix_content("a\nb\r\nc\n"), "a\nb\nc\n");
+        assert_eq!(fix_content("clean\n"), "clean\n");
+        assert_eq!(fix_content(""), "");
+    }
+}
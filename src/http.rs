@@ -0,0 +1,433 @@
+//! HTTP transport shared by the GitHub API and the AI providers.
+//!
+//! Callers build a [`Request`] and `send()` it through the current
+//! [`Transport`]: reqwest in the app, a [`mock::MockTransport`] in tests, so
+//! auth flows, API errors and AI error handling can be exercised offline.
+
+use std::fmt;
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+/// How long establishing a connection may take, whatever the request timeout.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Method {
+    Get,
+    Post,
+    Put,
+    Patch,
+    Delete,
+}
+
+impl Method {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Method::Get => "GET",
+            Method::Post => "POST",
+            Method::Put => "PUT",
+            Method::Patch => "PATCH",
+            Method::Delete => "DELETE",
+        }
+    }
+}
+
+/// An outgoing request. Built with the helpers below, e.g.
+/// `Request::new(Method::Get, url).header("Accept", "application/json").send()`.
+#[derive(Debug, Clone)]
+pub struct Request {
+    pub method: Method,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Option<Vec<u8>>,
+    /// Whole-request timeout; none means wait as long as the server takes.
+    pub timeout: Option<Duration>,
+}
+
+impl Request {
+    pub fn new(method: Method, url: impl Into<String>) -> Self {
+        Self {
+            method,
+            url: url.into(),
+            headers: Vec::new(),
+            body: None,
+            timeout: None,
+        }
+    }
+
+    pub fn header(mut self, name: &str, value: impl Into<String>) -> Self {
+        self.headers.push((name.to_string(), value.into()));
+        self
+    }
+
+    pub fn bearer(self, token: &str) -> Self {
+        self.header("Authorization", format!("Bearer {}", token))
+    }
+
+    /// Send `body` as JSON.
+    pub fn json<T: serde::Serialize + ?Sized>(mut self, body: &T) -> Self {
+        self.body = Some(serde_json::to_vec(body).unwrap_or_default());
+        self.header("Content-Type", "application/json")
+    }
+
+    /// Send `fields` URL-encoded, as an HTML form would.
+    pub fn form(mut self, fields: &[(&str, &str)]) -> Self {
+        let encoded: Vec<String> = fields
+            .iter()
+            .map(|(k, v)| format!("{}={}", form_encode(k), form_encode(v)))
+            .collect();
+        self.body = Some(encoded.join("&").into_bytes());
+        self.header("Content-Type", "application/x-www-form-urlencoded")
+    }
+
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Value of header `name` (case-insensitive).
+    #[cfg(test)]
+    pub fn header_value(&self, name: &str) -> Option<&str> {
+        find_header(&self.headers, name)
+    }
+
+    /// Body as text (lossy), empty when there is none.
+    #[cfg(test)]
+    pub fn body_text(&self) -> String {
+        String::from_utf8_lossy(self.body.as_deref().unwrap_or_default()).into_owned()
+    }
+
+    /// Send through the current transport.
+    pub fn send(self) -> Result<Response, Error> {
+        transport().send(self)
+    }
+}
+
+fn form_encode(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for byte in text.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                out.push(byte as char)
+            }
+            b' ' => out.push('+'),
+            _ => out.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    out
+}
+
+fn find_header<'a>(headers: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    headers
+        .iter()
+        .find(|(k, _)| k.eq_ignore_ascii_case(name))
+        .map(|(_, v)| v.as_str())
+}
+
+/// A response with its body read in full.
+#[derive(Debug, Clone)]
+pub struct Response {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl Response {
+    #[cfg(test)]
+    pub fn new(status: u16, body: impl Into<Vec<u8>>) -> Self {
+        Self {
+            status,
+            headers: Vec::new(),
+            body: body.into(),
+        }
+    }
+
+    #[cfg(test)]
+    pub fn with_header(mut self, name: &str, value: impl Into<String>) -> Self {
+        self.headers.push((name.to_string(), value.into()));
+        self
+    }
+
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+
+    pub fn is_redirection(&self) -> bool {
+        (300..400).contains(&self.status)
+    }
+
+    pub fn is_client_error(&self) -> bool {
+        (400..500).contains(&self.status)
+    }
+
+    pub fn is_server_error(&self) -> bool {
+        (500..600).contains(&self.status)
+    }
+
+    /// Value of header `name` (case-insensitive).
+    pub fn header(&self, name: &str) -> Option<&str> {
+        find_header(&self.headers, name)
+    }
+
+    /// Body as text (lossy).
+    pub fn text(&self) -> String {
+        String::from_utf8_lossy(&self.body).into_owned()
+    }
+
+    pub fn json<T: serde::de::DeserializeOwned>(&self) -> serde_json::Result<T> {
+        serde_json::from_slice(&self.body)
+    }
+}
+
+/// Why a request got no response.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Error {
+    /// The request took longer than its timeout.
+    Timeout,
+    /// No connection could be made (offline, DNS, refused, TLS).
+    Connect(String),
+    /// The response body could not be read.
+    Body(String),
+    Other(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Timeout => write!(f, "request timed out"),
+            Error::Connect(e) => write!(f, "connection failed: {}", e),
+            Error::Body(e) => write!(f, "failed to read response: {}", e),
+            Error::Other(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<reqwest::Error> for Error {
+    fn from(e: reqwest::Error) -> Self {
+        if e.is_timeout() {
+            Error::Timeout
+        } else if e.is_connect() {
+            Error::Connect(e.to_string())
+        } else if e.is_body() || e.is_decode() {
+            Error::Body(e.to_string())
+        } else {
+            Error::Other(e.to_string())
+        }
+    }
+}
+
+/// Something that can carry a request to a server and bring back the response.
+pub trait Transport: Send + Sync {
+    fn send(&self, request: Request) -> Result<Response, Error>;
+}
+
+/// The real network, through one shared reqwest client.
+pub struct ReqwestTransport {
+    client: reqwest::blocking::Client,
+}
+
+impl ReqwestTransport {
+    pub fn new() -> Self {
+        let client = reqwest::blocking::Client::builder()
+            .connect_timeout(CONNECT_TIMEOUT)
+            .build()
+            .unwrap_or_default();
+        Self { client }
+    }
+}
+
+impl Transport for ReqwestTransport {
+    fn send(&self, request: Request) -> Result<Response, Error> {
+        let method = reqwest::Method::from_bytes(request.method.as_str().as_bytes())
+            .map_err(|e| Error::Other(e.to_string()))?;
+        let mut builder = self.client.request(method, &request.url);
+        for (name, value) in &request.headers {
+            builder = builder.header(name, value);
+        }
+        if let Some(body) = request.body {
+            builder = builder.body(body);
+        }
+        if let Some(timeout) = request.timeout {
+            builder = builder.timeout(timeout);
+        }
+
+        let resp = builder.send()?;
+        let status = resp.status().as_u16();
+        let headers = resp
+            .headers()
+            .iter()
+            .filter_map(|(k, v)| Some((k.to_string(), v.to_str().ok()?.to_string())))
+            .collect();
+        let body = resp.bytes()?.to_vec();
+        Ok(Response {
+            status,
+            headers,
+            body,
+        })
+    }
+}
+
+static DEFAULT: OnceLock<Arc<dyn Transport>> = OnceLock::new();
+
+#[cfg(test)]
+thread_local! {
+    static OVERRIDE: std::cell::RefCell<Option<Arc<dyn Transport>>> =
+        const { std::cell::RefCell::new(None) };
+}
+
+/// The transport requests on this thread go through. Code that hands requests
+/// to another thread should take it along.
+pub fn transport() -> Arc<dyn Transport> {
+    #[cfg(test)]
+    if let Some(t) = OVERRIDE.with(|o| o.borrow().clone()) {
+        return t;
+    }
+    DEFAULT
+        .get_or_init(|| Arc::new(ReqwestTransport::new()))
+        .clone()
+}
+
+/// Run `f` with this thread's requests going through `transport`.
+#[cfg(test)]
+pub fn with_transport<R>(transport: Arc<dyn Transport>, f: impl FnOnce() -> R) -> R {
+    let previous = OVERRIDE.with(|o| o.borrow_mut().replace(transport));
+    let result = f();
+    OVERRIDE.with(|o| *o.borrow_mut() = previous);
+    result
+}
+
+/// A canned-response server for tests.
+#[cfg(test)]
+pub mod mock {
+    use super::*;
+    use std::collections::VecDeque;
+    use std::sync::Mutex;
+
+    struct Route {
+        method: Method,
+        path: String,
+        /// Answered in order; the last one repeats.
+        replies: VecDeque<Result<Response, Error>>,
+    }
+
+    /// Answers requests from routes matched by method and path prefix
+    /// (`/repos/o/r/pulls/7`, query included), and records every request.
+    /// Unmatched requests fail as if the host were unreachable.
+    #[derive(Default)]
+    pub struct MockTransport {
+        routes: Mutex<Vec<Route>>,
+        requests: Mutex<Vec<Request>>,
+    }
+
+    impl MockTransport {
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Reply to `method path…` with `response`. Repeated calls for the same
+        /// route queue up successive replies.
+        pub fn on(self, method: Method, path: &str, response: Response) -> Self {
+            self.reply(method, path, Ok(response))
+        }
+
+        /// Fail `method path…` with `error`.
+        pub fn fail(self, method: Method, path: &str, error: Error) -> Self {
+            self.reply(method, path, Err(error))
+        }
+
+        fn reply(self, method: Method, path: &str, reply: Result<Response, Error>) -> Self {
+            {
+                let mut routes = self.routes.lock().unwrap();
+                match routes
+                    .iter_mut()
+                    .find(|r| r.method == method && r.path == path)
+                {
+                    Some(route) => route.replies.push_back(reply),
+                    None => routes.push(Route {
+                        method,
+                        path: path.to_string(),
+                        replies: VecDeque::from([reply]),
+                    }),
+                }
+            }
+            self
+        }
+
+        /// Requests received so far, oldest first.
+        pub fn requests(&self) -> Vec<Request> {
+            self.requests.lock().unwrap().clone()
+        }
+
+        /// Run `f` with this thread's requests answered by the mock.
+        pub fn serve<R>(self: &Arc<Self>, f: impl FnOnce() -> R) -> R {
+            with_transport(self.clone(), f)
+        }
+    }
+
+    fn path_of(url: &str) -> &str {
+        let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+        rest.find('/').map_or("/", |i| &rest[i..])
+    }
+
+    impl Transport for MockTransport {
+        fn send(&self, request: Request) -> Result<Response, Error> {
+            self.requests.lock().unwrap().push(request.clone());
+            let path = path_of(&request.url);
+            let mut routes = self.routes.lock().unwrap();
+            let route = routes
+                .iter_mut()
+                .filter(|r| r.method == request.method && path.starts_with(&r.path))
+                .max_by_key(|r| r.path.len());
+            match route {
+                Some(route) if route.replies.len() > 1 => route.replies.pop_front().unwrap(),
+                Some(route) => route.replies[0].clone(),
+                None => Err(Error::Connect(format!(
+                    "no mock route for {} {}",
+                    request.method.as_str(),
+                    request.url
+                ))),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::mock::MockTransport;
+    use super::*;
+
+    #[test]
+    fn test_mock_routes_and_records() {
+        let mock = Arc::new(
+            MockTransport::new()
+                .on(Method::Get, "/a", Response::new(200, "first"))
+                .on(Method::Get, "/a", Response::new(200, "again"))
+                .on(Method::Get, "/a/b", Response::new(404, "")),
+        );
+        let texts = mock.serve(|| {
+            let get = |url: &str| Request::new(Method::Get, url).send();
+            [
+                get("https://x.test/a?page=1").unwrap().text(),
+                get("https://x.test/a").unwrap().text(),
+                get("https://x.test/a").unwrap().text(),
+                get("https://x.test/a/b").unwrap().status.to_string(),
+            ]
+        });
+        assert_eq!(texts, ["first", "again", "again", "404"]);
+        assert!(matches!(
+            mock.serve(|| Request::new(Method::Post, "https://x.test/a").send()),
+            Err(Error::Connect(_))
+        ));
+
+        let sent = Request::new(Method::Post, "https://x.test/login")
+            .form(&[("scope", "repo,read:user"), ("a b", "x&y")]);
+        assert_eq!(sent.body_text(), "scope=repo%2Cread%3Auser&a+b=x%26y");
+        assert_eq!(
+            sent.header_value("content-type"),
+            Some("application/x-www-form-urlencoded")
+        );
+        assert_eq!(mock.requests().len(), 5);
+    }
+}
@@ -0,0 +1,315 @@
+//! Remote control of a running zit, for editor and tmux bindings. `zit
+//! --server` listens on a unix socket in the git dir, and `zit remote blame
+//! src/app.rs:120` (run anywhere in the same repository) tells it what to show.
+//!
+//! The protocol is one line each way: `<command> <path>[:<line>]`, answered by
+//! `ok <message>` or `error <message>`.
+
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Component, Path, PathBuf};
+use std::sync::mpsc;
+use std::time::Duration;
+
+use anyhow::{Context, Result, bail};
+
+use crate::app::{App, View};
+use crate::event::AppEvent;
+use crate::git::{self, run_git};
+
+/// Socket file in the git dir of the repository being served.
+const SOCKET: &str = "zit.sock";
+
+/// How long a client waits for the running zit to act on a command.
+const REPLY_TIMEOUT: Duration = Duration::from_secs(5);
+
+const USAGE: &str = "Usage: zit remote <diff|blame|history> <path>[:<line>]";
+
+/// What a client asks the running zit to show.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    /// The file's uncommitted changes in Staging, scrolled to `line`.
+    Diff { path: String, line: Option<u32> },
+    /// The commit that last changed `line`, in the Timeline.
+    Blame { path: String, line: u32 },
+    /// The Timeline limited to commits touching the file.
+    History { path: String },
+}
+
+impl Command {
+    /// Parse `<command> <path>[:<line>]`. The path may contain spaces.
+    pub fn parse(input: &str) -> Result<Self> {
+        let Some((verb, target)) = input.trim().split_once(' ') else {
+            bail!(USAGE);
+        };
+        let (path, line) = split_target(target.trim());
+        if path.is_empty() {
+            bail!(USAGE);
+        }
+        match verb {
+            "diff" => Ok(Self::Diff { path, line }),
+            "blame" => Ok(Self::Blame {
+                path,
+                line: line.unwrap_or(1),
+            }),
+            "history" | "log" => Ok(Self::History { path }),
+            other => bail!("Unknown command '{}'. {}", other, USAGE),
+        }
+    }
+}
+
+/// Split `src/app.rs:120` into the path and line number.
+fn split_target(target: &str) -> (String, Option<u32>) {
+    match target.rsplit_once(':') {
+        Some((path, line)) if line.parse::<u32>().is_ok_and(|n| n > 0) => {
+            (path.to_string(), line.parse().ok())
+        }
+        _ => (target.to_string(), None),
+    }
+}
+
+/// A command from a client, with the channel its answer goes back on.
+pub struct Request {
+    pub command: Command,
+    pub reply: mpsc::Sender<Result<String, String>>,
+}
+
+/// Carry out `command` in the running app. Returns what to tell the client.
+pub fn run(app: &mut App, command: Command) -> Result<String> {
+    match command {
+        Command::Diff { path, line } => {
+            show_diff(app, &path, line)?;
+            Ok(format!("Showing the changes to {}", path))
+        }
+        Command::Blame { path, line } => {
+            let Some(hash) = git::blame::last_change(&path, line)? else {
+                show_diff(app, &path, Some(line))?;
+                return Ok(format!("Line {} of {} isn't committed yet", line, path));
+            };
+            app.timeline_state.set_path(Some(path.clone()));
+            app.timeline_state.show_commit(&hash)?;
+            app.open_view(View::Timeline);
+            Ok(format!(
+                "Line {} of {} was last changed in {}",
+                line,
+                path,
+                &hash[..hash.len().min(7)]
+            ))
+        }
+        Command::History { path } => {
+            app.timeline_state.set_path(Some(path.clone()));
+            app.open_view(View::Timeline);
+            Ok(format!("Showing the history of {}", path))
+        }
+    }
+}
+
+fn show_diff(app: &mut App, path: &str, line: Option<u32>) -> Result<()> {
+    app.staging_state.refresh();
+    if !app.staging_state.jump_to_file(path, line) {
+        bail!("{} has no uncommitted changes", path);
+    }
+    app.open_view(View::Staging);
+    Ok(())
+}
+
+fn socket_path() -> Result<PathBuf> {
+    let git_dir = run_git(&["rev-parse", "--absolute-git-dir"])?;
+    Ok(PathBuf::from(git_dir.trim()).join(SOCKET))
+}
+
+/// The socket of a running server, removed when dropped.
+pub struct Server {
+    path: PathBuf,
+}
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// Start listening for commands, passing each one to the main loop through
+/// `waker`.
+#[cfg(unix)]
+pub fn serve(waker: mpsc::Sender<AppEvent>) -> Result<Server> {
+    use std::os::unix::net::{UnixListener, UnixStream};
+
+    let path = socket_path()?;
+    if path.exists() {
+        if UnixStream::connect(&path).is_ok() {
+            bail!("Another zit --server is already running on this repository");
+        }
+        // Left behind by a zit that didn't shut down cleanly
+        std::fs::remove_file(&path).context("Failed to remove stale socket")?;
+    }
+    let listener = UnixListener::bind(&path).context("Failed to create socket")?;
+    std::thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            if let Err(e) = answer(&stream, &waker) {
+                log::warn!("Remote command failed: {}", e);
+            }
+        }
+    });
+    Ok(Server { path })
+}
+
+#[cfg(not(unix))]
+pub fn serve(_waker: mpsc::Sender<AppEvent>) -> Result<Server> {
+    bail!("--server needs unix sockets, which this platform doesn't have")
+}
+
+/// Read one command from a client and write back what came of it.
+#[cfg(unix)]
+fn answer(stream: &std::os::unix::net::UnixStream, waker: &mpsc::Sender<AppEvent>) -> Result<()> {
+    // One connection is served at a time: a client that never finishes its
+    // line mustn't hold up the ones after it
+    stream.set_read_timeout(Some(REPLY_TIMEOUT))?;
+    let mut line = String::new();
+    BufReader::new(stream).read_line(&mut line)?;
+    let result = match Command::parse(&line) {
+        Ok(command) => {
+            let (reply, answer) = mpsc::channel();
+            waker.send(AppEvent::Remote(Request { command, reply }))?;
+            answer
+                .recv_timeout(REPLY_TIMEOUT)
+                .unwrap_or_else(|_| Err("zit didn't answer".to_string()))
+        }
+        Err(e) => Err(e.to_string()),
+    };
+    let reply = match result {
+        Ok(message) => format!("ok {}\n", message),
+        Err(message) => format!("error {}\n", message),
+    };
+    let mut stream = stream;
+    stream.write_all(reply.as_bytes())?;
+    Ok(())
+}
+
+/// `zit remote <verb> <target>`: send a command to the zit serving this
+/// repository and return its answer.
+#[cfg(unix)]
+pub fn send(verb: &str, target: &str) -> Result<String> {
+    use std::os::unix::net::UnixStream;
+
+    let (path, line) = split_target(target);
+    let path = repo_path(&path)?;
+    let target = match line {
+        Some(line) => format!("{}:{}", path, line),
+        None => path,
+    };
+    // Checked here too, so mistakes are reported without a server
+    Command::parse(&format!("{} {}", verb, target))?;
+
+    let socket = socket_path()?;
+    let mut stream = UnixStream::connect(&socket).with_context(|| {
+        format!(
+            "No zit --server is running on this repository ({})",
+            socket.display()
+        )
+    })?;
+    stream.set_read_timeout(Some(REPLY_TIMEOUT + Duration::from_secs(1)))?;
+    writeln!(stream, "{} {}", verb, target)?;
+    let mut reply = String::new();
+    BufReader::new(&stream).read_line(&mut reply)?;
+    let reply = reply.trim_end();
+    match reply.split_once(' ').unwrap_or((reply, "")) {
+        ("ok", message) => Ok(message.to_string()),
+        ("error", message) => bail!("{}", message),
+        _ => bail!("Unexpected answer from zit: {}", reply),
+    }
+}
+
+#[cfg(not(unix))]
+pub fn send(_verb: &str, _target: &str) -> Result<String> {
+    bail!("zit remote needs unix sockets, which this platform doesn't have")
+}
+
+/// `path` as given on the command line (relative to the current directory, or
+/// absolute) relative to the top of the repository, as git reports paths.
+fn repo_path(path: &str) -> Result<String> {
+    let path = Path::new(path);
+    if path.is_absolute() {
+        let top = run_git(&["rev-parse", "--show-toplevel"])?;
+        let relative = path
+            .strip_prefix(top.trim())
+            .with_context(|| format!("{} is outside this repository", path.display()))?;
+        return Ok(normalize(Path::new(""), relative));
+    }
+    let prefix = run_git(&["rev-parse", "--show-prefix"])?;
+    Ok(normalize(Path::new(prefix.trim()), path))
+}
+
+/// `base` joined with `path`, with `.` and `..` resolved, '/'-separated.
+fn normalize(base: &Path, path: &Path) -> String {
+    let mut parts: Vec<String> = Vec::new();
+    for component in base.components().chain(path.components()) {
+        match component {
+            Component::Normal(part) => parts.push(part.to_string_lossy().into_owned()),
+            Component::ParentDir => {
+                parts.pop();
+            }
+            _ => {}
+        }
+    }
+    if parts.is_empty() {
+        // The top of the repository
+        return ".".to_string();
+    }
+    parts.join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_command() {
+        assert_eq!(
+            Command::parse("blame src/app.rs:120\n").unwrap(),
+            Command::Blame {
+                path: "src/app.rs".to_string(),
+                line: 120
+            }
+        );
+        assert_eq!(
+            Command::parse("diff docs/my notes.md").unwrap(),
+            Command::Diff {
+                path: "docs/my notes.md".to_string(),
+                line: None
+            }
+        );
+        assert_eq!(
+            Command::parse("history src/app.rs").unwrap(),
+            Command::History {
+                path: "src/app.rs".to_string()
+            }
+        );
+        assert!(Command::parse("blame").is_err());
+        assert!(Command::parse("open src/app.rs").is_err());
+    }
+
+    #[test]
+    fn test_split_target() {
+        assert_eq!(split_target("a.rs:7"), ("a.rs".to_string(), Some(7)));
+        assert_eq!(split_target("a.rs"), ("a.rs".to_string(), None));
+        assert_eq!(split_target("a.rs:0"), ("a.rs:0".to_string(), None));
+        assert_eq!(split_target("c:/x.rs"), ("c:/x.rs".to_string(), None));
+    }
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(
+            normalize(Path::new("src/ui/"), Path::new("app.rs")),
+            "src/ui/app.rs"
+        );
+        assert_eq!(
+            normalize(Path::new("src/ui/"), Path::new("../main.rs")),
+            "src/main.rs"
+        );
+        assert_eq!(
+            normalize(Path::new(""), Path::new("./README.md")),
+            "README.md"
+        );
+        assert_eq!(normalize(Path::new("src/"), Path::new("..")), ".");
+    }
+}
@@ -0,0 +1,319 @@
+//! Background job scheduler for network calls (GitHub API, AI requests).
+//!
+//! A fixed pool of workers caps how many requests run at once. Jobs the user
+//! asked for run before periodic refreshes, and a read whose key matches one
+//! still waiting in the queue is dropped instead of queued twice. Writes
+//! (reviews, new issues, AI requests) are never merged that way.
+//!
+//! Work that changes the repository (pushes, pulls, snapshots) runs on threads
+//! of its own started with [`spawn_tracked`], so quitting can wait for it
+//! instead of cutting it off halfway.
+
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// Most network jobs running at the same time.
+pub const MAX_CONCURRENT: usize = 4;
+
+/// How long quitting waits for background work before leaving it behind.
+pub const QUIT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Who asked for a job. User jobs are always started before background ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Priority {
+    /// Periodic polling and prefetching (watches, dashboard widgets, avatars).
+    Background,
+    /// Triggered by a key press; someone is waiting on the result.
+    User,
+}
+
+type Task = Box<dyn FnOnce() + Send>;
+
+struct Job {
+    key: String,
+    priority: Priority,
+    seq: u64,
+    task: Task,
+}
+
+/// Pending jobs, ordered by priority and then submission order.
+#[derive(Default)]
+struct Queue {
+    pending: Vec<Job>,
+    next_seq: u64,
+    /// Keys of the jobs workers are running right now.
+    running: Vec<String>,
+    /// Set on quit: pending jobs are dropped and no new ones start.
+    closed: bool,
+}
+
+impl Queue {
+    /// Queue `task` unless a job with the same key is already pending; that job
+    /// then inherits the higher of the two priorities. Only for idempotent
+    /// reads, where the pending job's result serves both. Returns whether it
+    /// was queued.
+    fn push(&mut self, key: String, priority: Priority, task: Task) -> bool {
+        if let Some(job) = self.pending.iter_mut().find(|j| j.key == key) {
+            job.priority = job.priority.max(priority);
+            return false;
+        }
+        self.push_always(key, priority, task)
+    }
+
+    /// Queue `task` even if a job with the same key is pending. Returns
+    /// `false` only once the queue is closed.
+    fn push_always(&mut self, key: String, priority: Priority, task: Task) -> bool {
+        if self.closed {
+            return false;
+        }
+        self.pending.push(Job {
+            key,
+            priority,
+            seq: self.next_seq,
+            task,
+        });
+        self.next_seq += 1;
+        true
+    }
+
+    /// The highest-priority job, oldest first within a priority.
+    fn pop(&mut self) -> Option<Job> {
+        let index = self
+            .pending
+            .iter()
+            .enumerate()
+            .max_by(|(_, a), (_, b)| a.priority.cmp(&b.priority).then(b.seq.cmp(&a.seq)))
+            .map(|(i, _)| i)?;
+        Some(self.pending.remove(index))
+    }
+}
+
+struct Shared {
+    queue: Mutex<Queue>,
+    ready: Condvar,
+}
+
+/// Runs submitted jobs on a fixed pool of worker threads.
+pub struct Scheduler {
+    shared: Arc<Shared>,
+}
+
+impl Scheduler {
+    /// Start a scheduler running at most `workers` jobs at once.
+    pub fn new(workers: usize) -> Self {
+        let shared = Arc::new(Shared {
+            queue: Mutex::new(Queue::default()),
+            ready: Condvar::new(),
+        });
+        for _ in 0..workers.max(1) {
+            let shared = Arc::clone(&shared);
+            thread::spawn(move || worker(&shared));
+        }
+        Self { shared }
+    }
+
+    /// Queue the idempotent read `task` under `key`. Identical requests share
+    /// a key (e.g. `"pr-detail:42"`) so a burst of them runs once. Returns
+    /// `false` when an identical job was already waiting, or after shutdown.
+    pub fn submit(
+        &self,
+        key: impl Into<String>,
+        priority: Priority,
+        task: impl FnOnce() + Send + 'static,
+    ) -> bool {
+        self.enqueue(|queue| queue.push(key.into(), priority, Box::new(task)))
+    }
+
+    /// Queue `task`, which changes something (a review, a new issue, an AI
+    /// request), without merging it into a pending job of the same key.
+    /// Returns `false` only after shutdown.
+    pub fn submit_write(
+        &self,
+        key: impl Into<String>,
+        priority: Priority,
+        task: impl FnOnce() + Send + 'static,
+    ) -> bool {
+        self.enqueue(|queue| queue.push_always(key.into(), priority, Box::new(task)))
+    }
+
+    fn enqueue(&self, push: impl FnOnce(&mut Queue) -> bool) -> bool {
+        let Ok(mut queue) = self.shared.queue.lock() else {
+            return false;
+        };
+        let queued = push(&mut queue);
+        if queued {
+            self.shared.ready.notify_one();
+        }
+        queued
+    }
+
+    /// Drop the jobs under `key` that haven't started. Returns how many.
+    pub fn cancel(&self, key: &str) -> usize {
+        let Ok(mut queue) = self.shared.queue.lock() else {
+            return 0;
+        };
+        let before = queue.pending.len();
+        queue.pending.retain(|j| j.key != key);
+        before - queue.pending.len()
+    }
+
+    /// Keys of the jobs running right now.
+    pub fn running(&self) -> Vec<String> {
+        self.shared
+            .queue
+            .lock()
+            .map(|q| q.running.clone())
+            .unwrap_or_default()
+    }
+
+    /// Drop the jobs still waiting and refuse new ones, leaving the running
+    /// ones to finish. Called on quit.
+    pub fn shutdown(&self) {
+        if let Ok(mut queue) = self.shared.queue.lock() {
+            queue.closed = true;
+            queue.pending.clear();
+        }
+    }
+}
+
+impl Default for Scheduler {
+    fn default() -> Self {
+        Self::new(MAX_CONCURRENT)
+    }
+}
+
+fn worker(shared: &Shared) {
+    loop {
+        let job = {
+            let Ok(mut queue) = shared.queue.lock() else {
+                return;
+            };
+            loop {
+                if let Some(job) = queue.pop() {
+                    queue.running.push(job.key.clone());
+                    break job;
+                }
+                queue = match shared.ready.wait(queue) {
+                    Ok(queue) => queue,
+                    Err(_) => return,
+                };
+            }
+        };
+        // A panicking job must not take its worker down with it
+        if catch_unwind(AssertUnwindSafe(job.task)).is_err() {
+            log::warn!("Background job '{}' panicked", job.key);
+        }
+        if let Ok(mut queue) = shared.queue.lock()
+            && let Some(i) = queue.running.iter().position(|k| *k == job.key)
+        {
+            queue.running.remove(i);
+        }
+    }
+}
+
+/// Threads started with [`spawn_tracked`], by name, until they're found finished.
+static TRACKED: Mutex<Vec<(String, JoinHandle<()>)>> = Mutex::new(Vec::new());
+
+/// Run `task` on a thread of its own that quitting waits for (see [`wait`]).
+/// `name` is what the user is told if it's still running, e.g. "Push".
+pub fn spawn_tracked(name: impl Into<String>, task: impl FnOnce() + Send + 'static) {
+    let handle = thread::spawn(task);
+    if let Ok(mut tracked) = TRACKED.lock() {
+        tracked.retain(|(_, h)| !h.is_finished());
+        tracked.push((name.into(), handle));
+    }
+}
+
+/// Names of the tracked threads still running.
+pub fn in_flight() -> Vec<String> {
+    let Ok(mut tracked) = TRACKED.lock() else {
+        return Vec::new();
+    };
+    tracked.retain(|(_, h)| !h.is_finished());
+    tracked.iter().map(|(name, _)| name.clone()).collect()
+}
+
+/// Wait up to `timeout` for the tracked threads and the scheduler's running
+/// jobs to finish, or until `give_up` says to stop. Returns the names of those
+/// still running at the end.
+pub fn wait(scheduler: &Scheduler, timeout: Duration, give_up: impl Fn() -> bool) -> Vec<String> {
+    let deadline = Instant::now() + timeout;
+    loop {
+        let mut left = in_flight();
+        left.extend(scheduler.running());
+        if left.is_empty() || Instant::now() >= deadline || give_up() {
+            return left;
+        }
+        thread::sleep(Duration::from_millis(50));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_queue_dedups_and_prioritizes() {
+        let mut queue = Queue::default();
+        assert!(queue.push("watch".into(), Priority::Background, Box::new(|| {})));
+        assert!(queue.push("avatars".into(), Priority::Background, Box::new(|| {})));
+        assert!(queue.push("prs".into(), Priority::User, Box::new(|| {})));
+        assert!(!queue.push("prs".into(), Priority::User, Box::new(|| {})));
+        // Asking for a pending background job makes it a user job
+        assert!(!queue.push("avatars".into(), Priority::User, Box::new(|| {})));
+
+        let order: Vec<String> = std::iter::from_fn(|| queue.pop().map(|j| j.key)).collect();
+        assert_eq!(order, ["avatars", "prs", "watch"]);
+    }
+
+    #[test]
+    fn test_writes_are_never_merged_and_can_be_cancelled() {
+        let scheduler = Scheduler::new(1);
+        let (started, is_started) = std::sync::mpsc::channel();
+        let (release, wait_release) = std::sync::mpsc::channel::<()>();
+        scheduler.submit("busy", Priority::User, move || {
+            let _ = started.send(());
+            let _ = wait_release.recv();
+        });
+        is_started.recv().unwrap();
+
+        let (ran, results) = std::sync::mpsc::channel();
+        for n in 0..3 {
+            let ran = ran.clone();
+            assert!(scheduler.submit_write("review", Priority::User, move || {
+                let _ = ran.send(n);
+            }));
+        }
+        let cancelled = ran.clone();
+        assert!(scheduler.submit_write("ai", Priority::User, move || {
+            let _ = cancelled.send(99);
+        }));
+        assert_eq!(scheduler.cancel("ai"), 1);
+        assert_eq!(scheduler.cancel("ai"), 0);
+        drop(ran);
+        release.send(()).unwrap();
+
+        let order: Vec<i32> = results.iter().collect();
+        assert_eq!(order, [0, 1, 2]);
+    }
+
+    #[test]
+    fn test_shutdown_drops_pending_and_waits_for_running() {
+        let scheduler = Scheduler::new(1);
+        let (started, is_started) = std::sync::mpsc::channel();
+        scheduler.submit("slow", Priority::User, move || {
+            let _ = started.send(());
+            thread::sleep(Duration::from_millis(200));
+        });
+        is_started.recv().unwrap();
+        assert!(scheduler.submit("queued", Priority::User, || {}));
+        assert_eq!(scheduler.running(), ["slow"]);
+
+        scheduler.shutdown();
+        assert!(!scheduler.submit("late", Priority::User, || {}));
+        assert_eq!(wait(&scheduler, Duration::from_secs(5), || true), ["slow"]);
+        assert!(wait(&scheduler, Duration::from_secs(5), || false).is_empty());
+    }
+}
@@ -10,7 +10,15 @@ const KEY_GITHUB_TOKEN: &str = "github-oauth-token";
 const KEY_GITHUB_PAT: &str = "github-pat";
 const KEY_AI_API_KEY: &str = "ai-api-key";
 
+/// Store a secret in the OS keychain. Tests never touch the real keychain,
+/// and see it as unavailable.
+#[cfg(test)]
+fn set_secret(_key: &str, _value: &str) -> Result<()> {
+    anyhow::bail!("No keychain in tests")
+}
+
 /// Store a secret in the OS keychain.
+#[cfg(not(test))]
 fn set_secret(key: &str, value: &str) -> Result<()> {
     let entry = keyring::Entry::new(SERVICE_NAME, key)?;
     entry.set_password(value)?;
@@ -39,6 +47,11 @@ fn get_secret(key: &str) -> Option<String> {
 }
 
 /// Delete a secret from the OS keychain.
+#[cfg(test)]
+fn delete_secret(_key: &str) {}
+
+/// Delete a secret from the OS keychain.
+#[cfg(not(test))]
 fn delete_secret(key: &str) {
     if let Ok(entry) = keyring::Entry::new(SERVICE_NAME, key) {
         let _ = entry.delete_credential();
@@ -46,6 +59,24 @@ fn delete_secret(key: &str) {
     }
 }
 
+/// Whether the OS keychain can be used, or why not.
+#[cfg(test)]
+pub fn availability() -> Result<(), String> {
+    Err("No keychain in tests".to_string())
+}
+
+/// Whether the OS keychain can be used, or why not. Looks up an entry that
+/// doesn't exist: "not found" means the keychain answered.
+#[cfg(not(test))]
+pub fn availability() -> Result<(), String> {
+    let entry =
+        keyring::Entry::new(SERVICE_NAME, "availability-check").map_err(|e| e.to_string())?;
+    match entry.get_password() {
+        Ok(_) | Err(keyring::Error::NoEntry) => Ok(()),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
 // ── GitHub OAuth Token ──────────────────────────────────────────────
 
 /// Store the GitHub OAuth token securely.
@@ -97,13 +128,28 @@ pub fn delete_ai_api_key() {
     delete_secret(KEY_AI_API_KEY);
 }
 
+/// Save a newly entered AI API key: in the keychain, or in the config file if
+/// the keychain is unavailable — then any old keychain entry is removed, as it
+/// would be read first. Returns whether the key went to the keychain.
+pub fn save_ai_api_key(config: &mut crate::config::AiConfig, key: &str) -> bool {
+    if store_ai_api_key(key).is_ok() {
+        config.api_key = None;
+        true
+    } else {
+        log::warn!("Keychain unavailable, storing AI API key in config file");
+        delete_ai_api_key();
+        config.api_key = Some(key.to_string());
+        false
+    }
+}
+
 // ── Bulk Operations ─────────────────────────────────────────────────
 
-/// Delete all zit secrets from the keychain (used on logout).
-pub fn clear_all() {
+/// Delete the GitHub secrets from the keychain (used on logout). The AI key
+/// isn't GitHub's and stays.
+pub fn clear_github() {
     delete_github_token();
     delete_github_pat();
-    delete_ai_api_key();
 }
 
 /// Migrate plaintext tokens from config to keychain.
@@ -112,30 +158,30 @@ pub fn migrate_from_config(config: &mut crate::config::Config) -> u32 {
     let mut count = 0;
 
     // Migrate GitHub OAuth token
-    if let Some(ref token) = config.github.oauth_token {
-        if store_github_token(token).is_ok() {
-            config.github.oauth_token = None;
-            count += 1;
-            log::info!("Migrated GitHub OAuth token to keychain");
-        }
+    if let Some(ref token) = config.github.oauth_token
+        && store_github_token(token).is_ok()
+    {
+        config.github.oauth_token = None;
+        count += 1;
+        log::info!("Migrated GitHub OAuth token to keychain");
     }
 
     // Migrate GitHub PAT
-    if let Some(ref pat) = config.github.pat {
-        if store_github_pat(pat).is_ok() {
-            config.github.pat = None;
-            count += 1;
-            log::info!("Migrated GitHub PAT to keychain");
-        }
+    if let Some(ref pat) = config.github.pat
+        && store_github_pat(pat).is_ok()
+    {
+        config.github.pat = None;
+        count += 1;
+        log::info!("Migrated GitHub PAT to keychain");
     }
 
     // Migrate AI API key
-    if let Some(ref key) = config.ai.api_key {
-        if store_ai_api_key(key).is_ok() {
-            config.ai.api_key = None;
-            count += 1;
-            log::info!("Migrated AI API key to keychain");
-        }
+    if let Some(ref key) = config.ai.api_key
+        && store_ai_api_key(key).is_ok()
+    {
+        config.ai.api_key = None;
+        count += 1;
+        log::info!("Migrated AI API key to keychain");
     }
 
     count
@@ -152,4 +198,15 @@ mod tests {
         assert_eq!(KEY_GITHUB_PAT, "github-pat");
         assert_eq!(KEY_AI_API_KEY, "ai-api-key");
     }
+
+    #[test]
+    fn test_ai_key_falls_back_to_config_without_keychain() {
+        let mut config = crate::config::AiConfig {
+            api_key: Some("old-key".to_string()),
+            ..Default::default()
+        };
+        assert!(!save_ai_api_key(&mut config, "new-key"));
+        assert_eq!(config.api_key.as_deref(), Some("new-key"));
+        assert_eq!(config.resolved_api_key().as_deref(), Some("new-key"));
+    }
 }
@@ -1,101 +1,143 @@
 mod ai;
 mod app;
+mod cli;
 mod config;
+mod crash;
+mod doctor;
 mod event;
 mod git;
+mod http;
+mod ipc;
+mod jobs;
 mod keychain;
+mod paths;
+mod recipes;
+mod stats;
+mod tasks;
 mod ui;
 
 use anyhow::{Context, Result};
 use crossterm::{
+    event::MouseEventKind,
     execute,
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+    terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
 };
 use ratatui::{
+    Frame, Terminal,
     backend::CrosstermBackend,
     layout::Rect,
     style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Clear, Paragraph, Wrap},
-    Frame, Terminal,
 };
 use std::io;
+use std::sync::mpsc;
 
+use crate::ui::icons::Icon;
 use app::{App, Popup, View};
+use clap::Parser;
+use cli::{Cli, Command, Exit};
 use event::{AppEvent, EventHandler};
 
-const VERSION: &str = env!("CARGO_PKG_VERSION");
-const PKG_NAME: &str = env!("CARGO_PKG_NAME");
-const PKG_DESC: &str = env!("CARGO_PKG_DESCRIPTION");
-
-fn print_help() {
-    println!("{} v{}", PKG_NAME, VERSION);
-    println!("{}", PKG_DESC);
-    println!();
-    println!("USAGE:");
-    println!("    zit [OPTIONS]");
-    println!();
-    println!("OPTIONS:");
-    println!("    -h, --help       Print this help message");
-    println!("    -v, --version    Print version information");
-    println!("    --verbose        Enable verbose logging (ZIT_LOG=debug)");
-    println!("    --no-ai          Disable AI features for this session");
-    println!();
-    println!("ENVIRONMENT:");
-    println!("    ZIT_LOG          Set log level (error, warn, info, debug, trace)");
-    println!("    ZIT_AI_ENDPOINT  AI mentor API endpoint URL");
-    println!("    ZIT_AI_API_KEY   AI mentor API key");
-    println!();
-    println!("VIEWS:");
-    println!("    s  Staging     c  Commit      b  Branches");
-    println!("    l  Timeline    t  Time Travel  r  Reflog");
-    println!("    g  GitHub      a  AI Mentor    x  Stash");
-    println!("    ?  Help");
-}
-
 fn main() -> Result<()> {
-    // Parse CLI flags
-    let args: Vec<String> = std::env::args().skip(1).collect();
-    let mut no_ai = false;
-    for arg in &args {
-        match arg.as_str() {
-            "-h" | "--help" => {
-                print_help();
-                return Ok(());
-            }
-            "-v" | "--version" => {
-                println!("{} {}", PKG_NAME, VERSION);
-                return Ok(());
-            }
-            "--verbose" => {
-                unsafe { std::env::set_var("ZIT_LOG", "debug") };
-            }
-            "--no-ai" => {
-                no_ai = true;
-            }
-            other => {
-                eprintln!("Unknown option: {}", other);
-                eprintln!("Run 'zit --help' for usage.");
-                std::process::exit(1);
-            }
-        }
+    let cli = Cli::parse();
+    if cli.verbose {
+        unsafe { std::env::set_var("ZIT_LOG", "debug") };
+    }
+    if let Some(path) = &cli.config {
+        paths::set_config_flag(path);
     }
 
-    // Initialize logging
-    env_logger::Builder::from_env(env_logger::Env::new().filter("ZIT_LOG"))
-        .format_timestamp(None)
-        .format_target(false)
-        .init();
-
-    log::info!("Starting {} v{}", PKG_NAME, VERSION);
+    let quiet = cli.quiet;
+    match cli.command {
+        None | Some(Command::Tui) => {}
+        Some(Command::Completions { shell }) => {
+            cli::completions(shell);
+            return Ok(());
+        }
+        Some(Command::Doctor) => {
+            let exit = cli::doctor(quiet).unwrap_or_else(|e| fail(&e));
+            std::process::exit(exit as i32);
+        }
+        Some(Command::Prompt { format }) => {
+            // Quiet outside a repository: a prompt runs this everywhere
+            let exit = cli::prompt(format).unwrap_or(Exit::Error);
+            std::process::exit(exit as i32);
+        }
+        Some(Command::Clone { url, dir }) => {
+            let dir = cli::clone(&url, dir, quiet).unwrap_or_else(|e| fail(&e));
+            std::env::set_current_dir(&dir)
+                .with_context(|| format!("Failed to enter {}", dir.display()))?;
+        }
+        Some(command) => {
+            require_git_repo();
+            scope_to(cli.path.as_deref());
+            let result = match command {
+                Command::Status => cli::status(quiet),
+                Command::Log { count } => cli::log(count, quiet),
+                Command::Pr { state } => cli::pr(&state, quiet),
+                Command::Remote { command, target } => {
+                    ipc::send(&command, &target).map(|message| {
+                        if !quiet {
+                            println!("{}", message);
+                        }
+                        Exit::Ok
+                    })
+                }
+                _ => unreachable!("handled above"),
+            };
+            let exit = result.unwrap_or_else(|e| fail(&e));
+            std::process::exit(exit as i32);
+        }
+    }
+    run_tui(cli.no_ai, cli.no_color, cli.server, cli.path.as_deref())
+}
 
-    // Check if we're in a git repo
+/// Exit with an explanation unless the current directory is in a git repository.
+fn require_git_repo() {
     if !git::runner::is_git_repo() {
         eprintln!(
             "Error: Not a git repository. Run 'git init' first or navigate to a git repository."
         );
-        std::process::exit(1);
+        std::process::exit(Exit::NotARepo as i32);
+    }
+}
+
+/// Apply `--path`, relative to the current directory.
+fn scope_to(path: Option<&std::path::Path>) {
+    let Some(path) = path else {
+        return;
+    };
+    let cwd = std::env::current_dir().unwrap_or_default();
+    match git::scope::resolve(path, &cwd) {
+        Ok(scope) => git::scope::set(scope),
+        Err(e) => fail(&anyhow::anyhow!("--path: {:#}", e)),
     }
+}
+
+/// Report a subcommand's error and exit with the code scripts can check for it.
+fn fail(err: &anyhow::Error) -> ! {
+    eprintln!("Error: {}", err);
+    std::process::exit(Exit::of_error(err) as i32);
+}
+
+fn run_tui(
+    no_ai: bool,
+    no_color: bool,
+    server: bool,
+    path: Option<&std::path::Path>,
+) -> Result<()> {
+    // Initialize logging
+    crash::init_logging();
+
+    log::info!(
+        "Starting {} v{}",
+        env!("CARGO_PKG_NAME"),
+        env!("CARGO_PKG_VERSION")
+    );
+
+    require_git_repo();
+    scope_to(path);
 
     // Check git version meets minimum requirements
     if let Err(e) = git::runner::check_git_version() {
@@ -113,29 +155,21 @@ fn main() -> Result<()> {
         log::info!("AI features disabled via --no-ai flag");
     }
 
-    // Migrate plaintext tokens to OS keychain (one-time)
-    let migrated = keychain::migrate_from_config(&mut config);
-    if migrated > 0 {
-        if let Err(e) = config.save() {
-            log::warn!("Failed to save config after keychain migration: {}", e);
-        } else {
-            log::info!("Migrated {} secret(s) from config to OS keychain", migrated);
-        }
-    }
-
     let tick_rate = config.general.tick_rate_ms;
+    git::backend::configure(&config.general.git_backend);
+    git::runner::set_observer(stats::record_git);
+    stats::set_enabled(config.stats.enabled);
 
-    // Install panic hook that restores the terminal before printing the panic
-    let original_hook = std::panic::take_hook();
-    std::panic::set_hook(Box::new(move |panic_info| {
-        let _ = disable_raw_mode();
-        let _ = execute!(
-            io::stdout(),
-            LeaveAlternateScreen,
-            crossterm::event::DisableMouseCapture
-        );
-        original_hook(panic_info);
-    }));
+    ui::icons::set_mode(ui::icons::IconMode::resolve(&config.ui.icons));
+    ui::theme::set_mode(ui::theme::ColorMode::resolve(&config.ui.colors, no_color));
+    ui::zen::set(config.ui.zen);
+    ui::datetime::set_format(ui::datetime::DateFormat::from_config(
+        &config.ui.date_format,
+    ));
+
+    // Restore the terminal on panics and termination signals
+    crash::install_panic_hook();
+    crash::install_signal_handlers();
 
     // Setup terminal
     enable_raw_mode().context("Failed to enable raw mode")?;
@@ -143,92 +177,462 @@ fn main() -> Result<()> {
     execute!(
         stdout,
         EnterAlternateScreen,
-        crossterm::event::EnableMouseCapture
+        crossterm::event::EnableMouseCapture,
+        crossterm::event::EnableBracketedPaste
     )
     .context("Failed to enter alternate screen")?;
+    crash::set_tui_active(true);
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
+    // Let other zit instances on this repository know about this one
+    let instance = git::lock::register();
+
     // Create app and event handler
     let mut app = App::new(config);
+    app.repo_warning = git::lock::warning();
     let events = EventHandler::new(tick_rate);
+    app.output_pane.set_waker(events.waker());
+    app.tasks.set_waker(events.waker());
+    let server = if server {
+        match ipc::serve(events.waker()) {
+            Ok(server) => Some(server),
+            Err(e) => {
+                app.set_status(format!("Error: {}", e));
+                None
+            }
+        }
+    } else {
+        None
+    };
+    // Keychain access and AI setup wait until the first frame is up
+    app.start_deferred_init(events.waker());
 
     // Main loop
-    let res = run_app(&mut terminal, &mut app, &events);
+    let mut reload = ConfigReload {
+        watcher: config::ConfigWatcher::new(config::Config::path()),
+        no_ai,
+        no_color,
+    };
+    let res = run_app(&mut terminal, &mut app, &events, &mut reload);
 
     // Restore terminal
+    crash::set_tui_active(false);
     disable_raw_mode()?;
     execute!(
         terminal.backend_mut(),
         LeaveAlternateScreen,
-        crossterm::event::DisableMouseCapture
+        crossterm::event::DisableMouseCapture,
+        crossterm::event::DisableBracketedPaste
     )?;
     terminal.show_cursor()?;
+    finish_background_work(&app);
+    stats::save();
+    // process::exit below skips destructors
+    drop(instance);
+    drop(server);
 
-    // Remove panic hook since terminal is restored
-    let _ = std::panic::take_hook();
-
+    if let Some(sig) = crash::terminated() {
+        std::process::exit(128 + sig);
+    }
     if let Err(err) = res {
         eprintln!("Error: {}", err);
+        match crash::write_error_report(&err) {
+            Ok(path) => eprintln!("A diagnostic report was written to {}", path.display()),
+            Err(e) => log::warn!("Failed to write diagnostic report: {}", e),
+        }
         std::process::exit(1);
     }
 
     Ok(())
 }
 
+/// Let pushes, snapshots and GitHub requests still running finish, up to
+/// `jobs::QUIT_TIMEOUT` (no wait after a signal), and say what's left behind.
+fn finish_background_work(app: &App) {
+    app.jobs.shutdown();
+    let mut running = jobs::in_flight();
+    running.extend(app.jobs.running());
+    if !running.is_empty() && crash::terminated().is_none() {
+        eprintln!("Waiting for {} to finish...", running.join(", "));
+    }
+    let left = jobs::wait(&app.jobs, jobs::QUIT_TIMEOUT, || {
+        crash::terminated().is_some()
+    });
+    for name in left {
+        eprintln!(
+            "Warning: '{}' was still running when zit exited; any git command it started keeps running in the background",
+            name
+        );
+    }
+}
+
+/// Picks up edits to config.toml while zit runs. Command-line flags keep
+/// winning over the file.
+struct ConfigReload {
+    watcher: config::ConfigWatcher,
+    no_ai: bool,
+    no_color: bool,
+}
+
+impl ConfigReload {
+    /// Apply config.toml if it changed on disk. Returns whether it did.
+    fn poll(&mut self, app: &mut App, events: &EventHandler) -> bool {
+        // Wait for keychain migration, which may rewrite the file itself
+        if app.starting_up() {
+            return false;
+        }
+        let Some(loaded) = self.watcher.poll() else {
+            return false;
+        };
+        let mut config = match loaded {
+            Ok(config) => config,
+            Err(e) => {
+                let reason = e.to_string();
+                app.set_status(format!(
+                    "{} config.toml not reloaded: {}",
+                    Icon::Warning,
+                    reason.lines().next().unwrap_or_default()
+                ));
+                return true;
+            }
+        };
+        if self.no_ai {
+            config.ai.enabled = false;
+        }
+        let changed = app.reload_config(config);
+        if changed.is_empty() {
+            // zit's own save, or an edit that changed nothing
+            return false;
+        }
+        events.set_tick_rate(app.config.general.tick_rate_ms);
+        ui::theme::set_mode(ui::theme::ColorMode::resolve(
+            &app.config.ui.colors,
+            self.no_color,
+        ));
+        let sections: Vec<String> = changed.iter().map(|s| format!("[{}]", s)).collect();
+        app.set_status(format!("✓ Reloaded config.toml: {}", sections.join(", ")));
+        true
+    }
+}
+
 fn run_app(
     terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
     app: &mut App,
     events: &EventHandler,
+    reload: &mut ConfigReload,
 ) -> Result<()> {
+    let mut dirty = true;
+    let mut first_frame = true;
     loop {
-        // Draw
-        terminal.draw(|f| draw(f, app))?;
-
-        // Handle events
-        match events.next()? {
-            AppEvent::Key(key) => {
-                app.poll_ai_result();
-                app.poll_agent_command();
-                app.handle_key(key)?;
+        if crash::terminated().is_some() {
+            return Ok(());
+        }
+
+        // Draw only when something changed since the last frame
+        if dirty {
+            terminal.draw(|f| {
+                draw(f, app);
+                ui::theme::apply(f.buffer_mut());
+            })?;
+            dirty = false;
+        }
+        if first_frame {
+            // The first frame shows placeholders; load the real data behind it
+            first_frame = false;
+            app.refresh_in_background(jobs::Priority::User);
+            dirty = true;
+            continue;
+        }
+
+        // Wake up for a deferred reload even if no event arrives
+        let event = match app.pending_reload() {
+            Some(wait) => match events.next_timeout(wait) {
+                Ok(event) => Some(event),
+                Err(mpsc::RecvTimeoutError::Timeout) => None,
+                Err(e) => return Err(e.into()),
+            },
+            None => Some(events.next()?),
+        };
+        if let Some(event) = event {
+            dirty |= handle_event(app, event)?;
+            // Coalesce: take everything already queued (e.g. key repeat from a
+            // held j/k) before drawing again
+            while app.running && app.editor_request.is_none() {
+                let Some(event) = events.try_next() else {
+                    break;
+                };
+                dirty |= handle_event(app, event)?;
             }
-            AppEvent::Tick => {
-                app.poll_ai_result();
-                app.poll_agent_command();
-                app.tick_animations();
-                // Auto-refresh on tick for the current view
-                app.refresh();
-                // Poll GitHub Device Flow if active
-                if app.view == View::GitHub {
-                    ui::github::tick_device_auth(app);
-                    ui::github::tick_pr_state(app);
-                    ui::github::tick_actions_state(app);
-                }
+        }
+        dirty |= app.run_pending_reloads(false);
+        dirty |= reload.poll(app, events);
+
+        if let Some(target) = app.editor_request.take() {
+            run_editor(terminal, events, app, &target)?;
+            dirty = true;
+        }
+
+        if !app.running {
+            return Ok(());
+        }
+    }
+}
+
+/// Handle one event. Returns whether the screen needs redrawing.
+fn handle_event(app: &mut App, event: AppEvent) -> Result<bool> {
+    match event {
+        AppEvent::Key(key) => {
+            app.poll_startup();
+            app.poll_refresh();
+            app.poll_recipe_steps();
+            app.poll_ai_result();
+            app.poll_agent_command();
+            app.poll_output_pane();
+            app.poll_push_gate();
+            app.handle_key(key)?;
+        }
+        AppEvent::Tick => {
+            app.poll_startup();
+            app.poll_ai_result();
+            app.poll_agent_command();
+            app.poll_output_pane();
+            app.poll_push_gate();
+            app.poll_snapshot();
+            app.repo_warning = git::lock::warning();
+            ui::github::tick_pr_watch(app);
+            if app.view == View::Dashboard {
+                ui::dashboard::tick_milestone(app);
             }
-            AppEvent::Mouse(mouse) => {
-                app.poll_ai_result();
-                app.poll_agent_command();
-                app.handle_mouse(mouse);
+            app.tick_animations();
+            // Auto-refresh on tick for the current view, off the main thread
+            app.poll_refresh();
+            app.poll_recipe_steps();
+            app.refresh_in_background(jobs::Priority::Background);
+            // Poll GitHub Device Flow if active
+            if app.view == View::GitHub {
+                ui::github::tick_device_auth(app);
+                ui::github::tick_pr_state(app);
+                ui::github::tick_issues_state(app);
+                ui::github::tick_actions_state(app);
             }
-            AppEvent::Resize(_, _) => {
-                // Terminal will handle resize automatically
+        }
+        AppEvent::Mouse(mouse) => {
+            // Bare pointer movement changes nothing on screen
+            if mouse.kind == MouseEventKind::Moved {
+                return Ok(false);
             }
+            app.poll_ai_result();
+            app.poll_agent_command();
+            app.handle_mouse(mouse);
+        }
+        AppEvent::Redraw => {
+            app.poll_startup();
+            app.poll_refresh();
+            app.poll_recipe_steps();
+            app.poll_output_pane();
         }
+        AppEvent::Paste(text) => app.handle_paste(&text)?,
+        AppEvent::Remote(request) => {
+            let result = ipc::run(app, request.command);
+            match &result {
+                Ok(message) => app.set_status(message.clone()),
+                Err(e) => app.set_status(format!("Error: {}", e)),
+            }
+            let _ = request.reply.send(result.map_err(|e| e.to_string()));
+        }
+        AppEvent::Resize(_, _) => {
+            // Terminal will handle resize automatically
+        }
+    }
+    Ok(true)
+}
 
-        if !app.running {
-            return Ok(());
+/// Hand the terminal to the user's editor, then take it back.
+fn run_editor(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    events: &EventHandler,
+    app: &mut App,
+    target: &ui::editor::EditorTarget,
+) -> Result<()> {
+    events.pause();
+    disable_raw_mode()?;
+    execute!(
+        terminal.backend_mut(),
+        LeaveAlternateScreen,
+        crossterm::event::DisableMouseCapture,
+        crossterm::event::DisableBracketedPaste
+    )?;
+    terminal.show_cursor()?;
+
+    let result = ui::editor::open(target);
+
+    enable_raw_mode()?;
+    execute!(
+        terminal.backend_mut(),
+        EnterAlternateScreen,
+        crossterm::event::EnableMouseCapture,
+        crossterm::event::EnableBracketedPaste
+    )?;
+    terminal.clear()?;
+    events.resume();
+
+    match result {
+        Ok(()) => {
+            if *target == ui::editor::EditorTarget::PullRequestBody {
+                ui::github::read_edited_pr_body(app);
+            }
+            app.set_status(target.edited())
         }
+        Err(e) => app.set_status(format!("Error: {}", e)),
     }
+    app.refresh();
+    Ok(())
 }
 
 fn draw(f: &mut Frame, app: &mut App) {
-    let area = f.area();
+    let full_area = f.area();
+
+    if ui::utils::is_too_small(full_area) {
+        render_too_small(f, full_area);
+        return;
+    }
+
+    // Reserve space for the output drawer at the bottom when it is open
+    let pane_height = app
+        .output_pane
+        .height()
+        .min(full_area.height.saturating_sub(3) / 2);
+    let mut area = Rect {
+        height: full_area.height - pane_height,
+        ..full_area
+    };
+    if pane_height > 0 {
+        let pane_area = Rect {
+            y: full_area.y + area.height,
+            height: pane_height,
+            ..full_area
+        };
+        ui::output_pane::render(f, pane_area, &app.output_pane);
+    }
+
+    // Warn about other zit or git processes working on the repository
+    if let Some(warning) = &app.repo_warning {
+        let banner = Rect { height: 1, ..area };
+        f.render_widget(
+            Paragraph::new(format!(" {} {}", Icon::Warning, warning)).style(
+                Style::default()
+                    .fg(Color::Black)
+                    .bg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            banner,
+        );
+        area.y += 1;
+        area.height -= 1;
+    }
+
+    // Keep detached HEAD in view, with the ways back to a branch
+    if let Some(detached) = &app.detached_head {
+        let banner = Rect { height: 1, ..area };
+        let orphaned = if detached.orphaned > 0 {
+            format!(" — {} commit(s) on no branch", detached.orphaned)
+        } else {
+            String::new()
+        };
+        let back = detached
+            .previous
+            .as_ref()
+            .map(|b| format!(" · Ctrl+P back to {}", b))
+            .unwrap_or_default();
+        f.render_widget(
+            Paragraph::new(format!(
+                " {} Detached HEAD at {}{} · Ctrl+B create branch here{}",
+                Icon::Warning,
+                detached.short_hash,
+                orphaned,
+                back
+            ))
+            .style(
+                Style::default()
+                    .fg(Color::Black)
+                    .bg(Color::Magenta)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            banner,
+        );
+        area.y += 1;
+        area.height -= 1;
+    }
+
+    // Where this view sits in the trail Esc walks back through
+    if app.view != View::Dashboard && !ui::zen::is_on() {
+        let crumbs = app.breadcrumb();
+        let last = crumbs.len() - 1;
+        let mut spans = Vec::new();
+        for (i, crumb) in crumbs.into_iter().enumerate() {
+            if i > 0 {
+                spans.push(Span::styled(" › ", Style::default().fg(Color::DarkGray)));
+            }
+            let style = if i == last {
+                Style::default()
+                    .fg(Color::White)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::DarkGray)
+            };
+            spans.push(Span::styled(crumb, style));
+        }
+        spans.insert(0, Span::raw(" "));
+        if let Some(dir) = git::scope::get() {
+            spans.push(Span::styled(
+                format!("  in {}/", dir),
+                Style::default().fg(Color::Yellow),
+            ));
+        }
+        spans.push(Span::styled(
+            "  Esc back",
+            Style::default().fg(Color::DarkGray),
+        ));
+        f.render_widget(
+            Paragraph::new(Line::from(spans)),
+            Rect { height: 1, ..area },
+        );
+        area.y += 1;
+        area.height -= 1;
+    }
+
+    // Status bar along the bottom of every view
+    let bar = Rect {
+        y: area.y + area.height - 1,
+        height: 1,
+        ..area
+    };
+    area.height -= 1;
+    let job = if app.output_pane.running {
+        Some(app.output_pane.title.as_str())
+    } else if app.ai_loading {
+        Some("AI is thinking")
+    } else {
+        None
+    };
+    ui::status_bar::render(
+        f,
+        bar,
+        &app.repo_summary,
+        &ui::status_bar::Activity {
+            job,
+            spinner_frame: app.ai_mentor_state.spinner_frame as usize,
+            message: app.status_message.as_deref(),
+        },
+    );
 
     // Render the current view
     match app.view {
         View::Dashboard => {
             let ai_available = app.ai_client.is_some();
-            let loading = app.ai_loading;
+            // A spinner until the AI client has been set up
+            let loading = app.ai_loading || app.starting_up();
             let provider_label = app
                 .ai_client
                 .as_ref()
@@ -238,7 +642,6 @@ fn draw(f: &mut Frame, app: &mut App) {
                 f,
                 area,
                 &app.dashboard_state,
-                &app.status_message,
                 &app.ai_mentor_state,
                 ai_available,
                 loading,
@@ -286,6 +689,18 @@ fn draw(f: &mut Frame, app: &mut App) {
         View::CherryPick => {
             ui::cherry_pick::render(f, area, &mut app.cherry_pick_state);
         }
+        View::Outgoing => {
+            ui::outgoing::render(f, area, &mut app.outgoing_state);
+        }
+        View::Rebase => {
+            ui::rebase::render(f, area, &mut app.rebase_state);
+        }
+        View::Tags => {
+            ui::tags::render(f, area, &mut app.tags_state);
+        }
+        View::Blame => {
+            ui::blame::render(f, area, &mut app.blame_state);
+        }
         View::Agent => {
             let ai_available = app.ai_client.is_some();
             let loading = app.ai_loading;
@@ -305,10 +720,17 @@ fn draw(f: &mut Frame, app: &mut App) {
         }
     }
 
+    // Popups keep their frames: they float over the view and need an edge
+    ui::zen::apply(f.buffer_mut());
+
     // Render popup overlay
+    if let Popup::Help { scroll } = &mut app.popup {
+        ui::help::render(f, area, app.view, scroll);
+    }
     match &app.popup {
-        Popup::Help => {
-            ui::help::render(f, area, app.view);
+        Popup::Help { .. } => {}
+        Popup::KeyInspect { key } => {
+            ui::help::render_inspector(f, area, app.view, key.as_ref());
         }
         Popup::Confirm { title, message, .. } => {
             render_popup(f, area, title, message, Color::Yellow);
@@ -317,21 +739,24 @@ fn draw(f: &mut Frame, app: &mut App) {
             title,
             prompt,
             value,
-            ..
-        } => {
-            let content = format!("{}{}", prompt, value);
-            render_popup(f, area, title, &content, Color::Cyan);
-        }
+            on_submit,
+        } => render_input_popup(f, area, app, title, prompt, value, on_submit),
         Popup::Message { title, message } => {
             render_popup(f, area, title, message, Color::White);
         }
         Popup::FollowUp {
             title,
-            context: _,
+            context,
             suggestions,
             selected,
         } => {
-            let popup_area = ui::utils::centered_rect(60, 50, area);
+            // Multi-line context (a report, a draft) gets a bigger popup to show it in
+            let document = context.lines().count() > 1;
+            let popup_area = if document {
+                ui::utils::centered_rect(70, 80, area)
+            } else {
+                ui::utils::centered_rect(60, 50, area)
+            };
             f.render_widget(Clear, popup_area);
 
             let mut lines = vec![
@@ -344,6 +769,28 @@ fn draw(f: &mut Frame, app: &mut App) {
                 )),
                 Line::from(""),
             ];
+            if !context.trim().is_empty() {
+                // Leave room for the suggestions and key hints below
+                let reserved = suggestions.len() * 2 + 7;
+                let room = (popup_area.height as usize).saturating_sub(reserved).max(1);
+                // Wrapped here rather than by the paragraph, so the rows can be
+                // counted and the suggestions always stay in view
+                let width = (popup_area.width as usize).saturating_sub(6);
+                let rows = ui::utils::wrap_to_width(context.trim_end(), width);
+                for row in rows.iter().take(room) {
+                    lines.push(Line::from(Span::styled(
+                        format!("  {}", row),
+                        Style::default().fg(Color::White),
+                    )));
+                }
+                if rows.len() > room {
+                    lines.push(Line::from(Span::styled(
+                        format!("  … {} more line(s)", rows.len() - room),
+                        Style::default().fg(Color::DarkGray),
+                    )));
+                }
+                lines.push(Line::from(""));
+            }
 
             for (i, item) in suggestions.iter().enumerate() {
                 let is_sel = i == *selected;
@@ -402,7 +849,7 @@ fn draw(f: &mut Frame, app: &mut App) {
             let mut lines = vec![
                 Line::from(""),
                 Line::from(Span::styled(
-                    "  🛡 Potential secrets detected!",
+                    format!("  {} Potential secrets detected!", Icon::Shield),
                     Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
                 )),
                 Line::from(Span::styled(
@@ -452,7 +899,7 @@ fn draw(f: &mut Frame, app: &mut App) {
                 .block(
                     Block::default()
                         .title(Span::styled(
-                            " 🛡 Secret Scanner ",
+                            format!(" {} Secret Scanner ", Icon::Shield),
                             Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
                         ))
                         .borders(Borders::ALL)
@@ -466,8 +913,119 @@ fn draw(f: &mut Frame, app: &mut App) {
     }
 }
 
+/// Minimal screen shown when the terminal is below the supported size.
+fn render_too_small(f: &mut Frame, area: Rect) {
+    let fits = |ok: bool| {
+        if ok {
+            Style::default().fg(Color::Green)
+        } else {
+            Style::default().fg(Color::Red)
+        }
+    };
+    let lines = vec![
+        Line::from(Span::styled(
+            "Terminal too small",
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(vec![
+            Span::raw("Current: "),
+            Span::styled(
+                area.width.to_string(),
+                fits(area.width >= ui::utils::MIN_WIDTH),
+            ),
+            Span::raw("x"),
+            Span::styled(
+                area.height.to_string(),
+                fits(area.height >= ui::utils::MIN_HEIGHT),
+            ),
+        ]),
+        Line::from(format!(
+            "Required: {}x{}",
+            ui::utils::MIN_WIDTH,
+            ui::utils::MIN_HEIGHT
+        )),
+    ];
+    let top = area.height.saturating_sub(lines.len() as u16) / 2;
+    let centered = Rect {
+        y: area.y + top,
+        height: area.height - top,
+        ..area
+    };
+    f.render_widget(
+        Paragraph::new(lines).alignment(ratatui::layout::Alignment::Center),
+        centered,
+    );
+}
+
+/// The Input popup: the prompt, the value with its cursor (masked for
+/// secrets), and what's wrong with the value, if anything.
+fn render_input_popup(
+    f: &mut Frame,
+    area: Rect,
+    app: &App,
+    title: &str,
+    prompt: &str,
+    value: &str,
+    on_submit: &app::InputAction,
+) {
+    let percent_x = if ui::utils::is_narrow(area) { 90 } else { 50 };
+    let percent_y = if ui::utils::is_short(area) { 80 } else { 40 };
+    let popup_area = ui::utils::centered_rect(percent_x, percent_y, area);
+    f.render_widget(Clear, popup_area);
+
+    let shown = if on_submit.is_secret() {
+        ui::line_edit::mask(value)
+    } else {
+        value.to_string()
+    };
+    let back = app.input_cursor_back.min(shown.chars().count());
+    let (before, after) = ui::line_edit::split(&shown, back);
+    let mut after = after.chars();
+    let under_cursor = after.next().map_or(" ".to_string(), String::from);
+
+    let text = Style::default().fg(Color::White);
+    let mut prompt_lines: Vec<&str> = prompt.lines().collect();
+    let last = prompt_lines.pop().unwrap_or("");
+    let mut lines: Vec<Line> = prompt_lines
+        .into_iter()
+        .map(|l| Line::from(Span::styled(format!("  {}", l), text)))
+        .collect();
+    lines.push(Line::from(vec![
+        Span::styled(format!("  {}{}", last, before), text),
+        Span::styled(under_cursor, text.add_modifier(Modifier::REVERSED)),
+        Span::styled(after.as_str().to_string(), text),
+    ]));
+    if let Some(error) = &app.input_error {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            format!("  {} {}", ui::icons::Icon::Warning, error),
+            Style::default().fg(Color::Red),
+        )));
+    }
+
+    let popup = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .title(Span::styled(
+                    format!(" {} ", title),
+                    Style::default()
+                        .fg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD),
+                ))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan)),
+        )
+        .wrap(Wrap { trim: false });
+    f.render_widget(popup, popup_area);
+}
+
 fn render_popup(f: &mut Frame, area: Rect, title: &str, message: &str, border_color: Color) {
-    let popup_area = ui::utils::centered_rect(50, 40, area);
+    // Give popups more of the screen when there is little of it
+    let percent_x = if ui::utils::is_narrow(area) { 90 } else { 50 };
+    let percent_y = if ui::utils::is_short(area) { 80 } else { 40 };
+    let popup_area = ui::utils::centered_rect(percent_x, percent_y, area);
     f.render_widget(Clear, popup_area);
 
     let lines: Vec<Line> = message
@@ -0,0 +1,149 @@
+//! Where zit keeps its files. Settings live in the platform's config directory
+//! (`$XDG_CONFIG_HOME/zit` on Linux, `%APPDATA%\zit` on Windows,
+//! `~/Library/Application Support/zit` on macOS) unless `--config` or
+//! `ZIT_CONFIG` name another file. What zit writes for itself — AI history,
+//! the AI audit log, crash reports — goes in the state directory
+//! (`$XDG_STATE_HOME/zit` on Linux, `%LOCALAPPDATA%\zit` on Windows).
+
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Environment variable naming the config file.
+pub const CONFIG_ENV: &str = "ZIT_CONFIG";
+
+/// The file `--config` named, made absolute so `zit clone` moving into the
+/// new repository doesn't change what it points at.
+static CONFIG_FLAG: OnceLock<PathBuf> = OnceLock::new();
+
+/// Where the config file's location came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigSource {
+    Flag,
+    Env,
+    Default,
+}
+
+impl std::fmt::Display for ConfigSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Flag => "from --config",
+            Self::Env => "from ZIT_CONFIG",
+            Self::Default => "default",
+        })
+    }
+}
+
+/// Use `path` as the config file for this run (`--config`).
+pub fn set_config_flag(path: &Path) {
+    let path = std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf());
+    let _ = CONFIG_FLAG.set(path);
+}
+
+/// The platform's config directory for zit.
+pub fn config_dir() -> PathBuf {
+    dirs::config_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("zit")
+}
+
+/// The platform's state directory for zit. macOS has none, so there it's
+/// the same as the config directory.
+pub fn state_dir() -> PathBuf {
+    dirs::state_dir()
+        .or_else(dirs::data_local_dir)
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("zit")
+}
+
+/// The config file and where its location came from.
+pub fn config_file() -> (PathBuf, ConfigSource) {
+    resolve_config(
+        CONFIG_FLAG.get().map(PathBuf::as_path),
+        std::env::var_os(CONFIG_ENV),
+        &config_dir(),
+    )
+}
+
+/// `--config` wins over `ZIT_CONFIG`, which wins over the default; an empty
+/// variable counts as unset.
+fn resolve_config(
+    flag: Option<&Path>,
+    env: Option<std::ffi::OsString>,
+    default_dir: &Path,
+) -> (PathBuf, ConfigSource) {
+    if let Some(path) = flag {
+        return (path.to_path_buf(), ConfigSource::Flag);
+    }
+    if let Some(path) = env.filter(|p| !p.is_empty()) {
+        return (PathBuf::from(path), ConfigSource::Env);
+    }
+    (default_dir.join("config.toml"), ConfigSource::Default)
+}
+
+/// A file or directory in the state directory. Earlier versions kept these
+/// next to config.toml; one found there is moved over the first time.
+pub fn state_file(name: &str) -> PathBuf {
+    let path = state_dir().join(name);
+    adopt_legacy(&config_dir().join(name), path)
+}
+
+/// `path`, after moving `legacy` there if only `legacy` exists. If the move
+/// fails, `legacy` keeps being used.
+fn adopt_legacy(legacy: &Path, path: PathBuf) -> PathBuf {
+    if legacy == path || path.exists() || !legacy.exists() {
+        return path;
+    }
+    let moved = path
+        .parent()
+        .is_none_or(|parent| std::fs::create_dir_all(parent).is_ok())
+        && std::fs::rename(legacy, &path).is_ok();
+    if moved {
+        log::info!("Moved {} to {}", legacy.display(), path.display());
+        path
+    } else {
+        legacy.to_path_buf()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_precedence() {
+        let default_dir = Path::new("/home/mona/.config/zit");
+        let flag = Path::new("/tmp/flag.toml");
+        let env = Some(std::ffi::OsString::from("/tmp/env.toml"));
+
+        assert_eq!(
+            resolve_config(Some(flag), env.clone(), default_dir),
+            (flag.to_path_buf(), ConfigSource::Flag)
+        );
+        assert_eq!(
+            resolve_config(None, env, default_dir),
+            (PathBuf::from("/tmp/env.toml"), ConfigSource::Env)
+        );
+        assert_eq!(
+            resolve_config(None, Some("".into()), default_dir),
+            (default_dir.join("config.toml"), ConfigSource::Default)
+        );
+    }
+
+    #[test]
+    fn test_legacy_state_is_moved() {
+        let dir = tempfile::tempdir().unwrap();
+        let legacy = dir.path().join("config/ai_history.json");
+        let path = dir.path().join("state/zit/ai_history.json");
+        std::fs::create_dir_all(legacy.parent().unwrap()).unwrap();
+        std::fs::write(&legacy, "[]").unwrap();
+
+        assert_eq!(adopt_legacy(&legacy, path.clone()), path);
+        assert!(!legacy.exists());
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "[]");
+
+        // Once moved, a new legacy file is left alone
+        std::fs::write(&legacy, "old").unwrap();
+        assert_eq!(adopt_legacy(&legacy, path.clone()), path);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "[]");
+    }
+}
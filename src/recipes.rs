@@ -0,0 +1,1065 @@
+//! Recipes: multi-step workflows written in TOML, such as a release that
+//! bumps the version file, commits, tags, pushes and publishes a GitHub
+//! release. They're read from `recipes.toml` in the config directory and
+//! `.zit/recipes.toml` in the repository (which wins on a name clash), and
+//! run from the Dashboard's `:` picker.
+//!
+//! ```toml
+//! [[recipe]]
+//! name = "release"
+//! description = "Bump the version, tag it and publish a release"
+//!
+//! [[recipe.param]]
+//! name = "version"
+//! prompt = "Version (e.g. 1.4.0)"
+//!
+//! [[recipe.step]]
+//! replace = { file = "Cargo.toml", pattern = '^version = ".*"', with = 'version = "{version}"' }
+//!
+//! [[recipe.step]]
+//! git = ["commit", "-am", "Release {version}"]
+//!
+//! [[recipe.step]]
+//! git = ["tag", "v{version}"]
+//!
+//! [[recipe.step]]
+//! git = ["push", "--follow-tags"]
+//! confirm = true
+//!
+//! [[recipe.step]]
+//! release = { tag = "v{version}" }
+//! ```
+//!
+//! Steps run in order after the whole plan is confirmed; a step with
+//! `confirm = true` asks again right before it runs. When a step fails, it
+//! and the steps before it are undone where that's possible: files are put back (and
+//! their staged versions, if a later step staged them), commits reset
+//! (`--soft`, so nothing is lost), branches switched back and new tags
+//! deleted. Pushes, shell commands and releases can't be taken back and are
+//! named instead.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+use serde::Deserialize;
+
+use crate::git::{self, runner::StreamLine};
+use crate::paths;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Recipe {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    /// Values asked for before running, used as `{name}` in the steps.
+    #[serde(default, rename = "param")]
+    pub params: Vec<Param>,
+    #[serde(rename = "step")]
+    pub steps: Vec<Step>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Param {
+    pub name: String,
+    #[serde(default)]
+    pub prompt: Option<String>,
+    #[serde(default)]
+    pub default: Option<String>,
+}
+
+/// One step: exactly one of `git`, `run`, `write`, `replace` or `release`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Step {
+    /// Shown in the plan and the output; defaults to the action itself.
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Ask again right before this step runs.
+    #[serde(default)]
+    pub confirm: bool,
+    /// git arguments, e.g. `["tag", "v{version}"]`.
+    #[serde(default)]
+    pub git: Option<Vec<String>>,
+    /// A shell command, run from the repository root.
+    #[serde(default)]
+    pub run: Option<String>,
+    #[serde(default)]
+    pub write: Option<WriteStep>,
+    #[serde(default)]
+    pub replace: Option<ReplaceStep>,
+    #[serde(default)]
+    pub release: Option<ReleaseStep>,
+}
+
+/// Replace a file's contents.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WriteStep {
+    pub file: String,
+    pub content: String,
+}
+
+/// Replace every match of a regular expression (`^` and `$` match at line
+/// ends) in a file.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ReplaceStep {
+    pub file: String,
+    pub pattern: String,
+    pub with: String,
+}
+
+/// Publish a GitHub release.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ReleaseStep {
+    pub tag: String,
+    /// Defaults to the tag.
+    #[serde(default)]
+    pub title: Option<String>,
+    /// Empty lets GitHub write them from the merged pull requests.
+    #[serde(default)]
+    pub notes: String,
+    #[serde(default)]
+    pub draft: bool,
+}
+
+#[derive(Deserialize)]
+struct RecipeFile {
+    #[serde(default, rename = "recipe")]
+    recipes: Vec<Recipe>,
+}
+
+/// A step with its placeholders filled in.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Action {
+    Git(Vec<String>),
+    Run(String),
+    Write {
+        file: String,
+        content: String,
+    },
+    Replace {
+        file: String,
+        pattern: String,
+        with: String,
+    },
+    Release {
+        tag: String,
+        title: String,
+        notes: String,
+        draft: bool,
+    },
+}
+
+impl Action {
+    /// The action in a line, e.g. "git tag v1.4.0".
+    pub fn describe(&self) -> String {
+        match self {
+            Action::Git(args) => format!("git {}", args.join(" ")),
+            Action::Run(command) => format!("$ {}", command),
+            Action::Write { file, .. } => format!("write {}", file),
+            Action::Replace { file, pattern, .. } => format!("replace /{}/ in {}", pattern, file),
+            Action::Release { tag, draft, .. } => format!(
+                "publish {}GitHub release {}",
+                if *draft { "draft " } else { "" },
+                tag
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlannedStep {
+    pub name: String,
+    pub action: Action,
+    pub confirm: bool,
+}
+
+/// A recipe ready to run.
+#[derive(Debug, Clone)]
+pub struct Plan {
+    pub recipe: String,
+    pub steps: Vec<PlannedStep>,
+    /// For release steps.
+    pub token: Option<String>,
+}
+
+impl Plan {
+    /// The steps, numbered, for the confirmation before running.
+    pub fn summary(&self) -> String {
+        self.steps
+            .iter()
+            .enumerate()
+            .map(|(i, step)| {
+                let mut line = format!("{}. {}", i + 1, step.name);
+                if step.name != step.action.describe() {
+                    line.push_str(&format!(" ({})", step.action.describe()));
+                }
+                if step.confirm {
+                    line.push_str(" — asks first");
+                }
+                line
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+impl Recipe {
+    /// Fill in `values` (the params, in order) and the built-in `{branch}`
+    /// and `{date}`, and check every step is well-formed. In `run` steps the
+    /// values are shell-quoted; `git` steps get them as arguments of their own.
+    pub fn plan(&self, values: &[String], token: Option<String>) -> Result<Plan> {
+        let mut vars: Vec<(String, String)> = self
+            .params
+            .iter()
+            .zip(values)
+            .map(|(param, value)| (param.name.clone(), value.clone()))
+            .collect();
+        vars.push((
+            "branch".to_string(),
+            git::run_git(&["branch", "--show-current"])
+                .map(|b| b.trim().to_string())
+                .unwrap_or_default(),
+        ));
+        vars.push(("date".to_string(), jiff::Zoned::now().date().to_string()));
+        let fill_with = |text: &str, vars: &[(String, String)]| {
+            vars.iter().fold(text.to_string(), |text, (name, value)| {
+                text.replace(&format!("{{{}}}", name), value)
+            })
+        };
+        let fill = |text: &str| fill_with(text, &vars);
+
+        let mut steps = Vec::new();
+        for (i, step) in self.steps.iter().enumerate() {
+            let mut actions = Vec::new();
+            if let Some(args) = &step.git {
+                actions.push(Action::Git(args.iter().map(|a| fill(a)).collect()));
+            }
+            if let Some(command) = &step.run {
+                let quoted = vars
+                    .iter()
+                    .map(|(name, value)| {
+                        let value = shell_quote(value)
+                            .with_context(|| format!("step {}: {{{}}}", i + 1, name))?;
+                        Ok((name.clone(), value))
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                actions.push(Action::Run(fill_with(command, &quoted)));
+            }
+            if let Some(write) = &step.write {
+                actions.push(Action::Write {
+                    file: relative_file(i, fill(&write.file))?,
+                    content: fill(&write.content),
+                });
+            }
+            if let Some(replace) = &step.replace {
+                let pattern = fill(&replace.pattern);
+                regex::Regex::new(&pattern)
+                    .with_context(|| format!("step {}: bad pattern /{}/", i + 1, pattern))?;
+                actions.push(Action::Replace {
+                    file: relative_file(i, fill(&replace.file))?,
+                    pattern,
+                    with: fill(&replace.with),
+                });
+            }
+            if let Some(release) = &step.release {
+                if token.is_none() {
+                    bail!(
+                        "step {} publishes a GitHub release: sign in from the GitHub view (g) first",
+                        i + 1
+                    );
+                }
+                let tag = fill(&release.tag);
+                actions.push(Action::Release {
+                    title: release.title.as_deref().map(&fill).unwrap_or(tag.clone()),
+                    tag,
+                    notes: fill(&release.notes),
+                    draft: release.draft,
+                });
+            }
+            let action = match actions.len() {
+                1 => actions.remove(0),
+                0 => bail!("step {} has nothing to do", i + 1),
+                _ => bail!("step {} does more than one thing; split it up", i + 1),
+            };
+            steps.push(PlannedStep {
+                name: step
+                    .name
+                    .as_deref()
+                    .map(&fill)
+                    .unwrap_or_else(|| action.describe()),
+                action,
+                confirm: step.confirm,
+            });
+        }
+        Ok(Plan {
+            recipe: self.name.clone(),
+            steps,
+            token,
+        })
+    }
+}
+
+/// `value` quoted as one word for the shell `run` steps go through, so a
+/// param or branch name can't add commands of its own. `cmd` has no quoting
+/// that stops `%VAR%` expansion, so there values using `"` or `%` are refused.
+fn shell_quote(value: &str) -> Result<String> {
+    if cfg!(target_os = "windows") {
+        if value.contains(['"', '%']) {
+            bail!("{:?} can't be passed to cmd safely", value);
+        }
+        Ok(format!("\"{}\"", value))
+    } else {
+        Ok(format!("'{}'", value.replace('\'', r"'\''")))
+    }
+}
+
+/// `file` for step `i`, refused when it isn't a path inside the repository.
+/// Symlinks are only caught when the step runs (see [`file_in`]).
+fn relative_file(i: usize, file: String) -> Result<String> {
+    let path = Path::new(&file);
+    if path.is_absolute()
+        || path.components().any(|c| {
+            !matches!(
+                c,
+                std::path::Component::Normal(_) | std::path::Component::CurDir
+            )
+        })
+    {
+        bail!("step {}: {} is outside the repository", i + 1, file);
+    }
+    Ok(file)
+}
+
+/// The user's recipes, then the repository's; a repository recipe replaces
+/// a user one of the same name.
+pub fn load() -> Result<Vec<Recipe>> {
+    let mut recipes = load_file(&paths::config_dir().join("recipes.toml"))?;
+    if let Ok(root) = git::run_git(&["rev-parse", "--show-toplevel"]) {
+        for recipe in load_file(&Path::new(root.trim()).join(".zit/recipes.toml"))? {
+            recipes.retain(|r| r.name != recipe.name);
+            recipes.push(recipe);
+        }
+    }
+    Ok(recipes)
+}
+
+fn load_file(path: &Path) -> Result<Vec<Recipe>> {
+    let Ok(text) = std::fs::read_to_string(path) else {
+        return Ok(Vec::new());
+    };
+    parse(&text).with_context(|| format!("in {}", path.display()))
+}
+
+fn parse(text: &str) -> Result<Vec<Recipe>> {
+    Ok(toml::from_str::<RecipeFile>(text)?.recipes)
+}
+
+/// How to take back a finished step.
+enum Undo {
+    /// Put the file back as it was (`None`: it didn't exist), and its index
+    /// entry (`git ls-files --stage`) if that changed since.
+    File {
+        file: String,
+        original: Option<Vec<u8>>,
+        index: Option<String>,
+    },
+    /// Switch back to the branch HEAD was on (`git symbolic-ref HEAD`;
+    /// `None`: detached), or on the same branch move it back to `head`, and
+    /// delete the tags that weren't in `tags_before`.
+    Git {
+        head: Option<String>,
+        branch: Option<String>,
+        tags_before: Vec<String>,
+    },
+    /// Can't be taken back; named in the report.
+    Irreversible(String),
+}
+
+fn line(text: impl Into<String>) -> StreamLine {
+    StreamLine {
+        text: text.into(),
+        is_stderr: false,
+    }
+}
+
+fn error_line(text: impl Into<String>) -> StreamLine {
+    StreamLine {
+        text: text.into(),
+        is_stderr: true,
+    }
+}
+
+/// Run `plan` step by step, sending output to `emit`. Before a step marked
+/// `confirm`, `confirm` is asked with the step's name; `false` stops the
+/// recipe. On failure the failed step and the ones before it are undone
+/// where possible.
+/// Returns whether every step ran.
+pub fn run(plan: &Plan, emit: &mut dyn FnMut(StreamLine), confirm: &dyn Fn(&str) -> bool) -> bool {
+    let root = git::run_git(&["rev-parse", "--show-toplevel"])
+        .map(|r| PathBuf::from(r.trim()))
+        .unwrap_or_else(|_| PathBuf::from("."));
+    run_in(&root, plan, emit, confirm)
+}
+
+/// [`run`] in the repository at `root`.
+fn run_in(
+    root: &Path,
+    plan: &Plan,
+    emit: &mut dyn FnMut(StreamLine),
+    confirm: &dyn Fn(&str) -> bool,
+) -> bool {
+    let total = plan.steps.len();
+    let mut done: Vec<Undo> = Vec::new();
+    for (i, step) in plan.steps.iter().enumerate() {
+        let label = format!("[{}/{}] {}", i + 1, total, step.name);
+        let stopped = if step.confirm && !confirm(&label) {
+            format!("Stopped before {}", label)
+        } else {
+            emit(line(format!("\x1b[1m▸ {}\x1b[0m", label)));
+            // Known before the step runs, so a step that fails partway is
+            // undone too
+            let result = undo_for(&step.action, root).and_then(|undo| {
+                let ran = execute(&step.action, root, plan.token.as_deref(), emit);
+                done.push(undo);
+                ran
+            });
+            match result {
+                Ok(()) => continue,
+                Err(e) => {
+                    // The failed step is named as where it stopped, not as
+                    // something left undone
+                    if matches!(done.last(), Some(Undo::Irreversible(_))) {
+                        done.pop();
+                    }
+                    emit(error_line(format!("{:#}", e)));
+                    format!("Stopped at {}", label)
+                }
+            }
+        };
+        let skipped = roll_back(done, root, emit);
+        // Last, so it's the status message
+        if skipped.is_empty() {
+            emit(error_line(stopped));
+        } else {
+            emit(error_line(format!(
+                "{}; not undone: {}",
+                stopped,
+                skipped.join(", ")
+            )));
+        }
+        return false;
+    }
+    true
+}
+
+/// How to take back `action`, read before it runs.
+fn undo_for(action: &Action, root: &Path) -> Result<Undo> {
+    Ok(match action {
+        Action::Git(args) if args.first().map(String::as_str) == Some("push") => {
+            Undo::Irreversible(format!("git {}", args.join(" ")))
+        }
+        Action::Git(_) => Undo::Git {
+            head: git_in(root, &["rev-parse", "HEAD"]).ok(),
+            branch: current_branch(root),
+            tags_before: tags(root),
+        },
+        Action::Run(command) => Undo::Irreversible(format!("$ {}", command)),
+        Action::Write { file, .. } | Action::Replace { file, .. } => Undo::File {
+            file: file.clone(),
+            original: std::fs::read(file_in(root, file)?).ok(),
+            index: index_entry(root, file),
+        },
+        Action::Release { tag, .. } => Undo::Irreversible(format!("GitHub release {}", tag)),
+    })
+}
+
+fn execute(
+    action: &Action,
+    root: &Path,
+    token: Option<&str>,
+    emit: &mut dyn FnMut(StreamLine),
+) -> Result<()> {
+    match action {
+        Action::Git(args) => {
+            let root_arg = root.to_string_lossy();
+            let mut refs = vec!["-C", &root_arg];
+            refs.extend(args.iter().map(String::as_str));
+            emit(line(format!("\x1b[2m$ git {}\x1b[0m", args.join(" "))));
+            if !git::runner::run_git_streaming(&refs, &mut *emit)? {
+                bail!("git {} failed", args.join(" "));
+            }
+            Ok(())
+        }
+        Action::Run(command) => {
+            emit(line(format!("\x1b[2m$ {}\x1b[0m", command)));
+            let mut cmd = if cfg!(target_os = "windows") {
+                let mut c = std::process::Command::new("cmd");
+                c.args(["/C", command]);
+                c
+            } else {
+                let mut c = std::process::Command::new("sh");
+                c.args(["-c", command]);
+                c
+            };
+            let output = cmd
+                .current_dir(root)
+                .stdin(std::process::Stdio::null())
+                .output()
+                .with_context(|| format!("could not run {}", command))?;
+            for text in String::from_utf8_lossy(&output.stdout).lines() {
+                emit(line(text));
+            }
+            for text in String::from_utf8_lossy(&output.stderr).lines() {
+                emit(error_line(text));
+            }
+            if !output.status.success() {
+                bail!("{} exited with {}", command, output.status);
+            }
+            Ok(())
+        }
+        Action::Write { file, content } => {
+            let path = file_in(root, file)?;
+            std::fs::write(&path, content).with_context(|| format!("writing {}", file))?;
+            emit(line(format!("Wrote {}", file)));
+            Ok(())
+        }
+        Action::Replace {
+            file,
+            pattern,
+            with,
+        } => {
+            let path = file_in(root, file)?;
+            let original =
+                std::fs::read_to_string(&path).with_context(|| format!("reading {}", file))?;
+            let re = regex::RegexBuilder::new(pattern).multi_line(true).build()?;
+            let count = re.find_iter(&original).count();
+            if count == 0 {
+                bail!("/{}/ matched nothing in {}", pattern, file);
+            }
+            let replaced = re.replace_all(&original, with.as_str());
+            std::fs::write(&path, replaced.as_bytes())
+                .with_context(|| format!("writing {}", file))?;
+            emit(line(format!(
+                "Replaced {} match{} in {}",
+                count,
+                if count == 1 { "" } else { "es" },
+                file
+            )));
+            Ok(())
+        }
+        Action::Release {
+            tag,
+            title,
+            notes,
+            draft,
+        } => {
+            let token = token.context("not signed in to GitHub")?;
+            let url = git::github_auth::create_release(token, tag, title, notes, *draft)?;
+            emit(line(format!("Published {}", url)));
+            Ok(())
+        }
+    }
+}
+
+/// `file` under `root`, refusing a path that leads out of the repository
+/// (absolute, `../`, or through a symlink), since recipes may come from a
+/// cloned repository's `.zit/recipes.toml`.
+fn file_in(root: &Path, file: &str) -> Result<PathBuf> {
+    let outside = || anyhow::anyhow!("{} is outside the repository", file);
+    let root = root
+        .canonicalize()
+        .with_context(|| format!("reading {}", root.display()))?;
+    let path = root.join(file);
+    let name = path.file_name().ok_or_else(outside)?;
+    let parent = path
+        .parent()
+        .ok_or_else(outside)?
+        .canonicalize()
+        .with_context(|| format!("no directory for {}", file))?;
+    let path = parent.join(name);
+    // The file itself may be a symlink out
+    let resolved = path.canonicalize().unwrap_or_else(|_| path.clone());
+    if !parent.starts_with(&root) || !resolved.starts_with(&root) {
+        return Err(outside());
+    }
+    Ok(path)
+}
+
+/// `git -C <root> <args>`, so a recipe keeps to the repository it runs in,
+/// with the output trimmed.
+fn git_in(root: &Path, args: &[&str]) -> Result<String> {
+    let root = root.to_string_lossy();
+    let mut full = vec!["-C", &root];
+    full.extend_from_slice(args);
+    git::run_git(&full).map(|out| out.trim().to_string())
+}
+
+fn tags(root: &Path) -> Vec<String> {
+    git_in(root, &["tag", "--list"])
+        .map(|t| t.lines().map(String::from).collect())
+        .unwrap_or_default()
+}
+
+/// The full ref of the branch HEAD is on, `None` when detached.
+fn current_branch(root: &Path) -> Option<String> {
+    git_in(root, &["symbolic-ref", "-q", "HEAD"]).ok()
+}
+
+/// `file`'s line in `git ls-files --stage`, `None` when it isn't in the index.
+fn index_entry(root: &Path, file: &str) -> Option<String> {
+    git_in(root, &["ls-files", "--stage", "--", file])
+        .ok()
+        .filter(|entry| !entry.is_empty())
+}
+
+/// Put `file`'s index entry back to `saved` if a step changed it, leaving
+/// whatever the user had staged before the recipe alone.
+fn restore_index_entry(root: &Path, file: &str, saved: Option<&str>) -> Result<()> {
+    if index_entry(root, file).as_deref() == saved {
+        return Ok(());
+    }
+    let meta = saved
+        .and_then(|entry| entry.split_once('\t'))
+        .map(|(meta, _)| meta);
+    match meta.map(|m| m.split(' ').collect::<Vec<_>>()).as_deref() {
+        Some([mode, oid, "0"]) => git_in(
+            root,
+            &[
+                "update-index",
+                "--add",
+                "--cacheinfo",
+                &format!("{},{},{}", mode, oid, file),
+            ],
+        ),
+        _ => git_in(
+            root,
+            &["rm", "--cached", "-q", "--ignore-unmatch", "--", file],
+        ),
+    }
+    .map(|_| ())
+}
+
+/// Undo `done`, last step first. Returns the steps that couldn't be undone.
+fn roll_back(done: Vec<Undo>, root: &Path, emit: &mut dyn FnMut(StreamLine)) -> Vec<String> {
+    if done.is_empty() {
+        return Vec::new();
+    }
+    emit(line("\x1b[1mRolling back\x1b[0m"));
+    let mut skipped = Vec::new();
+    for undo in done.into_iter().rev() {
+        let result = match undo {
+            Undo::File {
+                file,
+                original,
+                index,
+            } => {
+                let restored = file_in(root, &file)
+                    .map_err(std::io::Error::other)
+                    .and_then(|path| match original {
+                        Some(bytes) => std::fs::write(&path, bytes),
+                        None => std::fs::remove_file(&path),
+                    });
+                let staged = restore_index_entry(root, &file, index.as_deref());
+                match (restored, staged) {
+                    (Ok(()), Ok(())) => Ok(format!("Restored {}", file)),
+                    (Err(e), _) => Err(format!("Couldn't restore {}: {}", file, e)),
+                    (_, Err(e)) => Err(format!("Couldn't restore {} in the index: {}", file, e)),
+                }
+            }
+            Undo::Git {
+                head,
+                branch,
+                tags_before,
+            } => {
+                let mut undone = Vec::new();
+                let mut failed = None;
+                let made = tags(root).into_iter().filter(|t| !tags_before.contains(t));
+                for tag in made {
+                    match git_in(root, &["tag", "-d", &tag]) {
+                        Ok(_) => undone.push(format!("deleted tag {}", tag)),
+                        Err(e) => failed = Some(e.to_string()),
+                    }
+                }
+                let now = git_in(root, &["rev-parse", "HEAD"]).ok();
+                if branch != current_branch(root) {
+                    // The step switched branches: go back rather than move this one
+                    let back = match (&branch, &head) {
+                        (Some(branch), _) => {
+                            let name = branch.strip_prefix("refs/heads/").unwrap_or(branch);
+                            Some((vec!["checkout", "-q", name], name.to_string()))
+                        }
+                        (None, Some(head)) => Some((
+                            vec!["checkout", "-q", "--detach", head.as_str()],
+                            head[..7].to_string(),
+                        )),
+                        (None, None) => None,
+                    };
+                    if let Some((args, name)) = back {
+                        match git_in(root, &args) {
+                            Ok(_) => undone.push(format!("switched back to {}", name)),
+                            Err(e) => failed = Some(e.to_string()),
+                        }
+                    }
+                } else if let Some(head) = head.filter(|h| Some(h) != now.as_ref()) {
+                    match git_in(root, &["reset", "-q", "--soft", &head]) {
+                        Ok(_) => undone.push(format!("reset to {}", &head[..7])),
+                        Err(e) => failed = Some(e.to_string()),
+                    }
+                }
+                match failed {
+                    Some(e) => Err(e),
+                    None if undone.is_empty() => continue,
+                    None => Ok(capitalize(&undone.join(", "))),
+                }
+            }
+            Undo::Irreversible(what) => {
+                skipped.push(what.clone());
+                Ok(format!("Can't undo {}", what))
+            }
+        };
+        match result {
+            Ok(text) => emit(line(format!("  {}", text))),
+            Err(text) => emit(error_line(format!("  {}", text))),
+        }
+    }
+    skipped
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RELEASE: &str = r#"
+        [[recipe]]
+        name = "release"
+
+        [[recipe.param]]
+        name = "version"
+
+        [[recipe.step]]
+        name = "Bump to {version}"
+        replace = { file = "VERSION", pattern = '^\d+\.\d+\.\d+$', with = "{version}" }
+
+        [[recipe.step]]
+        git = ["tag", "v{version}"]
+        confirm = true
+    "#;
+
+    #[test]
+    fn test_plan_fills_placeholders() {
+        let recipes = parse(RELEASE).unwrap();
+        let plan = recipes[0].plan(&["1.4.0".to_string()], None).unwrap();
+        assert_eq!(
+            plan.steps[1].action,
+            Action::Git(vec!["tag".to_string(), "v1.4.0".to_string()])
+        );
+        assert_eq!(
+            plan.summary(),
+            "1. Bump to 1.4.0 (replace /^\\d+\\.\\d+\\.\\d+$/ in VERSION)\n\
+             2. git tag v1.4.0 — asks first"
+        );
+    }
+
+    #[test]
+    fn test_run_steps_quote_values() {
+        let toml = r#"[[recipe]]
+            name = "x"
+            [[recipe.param]]
+            name = "version"
+            [[recipe.step]]
+            run = "echo {version} > VERSION"
+            [[recipe.step]]
+            git = ["tag", "v{version}"]"#;
+        let recipes = parse(toml).unwrap();
+        let value = "1.0; touch pwned $(touch pwned) `touch pwned` 'x'".to_string();
+        let plan = recipes[0].plan(std::slice::from_ref(&value), None).unwrap();
+        #[cfg(unix)]
+        {
+            let Action::Run(command) = &plan.steps[0].action else {
+                panic!("{:?}", plan.steps[0].action);
+            };
+            let dir = tempfile::tempdir().unwrap();
+            let status = std::process::Command::new("sh")
+                .args(["-c", command])
+                .current_dir(dir.path())
+                .status()
+                .unwrap();
+            assert!(status.success());
+            assert_eq!(
+                std::fs::read_to_string(dir.path().join("VERSION")).unwrap(),
+                format!("{}\n", value)
+            );
+            assert!(!dir.path().join("pwned").exists());
+        }
+        // git gets the value as it is, as one argument
+        assert_eq!(
+            plan.steps[1].action,
+            Action::Git(vec!["tag".to_string(), format!("v{}", value)])
+        );
+    }
+
+    #[test]
+    fn test_bad_steps_are_refused() {
+        let plan = |toml: &str| parse(toml).unwrap()[0].plan(&[], None).map(|_| ());
+        let two = r#"[[recipe]]
+            name = "x"
+            [[recipe.step]]
+            git = ["status"]
+            run = "true""#;
+        assert!(plan(two).unwrap_err().to_string().contains("more than one"));
+        let release = r#"[[recipe]]
+            name = "x"
+            [[recipe.step]]
+            release = { tag = "v1" }"#;
+        assert!(plan(release).unwrap_err().to_string().contains("sign in"));
+        assert!(parse("[[recipe]]\nname = \"x\"\n[[recipe.step]]\nshell = \"ls\"").is_err());
+        for file in ["../x", "/tmp/x"] {
+            let write = format!(
+                "[[recipe]]\nname = \"x\"\n[[recipe.step]]\nwrite = {{ file = \"{}\", content = \"\" }}",
+                file
+            );
+            assert_eq!(
+                plan(&write).unwrap_err().to_string(),
+                format!("step 1: {} is outside the repository", file)
+            );
+        }
+    }
+
+    /// Run git in `dir` and return its trimmed stdout.
+    fn git(dir: &Path, args: &[&str]) -> String {
+        let output = std::process::Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "git {:?} failed: {}",
+            args,
+            String::from_utf8_lossy(&output.stderr)
+        );
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
+    }
+
+    /// A repository on `main` with VERSION and NOTES committed.
+    fn repo() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path();
+        git(path, &["init", "-q", "-b", "main"]);
+        git(path, &["config", "user.name", "Test User"]);
+        git(path, &["config", "user.email", "test@example.com"]);
+        git(path, &["config", "commit.gpgsign", "false"]);
+        std::fs::write(path.join("VERSION"), "1.0.0\n").unwrap();
+        std::fs::write(path.join("NOTES"), "old\n").unwrap();
+        git(path, &["add", "."]);
+        git(path, &["commit", "-q", "-m", "First"]);
+        dir
+    }
+
+    fn step(action: Action) -> PlannedStep {
+        PlannedStep {
+            name: action.describe(),
+            action,
+            confirm: false,
+        }
+    }
+
+    fn git_step(args: &[&str]) -> PlannedStep {
+        step(Action::Git(args.iter().map(|a| a.to_string()).collect()))
+    }
+
+    /// Run `steps` in `dir`, returning whether they all ran and the output.
+    fn run_steps(dir: &Path, steps: Vec<PlannedStep>) -> (bool, Vec<String>) {
+        let plan = Plan {
+            recipe: "test".to_string(),
+            steps,
+            token: None,
+        };
+        let mut out = Vec::new();
+        let ok = run_in(dir, &plan, &mut |l| out.push(l.text), &|_| true);
+        (ok, out)
+    }
+
+    #[test]
+    fn test_failure_rolls_back_files_commits_and_tags() {
+        let dir = repo();
+        let path = dir.path();
+        let head = git(path, &["rev-parse", "HEAD"]);
+        // Staged before the recipe, and kept staged after it
+        std::fs::write(path.join("NOTES"), "mine\n").unwrap();
+        git(path, &["add", "NOTES"]);
+        std::fs::write(path.join("NOTES"), "mine, unstaged\n").unwrap();
+
+        let (ok, out) = run_steps(
+            path,
+            vec![
+                step(Action::Replace {
+                    file: "VERSION".to_string(),
+                    pattern: r"^\d+\.\d+\.\d+$".to_string(),
+                    with: "2.0.0".to_string(),
+                }),
+                step(Action::Write {
+                    file: "NOTES".to_string(),
+                    content: "Release 2.0.0\n".to_string(),
+                }),
+                step(Action::Write {
+                    file: "NEW".to_string(),
+                    content: "new\n".to_string(),
+                }),
+                git_step(&["add", "NEW"]),
+                git_step(&["commit", "-q", "-am", "Release 2.0.0"]),
+                git_step(&["tag", "v2.0.0"]),
+                git_step(&["no-such-command"]),
+            ],
+        );
+        assert!(!ok);
+        assert!(out.iter().any(|l| l.contains("Rolling back")));
+        assert_eq!(out.last().unwrap(), "Stopped at [7/7] git no-such-command");
+
+        assert_eq!(git(path, &["rev-parse", "HEAD"]), head);
+        assert_eq!(git(path, &["tag", "--list"]), "");
+        assert_eq!(
+            std::fs::read_to_string(path.join("VERSION")).unwrap(),
+            "1.0.0\n"
+        );
+        assert!(!path.join("NEW").exists());
+        assert_eq!(
+            std::fs::read_to_string(path.join("NOTES")).unwrap(),
+            "mine, unstaged\n"
+        );
+        assert_eq!(git(path, &["show", ":NOTES"]), "mine");
+        assert_eq!(git(path, &["status", "--porcelain"]), "MM NOTES");
+    }
+
+    #[test]
+    fn test_failure_switches_back_instead_of_moving_the_branch() {
+        let dir = repo();
+        let path = dir.path();
+        let main = git(path, &["rev-parse", "main"]);
+        git(path, &["checkout", "-q", "-b", "feature"]);
+        git(path, &["commit", "-q", "--allow-empty", "-m", "Feature"]);
+        let feature = git(path, &["rev-parse", "feature"]);
+
+        let (ok, _) = run_steps(
+            path,
+            vec![
+                git_step(&["checkout", "-q", "main"]),
+                git_step(&["no-such-command"]),
+            ],
+        );
+        assert!(!ok);
+        assert_eq!(git(path, &["branch", "--show-current"]), "feature");
+        assert_eq!(git(path, &["rev-parse", "main"]), main);
+        assert_eq!(git(path, &["rev-parse", "feature"]), feature);
+    }
+
+    #[test]
+    fn test_files_outside_the_repository_are_refused() {
+        let dir = repo();
+        let path = dir.path();
+        let outside = tempfile::tempdir().unwrap();
+        let target = outside.path().join("x");
+        let escapes = [
+            format!(
+                "../{}/x",
+                outside.path().file_name().unwrap().to_string_lossy()
+            ),
+            target.to_string_lossy().to_string(),
+            "/tmp/x".to_string(),
+            "..".to_string(),
+        ];
+        for file in escapes {
+            let (ok, out) = run_steps(
+                path,
+                vec![step(Action::Write {
+                    file: file.clone(),
+                    content: "pwned\n".to_string(),
+                })],
+            );
+            assert!(!ok, "{}", file);
+            assert!(
+                out.contains(&format!("{} is outside the repository", file)),
+                "{:?}",
+                out
+            );
+        }
+        assert!(!target.exists());
+
+        #[cfg(unix)]
+        {
+            std::os::unix::fs::symlink(outside.path(), path.join("link")).unwrap();
+            let (ok, _) = run_steps(
+                path,
+                vec![step(Action::Replace {
+                    file: "link/x".to_string(),
+                    pattern: "a".to_string(),
+                    with: "b".to_string(),
+                })],
+            );
+            assert!(!ok);
+        }
+
+        std::fs::create_dir(path.join("sub")).unwrap();
+        let (ok, _) = run_steps(
+            path,
+            vec![step(Action::Write {
+                file: "sub/../sub/ok".to_string(),
+                content: "fine\n".to_string(),
+            })],
+        );
+        assert!(ok);
+        assert_eq!(
+            std::fs::read_to_string(path.join("sub/ok")).unwrap(),
+            "fine\n"
+        );
+    }
+
+    #[test]
+    fn test_failed_step_is_undone_too() {
+        let dir = repo();
+        let path = dir.path();
+        git(path, &["tag", "v1"]);
+
+        // Tags, then fails
+        let (ok, _) = run_steps(
+            path,
+            vec![git_step(&["-c", "alias.fail=!git tag v2 && false", "fail"])],
+        );
+        assert!(!ok);
+        assert_eq!(git(path, &["tag", "--list"]), "v1");
+    }
+
+    #[test]
+    fn test_irreversible_steps_are_named() {
+        let dir = repo();
+        let (ok, out) = run_steps(
+            dir.path(),
+            vec![
+                step(Action::Run("echo hi".to_string())),
+                step(Action::Run("exit 3".to_string())),
+            ],
+        );
+        assert!(!ok);
+        assert!(out.contains(&"hi".to_string()));
+        assert_eq!(
+            out.last().unwrap(),
+            "Stopped at [2/2] $ exit 3; not undone: $ echo hi"
+        );
+
+        let (ok, out) = run_steps(dir.path(), vec![git_step(&["tag", "v1"])]);
+        assert!(ok);
+        assert!(!out.iter().any(|l| l.contains("Rolling back")));
+        assert_eq!(git(dir.path(), &["tag", "--list"]), "v1");
+    }
+}
@@ -0,0 +1,334 @@
+//! Local usage statistics (`[stats] enabled`, off by default): which git
+//! actions zit ran for you, which views you opened, and a rough estimate of
+//! the time that saved. They're kept in `stats.json` in the state directory
+//! and never sent anywhere; the Dashboard's `S` shows them.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Instant;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::paths;
+
+/// What has been counted so far.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Stats {
+    /// The day counting started (YYYY-MM-DD).
+    #[serde(default)]
+    pub since: String,
+    #[serde(default)]
+    pub sessions: u64,
+    /// Time spent in zit, in seconds.
+    #[serde(default)]
+    pub seconds: u64,
+    /// Successful git actions, by name (see [`action`]).
+    #[serde(default)]
+    pub actions: BTreeMap<String, u64>,
+    /// Views opened, by name.
+    #[serde(default)]
+    pub views: BTreeMap<String, u64>,
+}
+
+struct Session {
+    stats: Stats,
+    /// When the time since the last save started counting.
+    started: Instant,
+}
+
+/// The counts while stats are on; `None` when they're off.
+static SESSION: Mutex<Option<Session>> = Mutex::new(None);
+
+/// Where the counts are kept.
+pub fn path() -> PathBuf {
+    paths::state_file("stats.json")
+}
+
+/// Start counting, carrying on from the saved counts, or stop, saving them.
+pub fn set_enabled(enabled: bool) {
+    let Ok(mut session) = SESSION.lock() else {
+        return;
+    };
+    match (enabled, session.is_some()) {
+        (true, false) => {
+            let mut stats = load(&path());
+            if stats.since.is_empty() {
+                stats.since = today();
+            }
+            stats.sessions += 1;
+            *session = Some(Session {
+                stats,
+                started: Instant::now(),
+            });
+        }
+        (false, true) => {
+            if let Some(mut s) = session.take() {
+                flush(&mut s);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Write the counts to disk, when stats are on.
+pub fn save() {
+    if let Ok(mut session) = SESSION.lock()
+        && let Some(s) = session.as_mut()
+    {
+        flush(s);
+    }
+}
+
+/// Start counting afresh, when stats are on.
+pub fn reset() {
+    if let Ok(mut session) = SESSION.lock()
+        && let Some(s) = session.as_mut()
+    {
+        s.stats = Stats {
+            since: today(),
+            sessions: 1,
+            ..Stats::default()
+        };
+        s.started = Instant::now();
+        flush(s);
+    }
+}
+
+/// The counts so far, including this session's time; `None` when stats are off.
+pub fn current() -> Option<Stats> {
+    let session = SESSION.lock().ok()?;
+    session.as_ref().map(|s| {
+        let mut stats = s.stats.clone();
+        stats.seconds += s.started.elapsed().as_secs();
+        stats
+    })
+}
+
+/// Count a git command that succeeded, if it's one of the actions counted.
+/// `rest` is what follows the subcommand. Installed as the git runner's observer.
+pub fn record_git(subcommand: &str, rest: &[&str]) {
+    if let Some(action) = action(subcommand, rest) {
+        bump(|stats| &mut stats.actions, action);
+    }
+}
+
+/// Count a view being opened.
+pub fn record_view(name: &str) {
+    bump(|stats| &mut stats.views, name);
+}
+
+fn bump(counts: impl FnOnce(&mut Stats) -> &mut BTreeMap<String, u64>, name: &str) {
+    if let Ok(mut session) = SESSION.lock()
+        && let Some(s) = session.as_mut()
+    {
+        *counts(&mut s.stats).entry(name.to_string()).or_default() += 1;
+    }
+}
+
+fn flush(session: &mut Session) {
+    session.stats.seconds += session.started.elapsed().as_secs();
+    session.started = Instant::now();
+    if let Err(e) = write(&path(), &session.stats) {
+        log::warn!("Failed to save usage stats: {:#}", e);
+    }
+}
+
+fn load(path: &Path) -> Stats {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|text| serde_json::from_str(&text).ok())
+        .unwrap_or_default()
+}
+
+fn write(path: &Path, stats: &Stats) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let text = serde_json::to_string_pretty(stats)?;
+    std::fs::write(path, text).with_context(|| format!("writing {}", path.display()))
+}
+
+fn today() -> String {
+    jiff::Zoned::now().date().to_string()
+}
+
+/// The action a git command counts as, or `None` for reads (`status`, `log`,
+/// `stash list`...) and plumbing zit runs on its own (`stash create`).
+pub fn action(subcommand: &str, rest: &[&str]) -> Option<&'static str> {
+    let has = |flags: &[&str]| rest.iter().any(|a| flags.contains(a));
+    let first = rest.first().copied();
+    Some(match subcommand {
+        "add" => "stage",
+        "restore" if has(&["--staged", "-S"]) => "unstage",
+        "restore" | "clean" if !has(&["-n", "--dry-run"]) => "discard",
+        "checkout" if has(&["--"]) => "discard",
+        "checkout" | "switch" => "switch",
+        "commit" => "commit",
+        "push" => "push",
+        "pull" => "pull",
+        "fetch" => "fetch",
+        "merge" => "merge",
+        "rebase" => "rebase",
+        "cherry-pick" => "cherry-pick",
+        "revert" => "revert",
+        "reset" => "reset",
+        "stash"
+            if matches!(
+                first,
+                None | Some("push" | "save" | "pop" | "apply" | "drop")
+            ) =>
+        {
+            "stash"
+        }
+        "branch"
+            if has(&[
+                "-d",
+                "-D",
+                "-m",
+                "-M",
+                "--delete",
+                "--move",
+                "--edit-description",
+            ]) || first.is_some_and(|a| !a.starts_with('-')) =>
+        {
+            "branch"
+        }
+        "tag"
+            if !rest.is_empty()
+                && !has(&["-l", "--list", "--contains", "--points-at", "--merged"]) =>
+        {
+            "tag"
+        }
+        "bisect" if !matches!(first, Some("log" | "visualize" | "view")) => "bisect",
+        "notes" if matches!(first, Some("add" | "edit" | "append" | "remove")) => "note",
+        _ => return None,
+    })
+}
+
+/// Roughly how many seconds doing `action` in zit saves over the command
+/// line: typing the command and its paths or hashes, then `git status` or
+/// `git log` to check it worked.
+fn seconds_saved(action: &str) -> u64 {
+    match action {
+        "stage" | "unstage" | "fetch" => 5,
+        "switch" | "push" | "pull" | "branch" | "stash" | "discard" => 8,
+        "tag" | "note" | "revert" => 10,
+        "merge" | "cherry-pick" | "reset" => 15,
+        "commit" | "bisect" => 20,
+        "rebase" => 30,
+        _ => 5,
+    }
+}
+
+/// The estimated time saved, in seconds.
+pub fn time_saved(stats: &Stats) -> u64 {
+    stats
+        .actions
+        .iter()
+        .map(|(action, count)| seconds_saved(action) * count)
+        .sum()
+}
+
+/// "2h 05m", "12m", or "under a minute".
+fn duration(seconds: u64) -> String {
+    let minutes = seconds / 60;
+    match (minutes / 60, minutes % 60) {
+        (0, 0) => "under a minute".to_string(),
+        (0, m) => format!("{}m", m),
+        (h, m) => format!("{}h {:02}m", h, m),
+    }
+}
+
+/// The "Your zit stats" screen's text.
+pub fn report(stats: &Stats) -> String {
+    let mut lines = vec![
+        format!(
+            "Since {}: {} session{}, {} in zit",
+            stats.since,
+            stats.sessions,
+            if stats.sessions == 1 { "" } else { "s" },
+            duration(stats.seconds)
+        ),
+        format!(
+            "Time saved over typing git commands: about {}",
+            duration(time_saved(stats))
+        ),
+    ];
+    for (heading, counts) in [("Git actions", &stats.actions), ("Views", &stats.views)] {
+        lines.push(String::new());
+        lines.push(heading.to_string());
+        if counts.is_empty() {
+            lines.push("  none yet".to_string());
+            continue;
+        }
+        let mut sorted: Vec<(&String, &u64)> = counts.iter().collect();
+        sorted.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+        let most = *sorted[0].1;
+        for (name, count) in sorted.into_iter().take(8) {
+            let bar = "▇".repeat(((count * 20).div_ceil(most)) as usize);
+            lines.push(format!("  {:<20} {:>5}  {}", name, count, bar));
+        }
+    }
+    lines.push(String::new());
+    lines.push("Kept only on this computer and never sent anywhere.".to_string());
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_actions_skip_reads() {
+        assert_eq!(action("add", &["--", "src/main.rs"]), Some("stage"));
+        assert_eq!(action("restore", &["--staged", "--", "a"]), Some("unstage"));
+        assert_eq!(action("checkout", &["--", "a"]), Some("discard"));
+        assert_eq!(action("checkout", &["main"]), Some("switch"));
+        assert_eq!(action("stash", &[]), Some("stash"));
+        assert_eq!(action("stash", &["list"]), None);
+        assert_eq!(action("stash", &["create"]), None);
+        assert_eq!(action("branch", &["feature"]), Some("branch"));
+        assert_eq!(action("branch", &["--show-current"]), None);
+        assert_eq!(action("tag", &["-l"]), None);
+        assert_eq!(action("tag", &["v1.0"]), Some("tag"));
+        assert_eq!(action("status", &["--porcelain=v2"]), None);
+        assert_eq!(action("cherry-pick", &["abc123"]), Some("cherry-pick"));
+    }
+
+    #[test]
+    fn test_report() {
+        let stats = Stats {
+            since: "2026-10-01".to_string(),
+            sessions: 3,
+            seconds: 2 * 3600 + 5 * 60,
+            actions: BTreeMap::from([("commit".to_string(), 6), ("stage".to_string(), 12)]),
+            views: BTreeMap::new(),
+        };
+        // 6 commits × 20s + 12 stages × 5s
+        assert_eq!(time_saved(&stats), 180);
+        let report = report(&stats);
+        assert!(report.starts_with("Since 2026-10-01: 3 sessions, 2h 05m in zit\n"));
+        assert!(report.contains("about 3m"));
+        let stage = report.lines().position(|l| l.contains("stage")).unwrap();
+        let commit = report.lines().position(|l| l.contains("commit")).unwrap();
+        assert!(stage < commit, "most used first");
+        assert!(report.contains("Views\n  none yet"));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("state/stats.json");
+        assert_eq!(load(&path), Stats::default());
+        let stats = Stats {
+            since: "2026-10-01".to_string(),
+            sessions: 1,
+            views: BTreeMap::from([("Staging".to_string(), 4)]),
+            ..Stats::default()
+        };
+        write(&path, &stats).unwrap();
+        assert_eq!(load(&path), stats);
+    }
+}
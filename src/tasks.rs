@@ -0,0 +1,211 @@
+//! Git reads for the views, run on worker threads so the event loop keeps
+//! drawing while `git status` or `git log` works through a big repository.
+//!
+//! The periodic refresh and opening a view submit a [`Read`]; a worker loads
+//! it and posts the result back through a channel, waking the main loop to
+//! hand it to the view. Each result carries when it was asked for, and a view
+//! drops results older than what it already shows, so a slow read can't undo
+//! the refresh that follows staging or committing (those still read straight
+//! away, since the user is looking for the effect).
+
+use std::sync::mpsc;
+use std::time::Instant;
+
+use crate::event::AppEvent;
+use crate::git;
+use crate::jobs::{Priority, Scheduler};
+use crate::ui::{blame, branches, commit, dashboard, staging, status_bar, timeline};
+
+/// Git reads running at once. They share git's locks and the disk, so more
+/// would mostly wait on each other.
+pub const WORKERS: usize = 2;
+
+/// What a view needs read from the repository.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Read {
+    Dashboard,
+    Staging,
+    Commit,
+    Branches,
+    Timeline(timeline::Query),
+    /// A Staging file's diff.
+    Diff {
+        path: String,
+        original_path: Option<String>,
+        is_staged: bool,
+        status: git::FileStatus,
+        image_preview: bool,
+    },
+    /// A file's blame, at a commit or in the working tree.
+    Blame {
+        path: String,
+        rev: Option<String>,
+    },
+}
+
+/// The result of a [`Read`].
+pub enum Loaded {
+    Dashboard(dashboard::Loaded),
+    Staging(staging::Loaded),
+    Commit(commit::Loaded),
+    Branches(branches::Loaded),
+    Timeline(timeline::Loaded),
+    Diff(staging::LoadedDiff),
+    Blame(blame::Loaded),
+}
+
+/// What shows whatever the view: the status bar and the detached-HEAD banner.
+pub struct Common {
+    pub at: Instant,
+    pub summary: status_bar::RepoSummary,
+    pub detached_head: Option<git::branch::DetachedHead>,
+}
+
+impl Common {
+    pub fn load(at: Instant) -> Self {
+        Self {
+            at,
+            summary: status_bar::RepoSummary::load(),
+            detached_head: git::BranchOps::detached_head(),
+        }
+    }
+}
+
+/// A finished background refresh.
+pub struct Refresh {
+    /// Left out of diff and blame reads.
+    pub common: Option<Common>,
+    pub view: Option<Loaded>,
+}
+
+/// Do `read`, as asked for at `at`.
+pub fn load(read: Read, at: Instant) -> Loaded {
+    match read {
+        Read::Dashboard => Loaded::Dashboard(dashboard::DashboardState::load(at)),
+        Read::Staging => Loaded::Staging(staging::StagingState::load(at)),
+        Read::Commit => Loaded::Commit(commit::CommitState::load(at)),
+        Read::Branches => Loaded::Branches(branches::BranchesState::load(at)),
+        Read::Timeline(query) => Loaded::Timeline(timeline::TimelineState::load(query, at)),
+        Read::Diff {
+            path,
+            original_path,
+            is_staged,
+            status,
+            image_preview,
+        } => {
+            let file = staging::StagingFile {
+                path,
+                status,
+                is_staged,
+                original_path,
+            };
+            Loaded::Diff(staging::StagingState::load_diff(&file, image_preview, at))
+        }
+        Read::Blame { path, rev } => Loaded::Blame(blame::BlameState::load(path, rev, at)),
+    }
+}
+
+/// Whether a result asked for at `at` is newer than the one shown, which was
+/// asked for at `shown`; if so it becomes the one shown.
+pub fn is_newer(shown: &mut Option<Instant>, at: Instant) -> bool {
+    if shown.is_some_and(|shown| at < shown) {
+        return false;
+    }
+    *shown = Some(at);
+    true
+}
+
+/// Runs [`Read`]s on a pool of its own, apart from the network jobs, so a
+/// slow GitHub call never holds up the file list.
+pub struct Tasks {
+    pool: Scheduler,
+    tx: mpsc::Sender<Refresh>,
+    rx: mpsc::Receiver<Refresh>,
+    waker: Option<mpsc::Sender<AppEvent>>,
+}
+
+impl Tasks {
+    pub fn new() -> Self {
+        let (tx, rx) = mpsc::channel();
+        Self {
+            pool: Scheduler::new(WORKERS),
+            tx,
+            rx,
+            waker: None,
+        }
+    }
+
+    /// Wake the main loop through `waker` when a read finishes.
+    pub fn set_waker(&mut self, waker: mpsc::Sender<AppEvent>) {
+        self.waker = Some(waker);
+    }
+
+    /// Read the status bar's data and, if given, `read`, in the background.
+    /// The same read already waiting isn't queued again.
+    pub fn submit(&self, read: Option<Read>, priority: Priority) {
+        let at = Instant::now();
+        let key = match &read {
+            Some(read) => format!("refresh:{:?}", read),
+            None => "refresh".to_string(),
+        };
+        // A diff or a blame changes nothing the status bar shows
+        let common = !matches!(read, Some(Read::Diff { .. } | Read::Blame { .. }));
+        let tx = self.tx.clone();
+        let waker = self.waker.clone();
+        self.pool.submit(key, priority, move || {
+            let refresh = Refresh {
+                common: common.then(|| Common::load(at)),
+                view: read.map(|read| load(read, at)),
+            };
+            if tx.send(refresh).is_ok()
+                && let Some(waker) = waker
+            {
+                let _ = waker.send(AppEvent::Redraw);
+            }
+        });
+    }
+
+    /// Reads finished since the last call, oldest first.
+    pub fn finished(&self) -> Vec<Refresh> {
+        self.rx.try_iter().collect()
+    }
+}
+
+impl Default for Tasks {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_older_results_are_dropped() {
+        let first = Instant::now();
+        let second = first + Duration::from_millis(10);
+        let mut shown = None;
+        assert!(is_newer(&mut shown, second));
+        assert!(!is_newer(&mut shown, first));
+        assert_eq!(shown, Some(second));
+        assert!(is_newer(&mut shown, second));
+    }
+
+    #[test]
+    fn test_finished_reads_come_back() {
+        let mut tasks = Tasks::new();
+        let (waker, woken) = mpsc::channel();
+        tasks.set_waker(waker);
+        tasks.submit(Some(Read::Branches), Priority::User);
+
+        assert!(matches!(
+            woken.recv_timeout(Duration::from_secs(10)),
+            Ok(AppEvent::Redraw)
+        ));
+        let finished = tasks.finished();
+        assert_eq!(finished.len(), 1);
+        assert!(matches!(finished[0].view, Some(Loaded::Branches(_))));
+    }
+}
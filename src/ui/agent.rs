@@ -1,14 +1,16 @@
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseEvent, MouseEventKind};
 use ratatui::{
+    Frame,
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Paragraph, Wrap},
-    Frame,
 };
 use std::sync::mpsc;
 use std::time::SystemTime;
 
+use crate::ui::utils::{is_narrow, render_paragraph_scrollbar, truncate_to_width};
+
 // ─── Message types ─────────────────────────────────────────────
 
 /// Role of a message in the agent conversation.
@@ -314,7 +316,8 @@ fn render_conversation(f: &mut Frame, area: Rect, state: &mut AgentState, loadin
         )
         .scroll((effective_scroll, 0))
         .wrap(Wrap { trim: false });
-    f.render_widget(conversation, area);
+    f.render_widget(&conversation, area);
+    render_paragraph_scrollbar(f, area, &conversation, effective_scroll);
 
     // Scroll indicator (right side)
     if total_lines > visible_height {
@@ -550,11 +553,7 @@ fn build_conversation_lines(state: &AgentState, loading: bool) -> Vec<Line<'stat
             ),
         ]));
         if !prompt.output_preview.is_empty() {
-            let preview = if prompt.output_preview.len() > 80 {
-                format!("{}…", &prompt.output_preview[..80])
-            } else {
-                prompt.output_preview.clone()
-            };
+            let preview = truncate_to_width(&prompt.output_preview, 80);
             lines.push(Line::from(Span::styled(
                 format!("    {}", preview),
                 Style::default().fg(Color::DarkGray),
@@ -696,9 +695,14 @@ fn render_input(f: &mut Frame, area: Rect, state: &AgentState) {
         ]
     };
 
+    // Key hints are dropped on narrow terminals so the input keeps its width
+    let hint_width = if is_narrow(area) { 0 } else { 35 };
     let input_chunks = Layout::default()
         .direction(Direction::Horizontal)
-        .constraints([Constraint::Percentage(65), Constraint::Percentage(35)])
+        .constraints([
+            Constraint::Percentage(100 - hint_width),
+            Constraint::Percentage(hint_width),
+        ])
         .split(area);
 
     let input_widget = Paragraph::new(Line::from(Span::styled(input_text, input_style))).block(
@@ -717,7 +721,9 @@ fn render_input(f: &mut Frame, area: Rect, state: &AgentState) {
             .borders(Borders::ALL)
             .border_style(Style::default().fg(Color::DarkGray)),
     );
-    f.render_widget(hints_widget, input_chunks[1]);
+    if input_chunks[1].width > 0 {
+        f.render_widget(hints_widget, input_chunks[1]);
+    }
 }
 
 // ─── Key handling ──────────────────────────────────────────────
@@ -726,6 +732,7 @@ pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()
     // Handle Ctrl+C: cancel AI request
     if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
         if app.ai_loading {
+            app.jobs.cancel("ai");
             app.ai_loading = false;
             app.agent_state.thinking = false;
             app.agent_state.messages.push(AgentMessage {
@@ -865,21 +872,20 @@ fn handle_input_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<
     match key.code {
         KeyCode::Esc => {
             if app.agent_state.input.is_empty() {
-                app.view = crate::app::View::Dashboard;
                 app.agent_state.input_active = false;
+                app.go_back();
             } else {
                 app.agent_state.input_active = false;
             }
         }
-        KeyCode::Up => {
-            if app.agent_state.input.is_empty() && !app.agent_state.input_history.is_empty() {
-                if app.agent_state.history_index < app.agent_state.input_history.len() {
-                    let idx =
-                        app.agent_state.input_history.len() - 1 - app.agent_state.history_index;
-                    app.agent_state.input = app.agent_state.input_history[idx].clone();
-                    app.agent_state.history_index += 1;
-                }
-            }
+        KeyCode::Up
+            if app.agent_state.input.is_empty()
+                && !app.agent_state.input_history.is_empty()
+                && app.agent_state.history_index < app.agent_state.input_history.len() =>
+        {
+            let idx = app.agent_state.input_history.len() - 1 - app.agent_state.history_index;
+            app.agent_state.input = app.agent_state.input_history[idx].clone();
+            app.agent_state.history_index += 1;
         }
         KeyCode::Down => {
             if app.agent_state.history_index > 0 {
@@ -915,10 +921,8 @@ fn handle_input_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<
 
             app.start_agent_chat();
         }
-        KeyCode::Char(c) => {
-            if !key.modifiers.contains(KeyModifiers::CONTROL) {
-                app.agent_state.input.push(c);
-            }
+        KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+            app.agent_state.input.push(c);
         }
         KeyCode::Backspace => {
             app.agent_state.input.pop();
@@ -935,14 +939,14 @@ fn handle_normal_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result
             state.input_active = true;
         }
         KeyCode::Char('q') => {
-            app.view = crate::app::View::Dashboard;
+            app.open_view(crate::app::View::Dashboard);
         }
         KeyCode::Esc => {
             if state.user_scrolled {
                 state.scroll = 0;
                 state.user_scrolled = false;
             } else {
-                app.view = crate::app::View::Dashboard;
+                app.go_back();
             }
         }
         KeyCode::Up | KeyCode::Char('k') => {
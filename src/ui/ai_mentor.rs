@@ -1,10 +1,16 @@
+use crate::ai::audit;
+use crate::ui::datetime;
+use crate::ui::icons::Icon;
+use crate::ui::pane_search::PaneSearch;
+use crate::ui::utils::{render_paragraph_scrollbar, truncate_to_width};
+use crate::ui::zen;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::{
+    Frame,
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Paragraph, Wrap},
-    Frame,
 };
 use serde::{Deserialize, Serialize};
 
@@ -28,12 +34,9 @@ pub struct AiHistoryEntry {
 /// Maximum history entries to keep.
 const MAX_HISTORY: usize = 50;
 
-/// Get the history file path (~/.config/zit/ai_history.json).
-fn history_path() -> std::path::PathBuf {
-    dirs::config_dir()
-        .unwrap_or_else(|| std::path::PathBuf::from("."))
-        .join("zit")
-        .join("ai_history.json")
+/// Get the history file path (ai_history.json in the state directory).
+pub fn history_path() -> std::path::PathBuf {
+    crate::paths::state_file("ai_history.json")
 }
 
 /// State for the AI Mentor panel.
@@ -50,6 +53,8 @@ pub struct AiMentorState {
     pub spinner_frame: u8,
     pub typewriter_chars: usize,
     pub typewriter_last_tick: std::time::Instant,
+    /// `/` search in the result.
+    pub search: Option<PaneSearch>,
 }
 
 impl Default for AiMentorState {
@@ -68,6 +73,7 @@ impl Default for AiMentorState {
             spinner_frame: 0,
             typewriter_chars: 0,
             typewriter_last_tick: std::time::Instant::now(),
+            search: None,
         }
     }
 }
@@ -119,31 +125,7 @@ impl AiMentorState {
 
     /// Add a new entry to the prompt history and persist to disk.
     pub fn add_history(&mut self, query: String, response: String) {
-        let timestamp = {
-            use std::time::SystemTime;
-            let secs = SystemTime::now()
-                .duration_since(SystemTime::UNIX_EPOCH)
-                .unwrap_or_default()
-                .as_secs();
-            // Get local time offset and compute local HH:MM
-            // Use libc on unix to get timezone offset, fall back to UTC
-            #[cfg(unix)]
-            let offset_secs: i64 = {
-                // Safe: we're single-threaded at this point
-                unsafe {
-                    let mut tm: libc::tm = std::mem::zeroed();
-                    let time = secs as libc::time_t;
-                    libc::localtime_r(&time, &mut tm);
-                    tm.tm_gmtoff
-                }
-            };
-            #[cfg(not(unix))]
-            let offset_secs: i64 = 0; // Windows falls back to UTC
-            let local_secs = (secs as i64 + offset_secs) as u64;
-            let hours = (local_secs / 3600) % 24;
-            let mins = (local_secs / 60) % 60;
-            format!("{:02}:{:02}", hours, mins)
-        };
+        let timestamp = datetime::now_iso();
         self.history.push(AiHistoryEntry {
             query,
             response,
@@ -169,6 +151,7 @@ pub const MENU_ITEMS: &[(&str, &str)] = &[
     ),
     ("Health Check", "Test Connectivity To The AI Service"),
     ("History", "View Past AI Interactions"),
+    ("Audit Log", "See What Was Sent To The AI Provider"),
     ("Switch Provider", "Change AI Provider Or API Key"),
 ];
 
@@ -184,15 +167,18 @@ pub fn render(
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(3), // Title
-            Constraint::Min(8),    // Content area
-            Constraint::Length(3), // Hints
+            Constraint::Length(zen::chrome(3)), // Title
+            Constraint::Min(8),                 // Content area
+            Constraint::Length(zen::chrome(3)), // Hints
         ])
         .split(area);
 
     // Title
     let ai_status = if loading {
-        Span::styled(" ⏳ Loading... ", Style::default().fg(Color::Yellow))
+        Span::styled(
+            format!(" {} Loading... ", Icon::Loading),
+            Style::default().fg(Color::Yellow),
+        )
     } else if ai_available {
         Span::styled(" ● Connected ", Style::default().fg(Color::Green))
     } else {
@@ -211,7 +197,7 @@ pub fn render(
 
     let title = Paragraph::new(Line::from(vec![
         Span::styled(
-            "🤖 AI Mentor",
+            format!("{} AI Mentor", Icon::Ai),
             Style::default()
                 .fg(Color::Magenta)
                 .add_modifier(Modifier::BOLD),
@@ -313,7 +299,10 @@ fn render_menu(f: &mut Frame, area: Rect, state: &AiMentorState, ai_available: b
     if !ai_available {
         lines.push(Line::from(Span::raw("")));
         lines.push(Line::from(Span::styled(
-            "  ⚠ AI not configured. Press Enter or 'p' to set up a provider.",
+            format!(
+                "  {} AI not configured. Press Enter or 'p' to set up a provider.",
+                Icon::Warning
+            ),
             Style::default().fg(Color::Yellow),
         )));
         lines.push(Line::from(Span::styled(
@@ -371,16 +360,8 @@ fn render_input(f: &mut Frame, area: Rect, state: &AiMentorState) {
 fn render_result(f: &mut Frame, area: Rect, state: &AiMentorState) {
     let title_text = state.last_action.as_deref().unwrap_or("AI Response");
 
-    let lines: Vec<Line> = state
-        .result_text
-        .lines()
-        .map(|l| {
-            Line::from(Span::styled(
-                format!("  {}", l),
-                Style::default().fg(Color::White),
-            ))
-        })
-        .collect();
+    let lines =
+        crate::ui::markdown::render(&state.result_text, "  ", Style::default().fg(Color::White));
 
     let result = Paragraph::new(lines)
         .block(
@@ -396,7 +377,8 @@ fn render_result(f: &mut Frame, area: Rect, state: &AiMentorState) {
         )
         .scroll((state.result_scroll, 0))
         .wrap(Wrap { trim: false });
-    f.render_widget(result, area);
+    f.render_widget(&result, area);
+    render_paragraph_scrollbar(f, area, &result, state.result_scroll);
 }
 
 #[allow(dead_code)]
@@ -417,7 +399,7 @@ fn render_history(f: &mut Frame, area: Rect, state: &AiMentorState) {
         .block(
             Block::default()
                 .title(Span::styled(
-                    " 📜 History ",
+                    format!(" {} History ", Icon::History),
                     Style::default()
                         .fg(Color::Yellow)
                         .add_modifier(Modifier::BOLD),
@@ -453,23 +435,19 @@ fn render_history(f: &mut Frame, area: Rect, state: &AiMentorState) {
                 }),
             ),
             Span::styled(
-                format!("[{}] ", entry.timestamp),
+                format!(
+                    "[{}] ",
+                    datetime::format_iso(&entry.timestamp, &entry.timestamp)
+                ),
                 Style::default().fg(Color::DarkGray),
             ),
-            Span::styled(entry.query.chars().take(60).collect::<String>(), style),
+            Span::styled(truncate_to_width(&entry.query, 60), style),
         ]));
 
         // Show truncated response preview
-        let preview: String = entry
-            .response
-            .lines()
-            .next()
-            .unwrap_or("")
-            .chars()
-            .take(50)
-            .collect();
+        let preview = truncate_to_width(entry.response.lines().next().unwrap_or(""), 50);
         lines.push(Line::from(Span::styled(
-            format!("       → {}...", preview),
+            format!("       → {}", preview),
             Style::default().fg(Color::DarkGray),
         )));
         lines.push(Line::from(Span::raw("")));
@@ -479,7 +457,11 @@ fn render_history(f: &mut Frame, area: Rect, state: &AiMentorState) {
         .block(
             Block::default()
                 .title(Span::styled(
-                    format!(" 📜 History ({} entries) ", state.history.len()),
+                    format!(
+                        " {} History ({} entries) ",
+                        Icon::History,
+                        state.history.len()
+                    ),
                     Style::default()
                         .fg(Color::Yellow)
                         .add_modifier(Modifier::BOLD),
@@ -489,7 +471,8 @@ fn render_history(f: &mut Frame, area: Rect, state: &AiMentorState) {
         )
         .scroll((state.history_scroll, 0))
         .wrap(Wrap { trim: false });
-    f.render_widget(history_widget, area);
+    f.render_widget(&history_widget, area);
+    render_paragraph_scrollbar(f, area, &history_widget, state.history_scroll);
 }
 
 pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()> {
@@ -503,22 +486,17 @@ pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()
 
 fn handle_menu_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()> {
     match key.code {
-        KeyCode::Up | KeyCode::Char('k') => {
-            if app.ai_mentor_state.selected > 0 {
-                app.ai_mentor_state.selected -= 1;
-            }
+        KeyCode::Up | KeyCode::Char('k') if app.ai_mentor_state.selected > 0 => {
+            app.ai_mentor_state.selected -= 1;
         }
-        KeyCode::Down | KeyCode::Char('j') => {
-            if app.ai_mentor_state.selected + 1 < MENU_ITEMS.len() {
-                app.ai_mentor_state.selected += 1;
-            }
+        KeyCode::Down | KeyCode::Char('j')
+            if app.ai_mentor_state.selected + 1 < MENU_ITEMS.len() =>
+        {
+            app.ai_mentor_state.selected += 1;
         }
         KeyCode::Enter => {
-            if app.ai_client.is_none()
-                && app.ai_mentor_state.selected != 6
-                && app.ai_mentor_state.selected != 7
-            {
-                // Launch interactive AI setup wizard (except for history/switch which don't need AI)
+            if app.ai_client.is_none() && !matches!(app.ai_mentor_state.selected, 6..=8) {
+                // Launch interactive AI setup wizard (except for history/audit/switch which don't need AI)
                 app.start_ai_setup();
                 return Ok(());
             }
@@ -562,6 +540,14 @@ fn handle_menu_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<(
                     app.ai_mentor_state.history_scroll = 0;
                 }
                 7 => {
+                    // Audit log — show recorded outbound requests in the result view
+                    let entries = audit::load_recent(audit::AUDIT_VIEW_LIMIT);
+                    app.ai_mentor_state.result_text = audit::format_entries(&entries);
+                    app.ai_mentor_state.result_scroll = 0;
+                    app.ai_mentor_state.last_action = Some("Audit Log".to_string());
+                    app.ai_mentor_state.mode = AiMode::Result;
+                }
+                8 => {
                     // Switch Provider — launch setup wizard
                     app.start_ai_setup();
                 }
@@ -598,10 +584,8 @@ fn handle_input_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<
                 app.start_ai_ask(query);
             }
         }
-        KeyCode::Char(c) => {
-            if !key.modifiers.contains(KeyModifiers::CONTROL) {
-                app.ai_mentor_state.input.push(c);
-            }
+        KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+            app.ai_mentor_state.input.push(c);
         }
         KeyCode::Backspace => {
             app.ai_mentor_state.input.pop();
@@ -613,6 +597,12 @@ fn handle_input_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<
 
 fn handle_result_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()> {
     match key.code {
+        KeyCode::Esc if app.ai_mentor_state.search.is_some() => app.ai_mentor_state.search = None,
+        KeyCode::Char('/') => app.open_pane_search(),
+        KeyCode::Char('n') => app.step_pane_search(true),
+        KeyCode::Char('N') => app.step_pane_search(false),
+        KeyCode::Char('c') => open_snippets(app),
+        KeyCode::Enter => open_next_steps(app),
         KeyCode::Esc | KeyCode::Char('q') => {
             app.ai_mentor_state.mode = AiMode::Menu;
             app.ai_mentor_state.result_scroll = 0;
@@ -634,6 +624,97 @@ fn handle_result_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result
     Ok(())
 }
 
+/// Offer the answer's code blocks and commands: copy any of them, or run the
+/// ones made of plain git commands.
+fn open_snippets(app: &mut crate::app::App) {
+    use crate::app::{FollowUpAction, FollowUpItem, Popup};
+
+    let snippets = crate::ai::snippets::extract(&app.ai_mentor_state.result_text);
+    if snippets.is_empty() {
+        app.set_status("No code blocks or commands in this answer");
+        return;
+    }
+    let mut suggestions = Vec::new();
+    for snippet in snippets {
+        let mut lines = snippet.code.lines();
+        let first = truncate_to_width(lines.next().unwrap_or_default().trim(), 50);
+        let more = lines.count();
+        let description = match (snippet.language.as_str(), more) {
+            ("", 0) => "Copy to the clipboard".to_string(),
+            ("", more) => format!("Copy all {} lines to the clipboard", more + 1),
+            (language, more) => format!("Copy this {} block ({} lines)", language, more + 1),
+        };
+        if snippet.commands.is_some() {
+            suggestions.push(FollowUpItem {
+                label: format!("Run: {}", first),
+                description: "Asks first, then runs in the output drawer (Ctrl+O)".to_string(),
+                action: FollowUpAction::ConfirmSnippet(snippet.clone()),
+            });
+        }
+        suggestions.push(FollowUpItem {
+            label: format!("Copy: {}", first),
+            description,
+            action: FollowUpAction::CopyToClipboard(snippet.code),
+        });
+    }
+    app.popup = Popup::FollowUp {
+        title: "Code in this answer".to_string(),
+        context: String::new(),
+        suggestions,
+        selected: 0,
+    };
+}
+
+/// Offer what the answer suggests doing next: commands to run, views to
+/// open, concepts to learn.
+fn open_next_steps(app: &mut crate::app::App) {
+    use crate::app::Popup;
+
+    let suggestions = crate::app::generate_answer_follow_ups(&app.ai_mentor_state.result_text);
+    if suggestions.is_empty() {
+        app.set_status("No next steps in this answer");
+        return;
+    }
+    app.popup = Popup::FollowUp {
+        title: "Next steps".to_string(),
+        context: String::new(),
+        suggestions,
+        selected: 0,
+    };
+}
+
+/// Ask before running the git commands of `snippet`, listing each one.
+pub fn confirm_snippet(app: &mut crate::app::App, snippet: crate::ai::snippets::Snippet) {
+    use crate::app::{ConfirmAction, Popup};
+
+    let commands = snippet.commands.clone().unwrap_or_default();
+    let mut message: String = commands
+        .iter()
+        .map(|args| format!("$ git {}\n", args.join(" ")))
+        .collect();
+    if snippet.is_destructive() {
+        message.push_str(&format!(
+            "\n{} This can throw away work that isn't committed or pushed.\n",
+            Icon::Warning
+        ));
+    }
+    message.push_str("\nRun as suggested by the AI?\n\n[y] Yes  [n] No");
+    let title = match commands.as_slice() {
+        [args] => format!("git {}", args.join(" ")),
+        _ => format!("{} AI-suggested commands", commands.len()),
+    };
+    app.popup = Popup::Confirm {
+        title: "Run AI Suggestion".to_string(),
+        message,
+        on_confirm: ConfirmAction::RunSnippet(crate::ui::output_pane::OutputJob {
+            success_msg: format!("✓ {}", title),
+            failure_msg: format!("{} failed (Ctrl+O for output)", title),
+            title,
+            commands,
+        }),
+    };
+}
+
 fn handle_history_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()> {
     let history_len = app.ai_mentor_state.history.len();
     match key.code {
@@ -641,31 +722,30 @@ fn handle_history_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Resul
             app.ai_mentor_state.mode = AiMode::Menu;
             app.ai_mentor_state.history_scroll = 0;
         }
-        KeyCode::Up | KeyCode::Char('k') => {
-            if app.ai_mentor_state.history_selected > 0 {
+        KeyCode::Up | KeyCode::Char('k')
+            if app.ai_mentor_state.history_selected > 0 => {
                 app.ai_mentor_state.history_selected -= 1;
             }
-        }
-        KeyCode::Down | KeyCode::Char('j') => {
-            if history_len > 0 && app.ai_mentor_state.history_selected + 1 < history_len {
+        KeyCode::Down | KeyCode::Char('j')
+            if history_len > 0 && app.ai_mentor_state.history_selected + 1 < history_len => {
                 app.ai_mentor_state.history_selected += 1;
             }
-        }
-        KeyCode::Enter => {
+        KeyCode::Enter
             // View selected history entry in the result view
-            if history_len > 0 {
+            if history_len > 0 => {
                 let idx = history_len.saturating_sub(1) - app.ai_mentor_state.history_selected;
                 if let Some(entry) = app.ai_mentor_state.history.get(idx) {
                     app.ai_mentor_state.result_text = format!(
                         "── {} ──\n[{}]\n\n{}",
-                        entry.query, entry.timestamp, entry.response
+                        entry.query,
+                        datetime::format_iso(&entry.timestamp, &entry.timestamp),
+                        entry.response
                     );
                     app.ai_mentor_state.result_scroll = 0;
                     app.ai_mentor_state.last_action = Some("History".to_string());
                     app.ai_mentor_state.mode = AiMode::Result;
                 }
             }
-        }
         KeyCode::PageDown => {
             app.ai_mentor_state.history_scroll =
                 app.ai_mentor_state.history_scroll.saturating_add(5);
@@ -2,14 +2,17 @@
 
 use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::{
+    Frame,
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap},
-    Frame,
 };
 
 use crate::git;
+use crate::ui::icons::Icon;
+use crate::ui::utils::{render_paragraph_scrollbar, render_scrollbar};
+use crate::ui::zen;
 
 /// Sub-view within the Bisect screen.
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -59,6 +62,12 @@ impl Default for BisectState {
 }
 
 impl BisectState {
+    /// Whether Esc has a step to undo here (picking the good commit) rather
+    /// than leaving the view.
+    pub fn esc_steps_back(&self) -> bool {
+        self.mode == BisectMode::PickGood
+    }
+
     pub fn refresh(&mut self) {
         self.phase = git::bisect::bisect_status();
         match &self.phase {
@@ -97,9 +106,9 @@ fn render_picker(f: &mut Frame, area: Rect, state: &mut BisectState) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(3), // Instructions
-            Constraint::Min(5),    // Commit list
-            Constraint::Length(3), // Keybindings
+            Constraint::Length(3),              // Instructions
+            Constraint::Min(5),                 // Commit list
+            Constraint::Length(zen::chrome(3)), // Keybindings
         ])
         .split(area);
 
@@ -194,7 +203,8 @@ fn render_picker(f: &mut Frame, area: Rect, state: &mut BisectState) {
         )
         .highlight_symbol("▶ ");
 
-    f.render_stateful_widget(list, chunks[1], &mut state.list_state);
+    f.render_stateful_widget(&list, chunks[1], &mut state.list_state);
+    render_scrollbar(f, chunks[1], list.len(), state.list_state.offset());
 
     // Keybindings
     let keys = Paragraph::new(Line::from(vec![
@@ -220,9 +230,9 @@ fn render_running(f: &mut Frame, area: Rect, state: &mut BisectState) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(5), // Status panel
-            Constraint::Min(5),    // Bisect log
-            Constraint::Length(3), // Keybindings
+            Constraint::Length(5),              // Status panel
+            Constraint::Min(5),                 // Bisect log
+            Constraint::Length(zen::chrome(3)), // Keybindings
         ])
         .split(area);
 
@@ -303,7 +313,7 @@ fn render_running(f: &mut Frame, area: Rect, state: &mut BisectState) {
     let status = Paragraph::new(status_lines).block(
         Block::default()
             .title(Span::styled(
-                " 🔍 Bisect ",
+                format!(" {} Bisect ", Icon::Search),
                 Style::default()
                     .fg(Color::Cyan)
                     .add_modifier(Modifier::BOLD),
@@ -366,7 +376,8 @@ fn render_running(f: &mut Frame, area: Rect, state: &mut BisectState) {
         )
         .scroll((state.log_scroll, 0))
         .wrap(Wrap { trim: false });
-    f.render_widget(log_widget, chunks[1]);
+    f.render_widget(&log_widget, chunks[1]);
+    render_paragraph_scrollbar(f, chunks[1], &log_widget, state.log_scroll);
 
     // Keybindings
     let key_spans = match &state.phase {
@@ -412,17 +423,15 @@ pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()
         match state.mode {
             BisectMode::PickBad | BisectMode::PickGood => {
                 match key.code {
-                    KeyCode::Up | KeyCode::Char('k') => {
-                        if state.selected > 0 {
-                            state.selected -= 1;
-                            state.list_state.select(Some(state.selected));
-                        }
+                    KeyCode::Up | KeyCode::Char('k') if state.selected > 0 => {
+                        state.selected -= 1;
+                        state.list_state.select(Some(state.selected));
                     }
-                    KeyCode::Down | KeyCode::Char('j') => {
-                        if state.selected + 1 < state.commits.len() {
-                            state.selected += 1;
-                            state.list_state.select(Some(state.selected));
-                        }
+                    KeyCode::Down | KeyCode::Char('j')
+                        if state.selected + 1 < state.commits.len() =>
+                    {
+                        state.selected += 1;
+                        state.list_state.select(Some(state.selected));
                     }
                     KeyCode::Enter => {
                         if let Some((hash, _)) = state.commits.get(state.selected) {
@@ -465,14 +474,12 @@ pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()
                             }
                         }
                     }
-                    KeyCode::Esc => {
-                        if state.mode == BisectMode::PickGood {
-                            // Go back to picking bad commit
-                            state.mode = BisectMode::PickBad;
-                            state.bad_commit = None;
-                            state.selected = 0;
-                            state.list_state.select(Some(0));
-                        }
+                    KeyCode::Esc if state.mode == BisectMode::PickGood => {
+                        // Go back to picking bad commit
+                        state.mode = BisectMode::PickBad;
+                        state.bad_commit = None;
+                        state.selected = 0;
+                        state.list_state.select(Some(0));
                     }
                     _ => {}
                 }
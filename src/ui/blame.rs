@@ -0,0 +1,301 @@
+use std::time::Instant;
+
+use crossterm::event::{KeyCode, KeyEvent};
+use jiff::Timestamp;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+};
+
+use crate::app::{App, View};
+use crate::git;
+use crate::git::blame::BlameLine;
+use crate::jobs::Priority;
+use crate::tasks;
+use crate::ui::datetime;
+use crate::ui::utils::{render_scrollbar, truncate_to_width};
+use crate::ui::zen;
+
+/// Commit colors from the newest changes in the file to the oldest.
+const AGE_COLORS: [Color; 5] = [
+    Color::LightGreen,
+    Color::Green,
+    Color::Cyan,
+    Color::Blue,
+    Color::DarkGray,
+];
+
+const AUTHOR_WIDTH: usize = 14;
+const AGE_WIDTH: usize = 15;
+
+#[derive(Default)]
+pub struct BlameState {
+    pub path: String,
+    /// The commit blamed at, or `None` for the working tree.
+    pub rev: Option<String>,
+    pub lines: Vec<BlameLine>,
+    pub selected: usize,
+    pub list_state: ListState,
+    /// `git blame` is still running on the git workers.
+    pub loading: bool,
+    /// The line to select once the blame arrives.
+    pub line: Option<u32>,
+    /// When the blame shown was asked for.
+    pub loaded_at: Option<Instant>,
+}
+
+/// A file's blame, read on a worker (see `tasks`).
+pub struct Loaded {
+    at: Instant,
+    path: String,
+    rev: Option<String>,
+    lines: Result<Vec<BlameLine>, String>,
+}
+
+impl BlameState {
+    /// Blame `path` at `rev`, as asked for at `at`.
+    pub fn load(path: String, rev: Option<String>, at: Instant) -> Loaded {
+        let lines = git::blame::blame(&path, rev.as_deref()).map_err(|e| e.to_string());
+        Loaded {
+            at,
+            path,
+            rev,
+            lines,
+        }
+    }
+
+    fn select(&mut self, index: usize) {
+        self.selected = index.min(self.lines.len().saturating_sub(1));
+        self.list_state
+            .select((!self.lines.is_empty()).then_some(self.selected));
+    }
+
+    /// Author times of the newest and oldest committed lines.
+    fn time_range(&self) -> (i64, i64) {
+        let times = self
+            .lines
+            .iter()
+            .filter(|l| l.is_committed())
+            .map(|l| l.time);
+        (times.clone().max().unwrap_or(0), times.min().unwrap_or(0))
+    }
+}
+
+/// Which of [`AGE_COLORS`] a change made at `time` gets, spreading the file's
+/// own history from `newest` to `oldest` over them.
+fn age_rank(time: i64, newest: i64, oldest: i64) -> usize {
+    let span = (newest - oldest).max(1);
+    let rank = (newest - time).clamp(0, span) * AGE_COLORS.len() as i64 / (span + 1);
+    rank as usize
+}
+
+/// Open the Blame view on `path` (at `rev`, else in the working tree) and
+/// blame it on the git workers; [`apply`] selects `line` once it's read.
+pub fn open(app: &mut App, path: &str, rev: Option<String>, line: Option<u32>) {
+    let state = &mut app.blame_state;
+    state.path = path.to_string();
+    state.rev = rev.clone();
+    state.lines.clear();
+    state.list_state = ListState::default();
+    state.selected = 0;
+    state.line = line;
+    state.loading = true;
+    app.open_view(View::Blame);
+    app.tasks.submit(
+        Some(tasks::Read::Blame {
+            path: path.to_string(),
+            rev,
+        }),
+        Priority::User,
+    );
+}
+
+/// Show what [`BlameState::load`] read, if the view still wants it. A failed
+/// blame goes back to where the view was opened from.
+pub fn apply(app: &mut App, loaded: Loaded) {
+    let state = &mut app.blame_state;
+    if loaded.path != state.path
+        || loaded.rev != state.rev
+        || !tasks::is_newer(&mut state.loaded_at, loaded.at)
+    {
+        return;
+    }
+    state.loading = false;
+    match loaded.lines {
+        Ok(lines) => {
+            state.lines = lines;
+            let index = state
+                .line
+                .take()
+                .and_then(|n| state.lines.iter().position(|l| l.line_no >= n))
+                .unwrap_or(0);
+            state.select(index);
+        }
+        Err(e) => {
+            if app.view == View::Blame {
+                app.go_back();
+            }
+            app.set_status(format!("Blame failed: {}", e));
+        }
+    }
+}
+
+pub fn render(f: &mut Frame, area: Rect, state: &mut BlameState) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(5),                 // Lines
+            Constraint::Length(zen::chrome(3)), // Selected commit & hints
+        ])
+        .split(area);
+
+    let (newest, oldest) = state.time_range();
+    let now = Timestamp::now();
+    let number_width = state.lines.len().to_string().len();
+    let items: Vec<ListItem> = state
+        .lines
+        .iter()
+        .enumerate()
+        .map(|(i, line)| {
+            let color = if line.is_committed() {
+                AGE_COLORS[age_rank(line.time, newest, oldest)]
+            } else {
+                Color::Yellow
+            };
+            // Like `tig blame`: the commit is shown where its run of lines starts
+            let starts_run = i == 0 || state.lines[i - 1].hash != line.hash;
+            let gutter = if !starts_run {
+                " ".repeat(8 + AUTHOR_WIDTH + AGE_WIDTH)
+            } else if line.is_committed() {
+                let age = Timestamp::from_second(line.time)
+                    .map(|ts| datetime::relative(ts, now))
+                    .unwrap_or_default();
+                format!(
+                    "{} {:<aw$}{:<gw$}",
+                    line.short_hash(),
+                    truncate_to_width(&line.author, AUTHOR_WIDTH - 1),
+                    age,
+                    aw = AUTHOR_WIDTH,
+                    gw = AGE_WIDTH
+                )
+            } else {
+                format!("{:<w$}", "not committed", w = 8 + AUTHOR_WIDTH + AGE_WIDTH)
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(gutter, Style::default().fg(color)),
+                Span::styled(
+                    format!("{:>w$} │ ", line.line_no, w = number_width),
+                    Style::default().fg(Color::DarkGray),
+                ),
+                Span::styled(line.content.as_str(), Style::default().fg(Color::White)),
+            ]))
+        })
+        .collect();
+    let items = if state.loading {
+        vec![ListItem::new(Span::styled(
+            " Running git blame…",
+            Style::default().fg(Color::DarkGray),
+        ))]
+    } else {
+        items
+    };
+
+    let title = match &state.rev {
+        Some(rev) => format!(" Blame: {} @ {} ", state.path, &rev[..rev.len().min(7)]),
+        None => format!(" Blame: {} ", state.path),
+    };
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .title(Span::styled(
+                    title,
+                    Style::default()
+                        .fg(Color::White)
+                        .add_modifier(Modifier::BOLD),
+                ))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan)),
+        )
+        .highlight_style(Style::default().bg(Color::DarkGray));
+    let total = list.len();
+    f.render_stateful_widget(list, chunks[0], &mut state.list_state);
+    render_scrollbar(f, chunks[0], total, state.list_state.offset());
+
+    let mut info = match state.lines.get(state.selected) {
+        Some(line) if line.is_committed() => vec![
+            Span::styled(
+                format!(" {} ", line.short_hash()),
+                Style::default().fg(Color::Yellow),
+            ),
+            Span::styled(
+                format!("{} — ", line.author),
+                Style::default().fg(Color::Cyan),
+            ),
+            Span::raw(line.summary.clone()),
+            Span::raw("  "),
+        ],
+        Some(_) => vec![Span::styled(
+            " Not committed yet  ",
+            Style::default().fg(Color::Yellow),
+        )],
+        None => Vec::new(),
+    };
+    info.extend([
+        Span::styled("[Enter]", Style::default().fg(Color::Cyan)),
+        Span::raw(" Commit in Timeline "),
+        Span::styled("[q]", Style::default().fg(Color::DarkGray)),
+        Span::raw(" Back"),
+    ]);
+    let info = Paragraph::new(Line::from(info)).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::DarkGray)),
+    );
+    f.render_widget(info, chunks[1]);
+}
+
+pub fn handle_key(app: &mut App, key: KeyEvent) -> anyhow::Result<()> {
+    let state = &mut app.blame_state;
+    match key.code {
+        KeyCode::Up | KeyCode::Char('k') => state.select(state.selected.saturating_sub(1)),
+        KeyCode::Down | KeyCode::Char('j') => state.select(state.selected + 1),
+        KeyCode::PageUp => state.select(state.selected.saturating_sub(20)),
+        KeyCode::PageDown => state.select(state.selected + 20),
+        KeyCode::Home => state.select(0),
+        KeyCode::End => state.select(state.lines.len()),
+        KeyCode::Enter => {
+            let Some(line) = state.lines.get(state.selected) else {
+                return Ok(());
+            };
+            if !line.is_committed() {
+                app.set_status("This line isn't committed yet");
+                return Ok(());
+            }
+            let (hash, path) = (line.hash.clone(), state.path.clone());
+            app.timeline_state.set_path(Some(path));
+            match app.timeline_state.show_commit(&hash) {
+                Ok(()) => app.open_view(View::Timeline),
+                Err(e) => app.set_status(format!("Error: {}", e)),
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_age_rank() {
+        assert_eq!(age_rank(1000, 1000, 0), 0);
+        assert_eq!(age_rank(0, 1000, 0), AGE_COLORS.len() - 1);
+        assert_eq!(age_rank(500, 1000, 0), 2);
+        // A file changed in a single commit is all "newest"
+        assert_eq!(age_rank(1000, 1000, 1000), 0);
+    }
+}
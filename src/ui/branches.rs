@@ -1,13 +1,16 @@
 use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::{
-    layout::{Constraint, Rect},
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::Span,
-    widgets::{Block, Borders, Cell, Row, Table, TableState},
-    Frame,
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table, TableState, Wrap},
 };
+use std::time::Instant;
 
 use crate::git;
+use crate::ui::datetime;
+use crate::ui::utils::render_scrollbar;
 
 #[derive(Default)]
 pub struct BranchesState {
@@ -15,11 +18,34 @@ pub struct BranchesState {
     pub selected: usize,
     pub table_state: TableState,
     pub show_remote: bool,
+    /// When the branches shown were asked for.
+    pub loaded_at: Option<Instant>,
+}
+
+/// Every branch, local and remote, read on a worker (see `tasks`).
+pub struct Loaded {
+    at: Instant,
+    branches: Option<Vec<git::BranchEntry>>,
 }
 
 impl BranchesState {
     pub fn refresh(&mut self) {
-        if let Ok(branches) = git::BranchOps::list() {
+        self.apply(Self::load(Instant::now()));
+    }
+
+    pub fn load(at: Instant) -> Loaded {
+        Loaded {
+            at,
+            branches: git::BranchOps::list().ok(),
+        }
+    }
+
+    /// Show what [`load`](Self::load) read, unless something newer is shown.
+    pub fn apply(&mut self, loaded: Loaded) {
+        if !crate::tasks::is_newer(&mut self.loaded_at, loaded.at) {
+            return;
+        }
+        if let Some(branches) = loaded.branches {
             self.branches = if self.show_remote {
                 branches
             } else {
@@ -37,7 +63,47 @@ impl BranchesState {
     }
 }
 
+/// Most lines of the selected branch's description shown under the table.
+const DESCRIPTION_LINES: u16 = 4;
+
 pub fn render(f: &mut Frame, area: Rect, state: &mut BranchesState) {
+    // The selected branch's description, when it has one
+    let description = state.branches.get(state.selected).and_then(|b| {
+        b.description
+            .as_ref()
+            .map(|d| (b.name.as_str(), d.as_str()))
+    });
+    let (area, description_area) = match description {
+        Some((_, text)) => {
+            let height = (text.lines().count() as u16).min(DESCRIPTION_LINES) + 2;
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Min(5), Constraint::Length(height)])
+                .split(area);
+            (chunks[0], Some(chunks[1]))
+        }
+        None => (area, None),
+    };
+    if let (Some((name, text)), Some(rect)) = (description, description_area) {
+        let panel = Paragraph::new(text)
+            .block(
+                Block::default()
+                    .title(Span::styled(
+                        format!(" {} — description ", name),
+                        Style::default().fg(Color::White),
+                    ))
+                    .title_bottom(Span::styled(
+                        " e edit ",
+                        Style::default().fg(Color::DarkGray),
+                    ))
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::DarkGray)),
+            )
+            .style(Style::default().fg(Color::Gray))
+            .wrap(Wrap { trim: false });
+        f.render_widget(panel, rect);
+    }
+
     let header_cells = ["", "Branch", "Upstream", "Last Commit", "Author", "Date"]
         .iter()
         .map(|h| {
@@ -59,6 +125,10 @@ pub fn render(f: &mut Frame, area: Rect, state: &mut BranchesState) {
             } else {
                 Color::DarkGray
             };
+            let name_cell = match &b.description {
+                Some(_) => format!("{} ✎", b.name),
+                None => b.name.clone(),
+            };
             let name_style = if b.is_current {
                 Style::default()
                     .fg(Color::Green)
@@ -71,12 +141,16 @@ pub fn render(f: &mut Frame, area: Rect, state: &mut BranchesState) {
 
             Row::new(vec![
                 Cell::from(current_marker).style(Style::default().fg(marker_color)),
-                Cell::from(b.name.as_str()).style(name_style),
+                Cell::from(name_cell).style(name_style),
                 Cell::from(b.upstream.as_str()).style(Style::default().fg(Color::DarkGray)),
                 Cell::from(b.last_commit_msg.as_str()).style(Style::default().fg(Color::White)),
                 Cell::from(b.last_commit_author.as_str())
                     .style(Style::default().fg(Color::DarkGray)),
-                Cell::from(b.last_commit_date.as_str()).style(Style::default().fg(Color::DarkGray)),
+                Cell::from(datetime::format_iso(
+                    &b.last_commit_date_iso,
+                    &b.last_commit_date,
+                ))
+                .style(Style::default().fg(Color::DarkGray)),
             ])
         })
         .collect();
@@ -87,6 +161,7 @@ pub fn render(f: &mut Frame, area: Rect, state: &mut BranchesState) {
         " (local) "
     };
 
+    let total = rows.len();
     let table = Table::new(
         rows,
         [
@@ -114,23 +189,175 @@ pub fn render(f: &mut Frame, area: Rect, state: &mut BranchesState) {
     .highlight_symbol("▶ ");
 
     f.render_stateful_widget(table, area, &mut state.table_state);
+    render_scrollbar(f, area, total, state.table_state.offset());
+}
+
+/// Switch to `name`; uncommitted changes that don't conflict come along.
+pub fn switch_to(app: &mut crate::app::App, name: &str) {
+    match git::BranchOps::switch(name) {
+        Ok(()) => {
+            app.set_status(format!("Switched to '{}'", name));
+            app.branches_state.refresh();
+        }
+        Err(e) => app.set_status(format!("Error: {}", e)),
+    }
+}
+
+/// Ask before checking out `hash` with HEAD detached.
+pub fn confirm_detach(app: &mut crate::app::App, hash: &str, short_hash: &str) {
+    app.popup = crate::app::Popup::Confirm {
+        title: "Check Out Commit".to_string(),
+        message: format!(
+            "Check out {} to look around?\n\nHEAD will be detached: commits made there belong to no branch.\nA banner stays up with Ctrl+B (create a branch here) and Ctrl+P\n(return to your branch) until you leave.\n\n[y] Yes  [n] No",
+            short_hash
+        ),
+        on_confirm: crate::app::ConfirmAction::DetachAt(hash.to_string()),
+    };
+}
+
+/// Name a branch at the detached HEAD and switch to it.
+pub fn branch_here(app: &mut crate::app::App) {
+    app.popup = crate::app::Popup::Input {
+        title: "Create Branch Here".to_string(),
+        prompt: "Branch name: ".to_string(),
+        value: String::new(),
+        on_submit: crate::app::InputAction::BranchHere,
+    };
+}
+
+/// Return to the branch checked out before detaching. Commits made while
+/// detached would be left on no branch, so offer to name one first.
+pub fn leave_detached_head(app: &mut crate::app::App) {
+    use crate::app::{FollowUpAction, FollowUpItem, Popup};
+
+    let Some(detached) = app.detached_head.clone() else {
+        return;
+    };
+    let Some(previous) = detached.previous else {
+        app.set_status("No branch to return to — create one here with Ctrl+B");
+        return;
+    };
+    if detached.orphaned == 0 {
+        switch_to(app, &previous);
+        app.refresh();
+        return;
+    }
+    app.popup = Popup::FollowUp {
+        title: "Leave Detached HEAD".to_string(),
+        context: format!(
+            "{} commit(s) made at {} are on no branch and would only be left in the reflog.",
+            detached.orphaned, detached.short_hash
+        ),
+        suggestions: vec![
+            FollowUpItem {
+                label: "Create a branch here".to_string(),
+                description: "Keep the commits on a new branch".to_string(),
+                action: FollowUpAction::BranchHere,
+            },
+            FollowUpItem {
+                label: format!("Return to '{}' anyway", previous),
+                description: "Leave the commits behind".to_string(),
+                action: FollowUpAction::LeaveDetachedHead(previous),
+            },
+        ],
+        selected: 0,
+    };
+}
+
+/// Stash everything (untracked files too), switch to `name` and re-apply the
+/// stash there. If it doesn't apply cleanly, the stash is kept.
+pub fn stash_and_switch(app: &mut crate::app::App, name: &str) {
+    let options = git::stash::StashOptions {
+        include_untracked: true,
+        ..Default::default()
+    };
+    let message = format!("zit: switching to {}", name);
+    if let Err(e) = git::stash::stash_push(Some(&message), &options) {
+        app.set_status(format!("Couldn't stash your changes: {}", e));
+        return;
+    }
+    if let Err(e) = git::BranchOps::switch(name) {
+        // Put the changes back where they were
+        let restored = git::stash::stash_pop_index(0).is_ok();
+        app.set_status(format!(
+            "Error: {}{}",
+            e,
+            if restored {
+                ""
+            } else {
+                " — your changes are in stash@{0}"
+            }
+        ));
+        return;
+    }
+    match git::stash::stash_pop_index(0) {
+        Ok(_) => app.set_status(format!(
+            "Switched to '{}' and re-applied your changes",
+            name
+        )),
+        Err(_) => app.set_status(format!(
+            "Switched to '{}', but your changes conflict here — the stash is kept as stash@{{0}}",
+            name
+        )),
+    }
+    app.branches_state.refresh();
+}
+
+/// Ask how to switch to `name` with uncommitted changes, instead of letting
+/// git fail with "would be overwritten by checkout".
+fn offer_dirty_switch(app: &mut crate::app::App, name: &str) {
+    use crate::app::{FollowUpAction, FollowUpItem};
+
+    let blockers = git::BranchOps::switch_blockers(name).unwrap_or_default();
+    let mut suggestions = Vec::new();
+    if blockers.is_empty() {
+        suggestions.push(FollowUpItem {
+            label: "Bring my changes along".to_string(),
+            description: format!("None of them touch files that differ on '{}'", name),
+            action: FollowUpAction::SwitchBranch(name.to_string()),
+        });
+    }
+    suggestions.push(FollowUpItem {
+        label: "Stash, switch and re-apply".to_string(),
+        description: "git stash -u, git switch, git stash pop — kept if it conflicts".to_string(),
+        action: FollowUpAction::StashAndSwitchBranch(name.to_string()),
+    });
+
+    let context = if blockers.is_empty() {
+        "You have uncommitted changes. Esc to cancel.".to_string()
+    } else {
+        let shown: Vec<&str> = blockers.iter().take(3).map(String::as_str).collect();
+        format!(
+            "Switching would overwrite your changes to {}{}. Esc to cancel.",
+            shown.join(", "),
+            if blockers.len() > shown.len() {
+                format!(" and {} more", blockers.len() - shown.len())
+            } else {
+                String::new()
+            }
+        )
+    };
+    app.popup = crate::app::Popup::FollowUp {
+        title: format!("Switch to '{}'", name),
+        context,
+        suggestions,
+        selected: 0,
+    };
 }
 
 pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()> {
     match key.code {
-        KeyCode::Up | KeyCode::Char('k') => {
-            if app.branches_state.selected > 0 {
-                app.branches_state.selected -= 1;
-                let sel = app.branches_state.selected;
-                app.branches_state.table_state.select(Some(sel));
-            }
+        KeyCode::Up | KeyCode::Char('k') if app.branches_state.selected > 0 => {
+            app.branches_state.selected -= 1;
+            let sel = app.branches_state.selected;
+            app.branches_state.table_state.select(Some(sel));
         }
-        KeyCode::Down | KeyCode::Char('j') => {
-            if app.branches_state.selected + 1 < app.branches_state.branches.len() {
-                app.branches_state.selected += 1;
-                let sel = app.branches_state.selected;
-                app.branches_state.table_state.select(Some(sel));
-            }
+        KeyCode::Down | KeyCode::Char('j')
+            if app.branches_state.selected + 1 < app.branches_state.branches.len() =>
+        {
+            app.branches_state.selected += 1;
+            let sel = app.branches_state.selected;
+            app.branches_state.table_state.select(Some(sel));
         }
         KeyCode::Enter => {
             // Switch to selected branch
@@ -143,25 +370,12 @@ pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()
 
                 let name = branch.name.clone();
 
-                // Check for uncommitted changes
+                // Uncommitted changes: ask what to do with them
                 if git::BranchOps::has_uncommitted_changes().unwrap_or(false) {
-                    app.popup = crate::app::Popup::Message {
-                        title: "Warning".to_string(),
-                        message: format!(
-                            "You have uncommitted changes. Commit or stash them before switching to '{}'.",
-                            name
-                        ),
-                    };
+                    offer_dirty_switch(app, &name);
                     return Ok(());
                 }
-
-                match git::BranchOps::switch(&name) {
-                    Ok(()) => {
-                        app.set_status(format!("Switched to '{}'", name));
-                        app.branches_state.refresh();
-                    }
-                    Err(e) => app.set_status(format!("Error: {}", e)),
-                }
+                switch_to(app, &name);
             }
         }
         KeyCode::Char('n') => {
@@ -190,6 +404,31 @@ pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()
                 };
             }
         }
+        KeyCode::Char('e') => {
+            // Describe what a long-lived branch is for
+            let selected = app.branches_state.selected;
+            if let Some(branch) = app.branches_state.branches.get(selected) {
+                if branch.is_remote {
+                    app.set_status("Only local branches have descriptions");
+                    return Ok(());
+                }
+                app.editor_request = Some(crate::ui::editor::EditorTarget::BranchDescription(
+                    branch.name.clone(),
+                ));
+            }
+        }
+        KeyCode::Char('u') => {
+            // What a push and a pull would change for the selected branch
+            let selected = app.branches_state.selected;
+            if let Some(branch) = app.branches_state.branches.get(selected) {
+                if branch.is_remote {
+                    app.set_status("Select a local branch to compare with its upstream");
+                    return Ok(());
+                }
+                let name = (!branch.is_current).then(|| branch.name.clone());
+                crate::ui::outgoing::open_upstream_compare(app, name);
+            }
+        }
         KeyCode::Char('R') => {
             app.popup = crate::app::Popup::Input {
                 title: "Rename Branch".to_string(),
@@ -198,6 +437,18 @@ pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()
                 on_submit: crate::app::InputAction::RenameBranch,
             };
         }
+        KeyCode::Char('S') => {
+            // Ask the AI advisor how to integrate the selected branch into the current one
+            let selected = app.branches_state.selected;
+            if let Some(branch) = app.branches_state.branches.get(selected) {
+                if branch.is_current {
+                    app.set_status("Select another branch to compare with the current one");
+                    return Ok(());
+                }
+                let name = branch.name.clone();
+                app.start_ai_merge_strategy(Some(name), None);
+            }
+        }
         KeyCode::Tab => {
             app.branches_state.show_remote = !app.branches_state.show_remote;
             app.branches_state.refresh();
@@ -2,15 +2,22 @@
 
 use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::{
+    Frame,
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap},
-    Frame,
 };
 
 use crate::git;
 use crate::git::log::CommitEntry;
+use crate::ui::datetime;
+use crate::ui::icons::Icon;
+use crate::ui::utils::{
+    Debounce, display_width, is_narrow, list_text_width, render_paragraph_scrollbar,
+    render_scrollbar, truncate_to_width,
+};
+use crate::ui::zen;
 
 /// Sub-view within the Cherry Pick screen.
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -45,6 +52,8 @@ pub struct CherryPickState {
     /// Diff preview of the currently highlighted commit.
     pub diff_text: String,
     pub diff_scroll: u16,
+    /// Diff reload deferred while moving through the commits.
+    pub diff_debounce: Debounce,
 
     /// Whether a cherry-pick conflict is active.
     pub conflict_active: bool,
@@ -65,12 +74,19 @@ impl Default for CherryPickState {
             marked: Vec::new(),
             diff_text: String::new(),
             diff_scroll: 0,
+            diff_debounce: Debounce::default(),
             conflict_active: false,
         }
     }
 }
 
 impl CherryPickState {
+    /// Whether Esc has a step to undo here (picking commits returns to the
+    /// branch list) rather than leaving the view.
+    pub fn esc_steps_back(&self) -> bool {
+        self.mode == CherryPickMode::CommitSelect
+    }
+
     pub fn refresh(&mut self) {
         self.current_branch = git::cherry_pick::get_current_branch();
         self.conflict_active = git::cherry_pick::is_cherry_picking();
@@ -108,13 +124,24 @@ impl CherryPickState {
         }
     }
 
+    /// Load the diff once navigation has paused (or right away with `force`).
+    /// Returns whether it was loaded.
+    pub fn settle_diff(&mut self, force: bool) -> bool {
+        let due = self.diff_debounce.fire(force);
+        if due {
+            self.update_diff();
+        }
+        due
+    }
+
     fn update_diff(&mut self) {
+        self.diff_debounce.cancel();
         self.diff_text.clear();
         self.diff_scroll = 0;
-        if let Some(commit) = self.commits.get(self.commit_selected) {
-            if let Ok(diff) = git::cherry_pick::commit_diff(&commit.hash) {
-                self.diff_text = diff;
-            }
+        if let Some(commit) = self.commits.get(self.commit_selected)
+            && let Ok(diff) = git::cherry_pick::commit_diff(&commit.hash)
+        {
+            self.diff_text = diff;
         }
     }
 
@@ -146,9 +173,9 @@ fn render_branch_select(f: &mut Frame, area: Rect, state: &mut CherryPickState)
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(3), // Header
-            Constraint::Min(5),    // Branch list
-            Constraint::Length(3), // Keys
+            Constraint::Length(zen::chrome(3)), // Header
+            Constraint::Min(5),                 // Branch list
+            Constraint::Length(zen::chrome(3)), // Keys
         ])
         .split(area);
 
@@ -169,7 +196,7 @@ fn render_branch_select(f: &mut Frame, area: Rect, state: &mut CherryPickState)
     .block(
         Block::default()
             .title(Span::styled(
-                " 🍒 Cherry Pick ",
+                format!(" {} Cherry Pick ", Icon::CherryPick),
                 Style::default()
                     .fg(Color::Magenta)
                     .add_modifier(Modifier::BOLD),
@@ -213,7 +240,8 @@ fn render_branch_select(f: &mut Frame, area: Rect, state: &mut CherryPickState)
         )
         .highlight_symbol("▶ ");
 
-    f.render_stateful_widget(list, chunks[1], &mut state.branch_list_state);
+    f.render_stateful_widget(&list, chunks[1], &mut state.branch_list_state);
+    render_scrollbar(f, chunks[1], list.len(), state.branch_list_state.offset());
 
     // Keys
     let keys = Paragraph::new(Line::from(vec![
@@ -252,9 +280,9 @@ fn render_commit_select(f: &mut Frame, area: Rect, state: &mut CherryPickState)
     let main_chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(3), // Header
-            Constraint::Min(5),    // Content
-            Constraint::Length(3), // Keys
+            Constraint::Length(zen::chrome(3)), // Header
+            Constraint::Min(5),                 // Content
+            Constraint::Length(zen::chrome(3)), // Keys
         ])
         .split(area);
 
@@ -283,7 +311,7 @@ fn render_commit_select(f: &mut Frame, area: Rect, state: &mut CherryPickState)
     .block(
         Block::default()
             .title(Span::styled(
-                " 🍒 Cherry Pick — Select Commits ",
+                format!(" {} Cherry Pick — Select Commits ", Icon::CherryPick),
                 Style::default()
                     .fg(Color::Magenta)
                     .add_modifier(Modifier::BOLD),
@@ -293,9 +321,13 @@ fn render_commit_select(f: &mut Frame, area: Rect, state: &mut CherryPickState)
     );
     f.render_widget(header, main_chunks[0]);
 
-    // Content: commit list + diff preview
+    // Content: commit list + diff preview, stacked on narrow terminals
     let content_chunks = Layout::default()
-        .direction(Direction::Horizontal)
+        .direction(if is_narrow(area) {
+            Direction::Vertical
+        } else {
+            Direction::Horizontal
+        })
         .constraints([
             Constraint::Percentage(45), // Commit list
             Constraint::Percentage(55), // Diff preview
@@ -303,10 +335,13 @@ fn render_commit_select(f: &mut Frame, area: Rect, state: &mut CherryPickState)
         .split(main_chunks[1]);
 
     // Commit list
+    let row_width = list_text_width(content_chunks[0]);
     let items: Vec<ListItem> = state
         .commits
         .iter()
         .map(|c| {
+            let date = datetime::format_iso(&c.date_iso, &c.date);
+            let used = display_width(&c.short_hash) + display_width(&date) + 6;
             let is_marked = state.marked.contains(&c.hash);
             let marker = if is_marked { "● " } else { "  " };
             let marker_color = if is_marked {
@@ -328,11 +363,11 @@ fn render_commit_select(f: &mut Frame, area: Rect, state: &mut CherryPickState)
                         .fg(Color::Yellow)
                         .add_modifier(Modifier::BOLD),
                 ),
-                Span::styled(&c.message, Style::default().fg(Color::White)),
                 Span::styled(
-                    format!(" ({})", c.date),
-                    Style::default().fg(Color::DarkGray),
+                    truncate_to_width(&c.message, row_width.saturating_sub(used).max(12)),
+                    Style::default().fg(Color::White),
                 ),
+                Span::styled(format!(" ({})", date), Style::default().fg(Color::DarkGray)),
             ]))
         })
         .collect();
@@ -354,7 +389,13 @@ fn render_commit_select(f: &mut Frame, area: Rect, state: &mut CherryPickState)
         )
         .highlight_symbol("▶ ");
 
-    f.render_stateful_widget(list, content_chunks[0], &mut state.commit_list_state);
+    f.render_stateful_widget(&list, content_chunks[0], &mut state.commit_list_state);
+    render_scrollbar(
+        f,
+        content_chunks[0],
+        list.len(),
+        state.commit_list_state.offset(),
+    );
 
     // Diff preview
     let diff_lines: Vec<Line> = state
@@ -381,7 +422,8 @@ fn render_commit_select(f: &mut Frame, area: Rect, state: &mut CherryPickState)
         )
         .scroll((state.diff_scroll, 0))
         .wrap(Wrap { trim: false });
-    f.render_widget(diff, content_chunks[1]);
+    f.render_widget(&diff, content_chunks[1]);
+    render_paragraph_scrollbar(f, content_chunks[1], &diff, state.diff_scroll);
 
     // Keybindings
     let keys = Paragraph::new(Line::from(vec![
@@ -429,9 +471,9 @@ fn render_in_progress(f: &mut Frame, area: Rect, state: &mut CherryPickState) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(5), // Status
-            Constraint::Min(5),    // Diff / conflict info
-            Constraint::Length(3), // Keys
+            Constraint::Length(5),              // Status
+            Constraint::Min(5),                 // Diff / conflict info
+            Constraint::Length(zen::chrome(3)), // Keys
         ])
         .split(area);
 
@@ -442,9 +484,7 @@ fn render_in_progress(f: &mut Frame, area: Rect, state: &mut CherryPickState) {
                 Span::styled("  Status: ", Style::default().fg(Color::DarkGray)),
                 Span::styled(
                     "CONFLICT",
-                    Style::default()
-                        .fg(Color::Red)
-                        .add_modifier(Modifier::BOLD),
+                    Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
                 ),
             ]),
             Line::from(Span::styled(
@@ -466,7 +506,7 @@ fn render_in_progress(f: &mut Frame, area: Rect, state: &mut CherryPickState) {
     let status = Paragraph::new(status_lines).block(
         Block::default()
             .title(Span::styled(
-                " 🍒 Cherry Pick — In Progress ",
+                format!(" {} Cherry Pick — In Progress ", Icon::CherryPick),
                 Style::default()
                     .fg(Color::Magenta)
                     .add_modifier(Modifier::BOLD),
@@ -543,17 +583,15 @@ pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()
 
         match state.mode {
             CherryPickMode::BranchSelect => match key.code {
-                KeyCode::Up | KeyCode::Char('k') => {
-                    if state.branch_selected > 0 {
-                        state.branch_selected -= 1;
-                        state.branch_list_state.select(Some(state.branch_selected));
-                    }
+                KeyCode::Up | KeyCode::Char('k') if state.branch_selected > 0 => {
+                    state.branch_selected -= 1;
+                    state.branch_list_state.select(Some(state.branch_selected));
                 }
-                KeyCode::Down | KeyCode::Char('j') => {
-                    if state.branch_selected + 1 < state.branches.len() {
-                        state.branch_selected += 1;
-                        state.branch_list_state.select(Some(state.branch_selected));
-                    }
+                KeyCode::Down | KeyCode::Char('j')
+                    if state.branch_selected + 1 < state.branches.len() =>
+                {
+                    state.branch_selected += 1;
+                    state.branch_list_state.select(Some(state.branch_selected));
                 }
                 KeyCode::Enter => {
                     if let Some(branch) = state.branches.get(state.branch_selected) {
@@ -566,19 +604,17 @@ pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()
             },
 
             CherryPickMode::CommitSelect => match key.code {
-                KeyCode::Up | KeyCode::Char('k') => {
-                    if state.commit_selected > 0 {
-                        state.commit_selected -= 1;
-                        state.commit_list_state.select(Some(state.commit_selected));
-                        state.update_diff();
-                    }
+                KeyCode::Up | KeyCode::Char('k') if state.commit_selected > 0 => {
+                    state.commit_selected -= 1;
+                    state.commit_list_state.select(Some(state.commit_selected));
+                    state.diff_debounce.schedule();
                 }
-                KeyCode::Down | KeyCode::Char('j') => {
-                    if state.commit_selected + 1 < state.commits.len() {
-                        state.commit_selected += 1;
-                        state.commit_list_state.select(Some(state.commit_selected));
-                        state.update_diff();
-                    }
+                KeyCode::Down | KeyCode::Char('j')
+                    if state.commit_selected + 1 < state.commits.len() =>
+                {
+                    state.commit_selected += 1;
+                    state.commit_list_state.select(Some(state.commit_selected));
+                    state.diff_debounce.schedule();
                 }
                 KeyCode::Char(' ') => {
                     // Toggle mark on current commit
@@ -1,13 +1,30 @@
-use crossterm::event::{KeyCode, KeyEvent};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::{
+    Frame,
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph, Wrap},
-    Frame,
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
 };
+use std::time::Instant;
 
+use crate::ai::client::CommitRefinement;
+use crate::app::{FollowUpAction, FollowUpItem, InputAction, Popup};
 use crate::git;
+use crate::ui::gitmoji;
+use crate::ui::icons::Icon;
+use crate::ui::spellcheck::{Misspelling, SpellChecker};
+use crate::ui::utils::{display_width, optional_height, render_paragraph_scrollbar, wrap_to_width};
+use crate::ui::zen;
+
+/// Gitmoji picker overlay in the message editor.
+#[derive(Debug, Default)]
+pub struct GitmojiPicker {
+    pub query: String,
+    pub selected: usize,
+    /// Opened by typing `:` — cancelling puts the typed text back.
+    pub from_colon: bool,
+}
 
 pub struct CommitState {
     pub message: String,
@@ -15,6 +32,29 @@ pub struct CommitState {
     pub stat_output: String,
     pub editing: bool,
     pub validation_warnings: Vec<String>,
+    /// Last AI suggestions, sent as context when regenerating.
+    pub ai_previous: Option<String>,
+    pub spellchecker: SpellChecker,
+    /// Possible misspellings in `message`, underlined in the editor.
+    pub misspellings: Vec<Misspelling>,
+    pub gitmoji_picker: Option<GitmojiPicker>,
+    /// Staged files left out of the next commit (Space in the file list).
+    /// They stay staged for a later commit.
+    pub excluded: Vec<String>,
+    /// Selected row in the staged file list, used while not editing.
+    pub file_cursor: usize,
+    /// Flags for the next commit, reset once it's made.
+    pub options: git::commit::CommitOptions,
+    /// Whether commits get a `Signed-off-by` trailer: this repository's
+    /// choice, else `[commit] sign_off`.
+    pub sign_off: bool,
+    /// Whether enabling sign-off was already offered this session.
+    pub sign_off_offered: bool,
+    /// Selected row while the options panel (Ctrl+T, or `o` when not editing)
+    /// is open.
+    pub options_panel: Option<usize>,
+    /// When the staged files shown were asked for.
+    pub loaded_at: Option<Instant>,
 }
 
 impl Default for CommitState {
@@ -25,16 +65,56 @@ impl Default for CommitState {
             stat_output: String::new(),
             editing: true,
             validation_warnings: Vec::new(),
+            ai_previous: None,
+            spellchecker: SpellChecker::default(),
+            misspellings: Vec::new(),
+            gitmoji_picker: None,
+            excluded: Vec::new(),
+            file_cursor: 0,
+            options: git::commit::CommitOptions::default(),
+            sign_off: false,
+            sign_off_offered: false,
+            options_panel: None,
+            loaded_at: None,
         }
     }
 }
 
+/// The staged files and their diffstat, read on a worker (see `tasks`).
+pub struct Loaded {
+    at: Instant,
+    staged: Option<Vec<git::FileEntry>>,
+    stat: Option<String>,
+}
+
 impl CommitState {
     pub fn refresh(&mut self) {
-        if let Ok(status) = git::status::get_status() {
-            self.staged_files = status.staged;
+        self.apply(Self::load(Instant::now()));
+    }
+
+    pub fn load(at: Instant) -> Loaded {
+        Loaded {
+            at,
+            staged: git::status::get_status().ok().map(|status| status.staged),
+            stat: git::diff::get_staged_stat().ok(),
         }
-        if let Ok(stat) = git::diff::get_staged_stat() {
+    }
+
+    /// Show what [`load`](Self::load) read, unless something newer is shown.
+    pub fn apply(&mut self, loaded: Loaded) {
+        if !crate::tasks::is_newer(&mut self.loaded_at, loaded.at) {
+            return;
+        }
+        if let Some(staged) = loaded.staged {
+            self.staged_files = staged;
+        }
+        let staged = &self.staged_files;
+        self.excluded
+            .retain(|path| staged.iter().any(|f| f.path == *path));
+        self.file_cursor = self
+            .file_cursor
+            .min(self.staged_files.len().saturating_sub(1));
+        if let Some(stat) = loaded.stat {
             self.stat_output = stat;
         }
         self.validate();
@@ -42,19 +122,22 @@ impl CommitState {
 
     pub fn validate(&mut self) {
         self.validation_warnings.clear();
+        self.misspellings.clear();
 
         if self.message.is_empty() {
             return;
         }
 
+        self.check_spelling();
+
         let lines: Vec<&str> = self.message.lines().collect();
 
         // Subject line checks
         if let Some(subject) = lines.first() {
-            if subject.len() > 72 {
+            if display_width(subject) > 72 {
                 self.validation_warnings.push(format!(
                     "Subject line is {} chars (recommended: ≤72)",
-                    subject.len()
+                    display_width(subject)
                 ));
             }
             if subject.ends_with('.') {
@@ -65,11 +148,11 @@ impl CommitState {
 
         // Body line checks
         for (i, line) in lines.iter().enumerate().skip(1) {
-            if line.len() > 80 {
+            if display_width(line) > 80 {
                 self.validation_warnings.push(format!(
                     "Line {} is {} chars (recommended: ≤80)",
                     i + 1,
-                    line.len()
+                    display_width(line)
                 ));
             }
         }
@@ -80,6 +163,129 @@ impl CommitState {
                 .push("Line 2 should be blank (separates subject from body)".to_string());
         }
     }
+
+    fn check_spelling(&mut self) {
+        let mut found = self.spellchecker.check(&self.message);
+        // Don't flag the word still being typed
+        if self.editing && !self.message.ends_with(|c: char| !c.is_alphabetic()) {
+            let last_line = self.message.lines().count().saturating_sub(1);
+            let last_len = self.message.lines().last().map_or(0, str::len);
+            found.retain(|m| !(m.line == last_line && m.end == last_len));
+        }
+        if !found.is_empty() {
+            let mut words: Vec<&str> = Vec::new();
+            for m in &found {
+                if !words.contains(&m.word.as_str()) {
+                    words.push(&m.word);
+                }
+            }
+            self.validation_warnings.push(format!(
+                "Possible misspelling: {} (Esc, then s for suggestions)",
+                words.join(", ")
+            ));
+        }
+        self.misspellings = found;
+    }
+
+    /// Whether `path` goes into the next commit.
+    pub fn is_included(&self, path: &str) -> bool {
+        !self.excluded.iter().any(|p| p == path)
+    }
+
+    /// Staged files that go into the next commit.
+    pub fn included_files(&self) -> Vec<git::FileEntry> {
+        self.staged_files
+            .iter()
+            .filter(|f| self.is_included(&f.path))
+            .cloned()
+            .collect()
+    }
+
+    /// Paths to unstage for the next commit; a left-out rename takes its
+    /// old path along so the deletion isn't committed on its own.
+    pub fn leave_out(&self) -> Vec<String> {
+        let mut paths = Vec::new();
+        for file in self
+            .staged_files
+            .iter()
+            .filter(|f| !self.is_included(&f.path))
+        {
+            paths.push(file.path.clone());
+            if let Some(original) = &file.original_path {
+                paths.push(original.clone());
+            }
+        }
+        paths
+    }
+
+    /// Put the selected file in or out of the next commit.
+    pub fn toggle_selected_file(&mut self) {
+        let Some(file) = self.staged_files.get(self.file_cursor) else {
+            return;
+        };
+        if self.is_included(&file.path) {
+            self.excluded.push(file.path.clone());
+        } else {
+            self.excluded.retain(|p| *p != file.path);
+        }
+    }
+
+    /// The message as it will be committed.
+    pub fn final_message(&self, gitmoji_prefix: bool) -> String {
+        let msg = self.message.trim();
+        if gitmoji_prefix {
+            gitmoji::auto_prefix(msg)
+        } else {
+            msg.to_string()
+        }
+    }
+
+    /// Replace every flagged occurrence of `word` with `replacement`.
+    pub fn replace_misspelling(&mut self, word: &str, replacement: &str) {
+        let mut lines: Vec<String> = self.message.lines().map(str::to_string).collect();
+        for m in self.misspellings.iter().rev().filter(|m| m.word == word) {
+            if let Some(line) = lines.get_mut(m.line) {
+                line.replace_range(m.start..m.end, replacement);
+            }
+        }
+        let trailing_newline = self.message.ends_with('\n');
+        self.message = lines.join("\n");
+        if trailing_newline {
+            self.message.push('\n');
+        }
+        self.validate();
+    }
+}
+
+/// Render a message line with possible misspellings underlined.
+fn spelled_line<'a>(
+    text: &'a str,
+    line_idx: usize,
+    misspellings: &[Misspelling],
+    color: Color,
+) -> Line<'a> {
+    let base = Style::default().fg(color);
+    let mut spans = Vec::new();
+    let mut pos = 0;
+    for m in misspellings.iter().filter(|m| m.line == line_idx) {
+        if m.start < pos || m.end > text.len() {
+            continue;
+        }
+        if m.start > pos {
+            spans.push(Span::styled(&text[pos..m.start], base));
+        }
+        spans.push(Span::styled(
+            &text[m.start..m.end],
+            Style::default()
+                .fg(Color::LightRed)
+                .add_modifier(Modifier::UNDERLINED),
+        ));
+        pos = m.end;
+    }
+    if pos < text.len() || spans.is_empty() {
+        spans.push(Span::styled(&text[pos..], base));
+    }
+    Line::from(spans)
 }
 
 pub fn render(
@@ -92,18 +298,24 @@ pub fn render(
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(3), // Title
-            Constraint::Length(6), // Staged files summary
-            Constraint::Min(8),    // Message editor
-            Constraint::Length(4), // Validation + hints
+            Constraint::Length(zen::chrome(3)),           // Title
+            Constraint::Length(optional_height(area, 6)), // Staged files summary
+            Constraint::Min(8),                           // Message editor
+            Constraint::Length(4),                        // Validation + hints
         ])
         .split(area);
 
     // Title
     let ai_indicator = if ai_loading {
-        Span::styled("  ⏳ AI generating...", Style::default().fg(Color::Yellow))
+        Span::styled(
+            format!("  {} AI generating...", Icon::Loading),
+            Style::default().fg(Color::Yellow),
+        )
     } else if ai_available {
-        Span::styled("  🤖 AI ready", Style::default().fg(Color::Green))
+        Span::styled(
+            format!("  {} AI ready", Icon::Ai),
+            Style::default().fg(Color::Green),
+        )
     } else {
         Span::raw("")
     };
@@ -120,6 +332,11 @@ pub fn render(
             format!("  ({} files staged)", state.staged_files.len()),
             Style::default().fg(Color::DarkGray),
         ),
+        if state.sign_off {
+            Span::styled("  ✍ signed off", Style::default().fg(Color::Green))
+        } else {
+            Span::raw("")
+        },
         ai_indicator,
     ]))
     .block(
@@ -130,19 +347,7 @@ pub fn render(
     f.render_widget(title, chunks[0]);
 
     // Staged files
-    let stat_paragraph = Paragraph::new(state.stat_output.as_str())
-        .block(
-            Block::default()
-                .title(Span::styled(
-                    " Changes to commit ",
-                    Style::default().fg(Color::Green),
-                ))
-                .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::DarkGray)),
-        )
-        .style(Style::default().fg(Color::DarkGray))
-        .wrap(Wrap { trim: false });
-    f.render_widget(stat_paragraph, chunks[1]);
+    render_staged_files(f, chunks[1], state);
 
     // Message editor
     let editor_border_color = if state.editing {
@@ -155,19 +360,19 @@ pub fn render(
         .lines()
         .enumerate()
         .map(|(i, l)| {
-            let color = if (i == 0 && l.len() > 72) || l.len() > 80 {
+            let color = if (i == 0 && display_width(l) > 72) || display_width(l) > 80 {
                 Color::Yellow
             } else {
                 Color::White
             };
-            Line::from(Span::styled(l, Style::default().fg(color)))
+            spelled_line(l, i, &state.misspellings, color)
         })
         .collect();
 
     let lines = if lines.is_empty() {
         if ai_loading {
             vec![Line::from(Span::styled(
-                "⏳ AI is generating a commit message...",
+                format!("{} AI is generating a commit message...", Icon::Loading),
                 Style::default().fg(Color::Yellow),
             ))]
         } else {
@@ -193,6 +398,15 @@ pub fn render(
         .wrap(Wrap { trim: false });
     f.render_widget(editor, chunks[2]);
 
+    if let Some(ref picker) = state.gitmoji_picker {
+        render_gitmoji_picker(f, chunks[2], picker);
+        return;
+    }
+    if let Some(selected) = state.options_panel {
+        render_options_panel(f, chunks[2], state, selected);
+        return;
+    }
+
     // Show cursor position if editing
     if state.editing {
         let msg_lines: Vec<&str> = state.message.lines().collect();
@@ -201,27 +415,22 @@ pub fn render(
 
         // Account for line wrapping: count how many visual lines come before the cursor
         let mut visual_y: u16 = 0;
-        for (i, line) in msg_lines.iter().enumerate() {
-            if i == msg_lines.len() - 1 {
-                break; // Don't count the current line
-            }
-            // Each line takes ceil(len / width) visual lines, minimum 1
-            let line_len = line.len().max(1);
-            if editor_inner_width > 0 {
-                visual_y += line_len.div_ceil(editor_inner_width) as u16;
-            } else {
-                visual_y += 1;
-            }
+        for line in msg_lines.iter().take(msg_lines.len().saturating_sub(1)) {
+            visual_y += wrap_to_width(line, editor_inner_width).len().max(1) as u16;
         }
 
-        // Cursor position within the last line, accounting for wrapping
-        let cursor_x = if editor_inner_width > 0 {
-            last_line.len() % editor_inner_width
+        // Cursor sits after the last wrapped segment (plus any trailing spaces just typed)
+        let wrapped = wrap_to_width(last_line, editor_inner_width);
+        let trailing = last_line.len() - last_line.trim_end().len();
+        let mut cursor_x = if last_line.trim().is_empty() {
+            display_width(last_line)
         } else {
-            last_line.len()
+            wrapped.last().map_or(0, |l| display_width(l)) + trailing
         };
-        if editor_inner_width > 0 {
-            visual_y += (last_line.len() / editor_inner_width) as u16;
+        visual_y += wrapped.len().saturating_sub(1) as u16;
+        if editor_inner_width > 0 && cursor_x >= editor_inner_width {
+            visual_y += (cursor_x / editor_inner_width) as u16;
+            cursor_x %= editor_inner_width;
         }
 
         f.set_cursor_position((
@@ -233,9 +442,25 @@ pub fn render(
     // Validation & hints
     let mut hint_lines = Vec::new();
 
+    if state.options.is_set() {
+        let color = if state.options.no_verify {
+            Color::Red
+        } else {
+            Color::Yellow
+        };
+        hint_lines.push(Line::from(Span::styled(
+            format!(
+                "  {} Committing with {}",
+                Icon::Warning,
+                state.options.flags().join(" ")
+            ),
+            Style::default().fg(color),
+        )));
+    }
+
     for w in &state.validation_warnings {
         hint_lines.push(Line::from(Span::styled(
-            format!("  ⚠ {}", w),
+            format!("  {} {}", Icon::Warning, w),
             Style::default().fg(Color::Yellow),
         )));
     }
@@ -249,8 +474,13 @@ pub fn render(
         Span::raw(" Cancel  "),
         Span::styled("Ctrl+A", Style::default().fg(Color::Cyan)),
         Span::raw(" Amend  "),
+        Span::styled("Ctrl+T", Style::default().fg(Color::Cyan)),
+        Span::raw(" Options  "),
         if ai_loading {
-            Span::styled("⏳ AI generating...", Style::default().fg(Color::Yellow))
+            Span::styled(
+                format!("{} AI generating...", Icon::Loading),
+                Style::default().fg(Color::Yellow),
+            )
         } else if ai_available {
             Span::styled("G", Style::default().fg(Color::Magenta))
         } else {
@@ -263,6 +493,17 @@ pub fn render(
         },
     ]));
 
+    if state.ai_previous.is_some() && !state.editing && !ai_loading {
+        hint_lines.push(Line::from(vec![
+            Span::styled(" r", Style::default().fg(Color::Magenta)),
+            Span::raw(" Try again  "),
+            Span::styled("-/+", Style::default().fg(Color::Magenta)),
+            Span::raw(" Shorter/Longer  "),
+            Span::styled("f", Style::default().fg(Color::Magenta)),
+            Span::raw(" Refine with instruction"),
+        ]));
+    }
+
     let hints = Paragraph::new(hint_lines).block(
         Block::default()
             .borders(Borders::TOP)
@@ -271,7 +512,420 @@ pub fn render(
     f.render_widget(hints, chunks[3]);
 }
 
+/// The staged files, each with a checkbox for whether it goes into this
+/// commit and its `--stat` line.
+fn render_staged_files(f: &mut Frame, area: Rect, state: &CommitState) {
+    let selecting = !state.editing;
+    let lines: Vec<Line> = state
+        .staged_files
+        .iter()
+        .enumerate()
+        .map(|(i, file)| {
+            let included = state.is_included(&file.path);
+            let cursor = if selecting && i == state.file_cursor {
+                "▶"
+            } else {
+                " "
+            };
+            let path_style = if !included {
+                Style::default()
+                    .fg(Color::DarkGray)
+                    .add_modifier(Modifier::CROSSED_OUT)
+            } else if selecting && i == state.file_cursor {
+                Style::default()
+                    .fg(Color::White)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::Gray)
+            };
+            Line::from(vec![
+                Span::styled(
+                    format!("{}{} ", cursor, if included { "[x]" } else { "[ ]" }),
+                    Style::default().fg(if included {
+                        Color::Green
+                    } else {
+                        Color::DarkGray
+                    }),
+                ),
+                Span::styled(file.path.clone(), path_style),
+                Span::styled(
+                    stat_for(&state.stat_output, &file.path)
+                        .map(|stat| format!("  {}", stat))
+                        .unwrap_or_default(),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ])
+        })
+        .collect();
+
+    let included = state.staged_files.len() - state.excluded.len();
+    let title = if state.excluded.is_empty() {
+        " Changes to commit ".to_string()
+    } else {
+        format!(
+            " Changes to commit ({} of {} files) ",
+            included,
+            state.staged_files.len()
+        )
+    };
+    // Keep the cursor in view
+    let visible = area.height.saturating_sub(2) as usize;
+    let scroll = if selecting {
+        (state.file_cursor + 1).saturating_sub(visible)
+    } else {
+        0
+    };
+    let list = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .title(Span::styled(title, Style::default().fg(Color::Green)))
+                .title_bottom(if selecting && !state.staged_files.is_empty() {
+                    Line::from(Span::styled(
+                        " Space include/leave out · j/k move ",
+                        Style::default().fg(Color::DarkGray),
+                    ))
+                } else {
+                    Line::from("")
+                })
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(if selecting {
+                    Color::Green
+                } else {
+                    Color::DarkGray
+                })),
+        )
+        .scroll((scroll as u16, 0));
+    f.render_widget(&list, area);
+    render_paragraph_scrollbar(f, area, &list, scroll as u16);
+}
+
+/// The `| 3 ++-` part of `path`'s line in `git diff --stat` output.
+fn stat_for<'a>(stat_output: &'a str, path: &str) -> Option<&'a str> {
+    stat_output.lines().find_map(|line| {
+        let (name, stat) = line.split_once('|')?;
+        (name.trim() == path).then(|| stat.trim())
+    })
+}
+
+/// Rows of matches shown in the gitmoji picker.
+const GITMOJI_PICKER_ROWS: usize = 6;
+
+fn render_gitmoji_picker(f: &mut Frame, editor: Rect, picker: &GitmojiPicker) {
+    let height = (GITMOJI_PICKER_ROWS as u16 + 3).min(editor.height);
+    let area = Rect {
+        x: editor.x + 1,
+        y: editor.y + editor.height - height,
+        width: editor.width.saturating_sub(2).min(64),
+        height,
+    };
+    f.render_widget(Clear, area);
+
+    let matches = gitmoji::search(&picker.query);
+    let first = picker.selected.saturating_sub(GITMOJI_PICKER_ROWS - 1);
+    let mut lines = vec![Line::from(vec![
+        Span::styled(" :", Style::default().fg(Color::Magenta)),
+        Span::styled(picker.query.as_str(), Style::default().fg(Color::White)),
+        Span::styled(
+            format!("   {} match(es)", matches.len()),
+            Style::default().fg(Color::DarkGray),
+        ),
+    ])];
+    for (i, g) in matches
+        .iter()
+        .enumerate()
+        .skip(first)
+        .take(GITMOJI_PICKER_ROWS)
+    {
+        let style = if i == picker.selected {
+            Style::default()
+                .fg(Color::White)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::Gray)
+        };
+        let prefix = if i == picker.selected { "▶ " } else { "  " };
+        lines.push(Line::from(vec![
+            Span::styled(format!("{}{} ", prefix, g.emoji), style),
+            Span::styled(format!(":{}: ", g.code), Style::default().fg(Color::Cyan)),
+            Span::styled(g.description, Style::default().fg(Color::DarkGray)),
+        ]));
+    }
+
+    let picker = Paragraph::new(lines).block(
+        Block::default()
+            .title(Span::styled(
+                " Gitmoji — Enter insert · ↑/↓ · Esc cancel ",
+                Style::default().fg(Color::Magenta),
+            ))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Magenta)),
+    );
+    f.render_widget(picker, area);
+}
+
+/// Rows of the options panel, in order.
+const OPTION_ROWS: usize = 5;
+
+fn render_options_panel(f: &mut Frame, editor: Rect, state: &CommitState, selected: usize) {
+    let options = &state.options;
+    let height = (OPTION_ROWS as u16 + 5).min(editor.height);
+    let area = Rect {
+        x: editor.x + 1,
+        y: editor.y + editor.height - height,
+        width: editor.width.saturating_sub(2).min(64),
+        height,
+    };
+    f.render_widget(Clear, area);
+
+    let check = |on: bool| if on { "[x]" } else { "[ ]" };
+    let value = |v: &Option<String>| v.clone().unwrap_or_else(|| "(default)".to_string());
+    let rows = [
+        format!("{} Skip hooks (--no-verify)", check(options.no_verify)),
+        format!("{} Allow empty commit", check(options.allow_empty)),
+        format!("Author: {}", value(&options.author)),
+        format!("Date:   {}", value(&options.date)),
+        format!(
+            "{} Sign off (Signed-off-by, kept for this repo)",
+            check(state.sign_off)
+        ),
+    ];
+    let mut lines: Vec<Line> = rows
+        .into_iter()
+        .enumerate()
+        .map(|(i, row)| {
+            let style = if i == selected {
+                Style::default()
+                    .fg(Color::White)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::Gray)
+            };
+            let prefix = if i == selected { "▶ " } else { "  " };
+            Line::from(Span::styled(format!("{}{}", prefix, row), style))
+        })
+        .collect();
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        if options.no_verify {
+            format!(
+                "  {} Hooks won't run: no lint, tests or message checks",
+                Icon::Warning
+            )
+        } else {
+            "  Apart from sign-off, applies to the next commit only".to_string()
+        },
+        Style::default().fg(if options.no_verify {
+            Color::Red
+        } else {
+            Color::DarkGray
+        }),
+    )));
+
+    let panel = Paragraph::new(lines).block(
+        Block::default()
+            .title(Span::styled(
+                " Commit Options — Space toggle/edit · x clear · Esc close ",
+                Style::default().fg(Color::Yellow),
+            ))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Yellow)),
+    );
+    f.render_widget(panel, area);
+}
+
+fn handle_options_key(app: &mut crate::app::App, key: KeyEvent) {
+    let Some(selected) = app.commit_state.options_panel else {
+        return;
+    };
+    let options = &mut app.commit_state.options;
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('o') | KeyCode::Char('t') => {
+            app.commit_state.options_panel = None;
+        }
+        KeyCode::Up | KeyCode::Char('k') => {
+            app.commit_state.options_panel = Some(selected.saturating_sub(1));
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            app.commit_state.options_panel = Some((selected + 1).min(OPTION_ROWS - 1));
+        }
+        KeyCode::Char('x') | KeyCode::Backspace | KeyCode::Delete => match selected {
+            0 => options.no_verify = false,
+            1 => options.allow_empty = false,
+            2 => options.author = None,
+            3 => options.date = None,
+            _ => set_sign_off(app, false),
+        },
+        KeyCode::Char(' ') | KeyCode::Enter => match selected {
+            0 => {
+                options.no_verify = !options.no_verify;
+                if options.no_verify {
+                    app.set_status(format!(
+                        "{} Hooks will be skipped for the next commit",
+                        Icon::Warning
+                    ));
+                }
+            }
+            1 => options.allow_empty = !options.allow_empty,
+            2 => {
+                app.popup = Popup::Input {
+                    title: "Commit Author".to_string(),
+                    prompt: "Name <email> (empty for your own identity):".to_string(),
+                    value: options.author.clone().unwrap_or_default(),
+                    on_submit: InputAction::CommitAuthor,
+                };
+            }
+            3 => {
+                app.popup = Popup::Input {
+                    title: "Commit Date".to_string(),
+                    prompt: "e.g. 2024-03-01 12:00 or 2 days ago (empty for now):".to_string(),
+                    value: options.date.clone().unwrap_or_default(),
+                    on_submit: InputAction::CommitDate,
+                };
+            }
+            _ => {
+                let on = !app.commit_state.sign_off;
+                set_sign_off(app, on);
+            }
+        },
+        _ => {}
+    }
+}
+
+/// Work out whether commits are signed off; call before committing.
+pub fn load_sign_off(app: &mut crate::app::App) {
+    app.commit_state.sign_off = git::commit::repo_sign_off().unwrap_or(app.config.commit.sign_off);
+}
+
+/// Sign off this repository's commits or stop, remembered in its git config.
+pub fn set_sign_off(app: &mut crate::app::App, on: bool) {
+    match git::commit::set_repo_sign_off(on) {
+        Ok(()) => {
+            app.commit_state.sign_off = on;
+            app.set_status(if on {
+                "✓ Commits in this repository will be signed off (Signed-off-by)"
+            } else {
+                "Commits in this repository won't be signed off"
+            });
+        }
+        Err(e) => app.set_status(format!("Couldn't save the sign-off setting: {}", e)),
+    }
+}
+
+/// The first time the Commit view opens on a repository that asks for DCO
+/// sign-off, offer to sign off its commits. Nothing is asked once the
+/// repository has a setting or sign-off is on everywhere.
+pub fn offer_sign_off(app: &mut crate::app::App) {
+    load_sign_off(app);
+    if app.commit_state.sign_off
+        || app.commit_state.sign_off_offered
+        || git::commit::repo_sign_off().is_some()
+    {
+        return;
+    }
+    app.commit_state.sign_off_offered = true;
+    let Some(why) = git::contributing::check(&[]).sign_off else {
+        return;
+    };
+    app.popup = Popup::FollowUp {
+        title: "DCO sign-off".to_string(),
+        context: format!(
+            "This project asks for a Signed-off-by trailer on every commit: {}",
+            why
+        ),
+        suggestions: vec![
+            FollowUpItem {
+                label: "Sign off commits in this repository".to_string(),
+                description: "Like git commit -s; change it later in the options (Ctrl+T)"
+                    .to_string(),
+                action: FollowUpAction::SetRepoSignOff(true),
+            },
+            FollowUpItem {
+                label: "Don't sign off here".to_string(),
+                description: "Don't ask again for this repository".to_string(),
+                action: FollowUpAction::SetRepoSignOff(false),
+            },
+        ],
+        selected: 0,
+    };
+}
+
+fn open_gitmoji_picker(state: &mut CommitState, from_colon: bool) {
+    state.editing = true;
+    state.gitmoji_picker = Some(GitmojiPicker {
+        from_colon,
+        ..GitmojiPicker::default()
+    });
+}
+
+fn handle_gitmoji_key(state: &mut CommitState, key: KeyEvent) {
+    let Some(mut picker) = state.gitmoji_picker.take() else {
+        return;
+    };
+    let matches = gitmoji::search(&picker.query);
+
+    // Closing without a pick restores what was typed after ':'
+    let cancel = |state: &mut CommitState, picker: &GitmojiPicker, extra: Option<char>| {
+        if picker.from_colon {
+            state.message.push(':');
+            state.message.push_str(&picker.query);
+        }
+        if let Some(c) = extra {
+            state.message.push(c);
+        }
+        state.validate();
+    };
+
+    match key.code {
+        KeyCode::Esc => cancel(state, &picker, None),
+        KeyCode::Enter | KeyCode::Tab | KeyCode::Char(':') => match matches.get(picker.selected) {
+            Some(g) => {
+                state.message.push_str(g.emoji);
+                state.message.push(' ');
+                state.validate();
+            }
+            None => cancel(state, &picker, None),
+        },
+        KeyCode::Up => {
+            picker.selected = picker.selected.saturating_sub(1);
+            state.gitmoji_picker = Some(picker);
+        }
+        KeyCode::Down => {
+            if picker.selected + 1 < matches.len() {
+                picker.selected += 1;
+            }
+            state.gitmoji_picker = Some(picker);
+        }
+        KeyCode::Backspace => {
+            // Backspace on an empty query closes the picker (dropping the ':')
+            if picker.query.pop().is_some() {
+                picker.selected = 0;
+                state.gitmoji_picker = Some(picker);
+            }
+        }
+        KeyCode::Char(c) if c.is_whitespace() => cancel(state, &picker, Some(c)),
+        KeyCode::Char(c) => {
+            picker.query.push(c);
+            picker.selected = 0;
+            state.gitmoji_picker = Some(picker);
+        }
+        _ => state.gitmoji_picker = Some(picker),
+    }
+}
+
 pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()> {
+    if app.commit_state.gitmoji_picker.is_some() {
+        handle_gitmoji_key(&mut app.commit_state, key);
+        return Ok(());
+    }
+    if app.commit_state.options_panel.is_some() {
+        handle_options_key(app, key);
+        return Ok(());
+    }
+    if key.code == KeyCode::Char('t') && key.modifiers.contains(KeyModifiers::CONTROL) {
+        app.commit_state.options_panel = Some(0);
+        return Ok(());
+    }
+
     if !app.commit_state.editing {
         match key.code {
             KeyCode::Char('i') | KeyCode::Enter => {
@@ -285,6 +939,33 @@ pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()
                     app.start_ai_suggest();
                 }
             }
+            // Iterate on the last AI suggestion
+            KeyCode::Char('r') if app.commit_state.ai_previous.is_some() => {
+                app.start_ai_refine(CommitRefinement::Retry);
+            }
+            KeyCode::Char('-') if app.commit_state.ai_previous.is_some() => {
+                app.start_ai_refine(CommitRefinement::Shorter);
+            }
+            KeyCode::Char('+') if app.commit_state.ai_previous.is_some() => {
+                app.start_ai_refine(CommitRefinement::Longer);
+            }
+            KeyCode::Char('f') if app.commit_state.ai_previous.is_some() => {
+                app.execute_follow_up(FollowUpAction::PromptCommitRefinement);
+            }
+            KeyCode::Char('s') => open_spelling_popup(app),
+            KeyCode::Char('e') => open_gitmoji_picker(&mut app.commit_state, false),
+            KeyCode::Char('o') => app.commit_state.options_panel = Some(0),
+            // Choose which staged files go into this commit
+            KeyCode::Char('j') | KeyCode::Down => {
+                let state = &mut app.commit_state;
+                if state.file_cursor + 1 < state.staged_files.len() {
+                    state.file_cursor += 1;
+                }
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                app.commit_state.file_cursor = app.commit_state.file_cursor.saturating_sub(1);
+            }
+            KeyCode::Char(' ') => app.commit_state.toggle_selected_file(),
             _ => {}
         }
         return Ok(());
@@ -306,24 +987,36 @@ pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()
         return Ok(());
     }
 
+    let gitmoji_on_colon = app.config.commit.gitmoji;
     let state = &mut app.commit_state;
 
     match key.code {
+        KeyCode::Char('e')
+            if key
+                .modifiers
+                .contains(crossterm::event::KeyModifiers::CONTROL) =>
+        {
+            open_gitmoji_picker(state, false);
+        }
+        // ':' at the start of a word opens the picker when `[commit] gitmoji` is on
+        KeyCode::Char(':')
+            if gitmoji_on_colon
+                && (state.message.is_empty() || state.message.ends_with(char::is_whitespace)) =>
+        {
+            open_gitmoji_picker(state, true);
+        }
         KeyCode::Esc => {
             if state.message.is_empty() {
-                // Go back to dashboard
-                app.view = crate::app::View::Dashboard;
-                app.dashboard_state.refresh();
+                app.go_back();
             } else {
                 state.editing = false;
             }
         }
-        KeyCode::Enter => {
+        KeyCode::Enter
             // Enter commits if message is non-empty
-            if !state.message.trim().is_empty() {
+            if !state.message.trim().is_empty() => {
                 do_commit(app)?;
             }
-        }
         KeyCode::Tab => {
             // Tab adds a newline for multi-line commit messages
             state.message.push('\n');
@@ -354,25 +1047,119 @@ pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()
     Ok(())
 }
 
+/// Number of flagged words offered in the spelling popup, and suggestions per word.
+const SPELLING_POPUP_WORDS: usize = 3;
+const SPELLING_SUGGESTIONS: usize = 3;
+
+/// Show corrections for the flagged words, plus "add to dictionary".
+fn open_spelling_popup(app: &mut crate::app::App) {
+    let state = &app.commit_state;
+    let mut words: Vec<&str> = Vec::new();
+    for m in &state.misspellings {
+        if !words.contains(&m.word.as_str()) {
+            words.push(&m.word);
+        }
+    }
+    if words.is_empty() {
+        app.set_status("✓ No spelling issues found");
+        return;
+    }
+
+    let mut suggestions = Vec::new();
+    for word in words.iter().take(SPELLING_POPUP_WORDS) {
+        for fix in state.spellchecker.suggestions(word, SPELLING_SUGGESTIONS) {
+            suggestions.push(FollowUpItem {
+                label: format!("{} → {}", word, fix),
+                description: format!("Replace '{}' with '{}'", word, fix),
+                action: FollowUpAction::FixSpelling(word.to_string(), fix),
+            });
+        }
+        suggestions.push(FollowUpItem {
+            label: format!("Add '{}' to dictionary", word),
+            description: "Accept this word from now on ([spellcheck] words)".to_string(),
+            action: FollowUpAction::AddSpellingWord(word.to_string()),
+        });
+    }
+
+    app.popup = Popup::FollowUp {
+        title: "Spelling".to_string(),
+        context: format!("{} possible misspelling(s)", words.len()),
+        suggestions,
+        selected: 0,
+    };
+}
+
 fn do_commit(app: &mut crate::app::App) -> anyhow::Result<()> {
+    commit_with_checks(app, true)
+}
+
+/// Commit after the user chose to keep leftover conflict markers.
+pub fn commit_ignoring_conflict_markers(app: &mut crate::app::App) -> anyhow::Result<()> {
+    commit_with_checks(app, false)
+}
+
+/// Offer to jump to each staged file with leftover conflict markers, or commit anyway.
+fn warn_conflict_markers(app: &mut crate::app::App, hits: &[git::merge::MarkerHit]) {
+    let mut suggestions: Vec<FollowUpItem> = Vec::new();
+    for hit in hits {
+        if let Some(item) = suggestions.iter_mut().find(
+            |s| matches!(&s.action, FollowUpAction::JumpToConflictMarker(p) if *p == hit.path),
+        ) {
+            item.description.push_str(&format!(", {}", hit.line));
+            continue;
+        }
+        suggestions.push(FollowUpItem {
+            label: format!("Jump to {}", hit.path),
+            description: format!("Marker on line {}", hit.line),
+            action: FollowUpAction::JumpToConflictMarker(hit.path.clone()),
+        });
+    }
+    suggestions.push(FollowUpItem {
+        label: "Commit anyway".to_string(),
+        description: "The markers are intentional (e.g. test fixtures)".to_string(),
+        action: FollowUpAction::CommitIgnoringConflictMarkers,
+    });
+    app.popup = Popup::FollowUp {
+        title: format!(
+            "{} Staged files still contain conflict markers",
+            Icon::Warning
+        ),
+        context: String::new(),
+        suggestions,
+        selected: 0,
+    };
+}
+
+fn commit_with_checks(app: &mut crate::app::App, check_markers: bool) -> anyhow::Result<()> {
     if app.commit_state.message.trim().is_empty() {
         app.set_status("Commit message cannot be empty");
         return Ok(());
     }
 
-    if app.commit_state.staged_files.is_empty() {
+    if app.commit_state.staged_files.is_empty() && !app.commit_state.options.allow_empty {
         app.set_status("No files staged for commit");
         return Ok(());
     }
+    let included = app.commit_state.included_files();
+    if included.is_empty() && !app.commit_state.options.allow_empty {
+        app.set_status("Every staged file is left out — select at least one with Space");
+        return Ok(());
+    }
+
+    // ── Leftover conflict markers ───────────────────────────────────
+    if check_markers {
+        let hits = git::merge::staged_conflict_markers();
+        if !hits.is_empty() {
+            warn_conflict_markers(app, &hits);
+            return Ok(());
+        }
+    }
 
     // ── Secret scanning before commit ───────────────────────────────
     if app.config.secrets.enabled {
         let rules = git::secrets::default_rules();
-        let findings = git::secrets::scan_staged_files(
-            &app.commit_state.staged_files,
-            &rules,
-            &app.config.secrets.allowlist,
-        );
+        let findings =
+            git::secrets::scan_staged_files(&included, &rules, &app.config.secrets.allowlist);
 
         if !findings.is_empty() {
             app.popup = crate::app::Popup::SecretWarning {
@@ -384,19 +1171,40 @@ fn do_commit(app: &mut crate::app::App) -> anyhow::Result<()> {
         }
     }
 
-    let msg = app.commit_state.message.trim().to_string();
-    match git::run_git(&["commit", "-m", &msg]) {
+    let msg = app
+        .commit_state
+        .final_message(app.config.commit.gitmoji_prefix);
+    load_sign_off(app);
+    let options = git::commit::CommitOptions {
+        sign_off: app.commit_state.sign_off,
+        ..app.commit_state.options.clone()
+    };
+    let leave_out = app.commit_state.leave_out();
+    match git::commit::commit(&msg, &options, &leave_out) {
         Ok(output) => {
+            app.output_pane.show_captured("commit", &output, true);
+            let left = app.commit_state.excluded.len();
             app.set_status(format!(
-                "✓ {}",
-                output.lines().next().unwrap_or("Committed")
+                "✓ {}{}",
+                output.lines().next().unwrap_or("Committed"),
+                if left > 0 {
+                    format!(" — {} file(s) left staged", left)
+                } else {
+                    String::new()
+                }
             ));
             app.commit_state.message.clear();
+            app.commit_state.excluded.clear();
+            app.commit_state.ai_previous = None;
+            app.commit_state.options = git::commit::CommitOptions::default();
             app.commit_state.editing = true;
-            app.view = crate::app::View::Dashboard;
+            app.open_view(crate::app::View::Dashboard);
             app.dashboard_state.refresh();
         }
         Err(e) => {
+            // Hook failures end up in stderr — keep the full text in the drawer
+            app.output_pane
+                .show_captured("commit", &e.to_string(), false);
             app.set_status(format!("Commit failed: {}", e));
         }
     }
@@ -463,6 +1271,36 @@ mod tests {
         assert!(warnings[0].contains("81 chars"));
     }
 
+    #[test]
+    fn test_leave_out_takes_renames_along() {
+        let entry = |path: &str, original: Option<&str>| git::FileEntry {
+            status: git::FileStatus::Renamed,
+            path: path.to_string(),
+            original_path: original.map(str::to_string),
+        };
+        let mut state = CommitState {
+            staged_files: vec![entry("a.rs", None), entry("new.rs", Some("old.rs"))],
+            ..CommitState::default()
+        };
+        assert!(state.leave_out().is_empty());
+
+        state.file_cursor = 1;
+        state.toggle_selected_file();
+        assert_eq!(state.leave_out(), ["new.rs", "old.rs"]);
+        assert_eq!(state.included_files().len(), 1);
+
+        state.toggle_selected_file();
+        assert!(state.excluded.is_empty());
+    }
+
+    #[test]
+    fn test_stat_for() {
+        let stat = " src/a.rs | 3 ++-\n b.txt    | 1 +\n 2 files changed, 3 insertions(+), 1 deletion(-)\n";
+        assert_eq!(stat_for(stat, "b.txt"), Some("1 +"));
+        assert_eq!(stat_for(stat, "src/a.rs"), Some("3 ++-"));
+        assert_eq!(stat_for(stat, "c.txt"), None);
+    }
+
     #[test]
     fn test_validate_multiple_warnings() {
         // Subject ends with period AND body line too long
@@ -1,13 +1,28 @@
 use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::{
+    Frame,
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, List, ListItem, Paragraph},
-    Frame,
 };
 
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
 use crate::git;
+use crate::git::milestone::MilestoneProgress;
+use crate::jobs::Priority;
+use crate::ui::datetime;
+use crate::ui::icons::Icon;
+use crate::ui::utils::{is_narrow, optional_height, render_paragraph_scrollbar, truncate_to_width};
+use crate::ui::zen;
+
+/// How often the milestone widget refetches its counts.
+const MILESTONE_INTERVAL: Duration = Duration::from_secs(300);
+
+/// Submodules listed before the panel stops growing.
+const SUBMODULE_ROWS: usize = 4;
 
 #[derive(Debug, Clone, Copy, PartialEq, Default)]
 pub enum DashboardFocus {
@@ -39,11 +54,21 @@ pub struct DashboardState {
     pub display_commit: usize,
     pub display_ahead: u32,
     pub display_behind: u32,
+    /// Progress of the tracked GitHub milestone, once fetched.
+    pub milestone: Option<MilestoneProgress>,
+    /// The repository's submodules; the panel is hidden without any.
+    pub submodules: Vec<git::submodule::Submodule>,
+    milestone_rx: Option<mpsc::Receiver<Result<Option<MilestoneProgress>, String>>>,
+    milestone_fetched: Option<Instant>,
+    /// Whether repository data has been loaded; until then placeholders show.
+    pub loaded: bool,
+    /// When the data shown was asked for.
+    pub loaded_at: Option<Instant>,
 }
 
 impl Default for DashboardState {
     fn default() -> Self {
-        let mut state = Self {
+        Self {
             branch: String::new(),
             upstream: None,
             ahead: 0,
@@ -66,15 +91,46 @@ impl Default for DashboardState {
             display_commit: 0,
             display_ahead: 0,
             display_behind: 0,
-        };
-        state.refresh();
-        state
+            milestone: None,
+            submodules: Vec::new(),
+            milestone_rx: None,
+            milestone_fetched: None,
+            loaded: false,
+            loaded_at: None,
+        }
     }
 }
 
+/// Status and recent history, read on a worker (see `tasks`).
+pub struct Loaded {
+    at: Instant,
+    status: anyhow::Result<git::status::RepoStatus>,
+    recent_commits: Vec<git::CommitEntry>,
+    commit_count: usize,
+    submodules: Vec<git::submodule::Submodule>,
+}
+
 impl DashboardState {
     pub fn refresh(&mut self) {
-        match git::status::get_status() {
+        self.apply(Self::load(Instant::now()));
+    }
+
+    pub fn load(at: Instant) -> Loaded {
+        Loaded {
+            at,
+            status: git::status::get_status(),
+            recent_commits: git::log::get_recent_commits(5).unwrap_or_default(),
+            commit_count: git::log::commit_count().unwrap_or(0),
+            submodules: git::submodule::list().unwrap_or_default(),
+        }
+    }
+
+    /// Show what [`load`](Self::load) read, unless something newer is shown.
+    pub fn apply(&mut self, loaded: Loaded) {
+        if !crate::tasks::is_newer(&mut self.loaded_at, loaded.at) {
+            return;
+        }
+        match loaded.status {
             Ok(status) => {
                 self.branch = status.branch.clone();
                 self.upstream = status.upstream.clone();
@@ -93,12 +149,9 @@ impl DashboardState {
             }
         }
 
-        match git::log::get_recent_commits(5) {
-            Ok(commits) => self.recent_commits = commits,
-            Err(_) => self.recent_commits = Vec::new(),
-        }
-
-        self.commit_count = git::log::commit_count().unwrap_or(0);
+        self.recent_commits = loaded.recent_commits;
+        self.commit_count = loaded.commit_count;
+        self.submodules = loaded.submodules;
 
         self.display_staged = self.staged_count;
         self.display_unstaged = self.unstaged_count;
@@ -108,6 +161,7 @@ impl DashboardState {
         self.display_commit = self.commit_count;
         self.display_ahead = self.ahead;
         self.display_behind = self.behind;
+        self.loaded = true;
     }
 
     pub fn tick_animations(&mut self) {
@@ -141,11 +195,175 @@ impl DashboardState {
     }
 }
 
+/// Placeholder for a value that hasn't loaded yet.
+fn skeleton(width: usize) -> Span<'static> {
+    Span::styled("░".repeat(width), Style::default().fg(Color::DarkGray))
+}
+
+/// Milestone title and due date above a progress bar and per-kind counts.
+fn render_milestone(f: &mut Frame, area: Rect, m: &MilestoneProgress, focus: DashboardFocus) {
+    let mut title = format!(" {} ", m.title);
+    if let Some(due) = &m.due_on {
+        title = format!(" {} · due {} ", m.title, due.get(..10).unwrap_or(due));
+    }
+
+    let summary = format!(
+        " {}/{} · Issues {} open {} closed · PRs {} open {} closed",
+        m.closed(),
+        m.total(),
+        m.open_issues,
+        m.closed_issues,
+        m.open_prs,
+        m.closed_prs
+    );
+    let inner_width = area.width.saturating_sub(2) as usize;
+    // The bar shrinks with the panel but keeps at least 12 cells; the counts
+    // are truncated instead
+    let bar_width = inner_width
+        .saturating_sub(summary.chars().count() + 7)
+        .clamp(12, 30)
+        .min(inner_width.saturating_sub(7));
+    let filled = (m.ratio() * bar_width as f64).round() as usize;
+    let color = if m.total() > 0 && m.closed() == m.total() {
+        Color::Green
+    } else {
+        Color::Cyan
+    };
+
+    let line = Line::from(vec![
+        Span::raw("  "),
+        Span::styled("█".repeat(filled), Style::default().fg(color)),
+        Span::styled(
+            "░".repeat(bar_width - filled),
+            Style::default().fg(Color::DarkGray),
+        ),
+        Span::styled(
+            format!(" {:>3.0}%", m.ratio() * 100.0),
+            Style::default().fg(color).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            truncate_to_width(&summary, inner_width.saturating_sub(bar_width + 7)),
+            Style::default().fg(Color::Gray),
+        ),
+    ]);
+
+    let widget = Paragraph::new(line).block(
+        Block::default()
+            .title(Span::styled(
+                format!(" Milestone:{}", title),
+                Style::default().fg(Color::White),
+            ))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(if focus == DashboardFocus::Left {
+                Color::Cyan
+            } else {
+                Color::DarkGray
+            })),
+    );
+    f.render_widget(widget, area);
+}
+
+/// One row per submodule, those needing attention first once they don't all fit.
+fn render_submodules(
+    f: &mut Frame,
+    area: Rect,
+    submodules: &[git::submodule::Submodule],
+    focus: DashboardFocus,
+) {
+    let mut shown: Vec<&git::submodule::Submodule> = submodules.iter().collect();
+    if shown.len() > SUBMODULE_ROWS {
+        shown.sort_by_key(|s| !s.needs_attention());
+        shown.truncate(SUBMODULE_ROWS);
+    }
+    let width = shown.iter().map(|s| s.path.len()).max().unwrap_or(0);
+    let rows: Vec<ListItem> = shown
+        .into_iter()
+        .map(|s| {
+            let color = if !s.initialized {
+                Color::DarkGray
+            } else if s.needs_attention() {
+                Color::Yellow
+            } else {
+                Color::Green
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!("  {:<width$} ", s.path, width = width),
+                    Style::default().fg(Color::White),
+                ),
+                Span::styled(
+                    format!("{} ", s.commit),
+                    Style::default().fg(Color::DarkGray),
+                ),
+                Span::styled(s.summary(), Style::default().fg(color)),
+            ]))
+        })
+        .collect();
+    let attention = submodules.iter().filter(|s| s.needs_attention()).count();
+    let title = match attention {
+        0 => format!(" Submodules ({}) · M ", submodules.len()),
+        n => format!(
+            " Submodules ({}, {} need attention) · M ",
+            submodules.len(),
+            n
+        ),
+    };
+    let widget = List::new(rows).block(
+        Block::default()
+            .title(Span::styled(title, Style::default().fg(Color::White)))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(if focus == DashboardFocus::Left {
+                Color::Cyan
+            } else {
+                Color::DarkGray
+            })),
+    );
+    f.render_widget(widget, area);
+}
+
+/// Collect a finished milestone fetch and start the next one when it is due.
+/// Only runs with a GitHub token; the widget stays hidden otherwise.
+pub fn tick_milestone(app: &mut crate::app::App) {
+    let state = &mut app.dashboard_state;
+    if let Some(rx) = &state.milestone_rx {
+        match rx.try_recv() {
+            Ok(Ok(progress)) => {
+                state.milestone = progress;
+                state.milestone_rx = None;
+            }
+            Ok(Err(e)) => {
+                log::warn!("Milestone fetch failed: {}", e);
+                state.milestone_rx = None;
+            }
+            Err(mpsc::TryRecvError::Empty) => return,
+            Err(mpsc::TryRecvError::Disconnected) => state.milestone_rx = None,
+        }
+    }
+    if state
+        .milestone_fetched
+        .is_some_and(|at| at.elapsed() < MILESTONE_INTERVAL)
+    {
+        return;
+    }
+    state.milestone_fetched = Some(Instant::now());
+    let Some(token) = app.config.github.get_token() else {
+        state.milestone = None;
+        return;
+    };
+    let name = app.config.github.milestone.clone();
+    let (tx, rx) = mpsc::channel();
+    state.milestone_rx = Some(rx);
+    app.jobs.submit("milestone", Priority::Background, move || {
+        let result = git::milestone::fetch(&token, name.as_deref()).map_err(|e| e.to_string());
+        let _ = tx.send(result);
+    });
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn render(
     f: &mut Frame,
     area: Rect,
     state: &DashboardState,
-    status_msg: &Option<String>,
     ai_mentor_state: &crate::ui::ai_mentor::AiMentorState,
     ai_available: bool,
     ai_loading: bool,
@@ -154,26 +372,41 @@ pub fn render(
     let main_chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(3),
+            Constraint::Length(zen::chrome(3)),
             Constraint::Min(5),
-            Constraint::Length(3),
-            Constraint::Length(1),
+            Constraint::Length(zen::chrome(optional_height(area, 3))),
+            Constraint::Length(state.error.is_some() as u16),
         ])
         .split(area);
 
+    // Narrow terminals show only the focused column; Tab switches between them
+    let columns = if !is_narrow(area) {
+        [Constraint::Percentage(55), Constraint::Percentage(45)]
+    } else if state.focus == DashboardFocus::Left {
+        [Constraint::Percentage(100), Constraint::Percentage(0)]
+    } else {
+        [Constraint::Percentage(0), Constraint::Percentage(100)]
+    };
+
     let top_panels = Layout::default()
         .direction(Direction::Horizontal)
-        .constraints([Constraint::Percentage(55), Constraint::Percentage(45)])
+        .constraints(columns)
         .split(main_chunks[0]);
 
     let title = Paragraph::new(Line::from(vec![
         Span::styled(
-            "⚡ zit",
+            format!("{} zit", Icon::Bolt),
             Style::default()
                 .fg(Color::Cyan)
                 .add_modifier(Modifier::BOLD),
         ),
         Span::raw(" — Repository Dashboard"),
+        Span::styled(
+            git::scope::get()
+                .map(|dir| format!(" · {}/", dir))
+                .unwrap_or_default(),
+            Style::default().fg(Color::Yellow),
+        ),
     ]))
     .block(
         Block::default()
@@ -211,7 +444,7 @@ pub fn render(
 
     let ai_title = Paragraph::new(Line::from(vec![
         Span::styled(
-            "🤖 AI Mentor",
+            format!("{} AI Mentor", Icon::Ai),
             Style::default()
                 .fg(Color::Magenta)
                 .add_modifier(Modifier::BOLD),
@@ -235,7 +468,7 @@ pub fn render(
 
     let content_panels = Layout::default()
         .direction(Direction::Horizontal)
-        .constraints([Constraint::Percentage(55), Constraint::Percentage(45)])
+        .constraints(columns)
         .split(main_chunks[1]);
 
     let left_chunks = Layout::default()
@@ -243,6 +476,11 @@ pub fn render(
         .constraints([
             Constraint::Length(3),
             Constraint::Length(3),
+            Constraint::Length(if state.milestone.is_some() { 3 } else { 0 }),
+            Constraint::Length(match state.submodules.len() {
+                0 => 0,
+                n => n.min(SUBMODULE_ROWS) as u16 + 2,
+            }),
             Constraint::Min(5),
         ])
         .split(content_panels[0]);
@@ -264,7 +502,10 @@ pub fn render(
         ),
     ];
 
-    if state.display_ahead > 0 || state.display_behind > 0 {
+    if !state.loaded {
+        branch_spans.truncate(1);
+        branch_spans.extend([skeleton(12), Span::raw("  │  "), skeleton(7)]);
+    } else if state.display_ahead > 0 || state.display_behind > 0 {
         branch_spans.push(Span::raw("  "));
         if state.display_ahead > 0 {
             branch_spans.push(Span::styled(
@@ -281,20 +522,22 @@ pub fn render(
         }
     }
 
-    branch_spans.push(Span::raw("  │  "));
-    branch_spans.push(Span::styled(
-        format!(
-            "{} {}",
-            status_icon,
-            if state.is_clean { "Clean" } else { "Dirty" }
-        ),
-        Style::default().fg(status_color),
-    ));
+    if state.loaded {
+        branch_spans.push(Span::raw("  │  "));
+        branch_spans.push(Span::styled(
+            format!(
+                "{} {}",
+                status_icon,
+                if state.is_clean { "Clean" } else { "Dirty" }
+            ),
+            Style::default().fg(status_color),
+        ));
+    }
 
     if state.display_conflict > 0 {
         branch_spans.push(Span::raw("  "));
         branch_spans.push(Span::styled(
-            format!("⚠ {} conflicts", state.display_conflict),
+            format!("{} {} conflicts", Icon::Warning, state.display_conflict),
             Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
         ));
     }
@@ -321,48 +564,65 @@ pub fn render(
         Span::styled(bar, Style::default().fg(color))
     }
 
-    let counts = Paragraph::new(Line::from(vec![
-        Span::styled("  Staged: ", Style::default().fg(Color::DarkGray)),
-        Span::styled(
-            format!("{}", state.display_staged),
-            Style::default().fg(Color::Green),
-        ),
-        Span::raw(" "),
-        gauge_bar(state.display_staged, 10, Color::Green),
-        Span::raw("  │  "),
-        Span::styled("Unstaged: ", Style::default().fg(Color::DarkGray)),
-        Span::styled(
-            format!("{}", state.display_unstaged),
-            Style::default().fg(Color::Yellow),
-        ),
-        Span::raw(" "),
-        gauge_bar(state.display_unstaged, 10, Color::Yellow),
-        Span::raw("  │  "),
-        Span::styled("Untracked: ", Style::default().fg(Color::DarkGray)),
-        Span::styled(
-            format!("{}", state.display_untracked),
-            Style::default().fg(Color::Gray),
-        ),
-        Span::raw(" "),
-        gauge_bar(state.display_untracked, 10, Color::Gray),
-        Span::raw("  │  "),
-        Span::styled("Stash: ", Style::default().fg(Color::DarkGray)),
-        Span::styled(
-            format!("{}", state.display_stash),
-            Style::default().fg(Color::Magenta),
-        ),
-        Span::raw(" "),
-        gauge_bar(state.display_stash as usize, 10, Color::Magenta),
-        Span::raw("  │  "),
-        Span::styled("Commits: ", Style::default().fg(Color::DarkGray)),
-        Span::styled(
-            format!("{}", state.display_commit),
-            Style::default().fg(Color::Blue),
-        ),
-        Span::raw(" "),
-        gauge_bar(state.display_commit, 50, Color::Blue),
-    ]))
-    .block(
+    let count_spans = if !state.loaded {
+        let mut spans = Vec::new();
+        for label in ["Staged", "Unstaged", "Untracked", "Stash", "Commits"] {
+            if !spans.is_empty() {
+                spans.push(Span::raw("  │  "));
+            } else {
+                spans.push(Span::raw("  "));
+            }
+            spans.push(Span::styled(
+                format!("{}: ", label),
+                Style::default().fg(Color::DarkGray),
+            ));
+            spans.push(skeleton(8));
+        }
+        spans
+    } else {
+        vec![
+            Span::styled("  Staged: ", Style::default().fg(Color::DarkGray)),
+            Span::styled(
+                format!("{}", state.display_staged),
+                Style::default().fg(Color::Green),
+            ),
+            Span::raw(" "),
+            gauge_bar(state.display_staged, 10, Color::Green),
+            Span::raw("  │  "),
+            Span::styled("Unstaged: ", Style::default().fg(Color::DarkGray)),
+            Span::styled(
+                format!("{}", state.display_unstaged),
+                Style::default().fg(Color::Yellow),
+            ),
+            Span::raw(" "),
+            gauge_bar(state.display_unstaged, 10, Color::Yellow),
+            Span::raw("  │  "),
+            Span::styled("Untracked: ", Style::default().fg(Color::DarkGray)),
+            Span::styled(
+                format!("{}", state.display_untracked),
+                Style::default().fg(Color::Gray),
+            ),
+            Span::raw(" "),
+            gauge_bar(state.display_untracked, 10, Color::Gray),
+            Span::raw("  │  "),
+            Span::styled("Stash: ", Style::default().fg(Color::DarkGray)),
+            Span::styled(
+                format!("{}", state.display_stash),
+                Style::default().fg(Color::Magenta),
+            ),
+            Span::raw(" "),
+            gauge_bar(state.display_stash as usize, 10, Color::Magenta),
+            Span::raw("  │  "),
+            Span::styled("Commits: ", Style::default().fg(Color::DarkGray)),
+            Span::styled(
+                format!("{}", state.display_commit),
+                Style::default().fg(Color::Blue),
+            ),
+            Span::raw(" "),
+            gauge_bar(state.display_commit, 50, Color::Blue),
+        ]
+    };
+    let counts = Paragraph::new(Line::from(count_spans)).block(
         Block::default()
             .borders(Borders::ALL)
             .border_style(Style::default().fg(if state.focus == DashboardFocus::Left {
@@ -373,6 +633,13 @@ pub fn render(
     );
     f.render_widget(counts, left_chunks[1]);
 
+    if let Some(milestone) = &state.milestone {
+        render_milestone(f, left_chunks[2], milestone, state.focus);
+    }
+    if !state.submodules.is_empty() {
+        render_submodules(f, left_chunks[3], &state.submodules, state.focus);
+    }
+
     let commit_items: Vec<ListItem> = state
         .recent_commits
         .iter()
@@ -395,14 +662,21 @@ pub fn render(
                 ),
                 Span::styled(&c.message, Style::default().fg(Color::White)),
                 Span::styled(
-                    format!(" ({})", c.date),
+                    format!(" ({})", datetime::format_iso(&c.date_iso, &c.date)),
                     Style::default().fg(Color::DarkGray),
                 ),
             ]))
         })
         .collect();
 
-    let commits = if commit_items.is_empty() {
+    let commits = if !state.loaded {
+        List::new(
+            [28, 22, 34]
+                .into_iter()
+                .map(|width| ListItem::new(Line::from(vec![Span::raw("  "), skeleton(width)])))
+                .collect::<Vec<_>>(),
+        )
+    } else if commit_items.is_empty() {
         List::new(vec![ListItem::new(Span::styled(
             "  No commits yet",
             Style::default().fg(Color::DarkGray),
@@ -424,7 +698,7 @@ pub fn render(
                 Color::DarkGray
             })),
     );
-    f.render_widget(commits, left_chunks[2]);
+    f.render_widget(commits, left_chunks[4]);
 
     let ai_content_area = content_panels[1];
     let ai_border_color = if state.focus == DashboardFocus::Right {
@@ -503,6 +777,13 @@ pub fn render(
                 .bg(Color::Rgb(155, 114, 215)),
         ),
         Span::raw("Reflog "),
+        Span::styled(
+            "[o]",
+            Style::default()
+                .fg(Color::Black)
+                .bg(Color::Rgb(155, 114, 215)),
+        ),
+        Span::raw("Outgoing "),
         Span::styled(
             "[g]",
             Style::default()
@@ -568,15 +849,11 @@ pub fn render(
                 .borders(Borders::ALL)
                 .border_style(Style::default().fg(Color::DarkGray)),
         );
-    f.render_widget(keys, main_chunks[2]);
+    if main_chunks[2].height > 0 {
+        f.render_widget(keys, main_chunks[2]);
+    }
 
-    if let Some(msg) = status_msg {
-        let status = Paragraph::new(Span::styled(
-            format!(" {}", msg),
-            Style::default().fg(Color::Yellow),
-        ));
-        f.render_widget(status, main_chunks[3]);
-    } else if let Some(err) = &state.error {
+    if let Some(err) = &state.error {
         let status = Paragraph::new(Span::styled(
             format!(" Error: {}", err),
             Style::default().fg(Color::Red),
@@ -629,7 +906,10 @@ fn render_ai_menu(
     if !ai_available {
         lines.push(Line::from(Span::raw("")));
         lines.push(Line::from(Span::styled(
-            "  ⚠ AI not configured. Press Enter or 'p' to set up a provider.",
+            format!(
+                "  {} AI not configured. Press Enter or 'p' to set up a provider.",
+                Icon::Warning
+            ),
             Style::default().fg(Color::Yellow),
         )));
         lines.push(Line::from(Span::styled(
@@ -704,15 +984,12 @@ fn render_ai_result(
 
     let visible_text: String = state.result_text.chars().take(visible_chars).collect();
 
-    let mut lines: Vec<Line> = visible_text
-        .lines()
-        .map(|l| {
-            Line::from(Span::styled(
-                format!("  {}", l),
-                Style::default().fg(Color::White),
-            ))
-        })
-        .collect();
+    let mut lines: Vec<Line> =
+        crate::ui::markdown::render(&visible_text, "  ", Style::default().fg(Color::White))
+            .into_iter()
+            .enumerate()
+            .map(|(i, line)| crate::ui::pane_search::highlight(line, state.search.as_ref(), i))
+            .collect();
 
     if is_typing {
         if let Some(last_line) = lines.last_mut() {
@@ -741,12 +1018,32 @@ fn render_ai_result(
                         .fg(Color::Green)
                         .add_modifier(Modifier::BOLD),
                 ))
+                .title_bottom(Span::styled(
+                    result_hint(&state.result_text),
+                    Style::default().fg(Color::DarkGray),
+                ))
                 .borders(Borders::ALL)
                 .border_style(Style::default().fg(border_color)),
         )
         .scroll((state.result_scroll, 0))
         .wrap(Wrap { trim: false });
-    f.render_widget(result, area);
+    f.render_widget(&result, area);
+    render_paragraph_scrollbar(f, area, &result, state.result_scroll);
+}
+
+/// Bottom-border hint pointing at the answer's next steps and code blocks, if
+/// it has any.
+fn result_hint(text: &str) -> String {
+    let mut hint = match crate::ai::next_steps::derive(text).len() {
+        0 => String::new(),
+        n => format!(" Enter next steps ({}) ", n),
+    };
+    match crate::ai::snippets::extract(text).len() {
+        0 => {}
+        1 => hint.push_str(" c copy/run code "),
+        n => hint.push_str(&format!(" c copy/run code ({} snippets) ", n)),
+    }
+    hint
 }
 
 fn render_ai_history(
@@ -773,7 +1070,7 @@ fn render_ai_history(
         .block(
             Block::default()
                 .title(Span::styled(
-                    " 📜 History ",
+                    format!(" {} History ", Icon::History),
                     Style::default()
                         .fg(Color::Yellow)
                         .add_modifier(Modifier::BOLD),
@@ -809,22 +1106,18 @@ fn render_ai_history(
                 }),
             ),
             Span::styled(
-                format!("[{}] ", entry.timestamp),
+                format!(
+                    "[{}] ",
+                    datetime::format_iso(&entry.timestamp, &entry.timestamp)
+                ),
                 Style::default().fg(Color::DarkGray),
             ),
-            Span::styled(entry.query.chars().take(60).collect::<String>(), style),
+            Span::styled(truncate_to_width(&entry.query, 60), style),
         ]));
 
-        let preview: String = entry
-            .response
-            .lines()
-            .next()
-            .unwrap_or("")
-            .chars()
-            .take(50)
-            .collect();
+        let preview = truncate_to_width(entry.response.lines().next().unwrap_or(""), 50);
         lines.push(Line::from(Span::styled(
-            format!("       → {}...", preview),
+            format!("       → {}", preview),
             Style::default().fg(Color::DarkGray),
         )));
         lines.push(Line::from(Span::raw("")));
@@ -834,7 +1127,11 @@ fn render_ai_history(
         .block(
             Block::default()
                 .title(Span::styled(
-                    format!(" 📜 History ({} entries) ", state.history.len()),
+                    format!(
+                        " {} History ({} entries) ",
+                        Icon::History,
+                        state.history.len()
+                    ),
                     Style::default()
                         .fg(Color::Yellow)
                         .add_modifier(Modifier::BOLD),
@@ -844,7 +1141,258 @@ fn render_ai_history(
         )
         .scroll((state.history_scroll, 0))
         .wrap(Wrap { trim: false });
-    f.render_widget(history_widget, area);
+    f.render_widget(&history_widget, area);
+    render_paragraph_scrollbar(f, area, &history_widget, state.history_scroll);
+}
+
+/// Pick one of the user's git aliases to run.
+pub fn open_alias_picker(app: &mut crate::app::App) {
+    use crate::app::{FollowUpAction, FollowUpItem, Popup};
+
+    let aliases = git::alias::list().unwrap_or_default();
+    // A broken recipes.toml shouldn't hide the aliases
+    let recipes = match crate::recipes::load() {
+        Ok(recipes) => recipes,
+        Err(e) => {
+            app.set_status(format!("Recipes: {:#}", e));
+            if aliases.is_empty() {
+                return;
+            }
+            Vec::new()
+        }
+    };
+    if aliases.is_empty() && recipes.is_empty() {
+        app.set_status(
+            "No git aliases or recipes (git config --global alias.<name> <command>, or .zit/recipes.toml)",
+        );
+        return;
+    }
+    let mut suggestions: Vec<FollowUpItem> = recipes
+        .into_iter()
+        .map(|recipe| FollowUpItem {
+            label: recipe.name.clone(),
+            description: truncate_to_width(
+                &if recipe.description.is_empty() {
+                    format!("Recipe, {} steps", recipe.steps.len())
+                } else {
+                    format!("Recipe: {}", recipe.description)
+                },
+                60,
+            ),
+            action: FollowUpAction::RunRecipe(recipe),
+        })
+        .collect();
+    suggestions.extend(aliases.into_iter().map(|alias| FollowUpItem {
+        label: alias.name.clone(),
+        description: truncate_to_width(alias.command.lines().next().unwrap_or_default(), 60),
+        action: FollowUpAction::RunAlias(alias),
+    }));
+    app.popup = Popup::FollowUp {
+        title: "Aliases & Recipes".to_string(),
+        context: "Runs `git <alias>` or the recipe's steps; output goes to the drawer (Ctrl+O)."
+            .to_string(),
+        suggestions,
+        selected: 0,
+    };
+}
+
+/// Pick a submodule, or all of them, to init, update or sync.
+pub fn open_submodules(app: &mut crate::app::App) {
+    use crate::app::{FollowUpAction, FollowUpItem, Popup};
+
+    let submodules = git::submodule::list().unwrap_or_default();
+    if submodules.is_empty() {
+        app.set_status("No submodules in this repository");
+        return;
+    }
+    let mut suggestions = vec![FollowUpItem {
+        label: "All submodules".to_string(),
+        description: format!("{} submodule(s)", submodules.len()),
+        action: FollowUpAction::SubmoduleActions(None),
+    }];
+    suggestions.extend(submodules.into_iter().map(|s| FollowUpItem {
+        description: format!("{} {}", s.commit, s.summary()),
+        action: FollowUpAction::SubmoduleActions(Some(s.path.clone())),
+        label: s.path,
+    }));
+    app.popup = Popup::FollowUp {
+        title: "Submodules".to_string(),
+        context: String::new(),
+        suggestions,
+        selected: 0,
+    };
+}
+
+/// Init, update or sync the submodule at `path`, or all of them.
+pub fn open_submodule_actions(app: &mut crate::app::App, path: Option<String>) {
+    use crate::app::{FollowUpAction, FollowUpItem, Popup};
+
+    let target = path.as_deref().unwrap_or("all submodules");
+    let suggestions = git::submodule::Action::ALL
+        .into_iter()
+        .map(|action| FollowUpItem {
+            label: action.label().to_string(),
+            description: action.description().to_string(),
+            action: FollowUpAction::RunGitSteps(
+                format!("{} {}", action.label(), target),
+                vec![action.args(path.as_deref())],
+            ),
+        })
+        .collect();
+    app.popup = Popup::FollowUp {
+        title: format!("Submodule: {}", target),
+        context: "Output goes to the drawer (Ctrl+O).".to_string(),
+        suggestions,
+        selected: 0,
+    };
+}
+
+/// The "Your zit stats" screen, or what turning stats on would count.
+pub fn open_stats(app: &mut crate::app::App) {
+    use crate::app::{FollowUpAction, FollowUpItem, Popup};
+
+    let item = |label: &str, description: &str, action| FollowUpItem {
+        label: label.to_string(),
+        description: description.to_string(),
+        action,
+    };
+    let (context, suggestions) = match crate::stats::current() {
+        Some(stats) => (
+            crate::stats::report(&stats),
+            vec![
+                item(
+                    "Reset",
+                    "Start counting from zero",
+                    FollowUpAction::ResetStats,
+                ),
+                item(
+                    "Turn off",
+                    "Stop counting; the counts so far are kept",
+                    FollowUpAction::SetStats(false),
+                ),
+            ],
+        ),
+        None => (
+            format!(
+                "Stats are off. Turned on, zit counts the git actions it runs for you and \
+                 the views you open, and estimates the time that saves.\n\n\
+                 They're kept in {} and never sent anywhere.",
+                crate::stats::path().display()
+            ),
+            vec![item(
+                "Turn on",
+                "Sets [stats] enabled = true",
+                FollowUpAction::SetStats(true),
+            )],
+        ),
+    };
+    app.popup = Popup::FollowUp {
+        title: "Your zit stats".to_string(),
+        context,
+        suggestions,
+        selected: 0,
+    };
+}
+
+/// Run `alias`, first asking for its `$1`, `$2`... arguments if it has any.
+pub fn start_alias(app: &mut crate::app::App, alias: git::alias::Alias) {
+    let count = alias.placeholders();
+    if count == 0 {
+        run_alias(app, &alias.name, Vec::new());
+        return;
+    }
+    let names: Vec<String> = (1..=count).map(|n| format!("${}", n)).collect();
+    app.popup = crate::app::Popup::Input {
+        title: format!("git {}", alias.name),
+        prompt: format!("Arguments ({}): ", names.join(" ")),
+        value: String::new(),
+        on_submit: crate::app::InputAction::AliasArgs(alias.name),
+    };
+}
+
+/// Run `git <name> <args>` in the output drawer.
+pub fn run_alias(app: &mut crate::app::App, name: &str, args: Vec<String>) {
+    let mut command = vec![name.to_string()];
+    command.extend(args);
+    let title = format!("git {}", command.join(" "));
+    app.start_output_job(crate::ui::output_pane::OutputJob {
+        success_msg: format!("✓ {}", title),
+        failure_msg: format!("{} failed (Ctrl+O for output)", title),
+        title,
+        commands: vec![command],
+    });
+}
+
+/// Ask for the recipe's next param, or when `values` has them all, show
+/// the steps it will run for confirmation.
+pub fn ask_recipe_param(
+    app: &mut crate::app::App,
+    recipe: crate::recipes::Recipe,
+    values: Vec<String>,
+) {
+    if let Some(param) = recipe.params.get(values.len()) {
+        let prompt = param.prompt.clone().unwrap_or_else(|| param.name.clone());
+        app.popup = crate::app::Popup::Input {
+            title: format!("Recipe {}", recipe.name),
+            prompt: format!("{}: ", prompt),
+            value: param.default.clone().unwrap_or_default(),
+            on_submit: crate::app::InputAction::RecipeParam(recipe, values),
+        };
+        return;
+    }
+    let token = recipe
+        .steps
+        .iter()
+        .any(|step| step.release.is_some())
+        .then(|| app.config.github.get_token())
+        .flatten();
+    match recipe.plan(&values, token) {
+        Ok(plan) => {
+            app.popup = crate::app::Popup::Confirm {
+                title: format!("Run recipe {}?", recipe.name),
+                message: format!(
+                    "{}\n\nIf a step fails, the ones before it are undone where possible.",
+                    plan.summary()
+                ),
+                on_confirm: crate::app::ConfirmAction::RunRecipe(plan),
+            };
+        }
+        Err(e) => app.set_status(format!("Recipe {}: {:#}", recipe.name, e)),
+    }
+}
+
+/// Run a confirmed recipe in the output drawer. Steps marked `confirm` are
+/// asked about through [`App::poll_recipe_steps`](crate::app::App::poll_recipe_steps).
+pub fn run_recipe(app: &mut crate::app::App, plan: crate::recipes::Plan) {
+    let (questions, asked) = mpsc::channel();
+    let waker = app.output_pane.waker();
+    let name = plan.recipe.clone();
+    let started = app.output_pane.start_task(
+        format!("recipe {}", name),
+        format!("✓ Recipe {} finished", name),
+        format!("Recipe {} failed", name),
+        move |emit| {
+            crate::recipes::run(&plan, emit, &|label| {
+                let (answer, answered) = mpsc::channel();
+                if questions.send((label.to_string(), answer)).is_err() {
+                    return false;
+                }
+                if let Some(waker) = &waker {
+                    let _ = waker.send(crate::event::AppEvent::Redraw);
+                }
+                answered.recv().unwrap_or(false)
+            })
+        },
+    );
+    if started {
+        app.recipe_steps = Some(asked);
+        app.set_status(format!("{} Running recipe {}...", Icon::Loading, name));
+    } else {
+        app.set_status(format!(
+            "{} Another command is still running (Ctrl+O to view)",
+            Icon::Loading
+        ));
+    }
 }
 
 pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()> {
@@ -862,13 +1410,12 @@ pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()
     }
 
     match state.focus {
-        DashboardFocus::Left => match key.code {
-            KeyCode::Char('a') => {
+        DashboardFocus::Left => {
+            if let KeyCode::Char('a') = key.code {
                 state.focus = DashboardFocus::Right;
                 return Ok(());
             }
-            _ => {}
-        },
+        }
         DashboardFocus::Right => {
             if let crate::ui::ai_mentor::AiMode::Menu = app.ai_mentor_state.mode {
                 match key.code {
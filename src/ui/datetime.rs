@@ -0,0 +1,177 @@
+//! Date/time formatting shared by every view, so commit dates, reflog entries and
+//! AI history all follow the `[ui] date_format` setting and the local timezone.
+
+use jiff::tz::TimeZone;
+use jiff::{Timestamp, Zoned};
+use std::sync::RwLock;
+
+/// Pattern used for `date_format = "absolute"` and as a fallback for bad patterns.
+const ABSOLUTE_PATTERN: &str = "%Y-%m-%d %H:%M";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DateFormat {
+    /// "2 hours ago"
+    Relative,
+    /// strftime-style pattern rendered in the local timezone.
+    Absolute(String),
+}
+
+impl DateFormat {
+    /// Parse the `[ui] date_format` config value: "relative", "absolute", or a strftime pattern.
+    pub fn from_config(value: &str) -> Self {
+        match value.trim() {
+            "" | "relative" => Self::Relative,
+            "absolute" => Self::Absolute(ABSOLUTE_PATTERN.to_string()),
+            pattern => Self::Absolute(pattern.to_string()),
+        }
+    }
+}
+
+static FORMAT: RwLock<DateFormat> = RwLock::new(DateFormat::Relative);
+
+/// Set the date format used by all views, at startup and when the config
+/// file changes.
+pub fn set_format(format: DateFormat) {
+    if let Ok(mut current) = FORMAT.write() {
+        *current = format;
+    }
+}
+
+fn format() -> DateFormat {
+    FORMAT
+        .read()
+        .map(|format| format.clone())
+        .unwrap_or(DateFormat::Relative)
+}
+
+/// Format a timestamp per the configured style, in the local timezone.
+pub fn format_timestamp(ts: Timestamp) -> String {
+    format_with(
+        &format(),
+        ts,
+        &Timestamp::now().to_zoned(TimeZone::system()),
+    )
+}
+
+/// Format an ISO 8601 / RFC 3339 date from git, or return `fallback` if it does not parse.
+pub fn format_iso(iso: &str, fallback: &str) -> String {
+    match iso.trim().parse::<Timestamp>() {
+        Ok(ts) => format_timestamp(ts),
+        Err(_) => fallback.to_string(),
+    }
+}
+
+/// Current time as an RFC 3339 string, for persisting alongside records.
+pub fn now_iso() -> String {
+    Timestamp::now().to_string()
+}
+
+fn format_with(format: &DateFormat, ts: Timestamp, now: &Zoned) -> String {
+    match format {
+        DateFormat::Relative => relative(ts, now.timestamp()),
+        DateFormat::Absolute(pattern) => {
+            let zoned = ts.to_zoned(now.time_zone().clone());
+            jiff::fmt::strtime::format(pattern.as_str(), &zoned)
+                .or_else(|_| jiff::fmt::strtime::format(ABSOLUTE_PATTERN, &zoned))
+                .unwrap_or_default()
+        }
+    }
+}
+
+/// Human-friendly age of `ts` relative to `now`, e.g. "5 minutes ago" or "in 2 days".
+pub fn relative(ts: Timestamp, now: Timestamp) -> String {
+    let secs = now.duration_since(ts).as_secs();
+    let abs = secs.unsigned_abs();
+    if abs < 45 {
+        return "just now".to_string();
+    }
+
+    const UNITS: [(u64, &str); 6] = [
+        (60 * 60 * 24 * 365, "year"),
+        (60 * 60 * 24 * 30, "month"),
+        (60 * 60 * 24 * 7, "week"),
+        (60 * 60 * 24, "day"),
+        (60 * 60, "hour"),
+        (60, "minute"),
+    ];
+    let (size, unit) = UNITS
+        .iter()
+        .find(|(size, _)| abs >= *size)
+        .copied()
+        .unwrap_or((60, "minute"));
+    let n = abs / size;
+    let plural = if n == 1 { "" } else { "s" };
+    if secs >= 0 {
+        format!("{} {}{} ago", n, unit, plural)
+    } else {
+        format!("in {} {}{}", n, unit, plural)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ts(s: &str) -> Timestamp {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_from_config() {
+        assert_eq!(DateFormat::from_config("relative"), DateFormat::Relative);
+        assert_eq!(
+            DateFormat::from_config("absolute"),
+            DateFormat::Absolute(ABSOLUTE_PATTERN.to_string())
+        );
+        assert_eq!(
+            DateFormat::from_config("%d/%m %H:%M"),
+            DateFormat::Absolute("%d/%m %H:%M".to_string())
+        );
+    }
+
+    #[test]
+    fn test_relative() {
+        let now = ts("2026-03-10T12:00:00Z");
+        assert_eq!(relative(ts("2026-03-10T11:59:50Z"), now), "just now");
+        assert_eq!(relative(ts("2026-03-10T11:55:00Z"), now), "5 minutes ago");
+        assert_eq!(relative(ts("2026-03-10T11:00:00Z"), now), "1 hour ago");
+        assert_eq!(relative(ts("2026-03-07T12:00:00Z"), now), "3 days ago");
+        assert_eq!(relative(ts("2026-02-20T12:00:00Z"), now), "2 weeks ago");
+        assert_eq!(relative(ts("2024-03-10T12:00:00Z"), now), "2 years ago");
+        assert_eq!(relative(ts("2026-03-12T12:00:00Z"), now), "in 2 days");
+    }
+
+    #[test]
+    fn test_absolute_uses_timezone() {
+        let now = ts("2026-03-10T12:00:00Z").to_zoned(TimeZone::fixed(jiff::tz::offset(5)));
+        let commit = ts("2026-02-10T10:00:00+05:30");
+        let out = format_with(&DateFormat::from_config("absolute"), commit, &now);
+        assert_eq!(out, "2026-02-10 09:30");
+    }
+
+    #[test]
+    fn test_bad_pattern_falls_back() {
+        let now = ts("2026-03-10T12:00:00Z").to_zoned(TimeZone::UTC);
+        let out = format_with(
+            &DateFormat::Absolute("%Y %".to_string()),
+            ts("2026-03-10T08:15:00Z"),
+            &now,
+        );
+        assert_eq!(out, "2026-03-10 08:15");
+    }
+
+    #[test]
+    fn test_set_format_after_first_use() {
+        let commit = ts("2020-06-01T12:00:00Z");
+        set_format(DateFormat::Relative);
+        assert!(format_timestamp(commit).ends_with(" ago"));
+        set_format(DateFormat::from_config("%Y (configured)"));
+        assert_eq!(format_timestamp(commit), "2020 (configured)");
+        set_format(DateFormat::Relative);
+    }
+
+    #[test]
+    fn test_format_iso_fallback() {
+        assert_eq!(format_iso("not a date", "2 hours ago"), "2 hours ago");
+    }
+}
@@ -0,0 +1,169 @@
+//! Opening a file in the user's editor at a given line — the same editor git
+//! uses for commit messages (`GIT_EDITOR`, `core.editor`, `VISUAL`, `EDITOR`) —
+//! or letting git itself open it for text it stores, like branch descriptions
+//! and commit notes.
+
+use crate::git;
+
+/// What to edit once the terminal is handed over.
+#[derive(Debug, Clone, PartialEq)]
+pub enum EditorTarget {
+    /// A file (relative to the repository root) and the 1-based line to open it at.
+    File { path: String, line: u32 },
+    /// A branch's description (`git branch --edit-description`).
+    BranchDescription(String),
+    /// The note on a commit (`git notes edit`).
+    Notes(String),
+    /// The description of the pull request being opened (`PULLREQ_EDITMSG`).
+    PullRequestBody,
+}
+
+impl EditorTarget {
+    /// Status line once the editor has closed.
+    pub fn edited(&self) -> String {
+        match self {
+            Self::File { path, line } => format!("Edited {}:{}", path, line),
+            Self::BranchDescription(name) => format!("✓ Saved the description of '{}'", name),
+            Self::Notes(commit) => format!("✓ Saved the note on {}", short(commit)),
+            Self::PullRequestBody => "✓ Updated the pull request description".to_string(),
+        }
+    }
+}
+
+fn short(hash: &str) -> &str {
+    &hash[..hash.len().min(7)]
+}
+
+/// The editor command line, split into program and arguments.
+pub fn resolve() -> Vec<String> {
+    let editor = git::run_git(&["var", "GIT_EDITOR"])
+        .ok()
+        .map(|e| e.trim().to_string())
+        .filter(|e| !e.is_empty())
+        .or_else(|| {
+            std::env::var("EDITOR")
+                .ok()
+                .filter(|e| !e.trim().is_empty())
+        })
+        .unwrap_or_else(|| "vi".to_string());
+    editor.split_whitespace().map(|s| s.to_string()).collect()
+}
+
+/// Arguments that open `file` at `line` in `editor` (the program path or name).
+/// GUI editors take `file:line`; terminal editors the traditional `+line file`.
+pub fn line_args(editor: &str, file: &str, line: u32) -> Vec<String> {
+    let name = std::path::Path::new(editor)
+        .file_stem()
+        .and_then(|n| n.to_str())
+        .unwrap_or(editor);
+    match name {
+        "code" | "code-insiders" | "codium" => {
+            vec!["--goto".to_string(), format!("{}:{}", file, line)]
+        }
+        "subl" | "zed" | "hx" | "helix" => vec![format!("{}:{}", file, line)],
+        _ => vec![format!("+{}", line), file.to_string()],
+    }
+}
+
+/// Run the editor on `target` and wait for it to exit. The caller hands over the
+/// terminal first.
+pub fn open(target: &EditorTarget) -> anyhow::Result<()> {
+    match target {
+        EditorTarget::File { path, line } => open_file(path, *line),
+        EditorTarget::BranchDescription(name) => {
+            run_git_editor(&["branch", "--edit-description", name])
+        }
+        EditorTarget::Notes(commit) => run_git_editor(&["notes", "edit", commit]),
+        EditorTarget::PullRequestBody => {
+            let file = git::pull_request::body_file()?;
+            run_editor(vec![file.to_string_lossy().to_string()])
+        }
+    }
+}
+
+/// Run a git command that opens the editor itself, on this terminal.
+fn run_git_editor(args: &[&str]) -> anyhow::Result<()> {
+    let status = std::process::Command::new("git")
+        .args(args)
+        .status()
+        .map_err(|e| anyhow::anyhow!("Failed to run git: {}", e))?;
+    if !status.success() {
+        anyhow::bail!("git {} exited with {}", args.join(" "), status);
+    }
+    Ok(())
+}
+
+fn open_file(path: &str, line: u32) -> anyhow::Result<()> {
+    let root = git::run_git(&["rev-parse", "--show-toplevel"])?;
+    let file = file_in(std::path::Path::new(root.trim()), path)?;
+    let program = resolve().first().cloned().unwrap_or_default();
+    run_editor(line_args(&program, &file.to_string_lossy(), line))
+}
+
+/// `path` in the checkout at `root`. The path comes from CI or the GitHub
+/// API, so one that resolves outside the repository is refused.
+fn file_in(root: &std::path::Path, path: &str) -> anyhow::Result<std::path::PathBuf> {
+    let file = root.join(path);
+    if !file.is_file() {
+        anyhow::bail!("{} does not exist in this checkout", path);
+    }
+    let file = file.canonicalize()?;
+    if !file.starts_with(root.canonicalize()?) {
+        anyhow::bail!("annotation path outside the repository: {}", path);
+    }
+    Ok(file)
+}
+
+/// Run the configured editor with `file_args` after its own arguments.
+fn run_editor(file_args: Vec<String>) -> anyhow::Result<()> {
+    let command = resolve();
+    let Some((program, args)) = command.split_first() else {
+        anyhow::bail!("No editor configured — set core.editor or $EDITOR");
+    };
+    let status = std::process::Command::new(program)
+        .args(args)
+        .args(file_args)
+        .status()
+        .map_err(|e| anyhow::anyhow!("Failed to start {}: {}", program, e))?;
+    if !status.success() {
+        anyhow::bail!("{} exited with {}", program, status);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_in_stays_in_the_repository() {
+        let outside = tempfile::tempdir().unwrap();
+        std::fs::write(outside.path().join("secret"), "").unwrap();
+        let root = outside.path().join("repo");
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::write(root.join("src/a.rs"), "").unwrap();
+
+        assert!(file_in(&root, "src/a.rs").unwrap().ends_with("src/a.rs"));
+        assert!(file_in(&root, "src/../src/a.rs").is_ok());
+        let absolute = outside.path().join("secret");
+        for path in ["../secret", absolute.to_str().unwrap()] {
+            assert!(
+                file_in(&root, path)
+                    .unwrap_err()
+                    .to_string()
+                    .starts_with("annotation path outside the repository"),
+                "{}",
+                path
+            );
+        }
+        assert!(file_in(&root, "missing.rs").is_err());
+    }
+
+    #[test]
+    fn test_line_args_per_editor() {
+        assert_eq!(line_args("vim", "src/a.rs", 12), ["+12", "src/a.rs"]);
+        assert_eq!(line_args("/usr/bin/nano", "a.rs", 3), ["+3", "a.rs"]);
+        assert_eq!(line_args("code", "a.rs", 7), ["--goto", "a.rs:7"]);
+        assert_eq!(line_args("hx", "a.rs", 7), ["a.rs:7"]);
+    }
+}
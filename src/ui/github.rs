@@ -1,14 +1,25 @@
-use crossterm::event::{KeyCode, KeyEvent};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::{
+    Frame,
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
-    text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Tabs},
-    Frame,
+    text::{Line, Span, Text},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Tabs, Wrap},
 };
+use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use crate::git;
+use crate::jobs::Priority;
+use crate::ui::icons::Icon;
+use crate::ui::image_preview::{self, RgbaImage};
+use crate::ui::output_pane::OutputJob;
+use crate::ui::utils::{
+    is_narrow, pad_to_width, render_paragraph_scrollbar, render_scrollbar, truncate_to_width,
+};
+use crate::ui::zen;
+use crate::ui::{identicon, staging, theme};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum GitHubView {
@@ -18,6 +29,9 @@ pub enum GitHubView {
     Collaborators,
     PullRequests,
     PullRequestDetail(u64),
+    NewPullRequest,
+    Issues,
+    IssueDetail(u64),
     Actions,
     ActionDetail(u64),
 }
@@ -118,6 +132,80 @@ pub enum PrBgResult {
     },
     MergeResult(Result<git::github_auth::MergeResponse, String>),
     CloseResult(Result<git::github_auth::PullRequest, String>),
+    Created(Result<git::github_auth::PullRequest, String>),
+    /// A review went in on the pull request (with a status message) or failed.
+    Reviewed(u64, Result<String, String>),
+    LineCommented(Result<String, String>),
+}
+
+// ─── Issue UI Types ────────────────────────────────
+
+#[derive(Debug, Clone)]
+pub enum IssueBgResult {
+    /// The issues, with the repository's labels for `l`.
+    List(
+        Result<Vec<git::github_auth::Issue>, String>,
+        Vec<git::github_auth::GhLabel>,
+    ),
+    Detail {
+        issue: Result<git::github_auth::Issue, String>,
+        comments: Result<Vec<git::github_auth::IssueComment>, String>,
+    },
+    /// A change to an issue went through (with a status message) or failed.
+    Updated(u64, Result<String, String>),
+    Created(Result<git::github_auth::Issue, String>),
+}
+
+pub struct IssuesState {
+    pub issues: Vec<git::github_auth::Issue>,
+    /// Narrows the list as typed with `/` (see `Issue::matches`).
+    pub query: String,
+    /// Index into the visible issues.
+    pub selected: usize,
+    pub list_state: ListState,
+    pub filter: PrFilter,
+    /// Labels defined in the repository.
+    pub labels: Vec<git::github_auth::GhLabel>,
+    pub loading: bool,
+    pub error: Option<String>,
+    // Detail view
+    pub detail: Option<git::github_auth::Issue>,
+    pub comments: Vec<git::github_auth::IssueComment>,
+    pub detail_scroll: u16,
+    pub bg_result: Arc<Mutex<Option<IssueBgResult>>>,
+}
+
+impl IssuesState {
+    pub fn new() -> Self {
+        Self {
+            issues: Vec::new(),
+            query: String::new(),
+            selected: 0,
+            list_state: ListState::default(),
+            filter: PrFilter::Open,
+            labels: Vec::new(),
+            loading: false,
+            error: None,
+            detail: None,
+            comments: Vec::new(),
+            detail_scroll: 0,
+            bg_result: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// The issues matching the query.
+    pub fn visible(&self) -> Vec<&git::github_auth::Issue> {
+        self.issues
+            .iter()
+            .filter(|i| i.matches(&self.query))
+            .collect()
+    }
+
+    pub fn select(&mut self, index: usize) {
+        self.selected = index;
+        self.list_state
+            .select((index < self.visible().len()).then_some(index));
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -125,10 +213,22 @@ pub enum ActionsBgResult {
     RunsList(Result<Vec<git::github_auth::WorkflowRun>, String>),
     RunDetail(Result<Vec<git::github_auth::WorkflowJob>, String>),
     JobLogs(u64, Result<String, String>), // (job_id, logs)
+    Annotations(u64, Result<Vec<CiProblem>, String>), // (run_id, problems)
+    Rerun(u64, Result<String, String>),   // (run_id, message)
+}
+
+/// A check run annotation of a failed job — one entry of the quickfix list.
+#[derive(Debug, Clone)]
+pub struct CiProblem {
+    pub job: String,
+    pub annotation: git::github_auth::CheckAnnotation,
 }
 
 pub struct ActionsState {
     pub runs: Vec<git::github_auth::WorkflowRun>,
+    /// The checked-out branch, whose runs are listed unless `all_branches`.
+    pub branch: String,
+    pub all_branches: bool,
     pub selected: usize,
     pub list_state: ListState,
     pub loading: bool,
@@ -138,21 +238,40 @@ pub struct ActionsState {
     pub selected_job: usize,
     pub job_list_state: ListState,
     pub job_logs: Option<String>,
+    /// The job whose logs are shown, followed while it runs.
+    pub log_job: Option<u64>,
     pub log_scroll: u16,
     pub detail_pane: ActionDetailPane,
-    pub bg_result: Arc<Mutex<Option<ActionsBgResult>>>,
+    /// Annotations of the run's failed jobs, failures first.
+    pub problems: Vec<CiProblem>,
+    pub selected_problem: usize,
+    pub problem_list_state: ListState,
+    /// Last refresh of runs or jobs still going, for live polling.
+    polled_at: Instant,
+    /// Results of the loads in flight — several can finish between ticks.
+    pub bg_result: Arc<Mutex<Vec<ActionsBgResult>>>,
+}
+
+/// Time between refreshes of runs and jobs that haven't finished.
+const LIVE_INTERVAL: Duration = Duration::from_secs(10);
+
+fn is_completed(status: Option<&str>) -> bool {
+    status == Some("completed")
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum ActionDetailPane {
     Jobs,
     Logs,
+    Problems,
 }
 
 impl ActionsState {
     pub fn new() -> Self {
         Self {
             runs: Vec::new(),
+            branch: String::new(),
+            all_branches: false,
             selected: 0,
             list_state: ListState::default(),
             loading: false,
@@ -161,13 +280,63 @@ impl ActionsState {
             selected_job: 0,
             job_list_state: ListState::default(),
             job_logs: None,
+            log_job: None,
             log_scroll: 0,
             detail_pane: ActionDetailPane::Jobs,
-            bg_result: Arc::new(Mutex::new(None)),
+            problems: Vec::new(),
+            selected_problem: 0,
+            problem_list_state: ListState::default(),
+            polled_at: Instant::now(),
+            bg_result: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// The branch to list runs for, if only one.
+    fn branch_filter(&self) -> Option<String> {
+        (!self.all_branches && !self.branch.is_empty() && self.branch != "HEAD")
+            .then(|| self.branch.clone())
+    }
+
+    /// Whether what's on screen is still running and worth refreshing.
+    fn is_live(&self, view: &GitHubView) -> bool {
+        match view {
+            GitHubView::Actions => self.runs.iter().any(|r| !is_completed(r.status.as_deref())),
+            GitHubView::ActionDetail(run_id) => {
+                self.jobs.iter().any(|j| !is_completed(Some(&j.status)))
+                    || self
+                        .runs
+                        .iter()
+                        .any(|r| r.id == *run_id && !is_completed(r.status.as_deref()))
+            }
+            _ => false,
         }
     }
 }
 
+/// Time between polls of a watched pull request.
+const WATCH_INTERVAL: Duration = Duration::from_secs(30);
+
+/// A pull request whose checks are polled in the background until they finish.
+pub struct PrWatch {
+    pub number: u64,
+    /// Merge once every check passes and GitHub reports the PR as cleanly mergeable.
+    pub auto_merge: bool,
+    /// API name of the merge method used for auto-merge.
+    merge_method: String,
+    /// Latest state, shown under the PR detail.
+    pub note: String,
+    last_poll: Option<Instant>,
+    in_flight: bool,
+    result: Arc<Mutex<Option<Result<WatchPoll, String>>>>,
+}
+
+/// A watched PR, the checks of its head commit and what to do about them.
+type WatchPoll = Box<(
+    git::github_auth::PullRequest,
+    git::github_auth::CheckRunsResponse,
+    git::github_auth::WatchStep,
+)>;
+
 pub struct PullRequestsState {
     pub prs: Vec<git::github_auth::PullRequest>,
     pub selected: usize,
@@ -184,8 +353,23 @@ pub struct PullRequestsState {
     pub detail_scroll: u16,
     pub files_selected: usize,
     pub files_list_state: ListState,
+    /// Whether the Files tab is moving through the selected file's patch,
+    /// rather than the list of files.
+    pub patch_focus: bool,
+    /// Index into the selected file's patch lines.
+    pub patch_line: usize,
+    /// Whether the patch fills the Files tab, hiding the list.
+    pub patch_expanded: bool,
+    /// Whether the patch shows old and new side by side.
+    pub side_by_side: bool,
     pub merge_method: MergeMethod,
     pub bg_result: Arc<Mutex<Option<PrBgResult>>>,
+    /// Parsed CODEOWNERS of the local checkout, for the detail view.
+    pub codeowners: Option<git::codeowners::CodeOwners>,
+    /// Downloaded avatars by login; `None` when the image couldn't be fetched or decoded.
+    pub avatars: Arc<Mutex<HashMap<String, Option<RgbaImage>>>>,
+    /// The New Pull Request form, while it's open.
+    pub new_pr: Option<NewPrState>,
 }
 
 impl PullRequestsState {
@@ -205,8 +389,111 @@ impl PullRequestsState {
             detail_scroll: 0,
             files_selected: 0,
             files_list_state: ListState::default(),
+            patch_focus: false,
+            patch_line: 0,
+            patch_expanded: false,
+            side_by_side: false,
             merge_method: MergeMethod::Merge,
             bg_result: Arc::new(Mutex::new(None)),
+            codeowners: None,
+            avatars: Arc::new(Mutex::new(HashMap::new())),
+            new_pr: None,
+        }
+    }
+}
+
+/// The New Pull Request form.
+pub struct NewPrState {
+    /// Local branches, which the pull request can come from.
+    pub heads: Vec<String>,
+    /// Branches it can go into: the local ones and the remote's.
+    pub bases: Vec<String>,
+    pub head: usize,
+    pub base: usize,
+    pub title: String,
+    pub body: String,
+    pub draft: bool,
+    pub field: usize,
+    /// Subjects of the commits the head adds over the base.
+    pub commits: Vec<String>,
+    pub push: git::pull_request::PushNeed,
+    /// Title and body as last drafted. Text still equal to them is redrafted
+    /// when the branches change; anything edited is kept.
+    pub drafted: (String, String),
+    pub submitting: bool,
+}
+
+impl NewPrState {
+    /// The form for the current branch into the default one.
+    pub fn open() -> anyhow::Result<Self> {
+        let (heads, bases) = git::pull_request::branches()?;
+        if heads.is_empty() {
+            anyhow::bail!("No local branches to open a pull request from");
+        }
+        let current = git::BranchOps::current().unwrap_or_default();
+        let default = git::BranchOps::default_branch().unwrap_or_else(|| "main".to_string());
+        let mut state = Self {
+            head: heads.iter().position(|b| *b == current).unwrap_or(0),
+            base: bases.iter().position(|b| *b == default).unwrap_or(0),
+            heads,
+            bases,
+            title: String::new(),
+            body: String::new(),
+            draft: false,
+            field: 0,
+            commits: Vec::new(),
+            push: git::pull_request::PushNeed::UpToDate,
+            drafted: (String::new(), String::new()),
+            submitting: false,
+        };
+        state.redraft();
+        Ok(state)
+    }
+
+    pub fn head_name(&self) -> &str {
+        &self.heads[self.head]
+    }
+
+    pub fn base_name(&self) -> &str {
+        &self.bases[self.base]
+    }
+
+    /// Count the commits and what needs pushing for the chosen branches, and
+    /// draft the title and body again where they haven't been edited.
+    pub fn redraft(&mut self) {
+        let head = self.head_name().to_string();
+        let base = self.base_name().to_string();
+        self.commits = if head == base {
+            Vec::new()
+        } else {
+            git::pull_request::commits_between(&base, &head).unwrap_or_default()
+        };
+        self.push = git::pull_request::push_need(&head);
+        let title = git::pull_request::draft_title(&head, &self.commits);
+        let body = git::pull_request::draft_body(&self.commits);
+        if self.title == self.drafted.0 {
+            self.title = title.clone();
+        }
+        if self.body == self.drafted.1 {
+            self.body = body.clone();
+        }
+        self.drafted = (title, body);
+    }
+
+    /// What stops the pull request from being opened, if anything.
+    pub fn problem(&self) -> Option<String> {
+        if self.head_name() == self.base_name() {
+            Some("Pick a base branch other than the head".to_string())
+        } else if self.commits.is_empty() {
+            Some(format!(
+                "'{}' has no commits that aren't on '{}'",
+                self.head_name(),
+                self.base_name()
+            ))
+        } else if self.title.trim().is_empty() {
+            Some("The pull request needs a title".to_string())
+        } else {
+            None
         }
     }
 }
@@ -231,6 +518,14 @@ pub struct GitHubState {
     pub repo_private: bool,
     pub create_field: usize,
     pub editing_field: bool,
+    // Scaffold options for the new repository
+    pub gitignore_templates: Vec<String>,
+    /// Index into `gitignore_templates` plus one; 0 means none.
+    pub gitignore_choice: usize,
+    pub licenses: Vec<git::github_auth::LicenseInfo>,
+    /// Index into `licenses` plus one; 0 means none.
+    pub license_choice: usize,
+    pub repo_readme: bool,
     // Collaborator fields
     pub collaborators: Vec<git::github_auth::Collaborator>,
     pub collab_selected: usize,
@@ -238,10 +533,16 @@ pub struct GitHubState {
     pub collab_error: Option<String>,
     // Background operation result
     pub bg_result: Arc<Mutex<Option<String>>>,
+    /// Answer to the last device-flow token poll, with the device code it was for.
+    pub device_poll: Arc<Mutex<Option<(String, git::github_auth::PollResult)>>>,
+    pub device_poll_in_flight: bool,
     // Pull-request state
     pub pr_state: PullRequestsState,
+    pub issues_state: IssuesState,
     // Actions state
     pub actions_state: ActionsState,
+    /// Pull request whose checks are being watched, from any view.
+    pub watch: Option<PrWatch>,
     // Status
     pub status: Option<String>,
 }
@@ -259,13 +560,22 @@ impl GitHubState {
             repo_private: true,
             create_field: 0,
             editing_field: false,
+            gitignore_templates: Vec::new(),
+            gitignore_choice: 0,
+            licenses: Vec::new(),
+            license_choice: 0,
+            repo_readme: false,
             collaborators: Vec::new(),
             collab_selected: 0,
             collab_list_state: ListState::default(),
             collab_error: None,
             bg_result: Arc::new(Mutex::new(None)),
+            device_poll: Arc::new(Mutex::new(None)),
+            device_poll_in_flight: false,
             pr_state: PullRequestsState::new(),
+            issues_state: IssuesState::new(),
             actions_state: ActionsState::new(),
+            watch: None,
             status: None,
         }
     }
@@ -279,8 +589,11 @@ pub fn render(f: &mut Frame, area: Rect, state: &mut GitHubState, config: &crate
         GitHubView::DeviceAuth(auth_state) => render_device_auth(f, area, auth_state),
         GitHubView::CreateRepo => render_create_repo(f, area, state),
         GitHubView::Collaborators => render_collaborators(f, area, state),
-        GitHubView::PullRequests => render_pull_requests(f, area, state),
-        GitHubView::PullRequestDetail(_) => render_pr_detail(f, area, state),
+        GitHubView::PullRequests => render_pull_requests(f, area, state, &config.ui),
+        GitHubView::PullRequestDetail(_) => render_pr_detail(f, area, state, &config.ui),
+        GitHubView::NewPullRequest => render_new_pr(f, area, state),
+        GitHubView::Issues => render_issues(f, area, state),
+        GitHubView::IssueDetail(_) => render_issue_detail(f, area, state),
         GitHubView::Actions => render_actions_list(f, area, state),
         GitHubView::ActionDetail(_) => render_action_detail(f, area, state),
     }
@@ -296,15 +609,15 @@ fn render_menu(
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(3), // Title
-            Constraint::Length(3), // Auth status
-            Constraint::Min(8),    // Menu
-            Constraint::Length(2), // Status
+            Constraint::Length(zen::chrome(3)), // Title
+            Constraint::Length(3),              // Auth status
+            Constraint::Min(8),                 // Menu
+            Constraint::Length(2),              // Status
         ])
         .split(area);
 
     let title = Paragraph::new(Line::from(vec![
-        Span::styled("  🐙 ", Style::default()),
+        Span::styled(format!("  {} ", Icon::GitHub), Style::default()),
         Span::styled(
             "GitHub Integration",
             Style::default()
@@ -351,39 +664,51 @@ fn render_menu(
     // Menu
     let items = vec![
         ListItem::new(Line::from(vec![
-            Span::styled("  🔑  ", Style::default()),
+            Span::styled(format!("  {}  ", Icon::Key), Style::default()),
             Span::styled("Login with GitHub", Style::default().fg(Color::White)),
         ])),
         ListItem::new(Line::from(vec![
-            Span::styled("  📦  ", Style::default()),
+            Span::styled(format!("  {}  ", Icon::Repo), Style::default()),
             Span::styled("Create Repository", Style::default().fg(Color::White)),
         ])),
         ListItem::new(Line::from(vec![
-            Span::styled("  📤  ", Style::default()),
+            Span::styled(format!("  {}  ", Icon::Push), Style::default()),
             Span::styled("Push to Remote", Style::default().fg(Color::White)),
         ])),
         ListItem::new(Line::from(vec![
-            Span::styled("  📥  ", Style::default()),
+            Span::styled(format!("  {}  ", Icon::Pull), Style::default()),
             Span::styled("Pull from Remote", Style::default().fg(Color::White)),
         ])),
         ListItem::new(Line::from(vec![
-            Span::styled("  🔄  ", Style::default()),
+            Span::styled(format!("  {}  ", Icon::Sync), Style::default()),
             Span::styled("Sync (Pull + Push)", Style::default().fg(Color::White)),
         ])),
         ListItem::new(Line::from(vec![
-            Span::styled("  👥  ", Style::default()),
+            Span::styled(format!("  {}  ", Icon::Users), Style::default()),
             Span::styled("Manage Collaborators", Style::default().fg(Color::White)),
         ])),
         ListItem::new(Line::from(vec![
-            Span::styled("  🔀  ", Style::default()),
+            Span::styled(format!("  {}  ", Icon::PullRequest), Style::default()),
             Span::styled("Pull Requests", Style::default().fg(Color::White)),
         ])),
         ListItem::new(Line::from(vec![
-            Span::styled("  ⚡  ", Style::default()),
-            Span::styled("Actions", Style::default().fg(Color::White)),
+            Span::styled(format!("  {}  ", Icon::Issue), Style::default()),
+            Span::styled("Issues", Style::default().fg(Color::White)),
+        ])),
+        ListItem::new(Line::from(vec![
+            Span::styled(format!("  {}  ", Icon::Bolt), Style::default()),
+            Span::styled("CI Runs", Style::default().fg(Color::White)),
+        ])),
+        ListItem::new(Line::from(vec![
+            Span::styled(format!("  {}  ", Icon::Shield), Style::default()),
+            Span::styled("Contributing Readiness", Style::default().fg(Color::White)),
+        ])),
+        ListItem::new(Line::from(vec![
+            Span::styled(format!("  {}  ", Icon::File), Style::default()),
+            Span::styled("Draft Release Notes", Style::default().fg(Color::White)),
         ])),
         ListItem::new(Line::from(vec![
-            Span::styled("  🚪  ", Style::default()),
+            Span::styled(format!("  {}  ", Icon::Logout), Style::default()),
             Span::styled(
                 "Logout",
                 Style::default().fg(if has_token {
@@ -425,18 +750,18 @@ fn render_device_auth(f: &mut Frame, area: Rect, auth: &DeviceAuthState) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(3), // Title
-            Constraint::Length(5), // Instructions
-            Constraint::Length(5), // Code display
-            Constraint::Length(3), // URL
-            Constraint::Length(3), // Status
-            Constraint::Min(1),    // Spacer
-            Constraint::Length(2), // Keys
+            Constraint::Length(zen::chrome(3)), // Title
+            Constraint::Length(5),              // Instructions
+            Constraint::Length(5),              // Code display
+            Constraint::Length(3),              // URL
+            Constraint::Length(3),              // Status
+            Constraint::Min(1),                 // Spacer
+            Constraint::Length(zen::chrome(2)), // Keys
         ])
         .split(area);
 
     let title = Paragraph::new(Line::from(vec![
-        Span::styled("  🔑 ", Style::default()),
+        Span::styled(format!("  {} ", Icon::Key), Style::default()),
         Span::styled(
             "Login with GitHub",
             Style::default()
@@ -551,17 +876,20 @@ fn render_create_repo(f: &mut Frame, area: Rect, state: &GitHubState) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(3), // Title
-            Constraint::Length(3), // Name
-            Constraint::Length(3), // Description
-            Constraint::Length(3), // Visibility
-            Constraint::Length(3), // Submit
-            Constraint::Min(1),    // Spacer
+            Constraint::Length(zen::chrome(3)), // Title
+            Constraint::Length(3),              // Name
+            Constraint::Length(3),              // Description
+            Constraint::Length(3),              // Visibility
+            Constraint::Length(3),              // .gitignore
+            Constraint::Length(3),              // License
+            Constraint::Length(3),              // README
+            Constraint::Length(3),              // Submit
+            Constraint::Min(1),                 // Spacer
         ])
         .split(area);
 
     let title = Paragraph::new(Span::styled(
-        "  📦 Create GitHub Repository",
+        format!("  {} Create GitHub Repository", Icon::Repo),
         Style::default()
             .fg(Color::White)
             .add_modifier(Modifier::BOLD),
@@ -607,9 +935,9 @@ fn render_create_repo(f: &mut Frame, area: Rect, state: &GitHubState) {
         Span::styled("  Visibility: ", Style::default().fg(Color::DarkGray)),
         Span::styled(
             if state.repo_private {
-                "Private 🔒"
+                format!("Private {}", Icon::Lock)
             } else {
-                "Public 🌍"
+                format!("Public {}", Icon::Globe)
             },
             Style::default().fg(if state.repo_private {
                 Color::Yellow
@@ -626,8 +954,66 @@ fn render_create_repo(f: &mut Frame, area: Rect, state: &GitHubState) {
     );
     f.render_widget(vis, chunks[3]);
 
+    let choice = |label: &str, value: Option<&str>, idx: usize| {
+        Paragraph::new(Line::from(vec![
+            Span::styled(
+                format!("  {}: ", label),
+                Style::default().fg(Color::DarkGray),
+            ),
+            Span::styled(
+                value.unwrap_or("None").to_string(),
+                Style::default().fg(if value.is_some() {
+                    Color::White
+                } else {
+                    Color::DarkGray
+                }),
+            ),
+            Span::styled(
+                "  (←/→ to choose, type a letter to jump)",
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(field_style(idx)),
+        )
+    };
+    let gitignore = state
+        .gitignore_choice
+        .checked_sub(1)
+        .and_then(|i| state.gitignore_templates.get(i))
+        .map(|s| s.as_str());
+    f.render_widget(choice(".gitignore", gitignore, 3), chunks[4]);
+    let license = state
+        .license_choice
+        .checked_sub(1)
+        .and_then(|i| state.licenses.get(i))
+        .map(|l| l.name.as_str());
+    f.render_widget(choice("License", license, 4), chunks[5]);
+
+    let readme = Paragraph::new(Line::from(vec![
+        Span::styled("  README: ", Style::default().fg(Color::DarkGray)),
+        Span::styled(
+            if state.repo_readme { "[x]" } else { "[ ]" },
+            Style::default().fg(Color::White),
+        ),
+        Span::styled("  (Space to toggle)", Style::default().fg(Color::DarkGray)),
+    ]))
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(field_style(5)),
+    );
+    f.render_widget(readme, chunks[6]);
+
+    let submit_label = if gitignore.is_some() || license.is_some() || state.repo_readme {
+        "  [Enter] Create Repository, commit scaffold and push"
+    } else {
+        "  [Enter] Create Repository"
+    };
     let submit = Paragraph::new(Span::styled(
-        "  [Enter] Create Repository",
+        submit_label,
         Style::default()
             .fg(Color::Green)
             .add_modifier(Modifier::BOLD),
@@ -635,24 +1021,24 @@ fn render_create_repo(f: &mut Frame, area: Rect, state: &GitHubState) {
     .block(
         Block::default()
             .borders(Borders::ALL)
-            .border_style(field_style(3)),
+            .border_style(field_style(CREATE_SUBMIT_FIELD)),
     );
-    f.render_widget(submit, chunks[4]);
+    f.render_widget(submit, chunks[7]);
 }
 
 fn render_collaborators(f: &mut Frame, area: Rect, state: &mut GitHubState) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(3), // Title
-            Constraint::Min(6),    // List
-            Constraint::Length(2), // Keys
-            Constraint::Length(2), // Status/Error
+            Constraint::Length(zen::chrome(3)), // Title
+            Constraint::Min(6),                 // List
+            Constraint::Length(zen::chrome(2)), // Keys
+            Constraint::Length(2),              // Status/Error
         ])
         .split(area);
 
     let title = Paragraph::new(Line::from(vec![
-        Span::styled("  👥 ", Style::default()),
+        Span::styled(format!("  {} ", Icon::Users), Style::default()),
         Span::styled(
             "Collaborators",
             Style::default()
@@ -721,7 +1107,8 @@ fn render_collaborators(f: &mut Frame, area: Rect, state: &mut GitHubState) {
             )
             .highlight_symbol("▶ ");
 
-        f.render_stateful_widget(list, chunks[1], &mut state.collab_list_state);
+        f.render_stateful_widget(&list, chunks[1], &mut state.collab_list_state);
+        render_scrollbar(f, chunks[1], list.len(), state.collab_list_state.offset());
     }
 
     // Keys
@@ -747,6 +1134,25 @@ fn render_collaborators(f: &mut Frame, area: Rect, state: &mut GitHubState) {
     }
 }
 
+/// The part of the GitHub view that's open, for the breadcrumb.
+pub fn location(state: &GitHubState) -> Vec<String> {
+    match &state.view {
+        GitHubView::Menu => Vec::new(),
+        GitHubView::DeviceAuth(_) => vec!["Login".to_string()],
+        GitHubView::CreateRepo => vec!["Create Repository".to_string()],
+        GitHubView::Collaborators => vec!["Collaborators".to_string()],
+        GitHubView::PullRequests => vec!["Pull Requests".to_string()],
+        GitHubView::PullRequestDetail(number) => {
+            vec!["Pull Requests".to_string(), format!("#{}", number)]
+        }
+        GitHubView::NewPullRequest => vec!["Pull Requests".to_string(), "New".to_string()],
+        GitHubView::Issues => vec!["Issues".to_string()],
+        GitHubView::IssueDetail(number) => vec!["Issues".to_string(), format!("#{}", number)],
+        GitHubView::Actions => vec!["CI Runs".to_string()],
+        GitHubView::ActionDetail(id) => vec!["CI Runs".to_string(), format!("Run {}", id)],
+    }
+}
+
 pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()> {
     let view = app.github_state.view.clone();
     match view {
@@ -756,6 +1162,9 @@ pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()
         GitHubView::Collaborators => handle_collaborators_key(app, key),
         GitHubView::PullRequests => handle_pull_requests_key(app, key),
         GitHubView::PullRequestDetail(_) => handle_pr_detail_key(app, key),
+        GitHubView::NewPullRequest => handle_new_pr_key(app, key),
+        GitHubView::Issues => handle_issues_key(app, key),
+        GitHubView::IssueDetail(_) => handle_issue_detail_key(app, key),
         GitHubView::Actions => handle_actions_key(app, key),
         GitHubView::ActionDetail(_) => handle_action_detail_key(app, key),
     }
@@ -763,19 +1172,15 @@ pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()
 
 fn handle_menu_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()> {
     match key.code {
-        KeyCode::Up | KeyCode::Char('k') => {
-            if app.github_state.menu_selected > 0 {
-                app.github_state.menu_selected -= 1;
-                let sel = app.github_state.menu_selected;
-                app.github_state.menu_state.select(Some(sel));
-            }
+        KeyCode::Up | KeyCode::Char('k') if app.github_state.menu_selected > 0 => {
+            app.github_state.menu_selected -= 1;
+            let sel = app.github_state.menu_selected;
+            app.github_state.menu_state.select(Some(sel));
         }
-        KeyCode::Down | KeyCode::Char('j') => {
-            if app.github_state.menu_selected < 8 {
-                app.github_state.menu_selected += 1;
-                let sel = app.github_state.menu_selected;
-                app.github_state.menu_state.select(Some(sel));
-            }
+        KeyCode::Down | KeyCode::Char('j') if app.github_state.menu_selected < 11 => {
+            app.github_state.menu_selected += 1;
+            let sel = app.github_state.menu_selected;
+            app.github_state.menu_state.select(Some(sel));
         }
         KeyCode::Enter => {
             match app.github_state.menu_selected {
@@ -796,59 +1201,48 @@ fn handle_menu_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<(
                     app.github_state.repo_private = true;
                     app.github_state.create_field = 0;
                     app.github_state.editing_field = true;
+                    app.github_state.gitignore_choice = 0;
+                    app.github_state.license_choice = 0;
+                    app.github_state.repo_readme = false;
+                    load_repo_templates(app);
                 }
                 2 => {
-                    // Push — run in background thread
+                    // Push — stream output into the drawer
                     if let Ok(branch) = git::BranchOps::current() {
-                        app.github_state.status = Some("⏳ Pushing...".to_string());
-                        let bg = app.github_state.bg_result.clone();
-                        let br = branch.clone();
-                        std::thread::spawn(move || {
-                            let result = match git::RemoteOps::push("origin", &br, true) {
-                                Ok(_) => format!("✓ Pushed to origin/{}", br),
-                                Err(e) => format!("Push failed: {}", e),
-                            };
-                            if let Ok(mut r) = bg.lock() {
-                                *r = Some(result);
-                            }
+                        app.github_state.status = Some(format!("{} Pushing...", Icon::Loading));
+                        app.start_push(OutputJob {
+                            title: format!("push origin/{}", branch),
+                            commands: vec![git::RemoteOps::push_args("origin", &branch, true)],
+                            success_msg: format!("✓ Pushed to origin/{}", branch),
+                            failure_msg: "Push failed".to_string(),
                         });
                     }
                 }
                 3 => {
-                    // Pull — run in background thread
+                    // Pull — stream output into the drawer
                     if let Ok(branch) = git::BranchOps::current() {
-                        app.github_state.status = Some("⏳ Pulling...".to_string());
-                        let bg = app.github_state.bg_result.clone();
-                        let br = branch.clone();
-                        std::thread::spawn(move || {
-                            let result = match git::RemoteOps::pull("origin", &br) {
-                                Ok(_) => format!("✓ Pulled from origin/{}", br),
-                                Err(e) => format!("Pull failed: {}", e),
-                            };
-                            if let Ok(mut r) = bg.lock() {
-                                *r = Some(result);
-                            }
+                        app.github_state.status = Some(format!("{} Pulling...", Icon::Loading));
+                        app.start_output_job(OutputJob {
+                            title: format!("pull origin/{}", branch),
+                            commands: vec![git::RemoteOps::pull_args("origin", &branch)],
+                            success_msg: format!("✓ Pulled from origin/{}", branch),
+                            failure_msg: "Pull failed".to_string(),
                         });
                     }
                 }
                 4 => {
-                    // Sync — pull then push in background
+                    // Sync — pull then push, stopping if the pull fails
                     if let Ok(branch) = git::BranchOps::current() {
-                        app.github_state.status = Some("⏳ Syncing (pull + push)...".to_string());
-                        let bg = app.github_state.bg_result.clone();
-                        let br = branch.clone();
-                        std::thread::spawn(move || {
-                            let pull = git::RemoteOps::pull("origin", &br);
-                            let result = match pull {
-                                Ok(_) => match git::RemoteOps::push("origin", &br, true) {
-                                    Ok(_) => format!("✓ Synced with origin/{}", br),
-                                    Err(e) => format!("Push failed after pull: {}", e),
-                                },
-                                Err(e) => format!("Pull failed: {}", e),
-                            };
-                            if let Ok(mut r) = bg.lock() {
-                                *r = Some(result);
-                            }
+                        app.github_state.status =
+                            Some(format!("{} Syncing (pull + push)...", Icon::Loading));
+                        app.start_push(OutputJob {
+                            title: format!("sync origin/{}", branch),
+                            commands: vec![
+                                git::RemoteOps::pull_args("origin", &branch),
+                                git::RemoteOps::push_args("origin", &branch, true),
+                            ],
+                            success_msg: format!("✓ Synced with origin/{}", branch),
+                            failure_msg: "Sync failed".to_string(),
                         });
                     }
                 }
@@ -873,18 +1267,36 @@ fn handle_menu_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<(
                     app.github_state.view = GitHubView::PullRequests;
                 }
                 7 => {
-                    // Actions
                     if app.config.github.get_token().is_none() {
-                        app.github_state.status = Some("Login first to view Actions".to_string());
+                        app.github_state.status = Some("Login first to view issues".to_string());
                         return Ok(());
                     }
+                    start_load_issues(app);
+                    app.github_state.view = GitHubView::Issues;
+                }
+                8 => {
+                    // CI Runs
+                    if app.config.github.get_token().is_none() {
+                        app.github_state.status = Some("Login first to view CI runs".to_string());
+                        return Ok(());
+                    }
+                    app.github_state.actions_state.branch = git::cherry_pick::get_current_branch();
                     start_load_actions(app);
                     app.github_state.view = GitHubView::Actions;
                 }
-                8 => {
+                9 => check_contributing(app),
+                10 => {
+                    if app.config.github.get_token().is_none() {
+                        app.github_state.status =
+                            Some("Login first to draft release notes".to_string());
+                        return Ok(());
+                    }
+                    draft_release_notes(app);
+                }
+                11 => {
                     // Logout — clear keychain and config
                     if app.config.github.get_token().is_some() {
-                        crate::keychain::clear_all();
+                        crate::keychain::clear_github();
                         app.config.github.oauth_token = None;
                         app.config.github.pat = None;
                         app.config.github.username = None;
@@ -901,6 +1313,22 @@ fn handle_menu_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<(
             // Quick auth shortcut
             start_device_flow(app);
         }
+        KeyCode::Char('F') => {
+            app.start_output_job(OutputJob {
+                title: "fetch --all".to_string(),
+                commands: vec![git::RemoteOps::fetch_all_args()],
+                success_msg: "✓ Fetched all remotes".to_string(),
+                failure_msg: "Fetch failed".to_string(),
+            });
+        }
+        KeyCode::Char('G') => {
+            app.start_output_job(OutputJob {
+                title: "gc".to_string(),
+                commands: vec![vec!["gc".to_string(), "--progress".to_string()]],
+                success_msg: "✓ Repository garbage-collected".to_string(),
+                failure_msg: "gc failed".to_string(),
+            });
+        }
         _ => {}
     }
 
@@ -936,30 +1364,60 @@ fn handle_device_auth_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::R
 /// Called on every tick event to poll GitHub for authorization status.
 pub fn tick_device_auth(app: &mut crate::app::App) {
     // Check for background operation results (push/pull/sync)
-    if let Ok(mut result) = app.github_state.bg_result.try_lock() {
-        if let Some(msg) = result.take() {
-            app.github_state.status = Some(msg);
-        }
+    if let Ok(mut result) = app.github_state.bg_result.try_lock()
+        && let Some(msg) = result.take()
+    {
+        app.github_state.status = Some(msg);
     }
 
-    let (device_code, _interval) = {
-        if let GitHubView::DeviceAuth(ref mut auth) = app.github_state.view {
-            auth.ticks_since_poll += 1;
-            // interval is in seconds, tick_rate is in ms (typically 2000ms = 2s)
-            // We need to wait `interval` seconds between polls
-            let ticks_needed = (auth.interval * 1000) / app.config.general.tick_rate_ms;
-            let ticks_needed = ticks_needed.max(1);
-            if auth.ticks_since_poll < ticks_needed {
-                return; // Not time to poll yet
+    let polled = app
+        .github_state
+        .device_poll
+        .try_lock()
+        .ok()
+        .and_then(|mut r| r.take());
+    let result = match polled {
+        Some((code, result)) => {
+            app.github_state.device_poll_in_flight = false;
+            match &app.github_state.view {
+                GitHubView::DeviceAuth(auth) if auth.device_code == code => result,
+                _ => return, // Answer for a flow that was cancelled or restarted
             }
-            auth.ticks_since_poll = 0;
-            (auth.device_code.clone(), auth.interval)
-        } else {
-            return; // Not in device auth view
+        }
+        None => {
+            if app.github_state.device_poll_in_flight {
+                return;
+            }
+            let device_code = {
+                if let GitHubView::DeviceAuth(ref mut auth) = app.github_state.view {
+                    auth.ticks_since_poll += 1;
+                    // interval is in seconds, tick_rate is in ms (typically 2000ms = 2s)
+                    // We need to wait `interval` seconds between polls
+                    let ticks_needed = (auth.interval * 1000) / app.config.general.tick_rate_ms;
+                    let ticks_needed = ticks_needed.max(1);
+                    if auth.ticks_since_poll < ticks_needed {
+                        return; // Not time to poll yet
+                    }
+                    auth.ticks_since_poll = 0;
+                    auth.device_code.clone()
+                } else {
+                    return; // Not in device auth view
+                }
+            };
+            app.github_state.device_poll_in_flight = true;
+            let slot = Arc::clone(&app.github_state.device_poll);
+            app.jobs
+                .submit("device-auth", Priority::Background, move || {
+                    let result = git::github_auth::poll_for_token(&device_code);
+                    if let Ok(mut r) = slot.lock() {
+                        *r = Some((device_code, result));
+                    }
+                });
+            return;
         }
     };
 
-    match git::github_auth::poll_for_token(&device_code) {
+    match result {
         git::github_auth::PollResult::Pending => {
             if let GitHubView::DeviceAuth(ref mut auth) = app.github_state.view {
                 auth.status = "Waiting for you to authorize in browser...".to_string();
@@ -1014,17 +1472,44 @@ fn handle_create_repo_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::R
             app.github_state.view = GitHubView::Menu;
         }
         KeyCode::Tab | KeyCode::Down => {
-            app.github_state.create_field = (app.github_state.create_field + 1).min(3);
+            app.github_state.create_field =
+                (app.github_state.create_field + 1).min(CREATE_SUBMIT_FIELD);
         }
-        KeyCode::BackTab | KeyCode::Up => {
-            if app.github_state.create_field > 0 {
-                app.github_state.create_field -= 1;
-            }
+        KeyCode::BackTab | KeyCode::Up if app.github_state.create_field > 0 => {
+            app.github_state.create_field -= 1;
         }
         KeyCode::Char(' ') if app.github_state.create_field == 2 => {
             app.github_state.repo_private = !app.github_state.repo_private;
         }
-        KeyCode::Enter if app.github_state.create_field == 3 => {
+        KeyCode::Char(' ') if app.github_state.create_field == 5 => {
+            app.github_state.repo_readme = !app.github_state.repo_readme;
+        }
+        KeyCode::Left | KeyCode::Right | KeyCode::Char(_)
+            if matches!(app.github_state.create_field, 3 | 4) =>
+        {
+            let state = &mut app.github_state;
+            let names: Vec<&str> = if state.create_field == 3 {
+                state
+                    .gitignore_templates
+                    .iter()
+                    .map(|s| s.as_str())
+                    .collect()
+            } else {
+                state.licenses.iter().map(|l| l.name.as_str()).collect()
+            };
+            let current = if state.create_field == 3 {
+                state.gitignore_choice
+            } else {
+                state.license_choice
+            };
+            let next = cycle_choice(&names, current, key.code);
+            if state.create_field == 3 {
+                state.gitignore_choice = next;
+            } else {
+                state.license_choice = next;
+            }
+        }
+        KeyCode::Enter if app.github_state.create_field == CREATE_SUBMIT_FIELD => {
             let name = app.github_state.repo_name.trim().to_string();
             if name.is_empty() {
                 app.github_state.status = Some("Repository name cannot be empty".to_string());
@@ -1041,6 +1526,7 @@ fn handle_create_repo_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::R
                         app.github_state.status =
                             Some(format!("✓ Created '{}' and added as origin", name));
                         app.github_state.view = GitHubView::Menu;
+                        commit_scaffold(app, &token, &name, &desc);
                     }
                     Err(e) => {
                         app.github_state.status = Some(format!("Error: {}", e));
@@ -1070,6 +1556,217 @@ fn handle_create_repo_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::R
     Ok(())
 }
 
+/// Index of the Create button in the Create Repository form.
+const CREATE_SUBMIT_FIELD: usize = 6;
+
+/// Next choice for a ←/→ or type-ahead key in a `None` + `names` selector, where
+/// 0 is none and `i + 1` is `names[i]`.
+fn cycle_choice(names: &[&str], current: usize, code: KeyCode) -> usize {
+    let count = names.len() + 1;
+    match code {
+        KeyCode::Right => (current + 1) % count,
+        KeyCode::Left => (current + count - 1) % count,
+        KeyCode::Char(c) => {
+            // Next option starting with the letter, wrapping around
+            let c = c.to_ascii_lowercase();
+            (1..count)
+                .map(|offset| (current + offset) % count)
+                .find(|&i| {
+                    i > 0
+                        && names[i - 1]
+                            .chars()
+                            .next()
+                            .is_some_and(|f| f.to_ascii_lowercase() == c)
+                })
+                .unwrap_or(current)
+        }
+        _ => current,
+    }
+}
+
+/// Fetch the .gitignore and license lists for the Create Repository form, once.
+fn load_repo_templates(app: &mut crate::app::App) {
+    let Some(token) = app.config.github.get_token() else {
+        return;
+    };
+    let state = &mut app.github_state;
+    if state.gitignore_templates.is_empty() {
+        match git::github_auth::list_gitignore_templates(&token) {
+            Ok(templates) => state.gitignore_templates = templates,
+            Err(e) => state.status = Some(format!("Couldn't load .gitignore templates: {}", e)),
+        }
+    }
+    if state.licenses.is_empty() {
+        match git::github_auth::list_licenses(&token) {
+            Ok(licenses) => state.licenses = licenses,
+            Err(e) => state.status = Some(format!("Couldn't load licenses: {}", e)),
+        }
+    }
+}
+
+/// Write and commit the chosen scaffold files for a just-created repository, then push.
+fn commit_scaffold(app: &mut crate::app::App, token: &str, name: &str, desc: &str) {
+    let state = &app.github_state;
+    let gitignore = state
+        .gitignore_choice
+        .checked_sub(1)
+        .and_then(|i| state.gitignore_templates.get(i))
+        .cloned();
+    let license = state
+        .license_choice
+        .checked_sub(1)
+        .and_then(|i| state.licenses.get(i))
+        .map(|l| l.key.clone());
+    let readme = state.repo_readme;
+    if gitignore.is_none() && license.is_none() && !readme {
+        return;
+    }
+
+    let files = (|| -> anyhow::Result<Vec<(&str, String)>> {
+        let mut files = Vec::new();
+        if let Some(template) = &gitignore {
+            files.push((
+                ".gitignore",
+                git::github_auth::get_gitignore_template(token, template)?,
+            ));
+        }
+        if let Some(key) = &license {
+            let body = git::github_auth::get_license_body(token, key)?;
+            let fullname = git::run_git(&["config", "user.name"]).unwrap_or_default();
+            let year = jiff::Zoned::now().year();
+            files.push((
+                "LICENSE",
+                git::scaffold::fill_license(&body, year, fullname.trim()),
+            ));
+        }
+        if readme {
+            files.push(("README.md", git::scaffold::readme_stub(name, desc)));
+        }
+        Ok(files)
+    })();
+
+    let written = files.and_then(|files| git::scaffold::commit_files(&files));
+    match written {
+        Ok(written) if written.is_empty() => {
+            app.github_state.status = Some(format!(
+                "✓ Created '{}' — scaffold files already exist, nothing committed",
+                name
+            ));
+        }
+        Ok(written) => {
+            app.github_state.status = Some(format!(
+                "✓ Created '{}' and committed {}",
+                name,
+                written.join(", ")
+            ));
+            if let Ok(branch) = git::BranchOps::current() {
+                app.start_push(OutputJob {
+                    title: format!("push origin/{}", branch),
+                    commands: vec![git::RemoteOps::push_args("origin", &branch, true)],
+                    success_msg: format!("✓ Pushed scaffold to origin/{}", branch),
+                    failure_msg: "Push failed".to_string(),
+                });
+            }
+        }
+        Err(e) => {
+            app.github_state.status = Some(format!(
+                "Created '{}', but the scaffold failed: {}",
+                name, e
+            ));
+        }
+    }
+}
+
+/// Detect what the project expects from a first PR and show it, offering an AI
+/// summary of the contributing guide when AI is set up.
+fn check_contributing(app: &mut crate::app::App) {
+    // CLA bots only show up in PR comments; without a token the check stays local
+    let commenters = app
+        .config
+        .github
+        .get_token()
+        .and_then(|token| git::github_auth::recent_comment_authors(&token).ok())
+        .unwrap_or_default();
+    let readiness = git::contributing::check(&commenters);
+    let report = readiness.report();
+
+    if app.ai_client.is_none() {
+        app.popup = crate::app::Popup::Message {
+            title: format!("{} Contributing Readiness", Icon::Shield),
+            message: report,
+        };
+        return;
+    }
+    let description = if readiness.has_requirements() {
+        "Turn these requirements and the guide into a checklist"
+    } else {
+        "Check the guide for anything the scan missed"
+    };
+    app.popup = crate::app::Popup::FollowUp {
+        title: format!("{} Contributing Readiness", Icon::Shield),
+        context: report.clone(),
+        suggestions: vec![crate::app::FollowUpItem {
+            label: "Summarize with AI".to_string(),
+            description: description.to_string(),
+            action: crate::app::FollowUpAction::SummarizeContributing(report, readiness.guide_text),
+        }],
+        selected: 0,
+    };
+}
+
+/// Draft release notes from the pull requests merged since the last tag and show
+/// them with options to copy or polish them.
+fn draft_release_notes(app: &mut crate::app::App) {
+    let Some(token) = app.config.github.get_token() else {
+        return;
+    };
+    let tag = git::release_notes::last_tag();
+    let since = tag
+        .as_ref()
+        .map_or("1970-01-01T00:00:00Z", |(_, date)| date.as_str());
+    let base = git::BranchOps::default_branch();
+    let prs = match git::github_auth::search_merged_pull_requests(&token, since, base.as_deref()) {
+        Ok(prs) => prs,
+        Err(e) => {
+            app.github_state.status = Some(format!("Error: {}", e));
+            return;
+        }
+    };
+    let compare_url = match (&tag, &base, git::github_auth::parse_repo_from_remote()) {
+        (Some((tag, _)), Some(base), Ok((owner, repo))) => Some(format!(
+            "https://github.com/{}/{}/compare/{}...{}",
+            owner, repo, tag, base
+        )),
+        _ => None,
+    };
+    let notes = git::release_notes::draft(&prs, compare_url.as_deref());
+    let since_label = tag.map_or("the first release".to_string(), |(tag, _)| tag);
+
+    let mut suggestions = vec![crate::app::FollowUpItem {
+        label: "Copy to clipboard".to_string(),
+        description: "Paste into a new GitHub release".to_string(),
+        action: crate::app::FollowUpAction::CopyToClipboard(notes.clone()),
+    }];
+    if app.ai_client.is_some() {
+        suggestions.push(crate::app::FollowUpItem {
+            label: "Polish with AI".to_string(),
+            description: "Tidy titles and add a short summary".to_string(),
+            action: crate::app::FollowUpAction::PolishReleaseNotes(notes.clone()),
+        });
+    }
+    app.popup = crate::app::Popup::FollowUp {
+        title: format!(
+            "{} Release Notes — {} PR(s) since {}",
+            Icon::File,
+            prs.len(),
+            since_label
+        ),
+        context: notes,
+        suggestions,
+        selected: 0,
+    };
+}
+
 fn load_collaborators(app: &mut crate::app::App) {
     if let Some(token) = app.config.github.get_token() {
         match git::github_auth::list_collaborators(&token) {
@@ -1099,21 +1796,18 @@ fn handle_collaborators_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow:
             app.github_state.view = GitHubView::Menu;
             app.github_state.collab_error = None;
         }
-        KeyCode::Up | KeyCode::Char('k') => {
-            if app.github_state.collab_selected > 0 {
-                app.github_state.collab_selected -= 1;
-                let sel = app.github_state.collab_selected;
-                app.github_state.collab_list_state.select(Some(sel));
-            }
+        KeyCode::Up | KeyCode::Char('k') if app.github_state.collab_selected > 0 => {
+            app.github_state.collab_selected -= 1;
+            let sel = app.github_state.collab_selected;
+            app.github_state.collab_list_state.select(Some(sel));
         }
-        KeyCode::Down | KeyCode::Char('j') => {
+        KeyCode::Down | KeyCode::Char('j')
             if !app.github_state.collaborators.is_empty()
-                && app.github_state.collab_selected + 1 < app.github_state.collaborators.len()
-            {
-                app.github_state.collab_selected += 1;
-                let sel = app.github_state.collab_selected;
-                app.github_state.collab_list_state.select(Some(sel));
-            }
+                && app.github_state.collab_selected + 1 < app.github_state.collaborators.len() =>
+        {
+            app.github_state.collab_selected += 1;
+            let sel = app.github_state.collab_selected;
+            app.github_state.collab_list_state.select(Some(sel));
         }
         KeyCode::Char('a') => {
             // Add collaborator via input popup
@@ -1154,7 +1848,8 @@ fn start_load_prs(app: &mut crate::app::App) {
     let token = app.config.github.get_token().unwrap_or_default();
     let filter = app.github_state.pr_state.filter.api_state().to_string();
     let bg = app.github_state.pr_state.bg_result.clone();
-    std::thread::spawn(move || {
+    let key = format!("prs:{}", filter);
+    app.jobs.submit(key, Priority::User, move || {
         let result =
             git::github_auth::list_pull_requests(&token, &filter).map_err(|e| e.to_string());
         if let Ok(mut r) = bg.lock() {
@@ -1163,15 +1858,77 @@ fn start_load_prs(app: &mut crate::app::App) {
     });
 }
 
+/// Avatar size requested from GitHub; drawn as two half-block cells.
+const AVATAR_PIXELS: u32 = 8;
+
+/// Fetch avatars for PR authors not seen yet (`[ui] github_avatars`, truecolor only).
+fn start_load_avatars(app: &mut crate::app::App) {
+    if !app.config.ui.github_avatars || theme::mode() != theme::ColorMode::TrueColor {
+        return;
+    }
+    let avatars = app.github_state.pr_state.avatars.clone();
+    let mut users: Vec<(String, String)> = Vec::new();
+    if let Ok(known) = avatars.lock() {
+        for pr in &app.github_state.pr_state.prs {
+            let user = &pr.user;
+            if !user.avatar_url.is_empty()
+                && !known.contains_key(&user.login)
+                && !users.iter().any(|(login, _)| *login == user.login)
+            {
+                users.push((user.login.clone(), user.avatar_url.clone()));
+            }
+        }
+    }
+    if users.is_empty() {
+        return;
+    }
+    app.jobs.submit("avatars", Priority::Background, move || {
+        for (login, url) in users {
+            let image = git::github_auth::get_avatar(&url, AVATAR_PIXELS)
+                .ok()
+                .and_then(|bytes| image_preview::decode(&bytes));
+            if let Ok(mut known) = avatars.lock() {
+                known.insert(login, image);
+            }
+        }
+    });
+}
+
+/// The author's avatar as two half-block cells when one was downloaded, else
+/// their identicon (`[ui] identicons`), followed by a space.
+fn author_mark(
+    pr_state: &PullRequestsState,
+    login: &str,
+    ui: &crate::config::UiConfig,
+) -> Vec<Span<'static>> {
+    if ui.github_avatars
+        && let Ok(known) = pr_state.avatars.lock()
+        && let Some(Some(image)) = known.get(login)
+        && let Some(line) = image_preview::preview_lines(image, 2, 1).into_iter().next()
+    {
+        let mut spans = line.spans;
+        spans.push(Span::raw(" "));
+        return spans;
+    }
+    if ui.identicons {
+        vec![identicon::span(login)]
+    } else {
+        Vec::new()
+    }
+}
+
 fn start_load_pr_detail(app: &mut crate::app::App, number: u64) {
     app.github_state.pr_state.loading = true;
     app.github_state.pr_state.error = None;
     app.github_state.pr_state.detail_tab = PrDetailTab::Overview;
     app.github_state.pr_state.detail_scroll = 0;
     app.github_state.pr_state.files_selected = 0;
+    app.github_state.pr_state.patch_focus = false;
+    app.github_state.pr_state.patch_line = 0;
     let token = app.config.github.get_token().unwrap_or_default();
     let bg = app.github_state.pr_state.bg_result.clone();
-    std::thread::spawn(move || {
+    let key = format!("pr-detail:{}", number);
+    app.jobs.submit(key, Priority::User, move || {
         let pr = git::github_auth::get_pull_request(&token, number).map_err(|e| e.to_string());
         let sha = pr.as_ref().map(|p| p.head.sha.clone()).unwrap_or_default();
         let checks = git::github_auth::get_check_runs(&token, &sha).map_err(|e| e.to_string());
@@ -1203,6 +1960,7 @@ pub fn tick_pr_state(app: &mut crate::app::App) {
         match bg {
             PrBgResult::PrList(Ok(prs)) => {
                 app.github_state.pr_state.prs = prs;
+                start_load_avatars(app);
                 app.github_state.pr_state.selected = 0;
                 app.github_state.pr_state.list_state.select(
                     if app.github_state.pr_state.prs.is_empty() {
@@ -1222,6 +1980,7 @@ pub fn tick_pr_state(app: &mut crate::app::App) {
                 files,
                 reviews,
             } => {
+                app.github_state.pr_state.codeowners = git::codeowners::load();
                 match pr {
                     Ok(p) => {
                         app.github_state.pr_state.detail_pr = Some(p);
@@ -1271,33 +2030,198 @@ pub fn tick_pr_state(app: &mut crate::app::App) {
             PrBgResult::CloseResult(Err(e)) => {
                 app.github_state.pr_state.error = Some(format!("Close failed: {}", e));
             }
+            PrBgResult::Created(Ok(pr)) => {
+                app.github_state.pr_state.new_pr = None;
+                app.github_state.view = GitHubView::PullRequestDetail(pr.number);
+                app.set_status(format!("✓ Opened pull request #{}", pr.number));
+                start_load_pr_detail(app, pr.number);
+            }
+            PrBgResult::Reviewed(number, Ok(message)) => {
+                app.set_status(message);
+                if app.github_state.view == GitHubView::PullRequestDetail(number) {
+                    start_load_pr_detail(app, number);
+                    app.github_state.pr_state.detail_tab = PrDetailTab::Reviews;
+                }
+            }
+            PrBgResult::LineCommented(Ok(message)) => app.set_status(message),
+            PrBgResult::Reviewed(_, Err(e)) | PrBgResult::LineCommented(Err(e)) => {
+                app.github_state.pr_state.error = Some(e);
+            }
+            PrBgResult::Created(Err(e)) => {
+                if let Some(form) = app.github_state.pr_state.new_pr.as_mut() {
+                    form.submitting = false;
+                }
+                app.github_state.pr_state.error = Some(e);
+            }
         }
     }
 }
 
-// ─── Pull Request List Rendering ────────────────────────────────
+// ─── Watching a Pull Request ────────────────────────────────
 
-fn render_pull_requests(f: &mut Frame, area: Rect, state: &mut GitHubState) {
-    let chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Length(3), // Title
-            Constraint::Length(3), // Filter bar
-            Constraint::Min(6),    // PR list
-            Constraint::Length(2), // Keys
-            Constraint::Length(2), // Status/Error
-        ])
-        .split(area);
+/// Start watching the open PR in the detail view, or stop if it's already watched.
+fn toggle_watch(app: &mut crate::app::App, auto_merge: bool) {
+    let Some(pr) = app.github_state.pr_state.detail_pr.as_ref() else {
+        return;
+    };
+    if pr.state != "open" {
+        app.github_state.pr_state.error = Some("Only open pull requests can be watched".into());
+        return;
+    }
+    let number = pr.number;
+    if let Some(watch) = &app.github_state.watch
+        && watch.number == number
+        && watch.auto_merge == auto_merge
+    {
+        app.github_state.watch = None;
+        app.set_status(format!("Stopped watching PR #{}", number));
+        return;
+    }
+    app.github_state.watch = Some(PrWatch {
+        number,
+        auto_merge,
+        merge_method: app.github_state.pr_state.merge_method.label().to_string(),
+        note: "Waiting for checks...".to_string(),
+        last_poll: None,
+        in_flight: false,
+        result: Arc::new(Mutex::new(None)),
+    });
+    app.set_status(if auto_merge {
+        format!(
+            "Watching PR #{} — will {} when checks pass",
+            number,
+            app.github_state.pr_state.merge_method.display()
+        )
+    } else {
+        format!("Watching PR #{} until its checks finish", number)
+    });
+}
 
-    // Title
-    let pr_count = state.pr_state.prs.len();
-    let title = Paragraph::new(Line::from(vec![
-        Span::styled("  🔀 ", Style::default()),
-        Span::styled(
-            "Pull Requests",
-            Style::default()
-                .fg(Color::White)
-                .add_modifier(Modifier::BOLD),
+/// Called on every tick, whatever the view: poll the watched PR and act on the result.
+pub fn tick_pr_watch(app: &mut crate::app::App) {
+    let Some(watch) = app.github_state.watch.as_mut() else {
+        return;
+    };
+    let taken = watch.result.try_lock().ok().and_then(|mut r| r.take());
+    let Some(result) = taken else {
+        let due = watch
+            .last_poll
+            .is_none_or(|t| t.elapsed() >= WATCH_INTERVAL);
+        if due && !watch.in_flight {
+            let Some(token) = app.config.github.get_token() else {
+                return;
+            };
+            watch.in_flight = true;
+            watch.last_poll = Some(Instant::now());
+            let number = watch.number;
+            let method = watch.auto_merge.then(|| watch.merge_method.clone());
+            let slot = Arc::clone(&watch.result);
+            let key = format!("pr-watch:{}", number);
+            app.jobs.submit(key, Priority::Background, move || {
+                let poll = git::github_auth::poll_watched_pr(&token, number, method.as_deref())
+                    .map(Box::new);
+                if let Ok(mut r) = slot.lock() {
+                    *r = Some(poll.map_err(|e| e.to_string()));
+                }
+            });
+        }
+        return;
+    };
+    watch.in_flight = false;
+    let number = watch.number;
+
+    let (checks, step) = match result {
+        Err(e) => {
+            // Transient API trouble: keep watching
+            watch.note = format!("Poll failed, retrying: {}", e);
+            return;
+        }
+        Ok(poll) => {
+            let (_, checks, step) = *poll;
+            (checks, step)
+        }
+    };
+    let pending = checks
+        .check_runs
+        .iter()
+        .filter(|r| r.status != "completed")
+        .count();
+    watch.note = format!(
+        "{} of {} checks done",
+        checks.check_runs.len() - pending,
+        checks.check_runs.len()
+    );
+    // Keep an open detail view of the same PR current
+    if app.github_state.view == GitHubView::PullRequestDetail(number) {
+        app.github_state.pr_state.detail_checks = Some(checks);
+    }
+
+    use git::github_auth::WatchStep;
+    let message = match step {
+        WatchStep::Wait | WatchStep::Merge => return,
+        WatchStep::Closed(merged) => format!(
+            "PR #{} {}",
+            number,
+            if merged { "was merged" } else { "was closed" }
+        ),
+        WatchStep::Failed(names) => format!("✗ PR #{}: {} failed", number, names.join(", ")),
+        WatchStep::Passed => format!("✓ PR #{}: all checks passed", number),
+        WatchStep::NotMerging(why) => format!(
+            "✓ PR #{}: all checks passed, not auto-merging ({})",
+            number, why
+        ),
+        WatchStep::Merged(Ok(resp)) if resp.merged => {
+            if app.github_state.view == GitHubView::PullRequestDetail(number) {
+                start_load_pr_detail(app, number);
+            }
+            format!("✓ PR #{} auto-merged", number)
+        }
+        WatchStep::Merged(Ok(resp)) => {
+            format!("PR #{} auto-merge failed: {}", number, resp.message)
+        }
+        WatchStep::Merged(Err(e)) => format!("PR #{} auto-merge failed: {}", number, e),
+    };
+    finish_watch(app, message);
+}
+
+/// Stop watching and tell the user how it ended, on the desktop too when enabled.
+fn finish_watch(app: &mut crate::app::App, message: String) {
+    app.github_state.watch = None;
+    if app.config.ui.desktop_notifications {
+        crate::ui::notify::desktop("zit", &message);
+    }
+    app.github_state.status = Some(message.clone());
+    app.set_status(message);
+}
+
+// ─── Pull Request List Rendering ────────────────────────────────
+
+fn render_pull_requests(
+    f: &mut Frame,
+    area: Rect,
+    state: &mut GitHubState,
+    ui: &crate::config::UiConfig,
+) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(zen::chrome(3)), // Title
+            Constraint::Length(3),              // Filter bar
+            Constraint::Min(6),                 // PR list
+            Constraint::Length(zen::chrome(2)), // Keys
+            Constraint::Length(2),              // Status/Error
+        ])
+        .split(area);
+
+    // Title
+    let pr_count = state.pr_state.prs.len();
+    let title = Paragraph::new(Line::from(vec![
+        Span::styled(format!("  {} ", Icon::PullRequest), Style::default()),
+        Span::styled(
+            "Pull Requests",
+            Style::default()
+                .fg(Color::White)
+                .add_modifier(Modifier::BOLD),
         ),
         Span::styled(
             format!(
@@ -1344,7 +2268,10 @@ fn render_pull_requests(f: &mut Frame, area: Rect, state: &mut GitHubState) {
     // PR list
     if state.pr_state.loading {
         let loading = Paragraph::new(Line::from(vec![
-            Span::styled("  ⏳ ", Style::default().fg(Color::Yellow)),
+            Span::styled(
+                format!("  {} ", Icon::Loading),
+                Style::default().fg(Color::Yellow),
+            ),
             Span::styled(
                 "Loading pull requests...",
                 Style::default().fg(Color::DarkGray),
@@ -1374,23 +2301,35 @@ fn render_pull_requests(f: &mut Frame, area: Rect, state: &mut GitHubState) {
             .iter()
             .map(|pr| {
                 let state_icon = if pr.draft {
-                    Span::styled("  📝 ", Style::default())
+                    Span::styled(
+                        format!("  {} ", Icon::Draft),
+                        Style::default().fg(Color::DarkGray),
+                    )
                 } else if pr.state == "open" {
-                    Span::styled("  🟢 ", Style::default())
+                    Span::styled(
+                        format!("  {} ", Icon::Open),
+                        Style::default().fg(Color::Green),
+                    )
                 } else if pr.merged_at.is_some() {
-                    Span::styled("  🟣 ", Style::default())
+                    Span::styled(
+                        format!("  {} ", Icon::Merged),
+                        Style::default().fg(Color::Magenta),
+                    )
                 } else {
-                    Span::styled("  🔴 ", Style::default())
+                    Span::styled(
+                        format!("  {} ", Icon::Closed),
+                        Style::default().fg(Color::Red),
+                    )
                 };
 
                 let number =
                     Span::styled(format!("#{} ", pr.number), Style::default().fg(Color::Cyan));
                 let title_text = Span::styled(
-                    pr.title.chars().take(60).collect::<String>(),
+                    truncate_to_width(&pr.title, 60),
                     Style::default().fg(Color::White),
                 );
                 let author = Span::styled(
-                    format!("  @{}", pr.user.login),
+                    format!("@{}", pr.user.login),
                     Style::default().fg(Color::DarkGray),
                 );
                 let stats = Span::styled(
@@ -1402,9 +2341,10 @@ fn render_pull_requests(f: &mut Frame, area: Rect, state: &mut GitHubState) {
                     Style::default().fg(Color::DarkGray),
                 );
 
-                ListItem::new(Line::from(vec![
-                    state_icon, number, title_text, author, stats,
-                ]))
+                let mut spans = vec![state_icon, number, title_text, Span::raw("  ")];
+                spans.extend(author_mark(&state.pr_state, &pr.user.login, ui));
+                spans.extend([author, stats]);
+                ListItem::new(Line::from(spans))
             })
             .collect();
 
@@ -1421,7 +2361,8 @@ fn render_pull_requests(f: &mut Frame, area: Rect, state: &mut GitHubState) {
             )
             .highlight_symbol("▶ ");
 
-        f.render_stateful_widget(list, chunks[2], &mut state.pr_state.list_state);
+        f.render_stateful_widget(&list, chunks[2], &mut state.pr_state.list_state);
+        render_scrollbar(f, chunks[2], list.len(), state.pr_state.list_state.offset());
     }
 
     // Keys
@@ -1430,6 +2371,8 @@ fn render_pull_requests(f: &mut Frame, area: Rect, state: &mut GitHubState) {
         Span::raw(" Open "),
         Span::styled("[f]", Style::default().fg(Color::Yellow)),
         Span::raw(" Filter "),
+        Span::styled("[n]", Style::default().fg(Color::Green)),
+        Span::raw(" New "),
         Span::styled("[r]", Style::default().fg(Color::Green)),
         Span::raw(" Refresh "),
         Span::styled("[Esc]", Style::default().fg(Color::DarkGray)),
@@ -1437,27 +2380,52 @@ fn render_pull_requests(f: &mut Frame, area: Rect, state: &mut GitHubState) {
     ]));
     f.render_widget(keys, chunks[3]);
 
-    // Error
+    // Error, else the watch state of this PR
     if let Some(ref err) = state.pr_state.error {
         let status = Paragraph::new(Span::styled(
             format!(" {}", err),
             Style::default().fg(Color::Red),
         ));
         f.render_widget(status, chunks[4]);
+    } else if let Some(watch) = state.watch.as_ref().filter(|w| {
+        state
+            .pr_state
+            .detail_pr
+            .as_ref()
+            .is_some_and(|p| p.number == w.number)
+    }) {
+        let label = if watch.auto_merge {
+            "Watching (auto-merge)"
+        } else {
+            "Watching"
+        };
+        let status = Paragraph::new(Line::from(vec![
+            Span::styled(
+                format!(" {} {} ", Icon::Loading, label),
+                Style::default().fg(Color::Cyan),
+            ),
+            Span::styled(&watch.note, Style::default().fg(Color::DarkGray)),
+        ]));
+        f.render_widget(status, chunks[4]);
     }
 }
 
 // ─── Pull Request Detail Rendering ────────────────────────────────
 
-fn render_pr_detail(f: &mut Frame, area: Rect, state: &mut GitHubState) {
+fn render_pr_detail(
+    f: &mut Frame,
+    area: Rect,
+    state: &mut GitHubState,
+    ui: &crate::config::UiConfig,
+) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(5), // Title + meta
-            Constraint::Length(3), // Tab bar
-            Constraint::Min(8),    // Tab content
-            Constraint::Length(2), // Keys
-            Constraint::Length(2), // Error
+            Constraint::Length(5),              // Title + meta
+            Constraint::Length(3),              // Tab bar
+            Constraint::Min(8),                 // Tab content
+            Constraint::Length(zen::chrome(2)), // Keys
+            Constraint::Length(2),              // Error
         ])
         .split(area);
 
@@ -1466,7 +2434,10 @@ fn render_pr_detail(f: &mut Frame, area: Rect, state: &mut GitHubState) {
     // Title + meta
     if state.pr_state.loading && pr.is_none() {
         let loading = Paragraph::new(Line::from(vec![
-            Span::styled("  ⏳ ", Style::default().fg(Color::Yellow)),
+            Span::styled(
+                format!("  {} ", Icon::Loading),
+                Style::default().fg(Color::Yellow),
+            ),
             Span::styled(
                 "Loading PR details...",
                 Style::default().fg(Color::DarkGray),
@@ -1497,6 +2468,29 @@ fn render_pr_detail(f: &mut Frame, area: Rect, state: &mut GitHubState) {
             None => Span::styled(" ? Checking...", Style::default().fg(Color::Yellow)),
         };
 
+        let mut meta_spans = vec![
+            Span::styled("  ", Style::default()),
+            Span::styled(
+                format!(" {} ", state_label),
+                Style::default()
+                    .fg(Color::Black)
+                    .bg(state_color)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!("  {} → {}  by ", pr.head.ref_name, pr.base.ref_name),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ];
+        meta_spans.extend(author_mark(&state.pr_state, &pr.user.login, ui));
+        meta_spans.extend([
+            Span::styled(
+                format!("@{}", pr.user.login),
+                Style::default().fg(Color::DarkGray),
+            ),
+            mergeable_info,
+        ]);
+
         let title_block = Paragraph::new(vec![
             Line::from(vec![
                 Span::styled(
@@ -1512,25 +2506,7 @@ fn render_pr_detail(f: &mut Frame, area: Rect, state: &mut GitHubState) {
                         .add_modifier(Modifier::BOLD),
                 ),
             ]),
-            Line::from(vec![
-                Span::styled("  ", Style::default()),
-                Span::styled(
-                    format!(" {} ", state_label),
-                    Style::default()
-                        .fg(Color::Black)
-                        .bg(state_color)
-                        .add_modifier(Modifier::BOLD),
-                ),
-                Span::styled(
-                    format!("  {} → {}", pr.head.ref_name, pr.base.ref_name),
-                    Style::default().fg(Color::DarkGray),
-                ),
-                Span::styled(
-                    format!("  by @{}", pr.user.login),
-                    Style::default().fg(Color::DarkGray),
-                ),
-                mergeable_info,
-            ]),
+            Line::from(meta_spans),
             Line::from(vec![Span::styled(
                 format!(
                     "  {} files changed  +{} -{}",
@@ -1589,7 +2565,35 @@ fn render_pr_detail(f: &mut Frame, area: Rect, state: &mut GitHubState) {
 
     // Keys
     let keys = if let Some(pr) = state.pr_state.detail_pr.as_ref() {
-        if pr.state == "open" {
+        if state.pr_state.detail_tab == PrDetailTab::Files {
+            let (enter, esc) = if state.pr_state.patch_focus {
+                (" Comment on line ", " Files")
+            } else {
+                (" Step into patch ", " Back")
+            };
+            Line::from(vec![
+                Span::styled(" [Enter]", Style::default().fg(Color::Green)),
+                Span::raw(enter),
+                Span::styled("[n/N]", Style::default().fg(Color::Cyan)),
+                Span::raw(" Next/prev hunk "),
+                Span::styled("[s]", Style::default().fg(Color::Yellow)),
+                Span::raw(if state.pr_state.side_by_side {
+                    " Unified "
+                } else {
+                    " Side by side "
+                }),
+                Span::styled("[e]", Style::default().fg(Color::Yellow)),
+                Span::raw(if state.pr_state.patch_expanded {
+                    " Show files "
+                } else {
+                    " Expand "
+                }),
+                Span::styled("[Tab]", Style::default().fg(Color::Yellow)),
+                Span::raw(" Switch Tab "),
+                Span::styled("[Esc]", Style::default().fg(Color::DarkGray)),
+                Span::raw(esc),
+            ])
+        } else if pr.state == "open" {
             Line::from(vec![
                 Span::styled(" [Tab]", Style::default().fg(Color::Yellow)),
                 Span::raw(" Switch Tab "),
@@ -1597,8 +2601,12 @@ fn render_pr_detail(f: &mut Frame, area: Rect, state: &mut GitHubState) {
                 Span::raw(format!(" {} ", state.pr_state.merge_method.display())),
                 Span::styled("[M]", Style::default().fg(Color::Yellow)),
                 Span::raw(" Cycle Method "),
+                Span::styled("[v]", Style::default().fg(Color::Green)),
+                Span::raw(" Review "),
                 Span::styled("[c]", Style::default().fg(Color::Red)),
                 Span::raw(" Close "),
+                Span::styled("[w/W]", Style::default().fg(Color::Cyan)),
+                Span::raw(" Watch / +merge "),
                 Span::styled("[o]", Style::default().fg(Color::Cyan)),
                 Span::raw(" Browser "),
                 Span::styled("[r]", Style::default().fg(Color::Green)),
@@ -1706,15 +2714,24 @@ fn render_pr_overview(f: &mut Frame, area: Rect, state: &GitHubState) {
     // Body
     if let Some(ref pr) = state.pr_state.detail_pr {
         let body_text = pr.body.as_deref().unwrap_or("No description provided.");
-        let body_lines: Vec<Line> = body_text
-            .lines()
-            .map(|l| {
-                Line::from(Span::styled(
-                    format!("  {}", l),
-                    Style::default().fg(Color::White),
-                ))
-            })
-            .collect();
+        let mut body_lines: Vec<Line> = Vec::new();
+        let missing = unrequested_owners(&state.pr_state);
+        if !missing.is_empty() {
+            body_lines.push(Line::from(Span::styled(
+                format!(
+                    "  {} Touches code owned by {} — not requested for review",
+                    Icon::Warning,
+                    missing.join(", ")
+                ),
+                Style::default().fg(Color::Yellow),
+            )));
+            body_lines.push(Line::from(""));
+        }
+        body_lines.extend(crate::ui::markdown::render(
+            body_text,
+            "  ",
+            Style::default().fg(Color::White),
+        ));
 
         let body = Paragraph::new(body_lines)
             .scroll((state.pr_state.detail_scroll, 0))
@@ -1727,10 +2744,32 @@ fn render_pr_overview(f: &mut Frame, area: Rect, state: &GitHubState) {
                     .borders(Borders::ALL)
                     .border_style(Style::default().fg(Color::DarkGray)),
             );
-        f.render_widget(body, chunks[1]);
+        f.render_widget(&body, chunks[1]);
+        render_paragraph_scrollbar(f, chunks[1], &body, state.pr_state.detail_scroll);
     }
 }
 
+/// CODEOWNERS of the PR's changed files who are neither requested reviewers nor
+/// have reviewed already.
+fn unrequested_owners(pr_state: &PullRequestsState) -> Vec<String> {
+    let (Some(codeowners), Some(pr)) = (&pr_state.codeowners, &pr_state.detail_pr) else {
+        return Vec::new();
+    };
+    let files: Vec<&str> = pr_state
+        .detail_files
+        .iter()
+        .map(|f| f.filename.as_str())
+        .collect();
+    let requested: Vec<String> = pr
+        .requested_reviewers
+        .iter()
+        .chain(pr_state.detail_reviews.iter().map(|r| &r.user))
+        .map(|u| format!("@{}", u.login))
+        .chain(pr.requested_teams.iter().map(|t| format!("@{}", t.slug)))
+        .collect();
+    git::codeowners::unrequested_owners(codeowners, &files, &requested, &pr.user.login)
+}
+
 fn render_pr_files(f: &mut Frame, area: Rect, state: &mut GitHubState) {
     if state.pr_state.detail_files.is_empty() {
         let empty = Paragraph::new(Span::styled(
@@ -1767,10 +2806,30 @@ fn render_pr_files(f: &mut Frame, area: Rect, state: &mut GitHubState) {
                 format!("  +{} -{}", file.additions, file.deletions),
                 Style::default().fg(Color::DarkGray),
             );
-            ListItem::new(Line::from(vec![status_icon, filename, changes]))
+            let owners = state
+                .pr_state
+                .codeowners
+                .as_ref()
+                .map(|c| c.owners_of(&file.filename).join(" "))
+                .filter(|o| !o.is_empty())
+                .map_or(Span::raw(""), |o| {
+                    Span::styled(format!("  {}", o), Style::default().fg(Color::Magenta))
+                });
+            ListItem::new(Line::from(vec![status_icon, filename, changes, owners]))
         })
         .collect();
 
+    // The list on top, the selected file's patch below; expanded, just the patch
+    if state.pr_state.patch_expanded {
+        render_patch(f, area, state);
+        return;
+    }
+    let list_height = (items.len() as u16 + 2).min(area.height / 3).max(3);
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(list_height), Constraint::Min(3)])
+        .split(area);
+
     let list = List::new(items)
         .block(
             Block::default()
@@ -1779,7 +2838,11 @@ fn render_pr_files(f: &mut Frame, area: Rect, state: &mut GitHubState) {
                     Style::default().fg(Color::White),
                 ))
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::DarkGray)),
+                .border_style(Style::default().fg(if state.pr_state.patch_focus {
+                    Color::DarkGray
+                } else {
+                    Color::Cyan
+                })),
         )
         .highlight_style(
             Style::default()
@@ -1788,7 +2851,133 @@ fn render_pr_files(f: &mut Frame, area: Rect, state: &mut GitHubState) {
         )
         .highlight_symbol("▶ ");
 
-    f.render_stateful_widget(list, area, &mut state.pr_state.files_list_state);
+    f.render_stateful_widget(&list, chunks[0], &mut state.pr_state.files_list_state);
+    render_scrollbar(
+        f,
+        chunks[0],
+        list.len(),
+        state.pr_state.files_list_state.offset(),
+    );
+    render_patch(f, chunks[1], state);
+}
+
+/// Indices of the hunk headers in a patch.
+fn hunk_starts(patch: &[git::github_auth::PatchLine]) -> Vec<usize> {
+    patch
+        .iter()
+        .enumerate()
+        .filter(|(_, l)| l.text.starts_with("@@"))
+        .map(|(i, _)| i)
+        .collect()
+}
+
+/// The selected file's patch, unified or side by side, with the cursor line
+/// highlighted while stepping through it.
+fn render_patch(f: &mut Frame, area: Rect, state: &GitHubState) {
+    let pr_state = &state.pr_state;
+    let patch = selected_patch(pr_state);
+    let focus = pr_state.patch_focus;
+    let cursor = pr_state.patch_line;
+    let Some(file) = pr_state.detail_files.get(pr_state.files_selected) else {
+        return;
+    };
+
+    let hunks = hunk_starts(&patch);
+    let hunk = hunks
+        .iter()
+        .rposition(|&h| h <= cursor)
+        .map_or(0, |i| i + 1);
+    let mut title = format!(" {} · hunk {}/{} ", file.filename, hunk, hunks.len());
+    if focus {
+        title.push_str("— [Enter] Comment on line ");
+    }
+    let block = Block::default()
+        .title(Span::styled(title, Style::default().fg(Color::White)))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(if focus { Color::Cyan } else { Color::DarkGray }));
+
+    if patch.is_empty() {
+        let empty = Paragraph::new(Span::styled(
+            "  No diff from GitHub (binary or too large)",
+            Style::default().fg(Color::DarkGray),
+        ))
+        .block(block);
+        f.render_widget(empty, area);
+        return;
+    }
+
+    let cursor_style = Style::default()
+        .bg(Color::DarkGray)
+        .add_modifier(Modifier::BOLD);
+    let numbered = |i: usize, number: Option<u32>| {
+        let line = &patch[i];
+        let mut spans = vec![
+            Span::styled(
+                number.map_or_else(|| " ".repeat(5), |n| format!("{:>5}", n)),
+                Style::default().fg(Color::DarkGray),
+            ),
+            Span::raw(" "),
+        ];
+        spans.extend(staging::diff_line(&line.kind, &line.text).spans);
+        let line = Line::from(spans);
+        if focus && i == cursor {
+            line.patch_style(cursor_style)
+        } else {
+            line
+        }
+    };
+
+    let visible = area.height.saturating_sub(2) as usize;
+    if pr_state.side_by_side && !is_narrow(area) {
+        let kinds: Vec<_> = patch.iter().map(|l| l.kind.clone()).collect();
+        let rows = git::diff::side_by_side(&kinds);
+        let cursor_row = rows
+            .iter()
+            .position(|&(old, new)| old == Some(cursor) || new == Some(cursor))
+            .unwrap_or(0);
+        let scroll = if focus {
+            (cursor_row + 1).saturating_sub(visible)
+        } else {
+            0
+        };
+        let side = |old: bool| {
+            rows.iter()
+                .map(|&(left, right)| match if old { left } else { right } {
+                    Some(i) => numbered(i, if old { patch[i].old } else { patch[i].new }),
+                    None => Line::from(""),
+                })
+                .collect::<Vec<_>>()
+        };
+        let inner = block.inner(area);
+        f.render_widget(block, area);
+        let halves = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(inner);
+        let old = Paragraph::new(side(true)).scroll((scroll as u16, 0)).block(
+            Block::default()
+                .borders(Borders::RIGHT)
+                .border_style(Style::default().fg(Color::DarkGray)),
+        );
+        f.render_widget(old, halves[0]);
+        let new = Paragraph::new(side(false)).scroll((scroll as u16, 0));
+        f.render_widget(new, halves[1]);
+        return;
+    }
+
+    let lines: Vec<Line> = (0..patch.len())
+        .map(|i| numbered(i, patch[i].new.or(patch[i].old)))
+        .collect();
+    let scroll = if focus {
+        (cursor + 1).saturating_sub(visible)
+    } else {
+        0
+    };
+    let patch_view = Paragraph::new(lines)
+        .scroll((scroll as u16, 0))
+        .block(block);
+    f.render_widget(&patch_view, area);
+    render_paragraph_scrollbar(f, area, &patch_view, scroll as u16);
 }
 
 fn render_pr_reviews(f: &mut Frame, area: Rect, state: &GitHubState) {
@@ -1812,7 +3001,10 @@ fn render_pr_reviews(f: &mut Frame, area: Rect, state: &GitHubState) {
         let icon = match review.state.as_str() {
             "APPROVED" => Span::styled("  ✓ ", Style::default().fg(Color::Green)),
             "CHANGES_REQUESTED" => Span::styled("  ✗ ", Style::default().fg(Color::Red)),
-            "COMMENTED" => Span::styled("  💬 ", Style::default().fg(Color::Cyan)),
+            "COMMENTED" => Span::styled(
+                format!("  {} ", Icon::Comment),
+                Style::default().fg(Color::Cyan),
+            ),
             "DISMISSED" => Span::styled("  ○ ", Style::default().fg(Color::DarkGray)),
             _ => Span::styled("  ? ", Style::default().fg(Color::DarkGray)),
         };
@@ -1828,15 +3020,14 @@ fn render_pr_reviews(f: &mut Frame, area: Rect, state: &GitHubState) {
         );
         lines.push(Line::from(vec![icon, user, state_text]));
 
-        if let Some(ref body) = review.body {
-            if !body.is_empty() {
-                for body_line in body.lines().take(3) {
-                    lines.push(Line::from(Span::styled(
-                        format!("      {}", body_line),
-                        Style::default().fg(Color::DarkGray),
-                    )));
-                }
-            }
+        if let Some(ref body) = review.body
+            && !body.is_empty()
+        {
+            lines.extend(
+                crate::ui::markdown::render(body, "      ", Style::default().fg(Color::DarkGray))
+                    .into_iter()
+                    .take(3),
+            );
         }
         lines.push(Line::from(""));
     }
@@ -1852,7 +3043,8 @@ fn render_pr_reviews(f: &mut Frame, area: Rect, state: &GitHubState) {
                 .borders(Borders::ALL)
                 .border_style(Style::default().fg(Color::DarkGray)),
         );
-    f.render_widget(reviews_widget, area);
+    f.render_widget(&reviews_widget, area);
+    render_paragraph_scrollbar(f, area, &reviews_widget, state.pr_state.detail_scroll);
 }
 
 // ─── Pull Request Key Handlers ────────────────────────────────
@@ -1863,21 +3055,18 @@ fn handle_pull_requests_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow:
             app.github_state.view = GitHubView::Menu;
             app.github_state.pr_state.error = None;
         }
-        KeyCode::Up | KeyCode::Char('k') => {
-            if app.github_state.pr_state.selected > 0 {
-                app.github_state.pr_state.selected -= 1;
-                let sel = app.github_state.pr_state.selected;
-                app.github_state.pr_state.list_state.select(Some(sel));
-            }
+        KeyCode::Up | KeyCode::Char('k') if app.github_state.pr_state.selected > 0 => {
+            app.github_state.pr_state.selected -= 1;
+            let sel = app.github_state.pr_state.selected;
+            app.github_state.pr_state.list_state.select(Some(sel));
         }
-        KeyCode::Down | KeyCode::Char('j') => {
+        KeyCode::Down | KeyCode::Char('j')
             if !app.github_state.pr_state.prs.is_empty()
-                && app.github_state.pr_state.selected + 1 < app.github_state.pr_state.prs.len()
-            {
-                app.github_state.pr_state.selected += 1;
-                let sel = app.github_state.pr_state.selected;
-                app.github_state.pr_state.list_state.select(Some(sel));
-            }
+                && app.github_state.pr_state.selected + 1 < app.github_state.pr_state.prs.len() =>
+        {
+            app.github_state.pr_state.selected += 1;
+            let sel = app.github_state.pr_state.selected;
+            app.github_state.pr_state.list_state.select(Some(sel));
         }
         KeyCode::Enter => {
             if let Some(pr) = app
@@ -1898,12 +3087,19 @@ fn handle_pull_requests_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow:
         KeyCode::Char('r') => {
             start_load_prs(app);
         }
+        KeyCode::Char('n') => open_new_pr(app),
         _ => {}
     }
     Ok(())
 }
 
 fn handle_pr_detail_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()> {
+    if app.github_state.pr_state.detail_tab == PrDetailTab::Files
+        && app.github_state.pr_state.patch_focus
+        && handle_patch_key(app, key)
+    {
+        return Ok(());
+    }
     match key.code {
         KeyCode::Esc => {
             app.github_state.view = GitHubView::PullRequests;
@@ -1914,6 +3110,7 @@ fn handle_pr_detail_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Res
         KeyCode::Tab => {
             app.github_state.pr_state.detail_tab = app.github_state.pr_state.detail_tab.next();
             app.github_state.pr_state.detail_scroll = 0;
+            app.github_state.pr_state.patch_focus = false;
         }
         KeyCode::Up | KeyCode::Char('k') => match app.github_state.pr_state.detail_tab {
             PrDetailTab::Files => {
@@ -1921,6 +3118,7 @@ fn handle_pr_detail_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Res
                     app.github_state.pr_state.files_selected -= 1;
                     let sel = app.github_state.pr_state.files_selected;
                     app.github_state.pr_state.files_list_state.select(Some(sel));
+                    app.github_state.pr_state.patch_line = 0;
                 }
             }
             _ => {
@@ -1938,6 +3136,7 @@ fn handle_pr_detail_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Res
                     app.github_state.pr_state.files_selected += 1;
                     let sel = app.github_state.pr_state.files_selected;
                     app.github_state.pr_state.files_list_state.select(Some(sel));
+                    app.github_state.pr_state.patch_line = 0;
                 }
             }
             _ => {
@@ -1946,55 +3145,86 @@ fn handle_pr_detail_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Res
         },
         KeyCode::Char('m') => {
             // Merge PR
-            if let Some(pr) = app.github_state.pr_state.detail_pr.as_ref() {
-                if pr.state == "open" {
-                    let number = pr.number;
-                    let method = app.github_state.pr_state.merge_method.label().to_string();
-                    app.popup = crate::app::Popup::Confirm {
-                        title: "Merge Pull Request".to_string(),
-                        message: format!(
-                            "Merge PR #{} using {}?\n\n[y] Yes  [n] No",
-                            number,
-                            app.github_state.pr_state.merge_method.display()
-                        ),
-                        on_confirm: crate::app::ConfirmAction::MergePullRequest { number, method },
-                    };
-                }
+            if let Some(pr) = app.github_state.pr_state.detail_pr.as_ref()
+                && pr.state == "open"
+            {
+                let number = pr.number;
+                let method = app.github_state.pr_state.merge_method.label().to_string();
+                app.popup = crate::app::Popup::Confirm {
+                    title: "Merge Pull Request".to_string(),
+                    message: format!(
+                        "Merge PR #{} using {}?\n\n[y] Yes  [n] No",
+                        number,
+                        app.github_state.pr_state.merge_method.display()
+                    ),
+                    on_confirm: crate::app::ConfirmAction::MergePullRequest { number, method },
+                };
+            }
+        }
+        KeyCode::Enter if app.github_state.pr_state.detail_tab == PrDetailTab::Files => {
+            if selected_patch(&app.github_state.pr_state).is_empty() {
+                app.github_state.pr_state.error =
+                    Some("GitHub shows no diff for this file (binary or too large)".to_string());
+            } else {
+                app.github_state.pr_state.patch_focus = true;
+                app.github_state.pr_state.error = None;
+            }
+        }
+        KeyCode::Char('n') | KeyCode::Char('N')
+            if app.github_state.pr_state.detail_tab == PrDetailTab::Files =>
+        {
+            let starts = hunk_starts(&selected_patch(&app.github_state.pr_state));
+            let state = &mut app.github_state.pr_state;
+            let next = if key.code == KeyCode::Char('n') {
+                starts
+                    .into_iter()
+                    .find(|&h| h > state.patch_line || !state.patch_focus)
+            } else {
+                starts.into_iter().rev().find(|&h| h < state.patch_line)
+            };
+            if let Some(line) = next {
+                state.patch_line = line;
+                state.patch_focus = true;
+            }
+        }
+        KeyCode::Char('e') if app.github_state.pr_state.detail_tab == PrDetailTab::Files => {
+            let state = &mut app.github_state.pr_state;
+            state.patch_expanded = !state.patch_expanded;
+        }
+        KeyCode::Char('s') if app.github_state.pr_state.detail_tab == PrDetailTab::Files => {
+            let state = &mut app.github_state.pr_state;
+            state.side_by_side = !state.side_by_side;
+        }
+        KeyCode::Char('v') => {
+            if let Some(pr) = app.github_state.pr_state.detail_pr.as_ref()
+                && pr.state == "open"
+            {
+                app.popup = review_popup(pr.number);
             }
         }
+        KeyCode::Char('w') => toggle_watch(app, false),
+        KeyCode::Char('W') => toggle_watch(app, true),
         KeyCode::Char('M') => {
             // Cycle merge method
             app.github_state.pr_state.merge_method = app.github_state.pr_state.merge_method.next();
         }
         KeyCode::Char('c') => {
             // Close PR
-            if let Some(pr) = app.github_state.pr_state.detail_pr.as_ref() {
-                if pr.state == "open" {
-                    let number = pr.number;
-                    app.popup = crate::app::Popup::Confirm {
-                        title: "Close Pull Request".to_string(),
-                        message: format!(
-                            "Close PR #{} without merging?\n\n[y] Yes  [n] No",
-                            number,
-                        ),
-                        on_confirm: crate::app::ConfirmAction::ClosePullRequest(number),
-                    };
-                }
+            if let Some(pr) = app.github_state.pr_state.detail_pr.as_ref()
+                && pr.state == "open"
+            {
+                let number = pr.number;
+                app.popup = crate::app::Popup::Confirm {
+                    title: "Close Pull Request".to_string(),
+                    message: format!("Close PR #{} without merging?\n\n[y] Yes  [n] No", number,),
+                    on_confirm: crate::app::ConfirmAction::ClosePullRequest(number),
+                };
             }
         }
         KeyCode::Char('o') => {
             // Open in browser
             if let Some(pr) = app.github_state.pr_state.detail_pr.as_ref() {
-                #[cfg(target_os = "macos")]
-                let _ = std::process::Command::new("open").arg(&pr.html_url).spawn();
-                #[cfg(target_os = "linux")]
-                let _ = std::process::Command::new("xdg-open")
-                    .arg(&pr.html_url)
-                    .spawn();
-                #[cfg(target_os = "windows")]
-                let _ = std::process::Command::new("cmd")
-                    .args(["/C", "start", &pr.html_url])
-                    .spawn();
+                open_in_browser(&pr.html_url);
             }
         }
         KeyCode::Char('r') => {
@@ -2008,60 +3238,1144 @@ fn handle_pr_detail_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Res
     Ok(())
 }
 
-// ─── GitHub Actions Rendering ────────────────────────────────
+/// The patch of the file selected in the Files tab.
+fn selected_patch(pr_state: &PullRequestsState) -> Vec<git::github_auth::PatchLine> {
+    pr_state
+        .detail_files
+        .get(pr_state.files_selected)
+        .map(|f| f.patch_lines())
+        .unwrap_or_default()
+}
 
-fn status_color(status: Option<&str>, conclusion: Option<&str>) -> Color {
-    match conclusion.unwrap_or("") {
-        "success" => Color::Green,
-        "failure" | "timed_out" => Color::Red,
-        "cancelled" => Color::DarkGray,
-        "skipped" => Color::DarkGray,
-        _ => match status.unwrap_or("") {
-            "in_progress" => Color::Cyan,
-            "queued" | "waiting" | "pending" => Color::Yellow,
-            "completed" => Color::Green,
-            _ => Color::White,
-        },
+/// Keys while moving through a file's patch. Returns whether the key was used.
+fn handle_patch_key(app: &mut crate::app::App, key: KeyEvent) -> bool {
+    let lines = selected_patch(&app.github_state.pr_state);
+    let state = &mut app.github_state.pr_state;
+    match key.code {
+        KeyCode::Esc => state.patch_focus = false,
+        KeyCode::Up | KeyCode::Char('k') => state.patch_line = state.patch_line.saturating_sub(1),
+        KeyCode::Down | KeyCode::Char('j') => {
+            state.patch_line = (state.patch_line + 1).min(lines.len().saturating_sub(1));
+        }
+        KeyCode::Enter => {
+            let Some((line, side)) = lines.get(state.patch_line).and_then(|l| l.anchor()) else {
+                return true;
+            };
+            let (Some(pr), Some(file)) = (
+                state.detail_pr.as_ref(),
+                state.detail_files.get(state.files_selected),
+            ) else {
+                return true;
+            };
+            let comment = git::github_auth::LineComment {
+                commit_id: pr.head.sha.clone(),
+                path: file.filename.clone(),
+                line,
+                side,
+                body: String::new(),
+            };
+            app.popup = crate::app::Popup::Input {
+                title: format!("Comment on {}:{}", file.filename, line),
+                prompt: "Comment: ".to_string(),
+                value: String::new(),
+                on_submit: crate::app::InputAction::PrLineComment(pr.number, comment),
+            };
+        }
+        _ => return false,
     }
+    true
 }
 
-fn status_icon(status: Option<&str>, conclusion: Option<&str>) -> &'static str {
-    match conclusion.unwrap_or("") {
-        "success" => "✅",
-        "failure" | "timed_out" => "❌",
-        "cancelled" => "⚪",
-        "skipped" => "⏭",
-        _ => match status.unwrap_or("") {
-            "in_progress" => "🔵",
-            "queued" | "waiting" | "pending" => "🟡",
-            _ => "⚪",
-        },
+/// The choice of review to submit on pull request `number`.
+fn review_popup(number: u64) -> crate::app::Popup {
+    use crate::app::{FollowUpAction, FollowUpItem};
+    let item = |label: &str, description: &str, event: &str| FollowUpItem {
+        label: label.to_string(),
+        description: description.to_string(),
+        action: FollowUpAction::ReviewPullRequest(number, event.to_string()),
+    };
+    crate::app::Popup::FollowUp {
+        title: format!("Review #{}", number),
+        context: "Line comments from the Files tab are posted as you write them; \
+                  the review adds your verdict."
+            .to_string(),
+        suggestions: vec![
+            item("Approve", "Approve merging these changes", "APPROVE"),
+            item(
+                "Request changes",
+                "Feedback that must be addressed before merging",
+                "REQUEST_CHANGES",
+            ),
+            item("Comment", "General feedback without a verdict", "COMMENT"),
+        ],
+        selected: 0,
     }
 }
 
-fn render_actions_list(f: &mut Frame, area: Rect, state: &mut GitHubState) {
-    let chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Length(3), // Title
-            Constraint::Min(6),    // Runs list
-            Constraint::Length(2), // Keys
-            Constraint::Length(2), // Status/Error
-        ])
-        .split(area);
+/// What to call a review `event` in messages.
+fn review_label(event: &str) -> &'static str {
+    match event {
+        "APPROVE" => "Approved",
+        "REQUEST_CHANGES" => "Requested changes on",
+        _ => "Commented on",
+    }
+}
 
-    let title = Paragraph::new(Line::from(vec![
-        Span::styled("  ⚡ ", Style::default()),
+/// Submit a review on pull request `number`; `event` as for
+/// `github_auth::submit_pr_review`.
+pub fn submit_review(app: &mut crate::app::App, number: u64, event: String, body: String) {
+    let Some(token) = app.config.github.get_token() else {
+        return;
+    };
+    app.github_state.pr_state.error = None;
+    let bg = app.github_state.pr_state.bg_result.clone();
+    let key = format!("pr-review:{}", number);
+    let queued = app.jobs.submit_write(key, Priority::User, move || {
+        let result = git::github_auth::submit_pr_review(&token, number, &event, &body)
+            .map(|_| format!("✓ {} #{}", review_label(&event), number))
+            .map_err(|e| e.to_string());
+        if let Ok(mut r) = bg.lock() {
+            *r = Some(PrBgResult::Reviewed(number, result));
+        }
+    });
+    if !queued {
+        app.set_status(format!(
+            "Review on #{} not sent: zit is shutting down",
+            number
+        ));
+    }
+}
+
+/// Post a comment on one line of pull request `number`'s diff.
+pub fn comment_on_line(
+    app: &mut crate::app::App,
+    number: u64,
+    comment: git::github_auth::LineComment,
+) {
+    let Some(token) = app.config.github.get_token() else {
+        return;
+    };
+    app.github_state.pr_state.error = None;
+    let bg = app.github_state.pr_state.bg_result.clone();
+    let key = format!(
+        "pr-line-comment:{}:{}:{}",
+        number, comment.path, comment.line
+    );
+    let queued = app.jobs.submit_write(key, Priority::User, move || {
+        let result = git::github_auth::comment_on_pr_line(&token, number, &comment)
+            .map(|_| format!("✓ Commented on {}:{}", comment.path, comment.line))
+            .map_err(|e| e.to_string());
+        if let Ok(mut r) = bg.lock() {
+            *r = Some(PrBgResult::LineCommented(result));
+        }
+    });
+    if !queued {
+        app.set_status("Line comment not sent: zit is shutting down");
+    }
+}
+
+fn open_in_browser(url: &str) {
+    #[cfg(target_os = "macos")]
+    let _ = std::process::Command::new("open").arg(url).spawn();
+    #[cfg(target_os = "linux")]
+    let _ = std::process::Command::new("xdg-open").arg(url).spawn();
+    #[cfg(target_os = "windows")]
+    let _ = std::process::Command::new("cmd")
+        .args(["/C", "start", url])
+        .spawn();
+}
+
+// ─── New Pull Request ────────────────────────────────
+
+/// Index of the Open button in the New Pull Request form.
+const NEW_PR_SUBMIT_FIELD: usize = 5;
+
+/// Open the New Pull Request form for the current branch.
+fn open_new_pr(app: &mut crate::app::App) {
+    match NewPrState::open() {
+        Ok(form) => {
+            app.github_state.pr_state.new_pr = Some(form);
+            app.github_state.pr_state.error = None;
+            app.github_state.view = GitHubView::NewPullRequest;
+        }
+        Err(e) => app.github_state.pr_state.error = Some(e.to_string()),
+    }
+}
+
+fn render_new_pr(f: &mut Frame, area: Rect, state: &GitHubState) {
+    let Some(form) = state.pr_state.new_pr.as_ref() else {
+        return;
+    };
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(zen::chrome(3)), // Title
+            Constraint::Length(3),              // Head
+            Constraint::Length(3),              // Base
+            Constraint::Length(3),              // PR title
+            Constraint::Min(5),                 // Description
+            Constraint::Length(3),              // Draft
+            Constraint::Length(3),              // Submit
+            Constraint::Length(zen::chrome(1)), // Keys
+            Constraint::Length(1),              // Error
+        ])
+        .split(area);
+
+    let title = Paragraph::new(Span::styled(
+        format!("  {} New Pull Request", Icon::PullRequest),
+        Style::default()
+            .fg(Color::White)
+            .add_modifier(Modifier::BOLD),
+    ))
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan)),
+    );
+    f.render_widget(title, chunks[0]);
+
+    let field_style = |idx: usize| {
+        if form.field == idx {
+            Style::default().fg(Color::Cyan)
+        } else {
+            Style::default().fg(Color::DarkGray)
+        }
+    };
+    let field = |label: &str, value: Span<'static>, hint: String, idx: usize| {
+        Paragraph::new(Line::from(vec![
+            Span::styled(
+                format!("  {}: ", label),
+                Style::default().fg(Color::DarkGray),
+            ),
+            value,
+            Span::styled(hint, Style::default().fg(Color::DarkGray)),
+        ]))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(field_style(idx)),
+        )
+    };
+    let branch = |name: &str| {
+        Span::styled(
+            name.to_string(),
+            Style::default()
+                .fg(Color::Magenta)
+                .add_modifier(Modifier::BOLD),
+        )
+    };
+
+    let push = match form.push {
+        git::pull_request::PushNeed::UpToDate => String::new(),
+        git::pull_request::PushNeed::New => {
+            format!("  (not on {} yet)", git::pull_request::REMOTE)
+        }
+        git::pull_request::PushNeed::Ahead(n) => format!("  ({} to push)", n),
+    };
+    f.render_widget(field("From", branch(form.head_name()), push, 0), chunks[1]);
+    let commits = match form.commits.len() {
+        0 => "  (nothing new)".to_string(),
+        1 => "  (1 commit)".to_string(),
+        n => format!("  ({} commits)", n),
+    };
+    f.render_widget(
+        field("Into", branch(form.base_name()), commits, 1),
+        chunks[2],
+    );
+    f.render_widget(
+        field(
+            "Title",
+            Span::styled(form.title.clone(), Style::default().fg(Color::White)),
+            String::new(),
+            2,
+        ),
+        chunks[3],
+    );
+
+    let body = if form.body.is_empty() {
+        Text::from(Span::styled(
+            "  (none — Enter to write one)",
+            Style::default().fg(Color::DarkGray),
+        ))
+    } else {
+        Text::from(form.body.as_str())
+    };
+    let description = Paragraph::new(body).wrap(Wrap { trim: false }).block(
+        Block::default()
+            .title(" Description ")
+            .borders(Borders::ALL)
+            .border_style(field_style(3)),
+    );
+    f.render_widget(description, chunks[4]);
+
+    f.render_widget(
+        field(
+            "Draft",
+            Span::styled(
+                if form.draft { "[x]" } else { "[ ]" },
+                Style::default().fg(Color::White),
+            ),
+            "  (Space to toggle)".to_string(),
+            4,
+        ),
+        chunks[5],
+    );
+
+    let kind = if form.draft {
+        "draft pull request"
+    } else {
+        "pull request"
+    };
+    let submit_label = if form.submitting {
+        format!("  {} Opening the {}...", Icon::Loading, kind)
+    } else if form.push == git::pull_request::PushNeed::UpToDate {
+        format!("  [Enter] Open {}", kind)
+    } else {
+        format!("  [Enter] Push '{}' and open {}", form.head_name(), kind)
+    };
+    let submit = Paragraph::new(Span::styled(
+        submit_label,
+        Style::default()
+            .fg(Color::Green)
+            .add_modifier(Modifier::BOLD),
+    ))
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(field_style(NEW_PR_SUBMIT_FIELD)),
+    );
+    f.render_widget(submit, chunks[6]);
+
+    let keys = Paragraph::new(Line::from(vec![
+        Span::styled(" [Tab]", Style::default().fg(Color::Cyan)),
+        Span::raw(" Next field "),
+        Span::styled("[←/→]", Style::default().fg(Color::Cyan)),
+        Span::raw(" Branch "),
+        Span::styled("[Enter]", Style::default().fg(Color::Yellow)),
+        Span::raw(" Edit "),
+        Span::styled("[Ctrl+G]", Style::default().fg(Color::Magenta)),
+        Span::raw(" AI describe "),
+        Span::styled("[Esc]", Style::default().fg(Color::DarkGray)),
+        Span::raw(" Back"),
+    ]));
+    f.render_widget(keys, chunks[7]);
+
+    if let Some(ref err) = state.pr_state.error {
+        let error = Paragraph::new(Span::styled(
+            format!(" {}", err),
+            Style::default().fg(Color::Red),
+        ));
+        f.render_widget(error, chunks[8]);
+    }
+}
+
+fn handle_new_pr_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()> {
+    let Some(form) = app.github_state.pr_state.new_pr.as_mut() else {
+        app.github_state.view = GitHubView::PullRequests;
+        return Ok(());
+    };
+    if form.submitting {
+        return Ok(());
+    }
+    match key.code {
+        KeyCode::Esc => {
+            app.github_state.pr_state.new_pr = None;
+            app.github_state.pr_state.error = None;
+            app.github_state.view = GitHubView::PullRequests;
+        }
+        KeyCode::Tab | KeyCode::Down => {
+            form.field = (form.field + 1).min(NEW_PR_SUBMIT_FIELD);
+        }
+        KeyCode::BackTab | KeyCode::Up if form.field > 0 => {
+            form.field -= 1;
+        }
+        KeyCode::Left | KeyCode::Right if form.field <= 1 => {
+            let (current, count) = if form.field == 0 {
+                (&mut form.head, form.heads.len())
+            } else {
+                (&mut form.base, form.bases.len())
+            };
+            *current = if key.code == KeyCode::Right {
+                (*current + 1) % count
+            } else {
+                (*current + count - 1) % count
+            };
+            form.redraft();
+            app.github_state.pr_state.error = None;
+        }
+        KeyCode::Char('g') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            if form.commits.is_empty() {
+                app.github_state.pr_state.error = form.problem();
+            } else {
+                let (base, head) = (form.base_name().to_string(), form.head_name().to_string());
+                app.start_ai_pr_description(base, head);
+            }
+        }
+        KeyCode::Enter if form.field == 2 => {
+            app.popup = crate::app::Popup::Input {
+                title: "Pull Request Title".to_string(),
+                prompt: "Title: ".to_string(),
+                value: form.title.clone(),
+                on_submit: crate::app::InputAction::PrTitle,
+            };
+        }
+        KeyCode::Enter if form.field == 3 => {
+            let written = git::pull_request::body_file()
+                .and_then(|path| Ok(std::fs::write(path, &form.body)?));
+            match written {
+                Ok(()) => {
+                    app.editor_request = Some(crate::ui::editor::EditorTarget::PullRequestBody)
+                }
+                Err(e) => {
+                    app.github_state.pr_state.error =
+                        Some(format!("Can't edit the description: {}", e))
+                }
+            }
+        }
+        KeyCode::Char(' ') if form.field == 4 => {
+            form.draft = !form.draft;
+        }
+        KeyCode::Enter if form.field == NEW_PR_SUBMIT_FIELD => submit_new_pr(app),
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Take the description back from the editor.
+pub fn read_edited_pr_body(app: &mut crate::app::App) {
+    let edited = git::pull_request::body_file().and_then(|path| Ok(std::fs::read_to_string(path)?));
+    if let Some(form) = app.github_state.pr_state.new_pr.as_mut()
+        && let Ok(text) = edited
+    {
+        form.body = text.trim_end().to_string();
+    }
+}
+
+/// Push the head if the remote needs it, then open the pull request.
+fn submit_new_pr(app: &mut crate::app::App) {
+    let Some(form) = app.github_state.pr_state.new_pr.as_mut() else {
+        return;
+    };
+    if let Some(problem) = form.problem() {
+        app.github_state.pr_state.error = Some(problem);
+        return;
+    }
+    let Some(token) = app.config.github.get_token() else {
+        app.github_state.pr_state.error = Some("Login first to open a pull request".to_string());
+        return;
+    };
+    form.submitting = true;
+    let head = form.head_name().to_string();
+    let base = form.base_name().to_string();
+    let title = form.title.trim().to_string();
+    let body = form.body.clone();
+    let draft = form.draft;
+    let push = form.push.clone();
+    app.github_state.pr_state.error = None;
+    let bg = app.github_state.pr_state.bg_result.clone();
+    let key = format!("pr-create:{}:{}", head, base);
+    let queued = app.jobs.submit_write(key, Priority::User, move || {
+        use git::pull_request::{PushNeed, REMOTE};
+        let pushed = match push {
+            PushNeed::UpToDate => Ok(String::new()),
+            need => git::RemoteOps::push(REMOTE, &head, need == PushNeed::New)
+                .map_err(|e| anyhow::anyhow!("Push failed: {}", e)),
+        };
+        let result = pushed
+            .and_then(|_| {
+                git::github_auth::create_pull_request(&token, &head, &base, &title, &body, draft)
+            })
+            .map_err(|e| e.to_string());
+        if let Ok(mut r) = bg.lock() {
+            *r = Some(PrBgResult::Created(result));
+        }
+    });
+    if !queued {
+        form.submitting = false;
+        app.github_state.pr_state.error =
+            Some("Pull request not opened: zit is shutting down".to_string());
+    }
+}
+
+// ─── Issues ────────────────────────────────
+
+fn start_load_issues(app: &mut crate::app::App) {
+    let state = &mut app.github_state.issues_state;
+    state.loading = true;
+    state.error = None;
+    let token = app.config.github.get_token().unwrap_or_default();
+    let filter = state.filter.api_state().to_string();
+    let bg = state.bg_result.clone();
+    let key = format!("issues:{}", filter);
+    app.jobs.submit(key, Priority::User, move || {
+        let result = git::github_auth::list_issues(&token, &filter).map_err(|e| e.to_string());
+        let labels = git::github_auth::list_labels(&token).unwrap_or_default();
+        if let Ok(mut r) = bg.lock() {
+            *r = Some(IssueBgResult::List(result, labels));
+        }
+    });
+}
+
+fn start_load_issue_detail(app: &mut crate::app::App, number: u64) {
+    let state = &mut app.github_state.issues_state;
+    state.loading = true;
+    state.error = None;
+    let token = app.config.github.get_token().unwrap_or_default();
+    let bg = state.bg_result.clone();
+    let key = format!("issue:{}", number);
+    app.jobs.submit(key, Priority::User, move || {
+        let issue = git::github_auth::get_issue(&token, number).map_err(|e| e.to_string());
+        let comments =
+            git::github_auth::get_issue_comments(&token, number).map_err(|e| e.to_string());
+        if let Ok(mut r) = bg.lock() {
+            *r = Some(IssueBgResult::Detail { issue, comments });
+        }
+    });
+}
+
+/// Run `change` on issue `number` in the background. The message it returns
+/// goes to the status bar, and the issue is reloaded if it's still open.
+pub fn update_issue(
+    app: &mut crate::app::App,
+    number: u64,
+    change: impl FnOnce(&str) -> anyhow::Result<String> + Send + 'static,
+) {
+    let Some(token) = app.config.github.get_token() else {
+        return;
+    };
+    let state = &mut app.github_state.issues_state;
+    state.loading = true;
+    state.error = None;
+    let bg = state.bg_result.clone();
+    let key = format!("issue-update:{}", number);
+    app.jobs.submit(key, Priority::User, move || {
+        let result = change(&token).map_err(|e| e.to_string());
+        if let Ok(mut r) = bg.lock() {
+            *r = Some(IssueBgResult::Updated(number, result));
+        }
+    });
+}
+
+/// Open a new issue, then show it.
+pub fn create_issue(app: &mut crate::app::App, title: String, body: String) {
+    let Some(token) = app.config.github.get_token() else {
+        return;
+    };
+    let state = &mut app.github_state.issues_state;
+    state.loading = true;
+    state.error = None;
+    let bg = state.bg_result.clone();
+    let queued = app
+        .jobs
+        .submit_write("issue-create", Priority::User, move || {
+            let result =
+                git::github_auth::create_issue(&token, &title, &body).map_err(|e| e.to_string());
+            if let Ok(mut r) = bg.lock() {
+                *r = Some(IssueBgResult::Created(result));
+            }
+        });
+    if !queued {
+        let state = &mut app.github_state.issues_state;
+        state.loading = false;
+        state.error = Some("Issue not created: zit is shutting down".to_string());
+    }
+}
+
+/// Edit the labels of issue `number` to the comma-separated names in `typed`.
+pub fn set_issue_labels(app: &mut crate::app::App, number: u64, typed: &str) {
+    let state = &mut app.github_state.issues_state;
+    let names = match git::github_auth::match_labels(typed, &state.labels) {
+        Ok(names) => names,
+        Err(e) => {
+            state.error = Some(e.to_string());
+            return;
+        }
+    };
+    update_issue(app, number, move |token| {
+        git::github_auth::set_issue_labels(token, number, &names)?;
+        Ok(if names.is_empty() {
+            format!("✓ Removed the labels of #{}", number)
+        } else {
+            format!("✓ Labeled #{}: {}", number, names.join(", "))
+        })
+    });
+}
+
+/// Called on each tick to poll for issue background results.
+pub fn tick_issues_state(app: &mut crate::app::App) {
+    let bg_taken = match app.github_state.issues_state.bg_result.try_lock() {
+        Ok(mut result) => result.take(),
+        Err(_) => None,
+    };
+    let Some(bg) = bg_taken else {
+        return;
+    };
+    let state = &mut app.github_state.issues_state;
+    state.loading = false;
+    match bg {
+        IssueBgResult::List(Ok(issues), labels) => {
+            state.issues = issues;
+            state.labels = labels;
+            let last = state.visible().len().saturating_sub(1);
+            state.select(state.selected.min(last));
+        }
+        IssueBgResult::List(Err(e), _) => state.error = Some(e),
+        IssueBgResult::Detail { issue, comments } => {
+            match issue {
+                Ok(issue) => state.detail = Some(issue),
+                Err(e) => state.error = Some(e),
+            }
+            if let Ok(comments) = comments {
+                state.comments = comments;
+            }
+        }
+        IssueBgResult::Updated(number, Ok(message)) => {
+            app.set_status(message);
+            if app.github_state.view == GitHubView::IssueDetail(number) {
+                start_load_issue_detail(app, number);
+            }
+        }
+        IssueBgResult::Updated(_, Err(e)) | IssueBgResult::Created(Err(e)) => {
+            state.error = Some(e);
+        }
+        IssueBgResult::Created(Ok(issue)) => {
+            let number = issue.number;
+            state.detail = Some(issue);
+            state.comments.clear();
+            state.detail_scroll = 0;
+            app.github_state.view = GitHubView::IssueDetail(number);
+            app.set_status(format!("✓ Opened issue #{}", number));
+        }
+    }
+}
+
+/// A label in its GitHub color.
+fn label_span(label: &git::github_auth::GhLabel) -> Span<'static> {
+    let color = u32::from_str_radix(&label.color, 16)
+        .ok()
+        .filter(|_| label.color.len() == 6)
+        .map_or(Color::Magenta, |rgb| {
+            Color::Rgb((rgb >> 16) as u8, (rgb >> 8) as u8, rgb as u8)
+        });
+    Span::styled(format!(" [{}]", label.name), Style::default().fg(color))
+}
+
+fn render_issues(f: &mut Frame, area: Rect, state: &mut GitHubState) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(zen::chrome(3)), // Title
+            Constraint::Length(3),              // Filter bar
+            Constraint::Min(6),                 // Issue list
+            Constraint::Length(zen::chrome(2)), // Keys
+            Constraint::Length(2),              // Error
+        ])
+        .split(area);
+
+    let issues_state = &mut state.issues_state;
+    let visible = issues_state.visible();
+
+    let mut title_spans = vec![
+        Span::styled(format!("  {} ", Icon::Issue), Style::default()),
+        Span::styled(
+            "Issues",
+            Style::default()
+                .fg(Color::White)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            format!(
+                "  ({} {})",
+                issues_state.issues.len(),
+                issues_state.filter.label().to_lowercase()
+            ),
+            Style::default().fg(Color::DarkGray),
+        ),
+    ];
+    if !issues_state.query.is_empty() {
+        title_spans.push(Span::styled(
+            format!("  {} matching '{}'", visible.len(), issues_state.query),
+            Style::default().fg(Color::Yellow),
+        ));
+    }
+    let title = Paragraph::new(Line::from(title_spans)).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan)),
+    );
+    f.render_widget(title, chunks[0]);
+
+    let selected_idx = match issues_state.filter {
+        PrFilter::Open => 0,
+        PrFilter::Closed => 1,
+        PrFilter::All => 2,
+    };
+    let tabs = Tabs::new(vec![
+        Line::from("Open"),
+        Line::from("Closed"),
+        Line::from("All"),
+    ])
+    .block(
+        Block::default()
+            .title(Span::styled(
+                " Filter [f] ",
+                Style::default().fg(Color::DarkGray),
+            ))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::DarkGray)),
+    )
+    .select(selected_idx)
+    .style(Style::default().fg(Color::DarkGray))
+    .highlight_style(
+        Style::default()
+            .fg(Color::Cyan)
+            .add_modifier(Modifier::BOLD),
+    );
+    f.render_widget(tabs, chunks[1]);
+
+    let placeholder = if issues_state.loading && issues_state.issues.is_empty() {
+        Some(format!("  {} Loading issues...", Icon::Loading))
+    } else if visible.is_empty() && !issues_state.query.is_empty() {
+        Some(format!("  No issues match '{}'.", issues_state.query))
+    } else if visible.is_empty() {
+        Some("  No issues found.".to_string())
+    } else {
+        None
+    };
+    if let Some(text) = placeholder {
+        let empty = Paragraph::new(Span::styled(text, Style::default().fg(Color::DarkGray))).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::DarkGray)),
+        );
+        f.render_widget(empty, chunks[2]);
+    } else {
+        let items: Vec<ListItem> = visible
+            .iter()
+            .map(|issue| {
+                let state_icon = if issue.state == "open" {
+                    Span::styled(
+                        format!("  {} ", Icon::Open),
+                        Style::default().fg(Color::Green),
+                    )
+                } else {
+                    Span::styled(
+                        format!("  {} ", Icon::Closed),
+                        Style::default().fg(Color::Red),
+                    )
+                };
+                let mut spans = vec![
+                    state_icon,
+                    Span::styled(
+                        format!("#{} ", issue.number),
+                        Style::default().fg(Color::Cyan),
+                    ),
+                    Span::styled(
+                        truncate_to_width(&issue.title, 60),
+                        Style::default().fg(Color::White),
+                    ),
+                ];
+                spans.extend(issue.labels.iter().map(label_span));
+                spans.push(Span::styled(
+                    format!("  @{}", issue.user.login),
+                    Style::default().fg(Color::DarkGray),
+                ));
+                if issue.comments > 0 {
+                    spans.push(Span::styled(
+                        format!("  {} {}", Icon::Comment, issue.comments),
+                        Style::default().fg(Color::DarkGray),
+                    ));
+                }
+                ListItem::new(Line::from(spans))
+            })
+            .collect();
+
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::DarkGray)),
+            )
+            .highlight_style(
+                Style::default()
+                    .bg(Color::DarkGray)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .highlight_symbol("▶ ");
+
+        f.render_stateful_widget(&list, chunks[2], &mut issues_state.list_state);
+        render_scrollbar(f, chunks[2], list.len(), issues_state.list_state.offset());
+    }
+
+    let keys = Paragraph::new(Line::from(vec![
+        Span::styled(" [Enter]", Style::default().fg(Color::Cyan)),
+        Span::raw(" Open "),
+        Span::styled("[n]", Style::default().fg(Color::Green)),
+        Span::raw(" New "),
+        Span::styled("[/]", Style::default().fg(Color::Yellow)),
+        Span::raw(" Search "),
+        Span::styled("[f]", Style::default().fg(Color::Yellow)),
+        Span::raw(" Filter "),
+        Span::styled("[r]", Style::default().fg(Color::Green)),
+        Span::raw(" Refresh "),
+        Span::styled("[Esc]", Style::default().fg(Color::DarkGray)),
+        Span::raw(" Back"),
+    ]));
+    f.render_widget(keys, chunks[3]);
+
+    if let Some(ref err) = issues_state.error {
+        let status = Paragraph::new(Span::styled(
+            format!(" {}", err),
+            Style::default().fg(Color::Red),
+        ));
+        f.render_widget(status, chunks[4]);
+    }
+}
+
+fn render_issue_detail(f: &mut Frame, area: Rect, state: &mut GitHubState) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(5),              // Title + meta
+            Constraint::Min(6),                 // Description and comments
+            Constraint::Length(zen::chrome(2)), // Keys
+            Constraint::Length(2),              // Error
+        ])
+        .split(area);
+
+    let issues_state = &state.issues_state;
+    let Some(issue) = issues_state.detail.as_ref() else {
+        let loading = Paragraph::new(Span::styled(
+            format!("  {} Loading issue...", Icon::Loading),
+            Style::default().fg(Color::DarkGray),
+        ))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan)),
+        );
+        f.render_widget(loading, chunks[0]);
+        return;
+    };
+
+    let open = issue.state == "open";
+    let mut labels: Vec<Span> = vec![Span::raw(" ")];
+    if issue.labels.is_empty() {
+        labels.push(Span::styled(
+            " No labels",
+            Style::default().fg(Color::DarkGray),
+        ));
+    } else {
+        labels.extend(issue.labels.iter().map(label_span));
+    }
+    if !issue.assignees.is_empty() {
+        let assignees: Vec<String> = issue
+            .assignees
+            .iter()
+            .map(|u| format!("@{}", u.login))
+            .collect();
+        labels.push(Span::styled(
+            format!("  assigned to {}", assignees.join(", ")),
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+    let header = Paragraph::new(vec![
+        Line::from(vec![
+            Span::styled(
+                format!("  #{} ", issue.number),
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                issue.title.clone(),
+                Style::default()
+                    .fg(Color::White)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ]),
+        Line::from(vec![
+            Span::raw("  "),
+            Span::styled(
+                if open { " OPEN " } else { " CLOSED " },
+                Style::default()
+                    .fg(Color::Black)
+                    .bg(if open { Color::Green } else { Color::Red })
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!(
+                    "  opened by @{} on {}",
+                    issue.user.login,
+                    crate::ui::datetime::format_iso(&issue.created_at, &issue.created_at)
+                ),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]),
+        Line::from(labels),
+    ])
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan)),
+    );
+    f.render_widget(header, chunks[0]);
+
+    let text = Style::default().fg(Color::White);
+    let mut lines = crate::ui::markdown::render(
+        issue
+            .body
+            .as_deref()
+            .filter(|b| !b.trim().is_empty())
+            .unwrap_or("No description provided."),
+        "  ",
+        text,
+    );
+    for comment in &issues_state.comments {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            format!(
+                "  ── @{} · {} ──",
+                comment.user.login,
+                crate::ui::datetime::format_iso(&comment.created_at, &comment.created_at)
+            ),
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )));
+        lines.extend(crate::ui::markdown::render(
+            comment.body.as_deref().unwrap_or_default(),
+            "  ",
+            text,
+        ));
+    }
+    let body = Paragraph::new(lines)
+        .wrap(Wrap { trim: false })
+        .scroll((issues_state.detail_scroll, 0))
+        .block(
+            Block::default()
+                .title(Span::styled(
+                    format!(" Conversation ({} comments) ", issues_state.comments.len()),
+                    Style::default().fg(Color::White),
+                ))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::DarkGray)),
+        );
+    f.render_widget(&body, chunks[1]);
+    render_paragraph_scrollbar(f, chunks[1], &body, issues_state.detail_scroll);
+
+    let keys = Paragraph::new(Line::from(vec![
+        Span::styled(" [c]", Style::default().fg(Color::Cyan)),
+        Span::raw(" Comment "),
+        Span::styled("[l]", Style::default().fg(Color::Yellow)),
+        Span::raw(" Labels "),
+        Span::styled("[x]", Style::default().fg(Color::Red)),
+        Span::raw(if open { " Close " } else { " Reopen " }),
+        Span::styled("[o]", Style::default().fg(Color::Cyan)),
+        Span::raw(" Browser "),
+        Span::styled("[r]", Style::default().fg(Color::Green)),
+        Span::raw(" Refresh "),
+        Span::styled("[Esc]", Style::default().fg(Color::DarkGray)),
+        Span::raw(" Back"),
+    ]));
+    f.render_widget(keys, chunks[2]);
+
+    let status = match (&issues_state.error, issues_state.loading) {
+        (Some(err), _) => Span::styled(format!(" {}", err), Style::default().fg(Color::Red)),
+        (None, true) => Span::styled(
+            format!(" {} Updating...", Icon::Loading),
+            Style::default().fg(Color::DarkGray),
+        ),
+        (None, false) => Span::raw(""),
+    };
+    f.render_widget(Paragraph::new(status), chunks[3]);
+}
+
+fn handle_issues_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()> {
+    let state = &mut app.github_state.issues_state;
+    let len = state.visible().len();
+    match key.code {
+        KeyCode::Esc => {
+            app.github_state.view = GitHubView::Menu;
+            state.error = None;
+        }
+        KeyCode::Up | KeyCode::Char('k') if state.selected > 0 => {
+            state.select(state.selected - 1);
+        }
+        KeyCode::Down | KeyCode::Char('j') if state.selected + 1 < len => {
+            state.select(state.selected + 1);
+        }
+        KeyCode::Enter => {
+            if let Some(issue) = state.visible().get(state.selected).map(|i| (*i).clone()) {
+                let number = issue.number;
+                state.detail = Some(issue);
+                state.comments.clear();
+                state.detail_scroll = 0;
+                app.github_state.view = GitHubView::IssueDetail(number);
+                start_load_issue_detail(app, number);
+            }
+        }
+        KeyCode::Char('/') => {
+            app.popup = crate::app::Popup::Input {
+                title: "Search Issues".to_string(),
+                prompt: "Words, #number, @user or label: ".to_string(),
+                value: state.query.clone(),
+                on_submit: crate::app::InputAction::FilterIssues,
+            };
+        }
+        KeyCode::Char('n') => {
+            app.popup = crate::app::Popup::Input {
+                title: "New Issue".to_string(),
+                prompt: "Title: ".to_string(),
+                value: String::new(),
+                on_submit: crate::app::InputAction::IssueTitle,
+            };
+        }
+        KeyCode::Char('f') => {
+            state.filter = state.filter.next();
+            start_load_issues(app);
+        }
+        KeyCode::Char('r') => start_load_issues(app),
+        _ => {}
+    }
+    Ok(())
+}
+
+fn handle_issue_detail_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()> {
+    let state = &mut app.github_state.issues_state;
+    let Some(issue) = state.detail.as_ref() else {
+        if key.code == KeyCode::Esc {
+            app.github_state.view = GitHubView::Issues;
+        }
+        return Ok(());
+    };
+    let number = issue.number;
+    match key.code {
+        KeyCode::Esc => {
+            app.github_state.view = GitHubView::Issues;
+            state.error = None;
+            // Refresh the list in case comments, labels or state changed
+            start_load_issues(app);
+        }
+        KeyCode::Up | KeyCode::Char('k') => {
+            state.detail_scroll = state.detail_scroll.saturating_sub(1);
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            state.detail_scroll = state.detail_scroll.saturating_add(1);
+        }
+        KeyCode::PageUp => state.detail_scroll = state.detail_scroll.saturating_sub(10),
+        KeyCode::PageDown => state.detail_scroll = state.detail_scroll.saturating_add(10),
+        KeyCode::Char('c') => {
+            app.popup = crate::app::Popup::Input {
+                title: format!("Comment on #{}", number),
+                prompt: "Comment: ".to_string(),
+                value: String::new(),
+                on_submit: crate::app::InputAction::IssueComment(number),
+            };
+        }
+        KeyCode::Char('l') => {
+            if state.labels.is_empty() {
+                state.error = Some("This repository has no labels".to_string());
+                return Ok(());
+            }
+            let known: Vec<&str> = state.labels.iter().map(|l| l.name.as_str()).collect();
+            let current: Vec<&str> = issue.labels.iter().map(|l| l.name.as_str()).collect();
+            app.popup = crate::app::Popup::Input {
+                title: truncate_to_width(&format!("Labels — {}", known.join(", ")), 70),
+                prompt: "Labels: ".to_string(),
+                value: current.join(", "),
+                on_submit: crate::app::InputAction::IssueLabels(number),
+            };
+        }
+        KeyCode::Char('x') if issue.state == "open" => {
+            app.popup = crate::app::Popup::Confirm {
+                title: "Close Issue".to_string(),
+                message: format!("Close issue #{}?\n\n[y] Yes  [n] No", number),
+                on_confirm: crate::app::ConfirmAction::CloseIssue(number),
+            };
+        }
+        KeyCode::Char('x') => update_issue(app, number, move |token| {
+            git::github_auth::set_issue_state(token, number, true)?;
+            Ok(format!("✓ Reopened issue #{}", number))
+        }),
+        KeyCode::Char('o') => open_in_browser(&issue.html_url),
+        KeyCode::Char('r') => start_load_issue_detail(app, number),
+        _ => {}
+    }
+    Ok(())
+}
+
+// ─── GitHub Actions Rendering ────────────────────────────────
+
+fn status_color(status: Option<&str>, conclusion: Option<&str>) -> Color {
+    match conclusion.unwrap_or("") {
+        "success" => Color::Green,
+        "failure" | "timed_out" => Color::Red,
+        "cancelled" => Color::DarkGray,
+        "skipped" => Color::DarkGray,
+        _ => match status.unwrap_or("") {
+            "in_progress" => Color::Cyan,
+            "queued" | "waiting" | "pending" => Color::Yellow,
+            "completed" => Color::Green,
+            _ => Color::White,
+        },
+    }
+}
+
+fn status_icon(status: Option<&str>, conclusion: Option<&str>) -> &'static str {
+    match conclusion.unwrap_or("") {
+        "success" => Icon::Success.glyph(),
+        "failure" | "timed_out" => Icon::Failure.glyph(),
+        "cancelled" => Icon::Cancelled.glyph(),
+        "skipped" => Icon::Skipped.glyph(),
+        _ => match status.unwrap_or("") {
+            "in_progress" => Icon::InProgress.glyph(),
+            "queued" | "waiting" | "pending" => Icon::Queued.glyph(),
+            _ => Icon::Cancelled.glyph(),
+        },
+    }
+}
+
+/// "● live" after a title while it's being refreshed.
+fn live_span(live: bool) -> Span<'static> {
+    if live {
+        Span::styled("  ● live", Style::default().fg(Color::Cyan))
+    } else {
+        Span::raw("")
+    }
+}
+
+fn render_actions_list(f: &mut Frame, area: Rect, state: &mut GitHubState) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(zen::chrome(3)), // Title
+            Constraint::Min(6),                 // Runs list
+            Constraint::Length(zen::chrome(2)), // Keys
+            Constraint::Length(2),              // Status/Error
+        ])
+        .split(area);
+
+    let title = Paragraph::new(Line::from(vec![
+        Span::styled(format!("  {} ", Icon::Bolt), Style::default()),
         Span::styled(
-            "GitHub Actions",
+            "CI Runs",
             Style::default()
                 .fg(Color::White)
                 .add_modifier(Modifier::BOLD),
         ),
         Span::styled(
-            format!("  ({} runs)", state.actions_state.runs.len()),
+            format!(
+                "  {} · {} runs",
+                state
+                    .actions_state
+                    .branch_filter()
+                    .unwrap_or_else(|| "all branches".to_string()),
+                state.actions_state.runs.len()
+            ),
             Style::default().fg(Color::DarkGray),
         ),
+        live_span(state.actions_state.is_live(&state.view)),
     ]))
     .block(
         Block::default()
@@ -2073,7 +4387,10 @@ fn render_actions_list(f: &mut Frame, area: Rect, state: &mut GitHubState) {
     // Loading state
     if state.actions_state.loading && state.actions_state.runs.is_empty() {
         let loading = Paragraph::new(Line::from(vec![
-            Span::styled("  ⏳ ", Style::default().fg(Color::Cyan)),
+            Span::styled(
+                format!("  {} ", Icon::Loading),
+                Style::default().fg(Color::Cyan),
+            ),
             Span::styled(
                 "Loading workflow runs...",
                 Style::default().fg(Color::DarkGray),
@@ -2121,7 +4438,7 @@ fn render_actions_list(f: &mut Frame, area: Rect, state: &mut GitHubState) {
                         Style::default().fg(Color::DarkGray),
                     ),
                     Span::styled(
-                        truncate_str(name, 35),
+                        pad_to_width(name, 35),
                         Style::default()
                             .fg(Color::White)
                             .add_modifier(Modifier::BOLD),
@@ -2152,13 +4469,27 @@ fn render_actions_list(f: &mut Frame, area: Rect, state: &mut GitHubState) {
             )
             .highlight_symbol("▶ ");
 
-        f.render_stateful_widget(list, chunks[1], &mut state.actions_state.list_state);
+        f.render_stateful_widget(&list, chunks[1], &mut state.actions_state.list_state);
+        render_scrollbar(
+            f,
+            chunks[1],
+            list.len(),
+            state.actions_state.list_state.offset(),
+        );
     }
 
     // Keys
     let keys = Paragraph::new(Line::from(vec![
         Span::styled(" [Enter]", Style::default().fg(Color::Cyan)),
         Span::raw(" View "),
+        Span::styled("[R]", Style::default().fg(Color::Cyan)),
+        Span::raw(" Re-run failed "),
+        Span::styled("[b]", Style::default().fg(Color::Cyan)),
+        Span::raw(if state.actions_state.all_branches {
+            " This branch "
+        } else {
+            " All branches "
+        }),
         Span::styled("[r]", Style::default().fg(Color::Yellow)),
         Span::raw(" Refresh "),
         Span::styled("[Esc]", Style::default().fg(Color::DarkGray)),
@@ -2169,28 +4500,20 @@ fn render_actions_list(f: &mut Frame, area: Rect, state: &mut GitHubState) {
     // Error
     if let Some(ref err) = state.actions_state.error {
         let status = Paragraph::new(Span::styled(
-            format!(" ❌ {}", err),
+            format!(" {} {}", Icon::Failure, err),
             Style::default().fg(Color::Red),
         ));
         f.render_widget(status, chunks[3]);
     }
 }
 
-fn truncate_str(s: &str, max_len: usize) -> String {
-    if s.len() > max_len {
-        format!("{}…", &s[..max_len - 1])
-    } else {
-        s.to_string()
-    }
-}
-
 fn render_action_detail(f: &mut Frame, area: Rect, state: &mut GitHubState) {
     let outer_chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(3), // Title
-            Constraint::Min(8),    // Content (split: jobs | logs)
-            Constraint::Length(2), // Keys
+            Constraint::Length(zen::chrome(3)), // Title
+            Constraint::Min(8),                 // Content (split: jobs | logs)
+            Constraint::Length(zen::chrome(2)), // Keys
         ])
         .split(area);
 
@@ -2212,12 +4535,15 @@ fn render_action_detail(f: &mut Frame, area: Rect, state: &mut GitHubState) {
         "  Workflow Run".to_string()
     };
 
-    let title = Paragraph::new(Span::styled(
-        run_title,
-        Style::default()
-            .fg(Color::White)
-            .add_modifier(Modifier::BOLD),
-    ))
+    let title = Paragraph::new(Line::from(vec![
+        Span::styled(
+            run_title,
+            Style::default()
+                .fg(Color::White)
+                .add_modifier(Modifier::BOLD),
+        ),
+        live_span(state.actions_state.is_live(&state.view)),
+    ]))
     .block(
         Block::default()
             .borders(Borders::ALL)
@@ -2225,19 +4551,33 @@ fn render_action_detail(f: &mut Frame, area: Rect, state: &mut GitHubState) {
     );
     f.render_widget(title, outer_chunks[0]);
 
-    // Split content area: jobs (left) | logs (right)
+    // Split content area: jobs (left) | logs (right), stacked on narrow terminals
     let content_chunks = Layout::default()
-        .direction(Direction::Horizontal)
+        .direction(if is_narrow(area) {
+            Direction::Vertical
+        } else {
+            Direction::Horizontal
+        })
         .constraints([
             Constraint::Percentage(35), // Jobs list
             Constraint::Percentage(65), // Logs
         ])
         .split(outer_chunks[1]);
 
+    // Left column: jobs, with the quickfix list below when the run has annotations
+    let left = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(if state.actions_state.problems.is_empty() {
+            [Constraint::Percentage(100), Constraint::Length(0)]
+        } else {
+            [Constraint::Percentage(50), Constraint::Percentage(50)]
+        })
+        .split(content_chunks[0]);
+
     // Jobs list
     if state.actions_state.loading && state.actions_state.jobs.is_empty() {
         let loading = Paragraph::new(Span::styled(
-            "  ⏳ Loading jobs...",
+            format!("  {} Loading jobs...", Icon::Loading),
             Style::default().fg(Color::DarkGray),
         ))
         .block(
@@ -2252,7 +4592,7 @@ fn render_action_detail(f: &mut Frame, area: Rect, state: &mut GitHubState) {
                     },
                 )),
         );
-        f.render_widget(loading, content_chunks[0]);
+        f.render_widget(loading, left[0]);
     } else {
         let job_items: Vec<ListItem> = state
             .actions_state
@@ -2300,13 +4640,19 @@ fn render_action_detail(f: &mut Frame, area: Rect, state: &mut GitHubState) {
             )
             .highlight_symbol("▶ ");
 
-        f.render_stateful_widget(
-            job_list,
-            content_chunks[0],
-            &mut state.actions_state.job_list_state,
+        f.render_stateful_widget(&job_list, left[0], &mut state.actions_state.job_list_state);
+        render_scrollbar(
+            f,
+            left[0],
+            job_list.len(),
+            state.actions_state.job_list_state.offset(),
         );
     }
 
+    if !state.actions_state.problems.is_empty() {
+        render_problems(f, left[1], &mut state.actions_state);
+    }
+
     // Logs pane
     let logs_border_color = if state.actions_state.detail_pane == ActionDetailPane::Logs {
         Color::Cyan
@@ -2314,7 +4660,45 @@ fn render_action_detail(f: &mut Frame, area: Rect, state: &mut GitHubState) {
         Color::DarkGray
     };
 
-    if let Some(ref logs) = state.actions_state.job_logs {
+    let focused_problem = if state.actions_state.detail_pane == ActionDetailPane::Problems {
+        state
+            .actions_state
+            .problems
+            .get(state.actions_state.selected_problem)
+    } else {
+        None
+    };
+    if let Some(problem) = focused_problem {
+        // Full annotation of the highlighted problem
+        let a = &problem.annotation;
+        let mut lines = vec![
+            Line::from(Span::styled(
+                format!("  {}:{}", a.path, a.start_line),
+                Style::default()
+                    .fg(Color::White)
+                    .add_modifier(Modifier::BOLD),
+            )),
+            Line::from(Span::styled(
+                format!("  {} · {}", problem.job, a.annotation_level),
+                Style::default().fg(Color::DarkGray),
+            )),
+            Line::from(""),
+        ];
+        if let Some(ref title) = a.title {
+            lines.push(Line::from(Span::styled(
+                format!("  {}", title),
+                Style::default().fg(Color::Yellow),
+            )));
+        }
+        lines.extend(a.message.lines().map(|l| Line::from(format!("  {}", l))));
+        let detail = Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+            Block::default()
+                .title(Span::styled(" Problem ", Style::default().fg(Color::White)))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(logs_border_color)),
+        );
+        f.render_widget(detail, content_chunks[1]);
+    } else if let Some(ref logs) = state.actions_state.job_logs {
         let log_lines: Vec<Line> = logs
             .lines()
             .skip(state.actions_state.log_scroll as usize)
@@ -2353,6 +4737,40 @@ fn render_action_detail(f: &mut Frame, area: Rect, state: &mut GitHubState) {
                 .border_style(Style::default().fg(logs_border_color)),
         );
         f.render_widget(logs_widget, content_chunks[1]);
+    } else if let Some(job) = state
+        .actions_state
+        .log_job
+        .and_then(|id| state.actions_state.jobs.iter().find(|j| j.id == id))
+    {
+        // Logs are published as the job goes; until then, follow its steps
+        let mut lines = vec![
+            Line::from(Span::styled(
+                format!("  {} Waiting for logs — steps so far:", Icon::Loading),
+                Style::default().fg(Color::DarkGray),
+            )),
+            Line::from(""),
+        ];
+        lines.extend(job.steps.iter().map(|step| {
+            let status = Some(step.status.as_str());
+            let conclusion = step.conclusion.as_deref();
+            Line::from(vec![
+                Span::raw(format!("  {} ", status_icon(status, conclusion))),
+                Span::styled(
+                    step.name.clone(),
+                    Style::default().fg(status_color(status, conclusion)),
+                ),
+            ])
+        }));
+        let steps = Paragraph::new(lines).block(
+            Block::default()
+                .title(Span::styled(
+                    format!(" {} — Steps ", job.name),
+                    Style::default().fg(Color::White),
+                ))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(logs_border_color)),
+        );
+        f.render_widget(steps, content_chunks[1]);
     } else {
         let placeholder = Paragraph::new(Line::from(vec![
             Span::styled(
@@ -2376,7 +4794,15 @@ fn render_action_detail(f: &mut Frame, area: Rect, state: &mut GitHubState) {
         Span::styled(" [Tab]", Style::default().fg(Color::Cyan)),
         Span::raw(" Switch pane "),
         Span::styled("[Enter]", Style::default().fg(Color::Cyan)),
-        Span::raw(" Load logs "),
+        Span::raw(
+            if state.actions_state.detail_pane == ActionDetailPane::Problems {
+                " Open in editor "
+            } else {
+                " Load logs "
+            },
+        ),
+        Span::styled("[R]", Style::default().fg(Color::Cyan)),
+        Span::raw(" Re-run failed "),
         Span::styled("[r]", Style::default().fg(Color::Yellow)),
         Span::raw(" Refresh "),
         Span::styled("[Esc]", Style::default().fg(Color::DarkGray)),
@@ -2385,6 +4811,61 @@ fn render_action_detail(f: &mut Frame, area: Rect, state: &mut GitHubState) {
     f.render_widget(keys, outer_chunks[2]);
 }
 
+/// The quickfix list: one line per annotation, `path:line` and the message.
+fn render_problems(f: &mut Frame, area: Rect, state: &mut ActionsState) {
+    let width = area.width.saturating_sub(4) as usize;
+    let items: Vec<ListItem> = state
+        .problems
+        .iter()
+        .map(|p| {
+            let a = &p.annotation;
+            let (icon, color) = match a.annotation_level.as_str() {
+                "failure" => (Icon::Failure, Color::Red),
+                "warning" => (Icon::Warning, Color::Yellow),
+                _ => (Icon::Idea, Color::Cyan),
+            };
+            let location = format!("{}:{}", a.path, a.start_line);
+            let message = a.message.lines().next().unwrap_or_default();
+            let used = location.chars().count() + 6;
+            ListItem::new(Line::from(vec![
+                Span::styled(format!(" {} ", icon), Style::default().fg(color)),
+                Span::styled(location, Style::default().fg(Color::White)),
+                Span::styled(
+                    format!(
+                        "  {}",
+                        truncate_to_width(message, width.saturating_sub(used))
+                    ),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]))
+        })
+        .collect();
+
+    let border = if state.detail_pane == ActionDetailPane::Problems {
+        Color::Cyan
+    } else {
+        Color::DarkGray
+    };
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .title(Span::styled(
+                    format!(" Problems ({}) ", state.problems.len()),
+                    Style::default().fg(Color::White),
+                ))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(border)),
+        )
+        .highlight_style(
+            Style::default()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol("▶ ");
+    f.render_stateful_widget(&list, area, &mut state.problem_list_state);
+    render_scrollbar(f, area, list.len(), state.problem_list_state.offset());
+}
+
 // ─── Actions Key Handlers ────────────────────────────────
 
 fn handle_actions_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()> {
@@ -2392,22 +4873,19 @@ fn handle_actions_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Resul
         KeyCode::Esc => {
             app.github_state.view = GitHubView::Menu;
         }
-        KeyCode::Up | KeyCode::Char('k') => {
-            if app.github_state.actions_state.selected > 0 {
-                app.github_state.actions_state.selected -= 1;
-                let sel = app.github_state.actions_state.selected;
-                app.github_state.actions_state.list_state.select(Some(sel));
-            }
+        KeyCode::Up | KeyCode::Char('k') if app.github_state.actions_state.selected > 0 => {
+            app.github_state.actions_state.selected -= 1;
+            let sel = app.github_state.actions_state.selected;
+            app.github_state.actions_state.list_state.select(Some(sel));
         }
-        KeyCode::Down | KeyCode::Char('j') => {
+        KeyCode::Down | KeyCode::Char('j')
             if !app.github_state.actions_state.runs.is_empty()
                 && app.github_state.actions_state.selected + 1
-                    < app.github_state.actions_state.runs.len()
-            {
-                app.github_state.actions_state.selected += 1;
-                let sel = app.github_state.actions_state.selected;
-                app.github_state.actions_state.list_state.select(Some(sel));
-            }
+                    < app.github_state.actions_state.runs.len() =>
+        {
+            app.github_state.actions_state.selected += 1;
+            let sel = app.github_state.actions_state.selected;
+            app.github_state.actions_state.list_state.select(Some(sel));
         }
         KeyCode::Enter => {
             if let Some(run) = app
@@ -2420,12 +4898,28 @@ fn handle_actions_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Resul
                 app.github_state.view = GitHubView::ActionDetail(run_id);
                 app.github_state.actions_state.jobs.clear();
                 app.github_state.actions_state.job_logs = None;
+                app.github_state.actions_state.log_job = None;
                 app.github_state.actions_state.selected_job = 0;
                 app.github_state.actions_state.log_scroll = 0;
                 app.github_state.actions_state.detail_pane = ActionDetailPane::Jobs;
+                app.github_state.actions_state.problems.clear();
                 start_load_action_detail(app, run_id);
             }
         }
+        KeyCode::Char('R') => {
+            let state = &app.github_state.actions_state;
+            if let Some(run) = state.runs.get(state.selected) {
+                confirm_rerun(app, run.id);
+            }
+        }
+        KeyCode::Char('b') => {
+            let state = &mut app.github_state.actions_state;
+            state.all_branches = !state.all_branches;
+            state.runs.clear();
+            state.selected = 0;
+            state.list_state.select(None);
+            start_load_actions(app);
+        }
         KeyCode::Char('r') => {
             start_load_actions(app);
         }
@@ -2439,12 +4933,15 @@ fn handle_action_detail_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow:
         KeyCode::Esc => {
             app.github_state.view = GitHubView::Actions;
             app.github_state.actions_state.job_logs = None;
+            app.github_state.actions_state.log_job = None;
         }
         KeyCode::Tab => {
+            let has_problems = !app.github_state.actions_state.problems.is_empty();
             app.github_state.actions_state.detail_pane =
                 match app.github_state.actions_state.detail_pane {
                     ActionDetailPane::Jobs => ActionDetailPane::Logs,
-                    ActionDetailPane::Logs => ActionDetailPane::Jobs,
+                    ActionDetailPane::Logs if has_problems => ActionDetailPane::Problems,
+                    ActionDetailPane::Logs | ActionDetailPane::Problems => ActionDetailPane::Jobs,
                 };
         }
         KeyCode::Up | KeyCode::Char('k') => match app.github_state.actions_state.detail_pane {
@@ -2464,6 +4961,15 @@ fn handle_action_detail_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow:
                         app.github_state.actions_state.log_scroll.saturating_sub(3);
                 }
             }
+            ActionDetailPane::Problems => {
+                let state = &mut app.github_state.actions_state;
+                if state.selected_problem > 0 {
+                    state.selected_problem -= 1;
+                    state
+                        .problem_list_state
+                        .select(Some(state.selected_problem));
+                }
+            }
         },
         KeyCode::Down | KeyCode::Char('j') => match app.github_state.actions_state.detail_pane {
             ActionDetailPane::Jobs => {
@@ -2482,7 +4988,28 @@ fn handle_action_detail_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow:
             ActionDetailPane::Logs => {
                 app.github_state.actions_state.log_scroll += 3;
             }
+            ActionDetailPane::Problems => {
+                let state = &mut app.github_state.actions_state;
+                if state.selected_problem + 1 < state.problems.len() {
+                    state.selected_problem += 1;
+                    state
+                        .problem_list_state
+                        .select(Some(state.selected_problem));
+                }
+            }
         },
+        KeyCode::Enter
+            if app.github_state.actions_state.detail_pane == ActionDetailPane::Problems =>
+        {
+            // Jump to the failing line locally
+            let state = &app.github_state.actions_state;
+            if let Some(problem) = state.problems.get(state.selected_problem) {
+                app.editor_request = Some(crate::ui::editor::EditorTarget::File {
+                    path: problem.annotation.path.clone(),
+                    line: problem.annotation.start_line.max(1),
+                });
+            }
+        }
         KeyCode::Enter => {
             // Load logs for the selected job
             if let Some(job) = app
@@ -2492,13 +5019,22 @@ fn handle_action_detail_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow:
                 .get(app.github_state.actions_state.selected_job)
             {
                 let job_id = job.id;
-                app.github_state.actions_state.log_scroll = 0;
+                let state = &mut app.github_state.actions_state;
+                state.log_scroll = 0;
+                state.job_logs = None;
+                state.log_job = Some(job_id);
                 start_load_job_logs(app, job_id);
             }
         }
+        KeyCode::Char('R') => {
+            if let GitHubView::ActionDetail(run_id) = app.github_state.view {
+                confirm_rerun(app, run_id);
+            }
+        }
         KeyCode::Char('r') => {
             // Refresh detail
             if let GitHubView::ActionDetail(run_id) = app.github_state.view {
+                app.github_state.actions_state.problems.clear();
                 start_load_action_detail(app, run_id);
             }
         }
@@ -2514,12 +5050,13 @@ fn start_load_actions(app: &mut crate::app::App) {
     app.github_state.actions_state.error = None;
     let token = app.config.github.get_token().unwrap_or_default();
     let bg = app.github_state.actions_state.bg_result.clone();
-    std::thread::spawn(move || {
-        let result = git::github_auth::list_workflow_runs(&token)
+    let branch = app.github_state.actions_state.branch_filter();
+    app.jobs.submit("actions", Priority::User, move || {
+        let result = git::github_auth::list_workflow_runs(&token, branch.as_deref())
             .map(|r| r.workflow_runs)
             .map_err(|e| e.to_string());
         if let Ok(mut r) = bg.lock() {
-            *r = Some(ActionsBgResult::RunsList(result));
+            r.push(ActionsBgResult::RunsList(result));
         }
     });
 }
@@ -2529,12 +5066,49 @@ fn start_load_action_detail(app: &mut crate::app::App, run_id: u64) {
     app.github_state.actions_state.error = None;
     let token = app.config.github.get_token().unwrap_or_default();
     let bg = app.github_state.actions_state.bg_result.clone();
-    std::thread::spawn(move || {
+    let key = format!("run-jobs:{}", run_id);
+    app.jobs.submit(key, Priority::User, move || {
         let result = git::github_auth::list_run_jobs(&token, run_id)
             .map(|r| r.jobs)
             .map_err(|e| e.to_string());
         if let Ok(mut r) = bg.lock() {
-            *r = Some(ActionsBgResult::RunDetail(result));
+            r.push(ActionsBgResult::RunDetail(result));
+        }
+    });
+}
+
+/// Fetch the annotations of the failed `jobs` (id, name) of a run.
+fn start_load_annotations(app: &mut crate::app::App, run_id: u64, jobs: Vec<(u64, String)>) {
+    let token = app.config.github.get_token().unwrap_or_default();
+    let bg = app.github_state.actions_state.bg_result.clone();
+    let key = format!("annotations:{}", run_id);
+    app.jobs.submit(key, Priority::Background, move || {
+        let mut problems = Vec::new();
+        let mut result = Ok(());
+        for (job_id, job) in jobs {
+            match git::github_auth::list_check_annotations(&token, job_id) {
+                Ok(annotations) => {
+                    problems.extend(annotations.into_iter().map(|annotation| CiProblem {
+                        job: job.clone(),
+                        annotation,
+                    }))
+                }
+                Err(e) => result = Err(e.to_string()),
+            }
+        }
+        // Failures first, then warnings, then notices
+        let rank = |p: &CiProblem| match p.annotation.annotation_level.as_str() {
+            "failure" => 0,
+            "warning" => 1,
+            _ => 2,
+        };
+        problems.sort_by_key(rank);
+        let result = match result {
+            Err(e) if problems.is_empty() => Err(e),
+            _ => Ok(problems),
+        };
+        if let Ok(mut r) = bg.lock() {
+            r.push(ActionsBgResult::Annotations(run_id, result));
         }
     });
 }
@@ -2543,66 +5117,212 @@ fn start_load_job_logs(app: &mut crate::app::App, job_id: u64) {
     app.github_state.actions_state.loading = true;
     let token = app.config.github.get_token().unwrap_or_default();
     let bg = app.github_state.actions_state.bg_result.clone();
-    std::thread::spawn(move || {
+    let key = format!("job-logs:{}", job_id);
+    app.jobs.submit(key, Priority::User, move || {
         let result = git::github_auth::get_job_logs(&token, job_id).map_err(|e| e.to_string());
         if let Ok(mut r) = bg.lock() {
-            *r = Some(ActionsBgResult::JobLogs(job_id, result));
+            r.push(ActionsBgResult::JobLogs(job_id, result));
+        }
+    });
+}
+
+/// Ask before re-running the failed jobs of a run.
+fn confirm_rerun(app: &mut crate::app::App, run_id: u64) {
+    let state = &app.github_state.actions_state;
+    let Some(run) = state.runs.iter().find(|r| r.id == run_id) else {
+        return;
+    };
+    if !is_completed(run.status.as_deref()) {
+        app.set_status(format!("Run #{} is still going", run.run_number));
+        return;
+    }
+    if matches!(
+        run.conclusion.as_deref(),
+        Some("success" | "skipped" | "neutral")
+    ) {
+        app.set_status(format!("Nothing failed in run #{}", run.run_number));
+        return;
+    }
+    // The jobs loaded are the open run's
+    let failed: Vec<&str> = if app.github_state.view == GitHubView::ActionDetail(run_id) {
+        state
+            .jobs
+            .iter()
+            .filter(|j| {
+                matches!(
+                    j.conclusion.as_deref(),
+                    Some("failure" | "timed_out" | "cancelled")
+                )
+            })
+            .map(|j| j.name.as_str())
+            .collect()
+    } else {
+        Vec::new()
+    };
+    let which = if failed.is_empty() {
+        "its failed jobs".to_string()
+    } else {
+        failed.join(", ")
+    };
+    app.popup = crate::app::Popup::Confirm {
+        title: "Re-run Failed Jobs".to_string(),
+        message: format!(
+            "Re-run {} in run #{} ({}), and the jobs that depend on them?\n\n[y] Yes  [n] No",
+            which,
+            run.run_number,
+            run.display_title
+                .as_deref()
+                .or(run.name.as_deref())
+                .unwrap_or("Workflow")
+        ),
+        on_confirm: crate::app::ConfirmAction::RerunFailedJobs(run_id),
+    };
+}
+
+/// Re-run the failed jobs of a run, then follow it.
+pub fn rerun_failed_jobs(app: &mut crate::app::App, run_id: u64) {
+    let Some(token) = app.config.github.get_token() else {
+        return;
+    };
+    let Some(run_number) = app
+        .github_state
+        .actions_state
+        .runs
+        .iter()
+        .find(|r| r.id == run_id)
+        .map(|r| r.run_number)
+    else {
+        return;
+    };
+    let state = &mut app.github_state.actions_state;
+    state.loading = true;
+    state.error = None;
+    let bg = state.bg_result.clone();
+    let key = format!("rerun:{}", run_id);
+    app.jobs.submit(key, Priority::User, move || {
+        let result = git::github_auth::rerun_failed_jobs(&token, run_id)
+            .map(|()| format!("✓ Re-running the failed jobs of run #{}", run_number))
+            .map_err(|e| e.to_string());
+        if let Ok(mut r) = bg.lock() {
+            r.push(ActionsBgResult::Rerun(run_id, result));
         }
     });
 }
 
-/// Called on each tick to poll for Actions background results.
+/// Called on each tick to poll for Actions background results, and to refresh
+/// runs and jobs that are still going.
 pub fn tick_actions_state(app: &mut crate::app::App) {
     let bg_taken = {
         if let Ok(mut result) = app.github_state.actions_state.bg_result.try_lock() {
-            result.take()
+            std::mem::take(&mut *result)
         } else {
-            None
+            Vec::new()
         }
     };
 
-    if let Some(bg) = bg_taken {
+    for bg in bg_taken {
         app.github_state.actions_state.loading = false;
         match bg {
             ActionsBgResult::RunsList(Ok(runs)) => {
-                app.github_state.actions_state.runs = runs;
-                app.github_state.actions_state.selected = 0;
-                app.github_state.actions_state.list_state.select(
-                    if app.github_state.actions_state.runs.is_empty() {
-                        None
-                    } else {
-                        Some(0)
-                    },
-                );
-                app.github_state.actions_state.error = None;
+                let state = &mut app.github_state.actions_state;
+                state.runs = runs;
+                state.selected = state.selected.min(state.runs.len().saturating_sub(1));
+                state
+                    .list_state
+                    .select((!state.runs.is_empty()).then_some(state.selected));
+                state.error = None;
             }
             ActionsBgResult::RunsList(Err(e)) => {
                 app.github_state.actions_state.error = Some(e);
             }
             ActionsBgResult::RunDetail(Ok(jobs)) => {
-                app.github_state.actions_state.jobs = jobs;
-                app.github_state.actions_state.selected_job = 0;
-                app.github_state.actions_state.job_list_state.select(
-                    if app.github_state.actions_state.jobs.is_empty() {
-                        None
-                    } else {
-                        Some(0)
-                    },
-                );
+                let state = &mut app.github_state.actions_state;
+                state.jobs = jobs;
+                state.selected_job = state.selected_job.min(state.jobs.len().saturating_sub(1));
+                state
+                    .job_list_state
+                    .select((!state.jobs.is_empty()).then_some(state.selected_job));
+                // Failed jobs usually carry annotations pointing at the culprit
+                let failed: Vec<(u64, String)> = app
+                    .github_state
+                    .actions_state
+                    .jobs
+                    .iter()
+                    .filter(|j| j.conclusion.as_deref() == Some("failure"))
+                    .map(|j| (j.id, j.name.clone()))
+                    .collect();
+                if let GitHubView::ActionDetail(run_id) = app.github_state.view
+                    && !failed.is_empty()
+                    && app.github_state.actions_state.problems.is_empty()
+                {
+                    start_load_annotations(app, run_id, failed);
+                }
+            }
+            ActionsBgResult::Annotations(run_id, Ok(problems)) => {
+                if app.github_state.view == GitHubView::ActionDetail(run_id) {
+                    let state = &mut app.github_state.actions_state;
+                    state.problems = problems;
+                    state.selected_problem = 0;
+                    state
+                        .problem_list_state
+                        .select((!state.problems.is_empty()).then_some(0));
+                }
+            }
+            ActionsBgResult::Annotations(_, Err(e)) => {
+                app.github_state.actions_state.error = Some(format!("Annotations: {}", e));
             }
             ActionsBgResult::RunDetail(Err(e)) => {
                 app.github_state.actions_state.error = Some(format!("Jobs: {}", e));
             }
+            ActionsBgResult::JobLogs(job_id, _)
+                if app.github_state.actions_state.log_job != Some(job_id) => {}
             ActionsBgResult::JobLogs(_job_id, Ok(logs)) => {
-                app.github_state.actions_state.job_logs = Some(logs);
-                app.github_state.actions_state.log_scroll = 0;
-                // Switch focus to logs pane
-                app.github_state.actions_state.detail_pane = ActionDetailPane::Logs;
+                let state = &mut app.github_state.actions_state;
+                // A refresh of logs already shown keeps the scroll and focus
+                if state.job_logs.is_none() {
+                    state.log_scroll = 0;
+                    state.detail_pane = ActionDetailPane::Logs;
+                }
+                state.job_logs = Some(logs);
+            }
+            ActionsBgResult::JobLogs(job_id, Err(e)) => {
+                let state = &mut app.github_state.actions_state;
+                let running = state
+                    .jobs
+                    .iter()
+                    .any(|j| j.id == job_id && !is_completed(Some(&j.status)));
+                // Logs of a running job may not be published yet; its steps show instead
+                if !running {
+                    state.job_logs = Some(format!("Failed to load logs: {}", e));
+                }
+            }
+            ActionsBgResult::Rerun(run_id, Ok(message)) => {
+                app.set_status(message);
+                app.github_state.actions_state.problems.clear();
+                start_load_actions(app);
+                if app.github_state.view == GitHubView::ActionDetail(run_id) {
+                    start_load_action_detail(app, run_id);
+                }
+            }
+            ActionsBgResult::Rerun(_, Err(e)) => {
+                app.github_state.actions_state.error = Some(format!("Re-run: {}", e));
             }
-            ActionsBgResult::JobLogs(_job_id, Err(e)) => {
-                app.github_state.actions_state.job_logs =
-                    Some(format!("Failed to load logs: {}", e));
+        }
+    }
+
+    let state = &app.github_state.actions_state;
+    if state.is_live(&app.github_state.view) && state.polled_at.elapsed() >= LIVE_INTERVAL {
+        app.github_state.actions_state.polled_at = Instant::now();
+        match app.github_state.view {
+            GitHubView::Actions => start_load_actions(app),
+            GitHubView::ActionDetail(run_id) => {
+                start_load_actions(app);
+                start_load_action_detail(app, run_id);
+                if let Some(job_id) = app.github_state.actions_state.log_job {
+                    start_load_job_logs(app, job_id);
+                }
             }
+            _ => {}
         }
     }
 }
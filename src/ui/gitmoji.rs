@@ -0,0 +1,274 @@
+//! Gitmoji catalogue, fuzzy search and conventional-commit prefixes.
+//!
+//! Used by the Commit view's emoji picker (`[commit] gitmoji`) and by
+//! `[commit] gitmoji_prefix`, which prepends the emoji matching a conventional type.
+
+/// One gitmoji: the emoji, its `:code:` name and what it is used for.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Gitmoji {
+    pub emoji: &'static str,
+    pub code: &'static str,
+    pub description: &'static str,
+}
+
+const fn g(emoji: &'static str, code: &'static str, description: &'static str) -> Gitmoji {
+    Gitmoji {
+        emoji,
+        code,
+        description,
+    }
+}
+
+/// The gitmoji.dev list, in its canonical order.
+pub const GITMOJIS: &[Gitmoji] = &[
+    g("🎨", "art", "Improve structure / format of the code"),
+    g("⚡️", "zap", "Improve performance"),
+    g("🔥", "fire", "Remove code or files"),
+    g("🐛", "bug", "Fix a bug"),
+    g("🚑️", "ambulance", "Critical hotfix"),
+    g("✨", "sparkles", "Introduce new features"),
+    g("📝", "memo", "Add or update documentation"),
+    g("🚀", "rocket", "Deploy stuff"),
+    g("💄", "lipstick", "Add or update the UI and style files"),
+    g("🎉", "tada", "Begin a project"),
+    g("✅", "white_check_mark", "Add, update, or pass tests"),
+    g("🔒️", "lock", "Fix security or privacy issues"),
+    g("🔐", "closed_lock_with_key", "Add or update secrets"),
+    g("🔖", "bookmark", "Release / Version tags"),
+    g("🚨", "rotating_light", "Fix compiler / linter warnings"),
+    g("🚧", "construction", "Work in progress"),
+    g("💚", "green_heart", "Fix CI Build"),
+    g("⬇️", "arrow_down", "Downgrade dependencies"),
+    g("⬆️", "arrow_up", "Upgrade dependencies"),
+    g("📌", "pushpin", "Pin dependencies to specific versions"),
+    g("👷", "construction_worker", "Add or update CI build system"),
+    g(
+        "📈",
+        "chart_with_upwards_trend",
+        "Add or update analytics or track code",
+    ),
+    g("♻️", "recycle", "Refactor code"),
+    g("➕", "heavy_plus_sign", "Add a dependency"),
+    g("➖", "heavy_minus_sign", "Remove a dependency"),
+    g("🔧", "wrench", "Add or update configuration files"),
+    g("🔨", "hammer", "Add or update development scripts"),
+    g(
+        "🌐",
+        "globe_with_meridians",
+        "Internationalization and localization",
+    ),
+    g("✏️", "pencil2", "Fix typos"),
+    g("💩", "poop", "Write bad code that needs to be improved"),
+    g("⏪️", "rewind", "Revert changes"),
+    g("🔀", "twisted_rightwards_arrows", "Merge branches"),
+    g("📦️", "package", "Add or update compiled files or packages"),
+    g("👽️", "alien", "Update code due to external API changes"),
+    g(
+        "🚚",
+        "truck",
+        "Move or rename resources (e.g.: files, paths, routes)",
+    ),
+    g("📄", "page_facing_up", "Add or update license"),
+    g("💥", "boom", "Introduce breaking changes"),
+    g("🍱", "bento", "Add or update assets"),
+    g("♿️", "wheelchair", "Improve accessibility"),
+    g("💡", "bulb", "Add or update comments in source code"),
+    g("🍻", "beers", "Write code drunkenly"),
+    g("💬", "speech_balloon", "Add or update text and literals"),
+    g("🗃️", "card_file_box", "Perform database related changes"),
+    g("🔊", "loud_sound", "Add or update logs"),
+    g("🔇", "mute", "Remove logs"),
+    g("👥", "busts_in_silhouette", "Add or update contributor(s)"),
+    g(
+        "🚸",
+        "children_crossing",
+        "Improve user experience / usability",
+    ),
+    g("🏗️", "building_construction", "Make architectural changes"),
+    g("📱", "iphone", "Work on responsive design"),
+    g("🤡", "clown_face", "Mock things"),
+    g("🥚", "egg", "Add or update an easter egg"),
+    g("🙈", "see_no_evil", "Add or update a .gitignore file"),
+    g("📸", "camera_flash", "Add or update snapshots"),
+    g("⚗️", "alembic", "Perform experiments"),
+    g("🔍️", "mag", "Improve SEO"),
+    g("🏷️", "label", "Add or update types"),
+    g("🌱", "seedling", "Add or update seed files"),
+    g(
+        "🚩",
+        "triangular_flag_on_post",
+        "Add, update, or remove feature flags",
+    ),
+    g("🥅", "goal_net", "Catch errors"),
+    g("💫", "dizzy", "Add or update animations and transitions"),
+    g(
+        "🗑️",
+        "wastebasket",
+        "Deprecate code that needs to be cleaned up",
+    ),
+    g(
+        "🛂",
+        "passport_control",
+        "Work on code related to authorization, roles and permissions",
+    ),
+    g(
+        "🩹",
+        "adhesive_bandage",
+        "Simple fix for a non-critical issue",
+    ),
+    g("🧐", "monocle_face", "Data exploration/inspection"),
+    g("⚰️", "coffin", "Remove dead code"),
+    g("🧪", "test_tube", "Add a failing test"),
+    g("👔", "necktie", "Add or update business logic"),
+    g("🩺", "stethoscope", "Add or update healthcheck"),
+    g("🧱", "bricks", "Infrastructure related changes"),
+    g("🧑‍💻", "technologist", "Improve developer experience"),
+    g(
+        "💸",
+        "money_with_wings",
+        "Add sponsorships or money related infrastructure",
+    ),
+    g(
+        "🧵",
+        "thread",
+        "Add or update code related to multithreading or concurrency",
+    ),
+    g(
+        "🦺",
+        "safety_vest",
+        "Add or update code related to validation",
+    ),
+];
+
+/// Gitmoji codes for conventional-commit types.
+const TYPE_CODES: &[(&str, &str)] = &[
+    ("feat", "sparkles"),
+    ("fix", "bug"),
+    ("docs", "memo"),
+    ("style", "art"),
+    ("refactor", "recycle"),
+    ("perf", "zap"),
+    ("test", "white_check_mark"),
+    ("build", "package"),
+    ("ci", "construction_worker"),
+    ("chore", "wrench"),
+    ("revert", "rewind"),
+    ("security", "lock"),
+    ("deps", "arrow_up"),
+    ("i18n", "globe_with_meridians"),
+    ("wip", "construction"),
+];
+
+/// Look up a gitmoji by its code (without colons).
+pub fn by_code(code: &str) -> Option<&'static Gitmoji> {
+    GITMOJIS.iter().find(|g| g.code == code)
+}
+
+/// Gitmojis matching `query`, best first. An empty query returns the whole list.
+///
+/// Codes are matched by prefix, substring, then as a subsequence ("wck" finds
+/// `white_check_mark`); descriptions are matched by substring.
+pub fn search(query: &str) -> Vec<&'static Gitmoji> {
+    let query = query.trim().to_lowercase();
+    if query.is_empty() {
+        return GITMOJIS.iter().collect();
+    }
+    let mut scored: Vec<(u8, usize, &'static Gitmoji)> = GITMOJIS
+        .iter()
+        .enumerate()
+        .filter_map(|(i, g)| score(g, &query).map(|s| (s, i, g)))
+        .collect();
+    scored.sort_by_key(|(s, i, _)| (*s, *i));
+    scored.into_iter().map(|(_, _, g)| g).collect()
+}
+
+/// Lower is better; `None` if the gitmoji does not match at all.
+fn score(g: &Gitmoji, query: &str) -> Option<u8> {
+    if g.code == query {
+        Some(0)
+    } else if g.code.starts_with(query) {
+        Some(1)
+    } else if g.code.contains(query) {
+        Some(2)
+    } else if g.description.to_lowercase().contains(query) {
+        Some(3)
+    } else if is_subsequence(query, g.code) {
+        Some(4)
+    } else {
+        None
+    }
+}
+
+fn is_subsequence(needle: &str, haystack: &str) -> bool {
+    let mut hay = haystack.chars();
+    needle.chars().all(|c| hay.any(|h| h == c))
+}
+
+/// Prefix a conventional-commit subject with the matching gitmoji, e.g.
+/// `feat(ui): add picker` → `✨ feat(ui): add picker`. Messages that already start
+/// with an emoji or `:code:`, or have no recognised type, are returned unchanged.
+pub fn auto_prefix(message: &str) -> String {
+    let subject = message.lines().next().unwrap_or("");
+    let already = subject.starts_with(':') || subject.chars().next().is_some_and(|c| !c.is_ascii());
+    if already {
+        return message.to_string();
+    }
+    let Some(colon) = subject.find(':') else {
+        return message.to_string();
+    };
+    let kind = subject[..colon]
+        .split('(')
+        .next()
+        .unwrap_or("")
+        .trim_end_matches('!')
+        .to_lowercase();
+    let emoji = TYPE_CODES
+        .iter()
+        .find(|(t, _)| *t == kind)
+        .and_then(|(_, code)| by_code(code));
+    match emoji {
+        Some(g) => format!("{} {}", g.emoji, message),
+        None => message.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_search_ranks_code_prefix_first() {
+        let found = search("bug");
+        assert_eq!(found[0].code, "bug");
+        let found = search("spark");
+        assert_eq!(found[0].emoji, "✨");
+        assert_eq!(search("").len(), GITMOJIS.len());
+        assert!(search("zzzzqq").is_empty());
+    }
+
+    #[test]
+    fn test_search_matches_descriptions_and_subsequences() {
+        assert!(search("typos").iter().any(|g| g.code == "pencil2"));
+        assert_eq!(search("wck")[0].code, "white_check_mark");
+    }
+
+    #[test]
+    fn test_type_codes_exist() {
+        for (kind, code) in TYPE_CODES {
+            assert!(by_code(code).is_some(), "missing gitmoji for {}", kind);
+        }
+    }
+
+    #[test]
+    fn test_auto_prefix() {
+        assert_eq!(
+            auto_prefix("feat(ui): add picker"),
+            "✨ feat(ui): add picker"
+        );
+        assert_eq!(auto_prefix("fix!: crash\n\nbody"), "🐛 fix!: crash\n\nbody");
+        assert_eq!(auto_prefix("✨ feat: x"), "✨ feat: x");
+        assert_eq!(auto_prefix(":bug: fix: x"), ":bug: fix: x");
+        assert_eq!(auto_prefix("Update README"), "Update README");
+        assert_eq!(auto_prefix("unknown: thing"), "unknown: thing");
+    }
+}
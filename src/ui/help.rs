@@ -1,185 +1,500 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::{
+    Frame,
     layout::Rect,
     style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Clear, Paragraph, Wrap},
-    Frame,
 };
 
-use super::utils::centered_rect;
+use super::utils::{centered_rect, render_paragraph_scrollbar};
 use crate::app::View;
+use crate::ui::icons::Icon;
 
-pub fn render(f: &mut Frame, area: Rect, current_view: View) {
-    // Center the popup
-    let popup_area = centered_rect(60, 70, area);
+/// One help entry: the key(s) as shown, what they do, and for the key
+/// inspector the git command behind it and a Learn topic.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Binding {
+    pub keys: &'static str,
+    pub action: &'static str,
+    pub git: Option<&'static str>,
+    pub topic: Option<&'static str>,
+}
 
-    // Clear the area behind the popup
-    f.render_widget(Clear, popup_area);
+const fn bind(keys: &'static str, action: &'static str) -> Binding {
+    Binding {
+        keys,
+        action,
+        git: None,
+        topic: None,
+    }
+}
+
+impl Binding {
+    const fn git(mut self, command: &'static str) -> Self {
+        self.git = Some(command);
+        self
+    }
 
-    let keybindings = match current_view {
+    const fn learn(mut self, topic: &'static str) -> Self {
+        self.topic = Some(topic);
+        self
+    }
+}
+
+/// Keys that work in every view.
+const GLOBAL: &[Binding] = &[
+    bind("?", "Toggle help"),
+    bind("F1", "Inspect what a key does"),
+    bind("Esc", "Back to the previous view"),
+    bind("Ctrl+O", "Toggle command output drawer"),
+    bind("Ctrl+Z", "Toggle zen mode (content only)"),
+    bind("Ctrl+B / Ctrl+P (detached)", "Branch here / back to branch")
+        .git("git switch -c <name>")
+        .learn("detached HEAD"),
+    bind("Ctrl+C", "Force quit"),
+];
+
+/// The keybindings of `view`, in help order.
+pub fn bindings(view: View) -> Vec<Binding> {
+    match view {
         View::Dashboard => vec![
-            ("s", "Open Staging view"),
-            ("c", "Open Commit view"),
-            ("b", "Open Branches view"),
-            ("l", "Open Timeline (Log) view"),
-            ("t", "Open Time Travel view"),
-            ("r", "Open Reflog view"),
-            ("g", "Open GitHub view"),
-            ("a", "Focus AI Mentor panel"),
-            ("x", "Open Stash view"),
-            ("m", "Open Merge Resolve view"),
-            ("w", "Open Workflow Builder"),
-            ("B", "Open Bisect view"),
-            ("p", "Open Cherry Pick view"),
-            ("A", "Open Agent Mode"),
-            ("Tab", "Switch panel focus"),
-            ("?", "Toggle this help"),
-            ("q", "Quit / Unfocus AI"),
-            ("Ctrl+C", "Force quit"),
+            bind("s", "Open Staging view").learn("the staging area"),
+            bind("c", "Open Commit view").learn("commits"),
+            bind("b", "Open Branches view").learn("branches"),
+            bind("l", "Open Timeline (Log) view").learn("git log"),
+            bind("t", "Open Time Travel view").learn("git reset"),
+            bind("r", "Open Reflog view").learn("the reflog"),
+            bind("o", "Open Outgoing / Incoming commits").learn("remote-tracking branches"),
+            bind("u", "Diff branch against its upstream")
+                .git("git diff @{upstream}...HEAD")
+                .learn("remote-tracking branches"),
+            bind("g", "Open GitHub view"),
+            bind("a", "Focus AI Mentor panel"),
+            bind("/ (AI result)", "Search the AI answer"),
+            bind("n / N (AI result)", "Next / previous match"),
+            bind("Enter (AI result)", "Next steps the answer suggests"),
+            bind("c (AI result)", "Copy or run its code"),
+            bind("x", "Open Stash view").learn("git stash"),
+            bind("m", "Open Merge Resolve view").learn("merge conflicts"),
+            bind("w", "Open Workflow Builder"),
+            bind("B", "Open Bisect view").learn("git bisect"),
+            bind("p", "Open Cherry Pick view").learn("cherry-picking"),
+            bind("A", "Open Agent Mode"),
+            bind(":", "Run a git alias or recipe").learn("git aliases"),
+            bind("S", "Your zit stats (local only)"),
+            bind("P", "Scope to a directory (monorepos)"),
+            bind("M", "Submodules: init, update, sync").git("git submodule update --init"),
+            bind("T", "Open Tags view").learn("git tag"),
+            bind("Tab", "Switch panel focus"),
+            bind("Ctrl+O", "Toggle command output drawer"),
+            bind("?", "Toggle this help"),
+            bind("F1", "Inspect what a key does"),
+            bind("q", "Quit / Unfocus AI"),
+            bind("Ctrl+C", "Force quit"),
         ],
         View::Staging => vec![
-            ("↑/↓ or j/k", "Navigate files"),
-            ("Space", "Toggle stage/unstage"),
-            ("h", "Toggle hunk mode"),
-            ("A or Ctrl+A", "Stage all files"),
-            ("u", "Unstage all files"),
-            ("R or Ctrl+R", "AI diff review"),
-            ("/", "Search files"),
-            ("c", "Open Commit view"),
-            ("PgDn/PgUp", "Scroll diff"),
-            ("q", "Back to Dashboard"),
+            bind("↑/↓ or j/k", "Navigate files"),
+            bind("Space", "Toggle stage/unstage (file or directory)")
+                .git("git add <file> / git restore --staged <file>")
+                .learn("the staging area"),
+            bind("h", "Toggle hunk mode")
+                .git("git apply --cached <hunk>")
+                .learn("staging hunks"),
+            bind("/ (hunk mode)", "Search the diff"),
+            bind("n / N (hunk mode)", "Next / previous match"),
+            bind("N", "Intent-to-add untracked file (shows its diff)")
+                .git("git add --intent-to-add <file>")
+                .learn("intent-to-add"),
+            bind("t", "Toggle directory tree"),
+            bind("o", "Cycle sort: path / status / modified"),
+            bind("g", "Toggle staged/unstaged/untracked sections"),
+            bind("Enter / ←/→", "Expand / collapse directory (tree)"),
+            bind("v", "Multi-select: Space marks, V marks a range"),
+            bind(
+                "s / u / d / S",
+                "Stage / unstage / discard / stash marked (multi-select)",
+            )
+            .git("git add / git restore --staged / git restore / git stash push -- <files>")
+            .learn("the staging area"),
+            bind("A or Ctrl+A", "Stage all files")
+                .git("git add -A")
+                .learn("the staging area"),
+            bind("u", "Unstage all files")
+                .git("git restore --staged .")
+                .learn("the staging area"),
+            bind("R or Ctrl+R", "AI diff review"),
+            bind("M", "Jump to staged conflict markers"),
+            bind("W", "Fix whitespace in selected file").learn("whitespace errors"),
+            bind("E", "Fix line endings flipped CRLF↔LF"),
+            bind("b", "Blame the selected file")
+                .git("git blame -- <file>")
+                .learn("git blame"),
+            bind("/", "Search files"),
+            bind("c", "Open Commit view"),
+            bind("PgDn/PgUp", "Scroll diff"),
+            bind("q", "Back to Dashboard"),
         ],
         View::Commit => vec![
-            ("Type", "Enter commit message"),
-            ("Enter", "New line"),
-            ("Ctrl+S", "Submit commit"),
-            ("Ctrl+A", "Amend previous commit"),
-            ("G or Ctrl+G", "Generate AI commit message"),
-            ("Esc", "Stop editing / Back"),
+            bind("Type", "Enter commit message"),
+            bind("Enter", "New line"),
+            bind("Ctrl+S", "Submit commit")
+                .git("git commit -m <message>")
+                .learn("commits"),
+            bind("Ctrl+A", "Amend previous commit")
+                .git("git commit --amend")
+                .learn("amending commits"),
+            bind(
+                "Ctrl+T / o",
+                "Commit options: skip hooks, author, date, empty",
+            )
+            .git("git commit --no-verify --author=<a> --date=<d> --allow-empty"),
+            bind("G or Ctrl+G", "Generate AI commit message"),
+            bind("r", "AI: try again (after a suggestion)"),
+            bind("-/+", "AI: shorter / longer suggestion"),
+            bind("f", "AI: refine with an instruction"),
+            bind(
+                "Space / j/k",
+                "Leave a staged file out of this commit (when not editing)",
+            )
+            .learn("staging area"),
+            bind("s", "Spelling suggestions (when not editing)"),
+            bind("Ctrl+E / e", "Gitmoji picker"),
+            bind("Esc", "Stop editing / Back"),
         ],
         View::Branches => vec![
-            ("↑/↓ or j/k", "Navigate branches"),
-            ("Enter", "Switch to branch"),
-            ("n", "Create new branch"),
-            ("d", "Delete branch"),
-            ("R", "Rename current branch"),
-            ("Tab", "Toggle local/remote"),
-            ("q", "Back to Dashboard"),
+            bind("↑/↓ or j/k", "Navigate branches"),
+            bind("Enter", "Switch to branch (stash or carry changes)")
+                .git("git switch <branch>")
+                .learn("branches"),
+            bind("n", "Create new branch")
+                .git("git branch <name>")
+                .learn("branches"),
+            bind("d", "Delete branch")
+                .git("git branch -d <branch>")
+                .learn("deleting branches"),
+            bind("R", "Rename current branch")
+                .git("git branch -m <new-name>")
+                .learn("renaming branches"),
+            bind("u", "Diff selected branch against its upstream")
+                .git("git diff <branch>@{upstream}...<branch>")
+                .learn("remote-tracking branches"),
+            bind("e", "Edit branch description")
+                .git("git branch --edit-description <branch>")
+                .learn("branch descriptions"),
+            bind("S", "AI: how to integrate selected branch").learn("merge vs rebase"),
+            bind("Tab", "Toggle local/remote"),
+            bind("q", "Back to Dashboard"),
         ],
         View::Timeline => vec![
-            ("↑/↓ or j/k", "Navigate commits"),
-            ("Enter", "View commit details & diff"),
-            ("/", "Search commits by message"),
-            ("y", "Copy commit hash"),
-            ("PgDn/PgUp", "Next/prev page"),
-            ("q", "Back to Dashboard"),
+            bind("↑/↓ or j/k", "Navigate commits"),
+            bind("Enter", "View commit details & diff")
+                .git("git show <commit>")
+                .learn("git log"),
+            bind("/", "Search commits by message")
+                .git("git log --grep=<text>")
+                .learn("searching history"),
+            bind("y", "Copy commit hash"),
+            bind("b", "Blame a file as of this commit")
+                .git("git blame <commit> -- <file>")
+                .learn("git blame"),
+            bind("c", "Check out commit (detached HEAD)")
+                .git("git switch --detach <commit>")
+                .learn("detached HEAD"),
+            bind("n (details)", "Add or edit the commit's note")
+                .git("git notes edit <commit>")
+                .learn("git notes"),
+            bind("/ (details)", "Search the diff"),
+            bind("n / N (searching details)", "Next / previous match"),
+            bind("Space", "Mark commit for cherry-picking"),
+            bind(
+                "p",
+                "Cherry-pick marked (or selected) commits onto current branch",
+            )
+            .git("git cherry-pick <commits>")
+            .learn("cherry-picking"),
+            bind("i", "Interactive rebase from this commit")
+                .git("git rebase -i <commit>^")
+                .learn("rewriting history"),
+            bind("I", "Fix wrong author email (unpushed)")
+                .git("git rebase --exec \"git commit --amend --reset-author\"")
+                .learn("rewriting history"),
+            bind("f", "Show current branch / one branch / all refs")
+                .git("git log --all")
+                .learn("git log"),
+            bind("R", "Toggle remote branches"),
+            bind("PgDn/PgUp", "Next/prev page"),
+            bind("q", "Back to Dashboard"),
         ],
         View::TimeTravel => vec![
-            ("↑/↓ or j/k", "Navigate commits"),
-            ("s", "Soft reset (safe)"),
-            ("m", "Mixed reset"),
-            ("h", "Hard reset (⚠ destructive)"),
-            ("b", "Create branch from commit"),
-            ("q", "Back to Dashboard"),
+            bind("↑/↓ or j/k", "Navigate commits"),
+            bind("s", "Soft reset (safe)")
+                .git("git reset --soft <commit>")
+                .learn("git reset"),
+            bind("m", "Mixed reset")
+                .git("git reset --mixed <commit>")
+                .learn("git reset"),
+            bind("h", "Hard reset (destructive!)")
+                .git("git reset --hard <commit>")
+                .learn("git reset"),
+            bind("b", "Create branch from commit")
+                .git("git branch <name> <commit>")
+                .learn("branches"),
+            bind("c", "Check out commit (detached HEAD)")
+                .git("git switch --detach <commit>")
+                .learn("detached HEAD"),
+            bind("p", "Preview files a reset would change").git("git diff HEAD <commit>"),
+            bind("r (preview)", "Restore the selected file from commit")
+                .git("git restore --source <commit> -SW -- <file>")
+                .learn("git restore"),
+            bind("f", "Restore a file by path from commit")
+                .git("git restore --source <commit> -SW -- <file>")
+                .learn("git restore"),
+            bind("q", "Back to Dashboard"),
         ],
         View::Reflog => vec![
-            ("↑/↓ or j/k", "Navigate entries"),
-            ("Enter", "View diff"),
-            ("b", "Create branch from entry"),
-            ("f", "Cycle operation filter"),
-            ("c", "Clear filter"),
-            ("q", "Back to Dashboard"),
+            bind("↑/↓ or j/k", "Navigate entries"),
+            bind("Enter", "View diff")
+                .git("git show <entry>")
+                .learn("the reflog"),
+            bind("b", "Create branch from entry")
+                .git("git branch <name> <entry>")
+                .learn("recovering lost commits"),
+            bind("f", "Cycle operation filter"),
+            bind("c", "Clear filter"),
+            bind("L", "Find lost commits (on no branch)")
+                .git("git fsck --no-reflogs")
+                .learn("recovering lost commits"),
+            bind("b (lost commits)", "Recover commit as a branch")
+                .git("git branch <name> <commit>")
+                .learn("recovering lost commits"),
+            bind("q", "Back to Dashboard"),
         ],
         View::GitHub => vec![
-            ("↑/↓ or j/k", "Navigate menu / list"),
-            ("Enter", "Select option / Open PR"),
-            ("a", "Login with GitHub"),
-            ("f", "Cycle PR filter (Open/Closed/All)"),
-            ("r", "Refresh"),
-            ("Tab", "Switch detail tab"),
-            ("m", "Merge PR (in detail)"),
-            ("M", "Cycle merge method"),
-            ("c", "Close PR (in detail)"),
-            ("o", "Open PR in browser"),
-            ("Esc", "Back"),
-            ("q", "Back to Dashboard"),
+            bind("↑/↓ or j/k", "Navigate menu / list"),
+            bind("Enter", "Select option / Open PR or issue"),
+            bind("a", "Login with GitHub"),
+            bind("f", "Cycle PR / issue filter (Open/Closed/All)"),
+            bind("/ (Issues)", "Search by words, #number, @user or label"),
+            bind("n (Issues)", "Open a new issue"),
+            bind("n (Pull Requests)", "Open a new pull request")
+                .git("git push -u origin <branch>")
+                .learn("pull requests"),
+            bind("←/→ (new PR)", "Pick the head / base branch"),
+            bind(
+                "Ctrl+G (new PR)",
+                "AI title and description from the commits",
+            ),
+            bind(
+                "c / l / x (issue)",
+                "Comment / Set labels / Close or reopen",
+            ),
+            bind("r", "Refresh"),
+            bind("Tab", "Switch detail tab / pane"),
+            bind("Enter (Problems)", "Open CI annotation in $EDITOR"),
+            bind("b (CI Runs)", "This branch's runs / all branches"),
+            bind("R (CI Runs)", "Re-run a run's failed jobs"),
+            bind("m", "Merge PR (in detail)").learn("pull requests"),
+            bind("M", "Cycle merge method"),
+            bind("c", "Close PR (in detail)"),
+            bind("v", "Review PR: approve, request changes or comment").learn("pull requests"),
+            bind(
+                "Enter (Files)",
+                "Step through the patch / Comment on a line",
+            ),
+            bind("n / N (Files)", "Next / previous hunk of the patch"),
+            bind("s (Files)", "Patch side by side / unified"),
+            bind("e (Files)", "Expand the patch / show the file list"),
+            bind("w / W", "Watch PR checks / and auto-merge when green"),
+            bind("o", "Open PR or issue in browser"),
+            bind("F", "Fetch all remotes")
+                .git("git fetch --all")
+                .learn("git fetch"),
+            bind("G", "Run git gc").git("git gc").learn("git gc"),
+            bind("Esc", "Back"),
+            bind("q", "Back to Dashboard"),
         ],
         View::Stash => vec![
-            ("↑/↓ or j/k", "Navigate stash entries"),
-            ("p", "Pop stash (apply & remove)"),
-            ("a", "Apply stash (keep in list)"),
-            ("d", "Drop stash entry"),
-            ("n", "New stash (push)"),
-            ("D", "Clear all stashes"),
-            ("PgDn/PgUp", "Scroll diff"),
-            ("q", "Back to Dashboard"),
+            bind("↑/↓ or j/k", "Navigate stash entries"),
+            bind("p", "Pop stash (apply & remove)")
+                .git("git stash pop <stash>")
+                .learn("git stash"),
+            bind("a", "Apply stash (keep in list)")
+                .git("git stash apply <stash>")
+                .learn("git stash"),
+            bind("d", "Drop stash entry")
+                .git("git stash drop <stash>")
+                .learn("git stash"),
+            bind("n", "New stash (push)")
+                .git("git stash push")
+                .learn("git stash"),
+            bind("u", "Toggle --include-untracked for new stashes")
+                .git("git stash push --include-untracked")
+                .learn("git stash"),
+            bind("i", "Toggle --keep-index for new stashes")
+                .git("git stash push --keep-index")
+                .learn("git stash"),
+            bind("s", "Toggle --staged (stash only staged changes)")
+                .git("git stash push --staged")
+                .learn("git stash"),
+            bind("D", "Clear all stashes")
+                .git("git stash clear")
+                .learn("git stash"),
+            bind("S", "Switch to safety-net snapshots"),
+            bind("n / r (snapshots)", "Snapshot now / restore snapshot"),
+            bind("PgDn/PgUp", "Scroll diff"),
+            bind("q", "Back to Dashboard"),
         ],
         View::MergeResolve => vec![
-            ("a", "Accept current (HEAD) changes"),
-            ("i", "Accept incoming changes"),
-            ("m", "Apply AI-suggested resolution"),
-            ("G or Ctrl+G", "Get AI merge suggestion"),
-            ("S or Ctrl+M", "AI merge strategy advice"),
-            ("[/]", "Navigate conflict regions"),
-            ("n/p", "Next/prev conflicted file"),
-            ("Tab", "Cycle panel focus"),
-            ("j/k", "Scroll focused panel"),
-            ("1-5", "Quick pick follow-up action"),
-            ("! or Ctrl+A", "Abort merge"),
-            ("F or Ctrl+F", "Continue/finalize merge"),
-            ("q", "Back to Dashboard"),
+            bind("a", "Accept current (HEAD) changes")
+                .git("git checkout --ours <file>")
+                .learn("merge conflicts"),
+            bind("i", "Accept incoming changes")
+                .git("git checkout --theirs <file>")
+                .learn("merge conflicts"),
+            bind("m", "Apply AI resolution (region, else file)"),
+            bind("g", "AI suggestion for selected region"),
+            bind("G or Ctrl+G", "AI suggestion for whole file"),
+            bind("S or Ctrl+M", "AI merge strategy advice").learn("merge vs rebase"),
+            bind("A / I", "Take current / incoming for the whole file")
+                .git("git checkout --ours/--theirs -- <file>")
+                .learn("merge conflicts"),
+            bind("u / U", "Undo / redo the last resolution").learn("merge conflicts"),
+            bind("[/]", "Navigate conflict regions"),
+            bind("n/p", "Next/prev conflicted file"),
+            bind("Tab", "Cycle panel focus"),
+            bind("j/k", "Scroll focused panel"),
+            bind("1-5", "Quick pick follow-up action"),
+            bind("! or Ctrl+A", "Abort merge")
+                .git("git merge --abort")
+                .learn("merge conflicts"),
+            bind("F or Ctrl+F", "Continue/finalize merge")
+                .git("git add <files> && git merge --continue")
+                .learn("merge conflicts"),
+            bind("q", "Back to Dashboard"),
         ],
         View::WorkflowBuilder => vec![
-            ("h/l or ←/→", "Navigate steps"),
-            ("a", "Add new step"),
-            ("Enter", "Edit selected step"),
-            ("u", "Edit action (uses)"),
-            ("r", "Edit command (run)"),
-            ("d", "Delete step"),
-            ("c", "Connect steps"),
-            ("g", "Generate YAML"),
-            ("n", "Edit workflow name"),
-            ("t", "Select triggers"),
-            ("Tab", "Skip to next field"),
-            ("Esc", "Cancel / Go back"),
-            ("q", "Back to Dashboard"),
+            bind("h/l or ←/→", "Navigate steps"),
+            bind("a", "Add new step"),
+            bind("Enter", "Edit selected step"),
+            bind("u", "Edit action (uses)"),
+            bind("r", "Edit command (run)"),
+            bind("d", "Delete step"),
+            bind("c", "Connect steps"),
+            bind("g", "Generate YAML").learn("GitHub Actions"),
+            bind("n", "Edit workflow name"),
+            bind("t", "Select triggers"),
+            bind("Tab", "Skip to next field"),
+            bind("Esc", "Cancel / Go back"),
+            bind("q", "Back to Dashboard"),
         ],
         View::Bisect => vec![
-            ("↑/↓ or j/k", "Navigate commits"),
-            ("Enter", "Select commit (bad → good → start)"),
-            ("g", "Mark current commit as good"),
-            ("b", "Mark current commit as bad"),
-            ("s", "Skip current commit"),
-            ("R", "Reset / end bisect session"),
-            ("PgDn/PgUp", "Scroll log"),
-            ("Esc", "Back one step"),
-            ("q", "Back to Dashboard"),
+            bind("↑/↓ or j/k", "Navigate commits"),
+            bind("Enter", "Select commit (bad → good → start)")
+                .git("git bisect start <bad> <good>")
+                .learn("git bisect"),
+            bind("g", "Mark current commit as good")
+                .git("git bisect good")
+                .learn("git bisect"),
+            bind("b", "Mark current commit as bad")
+                .git("git bisect bad")
+                .learn("git bisect"),
+            bind("s", "Skip current commit")
+                .git("git bisect skip")
+                .learn("git bisect"),
+            bind("R", "Reset / end bisect session")
+                .git("git bisect reset")
+                .learn("git bisect"),
+            bind("PgDn/PgUp", "Scroll log"),
+            bind("Esc", "Back one step"),
+            bind("q", "Back to Dashboard"),
         ],
         View::CherryPick => vec![
-            ("↑/↓ or j/k", "Navigate branches / commits"),
-            ("Enter", "Select branch / Apply cherry-pick"),
-            ("Space", "Toggle mark commit for multi-pick"),
-            ("c", "Continue after conflict"),
-            ("A", "Abort cherry-pick"),
-            ("PgDn/PgUp", "Scroll diff"),
-            ("Esc", "Back to branch select"),
-            ("q", "Back to Dashboard"),
+            bind("↑/↓ or j/k", "Navigate branches / commits"),
+            bind("Enter", "Select branch / Apply cherry-pick")
+                .git("git cherry-pick <commit>")
+                .learn("cherry-picking"),
+            bind("Space", "Toggle mark commit for multi-pick"),
+            bind("c", "Continue after conflict")
+                .git("git cherry-pick --continue")
+                .learn("cherry-picking"),
+            bind("A", "Abort cherry-pick")
+                .git("git cherry-pick --abort")
+                .learn("cherry-picking"),
+            bind("PgDn/PgUp", "Scroll diff"),
+            bind("Esc", "Back to branch select"),
+            bind("q", "Back to Dashboard"),
         ],
         View::Agent => vec![
-            ("i", "Start typing"),
-            ("Enter", "Send message"),
-            ("y", "Allow pending command"),
-            ("n", "Deny pending command"),
-            ("a", "Auto-approve all (session)"),
-            ("↑/↓ or j/k", "Scroll conversation"),
-            ("Ctrl+L", "Clear conversation"),
-            ("Ctrl+C", "Cancel AI request"),
-            ("Esc", "Exit input / Back"),
-            ("q", "Back to Dashboard"),
+            bind("i", "Start typing"),
+            bind("Enter", "Send message"),
+            bind("y", "Allow pending command"),
+            bind("n", "Deny pending command"),
+            bind("a", "Auto-approve all (session)"),
+            bind("↑/↓ or j/k", "Scroll conversation"),
+            bind("Ctrl+L", "Clear conversation"),
+            bind("Ctrl+C", "Cancel AI request"),
+            bind("Esc", "Exit input / Back"),
+            bind("q", "Back to Dashboard"),
         ],
-    };
+        View::Outgoing => vec![
+            bind("↑/↓ or j/k", "Navigate commits")
+                .git("git log @{u}..HEAD / git log HEAD..@{u}")
+                .learn("remote-tracking branches"),
+            bind("Tab", "Switch outgoing / incoming"),
+            bind("d", "Show changed files / commits")
+                .git("git diff @{u}...HEAD / git diff HEAD...@{u}"),
+            bind("PgDn/PgUp", "Scroll diff"),
+            bind("/", "Search the diff"),
+            bind("n / N", "Next / previous match"),
+            bind("f", "Fetch all remotes")
+                .git("git fetch --all")
+                .learn("git fetch"),
+            bind("q", "Back to Dashboard"),
+        ],
+        View::Rebase => vec![
+            bind("↑/↓ or j/k", "Navigate commits (oldest first)"),
+            bind("p", "Pick: keep the commit as it is"),
+            bind("r", "Reword: edit the commit's subject"),
+            bind("s", "Squash into the commit above, keeping both messages")
+                .learn("squashing commits"),
+            bind("f", "Fixup into the commit above, dropping its message")
+                .learn("squashing commits"),
+            bind("d", "Drop the commit"),
+            bind("J / K", "Move the commit down / up"),
+            bind("u", "Reset the plan"),
+            bind("Enter", "Run the rebase (asks first)")
+                .git("git rebase -i <base>")
+                .learn("rewriting history"),
+            bind("Esc", "Back to Timeline"),
+        ],
+        View::Tags => vec![
+            bind("↑/↓ or j/k", "Navigate tags (newest first)"),
+            bind("n", "Tag HEAD (annotated with a message)")
+                .git("git tag -a <name> -m <message>")
+                .learn("git tag"),
+            bind("P", "Push the tag to origin").git("git push origin refs/tags/<tag>"),
+            bind("d", "Delete the tag here").git("git tag -d <tag>"),
+            bind("D", "Delete the tag on origin").git("git push origin --delete refs/tags/<tag>"),
+            bind(
+                "R",
+                "GitHub release with a changelog since the previous tag",
+            ),
+            bind("q", "Back to Dashboard"),
+        ],
+        View::Blame => vec![
+            bind("↑/↓ or j/k", "Move through the file's lines"),
+            bind("PgUp/PgDn, Home/End", "Jump through the file"),
+            bind("Enter", "Open the line's commit in the Timeline")
+                .git("git show <commit>")
+                .learn("git blame"),
+            bind("Esc / q", "Back"),
+        ],
+    }
+}
 
-    let view_name = match current_view {
+pub fn view_name(view: View) -> &'static str {
+    match view {
         View::Dashboard => "Dashboard",
         View::Staging => "Staging",
         View::Commit => "Commit",
@@ -194,8 +509,23 @@ pub fn render(f: &mut Frame, area: Rect, current_view: View) {
         View::Bisect => "Bisect",
         View::CherryPick => "Cherry Pick",
         View::Agent => "Agent",
-    };
+        View::Outgoing => "Outgoing / Incoming",
+        View::Rebase => "Interactive Rebase",
+        View::Tags => "Tags",
+        View::Blame => "Blame",
+    }
+}
+
+/// The help popup for `current_view`, scrolled down `scroll` lines; `scroll`
+/// is clamped so the last line is at the bottom at most.
+pub fn render(f: &mut Frame, area: Rect, current_view: View, scroll: &mut u16) {
+    // Center the popup
+    let popup_area = centered_rect(60, 70, area);
 
+    // Clear the area behind the popup
+    f.render_widget(Clear, popup_area);
+
+    let view_name = view_name(current_view);
     let mut lines = vec![
         Line::from(Span::styled(
             format!("  {} — Keybindings", view_name),
@@ -206,29 +536,223 @@ pub fn render(f: &mut Frame, area: Rect, current_view: View) {
         Line::from(Span::raw("")),
     ];
 
-    for (key, desc) in keybindings {
+    for binding in bindings(current_view) {
         lines.push(Line::from(vec![
             Span::styled(
-                format!("  {:>14}  ", key),
+                format!("  {:>14}  ", binding.keys),
                 Style::default()
                     .fg(Color::Yellow)
                     .add_modifier(Modifier::BOLD),
             ),
-            Span::styled(desc, Style::default().fg(Color::White)),
+            Span::styled(binding.action, Style::default().fg(Color::White)),
         ]));
     }
 
     lines.push(Line::from(Span::raw("")));
     lines.push(Line::from(Span::styled(
-        "  Press ? or Esc to close",
+        "  Press ? or Esc to close · F1 then a key to inspect it",
+        Style::default().fg(Color::DarkGray),
+    )));
+
+    let help = Paragraph::new(lines).wrap(Wrap { trim: false });
+    let total = help.line_count(popup_area.width.saturating_sub(2));
+    let visible = popup_area.height.saturating_sub(2) as usize;
+    let max_scroll = total.saturating_sub(visible);
+    *scroll = (*scroll).min(max_scroll as u16);
+
+    let mut block = Block::default()
+        .title(Span::styled(
+            format!(" {} Help ", Icon::Help),
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        ))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+    if max_scroll > 0 {
+        block = block.title_bottom(Span::styled(
+            " ↑/↓ to scroll ",
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+    let help = help.block(block).scroll((*scroll, 0));
+    f.render_widget(&help, popup_area);
+    render_paragraph_scrollbar(f, popup_area, &help, *scroll);
+}
+
+/// The bindings `key` triggers in `view`, then any global one. Keys that mean
+/// different things by mode (e.g. `u` in Staging) match several.
+pub fn inspect(view: View, key: &KeyEvent) -> Vec<Binding> {
+    let mut found: Vec<Binding> = bindings(view)
+        .into_iter()
+        .filter(|b| label_matches(b.keys, key))
+        .collect();
+    for global in GLOBAL {
+        if label_matches(global.keys, key) && !found.iter().any(|b| b.keys == global.keys) {
+            found.push(*global);
+        }
+    }
+    found
+}
+
+/// Whether a help label such as "↑/↓ or j/k", "s / u / d / S",
+/// "A or Ctrl+A" or "n / r (snapshots)" covers `key`.
+fn label_matches(label: &str, key: &KeyEvent) -> bool {
+    // Drop a trailing qualifier like "(snapshots)"
+    let label = match label.find(" (") {
+        Some(i) => &label[..i],
+        None => label,
+    };
+    label
+        .split(" or ")
+        .flat_map(|part| part.split(" / "))
+        .flat_map(|part| {
+            // "j/k", "PgDn/PgUp", "[/]", but not "/" itself
+            if part.len() > 1 && part.contains('/') && !part.starts_with("Ctrl+") {
+                part.split('/').filter(|s| !s.is_empty()).collect()
+            } else {
+                vec![part]
+            }
+        })
+        .any(|token| token_matches(token.trim(), key))
+}
+
+fn token_matches(token: &str, key: &KeyEvent) -> bool {
+    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+    if let Some(rest) = token.strip_prefix("Ctrl+") {
+        return ctrl
+            && matches!(key.code, KeyCode::Char(c) if rest.eq_ignore_ascii_case(&c.to_string()));
+    }
+    if ctrl {
+        return false;
+    }
+    match (token, key.code) {
+        ("Space", KeyCode::Char(' ')) => true,
+        ("Enter", KeyCode::Enter)
+        | ("Tab", KeyCode::Tab)
+        | ("Esc", KeyCode::Esc)
+        | ("PgDn", KeyCode::PageDown)
+        | ("PgUp", KeyCode::PageUp)
+        | ("↑", KeyCode::Up)
+        | ("↓", KeyCode::Down)
+        | ("←", KeyCode::Left)
+        | ("→", KeyCode::Right)
+        | ("F1", KeyCode::F(1)) => true,
+        // Free text, e.g. the commit message editor
+        ("Type", KeyCode::Char(_)) => true,
+        (_, KeyCode::Char(c)) => {
+            if let Some((from, to)) = token.split_once('-')
+                && let (Some(from), Some(to)) = (from.chars().next(), to.chars().next())
+                && from.is_ascii_digit()
+            {
+                return (from..=to).contains(&c);
+            }
+            let mut chars = token.chars();
+            chars.next() == Some(c) && chars.next().is_none()
+        }
+        _ => false,
+    }
+}
+
+/// How a key is written in the inspector, e.g. "Ctrl+A", "Enter", "j".
+pub fn key_label(key: &KeyEvent) -> String {
+    let name = match key.code {
+        KeyCode::Char(' ') => "Space".to_string(),
+        KeyCode::Char(c) => c.to_string(),
+        KeyCode::Enter => "Enter".to_string(),
+        KeyCode::Tab => "Tab".to_string(),
+        KeyCode::BackTab => "Shift+Tab".to_string(),
+        KeyCode::Esc => "Esc".to_string(),
+        KeyCode::Backspace => "Backspace".to_string(),
+        KeyCode::PageDown => "PgDn".to_string(),
+        KeyCode::PageUp => "PgUp".to_string(),
+        KeyCode::Up => "↑".to_string(),
+        KeyCode::Down => "↓".to_string(),
+        KeyCode::Left => "←".to_string(),
+        KeyCode::Right => "→".to_string(),
+        KeyCode::F(n) => format!("F{}", n),
+        other => format!("{:?}", other),
+    };
+    if key.modifiers.contains(KeyModifiers::CONTROL) {
+        format!("Ctrl+{}", name.to_uppercase())
+    } else {
+        name
+    }
+}
+
+/// The key inspector popup: waits for a key, then shows what it does in
+/// `view`, the git command behind it and the Learn topic F1 opens.
+pub fn render_inspector(f: &mut Frame, area: Rect, view: View, key: Option<&KeyEvent>) {
+    let popup_area = centered_rect(60, 50, area);
+    f.render_widget(Clear, popup_area);
+
+    let label = Style::default().fg(Color::DarkGray);
+    let mut lines = vec![Line::from(Span::raw(""))];
+    let mut footer = "  Press a key to inspect it · Esc to close".to_string();
+
+    match key {
+        None => lines.push(Line::from(Span::styled(
+            format!("  Press any key to see what it does in {}", view_name(view)),
+            Style::default().fg(Color::White),
+        ))),
+        Some(key) => {
+            lines.push(Line::from(vec![
+                Span::styled("  Key      ", label),
+                Span::styled(
+                    key_label(key),
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(format!("  in {}", view_name(view)), label),
+            ]));
+            lines.push(Line::from(Span::raw("")));
+            let found = inspect(view, key);
+            if found.is_empty() {
+                lines.push(Line::from(Span::styled(
+                    "  Does nothing here",
+                    Style::default().fg(Color::DarkGray),
+                )));
+            }
+            for binding in &found {
+                lines.push(Line::from(vec![
+                    Span::styled("  Does     ", label),
+                    Span::styled(binding.action, Style::default().fg(Color::White)),
+                ]));
+                if let Some(git) = binding.git {
+                    lines.push(Line::from(vec![
+                        Span::styled("  Runs     ", label),
+                        Span::styled(format!("$ {}", git), Style::default().fg(Color::Green)),
+                    ]));
+                }
+                if let Some(topic) = binding.topic {
+                    lines.push(Line::from(vec![
+                        Span::styled("  Learn    ", label),
+                        Span::styled(topic, Style::default().fg(Color::Cyan)),
+                    ]));
+                }
+                lines.push(Line::from(Span::raw("")));
+            }
+            if let Some(topic) = found.iter().find_map(|b| b.topic) {
+                footer = format!(
+                    "  F1 learn about {} · another key to inspect · Esc to close",
+                    topic
+                );
+            }
+        }
+    }
+
+    lines.push(Line::from(Span::raw("")));
+    lines.push(Line::from(Span::styled(
+        footer,
         Style::default().fg(Color::DarkGray),
     )));
 
-    let help = Paragraph::new(lines)
+    let inspector = Paragraph::new(lines)
         .block(
             Block::default()
                 .title(Span::styled(
-                    " ❓ Help ",
+                    format!(" {} What does this key do? ", Icon::Help),
                     Style::default()
                         .fg(Color::Cyan)
                         .add_modifier(Modifier::BOLD),
@@ -238,5 +762,65 @@ pub fn render(f: &mut Frame, area: Rect, current_view: View) {
         )
         .wrap(Wrap { trim: false });
 
-    f.render_widget(help, popup_area);
+    f.render_widget(inspector, popup_area);
+}
+
+/// The Learn topic of the first binding `key` triggers in `view` that has one.
+pub fn learn_topic(view: View, key: &KeyEvent) -> Option<&'static str> {
+    inspect(view, key).iter().find_map(|b| b.topic)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn ctrl(c: char) -> KeyEvent {
+        KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL)
+    }
+
+    #[test]
+    fn test_label_matches() {
+        assert!(label_matches("↑/↓ or j/k", &key(KeyCode::Down)));
+        assert!(label_matches("↑/↓ or j/k", &key(KeyCode::Char('k'))));
+        assert!(label_matches("A or Ctrl+A", &ctrl('a')));
+        assert!(!label_matches("A or Ctrl+A", &key(KeyCode::Char('a'))));
+        assert!(label_matches("s / u / d / S", &key(KeyCode::Char('S'))));
+        assert!(label_matches("n / r (snapshots)", &key(KeyCode::Char('r'))));
+        assert!(label_matches("Enter / ←/→", &key(KeyCode::Left)));
+        assert!(label_matches("/", &key(KeyCode::Char('/'))));
+        assert!(label_matches("[/]", &key(KeyCode::Char(']'))));
+        assert!(label_matches("1-5", &key(KeyCode::Char('3'))));
+        assert!(!label_matches("1-5", &key(KeyCode::Char('6'))));
+        assert!(label_matches("PgDn/PgUp", &key(KeyCode::PageUp)));
+        assert!(!label_matches("c", &ctrl('c')));
+    }
+
+    #[test]
+    fn test_inspect() {
+        let found = inspect(View::Staging, &key(KeyCode::Char(' ')));
+        assert_eq!(found.len(), 1);
+        assert_eq!(
+            found[0].git,
+            Some("git add <file> / git restore --staged <file>")
+        );
+
+        // Mode-dependent keys list every meaning
+        let found = inspect(View::Staging, &key(KeyCode::Char('u')));
+        assert_eq!(found.len(), 2);
+
+        // Global keys work everywhere, without repeating the view's own entry
+        let found = inspect(View::Stash, &ctrl('o'));
+        assert_eq!(found[0].action, "Toggle command output drawer");
+        assert_eq!(inspect(View::Dashboard, &ctrl('o')).len(), 1);
+
+        assert!(inspect(View::Stash, &key(KeyCode::Char('z'))).is_empty());
+        assert_eq!(
+            learn_topic(View::TimeTravel, &key(KeyCode::Char('h'))),
+            Some("git reset")
+        );
+    }
 }
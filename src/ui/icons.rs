@@ -0,0 +1,247 @@
+//! Icon abstraction — every pictogram shown in the UI goes through here so it can
+//! be rendered as emoji, Nerd Font glyphs, or plain ASCII depending on what the
+//! terminal can display without breaking column alignment.
+
+use std::fmt;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IconMode {
+    Emoji,
+    NerdFont,
+    Ascii,
+}
+
+impl IconMode {
+    /// Parse the `[ui] icons` config value. Returns `None` for "auto" or unknown values.
+    pub fn from_config(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "emoji" => Some(Self::Emoji),
+            "nerd" | "nerdfont" | "nerd-font" => Some(Self::NerdFont),
+            "ascii" | "plain" => Some(Self::Ascii),
+            _ => None,
+        }
+    }
+
+    /// Resolve the config value, falling back to terminal auto-detection.
+    pub fn resolve(value: &str) -> Self {
+        Self::from_config(value).unwrap_or_else(detect)
+    }
+}
+
+static MODE: AtomicU8 = AtomicU8::new(0);
+
+/// Set the icon mode used by all views. Call once at startup.
+pub fn set_mode(mode: IconMode) {
+    let raw = match mode {
+        IconMode::Emoji => 0,
+        IconMode::NerdFont => 1,
+        IconMode::Ascii => 2,
+    };
+    MODE.store(raw, Ordering::Relaxed);
+}
+
+/// The icon mode currently in effect.
+pub fn mode() -> IconMode {
+    match MODE.load(Ordering::Relaxed) {
+        1 => IconMode::NerdFont,
+        2 => IconMode::Ascii,
+        _ => IconMode::Emoji,
+    }
+}
+
+/// Guess the best icon mode from the environment.
+///
+/// Nerd Fonts cannot be detected, so `ZIT_NERD_FONT=1` opts in explicitly. Non-UTF-8
+/// locales and the Linux console fall back to ASCII; everything else gets emoji.
+pub fn detect() -> IconMode {
+    detect_from(|name| std::env::var(name).ok())
+}
+
+fn detect_from(env: impl Fn(&str) -> Option<String>) -> IconMode {
+    if env("ZIT_NERD_FONT").is_some_and(|v| v == "1" || v.eq_ignore_ascii_case("true")) {
+        return IconMode::NerdFont;
+    }
+
+    let term = env("TERM").unwrap_or_default();
+    if term == "linux" || term == "dumb" || term == "vt100" {
+        return IconMode::Ascii;
+    }
+
+    // First non-empty locale variable wins, as in setlocale(3)
+    let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .filter_map(|name| env(name))
+        .find(|v| !v.is_empty());
+    if let Some(locale) = locale {
+        let lower = locale.to_lowercase();
+        if !lower.contains("utf-8") && !lower.contains("utf8") {
+            return IconMode::Ascii;
+        }
+    } else if env("WT_SESSION").is_none() && env("TERM_PROGRAM").is_none() {
+        // No locale at all (and not a known modern terminal) — play it safe
+        return IconMode::Ascii;
+    }
+
+    IconMode::Emoji
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Icon {
+    Ai,
+    Loading,
+    Warning,
+    Shield,
+    Star,
+    File,
+    History,
+    Search,
+    CherryPick,
+    Bolt,
+    GitHub,
+    Key,
+    Repo,
+    Push,
+    Pull,
+    Sync,
+    Users,
+    PullRequest,
+    Issue,
+    Logout,
+    Lock,
+    Globe,
+    Draft,
+    Open,
+    Merged,
+    Closed,
+    Comment,
+    Success,
+    Failure,
+    Cancelled,
+    Skipped,
+    InProgress,
+    Queued,
+    Help,
+    Conflict,
+    Idea,
+    Celebrate,
+    Rewind,
+    Tool,
+}
+
+impl Icon {
+    /// The glyph for this icon in the current mode. Zen mode always uses the
+    /// ASCII markers.
+    pub fn glyph(self) -> &'static str {
+        if crate::ui::zen::is_on() {
+            return self.glyph_for(IconMode::Ascii);
+        }
+        self.glyph_for(mode())
+    }
+
+    pub fn glyph_for(self, mode: IconMode) -> &'static str {
+        let (emoji, nerd, ascii) = match self {
+            Icon::Ai => ("🤖", "\u{f06a9}", "[AI]"),
+            Icon::Loading => ("⏳", "\u{f252}", "..."),
+            Icon::Warning => ("⚠", "\u{f071}", "!"),
+            Icon::Shield => ("🛡", "\u{f132}", "[!]"),
+            Icon::Star => ("⭐", "\u{f005}", "*"),
+            Icon::File => ("📄", "\u{f15b}", "#"),
+            Icon::History => ("📜", "\u{f1da}", "#"),
+            Icon::Search => ("🔍", "\u{f002}", "?"),
+            Icon::CherryPick => ("🍒", "\u{f417}", "*"),
+            Icon::Bolt => ("⚡", "\u{f0e7}", "~"),
+            Icon::GitHub => ("🐙", "\u{f09b}", "GH"),
+            Icon::Key => ("🔑", "\u{f084}", "*"),
+            Icon::Repo => ("📦", "\u{f401}", "+"),
+            Icon::Push => ("📤", "\u{f093}", "^"),
+            Icon::Pull => ("📥", "\u{f019}", "v"),
+            Icon::Sync => ("🔄", "\u{f021}", "~"),
+            Icon::Users => ("👥", "\u{f0c0}", "@"),
+            Icon::PullRequest => ("🔀", "\u{f407}", "PR"),
+            Icon::Issue => ("🎯", "\u{f41b}", "(i)"),
+            Icon::Logout => ("🚪", "\u{f08b}", "<-"),
+            Icon::Lock => ("🔒", "\u{f023}", "(private)"),
+            Icon::Globe => ("🌍", "\u{f0ac}", "(public)"),
+            Icon::Draft => ("📝", "\u{f040}", "~"),
+            Icon::Open => ("🟢", "\u{f111}", "o"),
+            Icon::Merged => ("🟣", "\u{f419}", "M"),
+            Icon::Closed => ("🔴", "\u{f057}", "x"),
+            Icon::Comment => ("💬", "\u{f075}", "\""),
+            Icon::Success => ("✅", "\u{f00c}", "+"),
+            Icon::Failure => ("❌", "\u{f00d}", "x"),
+            Icon::Cancelled => ("⚪", "\u{f10c}", "o"),
+            Icon::Skipped => ("⏭", "\u{f051}", ">"),
+            Icon::InProgress => ("🔵", "\u{f110}", "*"),
+            Icon::Queued => ("🟡", "\u{f017}", "."),
+            Icon::Help => ("❓", "\u{f059}", "?"),
+            Icon::Conflict => ("⚔", "\u{e727}", "X"),
+            Icon::Idea => ("💡", "\u{f0eb}", "*"),
+            Icon::Celebrate => ("🎉", "\u{f005}", "*"),
+            Icon::Rewind => ("⏪", "\u{f04a}", "<<"),
+            Icon::Tool => ("🔧", "\u{f0ad}", "*"),
+        };
+        match mode {
+            IconMode::Emoji => emoji,
+            IconMode::NerdFont => nerd,
+            IconMode::Ascii => ascii,
+        }
+    }
+}
+
+impl fmt::Display for Icon {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.glyph())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::testing::env_of;
+
+    #[test]
+    fn test_from_config() {
+        assert_eq!(IconMode::from_config("emoji"), Some(IconMode::Emoji));
+        assert_eq!(IconMode::from_config("Nerd"), Some(IconMode::NerdFont));
+        assert_eq!(IconMode::from_config("ascii"), Some(IconMode::Ascii));
+        assert_eq!(IconMode::from_config("auto"), None);
+    }
+
+    #[test]
+    fn test_detect_utf8_locale_is_emoji() {
+        let vars = [("LANG", "en_US.UTF-8"), ("TERM", "xterm-256color")];
+        assert_eq!(detect_from(env_of(&vars)), IconMode::Emoji);
+    }
+
+    #[test]
+    fn test_detect_linux_console_is_ascii() {
+        let vars = [("LANG", "en_US.UTF-8"), ("TERM", "linux")];
+        assert_eq!(detect_from(env_of(&vars)), IconMode::Ascii);
+    }
+
+    #[test]
+    fn test_detect_non_utf8_locale_is_ascii() {
+        let vars = [("LC_ALL", "C"), ("LANG", "en_US.UTF-8")];
+        assert_eq!(detect_from(env_of(&vars)), IconMode::Ascii);
+    }
+
+    #[test]
+    fn test_detect_nerd_font_opt_in() {
+        let vars = [("ZIT_NERD_FONT", "1"), ("TERM", "linux")];
+        assert_eq!(detect_from(env_of(&vars)), IconMode::NerdFont);
+    }
+
+    #[test]
+    fn test_ascii_glyphs_are_ascii() {
+        for icon in [
+            Icon::Ai,
+            Icon::Loading,
+            Icon::GitHub,
+            Icon::Success,
+            Icon::Conflict,
+        ] {
+            assert!(icon.glyph_for(IconMode::Ascii).is_ascii());
+        }
+    }
+}
@@ -0,0 +1,85 @@
+//! Two-cell author identicons: a mirrored quadrant-block pattern in a color, both
+//! derived from a hash of the author's email, so the same person always gets the
+//! same mark (`[ui] identicons`).
+
+use ratatui::{
+    style::{Color, Style},
+    text::Span,
+};
+
+/// Quadrant glyphs indexed by mask: upper-left 1, upper-right 2, lower-left 4, lower-right 8.
+const QUADRANTS: [char; 16] = [
+    ' ', '▘', '▝', '▀', '▖', '▌', '▞', '▛', '▗', '▚', '▐', '▜', '▄', '▙', '▟', '█',
+];
+
+/// FNV-1a — stable across builds and platforms, unlike `DefaultHasher`.
+fn hash(key: &str) -> u64 {
+    key.trim()
+        .to_lowercase()
+        .bytes()
+        .fold(0xcbf29ce484222325, |h, b| {
+            (h ^ b as u64).wrapping_mul(0x100000001b3)
+        })
+}
+
+/// Swap the left and right quadrants of a mask.
+fn mirror(mask: usize) -> usize {
+    ((mask & 0b0101) << 1) | ((mask & 0b1010) >> 1)
+}
+
+/// A bright color for a hue in degrees, readable on dark backgrounds.
+fn hue_color(hue: u64) -> Color {
+    let (s, l) = (0.65, 0.6);
+    let c = (1.0 - (2.0 * l - 1.0_f64).abs()) * s;
+    let h = hue as f64 / 60.0;
+    let x = c * (1.0 - (h % 2.0 - 1.0).abs());
+    let (r, g, b) = match hue / 60 {
+        0 => (c, x, 0.0),
+        1 => (x, c, 0.0),
+        2 => (0.0, c, x),
+        3 => (0.0, x, c),
+        4 => (x, 0.0, c),
+        _ => (c, 0.0, x),
+    };
+    let m = l - c / 2.0;
+    let channel = |v: f64| ((v + m) * 255.0).round() as u8;
+    Color::Rgb(channel(r), channel(g), channel(b))
+}
+
+/// The glyphs and color for `key` (an email, or a login when there is none).
+pub fn pattern(key: &str) -> (String, Color) {
+    let h = hash(key);
+    // Never empty, so every author gets a visible mark
+    let left = 1 + (h >> 16) as usize % 15;
+    let glyphs = format!("{}{}", QUADRANTS[left], QUADRANTS[mirror(left)]);
+    (glyphs, hue_color(h % 360))
+}
+
+/// The identicon followed by a space, ready to prepend to an author name.
+pub fn span(key: &str) -> Span<'static> {
+    let (glyphs, color) = pattern(key);
+    Span::styled(format!("{} ", glyphs), Style::default().fg(color))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pattern_is_deterministic_and_case_insensitive() {
+        assert_eq!(pattern("dev@example.com"), pattern(" Dev@Example.com"));
+        assert_ne!(pattern("dev@example.com"), pattern("ops@example.com"));
+        let (glyphs, _) = pattern("someone@example.com");
+        assert_eq!(glyphs.chars().count(), 2);
+        assert!(!glyphs.starts_with(' '));
+    }
+
+    #[test]
+    fn test_mirror() {
+        // ▘ ↔ ▝, ▙ ↔ ▟, symmetric shapes stay put
+        assert_eq!(mirror(1), 2);
+        assert_eq!(mirror(13), 14);
+        assert_eq!(mirror(3), 3);
+        assert_eq!(mirror(9), 6);
+    }
+}
@@ -0,0 +1,511 @@
+//! Unicode half-block previews of small images for the Staging diff pane.
+//!
+//! Decodes 8-bit non-interlaced PNGs (with a minimal inflate) and uncompressed
+//! 24/32-bit BMPs, then draws two pixel rows per terminal row with `▀`. Only
+//! used when the terminal supports truecolor (`[ui] image_preview`).
+
+use ratatui::{
+    style::{Color, Style},
+    text::{Line, Span},
+};
+
+/// Images with more pixels than this are not decoded.
+const MAX_PIXELS: usize = 1024 * 1024;
+
+/// A decoded image, RGBA row by row.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RgbaImage {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<[u8; 4]>,
+}
+
+/// Decode a PNG or BMP small enough to preview.
+pub fn decode(bytes: &[u8]) -> Option<RgbaImage> {
+    if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+        decode_png(bytes)
+    } else if bytes.starts_with(b"BM") {
+        decode_bmp(bytes)
+    } else {
+        None
+    }
+}
+
+/// Render the image scaled down (never up) to fit `max_cols` × `max_rows` cells.
+pub fn preview_lines(img: &RgbaImage, max_cols: usize, max_rows: usize) -> Vec<Line<'static>> {
+    if img.width == 0 || img.height == 0 || max_cols == 0 || max_rows == 0 {
+        return Vec::new();
+    }
+    let scale = (img.width as f64 / max_cols as f64)
+        .max(img.height as f64 / (max_rows * 2) as f64)
+        .max(1.0);
+    let cols = ((img.width as f64 / scale) as usize).max(1);
+    let rows = ((img.height as f64 / scale) as usize).max(1);
+    let sample = |x: usize, y: usize| -> Color {
+        let sx = ((x as f64 * scale) as usize).min(img.width - 1);
+        let sy = ((y as f64 * scale) as usize).min(img.height - 1);
+        let [r, g, b, a] = img.pixels[sy * img.width + sx];
+        // Composite over black so transparent areas read as background
+        let blend = |c: u8| (c as u16 * a as u16 / 255) as u8;
+        Color::Rgb(blend(r), blend(g), blend(b))
+    };
+
+    (0..rows.div_ceil(2))
+        .map(|row| {
+            let spans: Vec<Span> = (0..cols)
+                .map(|x| {
+                    let top = sample(x, row * 2);
+                    let style = if row * 2 + 1 < rows {
+                        Style::default().fg(top).bg(sample(x, row * 2 + 1))
+                    } else {
+                        Style::default().fg(top)
+                    };
+                    Span::styled("▀", style)
+                })
+                .collect();
+            Line::from(spans)
+        })
+        .collect()
+}
+
+// ─── BMP ───────────────────────────────────────────────────────
+
+fn decode_bmp(b: &[u8]) -> Option<RgbaImage> {
+    let le32 = |at: usize| Some(u32::from_le_bytes(b.get(at..at + 4)?.try_into().ok()?));
+    let offset = le32(10)? as usize;
+    let width = le32(18)? as i32;
+    let height = le32(22)? as i32;
+    let bpp = u16::from_le_bytes(b.get(28..30)?.try_into().ok()?) as usize;
+    let compression = le32(30)?;
+    // BI_RGB, or BI_BITFIELDS with the usual BGRA layout for 32-bit
+    if width <= 0 || !(bpp == 24 || bpp == 32) || !(compression == 0 || compression == 3) {
+        return None;
+    }
+    let (width, top_down) = (width as usize, height < 0);
+    let height = height.unsigned_abs() as usize;
+    if width * height > MAX_PIXELS {
+        return None;
+    }
+    let stride = (width * bpp).div_ceil(32) * 4;
+    let channels = bpp / 8;
+
+    let mut pixels = Vec::with_capacity(width * height);
+    for y in 0..height {
+        let src_row = if top_down { y } else { height - 1 - y };
+        let row = b.get(offset + src_row * stride..offset + src_row * stride + width * channels)?;
+        for px in row.chunks_exact(channels) {
+            let alpha = if channels == 4 && compression == 3 {
+                px[3]
+            } else {
+                255
+            };
+            pixels.push([px[2], px[1], px[0], alpha]);
+        }
+    }
+    Some(RgbaImage {
+        width,
+        height,
+        pixels,
+    })
+}
+
+// ─── PNG ───────────────────────────────────────────────────────
+
+fn decode_png(b: &[u8]) -> Option<RgbaImage> {
+    let mut pos = 8;
+    let (mut width, mut height, mut color_type) = (0usize, 0usize, 0u8);
+    let mut palette: Vec<[u8; 3]> = Vec::new();
+    let mut alpha: Vec<u8> = Vec::new();
+    let mut data = Vec::new();
+
+    while pos + 8 <= b.len() {
+        let len = u32::from_be_bytes(b[pos..pos + 4].try_into().ok()?) as usize;
+        let kind = &b[pos + 4..pos + 8];
+        let body = b.get(pos + 8..pos + 8 + len)?;
+        match kind {
+            b"IHDR" => {
+                width = u32::from_be_bytes(body.get(0..4)?.try_into().ok()?) as usize;
+                height = u32::from_be_bytes(body.get(4..8)?.try_into().ok()?) as usize;
+                let (depth, interlace) = (*body.get(8)?, *body.get(12)?);
+                color_type = *body.get(9)?;
+                if depth != 8 || interlace != 0 || width * height > MAX_PIXELS {
+                    return None;
+                }
+            }
+            b"PLTE" => palette = body.chunks_exact(3).map(|c| [c[0], c[1], c[2]]).collect(),
+            b"tRNS" => alpha = body.to_vec(),
+            b"IDAT" => data.extend_from_slice(body),
+            b"IEND" => break,
+            _ => {}
+        }
+        pos += 12 + len; // length + type + body + CRC
+    }
+
+    let channels = match color_type {
+        0 => 1, // gray
+        2 => 3, // RGB
+        3 => 1, // palette
+        4 => 2, // gray + alpha
+        6 => 4, // RGBA
+        _ => return None,
+    };
+    let stride = width * channels;
+    let raw = inflate(data.get(2..)?, (stride + 1) * height)?;
+    let pixels_raw = unfilter(&raw, stride, height, channels)?;
+
+    let pixels = pixels_raw
+        .chunks_exact(channels)
+        .map(|p| match color_type {
+            0 => [p[0], p[0], p[0], 255],
+            2 => [p[0], p[1], p[2], 255],
+            3 => {
+                let [r, g, b] = palette.get(p[0] as usize).copied().unwrap_or([0, 0, 0]);
+                [r, g, b, alpha.get(p[0] as usize).copied().unwrap_or(255)]
+            }
+            4 => [p[0], p[0], p[0], p[1]],
+            _ => [p[0], p[1], p[2], p[3]],
+        })
+        .collect();
+    Some(RgbaImage {
+        width,
+        height,
+        pixels,
+    })
+}
+
+/// Undo PNG per-row filters (None, Sub, Up, Average, Paeth).
+fn unfilter(raw: &[u8], stride: usize, height: usize, bpp: usize) -> Option<Vec<u8>> {
+    let mut out = vec![0u8; stride * height];
+    for y in 0..height {
+        let filter = *raw.get(y * (stride + 1))?;
+        let line = raw.get(y * (stride + 1) + 1..(y + 1) * (stride + 1))?;
+        for x in 0..stride {
+            let a = if x >= bpp {
+                out[y * stride + x - bpp]
+            } else {
+                0
+            };
+            let up = if y > 0 { out[(y - 1) * stride + x] } else { 0 };
+            let c = if x >= bpp && y > 0 {
+                out[(y - 1) * stride + x - bpp]
+            } else {
+                0
+            };
+            let predicted = match filter {
+                0 => 0,
+                1 => a,
+                2 => up,
+                3 => ((a as u16 + up as u16) / 2) as u8,
+                4 => paeth(a, up, c),
+                _ => return None,
+            };
+            out[y * stride + x] = line[x].wrapping_add(predicted);
+        }
+    }
+    Some(out)
+}
+
+fn paeth(a: u8, b: u8, c: u8) -> u8 {
+    let p = a as i16 + b as i16 - c as i16;
+    let (pa, pb, pc) = (
+        (p - a as i16).abs(),
+        (p - b as i16).abs(),
+        (p - c as i16).abs(),
+    );
+    if pa <= pb && pa <= pc {
+        a
+    } else if pb <= pc {
+        b
+    } else {
+        c
+    }
+}
+
+// ─── Inflate (RFC 1951) ────────────────────────────────────────
+
+const LEN_BASE: [u16; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131,
+    163, 195, 227, 258,
+];
+const LEN_EXTRA: [u8; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+];
+const DIST_BASE: [u16; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537,
+    2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577,
+];
+const DIST_EXTRA: [u8; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13,
+    13,
+];
+/// Order in which code-length code lengths are stored in a dynamic block.
+const CLEN_ORDER: [usize; 19] = [
+    16, 17, 18, 0, 8, 7, 9, 6, 10, 5, 11, 4, 12, 3, 13, 2, 14, 1, 15,
+];
+
+struct Bits<'a> {
+    data: &'a [u8],
+    pos: usize,
+    bit: u8,
+}
+
+impl Bits<'_> {
+    fn bit(&mut self) -> Option<u32> {
+        let byte = *self.data.get(self.pos)?;
+        let value = (byte >> self.bit) & 1;
+        self.bit += 1;
+        if self.bit == 8 {
+            self.bit = 0;
+            self.pos += 1;
+        }
+        Some(value as u32)
+    }
+
+    fn bits(&mut self, n: u8) -> Option<u32> {
+        let mut value = 0;
+        for i in 0..n {
+            value |= self.bit()? << i;
+        }
+        Some(value)
+    }
+
+    fn decode(&mut self, h: &Huffman) -> Option<u16> {
+        // Canonical Huffman decoding, one bit at a time
+        let (mut code, mut first, mut index) = (0i32, 0i32, 0i32);
+        for len in 1..16 {
+            code |= self.bit()? as i32;
+            let count = h.counts[len] as i32;
+            if code - first < count {
+                return h.symbols.get((index + code - first) as usize).copied();
+            }
+            index += count;
+            first = (first + count) << 1;
+            code <<= 1;
+        }
+        None
+    }
+}
+
+struct Huffman {
+    counts: [u16; 16],
+    symbols: Vec<u16>,
+}
+
+impl Huffman {
+    fn new(lengths: &[u8]) -> Self {
+        let mut counts = [0u16; 16];
+        for &l in lengths {
+            counts[l as usize] += 1;
+        }
+        counts[0] = 0;
+        let mut symbols: Vec<(u8, u16)> = lengths
+            .iter()
+            .enumerate()
+            .filter(|(_, l)| **l > 0)
+            .map(|(s, l)| (*l, s as u16))
+            .collect();
+        symbols.sort();
+        Self {
+            counts,
+            symbols: symbols.into_iter().map(|(_, s)| s).collect(),
+        }
+    }
+}
+
+/// Decompress a raw deflate stream, giving up past `limit` bytes of output.
+fn inflate(data: &[u8], limit: usize) -> Option<Vec<u8>> {
+    let mut bits = Bits {
+        data,
+        pos: 0,
+        bit: 0,
+    };
+    let mut out: Vec<u8> = Vec::with_capacity(limit);
+    loop {
+        let last = bits.bit()? == 1;
+        match bits.bits(2)? {
+            0 => {
+                // Stored block: byte-aligned LEN, NLEN, then raw bytes
+                if bits.bit != 0 {
+                    bits.bit = 0;
+                    bits.pos += 1;
+                }
+                let len = u16::from_le_bytes(data.get(bits.pos..bits.pos + 2)?.try_into().ok()?);
+                let start = bits.pos + 4;
+                out.extend_from_slice(data.get(start..start + len as usize)?);
+                bits.pos = start + len as usize;
+            }
+            1 => {
+                let mut lengths = [8u8; 288];
+                lengths[144..256].fill(9);
+                lengths[256..280].fill(7);
+                let (lit, dist) = (Huffman::new(&lengths), Huffman::new(&[5u8; 30]));
+                inflate_block(&mut bits, &mut out, &lit, &dist, limit)?;
+            }
+            2 => {
+                let (lit, dist) = read_dynamic_tables(&mut bits)?;
+                inflate_block(&mut bits, &mut out, &lit, &dist, limit)?;
+            }
+            _ => return None,
+        }
+        if out.len() > limit {
+            return None;
+        }
+        if last {
+            return Some(out);
+        }
+    }
+}
+
+fn read_dynamic_tables(bits: &mut Bits) -> Option<(Huffman, Huffman)> {
+    let hlit = bits.bits(5)? as usize + 257;
+    let hdist = bits.bits(5)? as usize + 1;
+    let hclen = bits.bits(4)? as usize + 4;
+    let mut clen = [0u8; 19];
+    for &i in &CLEN_ORDER[..hclen] {
+        clen[i] = bits.bits(3)? as u8;
+    }
+    let clen = Huffman::new(&clen);
+
+    let mut lengths = Vec::with_capacity(hlit + hdist);
+    while lengths.len() < hlit + hdist {
+        match bits.decode(&clen)? {
+            sym @ 0..=15 => lengths.push(sym as u8),
+            16 => {
+                let prev = *lengths.last()?;
+                let n = 3 + bits.bits(2)?;
+                lengths.extend(std::iter::repeat_n(prev, n as usize));
+            }
+            17 => {
+                let n = 3 + bits.bits(3)?;
+                lengths.extend(std::iter::repeat_n(0, n as usize));
+            }
+            _ => {
+                let n = 11 + bits.bits(7)?;
+                lengths.extend(std::iter::repeat_n(0, n as usize));
+            }
+        }
+    }
+    if lengths.len() != hlit + hdist {
+        return None;
+    }
+    Some((
+        Huffman::new(&lengths[..hlit]),
+        Huffman::new(&lengths[hlit..]),
+    ))
+}
+
+fn inflate_block(
+    bits: &mut Bits,
+    out: &mut Vec<u8>,
+    lit: &Huffman,
+    dist: &Huffman,
+    limit: usize,
+) -> Option<()> {
+    loop {
+        let sym = bits.decode(lit)? as usize;
+        match sym {
+            0..=255 => out.push(sym as u8),
+            256 => return Some(()),
+            _ => {
+                let i = sym - 257;
+                let len = *LEN_BASE.get(i)? as usize + bits.bits(*LEN_EXTRA.get(i)?)? as usize;
+                let d = bits.decode(dist)? as usize;
+                let distance =
+                    *DIST_BASE.get(d)? as usize + bits.bits(*DIST_EXTRA.get(d)?)? as usize;
+                if distance > out.len() {
+                    return None;
+                }
+                let start = out.len() - distance;
+                for k in 0..len {
+                    out.push(out[start + k]);
+                }
+            }
+        }
+        if out.len() > limit {
+            return None;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 2×2 RGB PNG (red, green / blue, white) with a stored (uncompressed) deflate block.
+    fn tiny_png() -> Vec<u8> {
+        let raw: Vec<u8> = vec![
+            0, 255, 0, 0, 0, 255, 0, // row 0: filter None
+            2, 1, 0, 255, 255, 0, 255, // row 1: filter Up (adds row 0, wrapping)
+        ];
+        let mut zlib = vec![0x78, 0x01, 0x01];
+        zlib.extend_from_slice(&(raw.len() as u16).to_le_bytes());
+        zlib.extend_from_slice(&(!(raw.len() as u16)).to_le_bytes());
+        zlib.extend_from_slice(&raw);
+        zlib.extend_from_slice(&[0, 0, 0, 0]); // Adler-32, not checked
+
+        let chunk = |kind: &[u8], body: &[u8]| {
+            let mut c = (body.len() as u32).to_be_bytes().to_vec();
+            c.extend_from_slice(kind);
+            c.extend_from_slice(body);
+            c.extend_from_slice(&[0, 0, 0, 0]); // CRC, not checked
+            c
+        };
+        let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+        png.extend(chunk(b"IHDR", &[0, 0, 0, 2, 0, 0, 0, 2, 8, 2, 0, 0, 0]));
+        png.extend(chunk(b"IDAT", &zlib));
+        png.extend(chunk(b"IEND", &[]));
+        png
+    }
+
+    #[test]
+    fn test_decode_png_stored_and_filtered() {
+        let img = decode(&tiny_png()).unwrap();
+        assert_eq!((img.width, img.height), (2, 2));
+        assert_eq!(
+            img.pixels,
+            vec![
+                [255, 0, 0, 255],
+                [0, 255, 0, 255],
+                [0, 0, 255, 255],
+                [255, 255, 255, 255]
+            ]
+        );
+    }
+
+    #[test]
+    fn test_inflate_fixed_huffman() {
+        // zlib.compress(b"abcabcabc") without the 2-byte header and Adler-32
+        let data = [0x4b, 0x4c, 0x4a, 0x4e, 0x04, 0x23, 0x00];
+        assert_eq!(inflate(&data, 100).unwrap(), b"abcabcabc");
+        assert!(inflate(&data, 4).is_none());
+    }
+
+    #[test]
+    fn test_decode_bmp_bottom_up() {
+        // 1×2 24-bit BMP: bottom row blue, top row red
+        let mut bmp = vec![0u8; 54];
+        bmp[..2].copy_from_slice(b"BM");
+        bmp[10..14].copy_from_slice(&54u32.to_le_bytes());
+        bmp[18..22].copy_from_slice(&1u32.to_le_bytes());
+        bmp[22..26].copy_from_slice(&2i32.to_le_bytes());
+        bmp[28..30].copy_from_slice(&24u16.to_le_bytes());
+        bmp.extend_from_slice(&[255, 0, 0, 0, 0, 0, 255, 0]); // BGR + padding per row
+        let img = decode(&bmp).unwrap();
+        assert_eq!(img.pixels, vec![[255, 0, 0, 255], [0, 0, 255, 255]]);
+    }
+
+    #[test]
+    fn test_preview_lines_fit_and_halve_rows() {
+        let img = RgbaImage {
+            width: 4,
+            height: 4,
+            pixels: vec![[255, 255, 255, 255]; 16],
+        };
+        let lines = preview_lines(&img, 10, 10);
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].spans.len(), 4);
+        // Scaled down to fit two columns
+        assert_eq!(preview_lines(&img, 2, 10)[0].spans.len(), 2);
+        assert!(preview_lines(&img, 0, 10).is_empty());
+    }
+}
@@ -0,0 +1,182 @@
+//! Single-line text editing for the Input popup: cursor movement, word and
+//! line deletion, and pasting. The cursor is kept as a count of characters
+//! back from the end, so a freshly opened popup (0) starts after any
+//! pre-filled text.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+/// Byte offset in `value` of the cursor `back` characters from the end.
+fn cursor_byte(value: &str, back: usize) -> usize {
+    let chars = value.chars().count();
+    let at = chars.saturating_sub(back);
+    value
+        .char_indices()
+        .nth(at)
+        .map(|(i, _)| i)
+        .unwrap_or(value.len())
+}
+
+/// `value` split at the cursor.
+pub fn split(value: &str, back: usize) -> (&str, &str) {
+    value.split_at(cursor_byte(value, back))
+}
+
+/// Start of the word before byte offset `at`, skipping whitespace first.
+fn word_start(value: &str, at: usize) -> usize {
+    let before = value[..at].trim_end();
+    before
+        .rfind(char::is_whitespace)
+        .map(|i| i + before[i..].chars().next().map_or(1, char::len_utf8))
+        .unwrap_or(0)
+}
+
+/// End of the word after byte offset `at`, skipping whitespace first.
+fn word_end(value: &str, at: usize) -> usize {
+    let after = &value[at..];
+    let skipped = after.len() - after.trim_start().len();
+    after[skipped..]
+        .find(char::is_whitespace)
+        .map(|i| at + skipped + i)
+        .unwrap_or(value.len())
+}
+
+/// Characters from byte offset `at` to the end.
+fn back_from(value: &str, at: usize) -> usize {
+    value[at..].chars().count()
+}
+
+/// Insert `text` at the cursor. Line breaks become spaces: the popup holds a
+/// single line.
+pub fn insert(value: &mut String, back: usize, text: &str) {
+    let text: String = text
+        .trim_end_matches(['\r', '\n'])
+        .chars()
+        .map(|c| if c == '\n' || c == '\r' { ' ' } else { c })
+        .collect();
+    value.insert_str(cursor_byte(value, back), &text);
+}
+
+/// Apply an editing key. Returns `false` for keys that aren't editing keys
+/// (Enter, Esc, ...), which the popup handles itself.
+pub fn handle_key(value: &mut String, back: &mut usize, key: KeyEvent) -> bool {
+    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+    let alt = key.modifiers.contains(KeyModifiers::ALT);
+    let at = cursor_byte(value, *back);
+    match key.code {
+        KeyCode::Left if ctrl || alt => *back = back_from(value, word_start(value, at)),
+        KeyCode::Right if ctrl || alt => *back = back_from(value, word_end(value, at)),
+        KeyCode::Char('b') if alt => *back = back_from(value, word_start(value, at)),
+        KeyCode::Char('f') if alt => *back = back_from(value, word_end(value, at)),
+        KeyCode::Left => *back = (*back + 1).min(value.chars().count()),
+        KeyCode::Right => *back = back.saturating_sub(1),
+        KeyCode::Home => *back = value.chars().count(),
+        KeyCode::Char('a') if ctrl => *back = value.chars().count(),
+        KeyCode::End => *back = 0,
+        KeyCode::Char('e') if ctrl => *back = 0,
+        KeyCode::Char('w') if ctrl => {
+            value.replace_range(word_start(value, at)..at, "");
+        }
+        KeyCode::Backspace if ctrl || alt => {
+            value.replace_range(word_start(value, at)..at, "");
+        }
+        KeyCode::Char('u') if ctrl => value.replace_range(..at, ""),
+        KeyCode::Char('k') if ctrl => {
+            value.truncate(at);
+            *back = 0;
+        }
+        KeyCode::Backspace => {
+            if let Some(c) = value[..at].chars().next_back() {
+                value.replace_range(at - c.len_utf8()..at, "");
+            }
+        }
+        KeyCode::Delete => {
+            if let Some(c) = value[at..].chars().next() {
+                value.replace_range(at..at + c.len_utf8(), "");
+                *back -= 1;
+            }
+        }
+        KeyCode::Char(c) if !ctrl && !alt => value.insert(at, c),
+        _ => return false,
+    }
+    true
+}
+
+/// `value` with every character hidden, for secrets.
+pub fn mask(value: &str) -> String {
+    "•".repeat(value.chars().count())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn ctrl(c: char) -> KeyEvent {
+        KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL)
+    }
+
+    fn type_keys(value: &str, keys: &[KeyEvent]) -> (String, usize) {
+        let mut value = value.to_string();
+        let mut back = 0;
+        for k in keys {
+            handle_key(&mut value, &mut back, *k);
+        }
+        (value, back)
+    }
+
+    #[test]
+    fn test_cursor_movement_and_insert() {
+        let (value, back) = type_keys(
+            "feture",
+            &[
+                key(KeyCode::Left),
+                key(KeyCode::Left),
+                key(KeyCode::Left),
+                key(KeyCode::Left),
+                key(KeyCode::Char('a')),
+            ],
+        );
+        assert_eq!((value.as_str(), back), ("feature", 4));
+
+        let (value, back) = type_keys("ünï", &[key(KeyCode::Home), key(KeyCode::Delete)]);
+        assert_eq!((value.as_str(), back), ("nï", 2));
+        let (value, _) = type_keys(
+            "ab",
+            &[
+                ctrl('a'),
+                key(KeyCode::Char('x')),
+                ctrl('e'),
+                key(KeyCode::Char('y')),
+            ],
+        );
+        assert_eq!(value, "xaby");
+    }
+
+    #[test]
+    fn test_word_and_line_deletion() {
+        assert_eq!(type_keys("git push origin  ", &[ctrl('w')]).0, "git push ");
+        let (value, back) = type_keys(
+            "one two three",
+            &[
+                KeyEvent::new(KeyCode::Left, KeyModifiers::CONTROL),
+                ctrl('w'),
+            ],
+        );
+        assert_eq!((value.as_str(), back), ("one three", 5));
+        let (value, back) = type_keys("one two", &[key(KeyCode::Left), ctrl('u')]);
+        assert_eq!((value.as_str(), back), ("o", 1));
+        assert_eq!(type_keys("one two", &[ctrl('a'), ctrl('k')]).0, "");
+    }
+
+    #[test]
+    fn test_insert_paste_and_mask() {
+        let mut value = "ab".to_string();
+        insert(&mut value, 1, "x\ny\n");
+        assert_eq!(value, "ax yb");
+        assert_eq!(split(&value, 1), ("ax y", "b"));
+        assert_eq!(mask("sk-ü1"), "•••••");
+    }
+}
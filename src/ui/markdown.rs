@@ -0,0 +1,358 @@
+//! A small markdown renderer for AI answers and GitHub text: headings, bold
+//! and italics, inline code and fenced code blocks, lists and task lists,
+//! block quotes, rules and links. Not CommonMark — just enough that replies
+//! and PR descriptions don't read as raw markup.
+//!
+//! Every source line becomes exactly one rendered line, so scroll offsets and
+//! `/` search line numbers still line up with the text.
+
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+};
+
+/// Background of inline code and code blocks.
+const CODE_BG: Color = Color::Rgb(48, 48, 48);
+
+fn code_style() -> Style {
+    Style::default().fg(Color::Yellow).bg(CODE_BG)
+}
+
+fn dim() -> Style {
+    Style::default().fg(Color::DarkGray)
+}
+
+/// `text` as styled lines, each starting with `indent`. Plain text is drawn in
+/// `base`.
+pub fn render(text: &str, indent: &str, base: Style) -> Vec<Line<'static>> {
+    let source: Vec<&str> = text.lines().collect();
+    let mut lines = Vec::with_capacity(source.len());
+    // Closing marker and width of the code block we're in
+    let mut fence: Option<(&str, usize)> = None;
+    let mut in_comment = false;
+
+    for (i, raw) in source.iter().enumerate() {
+        let trimmed = raw.trim_start();
+        let mut spans = vec![Span::raw(indent.to_string())];
+
+        if let Some((marker, width)) = fence {
+            if trimmed.starts_with(marker) {
+                fence = None;
+                spans.push(Span::styled(raw.to_string(), dim()));
+            } else {
+                // Pad so the block's background is one even rectangle
+                let pad = width.saturating_sub(raw.chars().count());
+                spans.push(Span::styled(
+                    format!(" {}{} ", raw, " ".repeat(pad)),
+                    code_style(),
+                ));
+            }
+        } else if let Some(marker) = ["```", "~~~"].into_iter().find(|m| trimmed.starts_with(m)) {
+            let width = source[i + 1..]
+                .iter()
+                .take_while(|l| !l.trim_start().starts_with(marker))
+                .map(|l| l.chars().count())
+                .max()
+                .unwrap_or(0);
+            fence = Some((marker, width));
+            spans.push(Span::styled(raw.to_string(), dim()));
+        } else if in_comment || trimmed.starts_with("<!--") {
+            // HTML comments, e.g. the hints in PR templates
+            in_comment = !raw.contains("-->");
+            spans.push(Span::styled(raw.to_string(), dim()));
+        } else {
+            spans.extend(block(raw, base));
+        }
+        lines.push(Line::from(spans));
+    }
+    lines
+}
+
+/// One line outside code blocks: headings, rules, quotes and list items.
+fn block(raw: &str, base: Style) -> Vec<Span<'static>> {
+    let trimmed = raw.trim_start();
+    let lead = raw[..raw.len() - trimmed.len()].to_string();
+
+    let hashes = trimmed.chars().take_while(|&c| c == '#').count();
+    if (1..=6).contains(&hashes) && (trimmed.len() == hashes || trimmed[hashes..].starts_with(' '))
+    {
+        let mut style = base.fg(Color::Cyan).add_modifier(Modifier::BOLD);
+        if hashes == 1 {
+            style = style.add_modifier(Modifier::UNDERLINED);
+        }
+        let title = trimmed[hashes..].trim().trim_end_matches('#').trim_end();
+        return inline(title, style);
+    }
+
+    let rule: Vec<char> = trimmed.chars().filter(|c| !c.is_whitespace()).collect();
+    if rule.len() >= 3 && matches!(rule[0], '-' | '*' | '_') && rule.iter().all(|&c| c == rule[0]) {
+        return vec![Span::styled("─".repeat(30), dim())];
+    }
+
+    if let Some(quote) = trimmed.strip_prefix('>') {
+        let mut spans = vec![Span::raw(lead), Span::styled("│ ", dim())];
+        spans.extend(inline(
+            quote.trim_start(),
+            base.fg(Color::Gray).add_modifier(Modifier::ITALIC),
+        ));
+        return spans;
+    }
+
+    let bullet = Style::default().fg(Color::Cyan);
+    let (marker, item) = if let Some(item) = ["- ", "* ", "+ "]
+        .iter()
+        .find_map(|m| trimmed.strip_prefix(m))
+    {
+        (Some("• ".to_string()), item)
+    } else {
+        let digits = trimmed.chars().take_while(char::is_ascii_digit).count();
+        match trimmed[digits..]
+            .strip_prefix(". ")
+            .or_else(|| trimmed[digits..].strip_prefix(") "))
+        {
+            Some(item) if digits > 0 => (Some(format!("{}. ", &trimmed[..digits])), item),
+            _ => (None, trimmed),
+        }
+    };
+    let mut spans = vec![Span::raw(lead)];
+    if let Some(marker) = marker {
+        spans.push(Span::styled(marker, bullet));
+    }
+    let item = if let Some(rest) = item.strip_prefix("[ ] ") {
+        spans.push(Span::styled("☐ ", bullet));
+        rest
+    } else if let Some(rest) = item
+        .strip_prefix("[x] ")
+        .or_else(|| item.strip_prefix("[X] "))
+    {
+        spans.push(Span::styled("☑ ", Style::default().fg(Color::Green)));
+        rest
+    } else {
+        item
+    };
+    spans.extend(inline(item, base));
+    spans
+}
+
+/// `[label](url)` at the start of `rest`: the label, the URL and the length.
+fn link(rest: &str) -> Option<(&str, &str, usize)> {
+    let close = rest.find("](")?;
+    let label = &rest[1..close];
+    if label.contains('[') {
+        return None;
+    }
+    let url_len = rest[close + 2..].find(')')?;
+    let url = &rest[close + 2..close + 2 + url_len];
+    Some((label, url, close + 3 + url_len))
+}
+
+/// Inline markup — code, links, bold and italics — on top of `base`.
+fn inline(text: &str, base: Style) -> Vec<Span<'static>> {
+    let mut spans: Vec<Span<'static>> = Vec::new();
+    let mut plain = String::new();
+    let mut bold = false;
+    let mut italic: Option<char> = None;
+    let mut prev: Option<char> = None;
+    let mut rest = text;
+
+    let style = |bold: bool, italic: bool| {
+        let mut style = base;
+        if bold {
+            style = style.add_modifier(Modifier::BOLD);
+        }
+        if italic {
+            style = style.add_modifier(Modifier::ITALIC);
+        }
+        style
+    };
+    let flush = |spans: &mut Vec<Span<'static>>, plain: &mut String, style: Style| {
+        if !plain.is_empty() {
+            spans.push(Span::styled(std::mem::take(plain), style));
+        }
+    };
+
+    while let Some(c) = rest.chars().next() {
+        let current = style(bold, italic.is_some());
+        let next = rest[c.len_utf8()..].chars().next();
+        let after_word = prev.is_some_and(char::is_alphanumeric);
+
+        if c == '`'
+            && let Some(end) = rest[1..].find('`')
+        {
+            flush(&mut spans, &mut plain, current);
+            spans.push(Span::styled(rest[1..1 + end].to_string(), code_style()));
+            prev = Some('`');
+            rest = &rest[end + 2..];
+            continue;
+        }
+
+        let image = c == '!' && rest[1..].starts_with('[');
+        let at = usize::from(image);
+        if (c == '[' || image)
+            && let Some((label, url, len)) = link(&rest[at..])
+        {
+            flush(&mut spans, &mut plain, current);
+            let label = if image {
+                format!("image: {}", label)
+            } else {
+                label.to_string()
+            };
+            let shown = current.fg(Color::Blue).add_modifier(Modifier::UNDERLINED);
+            let same = label == url;
+            spans.push(Span::styled(label, shown));
+            if !same {
+                spans.push(Span::styled(format!(" ({})", url), dim()));
+            }
+            prev = Some(')');
+            rest = &rest[at + len..];
+            continue;
+        }
+        if c == '<'
+            && rest.starts_with("<http")
+            && let Some(end) = rest.find('>')
+        {
+            flush(&mut spans, &mut plain, current);
+            spans.push(Span::styled(
+                rest[1..end].to_string(),
+                current.fg(Color::Blue).add_modifier(Modifier::UNDERLINED),
+            ));
+            prev = Some('>');
+            rest = &rest[end + 1..];
+            continue;
+        }
+
+        if c == '*' || c == '_' {
+            let double = next == Some(c);
+            let delim = if double { &rest[..2] } else { &rest[..1] };
+            let inner = &rest[delim.len()..];
+            let opens = !after_word
+                && inner.chars().next().is_some_and(|n| !n.is_whitespace())
+                && inner.contains(delim);
+            let closes = prev.is_some_and(|p| !p.is_whitespace());
+            let toggled = if double {
+                (bold && closes) || (!bold && opens)
+            } else {
+                match italic {
+                    Some(open) => open == c && closes,
+                    None => opens,
+                }
+            };
+            if toggled {
+                flush(&mut spans, &mut plain, current);
+                if double {
+                    bold = !bold;
+                } else {
+                    italic = if italic.is_some() { None } else { Some(c) };
+                }
+                rest = inner;
+                continue;
+            }
+            // A run like `__` that opens nothing stays literal as a whole
+            plain.push_str(delim);
+            prev = Some(c);
+            rest = inner;
+            continue;
+        }
+
+        plain.push(c);
+        prev = Some(c);
+        rest = &rest[c.len_utf8()..];
+    }
+    flush(&mut spans, &mut plain, style(bold, italic.is_some()));
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(line: &Line) -> String {
+        line.spans.iter().map(|s| s.content.as_ref()).collect()
+    }
+
+    fn styled<'a>(line: &'a Line, content: &str) -> &'a Span<'a> {
+        line.spans
+            .iter()
+            .find(|s| s.content == content)
+            .unwrap_or_else(|| panic!("no span '{}' in {:?}", content, line))
+    }
+
+    #[test]
+    fn test_inline_markup() {
+        let lines = render(
+            "Use **git add** and `git commit`, see [docs](https://git-scm.com) or *this*.",
+            "",
+            Style::default(),
+        );
+        let line = &lines[0];
+        assert_eq!(
+            text(line),
+            "Use git add and git commit, see docs (https://git-scm.com) or this."
+        );
+        assert!(
+            styled(line, "git add")
+                .style
+                .add_modifier
+                .contains(Modifier::BOLD)
+        );
+        assert_eq!(styled(line, "git commit").style.bg, Some(CODE_BG));
+        assert!(
+            styled(line, "docs")
+                .style
+                .add_modifier
+                .contains(Modifier::UNDERLINED)
+        );
+        assert!(
+            styled(line, "this")
+                .style
+                .add_modifier
+                .contains(Modifier::ITALIC)
+        );
+
+        // Unmatched or mid-word markers are left alone
+        for plain in ["snake_case_name", "2 * 3 = 6", "**not closed", "a__b__c"] {
+            assert_eq!(text(&render(plain, "", Style::default())[0]), plain);
+        }
+    }
+
+    #[test]
+    fn test_blocks() {
+        let source = "# Title\n- item\n  1. nested\n- [x] done\n> quoted\n---\n<!-- hint -->";
+        let lines = render(source, "  ", Style::default());
+        let rendered: Vec<String> = lines.iter().map(text).collect();
+        assert_eq!(
+            rendered,
+            [
+                "  Title",
+                "  • item",
+                "    1. nested",
+                "  • ☑ done",
+                "  │ quoted",
+                &format!("  {}", "─".repeat(30)),
+                "  <!-- hint -->",
+            ]
+        );
+        assert!(
+            styled(&lines[0], "Title")
+                .style
+                .add_modifier
+                .contains(Modifier::BOLD)
+        );
+        assert_eq!(
+            styled(&lines[6], "<!-- hint -->").style.fg,
+            Some(Color::DarkGray)
+        );
+    }
+
+    #[test]
+    fn test_code_block_keeps_one_line_per_source_line() {
+        let source = "Run:\n```sh\ngit fetch\ngit rebase origin/main\n```\n**done**";
+        let lines = render(source, "", Style::default());
+        assert_eq!(lines.len(), source.lines().count());
+        // Markup inside the block is kept, and the block is padded evenly
+        assert_eq!(text(&lines[2]), " git fetch              ");
+        assert_eq!(text(&lines[3]), " git rebase origin/main ");
+        assert_eq!(lines[2].spans[1].style.bg, Some(CODE_BG));
+        assert_eq!(text(&lines[5]), "done");
+    }
+}
@@ -2,18 +2,74 @@
 
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::{
+    Frame,
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, List, ListItem, Paragraph, Wrap},
-    Frame,
 };
 
 use crate::app::{FollowUpAction, FollowUpItem, Popup, View};
 use crate::git;
+use crate::git::merge::MergeType;
+use crate::ui::icons::Icon;
+use crate::ui::utils::{is_narrow, optional_height, render_paragraph_scrollbar};
+use crate::ui::zen;
 
 // ─── State ─────────────────────────────────────────────────────
 
+/// AI suggestion for a single conflict region.
+#[derive(Debug, Clone)]
+pub struct RegionSuggestion {
+    /// Full AI response.
+    pub response: String,
+    /// Parsed recommendation (ACCEPT_CURRENT, ACCEPT_INCOMING, MERGE_BOTH).
+    pub recommendation: Option<String>,
+    /// Lines that replace the region, without conflict markers.
+    pub resolved_content: Option<String>,
+}
+
+impl RegionSuggestion {
+    /// What applying this suggestion writes into the region: the resolved lines, or a
+    /// side choice ("current"/"incoming") understood by `git::merge::resolve_region`.
+    /// `None` if the AI gave nothing usable.
+    pub fn choice(&self) -> Option<String> {
+        if let Some(ref content) = self.resolved_content
+            && !content.lines().any(is_conflict_marker)
+        {
+            return Some(content.clone());
+        }
+        match self.recommendation.as_deref() {
+            Some(r) if r.contains("CURRENT") => Some("current".to_string()),
+            Some(r) if r.contains("INCOMING") => Some("incoming".to_string()),
+            _ => None,
+        }
+    }
+}
+
+fn is_conflict_marker(line: &str) -> bool {
+    ["<<<<<<<", "=======", ">>>>>>>", "|||||||"]
+        .iter()
+        .any(|m| line.starts_with(m))
+}
+
+/// How many resolutions `u` can take back.
+const UNDO_LIMIT: usize = 50;
+
+/// A file write made while resolving conflicts, with what's needed to take it
+/// back (`u`) or make it again (`U`).
+#[derive(Debug, Clone)]
+pub struct ResolutionStep {
+    pub path: String,
+    /// File content on the other side of the step: before the write on the
+    /// undo stack, after it on the redo stack.
+    pub content: Vec<u8>,
+    /// Unmerged index entries from before the file was staged.
+    pub entries: String,
+    /// What the step did, e.g. "took Incoming branch in region 2".
+    pub label: String,
+}
+
 /// State for the merge conflict resolution view.
 #[derive(Default)]
 pub struct MergeResolveState {
@@ -27,6 +83,9 @@ pub struct MergeResolveState {
     pub selected_region: usize,
     /// Raw file content with conflict markers.
     pub raw_conflict_content: Option<String>,
+    /// Both sides of the selected file when it's binary and can only be
+    /// resolved as a whole.
+    pub binary_conflict: Option<git::binary::BinarySummary>,
     /// Total lines in the file.
     pub total_lines: usize,
     /// AI suggestion text (full response).
@@ -35,6 +94,8 @@ pub struct MergeResolveState {
     pub ai_recommendation: Option<String>,
     /// Parsed resolved content from AI response.
     pub ai_resolved_content: Option<String>,
+    /// Per-region AI suggestions, indexed like `conflict_regions`.
+    pub region_suggestions: Vec<Option<RegionSuggestion>>,
     /// Current merge state (merge/rebase/cherry-pick).
     pub merge_state: Option<git::MergeState>,
     /// Scroll positions for the three panels.
@@ -47,6 +108,16 @@ pub struct MergeResolveState {
     pub follow_ups: Vec<FollowUpItem>,
     /// Selected follow-up index.
     pub follow_up_selected: usize,
+    /// Files resolved during this merge, in the order they were resolved.
+    pub resolved_files: Vec<String>,
+    /// Conflict regions resolved in zit during this merge.
+    pub resolved_regions: usize,
+    /// Identifies the merge being tracked; progress starts over for a new one.
+    pub session: Option<String>,
+    /// Resolutions made this session, most recent last.
+    pub undo_stack: Vec<ResolutionStep>,
+    /// Undone resolutions, most recently undone last.
+    pub redo_stack: Vec<ResolutionStep>,
 }
 
 impl MergeResolveState {
@@ -56,6 +127,9 @@ impl MergeResolveState {
         let saved_ai_recommendation = self.ai_recommendation.take();
         let saved_ai_resolved_content = self.ai_resolved_content.take();
         let saved_follow_ups = std::mem::take(&mut self.follow_ups);
+        let saved_region_suggestions = std::mem::take(&mut self.region_suggestions);
+        let saved_selected_region = self.selected_region;
+        let saved_path = self.selected_path();
         let saved_scroll_center = self.scroll_center;
 
         log::debug!(
@@ -64,25 +138,43 @@ impl MergeResolveState {
         );
 
         // Refresh conflict list
+        let previous: Vec<String> = self
+            .conflicted_files
+            .iter()
+            .map(|f| f.path.clone())
+            .collect();
         let status = git::status::get_status().unwrap_or_default();
         self.conflicted_files = status.conflicts;
         self.merge_state = git::merge::get_merge_state();
+        self.track_progress(&previous);
 
         log::debug!(
             "[MergeResolve] refresh: {} conflicted files",
             self.conflicted_files.len()
         );
 
-        // Clamp selected file
-        if self.selected_file >= self.conflicted_files.len() && !self.conflicted_files.is_empty() {
-            self.selected_file = self.conflicted_files.len() - 1;
+        // Stay on the same file; once it's resolved the next one has taken its
+        // index, wrapping around after the last
+        match saved_path
+            .as_ref()
+            .and_then(|p| self.conflicted_files.iter().position(|f| &f.path == p))
+        {
+            Some(index) => self.selected_file = index,
+            None if self.selected_file >= self.conflicted_files.len() => self.selected_file = 0,
+            None => {}
         }
 
         // Load the selected file
         self.load_selected_file();
 
+        // Region suggestions still apply if the same file has the same regions
+        let keep_regions = saved_path.is_some()
+            && saved_path == self.selected_path()
+            && saved_region_suggestions.len() == self.conflict_regions.len()
+            && saved_region_suggestions.iter().any(Option::is_some);
+
         // Restore AI state
-        if saved_ai_suggestion.is_some() {
+        if saved_ai_suggestion.is_some() || keep_regions {
             log::debug!("[MergeResolve] refresh: RESTORING ai_suggestion");
             self.ai_suggestion = saved_ai_suggestion;
             self.ai_recommendation = saved_ai_recommendation;
@@ -90,11 +182,126 @@ impl MergeResolveState {
             self.follow_ups = saved_follow_ups;
             self.scroll_center = saved_scroll_center;
         }
+        if keep_regions {
+            self.region_suggestions = saved_region_suggestions;
+            self.selected_region = saved_selected_region.min(self.conflict_regions.len() - 1);
+        }
+    }
+
+    /// Record the files in `previous` that are no longer conflicted, starting
+    /// over when a different merge begins.
+    fn track_progress(&mut self, previous: &[String]) {
+        let session = self.merge_state.as_ref().map(|s| {
+            format!(
+                "{} {} {}",
+                s.merge_type,
+                s.head_name,
+                s.merge_head.as_deref().unwrap_or_default()
+            )
+        });
+        if session != self.session {
+            self.session = session;
+            self.resolved_files.clear();
+            self.resolved_regions = 0;
+            self.undo_stack.clear();
+            self.redo_stack.clear();
+            return;
+        }
+        let conflicted = |path: &String| self.conflicted_files.iter().any(|f| &f.path == path);
+        let newly_resolved: Vec<String> = previous
+            .iter()
+            .filter(|p| !conflicted(p) && !self.resolved_files.contains(p))
+            .cloned()
+            .collect();
+        // A later rebase step can conflict on a file again
+        self.resolved_files.retain(|p| !conflicted(p));
+        self.resolved_files.extend(newly_resolved);
+    }
+
+    /// Remember `path` as it is now, before a resolution described by `label`
+    /// overwrites it. A new resolution makes undone ones unredoable.
+    pub fn record_resolution(&mut self, path: &str, label: String) {
+        let Ok(content) = std::fs::read(path) else {
+            return;
+        };
+        let entries = git::merge::unmerged_entries(path).unwrap_or_default();
+        if self.undo_stack.len() == UNDO_LIMIT {
+            self.undo_stack.remove(0);
+        }
+        self.undo_stack.push(ResolutionStep {
+            path: path.to_string(),
+            content,
+            entries,
+            label,
+        });
+        self.redo_stack.clear();
+    }
+
+    /// Select `path` if it's one of the conflicted files.
+    fn select_path(&mut self, path: &str) {
+        if let Some(index) = self.conflicted_files.iter().position(|f| f.path == path) {
+            self.selected_file = index;
+            self.load_selected_file();
+        }
+    }
+
+    /// The operation being resolved. Conflicts without one (e.g. from a stash
+    /// pop) are treated like a merge.
+    pub fn operation(&self) -> MergeType {
+        self.merge_state
+            .as_ref()
+            .map_or(MergeType::Merge, |s| s.merge_type)
+    }
+
+    /// Files resolved so far and the total for this merge.
+    pub fn progress(&self) -> (usize, usize) {
+        let done = self.resolved_files.len();
+        (done, done + self.conflicted_files.len())
+    }
+
+    /// Path of the selected conflicted file.
+    pub fn selected_path(&self) -> Option<String> {
+        self.conflicted_files
+            .get(self.selected_file)
+            .map(|f| f.path.clone())
+    }
+
+    /// AI suggestion for the selected conflict region, if one was requested.
+    pub fn selected_region_suggestion(&self) -> Option<&RegionSuggestion> {
+        self.region_suggestions
+            .get(self.selected_region)
+            .and_then(Option::as_ref)
+    }
+
+    /// Store the AI suggestion for region `index`.
+    pub fn set_region_suggestion(&mut self, index: usize, suggestion: RegionSuggestion) {
+        if self.region_suggestions.len() < self.conflict_regions.len() {
+            self.region_suggestions
+                .resize(self.conflict_regions.len(), None);
+        }
+        if let Some(slot) = self.region_suggestions.get_mut(index) {
+            *slot = Some(suggestion);
+        }
+    }
+
+    /// Reload the file after region `index` was resolved, keeping the suggestions
+    /// for the remaining regions and staying on the region that took its place.
+    fn reload_after_region_resolved(&mut self, index: usize) {
+        let mut suggestions = std::mem::take(&mut self.region_suggestions);
+        self.load_selected_file();
+        if index < suggestions.len() {
+            suggestions.remove(index);
+        }
+        if suggestions.len() == self.conflict_regions.len() {
+            self.region_suggestions = suggestions;
+        }
+        self.selected_region = index.min(self.conflict_regions.len().saturating_sub(1));
     }
 
     pub fn load_selected_file(&mut self) {
         self.conflict_regions.clear();
         self.raw_conflict_content = None;
+        self.binary_conflict = None;
         self.total_lines = 0;
         self.selected_region = 0;
         self.scroll_left = 0;
@@ -103,20 +310,27 @@ impl MergeResolveState {
         self.ai_suggestion = None;
         self.ai_recommendation = None;
         self.ai_resolved_content = None;
+        self.region_suggestions.clear();
         self.follow_ups.clear();
 
-        if let Some(file) = self.conflicted_files.get(self.selected_file) {
-            if let Ok(conflict_file) = git::merge::get_conflict_file(&file.path) {
-                self.raw_conflict_content = Some(conflict_file.raw_content);
-                self.conflict_regions = conflict_file.regions;
-                self.total_lines = conflict_file.total_lines;
-            }
+        let Some(file) = self.conflicted_files.get(self.selected_file) else {
+            return;
+        };
+        if let Some(sides) = git::binary::conflict_sides(&file.path) {
+            self.binary_conflict = Some(sides);
+        } else if let Ok(conflict_file) = git::merge::get_conflict_file(&file.path) {
+            self.raw_conflict_content = Some(conflict_file.raw_content);
+            self.conflict_regions = conflict_file.regions;
+            self.total_lines = conflict_file.total_lines;
         }
     }
 }
 
 // ─── Render ────────────────────────────────────────────────────
 
+/// Cells in the title bar's files-resolved bar.
+const PROGRESS_WIDTH: usize = 10;
+
 pub fn render(
     f: &mut Frame,
     area: Rect,
@@ -126,18 +340,18 @@ pub fn render(
 ) {
     // No conflicts state
     if state.conflicted_files.is_empty() {
-        render_no_conflicts(f, area, &state.merge_state);
+        render_no_conflicts(f, area, state);
         return;
     }
 
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(3), // Title bar
-            Constraint::Length(3), // File selector
-            Constraint::Min(8),    // Main three-panel area
-            Constraint::Length(5), // Follow-up suggestions
-            Constraint::Length(1), // Key hints
+            Constraint::Length(3),                        // Title bar
+            Constraint::Length(3),                        // File selector
+            Constraint::Min(8),                           // Main three-panel area
+            Constraint::Length(optional_height(area, 5)), // Follow-up suggestions
+            Constraint::Length(zen::chrome(1)),           // Key hints
         ])
         .split(area);
 
@@ -147,14 +361,24 @@ pub fn render(
     // ── File selector ──
     render_file_selector(f, chunks[1], state);
 
-    // ── Main three-panel layout ──
+    // ── Binary files can only be taken whole ──
+    if let Some(sides) = &state.binary_conflict {
+        render_binary_panel(f, chunks[2], state, sides);
+        render_key_hints(f, chunks[4], state, ai_loading, ai_available);
+        return;
+    }
+
+    // ── Main three-panel layout (only the focused panel when narrow) ──
+    let widths = if is_narrow(area) {
+        let mut widths = [0; 3];
+        widths[state.focused_panel.min(2)] = 100;
+        widths
+    } else {
+        [33, 34, 33] // Current (HEAD) | AI Suggestion | Incoming
+    };
     let panels = Layout::default()
         .direction(Direction::Horizontal)
-        .constraints([
-            Constraint::Percentage(33), // Current (HEAD)
-            Constraint::Percentage(34), // AI Suggestion
-            Constraint::Percentage(33), // Incoming
-        ])
+        .constraints(widths.map(Constraint::Percentage))
         .split(chunks[2]);
 
     render_current_panel(f, panels[0], state);
@@ -162,20 +386,25 @@ pub fn render(
     render_incoming_panel(f, panels[2], state);
 
     // ── Follow-up suggestions ──
-    render_follow_ups(f, chunks[3], state);
+    if chunks[3].height > 0 {
+        render_follow_ups(f, chunks[3], state);
+    }
 
     // ── Key hints ──
     render_key_hints(f, chunks[4], state, ai_loading, ai_available);
 }
 
-fn render_no_conflicts(f: &mut Frame, area: Rect, merge_state: &Option<git::MergeState>) {
-    let msg = if merge_state.is_some() {
-        "All conflicts have been resolved! Press Ctrl+F to continue merge."
-    } else {
-        "No merge conflicts detected. Press 'q' to go back."
+fn render_no_conflicts(f: &mut Frame, area: Rect, state: &MergeResolveState) {
+    let msg = match state.merge_state {
+        Some(ref s) => format!(
+            "All conflicts have been resolved! Press Ctrl+F to continue the {} ({}).",
+            s.merge_type,
+            s.merge_type.continue_command()
+        ),
+        None => "No merge conflicts detected. Press 'q' to go back.".to_string(),
     };
 
-    let content = Paragraph::new(vec![
+    let mut lines = vec![
         Line::from(""),
         Line::from(Span::styled(
             "  ✓ No Merge Conflicts",
@@ -188,8 +417,27 @@ fn render_no_conflicts(f: &mut Frame, area: Rect, merge_state: &Option<git::Merg
             format!("  {}", msg),
             Style::default().fg(Color::DarkGray),
         )),
-    ])
-    .block(
+    ];
+    if let Some(step) = state.undo_stack.last() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            format!("  Press u to undo: {} of {}", step.label, step.path),
+            Style::default().fg(Color::Yellow),
+        )));
+    }
+    if !state.resolved_files.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            format!(
+                "  Resolved this session: {} file(s), {} region(s) in zit",
+                state.resolved_files.len(),
+                state.resolved_regions
+            ),
+            Style::default().fg(Color::Green),
+        )));
+    }
+
+    let content = Paragraph::new(lines).block(
         Block::default()
             .title(Span::styled(
                 " Merge Resolve ",
@@ -211,10 +459,16 @@ fn render_title_bar(f: &mut Frame, area: Rect, state: &MergeResolveState) {
         .unwrap_or_default();
 
     let conflict_count = state.conflicted_files.len();
+    let (done, total) = state.progress();
+    let filled = (done * PROGRESS_WIDTH).checked_div(total).unwrap_or(0);
 
     let title = Paragraph::new(Line::from(vec![
         Span::styled(
-            "⚔ Merge Conflict Resolution",
+            format!(
+                "{} {} Conflict Resolution",
+                Icon::Conflict,
+                state.operation().title()
+            ),
             Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
         ),
         Span::styled(
@@ -229,9 +483,19 @@ fn render_title_bar(f: &mut Frame, area: Rect, state: &MergeResolveState) {
             Style::default().fg(Color::Cyan),
         ),
         Span::styled(
-            format!("│ {} conflict region(s)", state.conflict_regions.len()),
+            format!("│ {} conflict region(s) ", state.conflict_regions.len()),
             Style::default().fg(Color::Yellow),
         ),
+        Span::styled("│ ", Style::default().fg(Color::Yellow)),
+        Span::styled("█".repeat(filled), Style::default().fg(Color::Green)),
+        Span::styled(
+            "░".repeat(PROGRESS_WIDTH - filled),
+            Style::default().fg(Color::DarkGray),
+        ),
+        Span::styled(
+            format!(" {}/{} files resolved", done, total),
+            Style::default().fg(Color::Green),
+        ),
     ]))
     .block(
         Block::default()
@@ -242,6 +506,12 @@ fn render_title_bar(f: &mut Frame, area: Rect, state: &MergeResolveState) {
 }
 
 fn render_file_selector(f: &mut Frame, area: Rect, state: &MergeResolveState) {
+    let resolved = state.resolved_files.iter().flat_map(|path| {
+        vec![
+            Span::styled(format!(" ✓ {} ", path), Style::default().fg(Color::Green)),
+            Span::raw(" "),
+        ]
+    });
     let items: Vec<Span> = state
         .conflicted_files
         .iter()
@@ -260,6 +530,7 @@ fn render_file_selector(f: &mut Frame, area: Rect, state: &MergeResolveState) {
                 Span::raw(" "),
             ]
         })
+        .chain(resolved)
         .collect();
 
     let selector = Paragraph::new(Line::from(items)).block(
@@ -274,6 +545,50 @@ fn render_file_selector(f: &mut Frame, area: Rect, state: &MergeResolveState) {
     f.render_widget(selector, area);
 }
 
+fn render_binary_panel(
+    f: &mut Frame,
+    area: Rect,
+    state: &MergeResolveState,
+    sides: &git::binary::BinarySummary,
+) {
+    let op = state.operation();
+    let lines = vec![
+        Line::from(Span::styled(
+            "  Binary file — can't be merged line by line",
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled(
+                format!("  [a] {}: ", op.ours_label()),
+                Style::default().fg(Color::Green),
+            ),
+            Span::raw(git::binary::describe_side(sides.old.as_ref())),
+        ]),
+        Line::from(vec![
+            Span::styled(
+                format!("  [i] {}: ", op.theirs_label()),
+                Style::default().fg(Color::Cyan),
+            ),
+            Span::raw(git::binary::describe_side(sides.new.as_ref())),
+        ]),
+        Line::from(""),
+        Line::from(Span::styled(
+            "  Pick the version to keep for the whole file",
+            Style::default().fg(Color::DarkGray),
+        )),
+    ];
+    let panel = Paragraph::new(lines).block(
+        Block::default()
+            .title(" Binary Conflict ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Yellow)),
+    );
+    f.render_widget(panel, area);
+}
+
 fn render_current_panel(f: &mut Frame, area: Rect, state: &MergeResolveState) {
     let border_color = if state.focused_panel == 0 {
         Color::Green
@@ -328,7 +643,7 @@ fn render_current_panel(f: &mut Frame, area: Rect, state: &MergeResolveState) {
         .block(
             Block::default()
                 .title(Span::styled(
-                    " Current (HEAD) ",
+                    format!(" {} ", state.operation().ours_label()),
                     Style::default()
                         .fg(Color::Green)
                         .add_modifier(Modifier::BOLD),
@@ -338,7 +653,8 @@ fn render_current_panel(f: &mut Frame, area: Rect, state: &MergeResolveState) {
         )
         .scroll((state.scroll_left, 0))
         .wrap(Wrap { trim: false });
-    f.render_widget(panel, area);
+    f.render_widget(&panel, area);
+    render_paragraph_scrollbar(f, area, &panel, state.scroll_left);
 }
 
 fn render_incoming_panel(f: &mut Frame, area: Rect, state: &MergeResolveState) {
@@ -395,7 +711,7 @@ fn render_incoming_panel(f: &mut Frame, area: Rect, state: &MergeResolveState) {
         .block(
             Block::default()
                 .title(Span::styled(
-                    " Incoming ",
+                    format!(" {} ", state.operation().theirs_label()),
                     Style::default()
                         .fg(Color::Cyan)
                         .add_modifier(Modifier::BOLD),
@@ -405,7 +721,8 @@ fn render_incoming_panel(f: &mut Frame, area: Rect, state: &MergeResolveState) {
         )
         .scroll((state.scroll_right, 0))
         .wrap(Wrap { trim: false });
-    f.render_widget(panel, area);
+    f.render_widget(&panel, area);
+    render_paragraph_scrollbar(f, area, &panel, state.scroll_right);
 }
 
 fn render_ai_panel(
@@ -425,7 +742,7 @@ fn render_ai_panel(
         vec![
             Line::from(""),
             Line::from(Span::styled(
-                "  ⏳ AI analyzing conflict...",
+                format!("  {} AI analyzing conflict...", Icon::Loading),
                 Style::default()
                     .fg(Color::Yellow)
                     .add_modifier(Modifier::BOLD),
@@ -440,53 +757,25 @@ fn render_ai_panel(
                 Style::default().fg(Color::DarkGray),
             )),
         ]
-    } else if let Some(ref suggestion) = state.ai_suggestion {
-        // Parse and display the AI suggestion
-        let mut display_lines = Vec::new();
-
-        // Show recommendation badge
-        if let Some(ref rec) = state.ai_recommendation {
-            let (badge_text, badge_color) = match rec.as_str() {
-                s if s.contains("CURRENT") => ("✓ ACCEPT CURRENT", Color::Green),
-                s if s.contains("INCOMING") => ("→ ACCEPT INCOMING", Color::Cyan),
-                s if s.contains("BOTH") || s.contains("MERGE") => ("⚡ MERGE BOTH", Color::Yellow),
-                _ => ("💡 SUGGESTION", Color::Magenta),
-            };
-            display_lines.push(Line::from(Span::styled(
-                format!("  {}", badge_text),
-                Style::default()
-                    .fg(badge_color)
-                    .add_modifier(Modifier::BOLD),
-            )));
-            display_lines.push(Line::from(""));
-        }
-
-        // Show the explanation
-        for line in suggestion.lines().take(20) {
-            let style = if line.starts_with("RECOMMENDATION:")
-                || line.starts_with("RECOMMENDED:")
-                || line.starts_with("EXPLANATION:")
-                || line.starts_with("FOLLOW-UP:")
-                || line.starts_with("COMMANDS:")
-                || line.starts_with("RESOLVED CONTENT:")
-                || line.starts_with("CURRENT CHANGES")
-                || line.starts_with("INCOMING CHANGES")
-                || line.starts_with("WARNINGS:")
-            {
-                Style::default()
-                    .fg(Color::Yellow)
-                    .add_modifier(Modifier::BOLD)
-            } else if line.starts_with("```") {
-                Style::default().fg(Color::DarkGray)
-            } else if line.starts_with("- ") {
-                Style::default().fg(Color::White)
-            } else {
-                Style::default().fg(Color::Gray)
-            };
-            display_lines.push(Line::from(Span::styled(format!("  {}", line), style)));
-        }
-
+    } else if let Some(region) = state.selected_region_suggestion() {
+        let mut display_lines = vec![
+            Line::from(Span::styled(
+                format!(
+                    "  Region {} of {}",
+                    state.selected_region + 1,
+                    state.conflict_regions.len()
+                ),
+                Style::default().fg(Color::DarkGray),
+            )),
+            Line::from(""),
+        ];
+        display_lines.extend(suggestion_lines(
+            &region.response,
+            region.recommendation.as_deref(),
+        ));
         display_lines
+    } else if let Some(ref suggestion) = state.ai_suggestion {
+        suggestion_lines(suggestion, state.ai_recommendation.as_deref())
     } else if !ai_available {
         vec![
             Line::from(""),
@@ -503,18 +792,18 @@ fn render_ai_panel(
         vec![
             Line::from(""),
             Line::from(Span::styled(
-                "  🤖 AI Ready",
+                format!("  {} AI Ready", Icon::Ai),
                 Style::default()
                     .fg(Color::Magenta)
                     .add_modifier(Modifier::BOLD),
             )),
             Line::from(""),
             Line::from(Span::styled(
-                "  Press Ctrl+G to get AI suggestion",
+                "  Press g for this region, or Ctrl+G",
                 Style::default().fg(Color::DarkGray),
             )),
             Line::from(Span::styled(
-                "  for resolving this conflict",
+                "  to resolve the whole file",
                 Style::default().fg(Color::DarkGray),
             )),
         ]
@@ -524,7 +813,7 @@ fn render_ai_panel(
         .block(
             Block::default()
                 .title(Span::styled(
-                    " 🤖 AI Suggestion ",
+                    format!(" {} AI Suggestion ", Icon::Ai),
                     Style::default()
                         .fg(Color::Magenta)
                         .add_modifier(Modifier::BOLD),
@@ -534,7 +823,59 @@ fn render_ai_panel(
         )
         .scroll((state.scroll_center, 0))
         .wrap(Wrap { trim: false });
-    f.render_widget(panel, area);
+    f.render_widget(&panel, area);
+    render_paragraph_scrollbar(f, area, &panel, state.scroll_center);
+}
+
+/// Lines for an AI resolution: recommendation badge, then the response text.
+fn suggestion_lines<'a>(suggestion: &'a str, recommendation: Option<&str>) -> Vec<Line<'a>> {
+    let mut display_lines = Vec::new();
+
+    // Show recommendation badge
+    if let Some(rec) = recommendation {
+        let (badge_text, badge_color) = match rec {
+            s if s.contains("CURRENT") => ("✓ ACCEPT CURRENT".to_string(), Color::Green),
+            s if s.contains("INCOMING") => ("→ ACCEPT INCOMING".to_string(), Color::Cyan),
+            s if s.contains("BOTH") || s.contains("MERGE") => {
+                (format!("{} MERGE BOTH", Icon::Bolt), Color::Yellow)
+            }
+            _ => (format!("{} SUGGESTION", Icon::Idea), Color::Magenta),
+        };
+        display_lines.push(Line::from(Span::styled(
+            format!("  {}", badge_text),
+            Style::default()
+                .fg(badge_color)
+                .add_modifier(Modifier::BOLD),
+        )));
+        display_lines.push(Line::from(""));
+    }
+
+    // Show the explanation
+    for line in suggestion.lines().take(20) {
+        let style = if line.starts_with("RECOMMENDATION:")
+            || line.starts_with("RECOMMENDED:")
+            || line.starts_with("EXPLANATION:")
+            || line.starts_with("FOLLOW-UP:")
+            || line.starts_with("COMMANDS:")
+            || line.starts_with("RESOLVED CONTENT:")
+            || line.starts_with("CURRENT CHANGES")
+            || line.starts_with("INCOMING CHANGES")
+            || line.starts_with("WARNINGS:")
+        {
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD)
+        } else if line.starts_with("```") {
+            Style::default().fg(Color::DarkGray)
+        } else if line.starts_with("- ") {
+            Style::default().fg(Color::White)
+        } else {
+            Style::default().fg(Color::Gray)
+        };
+        display_lines.push(Line::from(Span::styled(format!("  {}", line), style)));
+    }
+
+    display_lines
 }
 
 fn render_follow_ups(f: &mut Frame, area: Rect, state: &MergeResolveState) {
@@ -604,26 +945,48 @@ fn render_key_hints(
     ai_loading: bool,
     _ai_available: bool,
 ) {
+    let (ours, theirs) = match state.operation() {
+        MergeType::Rebase => ("Upstream", "Your Commit"),
+        _ => ("Current", "Incoming"),
+    };
+    let scope = if state.binary_conflict.is_some() {
+        "File "
+    } else {
+        ""
+    };
     let mut hints = vec![
         Span::styled(" [a]", Style::default().fg(Color::Green)),
-        Span::raw(" Accept Current "),
+        Span::raw(format!(" Accept {} {}", ours, scope)),
         Span::styled("[i]", Style::default().fg(Color::Cyan)),
-        Span::raw(" Accept Incoming "),
+        Span::raw(format!(" Accept {} {}", theirs, scope)),
     ];
 
-    if state.ai_resolved_content.is_some() {
+    if state
+        .selected_region_suggestion()
+        .is_some_and(|s| s.choice().is_some())
+    {
+        hints.push(Span::styled("[m]", Style::default().fg(Color::Magenta)));
+        hints.push(Span::raw(" Apply AI to Region "));
+    } else if state.ai_resolved_content.is_some() {
         hints.push(Span::styled("[m]", Style::default().fg(Color::Magenta)));
         hints.push(Span::raw(" Apply AI "));
     }
 
     if ai_loading {
         hints.push(Span::styled(
-            "⏳ AI working... ",
+            format!("{} AI working... ", Icon::Loading),
             Style::default().fg(Color::Yellow),
         ));
     } else {
+        hints.push(Span::styled("[g]", Style::default().fg(Color::Magenta)));
+        hints.push(Span::raw(" AI Region "));
         hints.push(Span::styled("[G]", Style::default().fg(Color::Magenta)));
-        hints.push(Span::raw(" AI Suggest "));
+        hints.push(Span::raw(" AI File "));
+    }
+
+    if !state.undo_stack.is_empty() {
+        hints.push(Span::styled("[u]", Style::default().fg(Color::Yellow)));
+        hints.push(Span::raw(" Undo "));
     }
 
     hints.extend([
@@ -651,6 +1014,19 @@ pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()
     let state = &mut app.merge_resolve_state;
 
     match key.code {
+        // Take one side for the whole file; the only way for binary files
+        KeyCode::Char('A') => take_side(app, git::merge::Side::Ours),
+        KeyCode::Char('I') => take_side(app, git::merge::Side::Theirs),
+        KeyCode::Char('a')
+            if !key.modifiers.contains(KeyModifiers::CONTROL)
+                && state.binary_conflict.is_some() =>
+        {
+            take_side(app, git::merge::Side::Ours)
+        }
+        KeyCode::Char('i') if state.binary_conflict.is_some() => {
+            take_side(app, git::merge::Side::Theirs)
+        }
+
         // Accept current changes for the selected region
         KeyCode::Char('a')
             if !key.modifiers.contains(KeyModifiers::CONTROL)
@@ -664,49 +1040,43 @@ pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()
             resolve_current_region(app, "incoming")?;
         }
 
-        // Apply AI-suggested resolution
-        KeyCode::Char('m') if app.merge_resolve_state.ai_resolved_content.is_some() => {
+        // Apply the AI suggestion for the selected region
+        KeyCode::Char('m')
+            if !key.modifiers.contains(KeyModifiers::CONTROL)
+                && state.selected_region_suggestion().is_some() =>
+        {
+            apply_region_suggestion(app)?;
+        }
+
+        // Apply AI-suggested resolution for the whole file
+        KeyCode::Char('m')
+            if !key.modifiers.contains(KeyModifiers::CONTROL)
+                && app.merge_resolve_state.ai_resolved_content.is_some() =>
+        {
             let path = app
                 .merge_resolve_state
                 .conflicted_files
                 .get(app.merge_resolve_state.selected_file)
                 .map(|f| f.path.clone());
-            if let Some(path) = path {
-                if let Some(ref content) = app.merge_resolve_state.ai_resolved_content.clone() {
-                    match git::merge::resolve_file(&path, content) {
-                        Ok(()) => {
-                            app.set_status(format!("✓ Applied AI resolution to {}", path));
-                            app.merge_resolve_state.refresh();
-                            // Show follow-up
-                            if app.merge_resolve_state.conflicted_files.is_empty() {
-                                app.popup = Popup::FollowUp {
-                                    title: "🎉 All Conflicts Resolved!".to_string(),
-                                    context: "All merge conflicts have been resolved.".to_string(),
-                                    suggestions: vec![
-                                        FollowUpItem {
-                                            label: "Continue merge".to_string(),
-                                            description: "Finalize the merge operation".to_string(),
-                                            action: FollowUpAction::ContinueMerge,
-                                        },
-                                        FollowUpItem {
-                                            label: "Review changes".to_string(),
-                                            description: "Go to staging view to review".to_string(),
-                                            action: FollowUpAction::SwitchToView(View::Staging),
-                                        },
-                                        FollowUpItem {
-                                            label: "Commit now".to_string(),
-                                            description: "Go to commit view".to_string(),
-                                            action: FollowUpAction::CommitNow,
-                                        },
-                                    ],
-                                    selected: 0,
-                                };
-                            }
-                        }
-                        Err(e) => {
-                            app.set_status(format!("Error: {}", e));
+            if let Some(path) = path
+                && let Some(ref content) = app.merge_resolve_state.ai_resolved_content.clone()
+            {
+                app.merge_resolve_state
+                    .record_resolution(&path, "applied the AI resolution".to_string());
+                match git::merge::resolve_file(&path, content) {
+                    Ok(()) => {
+                        let state = &mut app.merge_resolve_state;
+                        state.resolved_regions += state.conflict_regions.len();
+                        state.refresh();
+                        app.set_status(resolved_file_status(&app.merge_resolve_state, &path));
+                        // Show follow-up
+                        if app.merge_resolve_state.conflicted_files.is_empty() {
+                            app.popup = all_resolved_popup(app.merge_resolve_state.operation());
                         }
                     }
+                    Err(e) => {
+                        app.set_status(format!("Error: {}", e));
+                    }
                 }
             }
         }
@@ -725,6 +1095,11 @@ pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()
                 app.set_status("No conflict content to analyze");
             }
         }
+        // AI suggest for the selected conflict region only
+        KeyCode::Char('g') if !app.merge_resolve_state.conflict_regions.is_empty() => {
+            let index = app.merge_resolve_state.selected_region;
+            app.start_ai_region_resolve(index);
+        }
         KeyCode::Char('G') => {
             // Mac-friendly alternative for Ctrl+G
             let state = &app.merge_resolve_state;
@@ -742,30 +1117,18 @@ pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()
 
         // Abort merge (Ctrl+A or !)
         KeyCode::Char('a') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-            app.popup = Popup::Confirm {
-                title: "⚠ Abort Merge".to_string(),
-                message: "This will discard ALL merge progress. Are you sure? (y/n)".to_string(),
-                on_confirm: crate::app::ConfirmAction::AbortMerge,
-            };
+            app.popup = confirm_abort(app.merge_resolve_state.operation());
         }
         KeyCode::Char('!') => {
             // Mac-friendly alternative for Ctrl+A (abort)
-            app.popup = Popup::Confirm {
-                title: "⚠ Abort Merge".to_string(),
-                message: "This will discard ALL merge progress. Are you sure? (y/n)".to_string(),
-                on_confirm: crate::app::ConfirmAction::AbortMerge,
-            };
+            app.popup = confirm_abort(app.merge_resolve_state.operation());
         }
 
         // Continue merge (Ctrl+F or F)
         KeyCode::Char('f') if key.modifiers.contains(KeyModifiers::CONTROL) => {
             let state = &app.merge_resolve_state;
             if state.conflicted_files.is_empty() || state.conflict_regions.is_empty() {
-                app.popup = Popup::Confirm {
-                    title: "Continue Merge".to_string(),
-                    message: "Finalize the merge? (y/n)".to_string(),
-                    on_confirm: crate::app::ConfirmAction::ContinueMerge,
-                };
+                app.popup = confirm_continue(state.operation());
             } else {
                 app.set_status(format!(
                     "Cannot continue — {} conflicts remaining",
@@ -777,11 +1140,7 @@ pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()
             // Mac-friendly alternative for Ctrl+F (continue/finalize)
             let state = &app.merge_resolve_state;
             if state.conflicted_files.is_empty() || state.conflict_regions.is_empty() {
-                app.popup = Popup::Confirm {
-                    title: "Continue Merge".to_string(),
-                    message: "Finalize the merge? (y/n)".to_string(),
-                    on_confirm: crate::app::ConfirmAction::ContinueMerge,
-                };
+                app.popup = confirm_continue(state.operation());
             } else {
                 app.set_status(format!(
                     "Cannot continue — {} conflicts remaining",
@@ -792,11 +1151,11 @@ pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()
 
         // Merge strategy (Ctrl+M or S)
         KeyCode::Char('m') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-            app.start_ai_merge_strategy(None);
+            app.start_ai_merge_strategy(None, None);
         }
         KeyCode::Char('S') => {
             // Mac-friendly alternative for Ctrl+M (strategy)
-            app.start_ai_merge_strategy(None);
+            app.start_ai_merge_strategy(None, None);
         }
 
         // Navigate conflict regions
@@ -854,6 +1213,10 @@ pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()
             }
         }
 
+        // Undo / redo the last resolution
+        KeyCode::Char('u') => undo_resolution(app),
+        KeyCode::Char('U') => redo_resolution(app),
+
         // Tab to cycle panels
         KeyCode::Tab => {
             let state = &mut app.merge_resolve_state;
@@ -888,6 +1251,194 @@ pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()
     Ok(())
 }
 
+/// Confirmation before continuing `op`, naming the command it runs.
+pub fn confirm_continue(op: MergeType) -> Popup {
+    Popup::Confirm {
+        title: format!("Continue {}", op.title()),
+        message: format!("{} (y/n)", op.continue_prompt()),
+        on_confirm: crate::app::ConfirmAction::ContinueMerge,
+    }
+}
+
+/// Confirmation before aborting `op`, saying what is thrown away.
+pub fn confirm_abort(op: MergeType) -> Popup {
+    Popup::Confirm {
+        title: format!("{} Abort {}", Icon::Warning, op.title()),
+        message: format!("{} (y/n)", op.abort_prompt()),
+        on_confirm: crate::app::ConfirmAction::AbortMerge,
+    }
+}
+
+/// Next steps once the last conflicted file of `op` is resolved.
+fn all_resolved_popup(op: MergeType) -> Popup {
+    let mut suggestions = vec![
+        FollowUpItem {
+            label: format!("Continue {}", op),
+            description: format!("Run {} to finish this step", op.continue_command()),
+            action: FollowUpAction::ContinueMerge,
+        },
+        FollowUpItem {
+            label: "Review changes".to_string(),
+            description: "Go to staging view to review".to_string(),
+            action: FollowUpAction::SwitchToView(View::Staging),
+        },
+    ];
+    // A rebase or pick has to go through --continue, not a plain commit
+    if op == MergeType::Merge {
+        suggestions.push(FollowUpItem {
+            label: "Commit now".to_string(),
+            description: "Go to commit view".to_string(),
+            action: FollowUpAction::CommitNow,
+        });
+    }
+    Popup::FollowUp {
+        title: format!("{} All Conflicts Resolved!", Icon::Celebrate),
+        context: format!("All {} conflicts have been resolved.", op),
+        suggestions,
+        selected: 0,
+    }
+}
+
+/// Status after `path` was resolved and staged, naming the file the view moved
+/// on to.
+pub fn resolved_file_status(state: &MergeResolveState, path: &str) -> String {
+    let (done, total) = state.progress();
+    match state.selected_path() {
+        Some(next) => format!(
+            "✓ {} resolved and staged ({}/{}) — next: {}",
+            path, done, total, next
+        ),
+        None => format!("✓ {} resolved and staged ({}/{})", path, done, total),
+    }
+}
+
+/// Resolve the selected file as a whole with one side's version.
+fn take_side(app: &mut crate::app::App, side: git::merge::Side) {
+    let state = &mut app.merge_resolve_state;
+    let Some(path) = state.selected_path() else {
+        return;
+    };
+    let op = state.operation();
+    let label = match side {
+        git::merge::Side::Ours => op.ours_label(),
+        git::merge::Side::Theirs => op.theirs_label(),
+    };
+    state.record_resolution(&path, format!("took {} for the whole file", label));
+    let regions = state.conflict_regions.len().max(1);
+    match git::merge::take_side(&path, side) {
+        Ok(()) => {
+            state.resolved_regions += regions;
+            state.refresh();
+            if app.merge_resolve_state.conflicted_files.is_empty() {
+                app.popup = all_resolved_popup(op);
+            }
+            let status = resolved_file_status(&app.merge_resolve_state, &path);
+            app.set_status(format!("{} — took {}", status, label));
+        }
+        Err(e) => {
+            state.undo_stack.pop();
+            app.set_status(format!("Error taking {}: {}", label, e));
+        }
+    }
+}
+
+/// Take back the last resolution: restore the file's previous content and,
+/// if it was staged since, its conflicted index entries.
+pub fn undo_resolution(app: &mut crate::app::App) {
+    let state = &mut app.merge_resolve_state;
+    let Some(step) = state.undo_stack.pop() else {
+        app.set_status("Nothing to undo");
+        return;
+    };
+    let staged = !step.entries.is_empty()
+        && git::merge::unmerged_entries(&step.path).is_ok_and(|e| e.is_empty());
+    let current = std::fs::read(&step.path).unwrap_or_default();
+
+    let result = std::fs::write(&step.path, &step.content)
+        .map_err(anyhow::Error::from)
+        .and_then(|()| {
+            if staged {
+                git::merge::restore_unmerged(&step.path, &step.entries)
+            } else {
+                Ok(())
+            }
+        });
+    if let Err(e) = result {
+        state.undo_stack.push(step);
+        app.set_status(format!("Error undoing: {}", e));
+        return;
+    }
+
+    state.resolved_regions = state.resolved_regions.saturating_sub(1);
+    state.refresh();
+    state.select_path(&step.path);
+    app.set_status(format!(
+        "↶ Undid: {} of {} (U to redo)",
+        step.label, step.path
+    ));
+    app.merge_resolve_state.redo_stack.push(ResolutionStep {
+        content: current,
+        // Redo stages the file again if undo un-staged it
+        entries: if staged {
+            step.entries.clone()
+        } else {
+            String::new()
+        },
+        ..step
+    });
+}
+
+/// Make the last undone resolution again.
+pub fn redo_resolution(app: &mut crate::app::App) {
+    let state = &mut app.merge_resolve_state;
+    let Some(step) = state.redo_stack.pop() else {
+        app.set_status("Nothing to redo");
+        return;
+    };
+    let before = std::fs::read(&step.path).unwrap_or_default();
+    let entries = git::merge::unmerged_entries(&step.path).unwrap_or_default();
+
+    let result = std::fs::write(&step.path, &step.content)
+        .map_err(anyhow::Error::from)
+        .and_then(|()| {
+            if step.entries.is_empty() {
+                Ok(())
+            } else {
+                git::run_git(&["add", "--", &step.path]).map(|_| ())
+            }
+        });
+    if let Err(e) = result {
+        state.redo_stack.push(step);
+        app.set_status(format!("Error redoing: {}", e));
+        return;
+    }
+
+    state.resolved_regions += 1;
+    state.refresh();
+    state.select_path(&step.path);
+    app.set_status(format!("↷ Redid: {} of {}", step.label, step.path));
+    app.merge_resolve_state.undo_stack.push(ResolutionStep {
+        content: before,
+        entries,
+        ..step
+    });
+}
+
+/// Apply the AI suggestion for the selected conflict region.
+pub fn apply_region_suggestion(app: &mut crate::app::App) -> anyhow::Result<()> {
+    let Some(suggestion) = app.merge_resolve_state.selected_region_suggestion() else {
+        app.set_status("No AI suggestion for this region — press g to ask");
+        return Ok(());
+    };
+    match suggestion.choice() {
+        Some(choice) => resolve_current_region(app, &choice),
+        None => {
+            app.set_status("AI suggestion has no applicable resolution — resolve manually");
+            Ok(())
+        }
+    }
+}
+
 /// Resolve the currently selected conflict region with the given choice.
 fn resolve_current_region(app: &mut crate::app::App, choice: &str) -> anyhow::Result<()> {
     let state = &app.merge_resolve_state;
@@ -896,79 +1447,103 @@ fn resolve_current_region(app: &mut crate::app::App, choice: &str) -> anyhow::Re
         .get(state.selected_file)
         .map(|f| f.path.clone());
 
-    if let Some(path) = file_path {
-        if let Some(region) = state.conflict_regions.get(state.selected_region) {
-            let region = region.clone();
-            match git::merge::resolve_region(&path, &region, choice) {
-                Ok(new_content) => {
-                    // Write the resolved content
-                    match std::fs::write(&path, &new_content) {
-                        Ok(()) => {
-                            let label = if choice == "current" {
-                                "current"
-                            } else {
-                                "incoming"
-                            };
-                            app.set_status(format!(
-                                "✓ Accepted {} changes in region {} of {}",
-                                label,
-                                app.merge_resolve_state.selected_region + 1,
-                                path
-                            ));
-
-                            // Reload the file to check for remaining conflicts
-                            app.merge_resolve_state.load_selected_file();
-
-                            // If no more conflict regions in this file, stage it
-                            if app.merge_resolve_state.conflict_regions.is_empty() {
-                                let _ = git::run_git(&["add", &path]);
-                                app.set_status(format!(
-                                    "✓ All conflicts resolved in {} — file staged",
-                                    path
-                                ));
-                                app.merge_resolve_state.refresh();
-
-                                // If all conflicts resolved, show follow-up
-                                if app.merge_resolve_state.conflicted_files.is_empty() {
-                                    app.popup = Popup::FollowUp {
-                                        title: "🎉 All Conflicts Resolved!".to_string(),
-                                        context: "All merge conflicts have been resolved."
-                                            .to_string(),
-                                        suggestions: vec![
-                                            FollowUpItem {
-                                                label: "Continue merge".to_string(),
-                                                description: "Finalize the merge operation"
-                                                    .to_string(),
-                                                action: FollowUpAction::ContinueMerge,
-                                            },
-                                            FollowUpItem {
-                                                label: "Review changes".to_string(),
-                                                description: "Go to staging view to review"
-                                                    .to_string(),
-                                                action: FollowUpAction::SwitchToView(View::Staging),
-                                            },
-                                            FollowUpItem {
-                                                label: "Commit now".to_string(),
-                                                description: "Go to commit view".to_string(),
-                                                action: FollowUpAction::CommitNow,
-                                            },
-                                        ],
-                                        selected: 0,
-                                    };
-                                }
+    if let Some(path) = file_path
+        && let Some(region) = state.conflict_regions.get(state.selected_region)
+    {
+        let region = region.clone();
+        let index = state.selected_region;
+        let op = state.operation();
+        let label = match choice {
+            "current" => op.ours_label(),
+            "incoming" => op.theirs_label(),
+            _ => "AI-suggested",
+        };
+        match git::merge::resolve_region(&path, &region, choice) {
+            Ok(new_content) => {
+                app.merge_resolve_state
+                    .record_resolution(&path, format!("took {} in region {}", label, index + 1));
+                // Write the resolved content
+                match std::fs::write(&path, &new_content) {
+                    Ok(()) => {
+                        app.merge_resolve_state.resolved_regions += 1;
+                        app.set_status(format!(
+                            "✓ Took {} in region {} of {}",
+                            label,
+                            index + 1,
+                            path
+                        ));
+
+                        // Reload the file to check for remaining conflicts
+                        app.merge_resolve_state.reload_after_region_resolved(index);
+
+                        // If no more conflict regions in this file, stage it
+                        if app.merge_resolve_state.conflict_regions.is_empty() {
+                            let _ = git::run_git(&["add", &path]);
+                            app.merge_resolve_state.refresh();
+                            app.set_status(resolved_file_status(&app.merge_resolve_state, &path));
+
+                            // If all conflicts resolved, show follow-up
+                            if app.merge_resolve_state.conflicted_files.is_empty() {
+                                app.popup = all_resolved_popup(app.merge_resolve_state.operation());
                             }
                         }
-                        Err(e) => {
-                            app.set_status(format!("Error writing file: {}", e));
-                        }
                     }
-                }
-                Err(e) => {
-                    app.set_status(format!("Error resolving region: {}", e));
+                    Err(e) => {
+                        app.set_status(format!("Error writing file: {}", e));
+                    }
                 }
             }
+            Err(e) => {
+                app.set_status(format!("Error resolving region: {}", e));
+            }
         }
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn conflicted(paths: &[&str]) -> Vec<git::FileEntry> {
+        paths
+            .iter()
+            .map(|p| git::FileEntry {
+                status: git::FileStatus::Conflicted,
+                path: p.to_string(),
+                original_path: None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_track_progress() {
+        let mut state = MergeResolveState {
+            conflicted_files: conflicted(&["a.rs", "b.rs"]),
+            ..Default::default()
+        };
+        let previous = ["a.rs".to_string(), "b.rs".to_string(), "c.rs".to_string()];
+        state.track_progress(&previous);
+        assert_eq!(state.resolved_files, vec!["c.rs"]);
+        assert_eq!(state.progress(), (1, 3));
+
+        // Already counted files aren't added twice; a file that conflicts
+        // again goes back to the to-do side
+        state.conflicted_files = conflicted(&["b.rs", "c.rs"]);
+        state.track_progress(&["a.rs".to_string(), "b.rs".to_string()]);
+        assert_eq!(state.resolved_files, vec!["a.rs"]);
+        assert_eq!(state.progress(), (1, 3));
+
+        // A different merge starts over
+        state.resolved_regions = 4;
+        state.merge_state = Some(git::MergeState {
+            merge_type: git::merge::MergeType::Merge,
+            head_name: "main".to_string(),
+            merge_head: Some("feature".to_string()),
+        });
+        state.track_progress(&["a.rs".to_string(), "b.rs".to_string()]);
+        assert!(state.resolved_files.is_empty());
+        assert_eq!(state.resolved_regions, 0);
+    }
+}
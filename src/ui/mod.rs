@@ -1,17 +1,38 @@
 pub mod agent;
 pub mod ai_mentor;
 pub mod bisect;
+pub mod blame;
 pub mod branches;
 pub mod cherry_pick;
 pub mod commit;
 pub mod dashboard;
+pub mod datetime;
+pub mod editor;
 pub mod github;
+pub mod gitmoji;
 pub mod help;
+pub mod icons;
+pub mod identicon;
+pub mod image_preview;
+pub mod line_edit;
+pub mod markdown;
 pub mod merge_resolve;
+pub mod notify;
+pub mod outgoing;
+pub mod output_pane;
+pub mod pane_search;
+pub mod rebase;
 pub mod reflog;
+pub mod spellcheck;
 pub mod staging;
 pub mod stash;
+pub mod status_bar;
+pub mod tags;
+#[cfg(test)]
+pub mod testing;
+pub mod theme;
 pub mod time_travel;
 pub mod timeline;
 pub mod utils;
 pub mod workflow_builder;
+pub mod zen;
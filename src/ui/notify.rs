@@ -0,0 +1,43 @@
+//! Desktop notifications, sent through the platform's own tool and silently
+//! skipped when it isn't available.
+
+use std::process::{Command, Stdio};
+
+/// Show a desktop notification; never blocks and never fails.
+pub fn desktop(title: &str, body: &str) {
+    #[cfg(target_os = "macos")]
+    {
+        let script = format!(
+            "display notification {} with title {}",
+            applescript_string(body),
+            applescript_string(title)
+        );
+        let mut cmd = Command::new("osascript");
+        cmd.args(["-e", &script]);
+        run(cmd);
+    }
+    #[cfg(target_os = "linux")]
+    {
+        let mut cmd = Command::new("notify-send");
+        cmd.args(["--app-name=zit", title, body]);
+        run(cmd);
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "linux")))]
+    let _ = (title, body);
+}
+
+/// Run `cmd` off the UI thread, keeping its output out of the terminal.
+#[allow(dead_code)]
+fn run(mut cmd: Command) {
+    cmd.stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+    std::thread::spawn(move || {
+        let _ = cmd.status();
+    });
+}
+
+#[cfg(target_os = "macos")]
+fn applescript_string(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
@@ -0,0 +1,452 @@
+//! Outgoing / Incoming — the commits a push would publish and the commits a pull
+//! would bring in (as of the last fetch), with a diff preview of each, or the
+//! files they change between a branch and its upstream.
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap},
+};
+
+use crate::git;
+use crate::git::log::CommitEntry;
+use crate::ui::datetime;
+use crate::ui::output_pane::OutputJob;
+use crate::ui::pane_search::{self, PaneSearch};
+use crate::ui::utils::{
+    display_width, is_narrow, list_text_width, render_paragraph_scrollbar, render_scrollbar,
+    truncate_to_width,
+};
+use crate::ui::zen;
+
+/// Commits listed per side.
+const COMMIT_LIMIT: usize = 200;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Side {
+    #[default]
+    Outgoing,
+    Incoming,
+}
+
+#[derive(Default)]
+pub struct OutgoingState {
+    /// Local branch compared with its upstream; `None` for the current branch.
+    pub branch: Option<String>,
+    /// Upstream of the branch; `None` when it has none yet.
+    pub upstream: Option<String>,
+    pub outgoing: Vec<CommitEntry>,
+    pub incoming: Vec<CommitEntry>,
+    /// List changed files instead of commits (`d`).
+    pub show_files: bool,
+    pub outgoing_files: Vec<git::diff::FileChange>,
+    pub incoming_files: Vec<git::diff::FileChange>,
+    pub side: Side,
+    pub selected: usize,
+    pub list_state: ListState,
+    /// Diff preview of the highlighted commit.
+    pub diff_text: String,
+    pub diff_scroll: u16,
+    /// `/` search in the diff preview.
+    pub diff_search: Option<PaneSearch>,
+    /// Commit or file the preview belongs to, so periodic refreshes keep the
+    /// scroll position.
+    diff_hash: String,
+}
+
+impl OutgoingState {
+    pub fn refresh(&mut self) {
+        let tip = self.tip().to_string();
+        self.upstream = git::log::upstream_of(&tip);
+        let range = git::log::outgoing_range_of(&tip);
+        let range: Vec<&str> = range.iter().map(|s| s.as_str()).collect();
+        self.outgoing = git::log::get_commits(&range, COMMIT_LIMIT).unwrap_or_default();
+        (self.incoming, self.outgoing_files, self.incoming_files) = match &self.upstream {
+            Some(u) => (
+                git::log::get_commits(&[&format!("{}..{}", tip, u)], COMMIT_LIMIT)
+                    .unwrap_or_default(),
+                git::diff::range_file_changes(u, &tip).unwrap_or_default(),
+                git::diff::range_file_changes(&tip, u).unwrap_or_default(),
+            ),
+            None => Default::default(),
+        };
+        let len = self.len();
+        if self.selected >= len {
+            self.selected = len.saturating_sub(1);
+        }
+        self.list_state
+            .select(if len == 0 { None } else { Some(self.selected) });
+        self.load_diff();
+    }
+
+    /// The branch being compared, as a revision.
+    fn tip(&self) -> &str {
+        self.branch.as_deref().unwrap_or("HEAD")
+    }
+
+    fn commits(&self) -> &[CommitEntry] {
+        match self.side {
+            Side::Outgoing => &self.outgoing,
+            Side::Incoming => &self.incoming,
+        }
+    }
+
+    fn files(&self) -> &[git::diff::FileChange] {
+        match self.side {
+            Side::Outgoing => &self.outgoing_files,
+            Side::Incoming => &self.incoming_files,
+        }
+    }
+
+    /// Rows in the list: files or commits.
+    fn len(&self) -> usize {
+        if self.show_files {
+            self.files().len()
+        } else {
+            self.commits().len()
+        }
+    }
+
+    fn select(&mut self, index: usize) {
+        self.selected = index;
+        self.list_state.select(Some(index));
+        self.load_diff();
+    }
+
+    fn load_diff(&mut self) {
+        if self.show_files {
+            self.load_file_diff();
+            return;
+        }
+        let hash = self
+            .commits()
+            .get(self.selected)
+            .map(|c| c.hash.clone())
+            .unwrap_or_default();
+        if hash == self.diff_hash {
+            return;
+        }
+        self.diff_text = if hash.is_empty() {
+            String::new()
+        } else {
+            git::cherry_pick::commit_diff(&hash).unwrap_or_default()
+        };
+        self.diff_hash = hash;
+        self.diff_scroll = 0;
+    }
+
+    fn load_file_diff(&mut self) {
+        let path = self.files().get(self.selected).map(|f| f.path.clone());
+        let key = format!("{:?}:{}", self.side, path.as_deref().unwrap_or_default());
+        if key == self.diff_hash {
+            return;
+        }
+        let tip = self.tip().to_string();
+        self.diff_text = match (path, &self.upstream) {
+            (Some(path), Some(u)) => {
+                let (from, to) = match self.side {
+                    Side::Outgoing => (u.as_str(), tip.as_str()),
+                    Side::Incoming => (tip.as_str(), u.as_str()),
+                };
+                git::diff::range_file_diff(from, to, &path).unwrap_or_default()
+            }
+            _ => String::new(),
+        };
+        self.diff_hash = key;
+        self.diff_scroll = 0;
+    }
+}
+
+/// Compare `branch` (the current one when `None`) with its upstream: the files
+/// a push and a pull would change.
+pub fn open_upstream_compare(app: &mut crate::app::App, branch: Option<String>) {
+    let tip = branch.as_deref().unwrap_or("HEAD");
+    if git::log::upstream_of(tip).is_none() {
+        let name = branch.unwrap_or_else(|| "This branch".to_string());
+        app.set_status(format!(
+            "{} has no upstream yet — push it with -u first",
+            name
+        ));
+        return;
+    }
+    let state = &mut app.outgoing_state;
+    state.branch = branch;
+    state.show_files = true;
+    state.side = Side::Outgoing;
+    state.selected = 0;
+    app.open_view(crate::app::View::Outgoing);
+    app.outgoing_state.refresh();
+}
+
+pub fn render(f: &mut Frame, area: Rect, state: &mut OutgoingState) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),              // Summary
+            Constraint::Min(8),                 // List + diff
+            Constraint::Length(zen::chrome(3)), // Keys
+        ])
+        .split(area);
+
+    // Summary: both sides, the active one highlighted
+    let tab = |label: String, active: bool, color: Color| {
+        let style = if active {
+            Style::default()
+                .fg(Color::Black)
+                .bg(color)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(color)
+        };
+        Span::styled(label, style)
+    };
+    let branch = state
+        .branch
+        .as_deref()
+        .map_or(String::new(), |b| format!(" {}", b));
+    let target = match &state.upstream {
+        Some(u) => format!(" {} ⇄ {}", branch, u),
+        None => format!(
+            " {} no upstream — outgoing = commits not on any remote",
+            branch
+        ),
+    };
+    let (outgoing_count, incoming_count, unit) = if state.show_files {
+        (
+            state.outgoing_files.len(),
+            state.incoming_files.len(),
+            " files",
+        )
+    } else {
+        (state.outgoing.len(), state.incoming.len(), "")
+    };
+    let summary = Paragraph::new(Line::from(vec![
+        Span::raw(" "),
+        tab(
+            format!(" ↑ Outgoing ({}{}) ", outgoing_count, unit),
+            state.side == Side::Outgoing,
+            Color::Green,
+        ),
+        Span::raw(" "),
+        tab(
+            format!(" ↓ Incoming ({}{}) ", incoming_count, unit),
+            state.side == Side::Incoming,
+            Color::Cyan,
+        ),
+        Span::styled(target, Style::default().fg(Color::DarkGray)),
+    ]))
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan)),
+    );
+    f.render_widget(summary, chunks[0]);
+
+    let content = Layout::default()
+        .direction(if is_narrow(area) {
+            Direction::Vertical
+        } else {
+            Direction::Horizontal
+        })
+        .constraints([
+            Constraint::Percentage(45), // Commit list
+            Constraint::Percentage(55), // Diff preview
+        ])
+        .split(chunks[1]);
+
+    let row_width = list_text_width(content[0]);
+    let commits = match state.side {
+        Side::Outgoing => &state.outgoing,
+        Side::Incoming => &state.incoming,
+    };
+    let items: Vec<ListItem> = if state.show_files {
+        file_items(state.files(), row_width)
+    } else {
+        commit_items(commits, row_width)
+    };
+
+    let list_title = match (state.side, state.show_files) {
+        (Side::Outgoing, false) => " Will be pushed ",
+        (Side::Incoming, false) => " Will be pulled ",
+        (Side::Outgoing, true) => " Changed by a push ",
+        (Side::Incoming, true) => " Changed by a pull ",
+    };
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .title(Span::styled(list_title, Style::default().fg(Color::White)))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::DarkGray)),
+        )
+        .highlight_style(
+            Style::default()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol("▶ ");
+    f.render_stateful_widget(&list, content[0], &mut state.list_state);
+    render_scrollbar(f, content[0], list.len(), state.list_state.offset());
+
+    if state.len() == 0 {
+        let hint = match state.side {
+            Side::Outgoing => "Nothing to push — the remote has everything",
+            Side::Incoming => "Nothing incoming — press f to fetch",
+        };
+        let inner = content[0].inner(ratatui::layout::Margin::new(2, 1));
+        f.render_widget(
+            Paragraph::new(Span::styled(hint, Style::default().fg(Color::DarkGray))),
+            inner,
+        );
+    }
+
+    // Diff preview
+    let diff_lines: Vec<Line> = state
+        .diff_text
+        .lines()
+        .map(|line| {
+            let color = crate::ui::utils::diff_line_color(line);
+            Line::from(Span::styled(line, Style::default().fg(color)))
+        })
+        .enumerate()
+        .map(|(i, line)| pane_search::highlight(line, state.diff_search.as_ref(), i))
+        .collect();
+    let diff_title = if state.show_files {
+        match state.files().get(state.selected) {
+            Some(file) => format!(" {} ", file.path),
+            None => " Diff Preview ".to_string(),
+        }
+    } else {
+        match commits.get(state.selected) {
+            Some(c) => format!(" {} — {} ", c.short_hash, c.message),
+            None => " Diff Preview ".to_string(),
+        }
+    };
+    let diff = Paragraph::new(diff_lines)
+        .block(
+            Block::default()
+                .title(Span::styled(diff_title, Style::default().fg(Color::White)))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::DarkGray)),
+        )
+        .scroll((state.diff_scroll, 0))
+        .wrap(Wrap { trim: false });
+    f.render_widget(&diff, content[1]);
+    render_paragraph_scrollbar(f, content[1], &diff, state.diff_scroll);
+
+    let keys = Paragraph::new(Line::from(vec![
+        Span::styled(" [↑/↓]", Style::default().fg(Color::Cyan)),
+        Span::raw(" Navigate "),
+        Span::styled("[Tab]", Style::default().fg(Color::Cyan)),
+        Span::raw(" Outgoing/Incoming "),
+        Span::styled("[d]", Style::default().fg(Color::Cyan)),
+        Span::raw(if state.show_files {
+            " Commits "
+        } else {
+            " Files "
+        }),
+        Span::styled("[PgDn/Up]", Style::default().fg(Color::Cyan)),
+        Span::raw(" Scroll diff "),
+        Span::styled("[f]", Style::default().fg(Color::Cyan)),
+        Span::raw(" Fetch "),
+        Span::styled("[q]", Style::default().fg(Color::Red)),
+        Span::raw(" Dashboard"),
+    ]))
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::DarkGray)),
+    );
+    f.render_widget(keys, chunks[2]);
+}
+
+fn commit_items(commits: &[CommitEntry], row_width: usize) -> Vec<ListItem<'static>> {
+    commits
+        .iter()
+        .map(|c| {
+            let meta = format!(
+                "  {} · {}",
+                c.author,
+                datetime::format_iso(&c.date_iso, &c.date)
+            );
+            let used = display_width(&c.short_hash) + 1 + display_width(&meta);
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!("{} ", c.short_hash),
+                    Style::default().fg(Color::Yellow),
+                ),
+                Span::styled(
+                    truncate_to_width(&c.message, row_width.saturating_sub(used).max(12)),
+                    Style::default().fg(Color::White),
+                ),
+                Span::styled(meta, Style::default().fg(Color::DarkGray)),
+            ]))
+        })
+        .collect()
+}
+
+pub fn file_items(files: &[git::diff::FileChange], row_width: usize) -> Vec<ListItem<'static>> {
+    files
+        .iter()
+        .map(|file| {
+            let stat = match (file.added, file.removed) {
+                (Some(added), Some(removed)) => format!("  +{} −{}", added, removed),
+                _ => "  binary".to_string(),
+            };
+            let used = display_width(&stat);
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    truncate_to_width(&file.path, row_width.saturating_sub(used).max(12)),
+                    Style::default().fg(Color::White),
+                ),
+                Span::styled(stat, Style::default().fg(Color::DarkGray)),
+            ]))
+        })
+        .collect()
+}
+
+pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()> {
+    match key.code {
+        KeyCode::Esc => app.outgoing_state.diff_search = None,
+        KeyCode::Char('/') => app.open_pane_search(),
+        KeyCode::Char('n') => app.step_pane_search(true),
+        KeyCode::Char('N') => app.step_pane_search(false),
+        _ => {}
+    }
+    let state = &mut app.outgoing_state;
+    match key.code {
+        KeyCode::Up | KeyCode::Char('k') if state.selected > 0 => {
+            state.select(state.selected - 1);
+        }
+        KeyCode::Down | KeyCode::Char('j') if state.selected + 1 < state.len() => {
+            state.select(state.selected + 1);
+        }
+        KeyCode::Tab | KeyCode::BackTab => {
+            state.side = match state.side {
+                Side::Outgoing => Side::Incoming,
+                Side::Incoming => Side::Outgoing,
+            };
+            state.selected = 0;
+            state.refresh();
+        }
+        KeyCode::Char('d') => {
+            state.show_files = !state.show_files;
+            state.selected = 0;
+            state.refresh();
+        }
+        KeyCode::PageDown => state.diff_scroll = state.diff_scroll.saturating_add(20),
+        KeyCode::PageUp => state.diff_scroll = state.diff_scroll.saturating_sub(20),
+        KeyCode::Char('f') => {
+            app.start_output_job(OutputJob {
+                title: "fetch --all".to_string(),
+                commands: vec![git::RemoteOps::fetch_all_args()],
+                success_msg: "✓ Fetched all remotes".to_string(),
+                failure_msg: "Fetch failed".to_string(),
+            });
+        }
+        _ => {}
+    }
+    Ok(())
+}
@@ -0,0 +1,511 @@
+//! Output drawer — a collapsible pane at the bottom of the screen that streams
+//! stdout/stderr of long-running git commands (push, pull, fetch, gc, hooks)
+//! with their ANSI colors preserved.
+
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+};
+use std::sync::mpsc;
+
+use crate::event::AppEvent;
+use crate::git::runner::{StreamLine, run_git_streaming};
+
+/// Maximum number of lines kept in the drawer before the oldest are dropped.
+const MAX_LINES: usize = 2000;
+
+/// Height of the expanded drawer, including borders.
+const EXPANDED_HEIGHT: u16 = 12;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum PaneMode {
+    #[default]
+    Hidden,
+    Collapsed,
+    Expanded,
+}
+
+/// Messages sent from the command thread to the drawer.
+enum OutputEvent {
+    Line(StreamLine),
+    Finished(bool),
+}
+
+/// A git command queued for the drawer, with the status text to show afterwards.
+#[derive(Debug, Clone)]
+pub struct OutputJob {
+    pub title: String,
+    pub commands: Vec<Vec<String>>,
+    pub success_msg: String,
+    pub failure_msg: String,
+}
+
+#[derive(Default)]
+pub struct OutputPaneState {
+    pub mode: PaneMode,
+    pub title: String,
+    pub lines: Vec<StreamLine>,
+    pub running: bool,
+    pub success: Option<bool>,
+    /// Lines scrolled up from the tail (0 = follow output).
+    pub scroll_back: usize,
+    success_msg: String,
+    failure_msg: String,
+    receiver: Option<mpsc::Receiver<OutputEvent>>,
+    waker: Option<mpsc::Sender<AppEvent>>,
+}
+
+impl OutputPaneState {
+    /// Register the event-loop sender used to request a redraw when output arrives.
+    pub fn set_waker(&mut self, waker: mpsc::Sender<AppEvent>) {
+        self.waker = Some(waker);
+    }
+
+    /// The main loop's waker, for tasks that need the user mid-run.
+    pub fn waker(&self) -> Option<mpsc::Sender<AppEvent>> {
+        self.waker.clone()
+    }
+
+    /// Run the job's commands sequentially in the background, stopping at the first failure.
+    pub fn start(&mut self, job: OutputJob) -> bool {
+        let commands = job.commands;
+        self.start_task(job.title, job.success_msg, job.failure_msg, move |emit| {
+            for args in &commands {
+                emit(StreamLine {
+                    text: format!("\x1b[2m$ git {}\x1b[0m", args.join(" ")),
+                    is_stderr: false,
+                });
+                let refs: Vec<&str> = args.iter().map(|s| s.as_str()).collect();
+                match run_git_streaming(&refs, &mut *emit) {
+                    Ok(true) => {}
+                    Ok(false) => return false,
+                    Err(e) => {
+                        emit(StreamLine {
+                            text: e.to_string(),
+                            is_stderr: true,
+                        });
+                        return false;
+                    }
+                }
+            }
+            true
+        })
+    }
+
+    /// Run `task` in the background, showing the lines it emits; it returns
+    /// whether it succeeded. Returns `false` if something is already running.
+    pub fn start_task(
+        &mut self,
+        title: String,
+        success_msg: String,
+        failure_msg: String,
+        task: impl FnOnce(&mut dyn FnMut(StreamLine)) -> bool + Send + 'static,
+    ) -> bool {
+        if self.running {
+            return false;
+        }
+        self.title = title;
+        self.success_msg = success_msg;
+        self.failure_msg = failure_msg;
+        self.lines.clear();
+        self.running = true;
+        self.success = None;
+        self.scroll_back = 0;
+        if self.mode == PaneMode::Hidden {
+            self.mode = PaneMode::Expanded;
+        }
+
+        let (tx, rx) = mpsc::channel();
+        self.receiver = Some(rx);
+        let waker = self.waker.clone();
+
+        crate::jobs::spawn_tracked(self.title.clone(), move || {
+            let wake = || {
+                if let Some(ref w) = waker {
+                    let _ = w.send(AppEvent::Redraw);
+                }
+            };
+            let ok = task(&mut |line| {
+                let _ = tx.send(OutputEvent::Line(line));
+                wake();
+            });
+            let _ = tx.send(OutputEvent::Finished(ok));
+            wake();
+        });
+        true
+    }
+
+    /// Show the captured output of an already-finished command (e.g. commit hooks).
+    pub fn show_captured(&mut self, title: &str, output: &str, success: bool) {
+        if self.running {
+            return;
+        }
+        self.title = title.to_string();
+        self.lines = output
+            .lines()
+            .filter(|l| !l.is_empty())
+            .map(|l| StreamLine {
+                text: l.to_string(),
+                is_stderr: !success,
+            })
+            .collect();
+        self.success = Some(success);
+        self.scroll_back = 0;
+        if self.mode == PaneMode::Hidden {
+            self.mode = PaneMode::Collapsed;
+        }
+    }
+
+    /// Drain pending output. Returns the status message once the job finishes.
+    pub fn poll(&mut self) -> Option<String> {
+        let rx = self.receiver.take()?;
+        let mut finished = None;
+        loop {
+            match rx.try_recv() {
+                Ok(OutputEvent::Line(line)) => self.push_line(line),
+                Ok(OutputEvent::Finished(ok)) => {
+                    finished = Some(ok);
+                    break;
+                }
+                Err(mpsc::TryRecvError::Empty) => break,
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    finished = Some(false);
+                    break;
+                }
+            }
+        }
+
+        let Some(ok) = finished else {
+            self.receiver = Some(rx);
+            return None;
+        };
+        self.running = false;
+        self.success = Some(ok);
+        if ok {
+            Some(self.success_msg.clone())
+        } else {
+            let reason = self
+                .lines
+                .iter()
+                .rev()
+                .find(|l| l.is_stderr)
+                .map(|l| strip_ansi(&l.text))
+                .unwrap_or_default();
+            if reason.is_empty() {
+                Some(self.failure_msg.clone())
+            } else {
+                Some(format!("{}: {}", self.failure_msg, reason.trim()))
+            }
+        }
+    }
+
+    fn push_line(&mut self, line: StreamLine) {
+        self.lines.push(line);
+        if self.lines.len() > MAX_LINES {
+            let excess = self.lines.len() - MAX_LINES;
+            self.lines.drain(..excess);
+        }
+    }
+
+    /// Cycle Expanded → Collapsed → Hidden → Expanded.
+    pub fn toggle(&mut self) {
+        self.mode = match self.mode {
+            PaneMode::Expanded => PaneMode::Collapsed,
+            PaneMode::Collapsed => PaneMode::Hidden,
+            PaneMode::Hidden => PaneMode::Expanded,
+        };
+    }
+
+    pub fn scroll_up(&mut self) {
+        if self.scroll_back + 1 < self.lines.len() {
+            self.scroll_back += 1;
+        }
+    }
+
+    pub fn scroll_down(&mut self) {
+        self.scroll_back = self.scroll_back.saturating_sub(1);
+    }
+
+    /// Height the drawer needs in the current mode.
+    pub fn height(&self) -> u16 {
+        match self.mode {
+            PaneMode::Hidden => 0,
+            PaneMode::Collapsed => 1,
+            PaneMode::Expanded => EXPANDED_HEIGHT,
+        }
+    }
+}
+
+pub fn render(f: &mut Frame, area: Rect, state: &OutputPaneState) {
+    let (status_text, status_color) = if state.running {
+        ("running", Color::Yellow)
+    } else {
+        match state.success {
+            Some(true) => ("done", Color::Green),
+            Some(false) => ("failed", Color::Red),
+            None => ("idle", Color::DarkGray),
+        }
+    };
+
+    if state.mode == PaneMode::Collapsed {
+        let last = state
+            .lines
+            .last()
+            .map(|l| strip_ansi(&l.text))
+            .unwrap_or_default();
+        let bar = Paragraph::new(Line::from(vec![
+            Span::styled(
+                format!(" ▸ {} ", state.title),
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!("[{}] ", status_text),
+                Style::default().fg(status_color),
+            ),
+            Span::styled(last, Style::default().fg(Color::DarkGray)),
+            Span::styled("  Ctrl+O", Style::default().fg(Color::Yellow)),
+            Span::styled(" toggle", Style::default().fg(Color::DarkGray)),
+        ]));
+        f.render_widget(bar, area);
+        return;
+    }
+
+    let inner_height = area.height.saturating_sub(2) as usize;
+    let end = state.lines.len().saturating_sub(state.scroll_back);
+    let start = end.saturating_sub(inner_height);
+    let lines: Vec<Line> = state.lines[start..end]
+        .iter()
+        .map(|l| {
+            let base = if l.is_stderr {
+                Style::default().fg(Color::Gray)
+            } else {
+                Style::default().fg(Color::White)
+            };
+            ansi_to_line(&l.text, base)
+        })
+        .collect();
+
+    let scroll_hint = if state.scroll_back > 0 {
+        format!(" ↑{} ", state.scroll_back)
+    } else {
+        String::new()
+    };
+
+    let pane = Paragraph::new(lines).block(
+        Block::default()
+            .title(Span::styled(
+                format!(" ▾ {} [{}]{} ", state.title, status_text, scroll_hint),
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            ))
+            .title_bottom(Span::styled(
+                " Ctrl+O collapse  Ctrl+↑/↓ scroll ",
+                Style::default().fg(Color::DarkGray),
+            ))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(status_color)),
+    );
+    f.render_widget(pane, area);
+}
+
+/// Remove ANSI escape sequences, leaving only printable text.
+pub fn strip_ansi(text: &str) -> String {
+    ansi_to_line(text, Style::default())
+        .spans
+        .iter()
+        .map(|s| s.content.as_ref())
+        .collect()
+}
+
+/// Convert a line containing ANSI SGR escape codes into styled ratatui spans.
+/// Non-SGR control sequences (e.g. `ESC[K`) are dropped.
+pub fn ansi_to_line(text: &str, base: Style) -> Line<'static> {
+    let mut spans = Vec::new();
+    let mut style = base;
+    let mut current = String::new();
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '\x1b' {
+            current.push(c);
+            continue;
+        }
+        if chars.peek() != Some(&'[') {
+            continue;
+        }
+        chars.next();
+        let mut params = String::new();
+        let mut terminator = None;
+        for p in chars.by_ref() {
+            if p.is_ascii_alphabetic() || p == '~' {
+                terminator = Some(p);
+                break;
+            }
+            params.push(p);
+        }
+        if terminator != Some('m') {
+            continue;
+        }
+        if !current.is_empty() {
+            spans.push(Span::styled(std::mem::take(&mut current), style));
+        }
+        style = apply_sgr(style, base, &params);
+    }
+    if !current.is_empty() {
+        spans.push(Span::styled(current, style));
+    }
+    Line::from(spans)
+}
+
+/// Apply a semicolon-separated SGR parameter list to a style.
+fn apply_sgr(mut style: Style, base: Style, params: &str) -> Style {
+    let codes: Vec<u16> = if params.is_empty() {
+        vec![0]
+    } else {
+        params.split(';').map(|p| p.parse().unwrap_or(0)).collect()
+    };
+    let mut i = 0;
+    while i < codes.len() {
+        match codes[i] {
+            0 => style = base,
+            1 => style = style.add_modifier(Modifier::BOLD),
+            2 => style = style.add_modifier(Modifier::DIM),
+            3 => style = style.add_modifier(Modifier::ITALIC),
+            4 => style = style.add_modifier(Modifier::UNDERLINED),
+            7 => style = style.add_modifier(Modifier::REVERSED),
+            22 => style = style.remove_modifier(Modifier::BOLD | Modifier::DIM),
+            23 => style = style.remove_modifier(Modifier::ITALIC),
+            24 => style = style.remove_modifier(Modifier::UNDERLINED),
+            27 => style = style.remove_modifier(Modifier::REVERSED),
+            n @ 30..=37 => style = style.fg(basic_color(n - 30, false)),
+            n @ 90..=97 => style = style.fg(basic_color(n - 90, true)),
+            n @ 40..=47 => style = style.bg(basic_color(n - 40, false)),
+            n @ 100..=107 => style = style.bg(basic_color(n - 100, true)),
+            39 => style.fg = base.fg,
+            49 => style.bg = base.bg,
+            n @ (38 | 48) => {
+                let color = match codes.get(i + 1) {
+                    Some(5) => {
+                        let c = codes.get(i + 2).map(|&v| Color::Indexed(v as u8));
+                        i += 2;
+                        c
+                    }
+                    Some(2) => {
+                        let rgb = (codes.get(i + 2), codes.get(i + 3), codes.get(i + 4));
+                        i += 4;
+                        match rgb {
+                            (Some(&r), Some(&g), Some(&b)) => {
+                                Some(Color::Rgb(r as u8, g as u8, b as u8))
+                            }
+                            _ => None,
+                        }
+                    }
+                    _ => None,
+                };
+                if let Some(color) = color {
+                    style = if n == 38 {
+                        style.fg(color)
+                    } else {
+                        style.bg(color)
+                    };
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    style
+}
+
+fn basic_color(idx: u16, bright: bool) -> Color {
+    match (idx, bright) {
+        (0, false) => Color::Black,
+        (1, false) => Color::Red,
+        (2, false) => Color::Green,
+        (3, false) => Color::Yellow,
+        (4, false) => Color::Blue,
+        (5, false) => Color::Magenta,
+        (6, false) => Color::Cyan,
+        (7, false) => Color::Gray,
+        (0, true) => Color::DarkGray,
+        (1, true) => Color::LightRed,
+        (2, true) => Color::LightGreen,
+        (3, true) => Color::LightYellow,
+        (4, true) => Color::LightBlue,
+        (5, true) => Color::LightMagenta,
+        (6, true) => Color::LightCyan,
+        _ => Color::White,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ansi_plain_text() {
+        let line = ansi_to_line("hello", Style::default());
+        assert_eq!(line.spans.len(), 1);
+        assert_eq!(line.spans[0].content, "hello");
+    }
+
+    #[test]
+    fn test_ansi_colors_split_spans() {
+        let line = ansi_to_line("\x1b[32m+added\x1b[m rest", Style::default());
+        assert_eq!(line.spans.len(), 2);
+        assert_eq!(line.spans[0].content, "+added");
+        assert_eq!(line.spans[0].style.fg, Some(Color::Green));
+        assert_eq!(line.spans[1].content, " rest");
+        assert_eq!(line.spans[1].style.fg, None);
+    }
+
+    #[test]
+    fn test_ansi_bold_and_256_color() {
+        let line = ansi_to_line("\x1b[1;38;5;208mwarn", Style::default());
+        let style = line.spans[0].style;
+        assert_eq!(style.fg, Some(Color::Indexed(208)));
+        assert!(style.add_modifier.contains(Modifier::BOLD));
+    }
+
+    #[test]
+    fn test_ansi_drops_non_sgr_sequences() {
+        assert_eq!(strip_ansi("remote: done\x1b[K"), "remote: done");
+    }
+
+    #[test]
+    fn test_poll_reports_failure_reason() {
+        let (tx, rx) = mpsc::channel();
+        let mut pane = OutputPaneState {
+            running: true,
+            failure_msg: "Push failed".to_string(),
+            receiver: Some(rx),
+            ..OutputPaneState::default()
+        };
+        tx.send(OutputEvent::Line(StreamLine {
+            text: "\x1b[31mrejected\x1b[m".to_string(),
+            is_stderr: true,
+        }))
+        .unwrap();
+        tx.send(OutputEvent::Finished(false)).unwrap();
+        assert_eq!(pane.poll(), Some("Push failed: rejected".to_string()));
+        assert!(!pane.running);
+        assert_eq!(pane.success, Some(false));
+    }
+
+    #[test]
+    fn test_toggle_cycles_modes() {
+        let mut pane = OutputPaneState::default();
+        pane.toggle();
+        assert_eq!(pane.mode, PaneMode::Expanded);
+        pane.toggle();
+        assert_eq!(pane.mode, PaneMode::Collapsed);
+        pane.toggle();
+        assert_eq!(pane.mode, PaneMode::Hidden);
+    }
+}
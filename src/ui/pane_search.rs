@@ -0,0 +1,203 @@
+//! `/` search inside a scrolling pane (AI answers, diffs): matches are
+//! highlighted and `n` / `N` scroll from one matching line to the next.
+//! Matching is smart-case — case-insensitive unless the query has a capital.
+
+use ratatui::{
+    style::{Color, Style},
+    text::{Line, Span},
+};
+
+/// Lines kept above a match when scrolling to it.
+const CONTEXT: usize = 2;
+
+/// An active search in a pane.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PaneSearch {
+    pub query: String,
+    /// Line of the current match (before the first jump, where to start).
+    pub line: usize,
+}
+
+impl PaneSearch {
+    /// A search for `query` starting at line `from`, usually the pane's scroll.
+    pub fn new(query: &str, from: usize) -> Self {
+        Self {
+            query: query.to_string(),
+            line: from,
+        }
+    }
+
+    /// Go to the first match at or after the starting line, wrapping around.
+    /// Returns the message for the status bar, an error when nothing matches.
+    pub fn first<'a>(
+        &mut self,
+        lines: impl IntoIterator<Item = &'a str>,
+    ) -> Result<String, String> {
+        let from = self.line;
+        self.go(lines, |matches| {
+            matches.iter().position(|&l| l >= from).unwrap_or(0)
+        })
+    }
+
+    /// Go to the next (or, going back, previous) match, wrapping around.
+    pub fn step<'a>(
+        &mut self,
+        lines: impl IntoIterator<Item = &'a str>,
+        forward: bool,
+    ) -> Result<String, String> {
+        let line = self.line;
+        self.go(lines, |matches| {
+            if forward {
+                matches.iter().position(|&l| l > line).unwrap_or(0)
+            } else {
+                matches
+                    .iter()
+                    .rposition(|&l| l < line)
+                    .unwrap_or(matches.len() - 1)
+            }
+        })
+    }
+
+    fn go<'a>(
+        &mut self,
+        lines: impl IntoIterator<Item = &'a str>,
+        pick: impl Fn(&[usize]) -> usize,
+    ) -> Result<String, String> {
+        let matches: Vec<usize> = lines
+            .into_iter()
+            .enumerate()
+            .filter(|(_, text)| !find(text, &self.query).is_empty())
+            .map(|(i, _)| i)
+            .collect();
+        if matches.is_empty() {
+            return Err(format!("No matches for '{}'", self.query));
+        }
+        let index = pick(&matches);
+        self.line = matches[index];
+        Ok(format!(
+            "Match {} of {} for '{}' (n/N next/previous)",
+            index + 1,
+            matches.len(),
+            self.query
+        ))
+    }
+
+    /// Scroll offset that shows the current match with a little context above.
+    pub fn scroll(&self) -> u16 {
+        self.line.saturating_sub(CONTEXT) as u16
+    }
+}
+
+/// Byte ranges of `query` in `text`, smart-case.
+pub fn find(text: &str, query: &str) -> Vec<(usize, usize)> {
+    if query.is_empty() {
+        return Vec::new();
+    }
+    let ignore_case = !query.chars().any(char::is_uppercase);
+    let mut ranges = Vec::new();
+    let mut i = 0;
+    while i < text.len() {
+        let matched = text.get(i..i + query.len()).is_some_and(|s| {
+            if ignore_case {
+                s.eq_ignore_ascii_case(query)
+            } else {
+                s == query
+            }
+        });
+        if matched {
+            ranges.push((i, i + query.len()));
+            i += query.len();
+        } else {
+            i += text[i..].chars().next().map_or(1, char::len_utf8);
+        }
+    }
+    ranges
+}
+
+/// `line` with the matches of `search` highlighted; those on the current
+/// match's line (`index`) stand out more.
+pub fn highlight<'a>(line: Line<'a>, search: Option<&PaneSearch>, index: usize) -> Line<'a> {
+    let Some(search) = search else {
+        return line;
+    };
+    let mark = if index == search.line {
+        Style::default().fg(Color::Black).bg(Color::LightMagenta)
+    } else {
+        Style::default().fg(Color::Black).bg(Color::Yellow)
+    };
+    let mut spans = Vec::new();
+    for span in line.spans {
+        let ranges = find(&span.content, &search.query);
+        if ranges.is_empty() {
+            spans.push(span);
+            continue;
+        }
+        let text = span.content.as_ref();
+        let mut at = 0;
+        for (start, end) in ranges {
+            if start > at {
+                spans.push(Span::styled(text[at..start].to_string(), span.style));
+            }
+            spans.push(Span::styled(
+                text[start..end].to_string(),
+                span.style.patch(mark),
+            ));
+            at = end;
+        }
+        if at < text.len() {
+            spans.push(Span::styled(text[at..].to_string(), span.style));
+        }
+    }
+    Line { spans, ..line }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEXT: [&str; 6] = ["fn main()", "", "  Main loop", "x", "MAIN", "end"];
+
+    #[test]
+    fn test_find_is_smart_case() {
+        assert_eq!(find("Main main MAIN", "main"), [(0, 4), (5, 9), (10, 14)]);
+        assert_eq!(find("Main main MAIN", "Main"), [(0, 4)]);
+        assert_eq!(find("ünï main", "main"), [(6, 10)]);
+        assert!(find("abc", "").is_empty());
+    }
+
+    #[test]
+    fn test_first_and_step_wrap_around() {
+        let mut search = PaneSearch::new("main", 1);
+        assert_eq!(
+            search.first(TEXT).unwrap(),
+            "Match 2 of 3 for 'main' (n/N next/previous)"
+        );
+        assert_eq!(search.line, 2);
+        assert_eq!(search.scroll(), 0);
+        search.step(TEXT, true).unwrap();
+        assert_eq!(search.line, 4);
+        search.step(TEXT, true).unwrap();
+        assert_eq!(search.line, 0);
+        search.step(TEXT, false).unwrap();
+        assert_eq!(search.line, 4);
+
+        let mut search = PaneSearch::new("nothing", 0);
+        assert_eq!(search.first(TEXT).unwrap_err(), "No matches for 'nothing'");
+        assert_eq!(search.line, 0);
+    }
+
+    #[test]
+    fn test_highlight_splits_spans() {
+        let search = PaneSearch::new("ab", 0);
+        let line = highlight(
+            Line::from(vec![Span::raw("xaby"), Span::raw("zz")]),
+            Some(&search),
+            0,
+        );
+        let parts: Vec<&str> = line.spans.iter().map(|s| s.content.as_ref()).collect();
+        assert_eq!(parts, ["x", "ab", "y", "zz"]);
+        assert_eq!(line.spans[1].style.bg, Some(Color::LightMagenta));
+        let other = highlight(Line::from("ab"), Some(&search), 3);
+        assert_eq!(other.spans[0].style.bg, Some(Color::Yellow));
+    }
+}
@@ -0,0 +1,378 @@
+//! Interactive rebase view — rewrite the commits from one picked in the
+//! Timeline up to HEAD: pick, reword, squash, fixup or drop each one and
+//! reorder them, then run the plan with `git rebase -i` (see `git::rebase`).
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+};
+
+use crate::app::{App, ConfirmAction, InputAction, Popup, View};
+use crate::git::{
+    self,
+    log::CommitEntry,
+    rebase::{TodoAction, TodoEntry},
+};
+use crate::ui::icons::Icon;
+use crate::ui::utils::render_scrollbar;
+use crate::ui::zen;
+
+#[derive(Default)]
+pub struct RebaseState {
+    /// Commit the plan is replayed onto; `None` rewrites from the root.
+    pub base: Option<String>,
+    /// The plan, oldest commit first, as the todo file lists it.
+    pub entries: Vec<TodoEntry>,
+    /// The plan as loaded, for `u`.
+    original: Vec<TodoEntry>,
+    pub branch: String,
+    pub selected: usize,
+    pub list_state: ListState,
+}
+
+impl RebaseState {
+    pub fn new(base: Option<String>, branch: String, entries: Vec<TodoEntry>) -> Self {
+        let mut state = Self {
+            base,
+            original: entries.clone(),
+            entries,
+            branch,
+            ..Self::default()
+        };
+        state.select(0);
+        state
+    }
+
+    /// Commits the branch will have in place of the rewritten ones.
+    fn result_count(&self) -> usize {
+        self.entries
+            .iter()
+            .filter(|e| e.action != TodoAction::Drop && !e.action.melds())
+            .count()
+    }
+
+    fn reordered(&self) -> bool {
+        self.entries
+            .iter()
+            .map(|e| &e.hash)
+            .ne(self.original.iter().map(|e| &e.hash))
+    }
+
+    fn changed(&self) -> bool {
+        self.reordered()
+            || self
+                .entries
+                .iter()
+                .any(|e| e.action != TodoAction::Pick || e.reworded_message().is_some())
+    }
+
+    fn select(&mut self, index: usize) {
+        self.selected = index;
+        self.list_state.select(Some(index));
+    }
+}
+
+/// Start planning a rebase of `from` and everything after it.
+pub fn open(app: &mut App, from: &CommitEntry) {
+    match git::rebase::plan_from(from) {
+        Ok((base, entries)) => {
+            let branch = git::cherry_pick::get_current_branch();
+            app.rebase_state = RebaseState::new(base, branch, entries);
+            app.open_view(View::Rebase);
+        }
+        Err(e) => app.set_status(format!("Can't rebase: {}", e)),
+    }
+}
+
+pub fn render(f: &mut Frame, area: Rect, state: &mut RebaseState) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(4),              // Summary
+            Constraint::Min(5),                 // Plan
+            Constraint::Length(zen::chrome(3)), // Keybindings
+        ])
+        .split(area);
+
+    let base = state
+        .base
+        .as_deref()
+        .map(|b| b[..7.min(b.len())].to_string())
+        .unwrap_or_else(|| "the root".to_string());
+    let mut summary = vec![Line::from(vec![
+        Span::styled("  Rewriting ", Style::default().fg(Color::DarkGray)),
+        Span::styled(
+            state.branch.clone(),
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            format!(
+                ": {} commit(s) after {} → {}{}",
+                state.entries.len(),
+                base,
+                state.result_count(),
+                if state.reordered() { ", reordered" } else { "" }
+            ),
+            Style::default().fg(Color::DarkGray),
+        ),
+    ])];
+    summary.push(match git::rebase::validate(&state.entries) {
+        Some(problem) => Line::from(Span::styled(
+            format!("  {} {}", Icon::Warning, problem),
+            Style::default().fg(Color::Red),
+        )),
+        None => Line::from(Span::styled(
+            "  Oldest first. Squash and fixup fold a commit into the one above it.",
+            Style::default().fg(Color::DarkGray),
+        )),
+    });
+    let header = Paragraph::new(summary).block(
+        Block::default()
+            .title(Span::styled(
+                " Interactive Rebase ",
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            ))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan)),
+    );
+    f.render_widget(header, chunks[0]);
+
+    let items: Vec<ListItem> = state
+        .entries
+        .iter()
+        .map(|e| {
+            let (color, dropped) = match e.action {
+                TodoAction::Pick => (Color::Green, false),
+                TodoAction::Reword => (Color::Cyan, false),
+                TodoAction::Squash => (Color::Yellow, false),
+                TodoAction::Fixup => (Color::Magenta, false),
+                TodoAction::Drop => (Color::Red, true),
+            };
+            let text_style = if dropped {
+                Style::default()
+                    .fg(Color::DarkGray)
+                    .add_modifier(Modifier::CROSSED_OUT)
+            } else {
+                Style::default().fg(Color::White)
+            };
+            let mut spans = vec![
+                Span::styled(
+                    format!("{:<7}", e.action.label()),
+                    Style::default().fg(color).add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(
+                    if e.action.melds() { "└ " } else { "" },
+                    Style::default().fg(color),
+                ),
+                Span::styled(
+                    format!("{} ", e.short_hash),
+                    Style::default().fg(Color::Yellow),
+                ),
+            ];
+            match (&e.new_subject, e.action) {
+                (Some(subject), TodoAction::Reword) => {
+                    spans.push(Span::styled(subject.clone(), text_style));
+                    spans.push(Span::styled(
+                        format!("  (was: {})", e.subject),
+                        Style::default().fg(Color::DarkGray),
+                    ));
+                }
+                _ => spans.push(Span::styled(e.subject.clone(), text_style)),
+            }
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .title(Span::styled(
+                    " Plan (oldest first) ",
+                    Style::default().fg(Color::White),
+                ))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::DarkGray)),
+        )
+        .highlight_style(
+            Style::default()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol("▶ ");
+    f.render_stateful_widget(&list, chunks[1], &mut state.list_state);
+    render_scrollbar(f, chunks[1], list.len(), state.list_state.offset());
+
+    let keys = Paragraph::new(Line::from(vec![
+        Span::styled(" [p/r/s/f/d]", Style::default().fg(Color::Cyan)),
+        Span::raw(" Pick/Reword/Squash/Fixup/Drop "),
+        Span::styled("[J/K]", Style::default().fg(Color::Cyan)),
+        Span::raw(" Move "),
+        Span::styled("[u]", Style::default().fg(Color::Cyan)),
+        Span::raw(" Reset "),
+        Span::styled("[Enter]", Style::default().fg(Color::Cyan)),
+        Span::raw(" Rebase "),
+        Span::styled("[Esc]", Style::default().fg(Color::Cyan)),
+        Span::raw(" Back"),
+    ]))
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::DarkGray)),
+    );
+    f.render_widget(keys, chunks[2]);
+}
+
+pub fn handle_key(app: &mut App, key: KeyEvent) -> anyhow::Result<()> {
+    let state = &mut app.rebase_state;
+    let len = state.entries.len();
+    if len == 0 {
+        return Ok(());
+    }
+    let i = state.selected.min(len - 1);
+    let set = |state: &mut RebaseState, action: TodoAction| {
+        state.entries[i].action = action;
+    };
+    match key.code {
+        KeyCode::Up | KeyCode::Char('k') if i > 0 => state.select(i - 1),
+        KeyCode::Down | KeyCode::Char('j') if i + 1 < len => state.select(i + 1),
+        KeyCode::Char('K') if i > 0 => {
+            state.entries.swap(i, i - 1);
+            state.select(i - 1);
+        }
+        KeyCode::Char('J') if i + 1 < len => {
+            state.entries.swap(i, i + 1);
+            state.select(i + 1);
+        }
+        KeyCode::Char('p') => set(state, TodoAction::Pick),
+        KeyCode::Char('s') => set(state, TodoAction::Squash),
+        KeyCode::Char('f') => set(state, TodoAction::Fixup),
+        KeyCode::Char('d') => set(state, TodoAction::Drop),
+        KeyCode::Char('r') => {
+            let entry = &state.entries[i];
+            let value = entry
+                .new_subject
+                .clone()
+                .unwrap_or_else(|| entry.subject.clone());
+            app.popup = Popup::Input {
+                title: format!("Reword {}", entry.short_hash),
+                prompt: "Subject: ".to_string(),
+                value,
+                on_submit: InputAction::RebaseReword(i),
+            };
+        }
+        KeyCode::Char('u') => {
+            state.entries = state.original.clone();
+            state.select(0);
+            app.set_status("Rebase plan reset");
+        }
+        KeyCode::Enter => confirm(app),
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Set the new subject of entry `index` (from the Reword input).
+pub fn reword(app: &mut App, index: usize, subject: &str) {
+    let subject = subject.trim();
+    let Some(entry) = app.rebase_state.entries.get_mut(index) else {
+        return;
+    };
+    if subject.is_empty() || subject == entry.subject {
+        entry.new_subject = None;
+        entry.action = TodoAction::Pick;
+    } else {
+        entry.new_subject = Some(subject.to_string());
+        entry.action = TodoAction::Reword;
+    }
+}
+
+/// Ask before running the plan, summing up what it will do.
+fn confirm(app: &mut App) {
+    let state = &app.rebase_state;
+    if let Some(problem) = git::rebase::validate(&state.entries) {
+        app.set_status(problem);
+        return;
+    }
+    if !state.changed() {
+        app.set_status("Nothing to do — change an action or move a commit first");
+        return;
+    }
+    let count = |f: fn(TodoAction) -> bool| state.entries.iter().filter(|e| f(e.action)).count();
+    let mut changes = Vec::new();
+    let melded = count(TodoAction::melds);
+    if melded > 0 {
+        changes.push(format!("{} folded into the commit above", melded));
+    }
+    let dropped = count(|a| a == TodoAction::Drop);
+    if dropped > 0 {
+        changes.push(format!("{} dropped", dropped));
+    }
+    let reworded = state
+        .entries
+        .iter()
+        .filter(|e| e.reworded_message().is_some())
+        .count();
+    if reworded > 0 {
+        changes.push(format!("{} reworded", reworded));
+    }
+    if state.reordered() {
+        changes.push("reordered".to_string());
+    }
+
+    let mut message = format!(
+        "Rewrite {} commit(s) on {} into {}:\n  {}\n",
+        state.entries.len(),
+        state.branch,
+        state.result_count(),
+        changes.join(", ")
+    );
+    let published = git::rebase::published_count(state.base.as_deref());
+    if published > 0 {
+        message.push_str(&format!(
+            "\n{} {} of them are already pushed — you'll need to force-push.\n",
+            Icon::Warning,
+            published
+        ));
+    }
+    message.push_str("\nThe old commits stay in the reflog (ORIG_HEAD).\n\n[y] Yes  [n] No");
+    app.popup = Popup::Confirm {
+        title: "Rebase".to_string(),
+        message,
+        on_confirm: ConfirmAction::RunRebase,
+    };
+}
+
+/// Run the confirmed plan. A stop on conflicts hands over to Merge Resolve.
+pub fn run(app: &mut App) {
+    let state = &app.rebase_state;
+    let (before, after) = (state.entries.len(), state.result_count());
+    match git::rebase::run(state.base.as_deref(), &state.entries) {
+        Ok(()) => {
+            app.set_status(format!(
+                "✓ Rebased: {} commit(s) rewritten into {}",
+                before, after
+            ));
+            app.go_back();
+        }
+        Err(_) if git::merge::get_merge_state().is_some() => {
+            app.set_status(
+                "Rebase stopped on a conflict — resolve it here, then Continue to finish the plan",
+            );
+            app.open_view(View::MergeResolve);
+            app.refresh();
+        }
+        Err(e) => {
+            let err = e.to_string();
+            app.set_status(format!("Error: {}", err));
+            app.start_ai_error_explain(err);
+        }
+    }
+}
@@ -1,13 +1,17 @@
 use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::{
+    Frame,
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Cell, Paragraph, Row, Table, TableState, Wrap},
-    Frame,
 };
 
 use crate::git;
+use crate::ui::datetime;
+use crate::ui::icons::Icon;
+use crate::ui::utils::{render_paragraph_scrollbar, render_scrollbar};
+use crate::ui::zen;
 
 #[derive(Default)]
 pub struct ReflogState {
@@ -18,6 +22,11 @@ pub struct ReflogState {
     pub show_diff: bool,
     pub detail_diff: Vec<git::DiffLine>,
     pub detail_scroll: u16,
+    detail_title: String,
+    /// Commits no ref reaches any more, while shown instead of the reflog.
+    pub lost: Option<Vec<git::reflog::LostCommit>>,
+    pub lost_selected: usize,
+    pub lost_table_state: TableState,
 }
 
 impl ReflogState {
@@ -44,15 +53,23 @@ impl ReflogState {
         }
     }
 
-    fn load_diff(&mut self) {
+    /// Look for lost commits (`git fsck`, so not on every refresh).
+    pub fn load_lost(&mut self) {
+        let lost = git::reflog::lost_commits().unwrap_or_default();
+        self.lost_selected = self.lost_selected.min(lost.len().saturating_sub(1));
+        self.lost_table_state
+            .select((!lost.is_empty()).then_some(self.lost_selected));
+        self.lost = Some(lost);
+    }
+
+    fn load_diff(&mut self, hash: &str, title: String) {
         self.detail_diff.clear();
         self.detail_scroll = 0;
-        if let Some(entry) = self.entries.get(self.selected) {
-            if let Ok(diffs) = git::diff::get_commit_diff(&entry.hash) {
-                for fd in &diffs {
-                    for hunk in &fd.hunks {
-                        self.detail_diff.extend(hunk.lines.clone());
-                    }
+        self.detail_title = title;
+        if let Ok(diffs) = git::diff::get_commit_diff(hash) {
+            for fd in &diffs {
+                for hunk in &fd.hunks {
+                    self.detail_diff.extend(hunk.lines.clone());
                 }
             }
         }
@@ -64,12 +81,16 @@ pub fn render(f: &mut Frame, area: Rect, state: &mut ReflogState) {
         render_detail(f, area, state);
         return;
     }
+    if state.lost.is_some() {
+        render_lost(f, area, state);
+        return;
+    }
 
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Min(10),   // Table
-            Constraint::Length(3), // Hints
+            Constraint::Min(10),                // Table
+            Constraint::Length(zen::chrome(3)), // Hints
         ])
         .split(area);
 
@@ -103,12 +124,14 @@ pub fn render(f: &mut Frame, area: Rect, state: &mut ReflogState) {
                 Cell::from(e.operation.as_str())
                     .style(Style::default().fg(op_color).add_modifier(Modifier::BOLD)),
                 Cell::from(e.message.as_str()).style(Style::default().fg(Color::White)),
-                Cell::from(e.date.as_str()).style(Style::default().fg(Color::DarkGray)),
+                Cell::from(datetime::format_iso(&e.date_iso, &e.date))
+                    .style(Style::default().fg(Color::DarkGray)),
             ])
         })
         .collect();
 
     let filter_text = state.filter_op.as_deref().unwrap_or("all");
+    let total = rows.len();
     let table = Table::new(
         rows,
         [
@@ -123,7 +146,7 @@ pub fn render(f: &mut Frame, area: Rect, state: &mut ReflogState) {
     .block(
         Block::default()
             .title(Span::styled(
-                format!(" 🔄 Reflog ({}) ", filter_text),
+                format!(" {} Reflog ({}) ", Icon::Sync, filter_text),
                 Style::default()
                     .fg(Color::White)
                     .add_modifier(Modifier::BOLD),
@@ -135,6 +158,7 @@ pub fn render(f: &mut Frame, area: Rect, state: &mut ReflogState) {
     .highlight_symbol("▶ ");
 
     f.render_stateful_widget(table, chunks[0], &mut state.table_state);
+    render_scrollbar(f, chunks[0], total, state.table_state.offset());
 
     // Hints
     let hints = Paragraph::new(Line::from(vec![
@@ -146,6 +170,8 @@ pub fn render(f: &mut Frame, area: Rect, state: &mut ReflogState) {
         Span::raw(" Filter "),
         Span::styled("[c]", Style::default().fg(Color::Cyan)),
         Span::raw(" Clear filter "),
+        Span::styled("[L]", Style::default().fg(Color::Cyan)),
+        Span::raw(" Lost commits "),
         Span::styled("[q]", Style::default().fg(Color::DarkGray)),
         Span::raw(" Back"),
     ]))
@@ -172,13 +198,10 @@ fn render_detail(f: &mut Frame, area: Rect, state: &ReflogState) {
         })
         .collect();
 
-    let title = if let Some(entry) = state.entries.get(state.selected) {
-        format!(
-            " Reflog #{} — {} {} ",
-            entry.index, entry.operation, entry.short_hash
-        )
+    let title = if state.detail_title.is_empty() {
+        " Reflog Detail "
     } else {
-        " Reflog Detail ".to_string()
+        &state.detail_title
     };
 
     let diff = Paragraph::new(diff_lines)
@@ -191,7 +214,151 @@ fn render_detail(f: &mut Frame, area: Rect, state: &ReflogState) {
         .scroll((state.detail_scroll, 0))
         .wrap(Wrap { trim: false });
 
-    f.render_widget(diff, area);
+    f.render_widget(&diff, area);
+    render_paragraph_scrollbar(f, area, &diff, state.detail_scroll);
+}
+
+fn render_lost(f: &mut Frame, area: Rect, state: &mut ReflogState) {
+    let lost = state.lost.as_deref().unwrap_or_default();
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(10),                // Table
+            Constraint::Length(zen::chrome(3)), // Hints
+        ])
+        .split(area);
+
+    let header = Row::new(["Hash", "Message", "Author", "When", "Kept by"].map(|h| {
+        Cell::from(h).style(
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )
+    }))
+    .height(1);
+
+    let rows: Vec<Row> = lost
+        .iter()
+        .map(|c| {
+            let (kept_by, kept_color) = if c.in_reflog {
+                ("reflog", Color::Yellow)
+            } else {
+                ("nothing (gc)", Color::Red)
+            };
+            let message = if c.is_stash {
+                format!("(dropped stash) {}", c.message)
+            } else {
+                c.message.clone()
+            };
+            Row::new(vec![
+                Cell::from(c.short_hash.as_str()).style(Style::default().fg(Color::Yellow)),
+                Cell::from(message).style(Style::default().fg(Color::White)),
+                Cell::from(c.author.as_str()).style(Style::default().fg(Color::Cyan)),
+                Cell::from(datetime::format_iso(&c.date_iso, &c.date))
+                    .style(Style::default().fg(Color::DarkGray)),
+                Cell::from(kept_by).style(Style::default().fg(kept_color)),
+            ])
+        })
+        .collect();
+
+    let total = rows.len();
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(10),
+            Constraint::Percentage(50),
+            Constraint::Percentage(15),
+            Constraint::Percentage(15),
+            Constraint::Length(13),
+        ],
+    )
+    .header(header)
+    .block(
+        Block::default()
+            .title(Span::styled(
+                format!(
+                    " {} Lost Commits ({}) — on no branch ",
+                    Icon::Warning,
+                    lost.len()
+                ),
+                Style::default()
+                    .fg(Color::White)
+                    .add_modifier(Modifier::BOLD),
+            ))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Magenta)),
+    )
+    .row_highlight_style(Style::default().bg(Color::DarkGray))
+    .highlight_symbol("▶ ");
+    let empty = lost.is_empty();
+    f.render_stateful_widget(table, chunks[0], &mut state.lost_table_state);
+    render_scrollbar(f, chunks[0], total, state.lost_table_state.offset());
+
+    if empty {
+        let inner = chunks[0].inner(ratatui::layout::Margin::new(2, 2));
+        f.render_widget(
+            Paragraph::new(Span::styled(
+                "No lost commits — every commit is on a branch, tag or stash",
+                Style::default().fg(Color::DarkGray),
+            )),
+            inner,
+        );
+    }
+
+    let hints = Paragraph::new(Line::from(vec![
+        Span::styled(" [Enter]", Style::default().fg(Color::Cyan)),
+        Span::raw(" View diff "),
+        Span::styled("[b]", Style::default().fg(Color::Cyan)),
+        Span::raw(" Recover as branch "),
+        Span::styled("[r]", Style::default().fg(Color::Cyan)),
+        Span::raw(" Rescan "),
+        Span::styled("[L/Esc]", Style::default().fg(Color::DarkGray)),
+        Span::raw(" Back to reflog"),
+    ]))
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::DarkGray)),
+    );
+    f.render_widget(hints, chunks[1]);
+}
+
+fn handle_lost_key(app: &mut crate::app::App, key: KeyEvent) {
+    let state = &mut app.reflog_state;
+    let count = state.lost.as_ref().map_or(0, Vec::len);
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('L') => state.lost = None,
+        KeyCode::Up | KeyCode::Char('k') if state.lost_selected > 0 => {
+            state.lost_selected -= 1;
+            state.lost_table_state.select(Some(state.lost_selected));
+        }
+        KeyCode::Down | KeyCode::Char('j') if state.lost_selected + 1 < count => {
+            state.lost_selected += 1;
+            state.lost_table_state.select(Some(state.lost_selected));
+        }
+        KeyCode::Char('r') => state.load_lost(),
+        KeyCode::Enter => {
+            if let Some(commit) = state.lost.as_ref().and_then(|l| l.get(state.lost_selected)) {
+                let (hash, title) = (
+                    commit.hash.clone(),
+                    format!(" Lost commit {} — {} ", commit.short_hash, commit.message),
+                );
+                state.load_diff(&hash, title);
+                state.show_diff = true;
+            }
+        }
+        KeyCode::Char('b') => {
+            if let Some(commit) = state.lost.as_ref().and_then(|l| l.get(state.lost_selected)) {
+                app.popup = crate::app::Popup::Input {
+                    title: format!("Recover {} as Branch", commit.short_hash),
+                    prompt: "Branch name: ".to_string(),
+                    value: format!("recovered-{}", commit.short_hash),
+                    on_submit: crate::app::InputAction::CreateBranchAt(commit.hash.clone()),
+                };
+            }
+        }
+        _ => {}
+    }
 }
 
 pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()> {
@@ -212,34 +379,47 @@ pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()
         }
         return Ok(());
     }
+    if state.lost.is_some() {
+        handle_lost_key(app, key);
+        return Ok(());
+    }
 
     match key.code {
-        KeyCode::Up | KeyCode::Char('k') => {
-            if state.selected > 0 {
-                state.selected -= 1;
-                state.table_state.select(Some(state.selected));
-            }
+        KeyCode::Up | KeyCode::Char('k') if state.selected > 0 => {
+            state.selected -= 1;
+            state.table_state.select(Some(state.selected));
         }
-        KeyCode::Down | KeyCode::Char('j') => {
-            if state.selected + 1 < state.entries.len() {
-                state.selected += 1;
-                state.table_state.select(Some(state.selected));
-            }
+        KeyCode::Down | KeyCode::Char('j') if state.selected + 1 < state.entries.len() => {
+            state.selected += 1;
+            state.table_state.select(Some(state.selected));
         }
         KeyCode::Enter => {
-            state.load_diff();
-            state.show_diff = true;
-        }
-        KeyCode::Char('b') => {
-            if state.entries.get(state.selected).is_some() {
-                app.popup = crate::app::Popup::Input {
-                    title: "Create Branch from Reflog".to_string(),
-                    prompt: "Branch name: ".to_string(),
-                    value: String::new(),
-                    on_submit: crate::app::InputAction::CreateBranch,
-                };
+            if let Some(entry) = state.entries.get(state.selected) {
+                let (hash, title) = (
+                    entry.hash.clone(),
+                    format!(
+                        " Reflog #{} — {} {} ",
+                        entry.index, entry.operation, entry.short_hash
+                    ),
+                );
+                state.load_diff(&hash, title);
+                state.show_diff = true;
             }
         }
+        KeyCode::Char('b') if state.entries.get(state.selected).is_some() => {
+            let hash = state.entries[state.selected].hash.clone();
+            app.popup = crate::app::Popup::Input {
+                title: "Create Branch from Reflog".to_string(),
+                prompt: "Branch name: ".to_string(),
+                value: String::new(),
+                on_submit: crate::app::InputAction::CreateBranchAt(hash),
+            };
+        }
+        KeyCode::Char('L') => {
+            state.load_lost();
+            let count = state.lost.as_ref().map_or(0, Vec::len);
+            app.set_status(format!("Found {} lost commit(s)", count));
+        }
         KeyCode::Char('f') => {
             // Cycle through operation filters
             let next = match state.filter_op.as_deref() {
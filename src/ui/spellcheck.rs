@@ -0,0 +1,671 @@
+//! Offline spellchecker for commit messages.
+//!
+//! Two sources are combined, neither of which needs the network or the AI service:
+//! - a built-in table of common English misspellings (always precise, no false alarms)
+//! - a full word list when one is available: `[spellcheck] dictionary`, or the system
+//!   hunspell dictionary for `[spellcheck] language`, or `/usr/share/dict/words`
+//!
+//! Code-like tokens (backticks, paths, snake_case, CamelCase, acronyms, numbers) and
+//! the conventional-commit prefix (`feat(ui):`) are never checked.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use crate::config::SpellcheckConfig;
+
+/// Maximum edit distance for dictionary suggestions.
+const MAX_SUGGESTION_DISTANCE: usize = 2;
+
+/// Common English misspellings and their corrections.
+const MISSPELLINGS: &[(&str, &str)] = &[
+    ("accidentaly", "accidentally"),
+    ("accomodate", "accommodate"),
+    ("acess", "access"),
+    ("acheive", "achieve"),
+    ("adress", "address"),
+    ("agressive", "aggressive"),
+    ("alredy", "already"),
+    ("alwasy", "always"),
+    ("ammount", "amount"),
+    ("appearence", "appearance"),
+    ("arguement", "argument"),
+    ("asynchonous", "asynchronous"),
+    ("atribute", "attribute"),
+    ("availabe", "available"),
+    ("availible", "available"),
+    ("becasue", "because"),
+    ("becuase", "because"),
+    ("beggining", "beginning"),
+    ("begining", "beginning"),
+    ("beleive", "believe"),
+    ("buisness", "business"),
+    ("calender", "calendar"),
+    ("cancelation", "cancellation"),
+    ("catagory", "category"),
+    ("chaning", "changing"),
+    ("charachter", "character"),
+    ("commited", "committed"),
+    ("commiting", "committing"),
+    ("comparision", "comparison"),
+    ("compatability", "compatibility"),
+    ("compatable", "compatible"),
+    ("compatiblity", "compatibility"),
+    ("completly", "completely"),
+    ("concurent", "concurrent"),
+    ("condtion", "condition"),
+    ("configuraiton", "configuration"),
+    ("connnection", "connection"),
+    ("consistant", "consistent"),
+    ("containg", "containing"),
+    ("correclty", "correctly"),
+    ("corrent", "correct"),
+    ("curent", "current"),
+    ("currenly", "currently"),
+    ("defualt", "default"),
+    ("defautl", "default"),
+    ("definately", "definitely"),
+    ("definetly", "definitely"),
+    ("dependancy", "dependency"),
+    ("dependancies", "dependencies"),
+    ("dependecy", "dependency"),
+    ("deprecatd", "deprecated"),
+    ("desciption", "description"),
+    ("destory", "destroy"),
+    ("diffrent", "different"),
+    ("dissapear", "disappear"),
+    ("documantation", "documentation"),
+    ("documenation", "documentation"),
+    ("doesnt", "doesn't"),
+    ("dont", "don't"),
+    ("duplicat", "duplicate"),
+    ("embarass", "embarrass"),
+    ("enviroment", "environment"),
+    ("enviornment", "environment"),
+    ("equivalant", "equivalent"),
+    ("exection", "execution"),
+    ("existant", "existent"),
+    ("existance", "existence"),
+    ("explicitely", "explicitly"),
+    ("extention", "extension"),
+    ("fucntion", "function"),
+    ("funciton", "function"),
+    ("funtion", "function"),
+    ("garantee", "guarantee"),
+    ("guarentee", "guarantee"),
+    ("handeling", "handling"),
+    ("happend", "happened"),
+    ("heirarchy", "hierarchy"),
+    ("highlighing", "highlighting"),
+    ("ignor", "ignore"),
+    ("immediatly", "immediately"),
+    ("implemenation", "implementation"),
+    ("implementaion", "implementation"),
+    ("implmentation", "implementation"),
+    ("inconsistant", "inconsistent"),
+    ("independant", "independent"),
+    ("infomation", "information"),
+    ("informations", "information"),
+    ("initalize", "initialize"),
+    ("inital", "initial"),
+    ("intial", "initial"),
+    ("instace", "instance"),
+    ("instaed", "instead"),
+    ("intergration", "integration"),
+    ("interupt", "interrupt"),
+    ("invaild", "invalid"),
+    ("isnt", "isn't"),
+    ("lenght", "length"),
+    ("libary", "library"),
+    ("maintainance", "maintenance"),
+    ("maintenence", "maintenance"),
+    ("managment", "management"),
+    ("mesage", "message"),
+    ("messsage", "message"),
+    ("miscellanous", "miscellaneous"),
+    ("mispelled", "misspelled"),
+    ("modifcation", "modification"),
+    ("neccessary", "necessary"),
+    ("necesary", "necessary"),
+    ("necessery", "necessary"),
+    ("noticable", "noticeable"),
+    ("occured", "occurred"),
+    ("occurence", "occurrence"),
+    ("occuring", "occurring"),
+    ("optionnal", "optional"),
+    ("paramter", "parameter"),
+    ("parmeter", "parameter"),
+    ("particularily", "particularly"),
+    ("performace", "performance"),
+    ("permision", "permission"),
+    ("persistant", "persistent"),
+    ("posible", "possible"),
+    ("possiblity", "possibility"),
+    ("preceeding", "preceding"),
+    ("prefered", "preferred"),
+    ("presense", "presence"),
+    ("previouly", "previously"),
+    ("priviledge", "privilege"),
+    ("probaly", "probably"),
+    ("proccess", "process"),
+    ("programatically", "programmatically"),
+    ("propery", "property"),
+    ("publically", "publicly"),
+    ("recieve", "receive"),
+    ("recieved", "received"),
+    ("recomend", "recommend"),
+    ("recommand", "recommend"),
+    ("recursivly", "recursively"),
+    ("redundent", "redundant"),
+    ("refered", "referred"),
+    ("refrence", "reference"),
+    ("relevent", "relevant"),
+    ("remvoe", "remove"),
+    ("repositry", "repository"),
+    ("repostiory", "repository"),
+    ("requirment", "requirement"),
+    ("resouce", "resource"),
+    ("respone", "response"),
+    ("responsability", "responsibility"),
+    ("retreive", "retrieve"),
+    ("retrive", "retrieve"),
+    ("seperate", "separate"),
+    ("seperated", "separated"),
+    ("seperator", "separator"),
+    ("sequental", "sequential"),
+    ("similiar", "similar"),
+    ("sinlge", "single"),
+    ("specifc", "specific"),
+    ("specifed", "specified"),
+    ("stragety", "strategy"),
+    ("succesful", "successful"),
+    ("successfull", "successful"),
+    ("succesfully", "successfully"),
+    ("sucess", "success"),
+    ("supress", "suppress"),
+    ("suport", "support"),
+    ("synchonize", "synchronize"),
+    ("teh", "the"),
+    ("thier", "their"),
+    ("threshhold", "threshold"),
+    ("throught", "through"),
+    ("tranform", "transform"),
+    ("transfered", "transferred"),
+    ("truely", "truly"),
+    ("udpate", "update"),
+    ("unecessary", "unnecessary"),
+    ("unneccessary", "unnecessary"),
+    ("untill", "until"),
+    ("upate", "update"),
+    ("usefull", "useful"),
+    ("usuage", "usage"),
+    ("varaible", "variable"),
+    ("verison", "version"),
+    ("visibilty", "visibility"),
+    ("wich", "which"),
+    ("wierd", "weird"),
+    ("withing", "within"),
+    ("writting", "writing"),
+];
+
+/// Suffixes stripped (with possible stem endings restored) so inflected forms match
+/// hunspell stems, which are stored without their affixes.
+const SUFFIX_RULES: &[(&str, &[&str])] = &[
+    ("ies", &["y"]),
+    ("ied", &["y"]),
+    ("es", &["", "e"]),
+    ("s", &[""]),
+    ("ed", &["", "e"]),
+    ("ing", &["", "e"]),
+    ("ly", &["", "le"]),
+    ("ers", &["", "e"]),
+    ("er", &["", "e"]),
+    ("est", &["", "e"]),
+    ("ment", &[""]),
+    ("ness", &[""]),
+    ("able", &["", "e"]),
+];
+
+/// A possibly misspelled word in a message.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Misspelling {
+    /// 0-based line index.
+    pub line: usize,
+    /// Byte range of the word within its line.
+    pub start: usize,
+    pub end: usize,
+    pub word: String,
+}
+
+/// Spellchecker built from `[spellcheck]`.
+#[derive(Debug, Default)]
+pub struct SpellChecker {
+    /// Lowercased dictionary words; empty when no word list was found.
+    words: HashSet<String>,
+    /// Lowercased words accepted by the user.
+    custom: HashSet<String>,
+    /// Whether the built-in English misspelling table applies.
+    english: bool,
+}
+
+impl SpellChecker {
+    /// Build a checker from config. Returns a checker that flags nothing when disabled.
+    pub fn from_config(config: &SpellcheckConfig) -> Self {
+        if !config.enabled {
+            return Self::default();
+        }
+        let words = dictionary_path(config)
+            .and_then(|path| match load_word_list(&path) {
+                Ok(words) => {
+                    log::debug!(
+                        "[spellcheck] Loaded {} words from {}",
+                        words.len(),
+                        path.display()
+                    );
+                    Some(words)
+                }
+                Err(e) => {
+                    log::warn!("Failed to load dictionary {}: {}", path.display(), e);
+                    None
+                }
+            })
+            .unwrap_or_default();
+        Self::new(
+            words,
+            &config.words,
+            config.language.to_lowercase().starts_with("en"),
+        )
+    }
+
+    fn new(words: HashSet<String>, custom: &[String], english: bool) -> Self {
+        Self {
+            words,
+            custom: custom.iter().map(|w| w.to_lowercase()).collect(),
+            english,
+        }
+    }
+
+    /// Accept `word` from now on.
+    pub fn add_word(&mut self, word: &str) {
+        self.custom.insert(word.to_lowercase());
+    }
+
+    /// Find possible misspellings in `text`.
+    pub fn check(&self, text: &str) -> Vec<Misspelling> {
+        if self.words.is_empty() && !self.english {
+            return Vec::new();
+        }
+        let mut out = Vec::new();
+        for (line_idx, line) in text.lines().enumerate() {
+            // Lines starting with '#' are stripped by git
+            if line.trim_start().starts_with('#') {
+                continue;
+            }
+            let skip_until = if line_idx == 0 {
+                conventional_prefix_len(line)
+            } else {
+                0
+            };
+            let mut in_code = false;
+            for (start, chunk) in chunks(line) {
+                let ticks = chunk.matches('`').count();
+                if in_code || ticks > 0 || start < skip_until {
+                    in_code ^= ticks % 2 == 1;
+                    continue;
+                }
+                for (offset, word) in words_in_chunk(chunk) {
+                    if !self.is_correct(word) {
+                        out.push(Misspelling {
+                            line: line_idx,
+                            start: start + offset,
+                            end: start + offset + word.len(),
+                            word: word.to_string(),
+                        });
+                    }
+                }
+            }
+        }
+        out
+    }
+
+    fn is_correct(&self, word: &str) -> bool {
+        let lower = word.to_lowercase();
+        if self.custom.contains(&lower) {
+            return true;
+        }
+        if self.english && known_misspelling(&lower).is_some() {
+            return false;
+        }
+        self.words.is_empty() || self.known(&lower)
+    }
+
+    fn known(&self, lower: &str) -> bool {
+        if self.words.contains(lower) || self.custom.contains(lower) {
+            return true;
+        }
+        if let Some(stem) = lower
+            .strip_suffix("'s")
+            .or_else(|| lower.strip_suffix("n't"))
+        {
+            return self.known(stem);
+        }
+        for (suffix, endings) in SUFFIX_RULES {
+            let Some(stem) = lower.strip_suffix(suffix) else {
+                continue;
+            };
+            if stem.len() < 2 {
+                continue;
+            }
+            if endings
+                .iter()
+                .any(|e| self.words.contains(&format!("{}{}", stem, e)))
+            {
+                return true;
+            }
+            // Doubled final consonant: "stopped" -> "stop"
+            let bytes = stem.as_bytes();
+            if bytes.len() >= 3
+                && bytes[bytes.len() - 1] == bytes[bytes.len() - 2]
+                && self.words.contains(&stem[..stem.len() - 1])
+            {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Up to `limit` replacement suggestions for `word`, best first.
+    pub fn suggestions(&self, word: &str, limit: usize) -> Vec<String> {
+        let lower = word.to_lowercase();
+        let mut out: Vec<String> = Vec::new();
+        if let Some(fix) = known_misspelling(&lower) {
+            out.push(fix.to_string());
+        }
+
+        let mut scored: Vec<(usize, &String)> = self
+            .words
+            .iter()
+            .chain(self.custom.iter())
+            .filter(|w| w.len().abs_diff(lower.len()) <= MAX_SUGGESTION_DISTANCE)
+            .filter_map(|w| {
+                let d = edit_distance(&lower, w);
+                (d > 0 && d <= MAX_SUGGESTION_DISTANCE).then_some((d, w))
+            })
+            .collect();
+        scored.sort_by(|a, b| {
+            a.0.cmp(&b.0)
+                .then(
+                    a.1.len()
+                        .abs_diff(lower.len())
+                        .cmp(&b.1.len().abs_diff(lower.len())),
+                )
+                .then(a.1.cmp(b.1))
+        });
+        for (_, w) in scored {
+            if out.len() >= limit {
+                break;
+            }
+            if !out.contains(w) {
+                out.push(w.clone());
+            }
+        }
+        out.truncate(limit);
+        out.into_iter().map(|s| match_case(word, &s)).collect()
+    }
+}
+
+fn known_misspelling(lower: &str) -> Option<&'static str> {
+    MISSPELLINGS
+        .iter()
+        .find(|(wrong, _)| *wrong == lower)
+        .map(|(_, right)| *right)
+}
+
+/// Where to read the word list from: the configured file, then system dictionaries.
+fn dictionary_path(config: &SpellcheckConfig) -> Option<PathBuf> {
+    if let Some(ref path) = config.dictionary {
+        let path = if let Some(rest) = path.strip_prefix("~/") {
+            dirs::home_dir()?.join(rest)
+        } else {
+            PathBuf::from(path)
+        };
+        return Some(path);
+    }
+
+    let lang = &config.language;
+    let mut candidates: Vec<PathBuf> = [
+        "/usr/share/hunspell",
+        "/usr/share/myspell",
+        "/usr/share/myspell/dicts",
+        "/usr/local/share/hunspell",
+        "/opt/homebrew/share/hunspell",
+    ]
+    .iter()
+    .map(|dir| Path::new(dir).join(format!("{}.dic", lang)))
+    .collect();
+    if let Some(home) = dirs::home_dir() {
+        candidates.push(home.join("Library/Spelling").join(format!("{}.dic", lang)));
+    }
+    if lang.to_lowercase().starts_with("en") {
+        candidates.push(PathBuf::from("/usr/share/dict/words"));
+    }
+    candidates.into_iter().find(|p| p.is_file())
+}
+
+/// Load a plain word list (one word per line) or a hunspell `.dic` file
+/// (first line is a count, entries carry `/FLAGS`).
+fn load_word_list(path: &Path) -> std::io::Result<HashSet<String>> {
+    let content = std::fs::read(path)?;
+    Ok(parse_word_list(&String::from_utf8_lossy(&content)))
+}
+
+fn parse_word_list(content: &str) -> HashSet<String> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let entry = line.split_whitespace().next()?;
+            let word = entry.split('/').next()?;
+            if word.is_empty() || word.chars().all(|c| c.is_ascii_digit()) {
+                None
+            } else {
+                Some(word.to_lowercase())
+            }
+        })
+        .collect()
+}
+
+/// Length of a conventional-commit prefix like `feat(ui)!:` at the start of the subject.
+fn conventional_prefix_len(subject: &str) -> usize {
+    let Some(colon) = subject.find(':') else {
+        return 0;
+    };
+    let prefix = &subject[..colon];
+    let kind = prefix.split('(').next().unwrap_or("").trim_end_matches('!');
+    let valid = !kind.is_empty()
+        && kind.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        && !prefix.contains(' ');
+    if valid { colon + 1 } else { 0 }
+}
+
+/// Whitespace-separated chunks with their byte offsets.
+fn chunks(line: &str) -> Vec<(usize, &str)> {
+    let mut out = Vec::new();
+    let mut start = None;
+    for (i, c) in line.char_indices() {
+        match (c.is_whitespace(), start) {
+            (true, Some(s)) => {
+                out.push((s, &line[s..i]));
+                start = None;
+            }
+            (false, None) => start = Some(i),
+            _ => {}
+        }
+    }
+    if let Some(s) = start {
+        out.push((s, &line[s..]));
+    }
+    out
+}
+
+/// Checkable words in a chunk with their offsets, or none if the chunk looks like code.
+fn words_in_chunk(chunk: &str) -> Vec<(usize, &str)> {
+    const EDGE_PUNCT: &[char] = &[
+        '.', ',', ';', ':', '!', '?', '"', '\'', '(', ')', '[', ']', '{', '}', '*',
+    ];
+    let trimmed_start = chunk.trim_start_matches(EDGE_PUNCT);
+    let offset = chunk.len() - trimmed_start.len();
+    let core = trimmed_start.trim_end_matches(EDGE_PUNCT);
+    if core.is_empty() || core.contains("://") {
+        return Vec::new();
+    }
+    // Anything other than letters, inner apostrophes and hyphens is code-like
+    if !core
+        .chars()
+        .all(|c| c.is_alphabetic() || c == '\'' || c == '-')
+    {
+        return Vec::new();
+    }
+
+    let mut out = Vec::new();
+    let mut pos = 0;
+    for part in core.split('-') {
+        if is_checkable_word(part) {
+            out.push((offset + pos, part));
+        }
+        pos += part.len() + 1;
+    }
+    out
+}
+
+/// Skip single letters, acronyms and mixed-case identifiers.
+fn is_checkable_word(word: &str) -> bool {
+    if word.chars().filter(|c| c.is_alphabetic()).count() < 2 {
+        return false;
+    }
+    let upper_after_first = word.chars().skip(1).any(|c| c.is_uppercase());
+    !upper_after_first
+}
+
+/// Apply the capitalisation of `original` to `suggestion`.
+fn match_case(original: &str, suggestion: &str) -> String {
+    if original.chars().next().is_some_and(|c| c.is_uppercase()) {
+        let mut chars = suggestion.chars();
+        match chars.next() {
+            Some(first) => first.to_uppercase().chain(chars).collect(),
+            None => String::new(),
+        }
+    } else {
+        suggestion.to_string()
+    }
+}
+
+/// Damerau-Levenshtein distance (optimal string alignment) over chars.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut d = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for (i, row) in d.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in d[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            d[i][j] = (d[i - 1][j] + 1)
+                .min(d[i][j - 1] + 1)
+                .min(d[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                d[i][j] = d[i][j].min(d[i - 2][j - 2] + 1);
+            }
+        }
+    }
+    d[a.len()][b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn checker(words: &[&str]) -> SpellChecker {
+        SpellChecker::new(
+            words.iter().map(|w| w.to_string()).collect(),
+            &["zit".to_string()],
+            true,
+        )
+    }
+
+    fn flagged(checker: &SpellChecker, text: &str) -> Vec<String> {
+        checker.check(text).into_iter().map(|m| m.word).collect()
+    }
+
+    #[test]
+    fn test_builtin_table_without_dictionary() {
+        let c = checker(&[]);
+        assert_eq!(
+            flagged(&c, "fix: recieve teh update\n\nAnything else passes"),
+            vec!["recieve", "teh"]
+        );
+        assert_eq!(c.suggestions("Recieve", 3), vec!["Receive"]);
+    }
+
+    #[test]
+    fn test_dictionary_with_inflections() {
+        let c = checker(&["add", "stop", "retry", "make", "the", "button", "config"]);
+        assert!(flagged(&c, "adds added adding stopped retries making the button").is_empty());
+        assert_eq!(flagged(&c, "add the buton"), vec!["buton"]);
+        assert_eq!(c.suggestions("buton", 3), vec!["button"]);
+    }
+
+    #[test]
+    fn test_skips_code_like_tokens() {
+        let c = checker(&["update", "the", "see"]);
+        let text = "feat(ui)!: update the `frobnicate` CLI\n\n\
+                    see src/app.rs, snake_case, CamelCase, v1.2, https://x.io, zit\n\
+                    # comment lines are ignored: qwzx";
+        assert!(flagged(&c, text).is_empty());
+    }
+
+    #[test]
+    fn test_positions_and_hyphenated_words() {
+        let c = checker(&["well", "known", "fix"]);
+        let found = c.check("fix\nwell-knwon fix.");
+        assert_eq!(
+            found,
+            vec![Misspelling {
+                line: 1,
+                start: 5,
+                end: 10,
+                word: "knwon".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_added_words_are_accepted() {
+        let mut c = checker(&["the"]);
+        assert_eq!(flagged(&c, "the ratatui"), vec!["ratatui"]);
+        c.add_word("Ratatui");
+        assert!(flagged(&c, "the ratatui").is_empty());
+    }
+
+    #[test]
+    fn test_non_english_without_dictionary_flags_nothing() {
+        let c = SpellChecker::new(HashSet::new(), &[], false);
+        assert!(c.check("teh recieve").is_empty());
+    }
+
+    #[test]
+    fn test_parse_hunspell_dic() {
+        let words = parse_word_list("3\nhello/MS\nworld\tpo:noun\nGit/M\n");
+        assert_eq!(words.len(), 3);
+        assert!(words.contains("hello") && words.contains("world") && words.contains("git"));
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("teh", "the"), 1);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("", "abc"), 3);
+    }
+}
@@ -1,19 +1,98 @@
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::{
+    Frame,
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap},
-    Frame,
 };
+use std::collections::{HashMap, HashSet};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
 
 use crate::git;
+use crate::ui::icons::Icon;
+use crate::ui::image_preview;
+use crate::ui::pane_search::{self, PaneSearch};
+use crate::ui::utils::{Debounce, render_paragraph_scrollbar, render_scrollbar};
 
 #[derive(Debug, Clone)]
 pub struct StagingFile {
     pub path: String,
     pub status: git::FileStatus,
     pub is_staged: bool,
+    /// Source path of a rename or copy.
+    pub original_path: Option<String>,
+}
+
+/// Order of the Staging file list (`[ui] staging_sort`, cycled with `o`).
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum SortOrder {
+    #[default]
+    Path,
+    /// Conflicts first, then modified, added, deleted, …
+    Status,
+    /// Most recently modified first.
+    Modified,
+}
+
+impl SortOrder {
+    /// Parse the `[ui] staging_sort` config value; unknown values sort by path.
+    pub fn from_config(value: &str) -> Self {
+        match value.trim() {
+            "status" => Self::Status,
+            "modified" | "mtime" => Self::Modified,
+            _ => Self::Path,
+        }
+    }
+
+    fn next(self) -> Self {
+        match self {
+            Self::Path => Self::Status,
+            Self::Status => Self::Modified,
+            Self::Modified => Self::Path,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Path => "path",
+            Self::Status => "status",
+            Self::Modified => "modified",
+        }
+    }
+}
+
+/// Sections of the grouped list, in display order.
+const SECTIONS: [&str; 3] = ["Staged", "Unstaged", "Untracked"];
+
+/// How often line counts are re-read while the file list stays the same.
+const DIFF_STATS_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Marked paths listed in the discard confirmation.
+const MARKED_LIST_LIMIT: usize = 5;
+
+/// A row of the file list in tree mode.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TreeRow {
+    /// A directory with the number of staged and unstaged entries beneath it.
+    Dir {
+        path: String,
+        depth: usize,
+        staged: usize,
+        unstaged: usize,
+    },
+    /// Index into `StagingState::files`.
+    File { index: usize, depth: usize },
+}
+
+/// A place in the selected file's diff to scroll to.
+#[derive(Debug, Clone, Copy)]
+pub enum DiffJump {
+    /// The first conflict marker.
+    Marker,
+    /// A line of the new version.
+    Line(u32),
 }
 
 #[derive(Default)]
@@ -26,12 +105,94 @@ pub struct StagingState {
     pub diff_scroll: u16,
     /// Hunk-level staging mode
     pub hunk_mode: bool,
+    /// `/` search in the diff, while in hunk mode.
+    pub diff_search: Option<PaneSearch>,
     pub hunk_index: usize,
     pub file_hunks: Vec<git::diff::Hunk>,
+    /// Leftover conflict markers in staged files.
+    pub conflict_markers: Vec<git::merge::MarkerHit>,
+    /// Submodules, so their entries say what changed inside them.
+    pub submodules: Vec<git::submodule::Submodule>,
+    /// Whitespace problems in the selected file's diff.
+    pub whitespace: git::whitespace::WhitespaceReport,
+    /// The selected file's encoding and line endings; `None` for binary files.
+    pub format: Option<git::encoding::FileFormat>,
+    /// Old and new line endings when the change flipped every line.
+    pub eol_flip: Option<(git::encoding::Eol, git::encoding::Eol)>,
+    /// Size/dimension summary when the selected file is binary.
+    pub binary: Option<git::binary::BinarySummary>,
+    /// Decoded image for the half-block preview (`[ui] image_preview`).
+    pub image: Option<image_preview::RgbaImage>,
+    pub image_preview: bool,
+    /// Group files under collapsible directories (`t`).
+    pub tree_mode: bool,
+    /// Expanded directories in tree mode; all others are collapsed.
+    pub expanded: HashSet<String>,
+    /// Selected row in tree mode. On a directory row `selected` points past the
+    /// end of `files`, so file actions don't apply.
+    pub tree_cursor: usize,
+    /// Multi-select mode (`v`): Space marks files, V marks a range.
+    pub multi_select: bool,
+    /// Marked entries as (path, is_staged), so marks survive a refresh.
+    pub marked: HashSet<(String, bool)>,
+    /// Index of the file last marked — where a V range starts.
+    pub mark_anchor: Option<usize>,
+    pub sort: SortOrder,
+    /// Show staged / unstaged / untracked sections with headers (`g`).
+    pub group: bool,
+    /// Parsed CODEOWNERS, to show who owns the selected file.
+    pub codeowners: Option<git::codeowners::CodeOwners>,
+    /// Added/removed line counts per (path, is_staged), filled in file by file
+    /// as a background `git diff` streams in.
+    pub diff_stats: HashMap<(String, bool), (usize, usize)>,
+    /// The running stats stream, and the entries it has reported so far.
+    pub stats_rx: Option<mpsc::Receiver<(bool, git::diff::FileDiff)>>,
+    pub stats_seen: HashSet<(String, bool)>,
+    /// The file list the last stream started for, and when.
+    pub stats_files: Vec<(String, bool)>,
+    pub stats_at: Option<Instant>,
+    /// Diff reload deferred while moving through the list.
+    pub diff_debounce: Debounce,
+    /// The selected file's diff, waiting to be handed to the git workers.
+    pub diff_read: Option<crate::tasks::Read>,
+    /// The diff was asked for and hasn't arrived yet.
+    pub diff_loading: bool,
+    /// When the diff shown was asked for.
+    pub diff_at: Option<Instant>,
+    /// Where to scroll the diff once it arrives.
+    pub diff_jump: Option<DiffJump>,
+    /// When the file list shown was asked for.
+    pub loaded_at: Option<Instant>,
+}
+
+/// The file list and conflict markers, read on a worker (see `tasks`).
+pub struct Loaded {
+    at: Instant,
+    files: Vec<StagingFile>,
+    conflict_markers: Vec<git::merge::MarkerHit>,
+    submodules: Vec<git::submodule::Submodule>,
+}
+
+/// The selected file's diff and what was found in it, read on a worker.
+pub struct LoadedDiff {
+    at: Instant,
+    path: String,
+    is_staged: bool,
+    hunks: Vec<git::diff::Hunk>,
+    whitespace: git::whitespace::WhitespaceReport,
+    format: Option<git::encoding::FileFormat>,
+    eol_flip: Option<(git::encoding::Eol, git::encoding::Eol)>,
+    binary: Option<git::binary::BinarySummary>,
+    image: Option<image_preview::RgbaImage>,
 }
 
 impl StagingState {
     pub fn refresh(&mut self) {
+        self.apply(Self::load(Instant::now()));
+    }
+
+    /// Read the changed files, resolving renames and copies.
+    pub fn load(at: Instant) -> Loaded {
         let mut files = Vec::new();
 
         if let Ok(status) = git::status::get_status() {
@@ -40,6 +201,7 @@ impl StagingState {
                     path: f.path.clone(),
                     status: f.status.clone(),
                     is_staged: true,
+                    original_path: f.original_path.clone(),
                 });
             }
             for f in &status.unstaged {
@@ -49,6 +211,7 @@ impl StagingState {
                         path: f.path.clone(),
                         status: f.status.clone(),
                         is_staged: false,
+                        original_path: None,
                     });
                 }
             }
@@ -57,11 +220,72 @@ impl StagingState {
                     path: f.path.clone(),
                     status: f.status.clone(),
                     is_staged: false,
+                    original_path: None,
                 });
             }
+
+            // Resolve rename/copy pairs so the list and diff show "old → new"
+            for staged in [true, false] {
+                if !files
+                    .iter()
+                    .any(|f| f.is_staged == staged && f.status != git::FileStatus::Untracked)
+                {
+                    continue;
+                }
+                for pair in git::diff::get_renames(staged).unwrap_or_default() {
+                    let Some(file) = files
+                        .iter_mut()
+                        .find(|f| f.is_staged == staged && f.path == pair.new_path)
+                    else {
+                        continue;
+                    };
+                    file.status = if pair.copy {
+                        git::FileStatus::Copied
+                    } else {
+                        git::FileStatus::Renamed
+                    };
+                    file.original_path = Some(pair.old_path.clone());
+                    if !pair.copy {
+                        // The rename replaces the separate deletion of the old path
+                        files.retain(|f| {
+                            !(f.is_staged == staged
+                                && f.status == git::FileStatus::Deleted
+                                && f.path == pair.old_path)
+                        });
+                    }
+                }
+            }
         }
 
-        self.files = files;
+        let conflict_markers = if files.iter().any(|f| f.is_staged) {
+            git::merge::staged_conflict_markers()
+        } else {
+            Vec::new()
+        };
+        let submodules = if files.is_empty() {
+            Vec::new()
+        } else {
+            git::submodule::list().unwrap_or_default()
+        };
+        Loaded {
+            at,
+            files,
+            conflict_markers,
+            submodules,
+        }
+    }
+
+    /// Show what [`load`](Self::load) read, unless something newer is shown.
+    pub fn apply(&mut self, loaded: Loaded) {
+        if !crate::tasks::is_newer(&mut self.loaded_at, loaded.at) {
+            return;
+        }
+        self.poll_diff_stats();
+        git::codeowners::reload(&mut self.codeowners);
+        self.conflict_markers = loaded.conflict_markers;
+        self.submodules = loaded.submodules;
+        self.files = loaded.files;
+        self.sort_files();
         if self.selected >= self.files.len() && !self.files.is_empty() {
             self.selected = self.files.len() - 1;
         }
@@ -70,6 +294,72 @@ impl StagingState {
         } else {
             Some(self.selected)
         });
+        if self.tree_mode {
+            self.sync_tree_cursor();
+        }
+        self.start_diff_stats();
+    }
+
+    /// Stream line counts for every changed file in the background, when the
+    /// file list changed or the last counts are getting old. Only counts are
+    /// parsed; the selected file's hunks come from `update_diff`.
+    fn start_diff_stats(&mut self) {
+        let files: Vec<(String, bool)> = self
+            .files
+            .iter()
+            .filter(|f| f.status != git::FileStatus::Untracked)
+            .map(|f| (f.path.clone(), f.is_staged))
+            .collect();
+        let fresh = self.stats_files == files
+            && self
+                .stats_at
+                .is_some_and(|at| at.elapsed() < DIFF_STATS_INTERVAL);
+        if self.stats_rx.is_some() || fresh {
+            return;
+        }
+        self.stats_files = files;
+        self.stats_at = Some(Instant::now());
+        self.stats_seen.clear();
+
+        let (tx, rx) = mpsc::channel();
+        self.stats_rx = Some(rx);
+        let scope = git::scope::pathspec();
+        std::thread::spawn(move || {
+            for staged in [false, true] {
+                let mut args = vec!["diff"];
+                if staged {
+                    args.push("--cached");
+                }
+                args.push("--");
+                args.extend(scope.iter().map(|s| s.as_str()));
+                let _ = git::diff::stream_diff(&args, git::diff::DiffParser::summary(), |f| {
+                    tx.send((staged, f)).is_ok()
+                });
+            }
+        });
+    }
+
+    /// Take the counts streamed so far. Once the stream ends, counts for files
+    /// it didn't report are dropped.
+    fn poll_diff_stats(&mut self) {
+        let Some(rx) = &self.stats_rx else {
+            return;
+        };
+        loop {
+            match rx.try_recv() {
+                Ok((staged, file)) => {
+                    let key = (file.path, staged);
+                    self.diff_stats
+                        .insert(key.clone(), (file.additions, file.deletions));
+                    self.stats_seen.insert(key);
+                }
+                Err(mpsc::TryRecvError::Empty) => return,
+                Err(mpsc::TryRecvError::Disconnected) => break,
+            }
+        }
+        self.stats_rx = None;
+        let seen = std::mem::take(&mut self.stats_seen);
+        self.diff_stats.retain(|key, _| seen.contains(key));
     }
 
     fn filtered_files(&self) -> Vec<(usize, &StagingFile)> {
@@ -83,26 +373,616 @@ impl StagingState {
             .collect()
     }
 
+    /// Rows of the tree view: filtered files sorted by path, with a row per
+    /// directory. Files under a collapsed directory are hidden.
+    fn tree_rows(&self) -> Vec<TreeRow> {
+        let mut entries = self.filtered_files();
+        entries.sort_by(|(_, a), (_, b)| a.path.cmp(&b.path).then(b.is_staged.cmp(&a.is_staged)));
+
+        let mut counts: HashMap<&str, (usize, usize)> = HashMap::new();
+        for (_, f) in &entries {
+            for dir in parent_dirs(&f.path) {
+                let count = counts.entry(dir).or_default();
+                if f.is_staged {
+                    count.0 += 1;
+                } else {
+                    count.1 += 1;
+                }
+            }
+        }
+
+        // Sorting by path keeps each directory's files contiguous
+        let mut rows = Vec::new();
+        let mut emitted = HashSet::new();
+        for (index, f) in &entries {
+            let dirs = parent_dirs(&f.path);
+            for (depth, dir) in dirs.iter().enumerate() {
+                if depth > 0 && !self.expanded.contains(dirs[depth - 1]) {
+                    break;
+                }
+                if emitted.insert(*dir) {
+                    let (staged, unstaged) = counts[dir];
+                    rows.push(TreeRow::Dir {
+                        path: dir.to_string(),
+                        depth,
+                        staged,
+                        unstaged,
+                    });
+                }
+            }
+            if dirs.iter().all(|d| self.expanded.contains(*d)) {
+                rows.push(TreeRow::File {
+                    index: *index,
+                    depth: dirs.len(),
+                });
+            }
+        }
+        rows
+    }
+
+    /// Point `selected` at the file under the tree cursor, or past the end of
+    /// `files` when the cursor is on a directory.
+    fn sync_tree_cursor(&mut self) {
+        let rows = self.tree_rows();
+        self.tree_cursor = self.tree_cursor.min(rows.len().saturating_sub(1));
+        self.selected = match rows.get(self.tree_cursor) {
+            Some(TreeRow::File { index, .. }) => *index,
+            _ => self.files.len(),
+        };
+        self.list_state
+            .select((!rows.is_empty()).then_some(self.tree_cursor));
+    }
+
+    /// The directory under the tree cursor with its staged and unstaged counts.
+    fn selected_dir(&self) -> Option<(String, usize, usize)> {
+        if !self.tree_mode {
+            return None;
+        }
+        match self.tree_rows().into_iter().nth(self.tree_cursor)? {
+            TreeRow::Dir {
+                path,
+                staged,
+                unstaged,
+                ..
+            } => Some((path, staged, unstaged)),
+            TreeRow::File { .. } => None,
+        }
+    }
+
+    fn toggle_tree_mode(&mut self) {
+        self.tree_mode = !self.tree_mode;
+        if self.tree_mode {
+            self.tree_cursor = 0;
+            if self.selected < self.files.len() {
+                self.select_file(self.selected);
+            } else {
+                self.sync_tree_cursor();
+            }
+        } else {
+            if self.selected >= self.files.len() {
+                self.selected = 0;
+            }
+            self.list_state
+                .select((!self.files.is_empty()).then_some(self.selected));
+        }
+        self.update_diff();
+    }
+
+    /// Select `files[index]`, expanding its directories in tree mode.
+    fn select_file(&mut self, index: usize) {
+        self.selected = index;
+        if !self.tree_mode {
+            self.list_state.select(Some(index));
+            return;
+        }
+        let dirs: Vec<String> = parent_dirs(&self.files[index].path)
+            .into_iter()
+            .map(String::from)
+            .collect();
+        self.expanded.extend(dirs);
+        if let Some(pos) = self
+            .tree_rows()
+            .iter()
+            .position(|r| matches!(r, TreeRow::File { index: i, .. } if *i == index))
+        {
+            self.tree_cursor = pos;
+        }
+        self.sync_tree_cursor();
+    }
+
+    fn move_tree_cursor(&mut self, down: bool) {
+        let len = self.tree_rows().len();
+        if down && self.tree_cursor + 1 < len {
+            self.tree_cursor += 1;
+        } else if !down && self.tree_cursor > 0 {
+            self.tree_cursor -= 1;
+        } else {
+            return;
+        }
+        self.sync_tree_cursor();
+        self.diff_debounce.schedule();
+    }
+
+    /// Enter toggles the directory under the cursor, → expands it and ← collapses
+    /// it. ← on a file collapses the file's directory.
+    fn expand_key(&mut self, code: KeyCode) {
+        let target = match (self.selected_dir(), self.files.get(self.selected)) {
+            (Some((dir, ..)), _) => dir,
+            (None, Some(file)) if code == KeyCode::Left => match parent_dirs(&file.path).last() {
+                Some(dir) => dir.to_string(),
+                None => return,
+            },
+            _ => return,
+        };
+        let expand = match code {
+            KeyCode::Right => true,
+            KeyCode::Left => false,
+            _ => !self.expanded.contains(&target),
+        };
+        if expand {
+            self.expanded.insert(target.clone());
+        } else {
+            self.expanded.remove(&target);
+        }
+        if let Some(pos) = self
+            .tree_rows()
+            .iter()
+            .position(|r| matches!(r, TreeRow::Dir { path, .. } if *path == target))
+        {
+            self.tree_cursor = pos;
+        }
+        self.sync_tree_cursor();
+        self.update_diff();
+    }
+
+    /// Sort `files` by the current order, section by section when grouping.
+    fn sort_files(&mut self) {
+        let (sort, group) = (self.sort, self.group);
+        self.files.sort_by_cached_key(|f| {
+            let section = if group { section(f) } else { 0 };
+            let rank = match sort {
+                SortOrder::Status => status_rank(&f.status),
+                _ => 0,
+            };
+            // Newest first; files missing from disk (deleted) go last
+            let modified = match sort {
+                SortOrder::Modified => std::fs::metadata(&f.path).and_then(|m| m.modified()).ok(),
+                _ => None,
+            };
+            (
+                section,
+                rank,
+                std::cmp::Reverse(modified),
+                f.path.clone(),
+                !f.is_staged,
+            )
+        });
+    }
+
+    /// Re-sort after changing the order or grouping, keeping the selected file.
+    fn reorder(&mut self) {
+        let current = self
+            .files
+            .get(self.selected)
+            .map(|f| (f.path.clone(), f.is_staged));
+        self.sort_files();
+        if let Some((path, staged)) = current
+            && let Some(index) = self
+                .files
+                .iter()
+                .position(|f| f.path == path && f.is_staged == staged)
+        {
+            self.select_file(index);
+        } else if self.tree_mode {
+            self.sync_tree_cursor();
+        }
+    }
+
+    /// File indices in the order they are listed.
+    fn visible_files(&self) -> Vec<usize> {
+        if self.tree_mode {
+            self.tree_rows()
+                .into_iter()
+                .filter_map(|r| match r {
+                    TreeRow::File { index, .. } => Some(index),
+                    TreeRow::Dir { .. } => None,
+                })
+                .collect()
+        } else {
+            self.filtered_files().into_iter().map(|(i, _)| i).collect()
+        }
+    }
+
+    fn is_marked(&self, file: &StagingFile) -> bool {
+        self.marked.contains(&(file.path.clone(), file.is_staged))
+    }
+
+    /// Mark or unmark the selected file.
+    fn toggle_mark(&mut self) {
+        let Some(file) = self.files.get(self.selected) else {
+            return;
+        };
+        let key = (file.path.clone(), file.is_staged);
+        if !self.marked.remove(&key) {
+            self.marked.insert(key);
+        }
+        self.mark_anchor = Some(self.selected);
+    }
+
+    /// Mark every listed file between the anchor and the selected file.
+    fn mark_range(&mut self) {
+        let visible = self.visible_files();
+        let Some(cursor) = visible.iter().position(|&i| i == self.selected) else {
+            return;
+        };
+        let start = self
+            .mark_anchor
+            .and_then(|a| visible.iter().position(|&i| i == a))
+            .unwrap_or(cursor);
+        for &i in &visible[start.min(cursor)..=start.max(cursor)] {
+            let file = &self.files[i];
+            self.marked.insert((file.path.clone(), file.is_staged));
+        }
+        self.mark_anchor = Some(self.selected);
+    }
+
+    /// Marked files that are (or aren't) staged, sorted by path.
+    fn marked_files(&self, staged: bool) -> Vec<&StagingFile> {
+        let mut files: Vec<&StagingFile> = self
+            .files
+            .iter()
+            .filter(|f| f.is_staged == staged && self.is_marked(f))
+            .collect();
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+        files
+    }
+
+    pub fn exit_multi_select(&mut self) {
+        self.multi_select = false;
+        self.marked.clear();
+        self.mark_anchor = None;
+    }
+
+    /// Load the diff once navigation has paused (or right away with `force`).
+    /// Returns whether it was loaded.
+    pub fn settle_diff(&mut self, force: bool) -> bool {
+        let due = self.diff_debounce.fire(force);
+        if due {
+            self.update_diff();
+        }
+        due
+    }
+
+    /// Clear the diff and ask for the selected file's; it's read on the git
+    /// workers and shown by [`apply_diff`](Self::apply_diff).
     fn update_diff(&mut self) {
+        self.diff_debounce.cancel();
         self.diff_lines.clear();
         self.diff_scroll = 0;
         self.file_hunks.clear();
         self.hunk_index = 0;
+        self.whitespace = Default::default();
+        self.format = None;
+        self.eol_flip = None;
+        self.binary = None;
+        self.image = None;
+        self.diff_jump = None;
+        self.diff_read = self
+            .files
+            .get(self.selected)
+            .map(|file| crate::tasks::Read::Diff {
+                path: file.path.clone(),
+                original_path: file.original_path.clone(),
+                is_staged: file.is_staged,
+                status: file.status.clone(),
+                image_preview: self.image_preview,
+            });
+        self.diff_loading = self.diff_read.is_some();
+    }
 
-        if let Some(file) = self.files.get(self.selected) {
-            let diffs = if file.is_staged {
-                git::diff::get_staged_diff_for_file(&file.path).unwrap_or_default()
-            } else {
-                git::diff::get_unstaged_diff_for_file(&file.path).unwrap_or_default()
-            };
+    /// Read `file`'s diff, its binary summary and its encoding, as asked for at `at`.
+    pub fn load_diff(file: &StagingFile, image_preview: bool, at: Instant) -> LoadedDiff {
+        let diffs = if let Some(old) = &file.original_path {
+            git::diff::get_rename_diff(old, &file.path, file.is_staged).unwrap_or_default()
+        } else if file.is_staged {
+            git::diff::get_staged_diff_for_file(&file.path).unwrap_or_default()
+        } else {
+            git::diff::get_unstaged_diff_for_file(&file.path).unwrap_or_default()
+        };
+        let hunks = diffs
+            .into_iter()
+            .next()
+            .map(|fd| fd.hunks)
+            .unwrap_or_default();
+        let lines = || hunks.iter().flat_map(|hunk| &hunk.lines);
+
+        let binary = if hunks.is_empty() {
+            git::binary::summarize(&file.path, file.is_staged)
+        } else {
+            None
+        };
+        let image = if image_preview
+            && crate::ui::theme::mode() == crate::ui::theme::ColorMode::TrueColor
+            && let Some(summary) = &binary
+        {
+            [&summary.new, &summary.old]
+                .into_iter()
+                .flatten()
+                .find_map(|v| v.bytes.as_deref().and_then(image_preview::decode))
+        } else {
+            None
+        };
+
+        // EOL and final-newline checks need the whole new version of the file
+        let content = match file.status {
+            git::FileStatus::Deleted => None,
+            _ if file.is_staged => git::run_git(&["show", &format!(":{}", file.path)]).ok(),
+            _ => std::fs::read_to_string(&file.path).ok(),
+        };
+        let added = lines()
+            .filter(|l| l.line_type == git::DiffLineType::Added)
+            .map(|l| l.content.as_str());
+        let whitespace = git::whitespace::check(added, content.as_deref());
 
-            if let Some(fd) = diffs.first() {
-                self.file_hunks = fd.hunks.clone();
-                for hunk in &fd.hunks {
-                    self.diff_lines.extend(hunk.lines.clone());
+        // Encoding and EOL style, old against new: an editor that rewrote
+        // every line ending shows up as the whole file removed and added
+        let (old, new) = match file.status {
+            git::FileStatus::Deleted => (None, None),
+            git::FileStatus::Untracked => (None, std::fs::read(&file.path).ok()),
+            _ if file.is_staged => (
+                git::runner::run_git_bytes(&["show", &format!("HEAD:{}", file.path)]).ok(),
+                git::runner::run_git_bytes(&["show", &format!(":{}", file.path)]).ok(),
+            ),
+            _ => (
+                git::runner::run_git_bytes(&["show", &format!(":{}", file.path)]).ok(),
+                std::fs::read(&file.path).ok(),
+            ),
+        };
+        let format = new.as_deref().and_then(git::encoding::FileFormat::detect);
+        let removed = lines()
+            .filter(|l| l.line_type == git::DiffLineType::Removed)
+            .count();
+        let eol_flip = match (old.as_deref(), &format) {
+            // With core.autocrlf the bytes differ but git sees no change,
+            // so only warn when the diff really replaces every line
+            (Some(old), Some(new))
+                if removed > 0 && removed >= old.iter().filter(|&&b| b == b'\n').count() =>
+            {
+                git::encoding::FileFormat::detect(old)
+                    .and_then(|old| git::encoding::eol_flip(&old, new))
+            }
+            _ => None,
+        };
+
+        LoadedDiff {
+            at,
+            path: file.path.clone(),
+            is_staged: file.is_staged,
+            hunks,
+            whitespace,
+            format,
+            eol_flip,
+            binary,
+            image,
+        }
+    }
+
+    /// Show what [`load_diff`](Self::load_diff) read, if it's still the
+    /// selected file's and nothing newer is shown.
+    pub fn apply_diff(&mut self, loaded: LoadedDiff) {
+        let selected = self
+            .files
+            .get(self.selected)
+            .is_some_and(|f| f.path == loaded.path && f.is_staged == loaded.is_staged);
+        if !selected || !crate::tasks::is_newer(&mut self.diff_at, loaded.at) {
+            return;
+        }
+        self.diff_loading = false;
+        self.diff_lines = loaded
+            .hunks
+            .iter()
+            .flat_map(|hunk| hunk.lines.clone())
+            .collect();
+        self.file_hunks = loaded.hunks;
+        self.whitespace = loaded.whitespace;
+        self.format = loaded.format;
+        self.eol_flip = loaded.eol_flip;
+        self.binary = loaded.binary;
+        self.image = loaded.image;
+        match self.diff_jump.take() {
+            Some(DiffJump::Marker) => {
+                if let Some(pos) = self.diff_lines.iter().position(|l| {
+                    l.content.starts_with("+<<<<<<<") || l.content.starts_with("+>>>>>>>")
+                }) {
+                    self.diff_scroll = pos.saturating_sub(3) as u16;
                 }
             }
+            Some(DiffJump::Line(line)) => {
+                self.diff_scroll =
+                    diff_row_of_line(&self.file_hunks, line).saturating_sub(3) as u16;
+            }
+            None => {}
+        }
+    }
+
+    /// Fix whitespace in the selected file. A staged entry is re-staged when the
+    /// file has no other unstaged changes, so the fix lands in the index too.
+    fn fix_whitespace(&mut self) -> Result<String, String> {
+        let Some(file) = self.files.get(self.selected).cloned() else {
+            return Err("No file selected".to_string());
+        };
+        if file.status == git::FileStatus::Deleted {
+            return Err("File is deleted".to_string());
+        }
+        let also_unstaged = self
+            .files
+            .iter()
+            .any(|f| f.path == file.path && !f.is_staged);
+        match git::whitespace::fix_file(&file.path) {
+            Ok(false) => return Ok(format!("No whitespace to fix in {}", file.path)),
+            Ok(true) => {}
+            Err(e) => return Err(e.to_string()),
+        }
+        let msg = if file.is_staged && !also_unstaged {
+            git::run_git(&["add", "--", &file.path]).map_err(|e| e.to_string())?;
+            format!("✓ Fixed whitespace in {} and re-staged it", file.path)
+        } else if file.is_staged {
+            format!(
+                "✓ Fixed whitespace in {} (working tree — stage to include)",
+                file.path
+            )
+        } else {
+            format!("✓ Fixed whitespace in {}", file.path)
+        };
+        self.refresh();
+        if let Some(index) = self
+            .files
+            .iter()
+            .position(|f| f.path == file.path && f.is_staged == file.is_staged)
+        {
+            self.select_file(index);
+        }
+        self.update_diff();
+        Ok(msg)
+    }
+
+    /// Store `path` with LF line endings through `.gitattributes`, which turns
+    /// a CRLF → LF flip into no change at all.
+    pub fn renormalize_eol(&mut self, path: &str) -> Result<String, String> {
+        let added = git::encoding::renormalize(path).map_err(|e| e.to_string())?;
+        self.refresh();
+        self.update_diff();
+        Ok(if added {
+            format!(
+                "✓ Added \"{}\" to .gitattributes and re-staged {} with LF",
+                git::encoding::TEXT_AUTO,
+                path
+            )
+        } else {
+            format!("✓ Re-staged {} with LF", path)
+        })
+    }
+
+    /// Put the old line endings back on `path`, re-staging it like
+    /// [`Self::fix_whitespace`] when the flip was staged.
+    pub fn restore_eol(&mut self, path: &str, eol: git::encoding::Eol) -> Result<String, String> {
+        git::encoding::restore_file(path, eol).map_err(|e| e.to_string())?;
+        let staged = self.files.iter().any(|f| f.path == path && f.is_staged);
+        let also_unstaged = self.files.iter().any(|f| f.path == path && !f.is_staged);
+        let msg = if staged && !also_unstaged {
+            git::run_git(&["add", "--", path]).map_err(|e| e.to_string())?;
+            format!(
+                "✓ Restored {} line endings in {} and re-staged it",
+                eol.label(),
+                path
+            )
+        } else if staged {
+            format!(
+                "✓ Restored {} line endings in {} (working tree — stage to include)",
+                eol.label(),
+                path
+            )
+        } else {
+            format!("✓ Restored {} line endings in {}", eol.label(), path)
+        };
+        self.refresh();
+        self.update_diff();
+        Ok(msg)
+    }
+
+    /// Mark the selected untracked file (or the marked ones, or the directory under
+    /// the tree cursor) intent-to-add, so its diff shows and hunks can be staged.
+    fn intent_to_add(&mut self) -> Result<String, String> {
+        let untracked = |f: &&StagingFile| f.status == git::FileStatus::Untracked;
+        let paths: Vec<String> = if self.multi_select && !self.marked.is_empty() {
+            self.marked_files(false)
+                .into_iter()
+                .filter(untracked)
+                .map(|f| f.path.clone())
+                .collect()
+        } else if let Some((dir, ..)) = self.selected_dir() {
+            vec![format!("{}/", dir)]
+        } else {
+            self.files
+                .get(self.selected)
+                .filter(untracked)
+                .map(|f| vec![f.path.clone()])
+                .unwrap_or_default()
+        };
+        if paths.is_empty() {
+            return Err("no untracked file selected".to_string());
+        }
+        let refs: Vec<&str> = paths.iter().map(|p| p.as_str()).collect();
+        git::diff::intent_to_add(&refs).map_err(|e| e.to_string())?;
+
+        self.exit_multi_select();
+        self.refresh();
+        if let [path] = paths.as_slice()
+            && let Some(index) = self
+                .files
+                .iter()
+                .position(|f| f.path == *path && !f.is_staged)
+        {
+            self.select_file(index);
         }
+        self.update_diff();
+        Ok(if let [path] = paths.as_slice() {
+            format!("✓ {} marked intent-to-add — h to stage hunks", path)
+        } else {
+            format!("✓ {} files marked intent-to-add", paths.len())
+        })
+    }
+
+    fn has_markers(&self, file: &StagingFile) -> bool {
+        file.is_staged && self.conflict_markers.iter().any(|m| m.path == file.path)
+    }
+
+    /// Select the staged entry for `path` and scroll its diff to the first conflict marker.
+    pub fn jump_to_marker_file(&mut self, path: &str) -> bool {
+        let Some(index) = self
+            .files
+            .iter()
+            .position(|f| f.is_staged && f.path == path)
+        else {
+            return false;
+        };
+        self.filter.clear();
+        self.exit_hunk_mode();
+        self.select_file(index);
+        self.update_diff();
+        self.diff_jump = Some(DiffJump::Marker);
+        true
+    }
+
+    /// Select `path`'s unstaged entry (its staged one if it has no unstaged
+    /// changes) and scroll the diff to `line` of the new version.
+    pub fn jump_to_file(&mut self, path: &str, line: Option<u32>) -> bool {
+        let Some(index) = self
+            .files
+            .iter()
+            .position(|f| !f.is_staged && f.path == path)
+            .or_else(|| self.files.iter().position(|f| f.path == path))
+        else {
+            return false;
+        };
+        self.filter.clear();
+        self.exit_hunk_mode();
+        self.exit_multi_select();
+        self.select_file(index);
+        self.update_diff();
+        self.diff_jump = line.map(DiffJump::Line);
+        true
+    }
+
+    /// Jump to the next staged file (after the selected one) that still has conflict markers.
+    fn jump_to_next_marker(&mut self) -> Option<String> {
+        let len = self.files.len();
+        let next = (1..=len)
+            .map(|offset| (self.selected + offset) % len)
+            .find(|&i| self.has_markers(&self.files[i]))?;
+        let path = self.files[next].path.clone();
+        self.jump_to_marker_file(&path);
+        Some(path)
     }
 
     /// Enter hunk mode for the currently selected file.
@@ -138,6 +1018,57 @@ impl StagingState {
     }
 }
 
+/// Index into `SECTIONS`.
+fn section(file: &StagingFile) -> usize {
+    if file.is_staged {
+        0
+    } else if file.status == git::FileStatus::Untracked {
+        2
+    } else {
+        1
+    }
+}
+
+fn status_rank(status: &git::FileStatus) -> u8 {
+    match status {
+        git::FileStatus::Conflicted => 0,
+        git::FileStatus::Modified => 1,
+        git::FileStatus::Added => 2,
+        git::FileStatus::Deleted => 3,
+        git::FileStatus::Renamed => 4,
+        git::FileStatus::Copied => 5,
+        git::FileStatus::Untracked => 6,
+        git::FileStatus::Ignored => 7,
+    }
+}
+
+/// Directories containing `path`, outermost first: "a/b/c.rs" → ["a", "a/b"].
+/// Untracked directories are listed with a trailing slash and count as entries.
+fn parent_dirs(path: &str) -> Vec<&str> {
+    let path = path.trim_end_matches('/');
+    path.match_indices('/').map(|(i, _)| &path[..i]).collect()
+}
+
+/// One line of a diff in its color, with trailing whitespace on added lines
+/// highlighted like `git diff --check`.
+pub fn diff_line<'a>(line_type: &git::DiffLineType, content: &'a str) -> Line<'a> {
+    let color = match line_type {
+        git::DiffLineType::Added => Color::Green,
+        git::DiffLineType::Removed => Color::Red,
+        git::DiffLineType::Header => Color::Cyan,
+        git::DiffLineType::Context => Color::DarkGray,
+    };
+    if *line_type == git::DiffLineType::Added
+        && let Some(start) = git::whitespace::trailing_whitespace_start(content)
+    {
+        return Line::from(vec![
+            Span::styled(&content[..start], Style::default().fg(color)),
+            Span::styled(&content[start..], Style::default().bg(Color::Red)),
+        ]);
+    }
+    Line::from(Span::styled(content, Style::default().fg(color)))
+}
+
 pub fn render(f: &mut Frame, area: Rect, state: &mut StagingState) {
     let chunks = Layout::default()
         .direction(Direction::Horizontal)
@@ -147,64 +1078,213 @@ pub fn render(f: &mut Frame, area: Rect, state: &mut StagingState) {
         ])
         .split(area);
 
-    // File list — collect into owned data to avoid borrow conflict with list_state
-    let filtered: Vec<StagingFile> = state
-        .filtered_files()
-        .into_iter()
-        .map(|(_, f)| f.clone())
-        .collect();
-    let items: Vec<ListItem> = filtered
-        .iter()
-        .map(|file| {
-            let icon = match file.status {
-                git::FileStatus::Modified => "M",
-                git::FileStatus::Added => "A",
-                git::FileStatus::Deleted => "D",
-                git::FileStatus::Renamed => "R",
-                git::FileStatus::Copied => "C",
-                git::FileStatus::Untracked => "?",
-                git::FileStatus::Conflicted => "!",
-                git::FileStatus::Ignored => "·",
-            };
-            let icon_color = match file.status {
-                git::FileStatus::Modified => Color::Yellow,
-                git::FileStatus::Added => Color::Green,
-                git::FileStatus::Deleted => Color::Red,
-                git::FileStatus::Untracked => Color::Gray,
-                git::FileStatus::Conflicted => Color::Red,
-                _ => Color::White,
-            };
-            let staged_marker = if file.is_staged { "●" } else { "○" };
-            let staged_color = if file.is_staged {
-                Color::Green
-            } else {
-                Color::DarkGray
-            };
+    // File list — build owned items to avoid borrow conflict with list_state
+    let file_item = |file: &StagingFile, name: &str, depth: usize| -> ListItem<'static> {
+        let icon = match file.status {
+            git::FileStatus::Modified => "M",
+            git::FileStatus::Added => "A",
+            git::FileStatus::Deleted => "D",
+            git::FileStatus::Renamed => "R",
+            git::FileStatus::Copied => "C",
+            git::FileStatus::Untracked => "?",
+            git::FileStatus::Conflicted => "!",
+            git::FileStatus::Ignored => "·",
+        };
+        let icon_color = match file.status {
+            git::FileStatus::Modified => Color::Yellow,
+            git::FileStatus::Added => Color::Green,
+            git::FileStatus::Deleted => Color::Red,
+            git::FileStatus::Untracked => Color::Gray,
+            git::FileStatus::Conflicted => Color::Red,
+            _ => Color::White,
+        };
+        let has_markers = state.has_markers(file);
+        let stats = state
+            .diff_stats
+            .get(&(file.path.clone(), file.is_staged))
+            .copied();
+        let staged_marker = if file.is_staged { "●" } else { "○" };
+        let staged_color = if file.is_staged {
+            Color::Green
+        } else {
+            Color::DarkGray
+        };
 
-            ListItem::new(Line::from(vec![
-                Span::styled(
-                    format!(" {} ", staged_marker),
-                    Style::default().fg(staged_color),
-                ),
-                Span::styled(
-                    format!("{} ", icon),
-                    Style::default().fg(icon_color).add_modifier(Modifier::BOLD),
-                ),
-                Span::styled(&file.path, Style::default().fg(Color::White)),
-            ]))
-        })
-        .collect();
+        ListItem::new(Line::from(vec![
+            Span::styled(
+                if state.multi_select && state.is_marked(file) {
+                    "✓"
+                } else {
+                    ""
+                },
+                Style::default()
+                    .fg(Color::Magenta)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!("{} {} ", "  ".repeat(depth), staged_marker),
+                Style::default().fg(staged_color),
+            ),
+            Span::styled(
+                format!("{} ", icon),
+                Style::default().fg(icon_color).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                match &file.original_path {
+                    Some(old) => format!("{} → {}", old, name),
+                    None => name.to_string(),
+                },
+                Style::default().fg(Color::White),
+            ),
+            Span::styled(
+                match stats {
+                    Some((added, _)) if added > 0 => format!(" +{}", added),
+                    _ => String::new(),
+                },
+                Style::default().fg(Color::Green),
+            ),
+            Span::styled(
+                match stats {
+                    Some((_, removed)) if removed > 0 => format!(" −{}", removed),
+                    _ => String::new(),
+                },
+                Style::default().fg(Color::Red),
+            ),
+            Span::styled(
+                if has_markers {
+                    format!(" {} conflict markers", Icon::Warning)
+                } else {
+                    String::new()
+                },
+                Style::default().fg(Color::Red),
+            ),
+            Span::styled(
+                state
+                    .submodules
+                    .iter()
+                    .find(|s| s.path == file.path.trim_end_matches('/'))
+                    .map(|s| format!(" submodule: {}", s.summary()))
+                    .unwrap_or_default(),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]))
+    };
+    let items: Vec<ListItem> = if state.tree_mode {
+        state
+            .tree_rows()
+            .into_iter()
+            .map(|row| match row {
+                TreeRow::Dir {
+                    path,
+                    depth,
+                    staged,
+                    unstaged,
+                } => {
+                    let arrow = if state.expanded.contains(&path) {
+                        "▾"
+                    } else {
+                        "▸"
+                    };
+                    let name = path.rsplit('/').next().unwrap_or(&path);
+                    let mut spans = vec![
+                        Span::raw(format!("{} {} ", "  ".repeat(depth), arrow)),
+                        Span::styled(
+                            format!("{}/", name),
+                            Style::default()
+                                .fg(Color::Blue)
+                                .add_modifier(Modifier::BOLD),
+                        ),
+                    ];
+                    if staged > 0 {
+                        spans.push(Span::styled(
+                            format!(" ●{}", staged),
+                            Style::default().fg(Color::Green),
+                        ));
+                    }
+                    if unstaged > 0 {
+                        spans.push(Span::styled(
+                            format!(" ○{}", unstaged),
+                            Style::default().fg(Color::DarkGray),
+                        ));
+                    }
+                    ListItem::new(Line::from(spans))
+                }
+                TreeRow::File { index, depth } => {
+                    let file = &state.files[index];
+                    let trimmed = file.path.trim_end_matches('/');
+                    let name = &file.path[trimmed.rfind('/').map_or(0, |i| i + 1)..];
+                    file_item(file, name, depth)
+                }
+            })
+            .collect()
+    } else {
+        let filtered = state.filtered_files();
+        let mut counts = [0; SECTIONS.len()];
+        for (_, file) in &filtered {
+            counts[section(file)] += 1;
+        }
+        let mut items = Vec::new();
+        let mut selected_row = None;
+        let mut current_section = None;
+        for (index, file) in &filtered {
+            if state.group && current_section != Some(section(file)) {
+                let s = section(file);
+                current_section = Some(s);
+                items.push(ListItem::new(Line::from(Span::styled(
+                    format!("{} ({})", SECTIONS[s], counts[s]),
+                    Style::default()
+                        .fg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD),
+                ))));
+            }
+            if *index == state.selected {
+                selected_row = Some(items.len());
+            }
+            items.push(file_item(file, &file.path, 0));
+        }
+        // Headers shift the rows, so select by row rather than file index
+        state.list_state.select(selected_row);
+        items
+    };
 
     let staged_count = state.files.iter().filter(|f| f.is_staged).count();
     let total = state.files.len();
 
+    let mut title = vec![Span::styled(
+        format!(
+            " Files ({}/{} staged) · sort: {} ",
+            staged_count,
+            total,
+            state.sort.label()
+        ),
+        Style::default().fg(Color::White),
+    )];
+    if state.multi_select {
+        title.push(Span::styled(
+            format!(
+                "{} marked — s stage · u unstage · d discard · S stash · Esc done ",
+                state.marked.len()
+            ),
+            Style::default()
+                .fg(Color::Magenta)
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
+    if !state.conflict_markers.is_empty() {
+        title.push(Span::styled(
+            format!(
+                "{} {} conflict marker(s) — M to jump ",
+                Icon::Warning,
+                state.conflict_markers.len()
+            ),
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        ));
+    }
+
     let list = List::new(items)
         .block(
             Block::default()
-                .title(Span::styled(
-                    format!(" Files ({}/{} staged) ", staged_count, total),
-                    Style::default().fg(Color::White),
-                ))
+                .title(Line::from(title))
                 .borders(Borders::ALL)
                 .border_style(Style::default().fg(Color::Cyan)),
         )
@@ -215,21 +1295,16 @@ pub fn render(f: &mut Frame, area: Rect, state: &mut StagingState) {
         )
         .highlight_symbol("▶ ");
 
-    f.render_stateful_widget(list, chunks[0], &mut state.list_state);
+    f.render_stateful_widget(&list, chunks[0], &mut state.list_state);
+    render_scrollbar(f, chunks[0], list.len(), state.list_state.offset());
 
     // Diff preview
-    let diff_items: Vec<Line> = state
+    let mut diff_items: Vec<Line> = state
         .diff_lines
         .iter()
-        .map(|dl| {
-            let (color, _prefix) = match dl.line_type {
-                git::DiffLineType::Added => (Color::Green, "+"),
-                git::DiffLineType::Removed => (Color::Red, "-"),
-                git::DiffLineType::Header => (Color::Cyan, "@"),
-                git::DiffLineType::Context => (Color::DarkGray, " "),
-            };
-            Line::from(Span::styled(&dl.content, Style::default().fg(color)))
-        })
+        .map(|dl| diff_line(&dl.line_type, &dl.content))
+        .enumerate()
+        .map(|(i, line)| pane_search::highlight(line, state.diff_search.as_ref(), i))
         .collect();
 
     let diff_title = if state.hunk_mode {
@@ -241,22 +1316,166 @@ pub fn render(f: &mut Frame, area: Rect, state: &mut StagingState) {
             format!(" Hunk {}/{} ", current, total)
         }
     } else if let Some(file) = state.files.get(state.selected) {
-        format!(" Diff: {} ", file.path)
+        match &file.original_path {
+            Some(old) => format!(" Diff: {} → {} ", old, file.path),
+            None => format!(" Diff: {} ", file.path),
+        }
+    } else if let Some((dir, staged, unstaged)) = state.selected_dir() {
+        diff_items = vec![
+            Line::from(format!("{} staged · {} unstaged", staged, unstaged)),
+            Line::from(Span::styled(
+                "Space: stage/unstage directory · Enter/←/→: expand/collapse",
+                Style::default().fg(Color::DarkGray),
+            )),
+        ];
+        format!(" Directory: {}/ ", dir)
     } else {
         " Diff Preview ".to_string()
     };
 
+    let mut title = vec![Span::styled(diff_title, Style::default().fg(Color::White))];
+    if let Some(codeowners) = &state.codeowners
+        && let Some(file) = state.files.get(state.selected)
+    {
+        let owners = codeowners.owners_of(&file.path);
+        if !owners.is_empty() {
+            title.push(Span::styled(
+                format!("owned by {} ", owners.join(" ")),
+                Style::default().fg(Color::Magenta),
+            ));
+        }
+    }
+    if let Some(format) = &state.format {
+        title.push(Span::styled(
+            format!("{} ", format.label()),
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+    if let Some((old, new)) = state.eol_flip {
+        title.push(Span::styled(
+            format!(
+                "{} {} → {}: every line changed — E to fix ",
+                Icon::Warning,
+                old.label(),
+                new.label()
+            ),
+            Style::default().fg(Color::Yellow),
+        ));
+    }
+    if !state.whitespace.is_clean() {
+        title.push(Span::styled(
+            format!(
+                "{} {} — W to fix ",
+                Icon::Warning,
+                state.whitespace.summary()
+            ),
+            Style::default().fg(Color::Yellow),
+        ));
+    }
+
+    if diff_items.is_empty() && state.diff_loading {
+        diff_items.push(Line::from(Span::styled(
+            "Loading diff…",
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+
+    // Untracked files have no diff until git knows about them
+    if diff_items.is_empty()
+        && state.binary.is_none()
+        && let Some(file) = state.files.get(state.selected)
+        && file.status == git::FileStatus::Untracked
+    {
+        diff_items.push(Line::from(Span::styled(
+            "Untracked file — press N (intent-to-add) to see its diff and stage hunks",
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+
+    if diff_items.is_empty()
+        && state.binary.is_none()
+        && let Some(file) = state.files.get(state.selected)
+        && let Some(old) = &file.original_path
+    {
+        let verb = if file.status == git::FileStatus::Copied {
+            "Copied"
+        } else {
+            "Renamed"
+        };
+        diff_items.push(Line::from(Span::styled(
+            format!("{} from {} — no content changes", verb, old),
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+
+    // Binary files have no textual diff — show what changed instead
+    if let Some(summary) = &state.binary {
+        diff_items = summary
+            .lines()
+            .into_iter()
+            .map(|l| Line::from(Span::styled(l, Style::default().fg(Color::Yellow))))
+            .collect();
+        if let Some(img) = &state.image {
+            let inner_w = chunks[1].width.saturating_sub(2) as usize;
+            let inner_h = chunks[1].height.saturating_sub(2) as usize;
+            diff_items.push(Line::from(""));
+            let rows = inner_h.saturating_sub(diff_items.len());
+            diff_items.extend(image_preview::preview_lines(img, inner_w, rows));
+        }
+    }
+
     let diff = Paragraph::new(diff_items)
         .block(
             Block::default()
-                .title(Span::styled(diff_title, Style::default().fg(Color::White)))
+                .title(Line::from(title))
                 .borders(Borders::ALL)
                 .border_style(Style::default().fg(Color::DarkGray)),
         )
         .scroll((state.diff_scroll, 0))
         .wrap(Wrap { trim: false });
 
-    f.render_widget(diff, chunks[1]);
+    f.render_widget(&diff, chunks[1]);
+    render_paragraph_scrollbar(f, chunks[1], &diff, state.diff_scroll);
+}
+
+/// `E`: what to do about a change that flipped every line ending.
+fn open_eol_fix(app: &mut crate::app::App) {
+    use crate::app::{FollowUpAction, FollowUpItem, Popup};
+    use git::encoding::Eol;
+
+    let state = &app.staging_state;
+    let (Some((old, new)), Some(file)) = (state.eol_flip, state.files.get(state.selected)) else {
+        app.set_status("No line-ending flip in the selected file");
+        return;
+    };
+    let path = file.path.clone();
+    let renormalize = if new == Eol::Crlf {
+        "Add \"* text=auto\" to .gitattributes: git keeps LF and the change goes away"
+    } else {
+        "Add \"* text=auto\" to .gitattributes and re-stage: LF from now on, for everyone"
+    };
+    app.popup = Popup::FollowUp {
+        title: format!("Line endings: {} → {}", old.label(), new.label()),
+        context: format!(
+            "{} switched every line from {} to {}, so the whole file shows as changed.",
+            path,
+            old.label(),
+            new.label()
+        ),
+        suggestions: vec![
+            FollowUpItem {
+                label: "Renormalize".to_string(),
+                description: renormalize.to_string(),
+                action: FollowUpAction::RenormalizeLineEndings(path.clone()),
+            },
+            FollowUpItem {
+                label: format!("Restore {}", old.label()),
+                description: format!("Convert the file back to {} line endings", old.label()),
+                action: FollowUpAction::RestoreLineEndings(path, old),
+            },
+        ],
+        selected: 0,
+    };
 }
 
 pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()> {
@@ -269,7 +1488,8 @@ pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()
     enum DeferredStage {
         None,
         ScanFile(String),
-        ScanAll(Vec<String>),
+        /// Paths to scan, then what to stage if they're clean.
+        ScanPaths(Vec<String>, crate::app::SecretPendingAction),
     }
     let mut deferred_stage = DeferredStage::None;
 
@@ -279,50 +1499,49 @@ pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()
         // Hunk mode key handling
         if state.hunk_mode {
             match key.code {
-                KeyCode::Up | KeyCode::Char('k') => {
-                    if state.hunk_index > 0 {
-                        state.hunk_index -= 1;
-                        state.scroll_to_hunk();
-                    }
+                KeyCode::Up | KeyCode::Char('k') if state.hunk_index > 0 => {
+                    state.hunk_index -= 1;
+                    state.scroll_to_hunk();
                 }
-                KeyCode::Down | KeyCode::Char('j') => {
-                    if state.hunk_index + 1 < state.file_hunks.len() {
-                        state.hunk_index += 1;
-                        state.scroll_to_hunk();
-                    }
+                KeyCode::Down | KeyCode::Char('j')
+                    if state.hunk_index + 1 < state.file_hunks.len() =>
+                {
+                    state.hunk_index += 1;
+                    state.scroll_to_hunk();
                 }
                 KeyCode::Char(' ') => {
                     // Stage/unstage current hunk
-                    if let Some(file) = state.files.get(state.selected).cloned() {
-                        if let Some(hunk) = state.file_hunks.get(state.hunk_index).cloned() {
-                            let result = if file.is_staged {
-                                git::diff::unstage_hunk(&file.path, &hunk)
-                            } else {
-                                git::diff::stage_hunk(&file.path, &hunk)
-                            };
-                            match result {
-                                Ok(_) => {
-                                    let action = if file.is_staged { "Unstaged" } else { "Staged" };
-                                    status_msg =
-                                        Some(format!("{} hunk {}", action, state.hunk_index + 1));
-                                }
-                                Err(e) => {
-                                    let err_str = e.to_string();
-                                    status_msg = Some(format!("Hunk error: {}", err_str));
-                                    ai_error = Some(err_str);
-                                }
+                    if let Some(file) = state.files.get(state.selected).cloned()
+                        && let Some(hunk) = state.file_hunks.get(state.hunk_index).cloned()
+                    {
+                        let result = if file.is_staged {
+                            git::diff::unstage_hunk(&file.path, &hunk)
+                        } else {
+                            git::diff::stage_hunk(&file.path, &hunk)
+                        };
+                        match result {
+                            Ok(_) => {
+                                let action = if file.is_staged { "Unstaged" } else { "Staged" };
+                                status_msg =
+                                    Some(format!("{} hunk {}", action, state.hunk_index + 1));
                             }
-                            state.refresh();
-                            // Stay in hunk mode if there are still hunks
-                            if state.file_hunks.is_empty() {
-                                state.exit_hunk_mode();
-                            } else if state.hunk_index >= state.file_hunks.len() {
-                                state.hunk_index = state.file_hunks.len() - 1;
-                                state.scroll_to_hunk();
+                            Err(e) => {
+                                let err_str = e.to_string();
+                                status_msg = Some(format!("Hunk error: {}", err_str));
+                                ai_error = Some(err_str);
                             }
                         }
+                        state.refresh();
+                        // Stay in hunk mode if there are still hunks
+                        if state.file_hunks.is_empty() {
+                            state.exit_hunk_mode();
+                        } else if state.hunk_index >= state.file_hunks.len() {
+                            state.hunk_index = state.file_hunks.len() - 1;
+                            state.scroll_to_hunk();
+                        }
                     }
                 }
+                KeyCode::Esc if state.diff_search.is_some() => state.diff_search = None,
                 KeyCode::Esc | KeyCode::Char('h') => {
                     state.exit_hunk_mode();
                 }
@@ -336,26 +1555,130 @@ pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()
             }
         } else {
             match key.code {
-                KeyCode::Up | KeyCode::Char('k') => {
-                    if state.selected > 0 {
-                        state.selected -= 1;
-                        state.list_state.select(Some(state.selected));
-                        state.update_diff();
+                KeyCode::Up | KeyCode::Char('k') if state.tree_mode => {
+                    state.move_tree_cursor(false);
+                }
+                KeyCode::Down | KeyCode::Char('j') if state.tree_mode => {
+                    state.move_tree_cursor(true);
+                }
+                KeyCode::Enter | KeyCode::Left | KeyCode::Right if state.tree_mode => {
+                    state.expand_key(key.code);
+                }
+                KeyCode::Char('t') => {
+                    state.toggle_tree_mode();
+                }
+                KeyCode::Char('o') => {
+                    state.sort = state.sort.next();
+                    state.reorder();
+                    status_msg = Some(format!("Sorted by {}", state.sort.label()));
+                }
+                KeyCode::Char('g') => {
+                    state.group = !state.group;
+                    state.reorder();
+                }
+                KeyCode::Char('v') if !state.multi_select => {
+                    state.multi_select = true;
+                    status_msg =
+                        Some("Multi-select: Space to mark, V for a range, then s/u/d".to_string());
+                }
+                KeyCode::Esc | KeyCode::Char('v') if state.multi_select => {
+                    state.exit_multi_select();
+                }
+                KeyCode::Char(' ') if state.multi_select => {
+                    state.toggle_mark();
+                }
+                KeyCode::Char('V') if state.multi_select => {
+                    state.mark_range();
+                }
+                KeyCode::Char('s') if state.multi_select => {
+                    let paths: Vec<String> = state
+                        .marked_files(false)
+                        .into_iter()
+                        .map(|f| f.path.clone())
+                        .collect();
+                    if paths.is_empty() {
+                        status_msg = Some("No unstaged files marked".to_string());
+                    } else {
+                        deferred_stage = DeferredStage::ScanPaths(
+                            paths.clone(),
+                            crate::app::SecretPendingAction::StagePaths(paths),
+                        );
+                    }
+                }
+                KeyCode::Char('u') if state.multi_select => {
+                    let paths: Vec<String> = state
+                        .marked_files(true)
+                        .into_iter()
+                        .map(|f| f.path.clone())
+                        .collect();
+                    if paths.is_empty() {
+                        status_msg = Some("No staged files marked".to_string());
+                    } else {
+                        let mut args = vec!["restore", "--staged", "--"];
+                        args.extend(paths.iter().map(|p| p.as_str()));
+                        match git::run_git(&args) {
+                            Ok(_) => status_msg = Some(format!("Unstaged {} file(s)", paths.len())),
+                            Err(e) => {
+                                let err_str = e.to_string();
+                                status_msg = Some(format!("Failed to unstage: {}", err_str));
+                                ai_error = Some(err_str);
+                            }
+                        }
+                        state.exit_multi_select();
+                        state.refresh();
                     }
                 }
-                KeyCode::Down | KeyCode::Char('j') => {
-                    if state.selected + 1 < state.files.len() {
-                        state.selected += 1;
-                        state.list_state.select(Some(state.selected));
+                KeyCode::Char(' ') if state.selected_dir().is_some() => {
+                    // Stage the directory if anything in it is unstaged, otherwise unstage it
+                    let (dir, _, unstaged) = state.selected_dir().unwrap_or_default();
+                    if unstaged > 0 {
+                        let prefix = format!("{}/", dir);
+                        let paths: Vec<String> = state
+                            .files
+                            .iter()
+                            .filter(|f| !f.is_staged && f.path.starts_with(&prefix))
+                            .map(|f| f.path.clone())
+                            .collect();
+                        deferred_stage = DeferredStage::ScanPaths(
+                            paths,
+                            crate::app::SecretPendingAction::StageFile(dir),
+                        );
+                    } else {
+                        match git::run_git(&["restore", "--staged", "--", &dir]) {
+                            Ok(_) => status_msg = Some(format!("Unstaged {}/", dir)),
+                            Err(e) => {
+                                let err_str = e.to_string();
+                                status_msg = Some(format!("Failed to unstage: {}", err_str));
+                                ai_error = Some(err_str);
+                            }
+                        }
+                        state.refresh();
                         state.update_diff();
                     }
                 }
+                KeyCode::Up | KeyCode::Char('k') if state.selected > 0 => {
+                    state.selected -= 1;
+                    state.list_state.select(Some(state.selected));
+                    state.diff_debounce.schedule();
+                }
+                KeyCode::Down | KeyCode::Char('j') if state.selected + 1 < state.files.len() => {
+                    state.selected += 1;
+                    state.list_state.select(Some(state.selected));
+                    state.diff_debounce.schedule();
+                }
                 KeyCode::Char(' ') => {
                     // Toggle stage/unstage
                     if let Some(file) = state.files.get(state.selected).cloned() {
                         if file.is_staged {
                             // Unstaging — no secret scanning needed
-                            let result = git::run_git(&["restore", "--staged", &file.path]);
+                            // A rename is unstaged as a whole: the new path and the deletion
+                            let mut args = vec!["restore", "--staged", "--", &file.path];
+                            if file.status == git::FileStatus::Renamed
+                                && let Some(old) = &file.original_path
+                            {
+                                args.push(old);
+                            }
+                            let result = git::run_git(&args);
                             if let Err(e) = result {
                                 let err_str = e.to_string();
                                 status_msg = Some(format!("Error: {}", err_str));
@@ -376,7 +1699,8 @@ pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()
                         .filter(|f| !f.is_staged)
                         .map(|f| f.path.clone())
                         .collect();
-                    deferred_stage = DeferredStage::ScanAll(paths);
+                    deferred_stage =
+                        DeferredStage::ScanPaths(paths, crate::app::SecretPendingAction::StageAll);
                 }
                 KeyCode::Char('A') => {
                     // Mac-friendly alternative for Ctrl+A (stage all)
@@ -386,7 +1710,8 @@ pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()
                         .filter(|f| !f.is_staged)
                         .map(|f| f.path.clone())
                         .collect();
-                    deferred_stage = DeferredStage::ScanAll(paths);
+                    deferred_stage =
+                        DeferredStage::ScanPaths(paths, crate::app::SecretPendingAction::StageAll);
                 }
                 KeyCode::Char('r') if key.modifiers.contains(KeyModifiers::CONTROL) => {
                     // AI diff review for selected file
@@ -425,8 +1750,11 @@ pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()
                     }
                 }
                 KeyCode::Char('u') => {
-                    // Unstage all
-                    match git::run_git(&["reset", "HEAD"]) {
+                    // Unstage all (in the path scope)
+                    let scope = git::scope::pathspec();
+                    let mut args = vec!["reset", "HEAD", "--"];
+                    args.extend(scope.iter().map(|s| s.as_str()));
+                    match git::run_git(&args) {
                         Ok(_) => status_msg = Some("All files unstaged".to_string()),
                         Err(e) => {
                             let err_str = e.to_string();
@@ -443,6 +1771,20 @@ pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()
                     // Enter hunk mode
                     state.enter_hunk_mode();
                 }
+                KeyCode::Char('N') => match state.intent_to_add() {
+                    Ok(msg) => status_msg = Some(msg),
+                    Err(e) => status_msg = Some(format!("Intent-to-add failed: {}", e)),
+                },
+                KeyCode::Char('W') => match state.fix_whitespace() {
+                    Ok(msg) => status_msg = Some(msg),
+                    Err(e) => status_msg = Some(format!("Whitespace fix failed: {}", e)),
+                },
+                KeyCode::Char('M') => {
+                    status_msg = Some(match state.jump_to_next_marker() {
+                        Some(path) => format!("Conflict markers in {}", path),
+                        None => "✓ No conflict markers in staged files".to_string(),
+                    });
+                }
                 KeyCode::Char('c') => {
                     // handled below after borrow is released
                 }
@@ -463,28 +1805,27 @@ pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()
     // ── Deferred secret scanning & staging ───────────────────────────
     match deferred_stage {
         DeferredStage::ScanFile(path) => {
-            if app.config.secrets.enabled && !git::secrets::is_binary(&path) {
-                if let Ok(content) = std::fs::read_to_string(&path) {
-                    let rules = git::secrets::default_rules();
-                    let findings = git::secrets::scan_content(&path, &content, &rules);
-                    let findings: Vec<_> = findings
-                        .into_iter()
-                        .filter(|f| {
-                            !app.config.secrets.allowlist.iter().any(|a| {
-                                f.preview.contains(a)
-                                    || f.file.contains(a)
-                                    || f.rule_name.contains(a)
-                            })
+            if app.config.secrets.enabled
+                && !git::secrets::is_binary(&path)
+                && let Ok(content) = std::fs::read_to_string(&path)
+            {
+                let rules = git::secrets::default_rules();
+                let findings = git::secrets::scan_content(&path, &content, &rules);
+                let findings: Vec<_> = findings
+                    .into_iter()
+                    .filter(|f| {
+                        !app.config.secrets.allowlist.iter().any(|a| {
+                            f.preview.contains(a) || f.file.contains(a) || f.rule_name.contains(a)
                         })
-                        .collect();
-                    if !findings.is_empty() {
-                        app.popup = crate::app::Popup::SecretWarning {
-                            findings,
-                            pending_action: crate::app::SecretPendingAction::StageFile(path),
-                            selected: 0,
-                        };
-                        return Ok(());
-                    }
+                    })
+                    .collect();
+                if !findings.is_empty() {
+                    app.popup = crate::app::Popup::SecretWarning {
+                        findings,
+                        pending_action: crate::app::SecretPendingAction::StageFile(path),
+                        selected: 0,
+                    };
+                    return Ok(());
                 }
             }
             // No secrets or scanning disabled — stage normally
@@ -495,7 +1836,7 @@ pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()
             }
             app.staging_state.refresh();
         }
-        DeferredStage::ScanAll(paths) => {
+        DeferredStage::ScanPaths(paths, target) => {
             if app.config.secrets.enabled {
                 let rules = git::secrets::default_rules();
                 let mut all_findings = Vec::new();
@@ -515,15 +1856,38 @@ pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()
                 if !all_findings.is_empty() {
                     app.popup = crate::app::Popup::SecretWarning {
                         findings: all_findings,
-                        pending_action: crate::app::SecretPendingAction::StageAll,
+                        pending_action: target,
                         selected: 0,
                     };
                     return Ok(());
                 }
             }
-            // No secrets — stage all
-            match git::run_git(&["add", "-A"]) {
-                Ok(_) => status_msg = Some("All files staged".to_string()),
+            // No secrets — stage the directory, the marked files or everything
+            // (in the path scope)
+            let scope = git::scope::pathspec();
+            let mut args = vec!["add", "-A"];
+            let done = match &target {
+                crate::app::SecretPendingAction::StageFile(dir) => {
+                    args.extend(["--", dir.as_str()]);
+                    format!("Staged {}/", dir)
+                }
+                crate::app::SecretPendingAction::StagePaths(paths) => {
+                    args.push("--");
+                    args.extend(paths.iter().map(|p| p.as_str()));
+                    format!("Staged {} file(s)", paths.len())
+                }
+                _ => {
+                    args.push("--");
+                    args.extend(scope.iter().map(|s| s.as_str()));
+                    "All files staged".to_string()
+                }
+            };
+            let result = git::run_git(&args);
+            if matches!(target, crate::app::SecretPendingAction::StagePaths(_)) {
+                app.staging_state.exit_multi_select();
+            }
+            match result {
+                Ok(_) => status_msg = Some(done),
                 Err(e) => {
                     let err_str = e.to_string();
                     status_msg = Some(format!("Failed to stage: {}", err_str));
@@ -537,6 +1901,10 @@ pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()
 
     // Handle actions that need full App access (no staging_state borrow)
     match key.code {
+        // In hunk mode the diff has focus: search it rather than the file list
+        KeyCode::Char('/') if app.staging_state.hunk_mode => app.open_pane_search(),
+        KeyCode::Char('n') if app.staging_state.hunk_mode => app.step_pane_search(true),
+        KeyCode::Char('N') if app.staging_state.hunk_mode => app.step_pane_search(false),
         KeyCode::Char('/') => {
             let filter = app.staging_state.filter.clone();
             app.popup = crate::app::Popup::Input {
@@ -546,25 +1914,106 @@ pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()
                 on_submit: crate::app::InputAction::SearchFiles,
             };
         }
+        KeyCode::Char('E') => open_eol_fix(app),
+        KeyCode::Char('b') => {
+            let state = &app.staging_state;
+            match state.files.get(state.selected) {
+                _ if state.selected_dir().is_some() => {
+                    app.set_status("Select a file to blame");
+                }
+                Some(file) if file.status == git::FileStatus::Untracked => {
+                    app.set_status(format!("{} is untracked — nothing to blame yet", file.path));
+                }
+                Some(file) => {
+                    let path = file.path.clone();
+                    // In hunk mode, start at the hunk being looked at
+                    let line = state
+                        .hunk_mode
+                        .then(|| state.file_hunks.get(state.hunk_index))
+                        .flatten()
+                        .map(|h| h.new_start);
+                    crate::ui::blame::open(app, &path, None, line);
+                }
+                None => {}
+            }
+        }
         KeyCode::Char('c') => {
-            app.view = crate::app::View::Commit;
+            app.open_view(crate::app::View::Commit);
             app.commit_state.refresh();
             app.auto_suggest_if_ready();
         }
+        KeyCode::Char('S') if app.staging_state.multi_select => {
+            let state = &app.staging_state;
+            let mut marked = state.marked_files(true);
+            marked.extend(state.marked_files(false));
+            if marked.is_empty() {
+                app.set_status("No files marked");
+            } else {
+                let options = git::stash::StashOptions {
+                    // Untracked files are only stashed with --include-untracked
+                    include_untracked: marked
+                        .iter()
+                        .any(|f| f.status == git::FileStatus::Untracked),
+                    paths: marked
+                        .iter()
+                        .map(|f| f.path.clone())
+                        .collect::<std::collections::BTreeSet<_>>()
+                        .into_iter()
+                        .collect(),
+                    ..Default::default()
+                };
+                app.popup = crate::app::Popup::Input {
+                    title: format!("Stash {} Marked File(s)", options.paths.len()),
+                    prompt: "Message (empty for default): ".to_string(),
+                    value: String::new(),
+                    on_submit: crate::app::InputAction::StashPush(options),
+                };
+            }
+        }
+        KeyCode::Char('d') if app.staging_state.multi_select => {
+            // Untracked files have no changes to restore — they're left alone
+            let paths: Vec<String> = app
+                .staging_state
+                .marked_files(false)
+                .into_iter()
+                .filter(|f| f.status != git::FileStatus::Untracked)
+                .map(|f| f.path.clone())
+                .collect();
+            if paths.is_empty() {
+                app.set_status("No unstaged tracked files marked");
+            } else {
+                let mut message = format!(
+                    "Discard all changes to {} file(s)? This cannot be undone.\n",
+                    paths.len()
+                );
+                for path in paths.iter().take(MARKED_LIST_LIMIT) {
+                    message.push_str(&format!("\n  {}", path));
+                }
+                if paths.len() > MARKED_LIST_LIMIT {
+                    message.push_str(&format!(
+                        "\n  … and {} more",
+                        paths.len() - MARKED_LIST_LIMIT
+                    ));
+                }
+                message.push_str("\n\n[y] Yes  [n] No");
+                app.popup = crate::app::Popup::Confirm {
+                    title: "Discard Marked Files".to_string(),
+                    message,
+                    on_confirm: crate::app::ConfirmAction::DiscardFiles(paths),
+                };
+            }
+        }
         KeyCode::Char('d') => {
             // Discard changes for the selected unstaged file
-            if let Some(file) = app.staging_state.files.get(app.staging_state.selected) {
-                if !file.is_staged {
-                    let path = file.path.clone();
-                    app.popup = crate::app::Popup::Confirm {
-                        title: "Discard Changes".to_string(),
-                        message: format!(
-                            "Discard all changes to '{}'? This cannot be undone.",
-                            path
-                        ),
-                        on_confirm: crate::app::ConfirmAction::DiscardFile(path),
-                    };
-                }
+            if let Some(file) = app.staging_state.files.get(app.staging_state.selected)
+                && !file.is_staged
+            {
+                let path = file.path.clone();
+                app.popup = crate::app::Popup::Confirm {
+                    title: "Discard Changes".to_string(),
+                    message: format!("Discard all changes to '{}'? This cannot be undone.", path),
+                    on_confirm: crate::app::ConfirmAction::DiscardFile(path),
+                };
             }
         }
         _ => {}
@@ -584,3 +2033,272 @@ pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()
 
     Ok(())
 }
+
+/// The row of the diff (hunk headers included) showing `line` of the new
+/// version, or the nearest hunk after it.
+fn diff_row_of_line(hunks: &[git::diff::Hunk], line: u32) -> usize {
+    let mut row = 0;
+    for hunk in hunks {
+        if line < hunk.new_start + hunk.new_count {
+            if line <= hunk.new_start {
+                return row;
+            }
+            let mut new_line = hunk.new_start;
+            for (i, l) in hunk.lines.iter().enumerate() {
+                match l.line_type {
+                    git::DiffLineType::Context | git::DiffLineType::Added => {
+                        if new_line == line {
+                            return row + i;
+                        }
+                        new_line += 1;
+                    }
+                    _ => {}
+                }
+            }
+            return row;
+        }
+        row += hunk.lines.len();
+    }
+    row.saturating_sub(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(path: &str, is_staged: bool) -> StagingFile {
+        StagingFile {
+            path: path.to_string(),
+            status: git::FileStatus::Modified,
+            is_staged,
+            original_path: None,
+        }
+    }
+
+    #[test]
+    fn test_diff_row_of_line() {
+        let line = |line_type, content: &str| git::DiffLine {
+            line_type,
+            content: content.to_string(),
+        };
+        let hunk = |new_start, new_count| git::diff::Hunk {
+            header: String::new(),
+            old_start: new_start,
+            old_count: new_count,
+            new_start,
+            new_count,
+            lines: vec![
+                line(git::DiffLineType::Header, "@@"),
+                line(git::DiffLineType::Context, " a"),
+                line(git::DiffLineType::Removed, "-b"),
+                line(git::DiffLineType::Added, "+c"),
+                line(git::DiffLineType::Context, " d"),
+            ],
+        };
+        let hunks = [hunk(10, 3), hunk(40, 3)];
+        assert_eq!(diff_row_of_line(&hunks, 1), 0);
+        assert_eq!(diff_row_of_line(&hunks, 11), 3);
+        assert_eq!(diff_row_of_line(&hunks, 12), 4);
+        // Between hunks: the next one's header
+        assert_eq!(diff_row_of_line(&hunks, 20), 5);
+        assert_eq!(diff_row_of_line(&hunks, 41), 8);
+        assert_eq!(diff_row_of_line(&hunks, 99), 9);
+    }
+
+    #[test]
+    fn test_parent_dirs() {
+        assert_eq!(parent_dirs("a/b/c.rs"), vec!["a", "a/b"]);
+        assert_eq!(parent_dirs("gen/out/"), vec!["gen"]);
+        assert!(parent_dirs("README.md").is_empty());
+    }
+
+    #[test]
+    fn test_tree_rows_collapse_and_counts() {
+        let mut state = StagingState {
+            files: vec![
+                file("src/gen/a.rs", true),
+                file("README.md", false),
+                file("src/gen/b.rs", false),
+                file("src/main.rs", false),
+            ],
+            ..Default::default()
+        };
+        let dir = |path: &str, depth, staged, unstaged| TreeRow::Dir {
+            path: path.to_string(),
+            depth,
+            staged,
+            unstaged,
+        };
+
+        // Collapsed by default: one row per top-level directory
+        assert_eq!(
+            state.tree_rows(),
+            vec![TreeRow::File { index: 1, depth: 0 }, dir("src", 0, 1, 2),]
+        );
+
+        state.expanded.insert("src".to_string());
+        assert_eq!(
+            state.tree_rows(),
+            vec![
+                TreeRow::File { index: 1, depth: 0 },
+                dir("src", 0, 1, 2),
+                dir("src/gen", 1, 1, 1),
+                TreeRow::File { index: 3, depth: 1 },
+            ]
+        );
+
+        state.expanded.insert("src/gen".to_string());
+        assert_eq!(state.tree_rows().len(), 6);
+    }
+
+    #[test]
+    fn test_mark_range_and_marked_files() {
+        let mut state = StagingState {
+            files: vec![
+                file("a.rs", true),
+                file("b.rs", false),
+                file("c.rs", false),
+                file("d.rs", false),
+            ],
+            multi_select: true,
+            ..Default::default()
+        };
+        state.selected = 1;
+        state.toggle_mark();
+        state.selected = 3;
+        state.mark_range();
+        let unstaged: Vec<&str> = state
+            .marked_files(false)
+            .iter()
+            .map(|f| f.path.as_str())
+            .collect();
+        assert_eq!(unstaged, vec!["b.rs", "c.rs", "d.rs"]);
+        assert!(state.marked_files(true).is_empty());
+
+        // Space on a marked file unmarks it
+        state.selected = 2;
+        state.toggle_mark();
+        assert_eq!(state.marked.len(), 2);
+        state.exit_multi_select();
+        assert!(state.marked.is_empty() && !state.multi_select);
+    }
+
+    #[test]
+    fn test_sort_and_group() {
+        let mut untracked = file("a_new.rs", false);
+        untracked.status = git::FileStatus::Untracked;
+        let mut conflicted = file("z.rs", false);
+        conflicted.status = git::FileStatus::Conflicted;
+        let mut state = StagingState {
+            files: vec![
+                file("m.rs", false),
+                untracked,
+                file("b.rs", true),
+                conflicted,
+            ],
+            group: true,
+            ..Default::default()
+        };
+        let paths = |state: &StagingState| -> Vec<String> {
+            state.files.iter().map(|f| f.path.clone()).collect()
+        };
+
+        state.sort_files();
+        assert_eq!(paths(&state), vec!["b.rs", "m.rs", "z.rs", "a_new.rs"]);
+
+        state.sort = SortOrder::Status;
+        state.sort_files();
+        assert_eq!(paths(&state), vec!["b.rs", "z.rs", "m.rs", "a_new.rs"]);
+
+        state.group = false;
+        state.sort = SortOrder::Path;
+        state.sort_files();
+        assert_eq!(paths(&state), vec!["a_new.rs", "b.rs", "m.rs", "z.rs"]);
+
+        assert_eq!(SortOrder::from_config("modified"), SortOrder::Modified);
+        assert_eq!(SortOrder::from_config("bogus"), SortOrder::Path);
+    }
+
+    #[test]
+    fn test_diff_arrives_for_the_selected_file_only() {
+        let loaded = |path: &str, at| LoadedDiff {
+            at,
+            path: path.to_string(),
+            is_staged: false,
+            hunks: vec![git::diff::Hunk {
+                header: String::new(),
+                old_start: 1,
+                old_count: 1,
+                new_start: 1,
+                new_count: 1,
+                lines: vec![git::DiffLine {
+                    line_type: git::DiffLineType::Added,
+                    content: format!("+{}", path),
+                }],
+            }],
+            whitespace: Default::default(),
+            format: None,
+            eol_flip: None,
+            binary: None,
+            image: None,
+        };
+        let mut state = StagingState {
+            files: vec![file("a.rs", false), file("b.rs", false)],
+            ..Default::default()
+        };
+        state.update_diff();
+        assert!(state.diff_loading);
+        assert!(matches!(
+            state.diff_read.take(),
+            Some(crate::tasks::Read::Diff { path, is_staged: false, .. }) if path == "a.rs"
+        ));
+
+        // A diff for a file the cursor has left is dropped
+        let first = Instant::now();
+        state.apply_diff(loaded("b.rs", first));
+        assert!(state.diff_lines.is_empty());
+        assert!(state.diff_loading);
+
+        let second = first + Duration::from_millis(10);
+        state.apply_diff(loaded("a.rs", second));
+        assert!(!state.diff_loading);
+        assert_eq!(state.diff_lines[0].content, "+a.rs");
+        assert_eq!(state.file_hunks.len(), 1);
+
+        // So is one asked for before the diff shown
+        let mut older = loaded("a.rs", first);
+        older.hunks.clear();
+        state.apply_diff(older);
+        assert_eq!(state.diff_lines.len(), 1);
+    }
+
+    #[test]
+    fn test_poll_diff_stats_streams_and_drops_stale() {
+        let diff = |path: &str, additions, deletions| git::diff::FileDiff {
+            path: path.to_string(),
+            old_path: None,
+            hunks: Vec::new(),
+            additions,
+            deletions,
+        };
+        let mut state = StagingState::default();
+        state
+            .diff_stats
+            .insert(("gone.rs".to_string(), false), (1, 1));
+        let (tx, rx) = mpsc::channel();
+        state.stats_rx = Some(rx);
+
+        // Counts show up as they arrive, before the stream ends
+        tx.send((false, diff("a.rs", 3, 1))).unwrap();
+        state.poll_diff_stats();
+        assert_eq!(state.diff_stats[&("a.rs".to_string(), false)], (3, 1));
+        assert!(state.stats_rx.is_some());
+
+        tx.send((true, diff("a.rs", 2, 0))).unwrap();
+        drop(tx);
+        state.poll_diff_stats();
+        assert!(state.stats_rx.is_none());
+        assert_eq!(state.diff_stats.len(), 2);
+        assert_eq!(state.diff_stats[&("a.rs".to_string(), true)], (2, 0));
+    }
+}
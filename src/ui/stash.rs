@@ -1,15 +1,20 @@
-//! Stash management UI — list, push, pop, apply, drop stash entries.
+//! Stash management UI — list, push, pop, apply, drop stash entries, and browse
+//! and restore safety-net snapshots.
 
 use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::{
+    Frame,
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap},
-    Frame,
 };
 
 use crate::git;
+use crate::ui::utils::{Debounce, render_paragraph_scrollbar, render_scrollbar};
+
+/// Snapshots listed in the browser.
+const SNAPSHOT_LIST_LIMIT: usize = 100;
 
 #[derive(Default)]
 pub struct StashState {
@@ -18,30 +23,64 @@ pub struct StashState {
     pub list_state: ListState,
     pub diff_text: String,
     pub diff_scroll: u16,
+    /// Flags for the next stash push (`u`, `i`, `s` toggle them).
+    pub options: git::stash::StashOptions,
+    /// List safety-net snapshots instead of stashes (`S`).
+    pub show_snapshots: bool,
+    pub snapshots: Vec<git::snapshot::Snapshot>,
+    /// Diff reload deferred while moving through the list.
+    pub diff_debounce: Debounce,
 }
 
 impl StashState {
     pub fn refresh(&mut self) {
         self.entries = git::stash::list_stashes().unwrap_or_default();
-        if self.selected >= self.entries.len() && !self.entries.is_empty() {
-            self.selected = self.entries.len() - 1;
+        if self.show_snapshots {
+            self.snapshots = git::snapshot::list_snapshots(SNAPSHOT_LIST_LIMIT);
         }
-        self.list_state.select(if self.entries.is_empty() {
-            None
-        } else {
-            Some(self.selected)
-        });
+        let len = self.len();
+        if self.selected >= len && len > 0 {
+            self.selected = len - 1;
+        }
+        self.list_state
+            .select(if len == 0 { None } else { Some(self.selected) });
         self.update_diff();
     }
 
+    /// Number of rows in the current list (stashes or snapshots).
+    fn len(&self) -> usize {
+        if self.show_snapshots {
+            self.snapshots.len()
+        } else {
+            self.entries.len()
+        }
+    }
+
+    /// Load the diff once navigation has paused (or right away with `force`).
+    /// Returns whether it was loaded.
+    pub fn settle_diff(&mut self, force: bool) -> bool {
+        let due = self.diff_debounce.fire(force);
+        if due {
+            self.update_diff();
+        }
+        due
+    }
+
     fn update_diff(&mut self) {
+        self.diff_debounce.cancel();
         self.diff_text.clear();
         self.diff_scroll = 0;
 
-        if let Some(entry) = self.entries.get(self.selected) {
-            if let Ok(diff) = git::stash::stash_show(entry.index) {
+        if self.show_snapshots {
+            if let Some(snapshot) = self.snapshots.get(self.selected)
+                && let Ok(diff) = git::snapshot::snapshot_diff(snapshot)
+            {
                 self.diff_text = diff;
             }
+        } else if let Some(entry) = self.entries.get(self.selected)
+            && let Ok(diff) = git::stash::stash_show(entry.index)
+        {
+            self.diff_text = diff;
         }
     }
 }
@@ -56,35 +95,91 @@ pub fn render(f: &mut Frame, area: Rect, state: &mut StashState) {
         .split(area);
 
     // Stash list
-    let items: Vec<ListItem> = state
-        .entries
-        .iter()
-        .map(|entry| {
-            ListItem::new(Line::from(vec![
-                Span::styled(
-                    format!(" {} ", entry.index),
-                    Style::default()
-                        .fg(Color::Magenta)
-                        .add_modifier(Modifier::BOLD),
-                ),
-                Span::styled(
-                    if entry.branch.is_empty() {
-                        String::new()
-                    } else {
-                        format!("[{}] ", entry.branch)
-                    },
-                    Style::default().fg(Color::Cyan),
-                ),
-                Span::styled(&entry.message, Style::default().fg(Color::White)),
-            ]))
-        })
-        .collect();
+    let items: Vec<ListItem> = if state.show_snapshots {
+        state
+            .snapshots
+            .iter()
+            .map(|snap| {
+                let when = jiff::Timestamp::from_second(snap.time)
+                    .map(crate::ui::datetime::format_timestamp)
+                    .unwrap_or_default();
+                ListItem::new(Line::from(vec![
+                    Span::styled(
+                        format!(" {} ", snap.short_hash),
+                        Style::default()
+                            .fg(Color::Magenta)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(
+                        format!("[{}] ", snap.branch),
+                        Style::default().fg(Color::Cyan),
+                    ),
+                    Span::styled(when, Style::default().fg(Color::White)),
+                ]))
+            })
+            .collect()
+    } else {
+        state
+            .entries
+            .iter()
+            .map(|entry| {
+                ListItem::new(Line::from(vec![
+                    Span::styled(
+                        format!(" {} ", entry.index),
+                        Style::default()
+                            .fg(Color::Magenta)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                    Span::styled(
+                        if entry.branch.is_empty() {
+                            String::new()
+                        } else {
+                            format!("[{}] ", entry.branch)
+                        },
+                        Style::default().fg(Color::Cyan),
+                    ),
+                    Span::styled(&entry.message, Style::default().fg(Color::White)),
+                ]))
+            })
+            .collect()
+    };
 
-    let title = format!(" Stash ({}) ", state.entries.len());
+    let title = if state.show_snapshots {
+        format!(" Snapshots ({}) ", state.snapshots.len())
+    } else {
+        format!(" Stash ({}) ", state.entries.len())
+    };
+    let flag = |key: &str, label: &str, on: bool| {
+        Span::styled(
+            format!("[{}] {} ", key, label),
+            if on {
+                Style::default()
+                    .fg(Color::Green)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::DarkGray)
+            },
+        )
+    };
+    let options = &state.options;
     let list = List::new(items)
         .block(
             Block::default()
                 .title(Span::styled(title, Style::default().fg(Color::White)))
+                .title_bottom(if state.show_snapshots {
+                    Line::from(Span::styled(
+                        " n snapshot now · r restore · D delete all · S stashes ",
+                        Style::default().fg(Color::DarkGray),
+                    ))
+                } else {
+                    Line::from(vec![
+                        Span::raw(" "),
+                        flag("u", "untracked", options.include_untracked),
+                        flag("i", "keep index", options.keep_index),
+                        flag("s", "staged only", options.staged_only),
+                        Span::styled("S snapshots ", Style::default().fg(Color::DarkGray)),
+                    ])
+                })
                 .borders(Borders::ALL)
                 .border_style(Style::default().fg(Color::Magenta)),
         )
@@ -95,7 +190,8 @@ pub fn render(f: &mut Frame, area: Rect, state: &mut StashState) {
         )
         .highlight_symbol("▶ ");
 
-    f.render_stateful_widget(list, chunks[0], &mut state.list_state);
+    f.render_stateful_widget(&list, chunks[0], &mut state.list_state);
+    render_scrollbar(f, chunks[0], list.len(), state.list_state.offset());
 
     // Diff preview — use structured diff coloring
     let diff_lines: Vec<Line> = state
@@ -107,7 +203,12 @@ pub fn render(f: &mut Frame, area: Rect, state: &mut StashState) {
         })
         .collect();
 
-    let diff_title = if let Some(entry) = state.entries.get(state.selected) {
+    let diff_title = if state.show_snapshots {
+        match state.snapshots.get(state.selected) {
+            Some(snap) => format!(" Snapshot {} ", snap.short_hash),
+            None => " Snapshot Diff ".to_string(),
+        }
+    } else if let Some(entry) = state.entries.get(state.selected) {
         format!(" stash@{{{}}} ", entry.index)
     } else {
         " Stash Diff ".to_string()
@@ -123,10 +224,24 @@ pub fn render(f: &mut Frame, area: Rect, state: &mut StashState) {
         .scroll((state.diff_scroll, 0))
         .wrap(Wrap { trim: false });
 
-    f.render_widget(diff, chunks[1]);
+    f.render_widget(&diff, chunks[1]);
+    render_paragraph_scrollbar(f, chunks[1], &diff, state.diff_scroll);
 
     // Keybinding hints at bottom if area is big enough
-    if area.height > 5 && state.entries.is_empty() {
+    if area.height > 5 && state.show_snapshots && state.snapshots.is_empty() {
+        let hint = Paragraph::new(Line::from(Span::styled(
+            " No snapshots yet. Press n to take one, or set [snapshot] enabled = true.",
+            Style::default().fg(Color::DarkGray),
+        )))
+        .wrap(Wrap { trim: false });
+        let hint_area = Rect {
+            x: chunks[0].x + 1,
+            y: chunks[0].y + 2,
+            width: chunks[0].width.saturating_sub(2),
+            height: 2,
+        };
+        f.render_widget(hint, hint_area);
+    } else if area.height > 5 && !state.show_snapshots && state.entries.is_empty() {
         let hint = Paragraph::new(Line::from(vec![
             Span::styled(
                 " No stash entries. Press ",
@@ -152,24 +267,51 @@ pub fn render(f: &mut Frame, area: Rect, state: &mut StashState) {
 pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()> {
     let mut status_msg: Option<String> = None;
     let mut ai_error: Option<String> = None;
+    let keep_snapshots = app.config.snapshot.keep;
 
     {
         let state = &mut app.stash_state;
 
         match key.code {
-            KeyCode::Up | KeyCode::Char('k') => {
-                if state.selected > 0 {
-                    state.selected -= 1;
-                    state.list_state.select(Some(state.selected));
-                    state.update_diff();
-                }
+            KeyCode::Up | KeyCode::Char('k') if state.selected > 0 => {
+                state.selected -= 1;
+                state.list_state.select(Some(state.selected));
+                state.diff_debounce.schedule();
+            }
+            KeyCode::Down | KeyCode::Char('j') if state.selected + 1 < state.len() => {
+                state.selected += 1;
+                state.list_state.select(Some(state.selected));
+                state.diff_debounce.schedule();
+            }
+            KeyCode::Char('S') => {
+                state.show_snapshots = !state.show_snapshots;
+                state.selected = 0;
+                state.refresh();
             }
-            KeyCode::Down | KeyCode::Char('j') => {
-                if state.selected + 1 < state.entries.len() {
-                    state.selected += 1;
-                    state.list_state.select(Some(state.selected));
-                    state.update_diff();
+            KeyCode::Char('n') if state.show_snapshots => {
+                match git::snapshot::take_snapshot(keep_snapshots) {
+                    Ok(Some(hash)) => {
+                        status_msg = Some(format!("Took snapshot {}", &hash[..hash.len().min(7)]))
+                    }
+                    Ok(None) => {
+                        status_msg = Some("Nothing changed since the last snapshot".to_string())
+                    }
+                    Err(e) => {
+                        let err_str = e.to_string();
+                        status_msg = Some(format!("Snapshot failed: {}", err_str));
+                        ai_error = Some(err_str);
+                    }
                 }
+                state.selected = 0;
+                state.refresh();
+            }
+            KeyCode::Char('r') | KeyCode::Enter | KeyCode::Char('D') if state.show_snapshots => {
+                // handled below (needs confirm popup)
+            }
+            _ if state.show_snapshots
+                && !matches!(key.code, KeyCode::PageUp | KeyCode::PageDown) =>
+            {
+                // Stash actions don't apply to snapshots
             }
             KeyCode::Char('p') => {
                 // Pop stash
@@ -213,6 +355,26 @@ pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()
                     state.refresh();
                 }
             }
+            KeyCode::Char('u') => {
+                state.options.include_untracked = !state.options.include_untracked;
+                if state.options.include_untracked {
+                    state.options.staged_only = false;
+                }
+            }
+            KeyCode::Char('i') => {
+                state.options.keep_index = !state.options.keep_index;
+                if state.options.keep_index {
+                    state.options.staged_only = false;
+                }
+            }
+            KeyCode::Char('s') => {
+                // --staged can't be combined with the other two
+                state.options.staged_only = !state.options.staged_only;
+                if state.options.staged_only {
+                    state.options.include_untracked = false;
+                    state.options.keep_index = false;
+                }
+            }
             KeyCode::Char('n') => {
                 // handled below (needs popup for message input)
             }
@@ -229,6 +391,40 @@ pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()
         }
     } // release mutable borrow
 
+    // Actions needing full App access
+    if app.stash_state.show_snapshots {
+        match key.code {
+            KeyCode::Char('r') | KeyCode::Enter => {
+                if let Some(snap) = app.stash_state.snapshots.get(app.stash_state.selected) {
+                    app.popup = crate::app::Popup::Confirm {
+                        title: "Restore Snapshot".to_string(),
+                        message: format!(
+                            "Restore the working tree to snapshot {} [{}]?\nUntracked files created since are removed. The index is left alone and the current state is snapshotted first.\n\n[y] Yes  [n] No",
+                            snap.short_hash, snap.branch
+                        ),
+                        on_confirm: crate::app::ConfirmAction::RestoreSnapshot(snap.hash.clone()),
+                    };
+                }
+            }
+            KeyCode::Char('D') if !app.stash_state.snapshots.is_empty() => {
+                app.popup = crate::app::Popup::Confirm {
+                    title: "Delete All Snapshots".to_string(),
+                    message: "Delete every safety-net snapshot? This cannot be undone.\n\n[y] Yes  [n] No"
+                        .to_string(),
+                    on_confirm: crate::app::ConfirmAction::ClearSnapshots,
+                };
+            }
+            _ => {}
+        }
+        if let Some(msg) = status_msg {
+            app.set_status(&msg);
+        }
+        if let Some(err) = ai_error {
+            app.start_ai_error_explain(err);
+        }
+        return Ok(());
+    }
+
     // Actions needing full App access
     match key.code {
         KeyCode::Char('n') => {
@@ -236,20 +432,18 @@ pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()
                 title: "Stash Push".to_string(),
                 prompt: "Message (empty for default): ".to_string(),
                 value: String::new(),
-                on_submit: crate::app::InputAction::StashPush,
+                on_submit: crate::app::InputAction::StashPush(app.stash_state.options.clone()),
             };
         }
-        KeyCode::Char('D') => {
-            if !app.stash_state.entries.is_empty() {
-                app.popup = crate::app::Popup::Confirm {
-                    title: "Clear All Stashes".to_string(),
-                    message: format!(
-                        "Drop all {} stash entries? This cannot be undone.",
-                        app.stash_state.entries.len()
-                    ),
-                    on_confirm: crate::app::ConfirmAction::ClearStash,
-                };
-            }
+        KeyCode::Char('D') if !app.stash_state.entries.is_empty() => {
+            app.popup = crate::app::Popup::Confirm {
+                title: "Clear All Stashes".to_string(),
+                message: format!(
+                    "Drop all {} stash entries? This cannot be undone.",
+                    app.stash_state.entries.len()
+                ),
+                on_confirm: crate::app::ConfirmAction::ClearStash,
+            };
         }
         _ => {}
     }
@@ -0,0 +1,154 @@
+//! The bar along the bottom of every view: where the repository stands, what
+//! is running in the background, and the last status message.
+
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+
+use crate::git;
+use crate::ui::icons::Icon;
+use crate::ui::utils::{display_width, truncate_to_width};
+
+const SPINNER_FRAMES: &[char] = &['⣾', '⣽', '⣻', '⢿', '⡿', '⣟', '⣯', '⣷'];
+
+/// The repository facts shown in the bar, reloaded with the current view.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RepoSummary {
+    pub branch: String,
+    pub has_upstream: bool,
+    pub ahead: u32,
+    pub behind: u32,
+    pub staged: usize,
+    /// Unstaged and untracked files.
+    pub unstaged: usize,
+    pub conflicts: usize,
+    /// A merge, rebase, cherry-pick or revert waiting to be finished.
+    pub operation: Option<String>,
+}
+
+impl RepoSummary {
+    pub fn load() -> Self {
+        let Ok(status) = git::status::get_status() else {
+            return Self::default();
+        };
+        Self {
+            branch: status.branch,
+            has_upstream: status.upstream.is_some(),
+            ahead: status.ahead,
+            behind: status.behind,
+            staged: status.staged.len(),
+            unstaged: status.unstaged.len() + status.untracked.len(),
+            conflicts: status.conflicts.len(),
+            operation: git::merge::get_merge_state().map(|m| m.merge_type.to_string()),
+        }
+    }
+}
+
+/// What the bar shows besides the repository summary.
+pub struct Activity<'a> {
+    /// Title of a running background job, if any.
+    pub job: Option<&'a str>,
+    pub spinner_frame: usize,
+    pub message: Option<&'a str>,
+}
+
+/// The summary as styled spans, in the order they appear.
+fn summary_spans(summary: &RepoSummary) -> Vec<Span<'static>> {
+    let mut spans = Vec::new();
+    if !summary.branch.is_empty() {
+        spans.push(Span::styled(
+            format!(" ⎇ {}", summary.branch),
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
+    if summary.ahead > 0 {
+        spans.push(Span::styled(
+            format!(" ⬆{}", summary.ahead),
+            Style::default().fg(Color::Green),
+        ));
+    }
+    if summary.behind > 0 {
+        spans.push(Span::styled(
+            format!(" ⬇{}", summary.behind),
+            Style::default().fg(Color::Yellow),
+        ));
+    }
+    if !summary.has_upstream && !summary.branch.is_empty() {
+        spans.push(Span::styled(
+            " (no upstream)",
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+    spans.push(Span::styled(" │", Style::default().fg(Color::DarkGray)));
+    spans.push(Span::styled(
+        format!(" ●{} staged", summary.staged),
+        Style::default().fg(if summary.staged > 0 {
+            Color::Green
+        } else {
+            Color::DarkGray
+        }),
+    ));
+    spans.push(Span::styled(
+        format!(" ✚{} unstaged", summary.unstaged),
+        Style::default().fg(if summary.unstaged > 0 {
+            Color::Yellow
+        } else {
+            Color::DarkGray
+        }),
+    ));
+    if summary.conflicts > 0 {
+        spans.push(Span::styled(
+            format!(" {} {} conflicted", Icon::Conflict, summary.conflicts),
+            Style::default().fg(Color::Red),
+        ));
+    }
+    if let Some(op) = &summary.operation {
+        spans.push(Span::styled(" │", Style::default().fg(Color::DarkGray)));
+        spans.push(Span::styled(
+            format!(" {} in progress", op.to_uppercase()),
+            Style::default()
+                .fg(Color::Black)
+                .bg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
+    spans
+}
+
+pub fn render(f: &mut Frame, area: Rect, summary: &RepoSummary, activity: &Activity) {
+    let mut spans = summary_spans(summary);
+    if let Some(job) = activity.job {
+        let spinner = SPINNER_FRAMES[activity.spinner_frame % SPINNER_FRAMES.len()];
+        spans.push(Span::styled(" │", Style::default().fg(Color::DarkGray)));
+        spans.push(Span::styled(
+            format!(" {} {}", spinner, job),
+            Style::default().fg(Color::Magenta),
+        ));
+    }
+    if let Some(message) = activity.message {
+        let used: usize = spans.iter().map(|s| display_width(&s.content)).sum();
+        let room = (area.width as usize).saturating_sub(used + 3);
+        if room > 0 {
+            let color = if message.starts_with("Error") {
+                Color::Red
+            } else {
+                Color::Yellow
+            };
+            spans.push(Span::styled(" │ ", Style::default().fg(Color::DarkGray)));
+            spans.push(Span::styled(
+                truncate_to_width(message, room),
+                Style::default().fg(color),
+            ));
+        }
+    }
+    f.render_widget(
+        Paragraph::new(Line::from(spans)).style(Style::default().bg(Color::Rgb(30, 32, 40))),
+        area,
+    );
+}
@@ -0,0 +1,333 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table, TableState},
+};
+
+use crate::git;
+use crate::ui::datetime;
+use crate::ui::icons::Icon;
+use crate::ui::utils::render_scrollbar;
+use crate::ui::zen;
+
+/// Tags are pushed to and deleted from this remote.
+const REMOTE: &str = "origin";
+
+#[derive(Default)]
+pub struct TagsState {
+    pub tags: Vec<git::tag::Tag>,
+    pub selected: usize,
+    pub table_state: TableState,
+}
+
+impl TagsState {
+    pub fn refresh(&mut self) {
+        self.tags = git::tag::list().unwrap_or_default();
+        self.selected = self.selected.min(self.tags.len().saturating_sub(1));
+        self.table_state
+            .select((!self.tags.is_empty()).then_some(self.selected));
+    }
+
+    fn selected_tag(&self) -> Option<&git::tag::Tag> {
+        self.tags.get(self.selected)
+    }
+}
+
+pub fn render(f: &mut Frame, area: Rect, state: &mut TagsState) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(10),                // Table
+            Constraint::Length(zen::chrome(3)), // Hints
+        ])
+        .split(area);
+
+    let header = Row::new(["Tag", "Type", "Commit", "When", "Message"].map(|h| {
+        Cell::from(h).style(
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )
+    }))
+    .height(1);
+
+    let rows: Vec<Row> = state
+        .tags
+        .iter()
+        .map(|t| {
+            let (kind, kind_color) = if t.annotated {
+                ("annotated", Color::Green)
+            } else {
+                ("lightweight", Color::DarkGray)
+            };
+            Row::new(vec![
+                Cell::from(t.name.as_str()).style(
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Cell::from(kind).style(Style::default().fg(kind_color)),
+                Cell::from(t.commit.as_str()).style(Style::default().fg(Color::Magenta)),
+                Cell::from(datetime::format_iso(&t.date_iso, &t.date_iso))
+                    .style(Style::default().fg(Color::DarkGray)),
+                Cell::from(t.subject.as_str()).style(Style::default().fg(Color::White)),
+            ])
+        })
+        .collect();
+
+    let total = rows.len();
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Percentage(20),
+            Constraint::Length(12),
+            Constraint::Length(10),
+            Constraint::Percentage(15),
+            Constraint::Percentage(45),
+        ],
+    )
+    .header(header)
+    .block(
+        Block::default()
+            .title(Span::styled(
+                format!(" Tags ({}) ", total),
+                Style::default()
+                    .fg(Color::White)
+                    .add_modifier(Modifier::BOLD),
+            ))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Yellow)),
+    )
+    .row_highlight_style(Style::default().bg(Color::DarkGray))
+    .highlight_symbol("▶ ");
+
+    f.render_stateful_widget(table, chunks[0], &mut state.table_state);
+    render_scrollbar(f, chunks[0], total, state.table_state.offset());
+
+    if total == 0 {
+        let inner = chunks[0].inner(ratatui::layout::Margin::new(2, 2));
+        f.render_widget(
+            Paragraph::new(Span::styled(
+                "No tags yet — press n to tag HEAD",
+                Style::default().fg(Color::DarkGray),
+            )),
+            inner,
+        );
+    }
+
+    let hints = Paragraph::new(Line::from(vec![
+        Span::styled(" [n]", Style::default().fg(Color::Cyan)),
+        Span::raw(" New tag "),
+        Span::styled("[P]", Style::default().fg(Color::Cyan)),
+        Span::raw(" Push "),
+        Span::styled("[d]", Style::default().fg(Color::Cyan)),
+        Span::raw(" Delete "),
+        Span::styled("[D]", Style::default().fg(Color::Cyan)),
+        Span::raw(" Delete on remote "),
+        Span::styled("[R]", Style::default().fg(Color::Cyan)),
+        Span::raw(" GitHub release "),
+        Span::styled("[q]", Style::default().fg(Color::DarkGray)),
+        Span::raw(" Back"),
+    ]))
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::DarkGray)),
+    );
+    f.render_widget(hints, chunks[1]);
+}
+
+pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()> {
+    use crate::app::{ConfirmAction, InputAction, Popup};
+
+    let state = &mut app.tags_state;
+    match key.code {
+        KeyCode::Up | KeyCode::Char('k') if state.selected > 0 => {
+            state.selected -= 1;
+            state.table_state.select(Some(state.selected));
+        }
+        KeyCode::Down | KeyCode::Char('j') if state.selected + 1 < state.tags.len() => {
+            state.selected += 1;
+            state.table_state.select(Some(state.selected));
+        }
+        KeyCode::Char('n') => {
+            app.popup = Popup::Input {
+                title: "New Tag".to_string(),
+                prompt: "Tag name (on HEAD): ".to_string(),
+                value: String::new(),
+                on_submit: InputAction::CreateTag,
+            };
+        }
+        KeyCode::Char('d') => {
+            if let Some(tag) = state.selected_tag() {
+                app.popup = Popup::Confirm {
+                    title: "Delete Tag".to_string(),
+                    message: format!(
+                        "Delete tag '{}' here? A pushed tag stays on {} (D deletes it there).",
+                        tag.name, REMOTE
+                    ),
+                    on_confirm: ConfirmAction::DeleteTag(tag.name.clone()),
+                };
+            }
+        }
+        KeyCode::Char('D') => {
+            if let Some(tag) = state.selected_tag() {
+                app.popup = Popup::Confirm {
+                    title: "Delete Tag on Remote".to_string(),
+                    message: format!(
+                        "Delete tag '{}' from {}? Anyone who fetched it keeps their copy.",
+                        tag.name, REMOTE
+                    ),
+                    on_confirm: ConfirmAction::DeleteRemoteTag(tag.name.clone()),
+                };
+            }
+        }
+        KeyCode::Char('P') => {
+            if let Some(tag) = state.selected_tag() {
+                let name = tag.name.clone();
+                app.start_output_job(crate::ui::output_pane::OutputJob {
+                    title: format!("Pushing tag {}", name),
+                    commands: vec![git::tag::push_args(REMOTE, &name)],
+                    success_msg: format!("✓ Pushed tag {} to {}", name, REMOTE),
+                    failure_msg: format!("Pushing tag {} failed", name),
+                });
+            }
+        }
+        KeyCode::Char('R') => {
+            if let Some(tag) = state.selected_tag() {
+                let name = tag.name.clone();
+                draft_release(app, &name);
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Delete the tag from the remote, in the output drawer.
+pub fn delete_remote(app: &mut crate::app::App, name: &str) {
+    app.start_output_job(crate::ui::output_pane::OutputJob {
+        title: format!("Deleting tag {} on {}", name, REMOTE),
+        commands: vec![git::tag::delete_remote_args(REMOTE, name)],
+        success_msg: format!("✓ Deleted tag {} on {}", name, REMOTE),
+        failure_msg: format!("Deleting tag {} on {} failed", name, REMOTE),
+    });
+}
+
+/// Draft a GitHub release for `tag` from the commits since the tag before it:
+/// a changelog from AI when it's set up, the plain commit list otherwise.
+fn draft_release(app: &mut crate::app::App, tag: &str) {
+    if app.config.github.get_token().is_none() {
+        app.set_status("Sign in to GitHub first (g on the Dashboard)");
+        return;
+    }
+    let previous = git::tag::previous(tag);
+    let commits = git::tag::commits_since(previous.as_deref(), tag).unwrap_or_default();
+    if commits.trim().is_empty() {
+        app.set_status(format!("No commits in {} to write a changelog from", tag));
+        return;
+    }
+    if app.ai_client.is_some() {
+        let since = previous.as_deref().unwrap_or("the first commit");
+        app.start_ai_changelog(
+            tag.to_string(),
+            format!(
+                "Tag: {}\nPrevious tag: {}\n\nCommits:\n{}",
+                tag, since, commits
+            ),
+        );
+    } else {
+        offer_release(app, tag, commits);
+    }
+}
+
+/// Show the release notes with the ways to publish them.
+pub fn offer_release(app: &mut crate::app::App, tag: &str, notes: String) {
+    use crate::app::{FollowUpAction, FollowUpItem, Popup};
+
+    let publish = |draft: bool| FollowUpAction::CreateRelease {
+        tag: tag.to_string(),
+        notes: notes.clone(),
+        draft,
+    };
+    app.popup = Popup::FollowUp {
+        title: format!("Release {}", tag),
+        context: notes.clone(),
+        suggestions: vec![
+            FollowUpItem {
+                label: "Publish release".to_string(),
+                description: format!("Push {} and publish it on GitHub", tag),
+                action: publish(false),
+            },
+            FollowUpItem {
+                label: "Save as draft".to_string(),
+                description: "Push the tag and create a draft to edit on GitHub".to_string(),
+                action: publish(true),
+            },
+            FollowUpItem {
+                label: "Copy to clipboard".to_string(),
+                description: "Just the notes".to_string(),
+                action: FollowUpAction::CopyToClipboard(notes.clone()),
+            },
+        ],
+        selected: 0,
+    };
+}
+
+/// Push the tag, so GitHub doesn't make its own from the default branch,
+/// then create the release, in the output drawer.
+pub fn publish_release(app: &mut crate::app::App, tag: String, notes: String, draft: bool) {
+    let Some(token) = app.config.github.get_token() else {
+        app.set_status("Sign in to GitHub first (g on the Dashboard)");
+        return;
+    };
+    let line = |text: String, is_stderr: bool| git::runner::StreamLine { text, is_stderr };
+    let status = format!(
+        "{} Publishing {}release {}...",
+        Icon::Loading,
+        if draft { "draft " } else { "" },
+        tag
+    );
+    let started = app.output_pane.start_task(
+        format!("Release {}", tag),
+        format!("✓ Published release {}", tag),
+        format!("Publishing release {} failed", tag),
+        move |emit| {
+            let push = git::tag::push_args(REMOTE, &tag);
+            emit(line(
+                format!("\x1b[2m$ git {}\x1b[0m", push.join(" ")),
+                false,
+            ));
+            let push: Vec<&str> = push.iter().map(String::as_str).collect();
+            match git::runner::run_git_streaming(&push, &mut *emit) {
+                Ok(true) => {}
+                Ok(false) => return false,
+                Err(e) => {
+                    emit(line(format!("{:#}", e), true));
+                    return false;
+                }
+            }
+            match git::github_auth::create_release(&token, &tag, &tag, &notes, draft) {
+                Ok(url) => {
+                    emit(line(format!("Published {}", url), false));
+                    true
+                }
+                Err(e) => {
+                    emit(line(format!("{:#}", e), true));
+                    false
+                }
+            }
+        },
+    );
+    if started {
+        app.set_status(status);
+    } else {
+        app.set_status(format!(
+            "{} Another command is still running (Ctrl+O to view)",
+            Icon::Loading
+        ));
+    }
+}
@@ -0,0 +1,1213 @@
+//! Headless UI test harness: renders views into ratatui's `TestBackend`,
+//! compares the text against snapshots in `src/ui/snapshots/`, and simulates
+//! key presses against a real `App`.
+//!
+//! Snapshots are plain text, one line per terminal row with trailing spaces
+//! trimmed. A missing snapshot is written on first run; set
+//! `ZIT_UPDATE_SNAPSHOTS=1` to accept changed output.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{Frame, Terminal, backend::TestBackend, buffer::Buffer};
+use std::path::PathBuf;
+
+use crate::app::App;
+use crate::config::Config;
+
+/// Render `draw` on a `width`×`height` test terminal and return the screen text.
+pub fn render(width: u16, height: u16, draw: impl FnOnce(&mut Frame)) -> String {
+    let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+    terminal.draw(draw).unwrap();
+    buffer_text(terminal.backend().buffer())
+}
+
+/// The buffer's symbols, one line per row, trailing spaces trimmed.
+pub fn buffer_text(buffer: &Buffer) -> String {
+    let area = buffer.area;
+    let mut out = String::new();
+    for y in area.top()..area.bottom() {
+        let row: String = (area.left()..area.right())
+            .map(|x| buffer[(x, y)].symbol())
+            .collect();
+        out.push_str(row.trim_end());
+        out.push('\n');
+    }
+    out
+}
+
+fn snapshot_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("src/ui/snapshots")
+        .join(format!("{}.snap", name))
+}
+
+/// Compare `actual` with the stored snapshot `name`, writing it when missing or
+/// when `ZIT_UPDATE_SNAPSHOTS` is set.
+#[track_caller]
+pub fn assert_snapshot(name: &str, actual: &str) {
+    let path = snapshot_path(name);
+    let update = std::env::var_os("ZIT_UPDATE_SNAPSHOTS").is_some();
+    if update || !path.exists() {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, actual).unwrap();
+        return;
+    }
+    let expected = std::fs::read_to_string(&path).unwrap();
+    pretty_assertions::assert_eq!(
+        expected,
+        actual,
+        "snapshot '{}' changed; rerun with ZIT_UPDATE_SNAPSHOTS=1 to accept",
+        name
+    );
+}
+
+/// A key press without modifiers.
+pub fn key(code: KeyCode) -> KeyEvent {
+    with(KeyModifiers::NONE, code)
+}
+
+/// A key press with `modifiers`, e.g. `with(KeyModifiers::SHIFT, KeyCode::BackTab)`.
+pub fn with(modifiers: KeyModifiers, code: KeyCode) -> KeyEvent {
+    KeyEvent::new(code, modifiers)
+}
+
+/// One key press per character of `text`, e.g. `chars("jjk")`.
+pub fn chars(text: &str) -> Vec<KeyEvent> {
+    text.chars().map(|c| key(KeyCode::Char(c))).collect()
+}
+
+/// An app on the repository the tests run in, with AI off.
+pub fn test_app() -> App {
+    let mut config = Config::default();
+    config.ai.enabled = false;
+    App::new(config)
+}
+
+/// Feed `keys` to `app` as the main loop would.
+pub fn press(app: &mut App, keys: impl IntoIterator<Item = KeyEvent>) {
+    for k in keys {
+        app.handle_key(k).unwrap();
+    }
+}
+
+/// An environment lookup over `vars`, for detection that reads env variables.
+pub fn env_of<'a>(vars: &'a [(&'a str, &'a str)]) -> impl Fn(&str) -> Option<String> + 'a {
+    move |name| {
+        vars.iter()
+            .find(|(k, _)| *k == name)
+            .map(|(_, v)| v.to_string())
+    }
+}
+
+/// Render the whole app as the main loop does.
+pub fn draw_app(app: &mut App, width: u16, height: u16) -> String {
+    render(width, height, |f| crate::draw(f, app))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::{InputAction, Popup, View};
+    use crate::git;
+    use crate::ui::{branches, dashboard, help, staging, stash, status_bar, workflow_builder};
+
+    fn commit(short_hash: &str, message: &str) -> git::CommitEntry {
+        git::CommitEntry {
+            hash: format!("{}0000000", short_hash),
+            short_hash: short_hash.to_string(),
+            message: message.to_string(),
+            author: "Dev".to_string(),
+            author_email: "dev@example.com".to_string(),
+            date: "2 days ago".to_string(),
+            date_iso: String::new(),
+            parents: Vec::new(),
+            refs: String::new(),
+            graph: "*".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_snapshot_dashboard() {
+        let mut state = dashboard::DashboardState::default();
+        state.branch = "main".to_string();
+        state.upstream = Some("origin/main".to_string());
+        (state.ahead, state.behind) = (2, 1);
+        (state.display_ahead, state.display_behind) = (2, 1);
+        (state.display_staged, state.display_unstaged) = (1, 3);
+        (state.display_untracked, state.display_conflict) = (0, 0);
+        (state.display_stash, state.display_commit) = (1, 42);
+        state.is_clean = false;
+        state.error = None;
+        state.loaded = true;
+        state.recent_commits = vec![
+            commit("a1b2c3d", "Add milestone widget"),
+            commit("e4f5a6b", "Fix crash on resize"),
+        ];
+        state.milestone = Some(git::milestone::MilestoneProgress {
+            title: "v1.2".to_string(),
+            due_on: Some("2026-11-01T00:00:00Z".to_string()),
+            open_issues: 2,
+            closed_issues: 5,
+            open_prs: 1,
+            closed_prs: 2,
+        });
+        let mut ai = crate::ui::ai_mentor::AiMentorState::default();
+        ai.history.clear();
+
+        let screen = render(110, 30, |f| {
+            dashboard::render(f, f.area(), &state, &ai, false, false, "")
+        });
+        assert_snapshot("dashboard", &screen);
+    }
+
+    #[test]
+    fn test_snapshot_dashboard_loading() {
+        // Before the first refresh the dashboard shows placeholders
+        let state = dashboard::DashboardState::default();
+        let mut ai = crate::ui::ai_mentor::AiMentorState::default();
+        ai.history.clear();
+        let screen = render(110, 30, |f| {
+            dashboard::render(f, f.area(), &state, &ai, false, true, "")
+        });
+        assert_snapshot("dashboard_loading", &screen);
+    }
+
+    #[test]
+    fn test_snapshot_staging() {
+        let file = |path: &str, status, is_staged| staging::StagingFile {
+            path: path.to_string(),
+            status,
+            is_staged,
+            original_path: None,
+        };
+        let mut state = staging::StagingState {
+            files: vec![
+                file("src/app.rs", git::FileStatus::Modified, true),
+                file("README.md", git::FileStatus::Modified, false),
+                file("notes.txt", git::FileStatus::Untracked, false),
+            ],
+            ..Default::default()
+        };
+        state.list_state.select(Some(0));
+
+        let screen = render(100, 20, |f| staging::render(f, f.area(), &mut state));
+        assert_snapshot("staging", &screen);
+    }
+
+    #[test]
+    fn test_snapshot_branches() {
+        let branch = |name: &str, is_current: bool| git::BranchEntry {
+            name: name.to_string(),
+            is_current,
+            is_remote: false,
+            last_commit_msg: format!("Work on {}", name),
+            last_commit_date: "3 hours ago".to_string(),
+            last_commit_date_iso: String::new(),
+            last_commit_author: "Dev".to_string(),
+            upstream: String::new(),
+            description: (name != "main").then(|| "New login flow\nShips with 2.0".to_string()),
+        };
+        let mut state = branches::BranchesState {
+            branches: vec![branch("main", true), branch("feature/login", false)],
+            ..Default::default()
+        };
+        state.table_state.select(Some(1));
+        state.selected = 1;
+
+        let screen = render(100, 12, |f| branches::render(f, f.area(), &mut state));
+        assert_snapshot("branches", &screen);
+    }
+
+    #[test]
+    fn test_snapshot_stash() {
+        let mut state = stash::StashState {
+            entries: vec![git::stash::StashEntry {
+                index: 0,
+                branch: "main".to_string(),
+                message: "WIP on main: tweak layout".to_string(),
+            }],
+            diff_text: "diff --git a/src/app.rs b/src/app.rs\n+added line\n".to_string(),
+            ..Default::default()
+        };
+        state.list_state.select(Some(0));
+
+        let screen = render(100, 14, |f| stash::render(f, f.area(), &mut state));
+        assert_snapshot("stash", &screen);
+    }
+
+    #[test]
+    fn test_snapshot_help_and_workflow_builder() {
+        let screen = render(100, 40, |f| {
+            help::render(f, f.area(), View::Dashboard, &mut 0)
+        });
+        assert_snapshot("help_dashboard", &screen);
+
+        let state = workflow_builder::WorkflowBuilderState::new();
+        let screen = render(100, 20, |f| workflow_builder::render(f, f.area(), &state));
+        assert_snapshot("workflow_builder", &screen);
+    }
+
+    #[test]
+    fn test_snapshot_too_small() {
+        let mut app = test_app();
+        assert_snapshot("too_small", &draw_app(&mut app, 40, 10));
+    }
+
+    #[test]
+    fn test_keys_switch_views_and_popups() {
+        let mut app = test_app();
+        press(&mut app, chars("s"));
+        assert_eq!(app.view, View::Staging);
+        press(&mut app, chars("q"));
+        assert_eq!(app.view, View::Dashboard);
+
+        press(&mut app, chars("?"));
+        assert!(matches!(app.popup, Popup::Help { .. }));
+        assert!(draw_app(&mut app, 120, 40).contains("Open Staging view"));
+        // The list is longer than the popup: its end scrolls into view
+        assert!(!draw_app(&mut app, 100, 40).contains("Press ? or Esc to close"));
+        press(&mut app, [key(KeyCode::End)]);
+        let screen = draw_app(&mut app, 100, 40);
+        assert!(screen.contains("Toggle this help"));
+        assert!(screen.contains("Press ? or Esc to close"));
+        assert!(!screen.contains("Open Staging view"));
+        press(&mut app, [key(KeyCode::Home)]);
+        assert!(draw_app(&mut app, 100, 40).contains("Open Staging view"));
+        press(&mut app, [key(KeyCode::Esc)]);
+        assert!(matches!(app.popup, Popup::None));
+
+        press(&mut app, chars("g"));
+        assert_eq!(app.view, View::GitHub);
+        press(&mut app, [key(KeyCode::Down), key(KeyCode::Down)]);
+        assert_eq!(app.github_state.menu_selected, 2);
+        press(&mut app, [with(KeyModifiers::NONE, KeyCode::Up)]);
+        assert_eq!(app.github_state.menu_selected, 1);
+    }
+
+    #[test]
+    fn test_stats_screen_offers_to_turn_stats_on() {
+        let mut app = test_app();
+        press(&mut app, chars("S"));
+        let screen = draw_app(&mut app, 120, 40);
+        assert!(screen.contains("Your zit stats"));
+        assert!(screen.contains("Stats are off"));
+        assert!(screen.contains("Turn on"));
+    }
+
+    #[test]
+    fn test_declining_a_recipe_step_answers_no() {
+        let mut app = test_app();
+        let (questions, asked) = std::sync::mpsc::channel();
+        let (answer, answered) = std::sync::mpsc::channel();
+        app.recipe_steps = Some(asked);
+        questions
+            .send(("[2/3] git tag v1.4.0".to_string(), answer))
+            .unwrap();
+        app.poll_recipe_steps();
+        assert!(draw_app(&mut app, 100, 30).contains("Run [2/3] git tag v1.4.0?"));
+
+        press(&mut app, [key(KeyCode::Esc)]);
+        assert_eq!(answered.try_recv(), Ok(false));
+        assert!(matches!(app.popup, Popup::None));
+    }
+
+    #[test]
+    fn test_esc_walks_back_through_views() {
+        let mut app = test_app();
+        press(&mut app, chars("s"));
+        press(&mut app, [key(KeyCode::Esc)]);
+        assert_eq!(app.view, View::Dashboard);
+
+        press(&mut app, chars("g"));
+        assert_eq!(app.view, View::GitHub);
+        assert_eq!(app.breadcrumb(), ["Dashboard", "GitHub"]);
+        assert!(draw_app(&mut app, 100, 30).contains("Dashboard › GitHub"));
+
+        press(&mut app, [key(KeyCode::Esc)]);
+        assert_eq!(app.view, View::Dashboard);
+
+        // Back to the view Branches was opened from, not straight to the Dashboard
+        press(&mut app, chars("s"));
+        app.open_view(View::Branches);
+        assert_eq!(app.breadcrumb(), ["Dashboard", "Staging", "Branches"]);
+        press(&mut app, [key(KeyCode::Esc)]);
+        assert_eq!(app.view, View::Staging);
+        press(&mut app, [key(KeyCode::Esc)]);
+        assert_eq!(app.view, View::Dashboard);
+        assert!(!draw_app(&mut app, 100, 30).contains("Esc back"));
+    }
+
+    #[test]
+    fn test_input_popup_masks_secrets_and_validates_branch_names() {
+        let mut app = test_app();
+        app.popup = Popup::Input {
+            title: "API Key".to_string(),
+            prompt: "Key: ".to_string(),
+            value: String::new(),
+            on_submit: InputAction::AiSetupApiKey("bedrock".to_string()),
+        };
+        app.handle_paste("sk-secret\n").unwrap();
+        let screen = draw_app(&mut app, 100, 30);
+        assert!(!screen.contains("sk-secret"));
+        assert!(screen.contains("•••••••••"));
+
+        app.popup = Popup::Input {
+            title: "New Branch".to_string(),
+            prompt: "Name: ".to_string(),
+            value: String::new(),
+            on_submit: InputAction::CreateBranch,
+        };
+        press(&mut app, chars("bad..name"));
+        assert!(draw_app(&mut app, 100, 30).contains("'bad..name' isn't a valid branch name"));
+        press(&mut app, [key(KeyCode::Enter)]);
+        assert!(matches!(app.popup, Popup::Input { .. }));
+        press(&mut app, [key(KeyCode::Esc)]);
+        assert!(matches!(app.popup, Popup::None));
+        assert_eq!(app.input_error, None);
+    }
+
+    #[test]
+    fn test_quit_asks_when_work_is_pending() {
+        let mut app = test_app();
+        press(&mut app, chars("q"));
+        assert!(!app.running);
+
+        let mut app = test_app();
+        app.repo_summary.staged = 2;
+        app.repo_summary.operation = Some("rebase".to_string());
+        app.commit_state.message = "fix: wip".to_string();
+        app.output_pane.running = true;
+        app.output_pane.title = "Push".to_string();
+        press(&mut app, [with(KeyModifiers::CONTROL, KeyCode::Char('c'))]);
+        assert!(app.running);
+        let screen = draw_app(&mut app, 120, 40);
+        for item in [
+            "2 staged file(s) not committed",
+            "An unfinished commit message",
+            "A rebase in progress",
+            "'Push' still running",
+        ] {
+            assert!(screen.contains(item), "missing {item}");
+        }
+        press(&mut app, chars("n"));
+        assert!(app.running);
+
+        press(&mut app, chars("q"));
+        press(&mut app, chars("y"));
+        assert!(!app.running);
+    }
+
+    #[test]
+    fn test_scrollbar_only_when_content_overflows() {
+        use ratatui::widgets::{Block, Borders, Paragraph};
+
+        let pane = |lines: usize, scroll: u16| {
+            render(20, 10, |f| {
+                let text: Vec<String> = (0..lines).map(|i| format!("line {}", i)).collect();
+                let paragraph =
+                    Paragraph::new(text.join("\n")).block(Block::default().borders(Borders::ALL));
+                f.render_widget(&paragraph, f.area());
+                crate::ui::utils::render_paragraph_scrollbar(f, f.area(), &paragraph, scroll);
+            })
+        };
+        let thumb_rows = |screen: &str| -> Vec<usize> {
+            screen
+                .lines()
+                .enumerate()
+                .filter(|(_, l)| l.ends_with('┃'))
+                .map(|(i, _)| i)
+                .collect()
+        };
+        assert!(thumb_rows(&pane(8, 0)).is_empty());
+        // 8 of 32 lines in view: a quarter of the track, at the top then the bottom
+        assert_eq!(thumb_rows(&pane(32, 0)), [1, 2]);
+        assert_eq!(thumb_rows(&pane(32, 24)), [7, 8]);
+    }
+
+    #[test]
+    fn test_pane_search_jumps_between_matches() {
+        let mut app = test_app();
+        app.view = View::Outgoing;
+        let diff: Vec<String> = (0..40)
+            .map(|i| {
+                if i % 15 == 5 {
+                    format!("+ let needle = {};", i)
+                } else {
+                    format!("  line {}", i)
+                }
+            })
+            .collect();
+        app.outgoing_state.diff_text = diff.join("\n");
+
+        press(&mut app, chars("/needle"));
+        press(&mut app, [key(KeyCode::Enter)]);
+        assert_eq!(app.outgoing_state.diff_scroll, 3);
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Match 1 of 3 for 'needle' (n/N next/previous)")
+        );
+        press(&mut app, chars("n"));
+        assert_eq!(app.outgoing_state.diff_scroll, 18);
+        press(&mut app, chars("NN"));
+        assert_eq!(app.outgoing_state.diff_scroll, 33);
+
+        // Nothing found leaves the pane where it was
+        press(&mut app, chars("/"));
+        press(&mut app, [with(KeyModifiers::CONTROL, KeyCode::Char('u'))]);
+        press(&mut app, chars("haystack"));
+        press(&mut app, [key(KeyCode::Enter)]);
+        assert_eq!(app.outgoing_state.diff_scroll, 33);
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("No matches for 'haystack'")
+        );
+
+        // Esc drops the search before leaving the view
+        press(&mut app, [key(KeyCode::Esc)]);
+        assert!(app.outgoing_state.diff_search.is_none());
+        assert_eq!(app.view, View::Outgoing);
+    }
+
+    #[test]
+    fn test_ai_answer_code_can_be_copied_or_run() {
+        use crate::app::ConfirmAction;
+        use crate::ui::ai_mentor::AiMode;
+
+        let mut app = test_app();
+        app.dashboard_state.focus = dashboard::DashboardFocus::Right;
+        app.ai_mentor_state.mode = AiMode::Result;
+        app.ai_mentor_state.result_text =
+            "Undo the last commit but keep the changes:\n```sh\ngit reset --soft HEAD~1\n```"
+                .to_string();
+
+        press(&mut app, chars("c"));
+        let Popup::FollowUp { suggestions, .. } = &app.popup else {
+            panic!("expected the snippet picker, got {:?}", app.popup);
+        };
+        let labels: Vec<&str> = suggestions.iter().map(|s| s.label.as_str()).collect();
+        assert_eq!(
+            labels,
+            [
+                "Run: git reset --soft HEAD~1",
+                "Copy: git reset --soft HEAD~1"
+            ]
+        );
+
+        // Running asks first
+        press(&mut app, [key(KeyCode::Enter)]);
+        let Popup::Confirm {
+            message,
+            on_confirm: ConfirmAction::RunSnippet(job),
+            ..
+        } = &app.popup
+        else {
+            panic!("expected a confirmation, got {:?}", app.popup);
+        };
+        assert!(message.starts_with("$ git reset --soft HEAD~1\n"));
+        assert_eq!(job.commands, [["reset", "--soft", "HEAD~1"]]);
+        press(&mut app, chars("n"));
+        assert!(matches!(app.popup, Popup::None));
+    }
+
+    #[test]
+    fn test_ai_answer_offers_next_steps() {
+        use crate::ui::ai_mentor::AiMode;
+
+        let mut app = test_app();
+        app.dashboard_state.focus = dashboard::DashboardFocus::Right;
+        app.ai_mentor_state.mode = AiMode::Result;
+        app.ai_mentor_state.result_text =
+            "Your changes are safe in a stash. Run `git stash list` to see it.".to_string();
+        assert!(draw_app(&mut app, 120, 40).contains("Enter next steps (3)"));
+
+        press(&mut app, [key(KeyCode::Enter)]);
+        let Popup::FollowUp { suggestions, .. } = &app.popup else {
+            panic!("expected the next steps, got {:?}", app.popup);
+        };
+        let labels: Vec<&str> = suggestions.iter().map(|s| s.label.as_str()).collect();
+        assert_eq!(
+            labels,
+            ["Run: git stash list", "Open Stash", "Learn: stashing"]
+        );
+
+        press(&mut app, chars("2"));
+        assert!(matches!(app.popup, Popup::None));
+        assert_eq!(app.view, View::Stash);
+    }
+
+    #[test]
+    fn test_ai_setup_edits_key_alone_and_checks_it() {
+        let mut app = test_app();
+        app.config.ai.enabled = true;
+        app.config.ai.provider = "anthropic".to_string();
+        app.start_ai_setup();
+        assert!(draw_app(&mut app, 100, 30).contains("k) Change API key only"));
+
+        press(&mut app, [key(KeyCode::Backspace)]);
+        press(&mut app, chars("k"));
+        press(&mut app, [key(KeyCode::Enter)]);
+        assert!(matches!(
+            &app.popup,
+            Popup::Input { on_submit: InputAction::AiSetupApiKey(p), .. } if p == "anthropic"
+        ));
+
+        press(&mut app, chars("sk-proj-Xq7vR2mK9pLwT4nB8cYz"));
+        assert_eq!(
+            app.input_error.as_deref(),
+            Some("Anthropic keys start with sk-ant-")
+        );
+        app.popup = Popup::Input {
+            title: "API Key".to_string(),
+            prompt: "Key: ".to_string(),
+            value: String::new(),
+            on_submit: InputAction::AiSetupApiKey("anthropic".to_string()),
+        };
+        press(&mut app, chars("sk-ant-REDACTED"));
+        assert!(draw_app(&mut app, 100, 30).contains("Too repetitive"));
+        press(&mut app, [key(KeyCode::Enter)]);
+        assert!(matches!(app.popup, Popup::Input { .. }));
+    }
+
+    #[test]
+    fn test_github_issues_search_and_edit() {
+        use crate::app::{ConfirmAction, InputAction};
+        use crate::git::github_auth::{GhLabel, GhUser, Issue};
+        use crate::ui::github::GitHubView;
+
+        let label = |name: &str| GhLabel {
+            name: name.to_string(),
+            color: "0075ca".to_string(),
+        };
+        let issue = |number: u64, title: &str, labels: &[&str]| Issue {
+            number,
+            title: title.to_string(),
+            state: "open".to_string(),
+            body: Some("Steps:\n- run `zit`".to_string()),
+            html_url: String::new(),
+            created_at: "2026-01-02T03:04:05Z".to_string(),
+            updated_at: "2026-01-02T03:04:05Z".to_string(),
+            user: GhUser {
+                login: "mona".to_string(),
+                avatar_url: String::new(),
+            },
+            labels: labels.iter().map(|l| label(l)).collect(),
+            assignees: Vec::new(),
+            comments: 0,
+            pull_request: None,
+        };
+        let mut app = test_app();
+        app.open_view(View::GitHub);
+        app.github_state.view = GitHubView::Issues;
+        let state = &mut app.github_state.issues_state;
+        state.issues = vec![
+            issue(3, "Crash on start", &["bug"]),
+            issue(5, "Document the config", &["docs"]),
+        ];
+        state.labels = vec![label("bug"), label("docs")];
+        state.select(0);
+        let screen = draw_app(&mut app, 120, 40);
+        assert!(screen.contains("#3 Crash on start [bug]"));
+        assert!(screen.contains("#5 Document the config [docs]"));
+
+        press(&mut app, chars("/"));
+        press(&mut app, chars("DOCS"));
+        press(&mut app, [key(KeyCode::Enter)]);
+        let screen = draw_app(&mut app, 120, 40);
+        assert!(screen.contains("1 matching 'DOCS'"));
+        assert!(!screen.contains("Crash on start"));
+
+        press(&mut app, [key(KeyCode::Enter)]);
+        assert_eq!(app.github_state.view, GitHubView::IssueDetail(5));
+        let screen = draw_app(&mut app, 120, 40);
+        assert!(screen.contains("#5 Document the config"));
+        assert!(screen.contains("• run zit"));
+
+        press(&mut app, chars("l"));
+        let Popup::Input {
+            value,
+            on_submit: InputAction::IssueLabels(5),
+            ..
+        } = &app.popup
+        else {
+            panic!("expected the label editor, got {:?}", app.popup);
+        };
+        assert_eq!(value, "docs");
+        press(&mut app, [key(KeyCode::Esc)]);
+
+        press(&mut app, chars("x"));
+        assert!(matches!(
+            app.popup,
+            Popup::Confirm {
+                on_confirm: ConfirmAction::CloseIssue(5),
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_github_ci_runs_follow_and_rerun() {
+        use crate::app::ConfirmAction;
+        use crate::ui::github::GitHubView;
+
+        let run = |id: u64, status: &str, conclusion: Option<&str>| {
+            serde_json::from_value(serde_json::json!({
+                "id": id, "name": "CI", "status": status, "conclusion": conclusion,
+                "head_branch": "main", "event": "push",
+                "created_at": "2024-05-01T10:00:00Z", "updated_at": "2024-05-01T10:05:00Z",
+                "html_url": "", "run_number": id + 40, "display_title": "Fix the build"
+            }))
+            .unwrap()
+        };
+        let mut app = test_app();
+        app.open_view(View::GitHub);
+        app.github_state.view = GitHubView::Actions;
+        let state = &mut app.github_state.actions_state;
+        state.branch = "main".to_string();
+        state.runs = vec![
+            run(8, "in_progress", None),
+            run(7, "completed", Some("failure")),
+        ];
+        state.list_state.select(Some(0));
+        let screen = draw_app(&mut app, 120, 40);
+        assert!(screen.contains("CI Runs  main · 2 runs  ● live"));
+
+        press(&mut app, chars("R"));
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Run #48 is still going")
+        );
+        press(&mut app, chars("jR"));
+        assert!(matches!(
+            app.popup,
+            Popup::Confirm {
+                on_confirm: ConfirmAction::RerunFailedJobs(7),
+                ..
+            }
+        ));
+        press(&mut app, chars("n"));
+
+        // A running job's steps show until its logs are published
+        app.github_state.view = GitHubView::ActionDetail(8);
+        let state = &mut app.github_state.actions_state;
+        state.jobs = vec![
+            serde_json::from_value(serde_json::json!({
+                "id": 81, "name": "test", "status": "in_progress", "conclusion": null,
+                "started_at": null, "completed_at": null,
+                "steps": [
+                    { "name": "Set up job", "status": "completed", "conclusion": "success", "number": 1 },
+                    { "name": "Run tests", "status": "in_progress", "conclusion": null, "number": 2 }
+                ]
+            }))
+            .unwrap(),
+        ];
+        state.log_job = Some(81);
+        let screen = draw_app(&mut app, 120, 40);
+        assert!(screen.contains("Waiting for logs"));
+        assert!(screen.contains("Run tests"));
+        assert!(screen.contains("● live"));
+    }
+
+    #[test]
+    fn test_github_review_and_line_comment() {
+        use crate::ui::github::{GitHubView, PrDetailTab};
+
+        let branch = |name: &str| serde_json::json!({ "ref": name, "sha": "abc123" });
+        let mut app = test_app();
+        app.open_view(View::GitHub);
+        app.github_state.view = GitHubView::PullRequestDetail(7);
+        let state = &mut app.github_state.pr_state;
+        state.detail_pr = Some(
+            serde_json::from_value(serde_json::json!({
+                "number": 7, "title": "Retry on timeout", "state": "open", "body": null,
+                "html_url": "", "created_at": "2024-05-01T10:00:00Z",
+                "updated_at": "2024-05-01T10:00:00Z", "merged_at": null, "mergeable": true,
+                "mergeable_state": "clean", "head": branch("feature/retry"),
+                "base": branch("main"), "user": { "login": "mona" }
+            }))
+            .unwrap(),
+        );
+        state.detail_files = vec![
+            serde_json::from_value(serde_json::json!({
+                "filename": "src/lib.rs", "status": "modified",
+                "additions": 1, "deletions": 1, "changes": 2,
+                "patch": "@@ -4,2 +4,2 @@\n fn retry() {\n-    1\n+    3"
+            }))
+            .unwrap(),
+        ];
+        state.files_list_state.select(Some(0));
+        state.detail_tab = PrDetailTab::Files;
+
+        press(&mut app, [key(KeyCode::Enter)]);
+        let screen = draw_app(&mut app, 120, 40);
+        assert!(screen.contains("[Enter] Comment on line"));
+        assert!(screen.contains("    5 -    1"));
+        assert!(screen.contains("src/lib.rs · hunk 1/1"));
+
+        // Side by side, the removed and added line share a row
+        press(&mut app, chars("s"));
+        let screen = draw_app(&mut app, 120, 40);
+        assert!(
+            screen
+                .lines()
+                .any(|l| l.contains("    5 -    1") && l.contains("    5 +    3"))
+        );
+        press(&mut app, chars("s"));
+
+        press(&mut app, chars("jj"));
+        press(&mut app, [key(KeyCode::Enter)]);
+        match &app.popup {
+            Popup::Input {
+                title,
+                on_submit: InputAction::PrLineComment(7, comment),
+                ..
+            } => {
+                assert_eq!(title, "Comment on src/lib.rs:5");
+                assert_eq!((comment.line, comment.side), (5, "LEFT"));
+                assert_eq!(comment.commit_id, "abc123");
+            }
+            other => panic!("expected the line comment input, got {:?}", other),
+        }
+        press(&mut app, [key(KeyCode::Esc)]);
+
+        // Esc leaves the patch first, then v picks the kind of review
+        press(&mut app, [key(KeyCode::Esc)]);
+        assert!(!app.github_state.pr_state.patch_focus);
+        // n jumps back into the patch at the first hunk
+        press(&mut app, chars("n"));
+        assert!(app.github_state.pr_state.patch_focus);
+        assert_eq!(app.github_state.pr_state.patch_line, 0);
+        press(&mut app, [key(KeyCode::Esc)]);
+        press(&mut app, chars("v"));
+        assert!(matches!(app.popup, Popup::FollowUp { .. }));
+        press(&mut app, chars("2"));
+        assert!(matches!(
+            &app.popup,
+            Popup::Input { on_submit: InputAction::PrReview(7, event), .. } if event == "REQUEST_CHANGES"
+        ));
+        press(&mut app, [key(KeyCode::Enter)]);
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Write what the review says — only approvals can be empty")
+        );
+    }
+
+    #[test]
+    fn test_github_new_pull_request_form() {
+        use crate::git::pull_request::PushNeed;
+        use crate::ui::github::{GitHubView, NewPrState};
+
+        let branches = vec!["feature/retry".to_string(), "main".to_string()];
+        let mut app = test_app();
+        app.open_view(View::GitHub);
+        app.github_state.view = GitHubView::NewPullRequest;
+        app.github_state.pr_state.new_pr = Some(NewPrState {
+            heads: branches.clone(),
+            bases: branches,
+            head: 0,
+            base: 1,
+            title: "Retry on timeout".to_string(),
+            body: String::new(),
+            draft: false,
+            field: 0,
+            commits: vec!["Add a retry".to_string()],
+            push: PushNeed::New,
+            drafted: Default::default(),
+            submitting: false,
+        });
+        let screen = draw_app(&mut app, 120, 40);
+        assert!(screen.contains("Pull Requests › New"));
+        assert!(screen.contains("From: feature/retry  (not on origin yet)"));
+        assert!(screen.contains("Into: main  (1 commit)"));
+        assert!(screen.contains("[Enter] Push 'feature/retry' and open pull request"));
+
+        // Text is entered in a popup, so q and ? are typed rather than acted on
+        press(&mut app, [key(KeyCode::Tab), key(KeyCode::Tab)]);
+        press(&mut app, [key(KeyCode::Enter)]);
+        assert!(matches!(
+            app.popup,
+            Popup::Input {
+                on_submit: InputAction::PrTitle,
+                ..
+            }
+        ));
+        press(&mut app, chars("?"));
+        press(&mut app, [key(KeyCode::Enter)]);
+        let form = app.github_state.pr_state.new_pr.as_ref().unwrap();
+        assert_eq!(form.title, "Retry on timeout?");
+
+        press(&mut app, [key(KeyCode::Tab), key(KeyCode::Tab)]);
+        press(&mut app, chars(" "));
+        let screen = draw_app(&mut app, 120, 40);
+        assert!(screen.contains("Draft: [x]"));
+        assert!(screen.contains("and open draft pull request"));
+
+        press(&mut app, [key(KeyCode::Esc)]);
+        assert_eq!(app.github_state.view, GitHubView::PullRequests);
+        assert!(app.github_state.pr_state.new_pr.is_none());
+    }
+
+    #[test]
+    fn test_rebase_plan_editing() {
+        use crate::git::rebase::{TodoAction, TodoEntry};
+        use crate::ui::rebase::RebaseState;
+
+        let entry = |short: &str, subject: &str| TodoEntry {
+            action: TodoAction::Pick,
+            hash: format!("{}0000000", short),
+            short_hash: short.to_string(),
+            subject: subject.to_string(),
+            body: String::new(),
+            new_subject: None,
+        };
+        let mut app = test_app();
+        app.rebase_state = RebaseState::new(
+            Some("base0000".to_string()),
+            "feature".to_string(),
+            vec![
+                entry("aaa", "Add parser"),
+                entry("bbb", "fix typo"),
+                entry("ccc", "Add tests"),
+            ],
+        );
+        app.open_view(View::Rebase);
+
+        // Squash the typo fix, move the tests up, reword the parser commit
+        press(&mut app, chars("js"));
+        press(&mut app, chars("jK"));
+        press(&mut app, chars("kr"));
+        press(&mut app, [with(KeyModifiers::CONTROL, KeyCode::Char('u'))]);
+        press(&mut app, chars("Add the parser"));
+        press(&mut app, [key(KeyCode::Enter)]);
+
+        let plan: Vec<(&str, &str)> = app
+            .rebase_state
+            .entries
+            .iter()
+            .map(|e| (e.action.label(), e.short_hash.as_str()))
+            .collect();
+        assert_eq!(
+            plan,
+            [("reword", "aaa"), ("pick", "ccc"), ("squash", "bbb")]
+        );
+        let screen = draw_app(&mut app, 100, 20);
+        assert!(screen.contains("feature: 3 commit(s) after base000 → 2, reordered"));
+        assert!(screen.contains("reword aaa Add the parser  (was: Add parser)"));
+        assert!(screen.contains("squash └ bbb fix typo"));
+
+        // Squashing the first commit has nothing to fold into
+        press(&mut app, chars("kks"));
+        assert!(draw_app(&mut app, 100, 20).contains("can't squash into nothing"));
+        press(&mut app, [key(KeyCode::Enter)]);
+        assert!(matches!(app.popup, Popup::None));
+
+        press(&mut app, chars("u"));
+        assert!(
+            app.rebase_state
+                .entries
+                .iter()
+                .all(|e| e.action == TodoAction::Pick)
+        );
+        press(&mut app, [key(KeyCode::Esc)]);
+        assert_ne!(app.view, View::Rebase);
+    }
+
+    #[test]
+    fn test_timeline_marks_commits_for_cherry_picking() {
+        let mut app = test_app();
+        app.open_view(View::Timeline);
+        app.timeline_state.commits = vec![
+            commit("ccc1111", "Third"),
+            commit("bbb1111", "Second"),
+            commit("aaa1111", "First"),
+        ];
+        app.timeline_state.list_state.select(Some(0));
+
+        press(&mut app, chars(" jj "));
+        assert_eq!(
+            app.timeline_state.marked,
+            ["ccc11110000000", "aaa11110000000"]
+        );
+        let screen = draw_app(&mut app, 100, 12);
+        assert!(screen.contains("2 marked, p to cherry-pick"));
+        assert!(screen.contains("● ccc1111 Third"));
+        assert!(screen.contains("  bbb1111 Second"));
+
+        // Space again unmarks
+        press(&mut app, chars(" "));
+        assert_eq!(app.timeline_state.marked, ["ccc11110000000"]);
+    }
+
+    #[test]
+    fn test_status_bar_shows_summary_job_and_message() {
+        let summary = status_bar::RepoSummary {
+            branch: "main".to_string(),
+            has_upstream: true,
+            ahead: 2,
+            behind: 0,
+            staged: 3,
+            unstaged: 1,
+            conflicts: 0,
+            operation: Some("rebase".to_string()),
+        };
+        let activity = status_bar::Activity {
+            job: Some("git push"),
+            spinner_frame: 0,
+            message: Some("Pushed to origin"),
+        };
+        let screen = render(100, 1, |f| {
+            status_bar::render(f, f.area(), &summary, &activity)
+        });
+        assert_eq!(
+            screen.trim_end(),
+            " ⎇ main ⬆2 │ ●3 staged ✚1 unstaged │ REBASE in progress │ ⣾ git push │ Pushed to origin"
+        );
+
+        // A long message gives way to the summary
+        let activity = status_bar::Activity {
+            job: None,
+            spinner_frame: 0,
+            message: Some("a very long message that does not fit in the bar at all"),
+        };
+        let screen = render(60, 1, |f| {
+            status_bar::render(f, f.area(), &summary, &activity)
+        });
+        assert!(screen.starts_with(" ⎇ main ⬆2 │ ●3 staged"));
+        assert!(screen.trim_end().ends_with('…'));
+    }
+
+    #[test]
+    fn test_key_inspector_explains_without_running() {
+        let mut app = test_app();
+        press(&mut app, chars("t"));
+        assert_eq!(app.view, View::TimeTravel);
+
+        press(&mut app, [key(KeyCode::F(1))]);
+        assert!(matches!(app.popup, Popup::KeyInspect { key: None }));
+        assert!(
+            draw_app(&mut app, 120, 40)
+                .contains("Press any key to see what it does in Time Travel")
+        );
+
+        // The hard reset is only described
+        press(&mut app, chars("hq"));
+        assert_eq!(app.view, View::TimeTravel);
+        press(&mut app, chars("h"));
+        let screen = draw_app(&mut app, 120, 40);
+        assert!(screen.contains("Hard reset (destructive!)"));
+        assert!(screen.contains("$ git reset --hard <commit>"));
+        assert!(screen.contains("F1 learn about git reset"));
+
+        press(&mut app, [key(KeyCode::Esc)]);
+        assert!(matches!(app.popup, Popup::None));
+        assert_eq!(app.view, View::TimeTravel);
+    }
+
+    #[test]
+    fn test_commit_options_panel() {
+        let mut app = test_app();
+        press(&mut app, chars("c"));
+        assert_eq!(app.view, View::Commit);
+        press(&mut app, [with(KeyModifiers::CONTROL, KeyCode::Char('t'))]);
+        assert_eq!(app.commit_state.options_panel, Some(0));
+
+        // Toggling --no-verify warns in the panel and under the editor
+        press(&mut app, chars(" j "));
+        assert!(app.commit_state.options.no_verify && app.commit_state.options.allow_empty);
+        assert!(draw_app(&mut app, 120, 40).contains("Hooks won't run"));
+        press(&mut app, [key(KeyCode::Esc)]);
+        assert_eq!(app.commit_state.options_panel, None);
+        assert!(draw_app(&mut app, 120, 40).contains("Committing with --no-verify --allow-empty"));
+
+        // The author is only taken in "Name <email>" form
+        press(&mut app, [with(KeyModifiers::CONTROL, KeyCode::Char('t'))]);
+        press(&mut app, chars("jj "));
+        assert!(matches!(app.popup, Popup::Input { .. }));
+        press(&mut app, chars("Ada"));
+        press(&mut app, [key(KeyCode::Enter)]);
+        assert_eq!(app.commit_state.options.author, None);
+        assert!(matches!(app.popup, Popup::Input { .. }));
+        assert!(draw_app(&mut app, 120, 40).contains("Must look like: Name <email>"));
+        press(&mut app, [with(KeyModifiers::CONTROL, KeyCode::Char('u'))]);
+        press(&mut app, chars("Ada <ada@example.com>"));
+        press(&mut app, [key(KeyCode::Enter)]);
+        assert_eq!(
+            app.commit_state.options.author.as_deref(),
+            Some("Ada <ada@example.com>")
+        );
+    }
+
+    #[test]
+    fn test_navigation_defers_diff_reload() {
+        let mut app = test_app();
+        press(&mut app, chars("s"));
+        let file = |path: &str| staging::StagingFile {
+            path: path.to_string(),
+            status: git::FileStatus::Modified,
+            is_staged: false,
+            original_path: None,
+        };
+        app.staging_state.files = vec![file("zit-test-a.rs"), file("zit-test-b.rs")];
+        app.staging_state.diff_debounce.cancel();
+
+        press(&mut app, chars("jk"));
+        assert!(
+            app.pending_reload().is_some(),
+            "moving only schedules a reload"
+        );
+        assert_eq!(app.staging_state.selected, 0);
+
+        // Any other key loads the selected file's diff before acting on it
+        press(&mut app, [key(KeyCode::PageDown)]);
+        assert!(app.pending_reload().is_none());
+        assert!(!app.run_pending_reloads(false));
+    }
+
+    #[test]
+    fn test_eol_flip_warning_offers_fixes() {
+        use crate::git::encoding::{Encoding, Eol, FileFormat};
+
+        let mut app = test_app();
+        press(&mut app, chars("s"));
+        app.staging_state.files = vec![staging::StagingFile {
+            path: "zit-test-crlf.txt".to_string(),
+            status: git::FileStatus::Modified,
+            is_staged: false,
+            original_path: None,
+        }];
+        app.staging_state.diff_debounce.cancel();
+        app.staging_state.format = Some(FileFormat {
+            encoding: Encoding::Utf8,
+            eol: Eol::Lf,
+        });
+        app.staging_state.eol_flip = Some((Eol::Crlf, Eol::Lf));
+
+        let screen = draw_app(&mut app, 160, 40);
+        assert!(screen.contains("UTF-8 · LF"));
+        assert!(screen.contains("CRLF → LF: every line changed — E to fix"));
+
+        press(&mut app, chars("E"));
+        let Popup::FollowUp { suggestions, .. } = &app.popup else {
+            panic!("expected the line-ending choices");
+        };
+        assert!(matches!(
+            &suggestions[1].action,
+            crate::app::FollowUpAction::RestoreLineEndings(path, Eol::Crlf)
+                if path == "zit-test-crlf.txt"
+        ));
+    }
+
+    #[test]
+    fn test_tags_view_creates_and_checks_names() {
+        let mut app = test_app();
+        press(&mut app, chars("T"));
+        assert_eq!(app.view, View::Tags);
+        app.tags_state.tags = vec![git::tag::Tag {
+            name: "v1.0.0".to_string(),
+            annotated: true,
+            commit: "abc1234".to_string(),
+            date_iso: String::new(),
+            subject: "First release".to_string(),
+        }];
+        app.tags_state.table_state.select(Some(0));
+        let screen = draw_app(&mut app, 120, 30);
+        assert!(screen.contains("Tags (1)"));
+        assert!(screen.contains("annotated"));
+        assert!(screen.contains("First release"));
+
+        press(&mut app, chars("n"));
+        assert!(matches!(app.popup, Popup::Input { .. }));
+        press(&mut app, chars("bad..name"));
+        assert!(draw_app(&mut app, 120, 30).contains("isn't a valid tag name"));
+        press(&mut app, [key(KeyCode::Esc)]);
+
+        press(&mut app, chars("d"));
+        assert!(matches!(
+            &app.popup,
+            Popup::Confirm { on_confirm: crate::app::ConfirmAction::DeleteTag(name), .. }
+                if name == "v1.0.0"
+        ));
+    }
+
+    #[test]
+    fn test_blame_view_shows_commits_by_run() {
+        let mut app = test_app();
+        press(&mut app, chars("s"));
+        app.staging_state.files = vec![staging::StagingFile {
+            path: "zit-test-new.rs".to_string(),
+            status: git::FileStatus::Untracked,
+            is_staged: false,
+            original_path: None,
+        }];
+        app.staging_state.diff_debounce.cancel();
+        press(&mut app, chars("b"));
+        assert_eq!(
+            app.view,
+            View::Staging,
+            "nothing to blame in an untracked file"
+        );
+
+        let line = |hash: &str, line_no: u32, content: &str| git::blame::BlameLine {
+            hash: hash.to_string(),
+            author: "Ada Lovelace".to_string(),
+            time: 1_700_000_000,
+            summary: "Add the parser".to_string(),
+            line_no,
+            content: content.to_string(),
+        };
+        app.blame_state.path = "src/parser.rs".to_string();
+        app.blame_state.lines = vec![
+            line(
+                "3f2a9c1d4e5b6a7c8d9e0f1a2b3c4d5e6f7a8b9c",
+                1,
+                "fn parse() {",
+            ),
+            line("3f2a9c1d4e5b6a7c8d9e0f1a2b3c4d5e6f7a8b9c", 2, "    todo!()"),
+            line(&"0".repeat(40), 3, "}"),
+        ];
+        app.open_view(View::Blame);
+        let screen = draw_app(&mut app, 120, 20);
+        assert!(screen.contains("Blame: src/parser.rs"));
+        assert_eq!(
+            screen.matches("3f2a9c1").count(),
+            2,
+            "run start and info bar"
+        );
+        assert!(screen.contains("Add the parser"));
+        assert!(screen.contains("not committed"));
+
+        press(&mut app, [key(KeyCode::End), key(KeyCode::Enter)]);
+        assert_eq!(app.view, View::Blame);
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("This line isn't committed yet")
+        );
+        press(&mut app, [key(KeyCode::Esc)]);
+        assert_eq!(app.view, View::Staging);
+    }
+
+    #[test]
+    fn test_blame_loads_in_the_background() {
+        let mut app = test_app();
+        press(&mut app, chars("s"));
+        crate::ui::blame::open(&mut app, "zit-test-missing.rs", None, Some(3));
+        assert_eq!(app.view, View::Blame);
+        assert!(app.blame_state.loading);
+        assert!(draw_app(&mut app, 120, 20).contains("Running git blame…"));
+
+        // A blame of a file the view has moved on from is dropped
+        let at = std::time::Instant::now();
+        let stale = crate::ui::blame::BlameState::load("zit-test-other.rs".to_string(), None, at);
+        crate::ui::blame::apply(&mut app, stale);
+        assert!(app.blame_state.loading);
+
+        let failed =
+            crate::ui::blame::BlameState::load("zit-test-missing.rs".to_string(), None, at);
+        crate::ui::blame::apply(&mut app, failed);
+        assert!(!app.blame_state.loading);
+        assert_eq!(app.view, View::Staging);
+        assert!(
+            app.status_message
+                .as_deref()
+                .is_some_and(|m| m.starts_with("Blame failed"))
+        );
+    }
+}
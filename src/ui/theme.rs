@@ -0,0 +1,207 @@
+//! Color theme layer — views pick colors freely, and the finished frame is mapped
+//! down to what the terminal supports: full truecolor, the 16 ANSI colors, or
+//! no color at all (`NO_COLOR`, `--no-color`).
+
+use ratatui::buffer::Buffer;
+use ratatui::style::{Color, Modifier};
+use std::sync::atomic::{AtomicU8, Ordering};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorMode {
+    TrueColor,
+    Ansi16,
+    NoColor,
+}
+
+impl ColorMode {
+    /// Parse the `[ui] colors` config value. Returns `None` for "auto" or unknown values.
+    pub fn from_config(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "truecolor" | "24bit" | "full" => Some(Self::TrueColor),
+            "16" | "ansi" | "basic" => Some(Self::Ansi16),
+            "none" | "off" | "mono" => Some(Self::NoColor),
+            _ => None,
+        }
+    }
+
+    /// Resolve the effective mode. `NO_COLOR` and `--no-color` always win over config.
+    pub fn resolve(value: &str, no_color_flag: bool) -> Self {
+        Self::resolve_from(value, no_color_flag, |name| std::env::var(name).ok())
+    }
+
+    fn resolve_from(
+        value: &str,
+        no_color_flag: bool,
+        env: impl Fn(&str) -> Option<String>,
+    ) -> Self {
+        // https://no-color.org: any non-empty value disables color
+        if no_color_flag || env("NO_COLOR").is_some_and(|v| !v.is_empty()) {
+            return Self::NoColor;
+        }
+        if let Some(mode) = Self::from_config(value) {
+            return mode;
+        }
+        if env("TERM").as_deref() == Some("dumb") {
+            return Self::NoColor;
+        }
+        let colorterm = env("COLORTERM").unwrap_or_default().to_lowercase();
+        if colorterm == "truecolor" || colorterm == "24bit" || env("WT_SESSION").is_some() {
+            Self::TrueColor
+        } else {
+            // COLORTERM is rarely forwarded over SSH; stay safe
+            Self::Ansi16
+        }
+    }
+}
+
+static MODE: AtomicU8 = AtomicU8::new(0);
+
+/// Set the color mode used when drawing. Call once at startup.
+pub fn set_mode(mode: ColorMode) {
+    let raw = match mode {
+        ColorMode::TrueColor => 0,
+        ColorMode::Ansi16 => 1,
+        ColorMode::NoColor => 2,
+    };
+    MODE.store(raw, Ordering::Relaxed);
+}
+
+/// The color mode currently in effect.
+pub fn mode() -> ColorMode {
+    match MODE.load(Ordering::Relaxed) {
+        1 => ColorMode::Ansi16,
+        2 => ColorMode::NoColor,
+        _ => ColorMode::TrueColor,
+    }
+}
+
+/// Map every cell of a rendered frame to the current color mode.
+///
+/// In no-color mode, cells that had a background (selections, key badges) are
+/// shown reversed so they stay distinguishable.
+pub fn apply(buf: &mut Buffer) {
+    let mode = mode();
+    if mode == ColorMode::TrueColor {
+        return;
+    }
+    for cell in buf.content.iter_mut() {
+        match mode {
+            ColorMode::NoColor => {
+                if cell.bg != Color::Reset {
+                    cell.modifier.insert(Modifier::REVERSED);
+                }
+                cell.fg = Color::Reset;
+                cell.bg = Color::Reset;
+            }
+            _ => {
+                cell.fg = to_ansi16(cell.fg);
+                cell.bg = to_ansi16(cell.bg);
+            }
+        }
+    }
+}
+
+/// The 16 ANSI colors with their conventional (xterm) RGB values.
+const ANSI16: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::Red, (205, 0, 0)),
+    (Color::Green, (0, 205, 0)),
+    (Color::Yellow, (205, 205, 0)),
+    (Color::Blue, (0, 0, 238)),
+    (Color::Magenta, (205, 0, 205)),
+    (Color::Cyan, (0, 205, 205)),
+    (Color::Gray, (229, 229, 229)),
+    (Color::DarkGray, (127, 127, 127)),
+    (Color::LightRed, (255, 0, 0)),
+    (Color::LightGreen, (0, 255, 0)),
+    (Color::LightYellow, (255, 255, 0)),
+    (Color::LightBlue, (92, 92, 255)),
+    (Color::LightMagenta, (255, 0, 255)),
+    (Color::LightCyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+/// Map any color to the nearest of the 16 ANSI colors; named colors pass through.
+pub fn to_ansi16(color: Color) -> Color {
+    let (r, g, b) = match color {
+        Color::Rgb(r, g, b) => (r, g, b),
+        Color::Indexed(i) if i < 16 => return ANSI16[i as usize].0,
+        Color::Indexed(i) => indexed_to_rgb(i),
+        other => return other,
+    };
+    ANSI16
+        .iter()
+        .min_by_key(|(_, (pr, pg, pb))| {
+            let dr = i32::from(r) - i32::from(*pr);
+            let dg = i32::from(g) - i32::from(*pg);
+            let db = i32::from(b) - i32::from(*pb);
+            dr * dr + dg * dg + db * db
+        })
+        .map(|(c, _)| *c)
+        .unwrap_or(Color::Reset)
+}
+
+/// RGB value of an xterm-256 palette entry (16..=255).
+fn indexed_to_rgb(i: u8) -> (u8, u8, u8) {
+    if i >= 232 {
+        let v = 8 + (i - 232) * 10;
+        return (v, v, v);
+    }
+    let i = i - 16;
+    let level = |n: u8| if n == 0 { 0 } else { 55 + n * 40 };
+    (level(i / 36), level((i / 6) % 6), level(i % 6))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::testing::env_of;
+
+    #[test]
+    fn test_no_color_env_and_flag_win() {
+        let vars = [("NO_COLOR", "1"), ("COLORTERM", "truecolor")];
+        assert_eq!(
+            ColorMode::resolve_from("truecolor", false, env_of(&vars)),
+            ColorMode::NoColor
+        );
+        assert_eq!(
+            ColorMode::resolve_from("auto", true, env_of(&[])),
+            ColorMode::NoColor
+        );
+        // Empty NO_COLOR is ignored
+        let vars = [("NO_COLOR", ""), ("COLORTERM", "truecolor")];
+        assert_eq!(
+            ColorMode::resolve_from("auto", false, env_of(&vars)),
+            ColorMode::TrueColor
+        );
+    }
+
+    #[test]
+    fn test_auto_detects_truecolor_or_falls_back() {
+        let vars = [("COLORTERM", "24bit")];
+        assert_eq!(
+            ColorMode::resolve_from("auto", false, env_of(&vars)),
+            ColorMode::TrueColor
+        );
+        let vars = [("TERM", "xterm-256color")];
+        assert_eq!(
+            ColorMode::resolve_from("auto", false, env_of(&vars)),
+            ColorMode::Ansi16
+        );
+        assert_eq!(
+            ColorMode::resolve_from("16", false, env_of(&[("COLORTERM", "truecolor")])),
+            ColorMode::Ansi16
+        );
+    }
+
+    #[test]
+    fn test_to_ansi16() {
+        assert_eq!(to_ansi16(Color::Rgb(190, 50, 60)), Color::Red);
+        assert_eq!(to_ansi16(Color::Rgb(50, 190, 140)), Color::Cyan);
+        assert_eq!(to_ansi16(Color::Indexed(9)), Color::LightRed);
+        assert_eq!(to_ansi16(Color::Indexed(196)), Color::LightRed);
+        assert_eq!(to_ansi16(Color::Indexed(244)), Color::DarkGray);
+        assert_eq!(to_ansi16(Color::Yellow), Color::Yellow);
+        assert_eq!(to_ansi16(Color::Reset), Color::Reset);
+    }
+}
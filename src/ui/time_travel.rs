@@ -1,13 +1,19 @@
 use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::{
+    Frame,
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap},
-    Frame,
 };
 
 use crate::git;
+use crate::ui::datetime;
+use crate::ui::icons::Icon;
+use crate::ui::utils::{
+    display_width, list_text_width, render_paragraph_scrollbar, render_scrollbar, truncate_to_width,
+};
+use crate::ui::zen;
 
 #[derive(Default)]
 pub struct TimeTravelState {
@@ -17,11 +23,70 @@ pub struct TimeTravelState {
     pub ai_suggestion: Option<String>,
     pub ai_loading: bool,
     pub ai_scroll: u16,
+    /// What resetting to the selected commit would change, while open.
+    pub preview: Option<ResetPreview>,
+}
+
+/// The files that differ between HEAD and a commit: what a reset there would
+/// change, looked at before doing it.
+pub struct ResetPreview {
+    pub hash: String,
+    pub short_hash: String,
+    pub files: Vec<git::diff::FileChange>,
+    /// Files with uncommitted changes, which a hard reset also discards.
+    pub uncommitted: usize,
+    pub selected: usize,
+    pub list_state: ListState,
+    pub diff_text: String,
+    pub diff_scroll: u16,
+}
+
+impl ResetPreview {
+    fn load(commit: &git::CommitEntry) -> Self {
+        let files = git::diff::file_changes("HEAD", &commit.hash).unwrap_or_default();
+        let uncommitted = git::status::get_status()
+            .map(|s| {
+                let mut paths: Vec<&str> = s
+                    .staged
+                    .iter()
+                    .chain(&s.unstaged)
+                    .map(|f| f.path.as_str())
+                    .collect();
+                paths.sort();
+                paths.dedup();
+                paths.len()
+            })
+            .unwrap_or(0);
+        let mut preview = Self {
+            hash: commit.hash.clone(),
+            short_hash: commit.short_hash.clone(),
+            files,
+            uncommitted,
+            selected: 0,
+            list_state: ListState::default(),
+            diff_text: String::new(),
+            diff_scroll: 0,
+        };
+        preview.select(0);
+        preview
+    }
+
+    fn select(&mut self, index: usize) {
+        self.selected = index;
+        self.list_state
+            .select((!self.files.is_empty()).then_some(index));
+        self.diff_text = self
+            .files
+            .get(index)
+            .and_then(|f| git::diff::file_diff("HEAD", &self.hash, &f.path).ok())
+            .unwrap_or_default();
+        self.diff_scroll = 0;
+    }
 }
 
 impl TimeTravelState {
     pub fn refresh(&mut self) {
-        match git::log::get_log(50, 0, None) {
+        match git::log::get_log(50, 0, &[]) {
             Ok(commits) => {
                 self.commits = commits;
                 if self.selected >= self.commits.len() && !self.commits.is_empty() {
@@ -41,40 +106,43 @@ impl TimeTravelState {
 }
 
 pub fn render(f: &mut Frame, area: Rect, state: &mut TimeTravelState) {
-    let has_ai = state.ai_suggestion.is_some() || state.ai_loading;
+    let has_panel = state.ai_suggestion.is_some() || state.ai_loading || state.preview.is_some();
 
     let chunks = Layout::default()
         .direction(Direction::Vertical)
-        .constraints(if has_ai {
+        .constraints(if has_panel {
             vec![
-                Constraint::Percentage(40), // Commit list
-                Constraint::Percentage(50), // AI suggestion panel
-                Constraint::Length(5),      // Action hints
+                Constraint::Percentage(40),         // Commit list
+                Constraint::Percentage(50),         // Preview or AI suggestion panel
+                Constraint::Length(zen::chrome(5)), // Action hints
             ]
         } else {
             vec![
-                Constraint::Min(10),   // Commit list
-                Constraint::Length(0), // No AI panel
-                Constraint::Length(5), // Action hints
+                Constraint::Min(10),                // Commit list
+                Constraint::Length(0),              // No AI panel
+                Constraint::Length(zen::chrome(5)), // Action hints
             ]
         })
         .split(area);
 
     // Commit list
+    let row_width = list_text_width(chunks[0]);
     let items: Vec<ListItem> = state
         .commits
         .iter()
         .map(|c| {
+            let date = datetime::format_iso(&c.date_iso, &c.date);
+            let used = display_width(&c.short_hash) + display_width(&date) + 5;
             ListItem::new(Line::from(vec![
                 Span::styled(
                     format!("  {} ", c.short_hash),
                     Style::default().fg(Color::Yellow),
                 ),
-                Span::styled(&c.message, Style::default().fg(Color::White)),
                 Span::styled(
-                    format!("  {}", c.date),
-                    Style::default().fg(Color::DarkGray),
+                    truncate_to_width(&c.message, row_width.saturating_sub(used).max(12)),
+                    Style::default().fg(Color::White),
                 ),
+                Span::styled(format!("  {}", date), Style::default().fg(Color::DarkGray)),
             ]))
         })
         .collect();
@@ -83,7 +151,7 @@ pub fn render(f: &mut Frame, area: Rect, state: &mut TimeTravelState) {
         .block(
             Block::default()
                 .title(Span::styled(
-                    " ⏪ Time Travel — Select a commit ",
+                    format!(" {} Time Travel — Select a commit ", Icon::Rewind),
                     Style::default()
                         .fg(Color::White)
                         .add_modifier(Modifier::BOLD),
@@ -98,13 +166,16 @@ pub fn render(f: &mut Frame, area: Rect, state: &mut TimeTravelState) {
         )
         .highlight_symbol("▶ ");
 
-    f.render_stateful_widget(list, chunks[0], &mut state.list_state);
+    f.render_stateful_widget(&list, chunks[0], &mut state.list_state);
+    render_scrollbar(f, chunks[0], list.len(), state.list_state.offset());
 
-    // AI suggestion panel
-    if has_ai {
+    if let Some(preview) = &mut state.preview {
+        render_preview(f, chunks[1], preview);
+    } else if has_panel {
+        // AI suggestion panel
         let ai_content = if state.ai_loading {
             vec![Line::from(Span::styled(
-                "  ⏳ AI is analyzing reset options...",
+                format!("  {} AI is analyzing reset options...", Icon::Loading),
                 Style::default()
                     .fg(Color::Yellow)
                     .add_modifier(Modifier::ITALIC),
@@ -140,7 +211,7 @@ pub fn render(f: &mut Frame, area: Rect, state: &mut TimeTravelState) {
             .block(
                 Block::default()
                     .title(Span::styled(
-                        " 🤖 AI Reset Insight — [Esc] dismiss ",
+                        format!(" {} AI Reset Insight — [Esc] dismiss ", Icon::Ai),
                         Style::default()
                             .fg(Color::Cyan)
                             .add_modifier(Modifier::BOLD),
@@ -151,7 +222,8 @@ pub fn render(f: &mut Frame, area: Rect, state: &mut TimeTravelState) {
             .scroll((state.ai_scroll, 0))
             .wrap(Wrap { trim: false });
 
-        f.render_widget(ai_panel, chunks[1]);
+        f.render_widget(&ai_panel, chunks[1]);
+        render_paragraph_scrollbar(f, chunks[1], &ai_panel, state.ai_scroll);
     }
 
     // Action hints
@@ -162,11 +234,13 @@ pub fn render(f: &mut Frame, area: Rect, state: &mut TimeTravelState) {
             Span::styled("[m]", Style::default().fg(Color::Yellow)),
             Span::raw(" Mixed Reset "),
             Span::styled("[h]", Style::default().fg(Color::Red)),
-            Span::raw(" Hard Reset (⚠ destructive) "),
+            Span::raw(format!(" Hard Reset ({} destructive) ", Icon::Warning)),
         ]),
         Line::from(vec![
             Span::styled(" [b]", Style::default().fg(Color::Cyan)),
             Span::raw(" Create Branch "),
+            Span::styled("[c]", Style::default().fg(Color::Cyan)),
+            Span::raw(" Check Out "),
             Span::styled("[f]", Style::default().fg(Color::Cyan)),
             Span::raw(" Restore File "),
             Span::styled("[i]", Style::default().fg(Color::Magenta)),
@@ -174,6 +248,21 @@ pub fn render(f: &mut Frame, area: Rect, state: &mut TimeTravelState) {
             Span::styled("[q]", Style::default().fg(Color::DarkGray)),
             Span::raw(" Back"),
         ]),
+        Line::from(if state.preview.is_some() {
+            vec![
+                Span::styled(" [r]", Style::default().fg(Color::Cyan)),
+                Span::raw(" Restore Selected File "),
+                Span::styled("[PgDn/Up]", Style::default().fg(Color::Cyan)),
+                Span::raw(" Scroll Diff "),
+                Span::styled("[p]", Style::default().fg(Color::DarkGray)),
+                Span::raw(" Close Preview"),
+            ]
+        } else {
+            vec![
+                Span::styled(" [p]", Style::default().fg(Color::Cyan)),
+                Span::raw(" Preview Changes (before resetting)"),
+            ]
+        }),
     ])
     .block(
         Block::default()
@@ -188,9 +277,160 @@ pub fn render(f: &mut Frame, area: Rect, state: &mut TimeTravelState) {
     f.render_widget(hints, chunks[2]);
 }
 
+fn render_preview(f: &mut Frame, area: Rect, preview: &mut ResetPreview) {
+    let panes = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+        .split(area);
+
+    let title = if preview.uncommitted > 0 {
+        format!(
+            " HEAD → {}: {} file(s), +{} uncommitted on hard reset ",
+            preview.short_hash,
+            preview.files.len(),
+            preview.uncommitted
+        )
+    } else {
+        format!(
+            " HEAD → {}: {} file(s) ",
+            preview.short_hash,
+            preview.files.len()
+        )
+    };
+    let row_width = list_text_width(panes[0]);
+    let list = List::new(crate::ui::outgoing::file_items(&preview.files, row_width))
+        .block(
+            Block::default()
+                .title(Span::styled(title, Style::default().fg(Color::White)))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Magenta)),
+        )
+        .highlight_style(
+            Style::default()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol("▶ ");
+    f.render_stateful_widget(&list, panes[0], &mut preview.list_state);
+    render_scrollbar(f, panes[0], list.len(), preview.list_state.offset());
+
+    if preview.files.is_empty() {
+        let inner = panes[0].inner(ratatui::layout::Margin::new(2, 1));
+        f.render_widget(
+            Paragraph::new(Span::styled(
+                "No committed differences — only uncommitted work would change",
+                Style::default().fg(Color::DarkGray),
+            ))
+            .wrap(Wrap { trim: false }),
+            inner,
+        );
+    }
+
+    let diff_lines: Vec<Line> = preview
+        .diff_text
+        .lines()
+        .map(|line| {
+            let color = crate::ui::utils::diff_line_color(line);
+            Line::from(Span::styled(line, Style::default().fg(color)))
+        })
+        .collect();
+    let diff_title = match preview.files.get(preview.selected) {
+        Some(file) => format!(" {} ", file.path),
+        None => " Diff Preview ".to_string(),
+    };
+    let diff = Paragraph::new(diff_lines)
+        .block(
+            Block::default()
+                .title(Span::styled(diff_title, Style::default().fg(Color::White)))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::DarkGray)),
+        )
+        .scroll((preview.diff_scroll, 0))
+        .wrap(Wrap { trim: false });
+    f.render_widget(&diff, panes[1]);
+    render_paragraph_scrollbar(f, panes[1], &diff, preview.diff_scroll);
+}
+
+/// Restore one file as it was at `commit`, in the index and working tree: a
+/// lighter alternative to resetting everything.
+pub fn restore_file(app: &mut crate::app::App, commit: &str, path: &str) {
+    let short = &commit[..7.min(commit.len())];
+    match git::run_git(&[
+        "restore",
+        "--source",
+        commit,
+        "--staged",
+        "--worktree",
+        "--",
+        path,
+    ]) {
+        Ok(_) => app.set_status(format!("Restored {} from {}", path, short)),
+        Err(e) => app.set_status(format!("Error: {}", e)),
+    }
+    // The restored file no longer differs from the commit
+    let state = &mut app.time_travel_state;
+    if let Some(commit) = state
+        .preview
+        .as_ref()
+        .and_then(|p| state.commits.iter().find(|c| c.hash == p.hash))
+    {
+        let selected = state.preview.as_ref().map_or(0, |p| p.selected);
+        let mut preview = ResetPreview::load(commit);
+        preview.select(selected.min(preview.files.len().saturating_sub(1)));
+        state.preview = Some(preview);
+    }
+}
+
 pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()> {
     let state = &mut app.time_travel_state;
 
+    // The reset preview takes navigation; reset keys still act on its commit
+    if let Some(preview) = &mut state.preview {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('p') => {
+                state.preview = None;
+                return Ok(());
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                if preview.selected + 1 < preview.files.len() {
+                    preview.select(preview.selected + 1);
+                }
+                return Ok(());
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                if preview.selected > 0 {
+                    preview.select(preview.selected - 1);
+                }
+                return Ok(());
+            }
+            KeyCode::PageDown => {
+                preview.diff_scroll = preview.diff_scroll.saturating_add(10);
+                return Ok(());
+            }
+            KeyCode::PageUp => {
+                preview.diff_scroll = preview.diff_scroll.saturating_sub(10);
+                return Ok(());
+            }
+            KeyCode::Char('r') => {
+                if let Some(file) = preview.files.get(preview.selected) {
+                    app.popup = crate::app::Popup::Confirm {
+                        title: "Restore File".to_string(),
+                        message: format!(
+                            "Restore {} as it was at {}?\n\nOnly this file changes; any uncommitted edits to it are replaced.\n\n[y] Yes  [n] No",
+                            file.path, preview.short_hash
+                        ),
+                        on_confirm: crate::app::ConfirmAction::RestoreFileFrom {
+                            commit: preview.hash.clone(),
+                            path: file.path.clone(),
+                        },
+                    };
+                }
+                return Ok(());
+            }
+            _ => {}
+        }
+    }
+
     // If AI panel is visible, handle scroll/dismiss first
     if state.ai_suggestion.is_some() {
         match key.code {
@@ -222,20 +462,25 @@ pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()
     }
 
     match key.code {
-        KeyCode::Up | KeyCode::Char('k') if state.ai_suggestion.is_none() => {
-            if state.selected > 0 {
-                state.selected -= 1;
-                state.list_state.select(Some(state.selected));
-            }
+        KeyCode::Up | KeyCode::Char('k') if state.ai_suggestion.is_none() && state.selected > 0 => {
+            state.selected -= 1;
+            state.list_state.select(Some(state.selected));
         }
-        KeyCode::Down | KeyCode::Char('j') if state.ai_suggestion.is_none() => {
-            if state.selected + 1 < state.commits.len() {
-                state.selected += 1;
-                state.list_state.select(Some(state.selected));
+        KeyCode::Down | KeyCode::Char('j')
+            if state.ai_suggestion.is_none() && state.selected + 1 < state.commits.len() =>
+        {
+            state.selected += 1;
+            state.list_state.select(Some(state.selected));
+        }
+        KeyCode::Char('p') => {
+            if let Some(commit) = state.commits.get(state.selected) {
+                state.ai_suggestion = None;
+                state.preview = Some(ResetPreview::load(commit));
             }
         }
         KeyCode::Char('i') => {
             // AI reset insight
+            state.preview = None;
             if let Some(commit) = state.commits.get(state.selected) {
                 let target_hash = commit.short_hash.clone();
                 let target_msg = commit.message.clone();
@@ -287,10 +532,13 @@ pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()
                 let hash = commit.hash.clone();
                 let short = &commit.short_hash;
                 app.popup = crate::app::Popup::Confirm {
-                    title: "⚠ HARD RESET — DESTRUCTIVE".to_string(),
+                    title: format!("{} HARD RESET — DESTRUCTIVE", Icon::Warning),
                     message: format!(
-                        "Hard reset to {}?\n\n⚠ WARNING: This will PERMANENTLY DELETE all uncommitted changes!\n⚠ All staged and unstaged work will be LOST.\n⚠ This cannot be undone (but lost commits may be in reflog).\n\nAre you ABSOLUTELY sure? [y] Yes  [n] No",
-                        short
+                        "Hard reset to {}?\n\n{} WARNING: This will PERMANENTLY DELETE all uncommitted changes!\n{} All staged and unstaged work will be LOST.\n{} This cannot be undone (but lost commits may be in reflog).\n\nAre you ABSOLUTELY sure? [y] Yes  [n] No",
+                        short,
+                        Icon::Warning,
+                        Icon::Warning,
+                        Icon::Warning
                     ),
                     on_confirm: crate::app::ConfirmAction::HardReset(hash),
                 };
@@ -307,15 +555,21 @@ pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()
                 };
             }
         }
+        KeyCode::Char('c') => {
+            // Check out the selected commit to inspect it
+            if let Some(commit) = state.commits.get(state.selected) {
+                let (hash, short) = (commit.hash.clone(), commit.short_hash.clone());
+                crate::ui::branches::confirm_detach(app, &hash, &short);
+            }
+        }
         KeyCode::Char('f') => {
             // Restore a specific file from the selected commit
             if let Some(commit) = state.commits.get(state.selected) {
-                let hash = commit.short_hash.clone();
                 app.popup = crate::app::Popup::Input {
-                    title: format!("Restore File from {}", hash),
+                    title: format!("Restore File from {}", commit.short_hash),
                     prompt: "File path to restore: ".to_string(),
                     value: String::new(),
-                    on_submit: crate::app::InputAction::SearchFiles,
+                    on_submit: crate::app::InputAction::RestoreFileFrom(commit.hash.clone()),
                 };
             }
         }
@@ -1,13 +1,43 @@
 use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::{
+    Frame,
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap},
-    Frame,
 };
+use std::time::Instant;
 
 use crate::git;
+use crate::git::log::{Decoration, RefKind};
+use crate::ui::datetime;
+use crate::ui::icons::Icon;
+use crate::ui::identicon;
+use crate::ui::pane_search::{self, PaneSearch};
+use crate::ui::utils::{
+    display_width, list_text_width, render_paragraph_scrollbar, render_scrollbar, truncate_to_width,
+};
+
+/// Which history the timeline walks.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum RefScope {
+    /// HEAD only
+    #[default]
+    Current,
+    Branch(String),
+    /// Every branch and tag, plus remote branches when they're included
+    All,
+}
+
+impl RefScope {
+    fn label(&self) -> String {
+        match self {
+            RefScope::Current => "current branch".to_string(),
+            RefScope::Branch(name) => name.clone(),
+            RefScope::All => "all refs".to_string(),
+        }
+    }
+}
 
 #[derive(Default)]
 pub struct TimelineState {
@@ -17,16 +47,83 @@ pub struct TimelineState {
     pub detail_commit: Option<git::CommitEntry>,
     pub detail_diff: Vec<git::DiffLine>,
     pub detail_scroll: u16,
+    /// The detail commit's note (`git notes`), if it has one.
+    pub detail_notes: Option<String>,
+    /// `/` search in the detail diff.
+    pub detail_search: Option<PaneSearch>,
     pub search_query: String,
     pub page: usize,
     pub show_detail: bool,
+    pub scope: RefScope,
+    /// Walk and decorate remote-tracking branches too.
+    pub include_remotes: bool,
+    /// Show an identicon before each author (`[ui] identicons`).
+    pub identicons: bool,
+    /// Only commits touching this file (`zit remote history <path>`).
+    pub path: Option<String>,
+    /// Commits marked with Space for cherry-picking, by hash.
+    pub marked: Vec<String>,
+    /// When the page shown was asked for.
+    pub loaded_at: Option<Instant>,
+}
+
+/// Commits per timeline page.
+const PAGE_SIZE: usize = 100;
+
+/// Which commits the timeline shows, and whose note the detail pane shows.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Query {
+    skip: usize,
+    revs: Vec<String>,
+    detail: Option<String>,
+}
+
+/// A page of history, read on a worker (see `tasks`).
+pub struct Loaded {
+    at: Instant,
+    query: Query,
+    commits: anyhow::Result<Vec<git::CommitEntry>>,
+    notes: Option<String>,
 }
 
 impl TimelineState {
     pub fn refresh(&mut self) {
-        let count = 100;
-        let skip = self.page * count;
-        match git::log::get_log(count, skip, None) {
+        self.apply(Self::load(self.query(), Instant::now()));
+    }
+
+    /// What [`refresh`](Self::refresh) reads as the state stands.
+    pub fn query(&self) -> Query {
+        Query {
+            skip: self.page * PAGE_SIZE,
+            revs: self.revs().into_iter().map(String::from).collect(),
+            detail: self
+                .detail_commit
+                .as_ref()
+                .filter(|_| self.show_detail)
+                .map(|c| c.hash.clone()),
+        }
+    }
+
+    pub fn load(query: Query, at: Instant) -> Loaded {
+        let revs: Vec<&str> = query.revs.iter().map(String::as_str).collect();
+        let commits = git::log::get_log(PAGE_SIZE, query.skip, &revs);
+        // The note may just have been edited
+        let notes = query.detail.as_deref().and_then(git::notes::show);
+        Loaded {
+            at,
+            query,
+            commits,
+            notes,
+        }
+    }
+
+    /// Show what [`load`](Self::load) read, unless something newer is shown
+    /// or the page, scope or detail commit changed since it was asked for.
+    pub fn apply(&mut self, loaded: Loaded) {
+        if loaded.query != self.query() || !crate::tasks::is_newer(&mut self.loaded_at, loaded.at) {
+            return;
+        }
+        match loaded.commits {
             Ok(commits) => {
                 self.commits = commits;
                 if self.selected >= self.commits.len() && !self.commits.is_empty() {
@@ -42,6 +139,67 @@ impl TimelineState {
                 self.commits = Vec::new();
             }
         }
+        if loaded.query.detail.is_some() {
+            self.detail_notes = loaded.notes;
+        }
+    }
+
+    fn revs(&self) -> Vec<&str> {
+        let mut revs = match &self.scope {
+            RefScope::Current => Vec::new(),
+            RefScope::Branch(name) => vec![name.as_str()],
+            RefScope::All => {
+                let mut revs = vec!["--branches", "--tags"];
+                if self.include_remotes {
+                    revs.push("--remotes");
+                }
+                revs.push("HEAD");
+                revs
+            }
+        };
+        if let Some(path) = &self.path {
+            revs.extend(["--", path.as_str()]);
+        }
+        revs
+    }
+
+    /// Show only the history of `path` (or everything again for `None`),
+    /// starting from the first page.
+    pub fn set_path(&mut self, path: Option<String>) {
+        self.path = path;
+        self.search_query.clear();
+        self.show_detail = false;
+        self.set_scope(self.scope.clone());
+    }
+
+    /// Open the detail pane on `hash`, whether or not it's on the current page.
+    pub fn show_commit(&mut self, hash: &str) -> anyhow::Result<()> {
+        let commit = git::log::get_commits(&["--no-walk", hash], 1)?
+            .into_iter()
+            .next()
+            .ok_or_else(|| anyhow::anyhow!("No such commit: {}", hash))?;
+        if let Some(index) = self.commits.iter().position(|c| c.hash == commit.hash) {
+            self.selected = index;
+            self.list_state.select(Some(index));
+        }
+        self.open_detail(commit);
+        Ok(())
+    }
+
+    /// Switch to another scope, starting again from the first page.
+    pub fn set_scope(&mut self, scope: RefScope) {
+        self.scope = scope;
+        self.page = 0;
+        self.selected = 0;
+        self.refresh();
+    }
+
+    fn decorations(&self, commit: &git::CommitEntry) -> Vec<Decoration> {
+        let mut decorations = commit.decorations();
+        if !self.include_remotes {
+            decorations.retain(|d| d.kind != RefKind::Remote);
+        }
+        decorations
     }
 
     pub fn do_search(&mut self) {
@@ -67,19 +225,23 @@ impl TimelineState {
                 self.detail_diff.clear();
                 return;
             }
+            self.open_detail(commit.clone());
+        }
+    }
 
-            self.detail_commit = Some(commit.clone());
-            self.detail_diff.clear();
-            self.detail_scroll = 0;
-
-            if let Ok(diffs) = git::diff::get_commit_diff(&commit.hash) {
-                for fd in &diffs {
-                    for hunk in &fd.hunks {
-                        self.detail_diff.extend(hunk.lines.clone());
-                    }
+    fn open_detail(&mut self, commit: git::CommitEntry) {
+        self.detail_diff.clear();
+        self.detail_scroll = 0;
+        self.detail_notes = git::notes::show(&commit.hash);
+        if let Ok(diffs) = git::diff::get_commit_diff(&commit.hash) {
+            for fd in &diffs {
+                for hunk in &fd.hunks {
+                    self.detail_diff.extend(hunk.lines.clone());
                 }
             }
         }
+        self.detail_commit = Some(commit);
+        self.show_detail = true;
     }
 }
 
@@ -90,6 +252,7 @@ pub fn render(f: &mut Frame, area: Rect, state: &mut TimelineState) {
     }
 
     // Commit list
+    let row_width = list_text_width(area);
     let items: Vec<ListItem> = state
         .commits
         .iter()
@@ -105,37 +268,73 @@ pub fn render(f: &mut Frame, area: Rect, state: &mut TimelineState) {
                 return ListItem::new(Line::from(vec![graph_span]));
             }
 
+            let mark_span = if state.marked.contains(&c.hash) {
+                Span::styled("● ", Style::default().fg(Color::Green))
+            } else if state.marked.is_empty() {
+                Span::raw("")
+            } else {
+                Span::raw("  ")
+            };
             let hash_span = Span::styled(
                 format!("{} ", c.short_hash),
                 Style::default().fg(Color::Yellow),
             );
 
-            let refs_span = if c.refs.is_empty() {
-                Span::raw("")
+            let ref_spans = decoration_spans(&state.decorations(c));
+
+            let meta = format!(
+                "{} · {}",
+                c.author,
+                datetime::format_iso(&c.date_iso, &c.date)
+            );
+            let avatar_span = if state.identicons {
+                let key = if c.author_email.is_empty() {
+                    &c.author
+                } else {
+                    &c.author_email
+                };
+                identicon::span(key)
             } else {
-                Span::styled(
-                    format!("({}) ", c.refs),
-                    Style::default()
-                        .fg(Color::Cyan)
-                        .add_modifier(Modifier::BOLD),
-                )
+                Span::raw("")
             };
-
-            let msg_span = Span::styled(&c.message, Style::default().fg(Color::White));
-
-            let meta_span = Span::styled(
-                format!("  {} · {}", c.author, c.date),
-                Style::default().fg(Color::DarkGray),
+            // Shorten the subject so author and date stay visible
+            let used = display_width(&c.graph)
+                + mark_span.width()
+                + hash_span.width()
+                + ref_spans.iter().map(Span::width).sum::<usize>()
+                + 2
+                + avatar_span.width()
+                + display_width(&meta);
+            let msg_span = Span::styled(
+                truncate_to_width(&c.message, row_width.saturating_sub(used).max(12)),
+                Style::default().fg(Color::White),
             );
 
-            ListItem::new(Line::from(vec![
-                graph_span, hash_span, refs_span, msg_span, meta_span,
-            ]))
+            let meta_span = Span::styled(meta, Style::default().fg(Color::DarkGray));
+
+            let mut spans = vec![graph_span, mark_span, hash_span];
+            spans.extend(ref_spans);
+            spans.extend([msg_span, Span::raw("  "), avatar_span, meta_span]);
+            ListItem::new(Line::from(spans))
         })
         .collect();
 
     let title = if state.search_query.is_empty() {
-        format!(" Commit Timeline (page {}) ", state.page + 1)
+        format!(
+            " Commit Timeline — {}{}{} (page {}) ",
+            state.scope.label(),
+            if state.include_remotes {
+                " +remotes"
+            } else {
+                ""
+            },
+            state
+                .path
+                .as_ref()
+                .map(|p| format!(" — {}", p))
+                .unwrap_or_default(),
+            state.page + 1
+        )
     } else {
         format!(
             " Search: '{}' ({} results) ",
@@ -144,6 +343,16 @@ pub fn render(f: &mut Frame, area: Rect, state: &mut TimelineState) {
         )
     };
 
+    let title = if state.marked.is_empty() {
+        title
+    } else {
+        format!(
+            "{}— {} marked, p to cherry-pick ",
+            title,
+            state.marked.len()
+        )
+    };
+
     let list = List::new(items)
         .block(
             Block::default()
@@ -163,20 +372,51 @@ pub fn render(f: &mut Frame, area: Rect, state: &mut TimelineState) {
         )
         .highlight_symbol("▶ ");
 
-    f.render_stateful_widget(list, area, &mut state.list_state);
+    f.render_stateful_widget(&list, area, &mut state.list_state);
+    render_scrollbar(f, area, list.len(), state.list_state.offset());
+}
+
+/// Colored ref chips: HEAD's branch bold, branches green, remotes red, tags yellow.
+fn decoration_spans(decorations: &[Decoration]) -> Vec<Span<'static>> {
+    decorations
+        .iter()
+        .map(|d| {
+            let (text, color) = match d.kind {
+                RefKind::Head => ("HEAD".to_string(), Color::Cyan),
+                RefKind::Branch if d.is_head => (format!("HEAD → {}", d.name), Color::Green),
+                RefKind::Branch => (d.name.clone(), Color::Green),
+                RefKind::Remote => (d.name.clone(), Color::Red),
+                RefKind::Tag => (format!("tag: {}", d.name), Color::Yellow),
+                RefKind::Other => (d.name.clone(), Color::DarkGray),
+            };
+            let mut style = Style::default().fg(color);
+            if d.is_head {
+                style = style.add_modifier(Modifier::BOLD);
+            }
+            Span::styled(format!("[{}] ", text), style)
+        })
+        .collect()
 }
 
+/// Most note lines shown above the diff.
+const NOTE_LINES: usize = 4;
+
 fn render_detail(f: &mut Frame, area: Rect, state: &TimelineState) {
+    let note_lines: Vec<&str> = state
+        .detail_notes
+        .as_deref()
+        .map(|note| note.lines().take(NOTE_LINES).collect())
+        .unwrap_or_default();
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(6), // Commit info
-            Constraint::Min(10),   // Diff
+            Constraint::Length(6 + note_lines.len() as u16), // Commit info
+            Constraint::Min(10),                             // Diff
         ])
         .split(area);
 
     if let Some(commit) = &state.detail_commit {
-        let info = Paragraph::new(vec![
+        let mut info = vec![
             Line::from(vec![
                 Span::styled("  Commit: ", Style::default().fg(Color::DarkGray)),
                 Span::styled(&commit.hash, Style::default().fg(Color::Yellow)),
@@ -187,7 +427,10 @@ fn render_detail(f: &mut Frame, area: Rect, state: &TimelineState) {
             ]),
             Line::from(vec![
                 Span::styled("  Date:   ", Style::default().fg(Color::DarkGray)),
-                Span::styled(&commit.date, Style::default().fg(Color::White)),
+                Span::styled(
+                    datetime::format_iso(&commit.date_iso, &commit.date),
+                    Style::default().fg(Color::White),
+                ),
             ]),
             Line::from(vec![
                 Span::styled("  Message: ", Style::default().fg(Color::DarkGray)),
@@ -198,13 +441,28 @@ fn render_detail(f: &mut Frame, area: Rect, state: &TimelineState) {
                         .add_modifier(Modifier::BOLD),
                 ),
             ]),
-        ])
-        .block(
+        ];
+        for (i, line) in note_lines.iter().enumerate() {
+            let label = if i == 0 { "  Notes:  " } else { "          " };
+            info.push(Line::from(vec![
+                Span::styled(label, Style::default().fg(Color::DarkGray)),
+                Span::styled(*line, Style::default().fg(Color::Yellow)),
+            ]));
+        }
+        let info = Paragraph::new(info).block(
             Block::default()
                 .title(Span::styled(
                     " Commit Details ",
                     Style::default().fg(Color::White),
                 ))
+                .title_bottom(Span::styled(
+                    if state.detail_notes.is_some() {
+                        " n edit note "
+                    } else {
+                        " n add note "
+                    },
+                    Style::default().fg(Color::DarkGray),
+                ))
                 .borders(Borders::ALL)
                 .border_style(Style::default().fg(Color::Cyan)),
         );
@@ -223,6 +481,8 @@ fn render_detail(f: &mut Frame, area: Rect, state: &TimelineState) {
             };
             Line::from(Span::styled(&dl.content, Style::default().fg(color)))
         })
+        .enumerate()
+        .map(|(i, line)| pane_search::highlight(line, state.detail_search.as_ref(), i))
         .collect();
 
     let diff = Paragraph::new(diff_lines)
@@ -235,12 +495,22 @@ fn render_detail(f: &mut Frame, area: Rect, state: &TimelineState) {
         .scroll((state.detail_scroll, 0))
         .wrap(Wrap { trim: false });
 
-    f.render_widget(diff, chunks[1]);
+    f.render_widget(&diff, chunks[1]);
+    render_paragraph_scrollbar(f, chunks[1], &diff, state.detail_scroll);
 }
 
 pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()> {
     if app.timeline_state.show_detail {
         match key.code {
+            KeyCode::Esc if app.timeline_state.detail_search.is_some() => {
+                app.timeline_state.detail_search = None;
+            }
+            KeyCode::Char('/') => app.open_pane_search(),
+            // While searching, n/N move between matches instead of editing the note
+            KeyCode::Char('n') if app.timeline_state.detail_search.is_some() => {
+                app.step_pane_search(true);
+            }
+            KeyCode::Char('N') => app.step_pane_search(false),
             KeyCode::Esc | KeyCode::Char('q') => {
                 app.timeline_state.show_detail = false;
             }
@@ -260,32 +530,35 @@ pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()
                 app.timeline_state.detail_scroll =
                     app.timeline_state.detail_scroll.saturating_sub(20);
             }
+            KeyCode::Char('n') => {
+                if let Some(commit) = &app.timeline_state.detail_commit {
+                    app.editor_request =
+                        Some(crate::ui::editor::EditorTarget::Notes(commit.hash.clone()));
+                }
+            }
             _ => {}
         }
         return Ok(());
     }
 
     match key.code {
-        KeyCode::Up | KeyCode::Char('k') => {
-            if app.timeline_state.selected > 0 {
-                app.timeline_state.selected -= 1;
-                let sel = app.timeline_state.selected;
-                app.timeline_state.list_state.select(Some(sel));
-            }
+        KeyCode::Up | KeyCode::Char('k') if app.timeline_state.selected > 0 => {
+            app.timeline_state.selected -= 1;
+            let sel = app.timeline_state.selected;
+            app.timeline_state.list_state.select(Some(sel));
         }
-        KeyCode::Down | KeyCode::Char('j') => {
-            if app.timeline_state.selected + 1 < app.timeline_state.commits.len() {
-                app.timeline_state.selected += 1;
-                let sel = app.timeline_state.selected;
-                app.timeline_state.list_state.select(Some(sel));
-            }
+        KeyCode::Down | KeyCode::Char('j')
+            if app.timeline_state.selected + 1 < app.timeline_state.commits.len() =>
+        {
+            app.timeline_state.selected += 1;
+            let sel = app.timeline_state.selected;
+            app.timeline_state.list_state.select(Some(sel));
         }
         KeyCode::Enter => {
-            if let Some(commit) = app.timeline_state.commits.get(app.timeline_state.selected) {
-                if !commit.hash.is_empty() {
-                    app.timeline_state.load_detail();
-                    app.timeline_state.show_detail = true;
-                }
+            if let Some(commit) = app.timeline_state.commits.get(app.timeline_state.selected)
+                && !commit.hash.is_empty()
+            {
+                app.timeline_state.load_detail();
             }
         }
         KeyCode::Char('/') => {
@@ -300,30 +573,332 @@ pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()
         KeyCode::Char('y') => {
             // Copy hash to clipboard
             let selected = app.timeline_state.selected;
-            if let Some(commit) = app.timeline_state.commits.get(selected) {
-                if !commit.hash.is_empty() {
-                    let hash = commit.short_hash.clone();
-                    match cli_clipboard::set_contents(hash.clone()) {
-                        Ok(()) => app.set_status(format!("✓ Copied to clipboard: {}", hash)),
-                        Err(_) => app.set_status(format!("Copied (display only): {}", hash)),
+            if let Some(commit) = app.timeline_state.commits.get(selected)
+                && !commit.hash.is_empty()
+            {
+                let hash = commit.short_hash.clone();
+                match cli_clipboard::set_contents(hash.clone()) {
+                    Ok(()) => app.set_status(format!("✓ Copied to clipboard: {}", hash)),
+                    Err(_) => app.set_status(format!("Copied (display only): {}", hash)),
+                }
+            }
+        }
+        KeyCode::Char('b') => {
+            if let Some(commit) = app.timeline_state.commits.get(app.timeline_state.selected)
+                && !commit.hash.is_empty()
+            {
+                let hash = commit.hash.clone();
+                match app.timeline_state.path.clone() {
+                    // Showing one file's history: blame that file
+                    Some(path) => crate::ui::blame::open(app, &path, Some(hash), None),
+                    None => {
+                        app.popup = crate::app::Popup::Input {
+                            title: format!("Blame at {}", &hash[..7.min(hash.len())]),
+                            prompt: "File: ".to_string(),
+                            value: String::new(),
+                            on_submit: crate::app::InputAction::BlameFile(hash),
+                        };
                     }
                 }
             }
         }
+        KeyCode::Char('c') => {
+            if let Some(commit) = app.timeline_state.commits.get(app.timeline_state.selected)
+                && !commit.hash.is_empty()
+            {
+                let (hash, short) = (commit.hash.clone(), commit.short_hash.clone());
+                crate::ui::branches::confirm_detach(app, &hash, &short);
+            }
+        }
+        KeyCode::Char('i') => {
+            if let Some(commit) = app.timeline_state.commits.get(app.timeline_state.selected)
+                && !commit.hash.is_empty()
+            {
+                let commit = commit.clone();
+                crate::ui::rebase::open(app, &commit);
+            }
+        }
+        KeyCode::Char(' ') => {
+            let state = &mut app.timeline_state;
+            if let Some(commit) = state.commits.get(state.selected)
+                && !commit.hash.is_empty()
+            {
+                let hash = commit.hash.clone();
+                if let Some(pos) = state.marked.iter().position(|h| *h == hash) {
+                    state.marked.remove(pos);
+                } else {
+                    state.marked.push(hash);
+                }
+            }
+        }
+        KeyCode::Char('p') => confirm_cherry_pick(app),
+        KeyCode::Char('I') => open_identity_check(app),
+        KeyCode::Char('f') => open_scope_picker(app),
+        KeyCode::Char('R') => {
+            let state = &mut app.timeline_state;
+            state.include_remotes = !state.include_remotes;
+            if state.scope == RefScope::All {
+                state.page = 0;
+                state.selected = 0;
+            }
+            state.refresh();
+            app.set_status(if app.timeline_state.include_remotes {
+                "Timeline: including remote branches"
+            } else {
+                "Timeline: local refs only"
+            });
+        }
         KeyCode::PageDown => {
             app.timeline_state.page += 1;
             app.timeline_state.selected = 0;
             app.timeline_state.refresh();
         }
-        KeyCode::PageUp => {
-            if app.timeline_state.page > 0 {
-                app.timeline_state.page -= 1;
-                app.timeline_state.selected = 0;
-                app.timeline_state.refresh();
-            }
+        KeyCode::PageUp if app.timeline_state.page > 0 => {
+            app.timeline_state.page -= 1;
+            app.timeline_state.selected = 0;
+            app.timeline_state.refresh();
         }
         _ => {}
     }
 
     Ok(())
 }
+
+/// Most commits listed when asking to cherry-pick.
+const CHERRY_PICK_LISTED: usize = 8;
+
+/// Ask before cherry-picking the marked commits (or the selected one) onto
+/// the current branch.
+fn confirm_cherry_pick(app: &mut crate::app::App) {
+    use crate::app::{ConfirmAction, Popup};
+
+    let state = &app.timeline_state;
+    let hashes: Vec<String> = if state.marked.is_empty() {
+        match state.commits.get(state.selected) {
+            Some(c) if !c.hash.is_empty() => vec![c.hash.clone()],
+            _ => return,
+        }
+    } else {
+        state.marked.clone()
+    };
+    let branch = git::cherry_pick::get_current_branch();
+    let (on_branch, hashes): (Vec<String>, Vec<String>) = hashes
+        .into_iter()
+        .partition(|h| git::cherry_pick::is_on_current_branch(h));
+    if hashes.is_empty() {
+        app.set_status(format!("Already on {} — nothing to cherry-pick", branch));
+        return;
+    }
+    let hashes = match git::cherry_pick::oldest_first(&hashes) {
+        Ok(hashes) => hashes,
+        Err(e) => {
+            app.set_status(format!("Error: {}", e));
+            return;
+        }
+    };
+
+    let mut message = format!(
+        "Apply {} commit(s) onto {}, oldest first:\n\n",
+        hashes.len(),
+        branch
+    );
+    for hash in hashes.iter().take(CHERRY_PICK_LISTED) {
+        let subject = state
+            .commits
+            .iter()
+            .find(|c| c.hash == *hash)
+            .map(|c| c.message.as_str())
+            .unwrap_or_default();
+        message.push_str(&format!(
+            "  {} {}\n",
+            &hash[..7.min(hash.len())],
+            truncate_to_width(subject, 50)
+        ));
+    }
+    if hashes.len() > CHERRY_PICK_LISTED {
+        message.push_str(&format!(
+            "  ...and {} more\n",
+            hashes.len() - CHERRY_PICK_LISTED
+        ));
+    }
+    if !on_branch.is_empty() {
+        message.push_str(&format!(
+            "\n{} already on {} (skipped)\n",
+            on_branch.len(),
+            branch
+        ));
+    }
+    message.push_str("\n[y] Yes  [n] No");
+    app.popup = Popup::Confirm {
+        title: "Cherry-pick".to_string(),
+        message,
+        on_confirm: ConfirmAction::CherryPickCommits(hashes),
+    };
+}
+
+/// Cherry-pick `hashes` (oldest first). Conflicts are resolved in Merge
+/// Resolve, whose Continue picks the rest.
+pub fn cherry_pick(app: &mut crate::app::App, hashes: Vec<String>) {
+    let refs: Vec<&str> = hashes.iter().map(String::as_str).collect();
+    match git::cherry_pick::cherry_pick_multiple(&refs) {
+        Ok(_) => {
+            app.set_status(format!(
+                "✓ Cherry-picked {} commit(s) onto {}",
+                hashes.len(),
+                git::cherry_pick::get_current_branch()
+            ));
+            app.timeline_state.marked.clear();
+            app.timeline_state.refresh();
+        }
+        Err(_) if git::cherry_pick::is_cherry_picking() => {
+            app.timeline_state.marked.clear();
+            app.set_status("Cherry-pick stopped on a conflict — resolve it here, then Continue");
+            app.open_view(crate::app::View::MergeResolve);
+            app.refresh();
+        }
+        Err(e) => {
+            let err = e.to_string();
+            app.set_status(format!("Cherry-pick failed: {}", err));
+            app.start_ai_error_explain(err);
+        }
+    }
+}
+
+/// Branches offered in the scope picker, so every entry has a digit shortcut.
+const SCOPE_PICKER_BRANCHES: usize = 7;
+
+/// Pick which history to show: the current branch, every ref, or one branch.
+fn open_scope_picker(app: &mut crate::app::App) {
+    use crate::app::{FollowUpAction, FollowUpItem, Popup};
+
+    let item = |label: String, description: &str, scope: RefScope| FollowUpItem {
+        label,
+        description: description.to_string(),
+        action: FollowUpAction::TimelineScope(scope),
+    };
+    let mut suggestions = vec![
+        item(
+            "Current branch".to_string(),
+            "History of HEAD",
+            RefScope::Current,
+        ),
+        item(
+            "All refs".to_string(),
+            "Every branch and tag (R includes remotes)",
+            RefScope::All,
+        ),
+    ];
+    let include_remotes = app.timeline_state.include_remotes;
+    let branches = git::branch::BranchOps::list().unwrap_or_default();
+    suggestions.extend(
+        branches
+            .into_iter()
+            .filter(|b| !b.is_current && (include_remotes || !b.is_remote))
+            .take(SCOPE_PICKER_BRANCHES)
+            .map(|b| {
+                let description = if b.is_remote {
+                    "Remote branch"
+                } else {
+                    "Local branch"
+                };
+                item(b.name.clone(), description, RefScope::Branch(b.name))
+            }),
+    );
+    let selected = suggestions
+        .iter()
+        .position(|s| {
+            matches!(&s.action, FollowUpAction::TimelineScope(scope) if *scope == app.timeline_state.scope)
+        })
+        .unwrap_or(0);
+
+    app.popup = Popup::FollowUp {
+        title: "Show History Of".to_string(),
+        context: String::new(),
+        suggestions,
+        selected,
+    };
+}
+
+/// Shown at most in the identity popup; the rest are summarised.
+const IDENTITY_LIST_LIMIT: usize = 5;
+
+/// Check recent commits for a wrong author email and offer to rewrite the unpushed ones.
+fn open_identity_check(app: &mut crate::app::App) {
+    use crate::app::{ConfirmAction, Popup};
+
+    let report = match crate::git::identity::check_recent_commits(crate::git::identity::SCAN_LIMIT)
+    {
+        Ok(r) => r,
+        Err(e) => {
+            app.popup = Popup::Message {
+                title: "Author Identity".to_string(),
+                message: format!("Error: {}", e),
+            };
+            return;
+        }
+    };
+
+    let identity = &report.identity;
+    if report.mismatches.is_empty() {
+        app.set_status(format!(
+            "✓ All {} recent commits are authored by {}",
+            report.scanned, identity.email
+        ));
+        return;
+    }
+
+    let mut message = format!(
+        "Configured: {} <{}>\n{} of the last {} commits use a different email:\n",
+        identity.name,
+        identity.email,
+        report.mismatches.len(),
+        report.scanned
+    );
+    for m in report.mismatches.iter().take(IDENTITY_LIST_LIMIT) {
+        message.push_str(&format!(
+            "  {} <{}>{} {}\n",
+            m.short_hash,
+            m.author_email,
+            if m.pushed { " [pushed]" } else { "" },
+            m.subject
+        ));
+    }
+    if report.mismatches.len() > IDENTITY_LIST_LIMIT {
+        message.push_str(&format!(
+            "  ...and {} more\n",
+            report.mismatches.len() - IDENTITY_LIST_LIMIT
+        ));
+    }
+
+    let pushed = report.pushed_count();
+    if pushed > 0 {
+        message.push_str(&format!(
+            "\n{} {} pushed commit(s) will NOT be rewritten: that needs a force push, \
+             which breaks every clone and open PR based on them. Prefer a .mailmap \
+             entry, or coordinate with collaborators and rewrite manually.\n",
+            Icon::Warning,
+            pushed
+        ));
+    }
+
+    let Some(oldest) = report.oldest_unpushed() else {
+        app.popup = Popup::Message {
+            title: format!("{} Author Identity", Icon::Warning),
+            message,
+        };
+        return;
+    };
+    message.push_str(&format!(
+        "\nRewrite the {} unpushed commit(s) with your identity? Commits from {} \
+         to HEAD are replayed with --reset-author (new hashes, author dates kept, \
+         hooks skipped).\n\n\
+         [y] Yes  [n] No",
+        report.unpushed_count(),
+        oldest.short_hash
+    ));
+    app.popup = Popup::Confirm {
+        title: "Fix Author Email".to_string(),
+        message,
+        on_confirm: ConfirmAction::FixAuthorIdentity,
+    };
+}
@@ -1,5 +1,9 @@
-use ratatui::layout::{Constraint, Direction, Layout, Rect};
-use ratatui::style::Color;
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Direction, Layout, Margin, Rect};
+use ratatui::style::{Color, Style};
+use ratatui::widgets::{Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState};
+use std::time::{Duration, Instant};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
 /// Create a centered rectangle within a given area, using percentage-based sizing.
 pub fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
@@ -22,6 +26,35 @@ pub fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
         .split(popup_layout[1])[1]
 }
 
+/// Smallest terminal zit will draw into; below this a "terminal too small" notice is shown.
+pub const MIN_WIDTH: u16 = 40;
+pub const MIN_HEIGHT: u16 = 12;
+
+/// Below this width, side-by-side panes collapse into a single column.
+const NARROW_WIDTH: u16 = 80;
+/// Below this height, optional panes (hints, summaries, suggestions) are hidden.
+const SHORT_HEIGHT: u16 = 24;
+
+/// Whether `area` is too small to render any view.
+pub fn is_too_small(area: Rect) -> bool {
+    area.width < MIN_WIDTH || area.height < MIN_HEIGHT
+}
+
+/// Whether side-by-side panes should collapse into a single column.
+pub fn is_narrow(area: Rect) -> bool {
+    area.width < NARROW_WIDTH
+}
+
+/// Whether optional panes should be collapsed to leave room for the main content.
+pub fn is_short(area: Rect) -> bool {
+    area.height < SHORT_HEIGHT
+}
+
+/// Height for an optional pane: `height` normally, 0 when `area` is short.
+pub fn optional_height(area: Rect, height: u16) -> u16 {
+    if is_short(area) { 0 } else { height }
+}
+
 /// Return the appropriate color for a unified-diff line.
 ///
 /// Handles `+++`/`---` header lines (Yellow), `@@` hunk headers (Cyan),
@@ -50,3 +83,253 @@ pub fn navigate_list(current: usize, delta: isize, len: usize) -> usize {
     let new = current as isize + delta;
     new.clamp(0, (len as isize) - 1) as usize
 }
+
+/// Usable text columns inside a bordered list that shows a two-column highlight symbol.
+pub fn list_text_width(area: Rect) -> usize {
+    area.width.saturating_sub(4) as usize
+}
+
+/// Draw a scrollbar over the right border of the bordered pane `area` when
+/// its `total` lines don't fit: the thumb's size is the share in view, its
+/// place how far down `offset` (the first line shown) is. The thumb is a
+/// border line, so zen mode still recognizes the pane and strips it.
+pub fn render_scrollbar(f: &mut Frame, area: Rect, total: usize, offset: usize) {
+    let track = area.inner(Margin {
+        vertical: 1,
+        horizontal: 0,
+    });
+    let visible = track.height as usize;
+    if total <= visible || track.width == 0 {
+        return;
+    }
+    let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
+        .begin_symbol(None)
+        .end_symbol(None)
+        .track_symbol(None)
+        .thumb_symbol("┃")
+        .thumb_style(Style::default().fg(Color::Cyan));
+    // Thumb at the bottom once the last line is in view
+    let mut state = ScrollbarState::new(total - visible + 1)
+        .viewport_content_length(visible)
+        .position(offset);
+    f.render_stateful_widget(scrollbar, track, &mut state);
+}
+
+/// [`render_scrollbar`] for a bordered paragraph scrolled down `scroll`
+/// lines, counting the lines it takes once wrapped to the pane.
+pub fn render_paragraph_scrollbar(f: &mut Frame, area: Rect, paragraph: &Paragraph, scroll: u16) {
+    let width = area.width.saturating_sub(2);
+    // line_count includes the top and bottom borders
+    let total = paragraph.line_count(width).saturating_sub(2);
+    render_scrollbar(f, area, total, scroll as usize);
+}
+
+/// Terminal column width of a string (CJK and most emoji take two columns).
+pub fn display_width(s: &str) -> usize {
+    UnicodeWidthStr::width(s)
+}
+
+/// Truncate `s` to at most `max_width` terminal columns, appending `…` when cut.
+///
+/// Never splits a character, so it is safe on any UTF-8 input and any width.
+pub fn truncate_to_width(s: &str, max_width: usize) -> String {
+    if display_width(s) <= max_width {
+        return s.to_string();
+    }
+    if max_width == 0 {
+        return String::new();
+    }
+    let budget = max_width - 1; // room for the ellipsis
+    let mut out = String::new();
+    let mut used = 0;
+    for ch in s.chars() {
+        let w = UnicodeWidthChar::width(ch).unwrap_or(0);
+        if used + w > budget {
+            break;
+        }
+        out.push(ch);
+        used += w;
+    }
+    out.push('…');
+    out
+}
+
+/// Truncate or right-pad `s` with spaces so it occupies exactly `width` columns.
+pub fn pad_to_width(s: &str, width: usize) -> String {
+    let mut out = truncate_to_width(s, width);
+    let used = display_width(&out);
+    out.push_str(&" ".repeat(width.saturating_sub(used)));
+    out
+}
+
+/// Truncate or pad `s` on both sides so it is centered in exactly `width` columns.
+pub fn center_to_width(s: &str, width: usize) -> String {
+    let text = truncate_to_width(s, width);
+    let slack = width.saturating_sub(display_width(&text));
+    let left = slack / 2;
+    format!("{}{}{}", " ".repeat(left), text, " ".repeat(slack - left))
+}
+
+/// Word-wrap `s` into lines of at most `width` columns.
+///
+/// Leading indentation of each input line is kept; words wider than a full line are
+/// hard-split. A zero width yields no lines.
+pub fn wrap_to_width(s: &str, width: usize) -> Vec<String> {
+    if width == 0 {
+        return Vec::new();
+    }
+    let mut lines = Vec::new();
+    for paragraph in s.split('\n') {
+        let body = paragraph.trim_start();
+        let mut line = truncate_to_width(&paragraph[..paragraph.len() - body.len()], width);
+        let mut used = display_width(&line);
+        let mut has_word = false;
+        for word in body.split_whitespace() {
+            let word_width = display_width(word);
+            let sep = usize::from(has_word);
+            if used + sep + word_width <= width {
+                if has_word {
+                    line.push(' ');
+                }
+                line.push_str(word);
+                used += sep + word_width;
+                has_word = true;
+                continue;
+            }
+            if has_word {
+                lines.push(std::mem::take(&mut line));
+                used = 0;
+            }
+            for ch in word.chars() {
+                let w = UnicodeWidthChar::width(ch).unwrap_or(0);
+                if used + w > width && !line.is_empty() {
+                    lines.push(std::mem::take(&mut line));
+                    used = 0;
+                }
+                line.push(ch);
+                used += w;
+            }
+            has_word = true;
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+/// How long navigation has to pause before a deferred reload runs.
+pub const SETTLE_DELAY: Duration = Duration::from_millis(120);
+
+/// A reload put off until navigation pauses, so holding j/k moves the cursor
+/// without loading data for every row it passes.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Debounce {
+    due: Option<Instant>,
+}
+
+impl Debounce {
+    /// Start (or restart) the wait.
+    pub fn schedule(&mut self) {
+        self.due = Some(Instant::now() + SETTLE_DELAY);
+    }
+
+    /// Drop the pending reload, e.g. because it just ran directly.
+    pub fn cancel(&mut self) {
+        self.due = None;
+    }
+
+    /// Time left until the reload is due, if one is pending.
+    pub fn remaining(&self) -> Option<Duration> {
+        self.due
+            .map(|due| due.saturating_duration_since(Instant::now()))
+    }
+
+    /// Whether the reload should run now — because it's due, or because
+    /// `force` is set and one is pending. Clears it if so.
+    pub fn fire(&mut self, force: bool) -> bool {
+        match self.due {
+            Some(due) if force || due <= Instant::now() => {
+                self.due = None;
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_size_classes() {
+        let tiny = Rect::new(0, 0, 30, 10);
+        assert!(is_too_small(tiny));
+        let small = Rect::new(0, 0, 60, 20);
+        assert!(!is_too_small(small));
+        assert!(is_narrow(small));
+        assert!(is_short(small));
+        assert_eq!(optional_height(small, 5), 0);
+        let large = Rect::new(0, 0, 120, 40);
+        assert!(!is_narrow(large) && !is_short(large));
+        assert_eq!(optional_height(large, 5), 5);
+    }
+
+    #[test]
+    fn test_truncate_ascii() {
+        assert_eq!(truncate_to_width("hello world", 5), "hell…");
+        assert_eq!(truncate_to_width("hello", 5), "hello");
+        assert_eq!(truncate_to_width("hello", 0), "");
+    }
+
+    #[test]
+    fn test_truncate_wide_chars() {
+        // Each CJK character is two columns wide
+        let s = "修复提交信息";
+        assert_eq!(display_width(s), 12);
+        let t = truncate_to_width(s, 6);
+        assert_eq!(t, "修复…");
+        assert!(display_width(&t) <= 6);
+        assert_eq!(truncate_to_width("🎉🎉🎉", 4), "🎉…");
+    }
+
+    #[test]
+    fn test_pad_and_center() {
+        assert_eq!(pad_to_width("日本", 6), "日本  ");
+        assert_eq!(display_width(&pad_to_width("日本語テキスト", 6)), 6);
+        assert_eq!(center_to_width("ab", 6), "  ab  ");
+        assert_eq!(display_width(&center_to_width("日本語", 5)), 5);
+    }
+
+    #[test]
+    fn test_wrap_to_width() {
+        assert_eq!(
+            wrap_to_width("the quick brown fox", 9),
+            vec!["the quick", "brown fox"]
+        );
+        assert_eq!(
+            wrap_to_width("日本語のテキスト", 6),
+            vec!["日本語", "のテキ", "スト"]
+        );
+        assert_eq!(wrap_to_width("a\n\nb", 5), vec!["a", "", "b"]);
+        assert_eq!(wrap_to_width("  - item one", 8), vec!["  - item", "one"]);
+        assert!(wrap_to_width("anything", 0).is_empty());
+    }
+
+    #[test]
+    fn test_debounce() {
+        let mut d = Debounce::default();
+        assert!(d.remaining().is_none() && !d.fire(true));
+
+        d.schedule();
+        assert!(d.remaining().unwrap() > Duration::ZERO);
+        assert!(!d.fire(false));
+        assert!(d.fire(true), "forcing runs a pending reload early");
+        assert!(d.remaining().is_none());
+
+        d.schedule();
+        std::thread::sleep(SETTLE_DELAY);
+        assert_eq!(d.remaining(), Some(Duration::ZERO));
+        assert!(d.fire(false));
+        assert!(!d.fire(false));
+    }
+}
@@ -1,10 +1,13 @@
+use crate::ui::icons::Icon;
+use crate::ui::utils::{center_to_width, render_paragraph_scrollbar, truncate_to_width};
+use crate::ui::zen;
 use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::{
+    Frame,
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Paragraph, Wrap},
-    Frame,
 };
 
 // ─── Data Model ────────────────────────────────────────────
@@ -214,17 +217,17 @@ pub fn render(f: &mut Frame, area: Rect, state: &WorkflowBuilderState) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(3), // Title
-            Constraint::Length(3), // Workflow info
-            Constraint::Min(10),   // Pipeline view
-            Constraint::Length(3), // Keys
-            Constraint::Length(2), // Status
+            Constraint::Length(zen::chrome(3)), // Title
+            Constraint::Length(3),              // Workflow info
+            Constraint::Min(10),                // Pipeline view
+            Constraint::Length(zen::chrome(3)), // Keys
+            Constraint::Length(2),              // Status
         ])
         .split(area);
 
     // Title
     let title = Paragraph::new(Line::from(vec![
-        Span::styled("  🔧 ", Style::default()),
+        Span::styled(format!("  {} ", Icon::Tool), Style::default()),
         Span::styled(
             "Workflow Builder",
             Style::default()
@@ -453,29 +456,17 @@ fn render_pipeline(f: &mut Frame, area: Rect, state: &WorkflowBuilderState) {
             };
 
             let box_width = 18;
-            let name = if node.name.len() > box_width - 4 {
-                format!("{}…", &node.name[..box_width - 5])
-            } else {
-                node.name.clone()
-            };
+            let name = truncate_to_width(&node.name, box_width - 4);
             let detail = if let Some(ref uses) = node.uses {
-                if uses.len() > box_width - 4 {
-                    format!("{}…", &uses[..box_width - 5])
-                } else {
-                    uses.clone()
-                }
+                truncate_to_width(uses, box_width - 4)
             } else if let Some(ref run) = node.run {
-                if run.len() > box_width - 4 {
-                    format!("{}…", &run[..box_width - 5])
-                } else {
-                    run.clone()
-                }
+                truncate_to_width(run, box_width - 4)
             } else {
                 "(empty)".to_string()
             };
 
-            let name_padded = format!("{:^width$}", name, width = box_width - 2);
-            let detail_padded = format!("{:^width$}", detail, width = box_width - 2);
+            let name_padded = center_to_width(&name, box_width - 2);
+            let detail_padded = center_to_width(&detail, box_width - 2);
             let border_top = "─".repeat(box_width - 2);
             let border_bot = "─".repeat(box_width - 2);
 
@@ -567,7 +558,8 @@ fn render_pipeline(f: &mut Frame, area: Rect, state: &WorkflowBuilderState) {
         )
         .wrap(Wrap { trim: false })
         .scroll((state.scroll, 0));
-    f.render_widget(pipeline, area);
+    f.render_widget(&pipeline, area);
+    render_paragraph_scrollbar(f, area, &pipeline, state.scroll);
 }
 
 /// Build topological layers for rendering. Nodes with no dependencies go
@@ -641,21 +633,18 @@ pub fn handle_key(app: &mut crate::app::App, key: KeyEvent) -> anyhow::Result<()
 
 fn handle_navigate_key(state: &mut WorkflowBuilderState, key: KeyEvent) {
     match key.code {
-        KeyCode::Left | KeyCode::Char('h') => {
-            if state.selected > 0 {
+        KeyCode::Left | KeyCode::Char('h')
+            if state.selected > 0 => {
                 state.selected -= 1;
             }
-        }
-        KeyCode::Right | KeyCode::Char('l') => {
-            if !state.nodes.is_empty() && state.selected + 1 < state.nodes.len() {
+        KeyCode::Right | KeyCode::Char('l')
+            if !state.nodes.is_empty() && state.selected + 1 < state.nodes.len() => {
                 state.selected += 1;
             }
-        }
-        KeyCode::Up | KeyCode::Char('k') => {
-            if state.scroll > 0 {
+        KeyCode::Up | KeyCode::Char('k')
+            if state.scroll > 0 => {
                 state.scroll -= 1;
             }
-        }
         KeyCode::Down | KeyCode::Char('j') => {
             state.scroll += 1;
         }
@@ -665,44 +654,39 @@ fn handle_navigate_key(state: &mut WorkflowBuilderState, key: KeyEvent) {
             state.mode = BuilderMode::EditName;
             state.input_buffer.clear();
         }
-        KeyCode::Enter => {
+        KeyCode::Enter
             // Edit selected node — cycle name → uses → run
-            if !state.nodes.is_empty() {
+            if !state.nodes.is_empty() => {
                 state.adding_new = false;
                 state.mode = BuilderMode::EditName;
                 state.input_buffer = state.nodes[state.selected].name.clone();
             }
-        }
-        KeyCode::Char('u') => {
+        KeyCode::Char('u')
             // Edit uses directly
-            if !state.nodes.is_empty() {
+            if !state.nodes.is_empty() => {
                 state.mode = BuilderMode::EditUses;
                 state.input_buffer = state.nodes[state.selected].uses.clone().unwrap_or_default();
             }
-        }
-        KeyCode::Char('r') => {
+        KeyCode::Char('r')
             // Edit run directly
-            if !state.nodes.is_empty() {
+            if !state.nodes.is_empty() => {
                 state.mode = BuilderMode::EditRun;
                 state.input_buffer = state.nodes[state.selected].run.clone().unwrap_or_default();
             }
-        }
-        KeyCode::Char('d') => {
+        KeyCode::Char('d')
             // Delete selected node
-            if !state.nodes.is_empty() {
+            if !state.nodes.is_empty() => {
                 let name = state.nodes[state.selected].name.clone();
                 state.remove_selected();
                 state.status = Some(format!("Removed '{}'", name));
             }
-        }
-        KeyCode::Char('c') => {
+        KeyCode::Char('c')
             // Start connecting
-            if !state.nodes.is_empty() {
+            if !state.nodes.is_empty() => {
                 state.mode = BuilderMode::Connect;
                 state.connect_from = Some(state.nodes[state.selected].id);
                 state.status = Some("Select target node and press [c] to connect".to_string());
             }
-        }
         KeyCode::Char('g') => {
             // Generate YAML
             let yaml = state.generate_yaml();
@@ -769,22 +753,22 @@ fn handle_edit_key(state: &mut WorkflowBuilderState, key: KeyEvent) {
                             state.nodes[state.selected].uses.clone().unwrap_or_default();
                     }
                 }
-                BuilderMode::EditUses => {
-                    if !state.nodes.is_empty() && state.selected < state.nodes.len() {
-                        state.nodes[state.selected].uses =
-                            if value.is_empty() { None } else { Some(value) };
-                        state.mode = BuilderMode::EditRun;
-                        state.input_buffer =
-                            state.nodes[state.selected].run.clone().unwrap_or_default();
-                    }
+                BuilderMode::EditUses
+                    if !state.nodes.is_empty() && state.selected < state.nodes.len() =>
+                {
+                    state.nodes[state.selected].uses =
+                        if value.is_empty() { None } else { Some(value) };
+                    state.mode = BuilderMode::EditRun;
+                    state.input_buffer =
+                        state.nodes[state.selected].run.clone().unwrap_or_default();
                 }
-                BuilderMode::EditRun => {
-                    if !state.nodes.is_empty() && state.selected < state.nodes.len() {
-                        state.nodes[state.selected].run =
-                            if value.is_empty() { None } else { Some(value) };
-                        state.mode = BuilderMode::Navigate;
-                        state.status = Some("✓ Step updated".to_string());
-                    }
+                BuilderMode::EditRun
+                    if !state.nodes.is_empty() && state.selected < state.nodes.len() =>
+                {
+                    state.nodes[state.selected].run =
+                        if value.is_empty() { None } else { Some(value) };
+                    state.mode = BuilderMode::Navigate;
+                    state.status = Some("✓ Step updated".to_string());
                 }
                 _ => {}
             }
@@ -852,15 +836,13 @@ fn handle_connect_key(state: &mut WorkflowBuilderState, key: KeyEvent) {
             state.connect_from = None;
             state.status = None;
         }
-        KeyCode::Left | KeyCode::Char('h') => {
-            if state.selected > 0 {
-                state.selected -= 1;
-            }
+        KeyCode::Left | KeyCode::Char('h') if state.selected > 0 => {
+            state.selected -= 1;
         }
-        KeyCode::Right | KeyCode::Char('l') => {
-            if !state.nodes.is_empty() && state.selected + 1 < state.nodes.len() {
-                state.selected += 1;
-            }
+        KeyCode::Right | KeyCode::Char('l')
+            if !state.nodes.is_empty() && state.selected + 1 < state.nodes.len() =>
+        {
+            state.selected += 1;
         }
         KeyCode::Char('c') | KeyCode::Enter => {
             if let Some(from_id) = state.connect_from {
@@ -0,0 +1,124 @@
+//! Zen mode — a distraction-free look for zit squeezed into a tmux pane next
+//! to an editor. Title bars and key hint panes collapse, emoji give way to
+//! plain ASCII markers, and the finished frame is stripped of panel borders and
+//! the titles drawn on them, leaving only the lists and diffs.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use ratatui::buffer::Buffer;
+
+static ON: AtomicBool = AtomicBool::new(false);
+
+/// Whether zen mode is on.
+pub fn is_on() -> bool {
+    ON.load(Ordering::Relaxed)
+}
+
+/// Turn zen mode on or off.
+pub fn set(on: bool) {
+    ON.store(on, Ordering::Relaxed);
+}
+
+/// Height for view chrome (title bars, key hints): `height` normally, 0 in
+/// zen mode.
+pub fn chrome(height: u16) -> u16 {
+    if is_on() { 0 } else { height }
+}
+
+const TOP_LEFT: &[&str] = &["┌", "╭", "╔", "┏"];
+const TOP_RIGHT: &[&str] = &["┐", "╮", "╗", "┓"];
+const BOTTOM_LEFT: &[&str] = &["└", "╰", "╚", "┗"];
+const BOTTOM_RIGHT: &[&str] = &["┘", "╯", "╝", "┛"];
+const VERTICAL: &[&str] = &["│", "║", "┃"];
+
+/// Blank out every bordered panel's frame, titles included, when zen mode is
+/// on. Only complete rectangles are touched, so box-drawing characters inside
+/// content (the commit graph, diffs) stay as they are.
+pub fn apply(buf: &mut Buffer) {
+    if is_on() {
+        strip_borders(buf);
+    }
+}
+
+fn symbol(buf: &Buffer, x: u16, y: u16) -> &str {
+    buf[(x, y)].symbol()
+}
+
+fn strip_borders(buf: &mut Buffer) {
+    let area = buf.area;
+    let mut boxes = Vec::new();
+    for y in area.top()..area.bottom() {
+        for x in area.left()..area.right() {
+            if !TOP_LEFT.contains(&symbol(buf, x, y)) {
+                continue;
+            }
+            // Left side down to the bottom corner
+            let Some(bottom) = (y + 1..area.bottom())
+                .take_while(|&by| {
+                    let s = symbol(buf, x, by);
+                    VERTICAL.contains(&s) || BOTTOM_LEFT.contains(&s)
+                })
+                .find(|&by| BOTTOM_LEFT.contains(&symbol(buf, x, by)))
+            else {
+                continue;
+            };
+            // The nearest top-right corner whose right side closes the box
+            let right = (x + 1..area.right()).find(|&rx| {
+                TOP_RIGHT.contains(&symbol(buf, rx, y))
+                    && BOTTOM_RIGHT.contains(&symbol(buf, rx, bottom))
+                    && (y + 1..bottom).all(|ry| VERTICAL.contains(&symbol(buf, rx, ry)))
+            });
+            if let Some(right) = right {
+                boxes.push((x, y, right, bottom));
+            }
+        }
+    }
+    for (left, top, right, bottom) in boxes {
+        for x in left..=right {
+            buf[(x, top)].set_symbol(" ");
+            buf[(x, bottom)].set_symbol(" ");
+        }
+        for y in top + 1..bottom {
+            buf[(left, y)].set_symbol(" ");
+            buf[(right, y)].set_symbol(" ");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn buffer(lines: &[&str]) -> Buffer {
+        Buffer::with_lines(lines.iter().copied())
+    }
+
+    fn text(buf: &Buffer) -> Vec<String> {
+        (0..buf.area.height)
+            .map(|y| {
+                (0..buf.area.width)
+                    .map(|x| buf[(x, y)].symbol())
+                    .collect::<String>()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_strip_borders_blanks_panels_and_titles() {
+        let mut buf = buffer(&["┌ Files ─┐╭──╮", "│ a.rs   ││x │", "└────────┘╰──╯"]);
+        strip_borders(&mut buf);
+        assert_eq!(
+            text(&buf),
+            ["              ", "  a.rs     x  ", "              "]
+        );
+    }
+
+    #[test]
+    fn test_strip_borders_keeps_open_shapes() {
+        // A commit graph: corners and lines that never close into a box
+        let lines = ["│ ╭─╮ ", "├─╯ │ ", "│   ╰┐"];
+        let mut buf = buffer(&lines);
+        strip_borders(&mut buf);
+        assert_eq!(text(&buf), lines);
+    }
+}
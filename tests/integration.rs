@@ -106,6 +106,28 @@ fn test_no_diff_when_clean() {
     assert!(output.trim().is_empty());
 }
 
+#[test]
+fn test_single_file_diff_ignores_pathspec_magic() {
+    let dir = init_repo();
+    for name in ["a.txt", "b.txt", "*.txt"] {
+        std::fs::write(dir.path().join(name), "one\n").unwrap();
+    }
+    git(dir.path(), &["add", "."]);
+    git(dir.path(), &["commit", "-m", "add files"]);
+    for name in ["a.txt", "b.txt", "*.txt"] {
+        std::fs::write(dir.path().join(name), "two\n").unwrap();
+    }
+
+    // As a pathspec, "*.txt" matches every changed file
+    let globbed = git(dir.path(), &["diff", "--", "*.txt"]);
+    assert_eq!(globbed.matches("diff --git").count(), 3);
+
+    // The Staging view diffs the selected file only
+    let literal = git(dir.path(), &["--literal-pathspecs", "diff", "--", "*.txt"]);
+    assert_eq!(literal.matches("diff --git").count(), 1);
+    assert!(literal.contains("diff --git a/*.txt b/*.txt"));
+}
+
 // ────────────────────────────────────────────────────────────────────────
 // Git log tests
 // ────────────────────────────────────────────────────────────────────────
@@ -156,6 +178,149 @@ fn test_delete_branch() {
     assert!(!output.contains("to-delete"));
 }
 
+#[test]
+fn test_switch_with_uncommitted_changes_via_stash() {
+    let dir = init_repo();
+    git(dir.path(), &["checkout", "-b", "feature"]);
+    std::fs::write(dir.path().join("README.md"), "# Feature\n").unwrap();
+    git(dir.path(), &["commit", "-am", "feature readme"]);
+    git(dir.path(), &["checkout", "main"]);
+
+    // An edit to a file that differs on the target blocks a plain switch
+    std::fs::write(dir.path().join("README.md"), "# Test\nlocal\n").unwrap();
+    std::fs::write(dir.path().join("notes.txt"), "scratch\n").unwrap();
+    let differing = git(
+        dir.path(),
+        &["diff", "--name-only", "--no-renames", "HEAD", "feature"],
+    );
+    assert_eq!(differing.trim(), "README.md");
+    git(dir.path(), &["switch", "feature"]);
+    assert_eq!(
+        git(dir.path(), &["branch", "--show-current"]).trim(),
+        "main"
+    );
+
+    // Stash (untracked too), switch, pop: the edit conflicts and the stash is kept
+    git(
+        dir.path(),
+        &[
+            "stash",
+            "push",
+            "--include-untracked",
+            "-m",
+            "zit: switching to feature",
+        ],
+    );
+    git(dir.path(), &["switch", "feature"]);
+    assert_eq!(
+        git(dir.path(), &["branch", "--show-current"]).trim(),
+        "feature"
+    );
+    git(dir.path(), &["stash", "pop", "stash@{0}"]);
+    assert!(git(dir.path(), &["stash", "list"]).contains("zit: switching to feature"));
+    assert!(dir.path().join("notes.txt").exists());
+}
+
+#[test]
+fn test_reset_preview_and_single_file_restore() {
+    let dir = init_repo();
+    std::fs::write(dir.path().join("README.md"), "# Changed\n").unwrap();
+    std::fs::write(dir.path().join("new.txt"), "new\n").unwrap();
+    git(dir.path(), &["add", "."]);
+    git(dir.path(), &["commit", "-m", "change"]);
+
+    // What a reset to the first commit would change
+    let numstat = git(
+        dir.path(),
+        &["diff", "--numstat", "--no-renames", "HEAD", "HEAD~1"],
+    );
+    assert_eq!(numstat, "1\t1\tREADME.md\n0\t1\tnew.txt\n");
+
+    // Restoring one file leaves HEAD and the other file alone
+    git(
+        dir.path(),
+        &[
+            "restore",
+            "--source",
+            "HEAD~1",
+            "--staged",
+            "--worktree",
+            "--",
+            "README.md",
+        ],
+    );
+    assert_eq!(
+        std::fs::read_to_string(dir.path().join("README.md")).unwrap(),
+        "# Test\n"
+    );
+    assert!(dir.path().join("new.txt").exists());
+    assert_eq!(
+        git(dir.path(), &["status", "--porcelain"]),
+        "M  README.md\n"
+    );
+    assert!(git(dir.path(), &["log", "-1", "--format=%s"]).starts_with("change"));
+}
+
+#[test]
+fn test_detached_head_commits_and_return() {
+    let dir = init_repo();
+    git(dir.path(), &["commit", "--allow-empty", "-m", "second"]);
+    git(dir.path(), &["switch", "--detach", "HEAD~1"]);
+    assert!(
+        git(dir.path(), &["branch", "--show-current"])
+            .trim()
+            .is_empty()
+    );
+
+    // A commit made while detached is on no branch
+    git(dir.path(), &["commit", "--allow-empty", "-m", "experiment"]);
+    let orphaned = git(
+        dir.path(),
+        &[
+            "rev-list",
+            "--count",
+            "HEAD",
+            "--not",
+            "--branches",
+            "--tags",
+            "--remotes",
+        ],
+    );
+    assert_eq!(orphaned.trim(), "1");
+    let reflog = git(dir.path(), &["reflog", "-n", "200", "--format=%gs"]);
+    assert!(reflog.contains("checkout: moving from main to "));
+
+    // Naming a branch here keeps it
+    git(dir.path(), &["switch", "-c", "experiment"]);
+    assert_eq!(
+        git(dir.path(), &["branch", "--show-current"]).trim(),
+        "experiment"
+    );
+    git(dir.path(), &["switch", "main"]);
+    assert!(git(dir.path(), &["log", "--oneline", "experiment"]).contains("experiment"));
+}
+
+#[test]
+fn test_git_aliases_listed_and_run_with_arguments() {
+    let dir = init_repo();
+    git(dir.path(), &["config", "alias.st", "status -sb"]);
+    git(
+        dir.path(),
+        &[
+            "config",
+            "alias.nb",
+            "!git branch $1 && git branch --list $1",
+        ],
+    );
+    let output = git(dir.path(), &["config", "-z", "--get-regexp", r"^alias\."]);
+    assert!(output.contains("alias.st\nstatus -sb\0"));
+    assert!(output.contains("alias.nb\n!git branch $1 && git branch --list $1\0"));
+
+    // A shell alias gets its $1 from the arguments after it
+    let listed = git(dir.path(), &["nb", "from-alias"]);
+    assert!(listed.contains("from-alias"));
+}
+
 // ────────────────────────────────────────────────────────────────────────
 // Git reflog tests
 // ────────────────────────────────────────────────────────────────────────
@@ -167,6 +332,25 @@ fn test_reflog_has_entries() {
     assert!(!output.trim().is_empty());
 }
 
+#[test]
+fn test_lost_commits_found_by_fsck() {
+    let dir = init_repo();
+    git(dir.path(), &["commit", "--allow-empty", "-m", "reset away"]);
+    let lost = git(dir.path(), &["rev-parse", "HEAD"]);
+    git(dir.path(), &["reset", "--hard", "HEAD~1"]);
+
+    // Only in the reflog now: dangling once reflogs don't count
+    let fsck = git(dir.path(), &["fsck", "--no-reflogs", "--no-progress"]);
+    assert!(fsck.contains(&format!("dangling commit {}", lost.trim())));
+    let reflog = git(dir.path(), &["reflog", "show", "--all", "--format=%H"]);
+    assert!(reflog.contains(lost.trim()));
+
+    // Recovering it as a branch makes it reachable again
+    git(dir.path(), &["branch", "recovered", lost.trim()]);
+    let fsck = git(dir.path(), &["fsck", "--no-reflogs", "--no-progress"]);
+    assert!(!fsck.contains("dangling commit"));
+}
+
 #[test]
 fn test_reflog_records_branch_switch() {
     let dir = init_repo();
@@ -220,8 +404,9 @@ fn test_cli_help_flag() {
         .output()
         .expect("failed to run zit");
     let stdout = String::from_utf8(output.stdout).unwrap();
-    assert!(stdout.contains("USAGE"));
-    assert!(stdout.contains("OPTIONS"));
+    assert!(stdout.contains("Usage"));
+    assert!(stdout.contains("Options"));
+    assert!(stdout.contains("Commands"));
     assert!(stdout.contains("--verbose"));
     assert!(output.status.success());
 }
@@ -233,7 +418,7 @@ fn test_cli_unknown_flag_errors() {
         .output()
         .expect("failed to run zit");
     let stderr = String::from_utf8(output.stderr).unwrap();
-    assert!(stderr.contains("Unknown option"));
+    assert!(stderr.contains("unexpected argument '--nonexistent'"));
     assert!(!output.status.success());
 }
 
@@ -249,6 +434,109 @@ fn test_cli_not_git_repo() {
     assert!(!output.status.success());
 }
 
+#[test]
+fn test_cli_status_quiet_exit_codes() {
+    let dir = init_repo();
+    let status = |dir: &std::path::Path| {
+        Command::new(env!("CARGO_BIN_EXE_zit"))
+            .args(["status", "--quiet"])
+            .current_dir(dir)
+            .output()
+            .expect("failed to run zit")
+    };
+    let output = status(dir.path());
+    assert_eq!(output.status.code(), Some(0));
+    assert!(output.stdout.is_empty());
+
+    std::fs::write(dir.path().join("README.md"), "# Changed\n").unwrap();
+    assert_eq!(status(dir.path()).status.code(), Some(3));
+
+    let not_a_repo = TempDir::new().unwrap();
+    assert_eq!(status(not_a_repo.path()).status.code(), Some(4));
+}
+
+#[test]
+fn test_cli_prompt() {
+    let dir = init_repo();
+    let prompt = |dir: &std::path::Path, args: &[&str]| {
+        Command::new(env!("CARGO_BIN_EXE_zit"))
+            .arg("prompt")
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .expect("failed to run zit")
+    };
+    let output = prompt(dir.path(), &[]);
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "main\n");
+
+    std::fs::write(dir.path().join("new.txt"), "new\n").unwrap();
+    std::fs::write(dir.path().join("README.md"), "# Changed\n").unwrap();
+    let output = prompt(dir.path(), &[]);
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "main !1?1\n");
+    let output = prompt(dir.path(), &["--format", "[{branch}{dirty}]"]);
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "[main*]\n");
+
+    let not_a_repo = TempDir::new().unwrap();
+    let output = prompt(not_a_repo.path(), &[]);
+    assert_eq!(output.status.code(), Some(4));
+    assert!(output.stdout.is_empty() && output.stderr.is_empty());
+}
+
+#[test]
+fn test_cli_remote_without_server_errors() {
+    let dir = init_repo();
+    let output = Command::new(env!("CARGO_BIN_EXE_zit"))
+        .args(["remote", "blame", "README.md:1"])
+        .current_dir(dir.path())
+        .output()
+        .expect("failed to run zit");
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("No zit --server is running"));
+    assert!(!output.status.success());
+
+    let output = Command::new(env!("CARGO_BIN_EXE_zit"))
+        .args(["remote", "open", "README.md"])
+        .current_dir(dir.path())
+        .output()
+        .expect("failed to run zit");
+    assert!(
+        String::from_utf8(output.stderr)
+            .unwrap()
+            .contains("invalid value 'open'")
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn test_cli_remote_sends_repo_relative_path() {
+    use std::io::{BufRead, BufReader, Write};
+    use std::os::unix::net::UnixListener;
+
+    let dir = init_repo();
+    std::fs::create_dir(dir.path().join("src")).unwrap();
+    // Stands in for `zit --server`: echoes the command it got
+    let listener = UnixListener::bind(dir.path().join(".git/zit.sock")).unwrap();
+    let server = std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut line = String::new();
+        BufReader::new(&stream).read_line(&mut line).unwrap();
+        write!(stream, "ok got {}", line).unwrap();
+    });
+
+    let output = Command::new(env!("CARGO_BIN_EXE_zit"))
+        .args(["remote", "blame", "../README.md:1"])
+        .current_dir(dir.path().join("src"))
+        .output()
+        .expect("failed to run zit");
+    server.join().unwrap();
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "got blame README.md:1\n"
+    );
+    assert!(output.status.success());
+}
+
 // ────────────────────────────────────────────────────────────────────────
 // --no-ai flag test
 // ────────────────────────────────────────────────────────────────────────
@@ -260,7 +548,7 @@ fn test_cli_no_ai_flag_accepted() {
         .output()
         .expect("failed to run zit");
     let stdout = String::from_utf8(output.stdout).unwrap();
-    assert!(stdout.contains("USAGE"));
+    assert!(stdout.contains("Usage"));
     assert!(output.status.success());
 }
 
@@ -453,6 +741,89 @@ fn test_reflog_preserves_reset_history() {
     assert!(reflog.contains("v2"));
 }
 
+// ────────────────────────────────────────────────────────────────────────
+// Author identity rewrite test
+// ────────────────────────────────────────────────────────────────────────
+
+#[test]
+fn test_reset_author_rewrites_only_mismatched_commits() {
+    let dir = init_repo();
+    git(dir.path(), &["config", "user.name", "Me"]);
+    git(dir.path(), &["config", "user.email", "me@example.com"]);
+    let base = git(dir.path(), &["rev-parse", "HEAD"]).trim().to_string();
+
+    // One commit with the wrong email (test@example.com from the helper)...
+    std::fs::write(dir.path().join("a.txt"), "a\n").unwrap();
+    git(dir.path(), &["add", "."]);
+    git(
+        dir.path(),
+        &[
+            "commit",
+            "-m",
+            "wrong author",
+            "--date",
+            "2002-01-01T00:00:00",
+        ],
+    );
+    // ...and one already using the configured identity
+    std::fs::write(dir.path().join("b.txt"), "b\n").unwrap();
+    git(dir.path(), &["add", "."]);
+    let status = Command::new("git")
+        .args([
+            "commit",
+            "-m",
+            "right author",
+            "--date",
+            "2001-01-01T00:00:00",
+        ])
+        .current_dir(dir.path())
+        .env_remove("GIT_AUTHOR_NAME")
+        .env_remove("GIT_AUTHOR_EMAIL")
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    // A failing hook must not stop the rewrite
+    let hook = dir.path().join(".git/hooks/pre-commit");
+    std::fs::write(&hook, "#!/bin/sh\nexit 1\n").unwrap();
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&hook, std::fs::Permissions::from_mode(0o755)).unwrap();
+    }
+
+    // Same rebase zit runs: reset the author only where the email differs
+    let exec = "[ \"$(git log -1 --format=%ae)\" = 'me@example.com' ] || git commit --amend --no-edit --no-verify --reset-author --date=\"$(git log -1 --format=%aD)\"";
+    let status = Command::new("git")
+        .args([
+            "-c",
+            "sequence.editor=true",
+            "rebase",
+            "--autostash",
+            "--exec",
+            exec,
+            &base,
+        ])
+        .current_dir(dir.path())
+        .env_remove("GIT_AUTHOR_NAME")
+        .env_remove("GIT_AUTHOR_EMAIL")
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let log = git(
+        dir.path(),
+        &["log", "--format=%ae %ad %s", "--date=format:%Y", "-3"],
+    );
+    let lines: Vec<&str> = log.lines().collect();
+    // The correctly attributed commit keeps its original author date
+    assert_eq!(lines[0], "me@example.com 2001 right author");
+    // The rewritten one gets the new email but keeps its author date too
+    assert_eq!(lines[1], "me@example.com 2002 wrong author");
+    // The base commit is not part of the rewrite
+    assert!(lines[2].starts_with("test@example.com "));
+}
+
 // ────────────────────────────────────────────────────────────────────────
 // Edge case: empty stash on clean repo
 // ────────────────────────────────────────────────────────────────────────
@@ -475,3 +846,444 @@ fn test_stash_on_clean_repo_fails() {
         combined
     );
 }
+
+#[test]
+fn test_intent_to_add_shows_diff_and_allows_hunk_staging() {
+    let dir = init_repo();
+    std::fs::write(dir.path().join("new.txt"), "one\ntwo\n").unwrap();
+    assert!(git(dir.path(), &["diff", "--", "new.txt"]).is_empty());
+
+    git(dir.path(), &["add", "--intent-to-add", "--", "new.txt"]);
+    let status = git(dir.path(), &["status", "--porcelain=v2"]);
+    assert!(status.contains("1 .A N..."), "status was: {}", status);
+    let diff = git(dir.path(), &["diff", "--", "new.txt"]);
+    assert!(diff.contains("+one") && diff.contains("+two"));
+
+    // Stage only the first line, the way hunk staging builds its patch
+    let patch = "--- a/new.txt\n+++ b/new.txt\n@@ -0,0 +1 @@\n+one\n";
+    let mut child = Command::new("git")
+        .args(["apply", "--unidiff-zero", "--cached", "-"])
+        .current_dir(dir.path())
+        .stdin(std::process::Stdio::piped())
+        .spawn()
+        .unwrap();
+    use std::io::Write;
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(patch.as_bytes())
+        .unwrap();
+    assert!(child.wait().unwrap().success());
+    assert_eq!(git(dir.path(), &["show", ":new.txt"]), "one\n");
+}
+
+#[test]
+fn test_staged_rename_resolves_to_content_diff() {
+    let dir = init_repo();
+    let body: String = (1..=20).map(|i| format!("line {}\n", i)).collect();
+    std::fs::write(dir.path().join("old.txt"), &body).unwrap();
+    git(dir.path(), &["add", "."]);
+    git(dir.path(), &["commit", "-m", "add old"]);
+
+    git(dir.path(), &["mv", "old.txt", "new.txt"]);
+    std::fs::write(
+        dir.path().join("new.txt"),
+        body.replace("line 5\n", "line five\n"),
+    )
+    .unwrap();
+    git(dir.path(), &["add", "new.txt"]);
+
+    let status = git(
+        dir.path(),
+        &["diff", "--cached", "-M", "-C", "--name-status", "-z"],
+    );
+    assert!(status.starts_with("R0"), "name-status was: {:?}", status);
+    assert!(status.contains("old.txt\0new.txt\0"));
+
+    // Limiting the pathspec to both sides keeps rename detection working
+    let diff = git(
+        dir.path(),
+        &["diff", "--cached", "-M", "-C", "--", "old.txt", "new.txt"],
+    );
+    assert!(diff.contains("rename from old.txt"));
+    assert!(diff.contains("-line 5") && diff.contains("+line five"));
+    assert!(
+        !diff.contains("-line 1\n"),
+        "expected a content diff, got: {}",
+        diff
+    );
+}
+
+#[test]
+fn test_snapshot_via_temp_index_restores_worktree_only() {
+    let dir = init_repo();
+    std::fs::write(dir.path().join("README.md"), "# Changed\n").unwrap();
+    std::fs::write(dir.path().join("notes.txt"), "untracked work\n").unwrap();
+    let status_before = git(dir.path(), &["status", "--porcelain"]);
+
+    // Build the snapshot tree through a throwaway index, like zit does
+    let temp_index = dir.path().join(".git").join("zit-snapshot-index");
+    std::fs::copy(dir.path().join(".git").join("index"), &temp_index).unwrap();
+    let with_index = |args: &[&str]| {
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(dir.path())
+            .env("GIT_INDEX_FILE", &temp_index)
+            .output()
+            .unwrap();
+        String::from_utf8(output.stdout).unwrap()
+    };
+    with_index(&["add", "-A"]);
+    let tree = with_index(&["write-tree"]);
+    std::fs::remove_file(&temp_index).unwrap();
+    let commit = git(
+        dir.path(),
+        &["commit-tree", tree.trim(), "-m", "zit snapshot on main"],
+    );
+    let snapshot_ref = format!("refs/zit/snapshot/{}", commit.trim());
+    git(dir.path(), &["update-ref", &snapshot_ref, commit.trim()]);
+    assert_eq!(git(dir.path(), &["status", "--porcelain"]), status_before);
+
+    // Lose the work, then restore it from the snapshot
+    std::fs::write(dir.path().join("README.md"), "# Oops\n").unwrap();
+    std::fs::remove_file(dir.path().join("notes.txt")).unwrap();
+    let source = format!("--source={}", commit.trim());
+    git(dir.path(), &["restore", &source, "--worktree", "--", ":/"]);
+    assert_eq!(
+        std::fs::read_to_string(dir.path().join("README.md")).unwrap(),
+        "# Changed\n"
+    );
+    assert_eq!(
+        std::fs::read_to_string(dir.path().join("notes.txt")).unwrap(),
+        "untracked work\n"
+    );
+    assert_eq!(git(dir.path(), &["status", "--porcelain"]), status_before);
+}
+
+#[test]
+fn test_outgoing_and_incoming_ranges_against_upstream() {
+    let remote = TempDir::new().unwrap();
+    git(remote.path(), &["init", "--bare", "-b", "main"]);
+    let dir = init_repo();
+    let url = remote.path().to_string_lossy().to_string();
+    git(dir.path(), &["remote", "add", "origin", &url]);
+
+    // No upstream yet: everything not on a remote is outgoing
+    let unpublished = git(
+        dir.path(),
+        &["log", "--format=%s", "HEAD", "--not", "--remotes"],
+    );
+    assert_eq!(unpublished.trim(), "initial commit");
+
+    git(dir.path(), &["push", "-u", "origin", "main"]);
+    let upstream = git(
+        dir.path(),
+        &["rev-parse", "--abbrev-ref", "--symbolic-full-name", "@{u}"],
+    );
+    assert_eq!(upstream.trim(), "origin/main");
+
+    // A commit pushed from another clone shows up as incoming after a fetch
+    let other = TempDir::new().unwrap();
+    git(other.path(), &["clone", &url, "."]);
+    std::fs::write(other.path().join("theirs.txt"), "x\n").unwrap();
+    git(other.path(), &["add", "."]);
+    git(other.path(), &["commit", "-m", "their change"]);
+    git(other.path(), &["push"]);
+
+    std::fs::write(dir.path().join("mine.txt"), "y\n").unwrap();
+    git(dir.path(), &["add", "."]);
+    git(dir.path(), &["commit", "-m", "my change"]);
+    git(dir.path(), &["fetch"]);
+
+    let outgoing = git(dir.path(), &["log", "--format=%s", "origin/main..HEAD"]);
+    let incoming = git(dir.path(), &["log", "--format=%s", "HEAD..origin/main"]);
+    assert_eq!(outgoing.trim(), "my change");
+    assert_eq!(incoming.trim(), "their change");
+
+    // The files each side changes since they forked, also for a named branch
+    let pushed = git(
+        dir.path(),
+        &["diff", "--numstat", "--no-renames", "main@{u}...main"],
+    );
+    let pulled = git(
+        dir.path(),
+        &["diff", "--numstat", "--no-renames", "main...main@{u}"],
+    );
+    assert_eq!(pushed, "1\t0\tmine.txt\n");
+    assert_eq!(pulled, "1\t0\ttheirs.txt\n");
+}
+
+#[test]
+fn test_scaffold_commit_leaves_other_staged_work_alone() {
+    let dir = TempDir::new().unwrap();
+    git(dir.path(), &["init", "-b", "main"]);
+    std::fs::write(dir.path().join("wip.txt"), "work in progress\n").unwrap();
+    git(dir.path(), &["add", "wip.txt"]);
+
+    // Like zit's scaffold: add the new files, then a pathspec commit of just those
+    std::fs::write(dir.path().join(".gitignore"), "target/\n").unwrap();
+    std::fs::write(dir.path().join("README.md"), "# demo\n").unwrap();
+    git(dir.path(), &["add", "--", ".gitignore", "README.md"]);
+    git(
+        dir.path(),
+        &[
+            "commit",
+            "-m",
+            "Initial scaffold",
+            "--",
+            ".gitignore",
+            "README.md",
+        ],
+    );
+
+    let committed = git(dir.path(), &["show", "--name-only", "--format=", "HEAD"]);
+    assert_eq!(
+        committed.lines().collect::<Vec<_>>(),
+        [".gitignore", "README.md"]
+    );
+    assert_eq!(git(dir.path(), &["status", "--porcelain"]), "A  wip.txt\n");
+}
+
+#[test]
+fn test_revert_conflict_continues_without_an_editor() {
+    let dir = init_repo();
+    let commit = |content: &str, message: &str| {
+        std::fs::write(dir.path().join("f.txt"), content).unwrap();
+        git(dir.path(), &["add", "f.txt"]);
+        git(dir.path(), &["commit", "-m", message]);
+    };
+    commit("a\n", "add f");
+    commit("b\n", "a to b");
+    let target = git(dir.path(), &["rev-parse", "HEAD"]);
+    commit("c\n", "b to c");
+
+    // Undoing "a to b" conflicts with the later change
+    git(dir.path(), &["revert", "--no-edit", target.trim()]);
+    let git_dir = dir.path().join(".git");
+    assert!(git_dir.join("REVERT_HEAD").exists());
+    assert!(!git_dir.join("MERGE_HEAD").exists());
+
+    std::fs::write(dir.path().join("f.txt"), "a\n").unwrap();
+    git(dir.path(), &["add", "f.txt"]);
+    // GIT_EDITOR=true (as zit continues) wins over any configured editor
+    let output = Command::new("git")
+        .args(["revert", "--continue"])
+        .current_dir(dir.path())
+        .env("GIT_EDITOR", "true")
+        .env("EDITOR", "false")
+        .env("GIT_AUTHOR_NAME", "Test User")
+        .env("GIT_AUTHOR_EMAIL", "test@example.com")
+        .env("GIT_COMMITTER_NAME", "Test User")
+        .env("GIT_COMMITTER_EMAIL", "test@example.com")
+        .stdin(std::process::Stdio::null())
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(!git_dir.join("REVERT_HEAD").exists());
+    let subject = git(dir.path(), &["log", "-1", "--format=%s"]);
+    assert_eq!(subject.trim(), "Revert \"a to b\"");
+}
+
+#[test]
+fn test_staged_resolution_can_be_made_conflicted_again() {
+    let dir = init_repo();
+    std::fs::write(dir.path().join("f.txt"), "base\n").unwrap();
+    git(dir.path(), &["add", "f.txt"]);
+    git(dir.path(), &["commit", "-m", "base"]);
+    git(dir.path(), &["checkout", "-b", "feature"]);
+    std::fs::write(dir.path().join("f.txt"), "theirs\n").unwrap();
+    git(dir.path(), &["commit", "-am", "theirs"]);
+    git(dir.path(), &["checkout", "main"]);
+    std::fs::write(dir.path().join("f.txt"), "ours\n").unwrap();
+    git(dir.path(), &["commit", "-am", "ours"]);
+    git(dir.path(), &["merge", "feature"]);
+
+    // The stage 1-3 entries, saved before the resolution is staged
+    let entries: Vec<String> = git(dir.path(), &["ls-files", "-s", "--", "f.txt"])
+        .lines()
+        .map(str::to_string)
+        .collect();
+    assert_eq!(entries.len(), 3);
+    std::fs::write(dir.path().join("f.txt"), "theirs\n").unwrap();
+    git(dir.path(), &["add", "f.txt"]);
+    assert_eq!(
+        git(dir.path(), &["diff", "--name-only", "--diff-filter=U"]),
+        ""
+    );
+
+    // A zero id drops the staged entry, then the saved stages go back in
+    let oid_len = entries[0].split_whitespace().nth(1).unwrap().len();
+    let input = format!("0 {}\tf.txt\n{}\n", "0".repeat(oid_len), entries.join("\n"));
+    let mut child = Command::new("git")
+        .args(["update-index", "--index-info"])
+        .current_dir(dir.path())
+        .stdin(std::process::Stdio::piped())
+        .spawn()
+        .unwrap();
+    std::io::Write::write_all(child.stdin.as_mut().unwrap(), input.as_bytes()).unwrap();
+    assert!(child.wait().unwrap().success());
+
+    assert_eq!(
+        git(dir.path(), &["diff", "--name-only", "--diff-filter=U"]).trim(),
+        "f.txt"
+    );
+    assert_eq!(
+        git(dir.path(), &["ls-files", "-s", "--", "f.txt"])
+            .lines()
+            .count(),
+        3
+    );
+}
+
+#[test]
+fn test_binary_conflict_resolved_by_taking_one_side() {
+    let dir = init_repo();
+    std::fs::write(dir.path().join("logo.bin"), b"\x00base\x00").unwrap();
+    git(dir.path(), &["add", "logo.bin"]);
+    git(dir.path(), &["commit", "-m", "base"]);
+    git(dir.path(), &["checkout", "-b", "feature"]);
+    std::fs::write(dir.path().join("logo.bin"), b"\x00theirs\x00").unwrap();
+    git(dir.path(), &["commit", "-am", "theirs"]);
+    git(dir.path(), &["checkout", "main"]);
+    std::fs::write(dir.path().join("logo.bin"), b"\x00ours\x00").unwrap();
+    git(dir.path(), &["commit", "-am", "ours"]);
+    git(dir.path(), &["merge", "feature"]);
+
+    // Git leaves no markers in a binary file, only the two index stages
+    assert_eq!(
+        std::fs::read(dir.path().join("logo.bin")).unwrap(),
+        b"\x00ours\x00"
+    );
+    git(dir.path(), &["checkout", "--theirs", "--", "logo.bin"]);
+    git(dir.path(), &["add", "--", "logo.bin"]);
+
+    assert_eq!(
+        std::fs::read(dir.path().join("logo.bin")).unwrap(),
+        b"\x00theirs\x00"
+    );
+    assert_eq!(
+        git(dir.path(), &["diff", "--name-only", "--diff-filter=U"]),
+        ""
+    );
+}
+
+#[test]
+fn test_backdated_commit_sets_author_and_committer_date() {
+    let dir = init_repo();
+    // GIT_COMMITTER_DATE rejects relative dates, so resolve them first
+    let since = git(dir.path(), &["rev-parse", "--since=2 days ago"]);
+    let stamp = format!("@{}", since.trim().strip_prefix("--max-age=").unwrap());
+    let output = Command::new("git")
+        .args([
+            "commit",
+            "--allow-empty",
+            "--no-verify",
+            "--author=Ada <ada@example.com>",
+            &format!("--date={}", stamp),
+            "-m",
+            "backdated",
+        ])
+        .env("GIT_COMMITTER_NAME", "Test User")
+        .env("GIT_COMMITTER_EMAIL", "test@example.com")
+        .env("GIT_COMMITTER_DATE", &stamp)
+        .current_dir(dir.path())
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let log = git(dir.path(), &["log", "-1", "--format=%an <%ae>|%at|%ct"]);
+    let fields: Vec<&str> = log.trim().split('|').collect();
+    assert_eq!(fields[0], "Ada <ada@example.com>");
+    assert_eq!(fields[1], fields[2]);
+    assert_eq!(format!("@{}", fields[1]), stamp);
+}
+
+#[test]
+fn test_partial_commit_leaves_other_files_staged() {
+    let dir = init_repo();
+    std::fs::write(dir.path().join("a.txt"), "a\n").unwrap();
+    std::fs::write(dir.path().join("b.txt"), "b\n").unwrap();
+    git(dir.path(), &["add", "a.txt", "b.txt"]);
+
+    // Commit from a copy of the index with b.txt unstaged
+    let git_dir = git(dir.path(), &["rev-parse", "--absolute-git-dir"]);
+    let git_dir = std::path::Path::new(git_dir.trim());
+    let partial = git_dir.join("zit-partial-index");
+    std::fs::copy(git_dir.join("index"), &partial).unwrap();
+    let with_partial = |args: &[&str]| {
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(dir.path())
+            .env("GIT_INDEX_FILE", &partial)
+            .env("GIT_AUTHOR_NAME", "Test User")
+            .env("GIT_AUTHOR_EMAIL", "test@example.com")
+            .env("GIT_COMMITTER_NAME", "Test User")
+            .env("GIT_COMMITTER_EMAIL", "test@example.com")
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "git {:?} failed", args);
+    };
+    with_partial(&["reset", "-q", "--", "b.txt"]);
+    with_partial(&["commit", "-m", "only a"]);
+    std::fs::remove_file(&partial).unwrap();
+
+    assert_eq!(
+        git(dir.path(), &["show", "--name-only", "--format=", "HEAD"]).trim(),
+        "a.txt"
+    );
+    // b.txt is still staged against the new HEAD
+    assert_eq!(
+        git(dir.path(), &["diff", "--cached", "--name-only"]).trim(),
+        "b.txt"
+    );
+}
+
+#[test]
+fn test_repo_sign_off_setting_and_trailer() {
+    let dir = init_repo();
+    git(dir.path(), &["config", "--local", "zit.signOff", "yes"]);
+    assert_eq!(
+        git(
+            dir.path(),
+            &["config", "--local", "--type=bool", "--get", "zit.signOff"]
+        )
+        .trim(),
+        "true"
+    );
+
+    git(
+        dir.path(),
+        &["commit", "--allow-empty", "--signoff", "-m", "signed"],
+    );
+    let body = git(dir.path(), &["log", "-1", "--format=%B"]);
+    assert!(body.contains("Signed-off-by: Test User <test@example.com>"));
+}
+
+#[test]
+fn test_branch_description_and_commit_notes() {
+    let dir = init_repo();
+    git(
+        dir.path(),
+        &["config", "branch.main.description", "Line one\nLine two"],
+    );
+    let output = git(
+        dir.path(),
+        &["config", "-z", "--get-regexp", r"^branch\..*\.description$"],
+    );
+    assert_eq!(output, "branch.main.description\nLine one\nLine two\0");
+
+    assert!(git(dir.path(), &["notes", "show", "HEAD"]).is_empty());
+    git(
+        dir.path(),
+        &["notes", "add", "-m", "Reviewed in #12", "HEAD"],
+    );
+    assert_eq!(
+        git(dir.path(), &["notes", "show", "HEAD"]).trim_end(),
+        "Reviewed in #12"
+    );
+}